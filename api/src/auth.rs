@@ -1,9 +1,18 @@
 //! JWT-based authentication middleware
 //!
 //! This module provides JWT token validation, generation, and middleware integration
-//! for securing API endpoints with stateless authentication and authorization.
-
-use axum::{extract::Request, http::header, middleware::Next, response::IntoResponse};
+//! for securing API endpoints. Tokens are stateless JWTs, but every request is
+//! also checked against the [`TokenStore`] so logout, session kills and
+//! refresh-token reuse take effect immediately.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::IntoResponse,
+};
 use axum_extra::extract::CookieJar;
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
@@ -11,6 +20,7 @@ use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
 use crate::error::{ApiError, ApiResult};
+use crate::sessions::{expiry_of, TokenStore};
 
 /// JWT Claims structure
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -31,6 +41,12 @@ pub struct Claims {
     pub account_ids: Vec<String>,
     /// Token type
     pub token_type: TokenType,
+    /// Unique token identifier, used for revocation and refresh rotation
+    #[serde(default)]
+    pub jti: String,
+    /// Session the token belongs to; absent for tokens issued outside a login
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
 }
 
 /// Token types for different use cases
@@ -42,6 +58,14 @@ pub enum TokenType {
     Refresh,
 }
 
+/// Token pair issued for a session
+#[derive(Debug, Clone)]
+pub struct SessionTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub session_id: String,
+}
+
 /// JWT Authentication middleware
 pub struct AuthMiddleware;
 
@@ -52,7 +76,10 @@ impl AuthMiddleware {
     }
 
     /// Authenticate request using JWT token from Authorization header or cookies
+    ///
+    /// Install with `axum::middleware::from_fn_with_state(token_store, AuthMiddleware::authenticate)`.
     pub async fn authenticate(
+        State(store): State<Arc<TokenStore>>,
         cookie_jar: CookieJar,
         request: Request,
         next: Next,
@@ -62,7 +89,7 @@ impl AuthMiddleware {
 
         match token {
             Some(token) => {
-                match Self::validate_token(&token).await {
+                match Self::authorize_token(&store, &token).await {
                     Ok(claims) => {
                         info!("Authentication successful for user: {}", claims.sub);
 
@@ -111,6 +138,13 @@ impl AuthMiddleware {
         None
     }
 
+    /// Validate a JWT and check it against the revocation and session store
+    pub async fn authorize_token(store: &TokenStore, token: &str) -> ApiResult<Claims> {
+        let claims = Self::validate_token(token).await?;
+        store.check(&claims).await?;
+        Ok(claims)
+    }
+
     /// Validate JWT token and return claims
     async fn validate_token(token: &str) -> ApiResult<Claims> {
         let decoding_key = DecodingKey::from_secret(Self::get_jwt_secret().as_ref());
@@ -141,21 +175,8 @@ impl AuthMiddleware {
         roles: Vec<String>,
         account_ids: Vec<String>,
     ) -> ApiResult<String> {
-        let claims = Claims {
-            sub: user_id.to_string(),
-            iat: Utc::now().timestamp() as usize,
-            exp: (Utc::now() + Duration::hours(1)).timestamp() as usize, // 1 hour expiry
-            iss: Some("ninja-gekko-api".to_string()),
-            aud: Some("trading-platform".to_string()),
-            roles,
-            account_ids,
-            token_type: TokenType::Access,
-        };
-
-        let encoding_key = EncodingKey::from_secret(Self::get_jwt_secret().as_ref());
-        let token = encode(&Header::default(), &claims, &encoding_key)?;
-
-        Ok(token)
+        let claims = Self::build_claims(user_id, roles, account_ids, TokenType::Access, None);
+        Self::sign(&claims, &Self::get_jwt_secret())
     }
 
     /// Generate new refresh token
@@ -164,51 +185,123 @@ impl AuthMiddleware {
         roles: Vec<String>,
         account_ids: Vec<String>,
     ) -> ApiResult<String> {
-        let claims = Claims {
-            sub: user_id.to_string(),
-            iat: Utc::now().timestamp() as usize,
-            exp: (Utc::now() + Duration::days(30)).timestamp() as usize, // 30 days expiry
-            iss: Some("ninja-gekko-api".to_string()),
-            aud: Some("trading-platform".to_string()),
+        let claims = Self::build_claims(user_id, roles, account_ids, TokenType::Refresh, None);
+        Self::sign(&claims, &Self::get_refresh_secret())
+    }
+
+    /// Open a new session and issue its first access/refresh token pair
+    pub async fn start_session(
+        store: &TokenStore,
+        user_id: &str,
+        roles: Vec<String>,
+        account_ids: Vec<String>,
+    ) -> ApiResult<SessionTokens> {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let access = Self::build_claims(
+            user_id,
+            roles.clone(),
+            account_ids.clone(),
+            TokenType::Access,
+            Some(&session_id),
+        );
+        let refresh = Self::build_claims(
+            user_id,
             roles,
             account_ids,
-            token_type: TokenType::Refresh,
-        };
-
-        let encoding_key = EncodingKey::from_secret(Self::get_refresh_secret().as_ref());
-        let token = encode(&Header::default(), &claims, &encoding_key)?;
-
-        Ok(token)
+            TokenType::Refresh,
+            Some(&session_id),
+        );
+
+        store
+            .create_session(&session_id, user_id, &refresh.jti, expiry_of(&refresh))
+            .await?;
+
+        Ok(SessionTokens {
+            access_token: Self::sign(&access, &Self::get_jwt_secret())?,
+            refresh_token: Self::sign(&refresh, &Self::get_refresh_secret())?,
+            session_id,
+        })
     }
 
     /// Refresh access token using refresh token
-    pub async fn refresh_access_token(refresh_token: &str) -> ApiResult<(String, String)> {
+    ///
+    /// The presented refresh token is consumed: its session moves on to the
+    /// newly issued one, and replaying the old token revokes the session.
+    pub async fn refresh_access_token(
+        store: &TokenStore,
+        refresh_token: &str,
+    ) -> ApiResult<(String, String)> {
         // Validate refresh token
         let refresh_claims = Self::validate_refresh_token(refresh_token).await?;
+        let session_id = refresh_claims.sid.clone().ok_or_else(|| ApiError::Auth {
+            message: "Refresh token is not bound to a session".to_string(),
+        })?;
 
-        // Generate new access token
-        let access_token = Self::generate_access_token(
+        let access = Self::build_claims(
             &refresh_claims.sub,
             refresh_claims.roles.clone(),
             refresh_claims.account_ids.clone(),
-        )
-        .await?;
-
-        // Generate new refresh token
-        let new_refresh_token = Self::generate_refresh_token(
+            TokenType::Access,
+            Some(&session_id),
+        );
+        let refresh = Self::build_claims(
             &refresh_claims.sub,
             refresh_claims.roles,
             refresh_claims.account_ids,
-        )
-        .await?;
+            TokenType::Refresh,
+            Some(&session_id),
+        );
+
+        store
+            .rotate_refresh(
+                &session_id,
+                &refresh_claims.jti,
+                &refresh.jti,
+                expiry_of(&refresh),
+            )
+            .await?;
+
+        Ok((
+            Self::sign(&access, &Self::get_jwt_secret())?,
+            Self::sign(&refresh, &Self::get_refresh_secret())?,
+        ))
+    }
+
+    fn build_claims(
+        user_id: &str,
+        roles: Vec<String>,
+        account_ids: Vec<String>,
+        token_type: TokenType,
+        session_id: Option<&str>,
+    ) -> Claims {
+        let lifetime = match token_type {
+            TokenType::Access => Duration::hours(1),  // 1 hour expiry
+            TokenType::Refresh => Duration::days(30), // 30 days expiry
+        };
+        Claims {
+            sub: user_id.to_string(),
+            iat: Utc::now().timestamp() as usize,
+            exp: (Utc::now() + lifetime).timestamp() as usize,
+            iss: Some("ninja-gekko-api".to_string()),
+            aud: Some("trading-platform".to_string()),
+            roles,
+            account_ids,
+            token_type,
+            jti: uuid::Uuid::new_v4().to_string(),
+            sid: session_id.map(str::to_string),
+        }
+    }
 
-        Ok((access_token, new_refresh_token))
+    fn sign(claims: &Claims, secret: &str) -> ApiResult<String> {
+        let encoding_key = EncodingKey::from_secret(secret.as_ref());
+        Ok(encode(&Header::default(), claims, &encoding_key)?)
     }
 
     /// Validate refresh token
     async fn validate_refresh_token(refresh_token: &str) -> ApiResult<Claims> {
         let decoding_key = DecodingKey::from_secret(Self::get_refresh_secret().as_ref());
-        let validation = Validation::default();
+        let mut validation = Validation::default();
+        validation.set_audience(&["trading-platform"]);
 
         match decode::<Claims>(refresh_token, &decoding_key, &validation) {
             Ok(token_data) => {
@@ -257,17 +350,25 @@ impl AuthMiddleware {
         })
     }
 
-    /// Revoke a user's tokens (logout)
-    pub async fn revoke_user_tokens(user_id: &str) -> ApiResult<()> {
-        // In a production system, you would store revoked tokens in a cache/database
-        // For this example, we'll just log the revocation
-        info!("Token revocation requested for user: {}", user_id);
-
-        // Here you would:
-        // 1. Add the user's tokens to a revocation list
-        // 2. Clear any cached user sessions
-        // 3. Log the logout event
+    /// Revoke all of a user's tokens and sessions; returns the number of sessions killed
+    pub async fn revoke_user_tokens(store: &TokenStore, user_id: &str) -> ApiResult<usize> {
+        let revoked = store.revoke_user(user_id).await?;
+        info!(
+            "Revoked {} session(s) and all outstanding tokens for user: {}",
+            revoked, user_id
+        );
+        Ok(revoked)
+    }
 
+    /// Revoke the token behind `claims` and, if it has one, its whole session (logout)
+    pub async fn revoke_token(store: &TokenStore, claims: &Claims) -> ApiResult<()> {
+        if !claims.jti.is_empty() {
+            store.revoke_token(&claims.jti, expiry_of(claims)).await?;
+        }
+        if let Some(session_id) = &claims.sid {
+            store.revoke_session(session_id).await?;
+        }
+        info!("Token revoked for user: {}", claims.sub);
         Ok(())
     }
 
@@ -298,10 +399,37 @@ impl AuthMiddleware {
     }
 }
 
+/// Roles allowed to upload WASM strategy modules and run them against accounts
+pub const STRATEGY_DEPLOY_ROLES: &[&str] = &["admin", "trader"];
+
 /// Authorization middleware for role-based access control
 pub struct AuthorizationMiddleware;
 
 impl AuthorizationMiddleware {
+    /// Require one of [`STRATEGY_DEPLOY_ROLES`] to upload or deploy strategy code
+    ///
+    /// Reads the claims [`AuthMiddleware::authenticate`] stored on the request,
+    /// so it must be layered inside it with `axum::middleware::from_fn`.
+    pub async fn require_strategy_deployer(request: Request, next: Next) -> impl IntoResponse {
+        let Some(claims) = request.extensions().get::<Claims>() else {
+            warn!("Strategy deployment attempted without authenticated claims");
+            return ApiError::auth("Authentication required").into_response();
+        };
+
+        if !AuthMiddleware::has_any_permission(claims, STRATEGY_DEPLOY_ROLES) {
+            warn!(
+                "Access denied for user {} - strategy deployment requires one of {:?}",
+                claims.sub, STRATEGY_DEPLOY_ROLES
+            );
+            return ApiError::Authorization {
+                message: format!("Required role: one of {:?}", STRATEGY_DEPLOY_ROLES),
+            }
+            .into_response();
+        }
+
+        next.run(request).await
+    }
+
     /// Require specific role for endpoint access
     pub async fn require_role(
         claims: Claims,
@@ -375,7 +503,7 @@ impl AuthorizationMiddleware {
 /// Utility functions for authentication
 pub mod auth_utils {
     use super::*;
-    use axum::response::Json;
+    use axum::{extract::Extension, response::Json};
     use serde_json::json;
 
     /// Login response structure
@@ -430,22 +558,20 @@ pub mod auth_utils {
 
     /// Handle login endpoint
     pub async fn login_handler(
+        State(store): State<Arc<TokenStore>>,
         Json(request): Json<LoginRequest>,
     ) -> ApiResult<Json<LoginResponse>> {
         // Authenticate user (mock implementation)
         let (user_id, roles, account_ids) = authenticate_user(&request).await?;
 
-        // Generate tokens
-        let access_token =
-            AuthMiddleware::generate_access_token(&user_id, roles.clone(), account_ids.clone())
-                .await?;
-        let refresh_token =
-            AuthMiddleware::generate_refresh_token(&user_id, roles.clone(), account_ids.clone())
+        // Open a session and issue its tokens
+        let tokens =
+            AuthMiddleware::start_session(&store, &user_id, roles.clone(), account_ids.clone())
                 .await?;
 
         let response = LoginResponse {
-            access_token,
-            refresh_token,
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
             token_type: "Bearer".to_string(),
             expires_in: 3600, // 1 hour
             user_id: user_id.clone(),
@@ -459,10 +585,11 @@ pub mod auth_utils {
 
     /// Handle refresh token endpoint
     pub async fn refresh_handler(
+        State(store): State<Arc<TokenStore>>,
         Json(request): Json<RefreshRequest>,
     ) -> ApiResult<Json<LoginResponse>> {
         let (access_token, refresh_token) =
-            AuthMiddleware::refresh_access_token(&request.refresh_token).await?;
+            AuthMiddleware::refresh_access_token(&store, &request.refresh_token).await?;
 
         // Decode refresh token to get user info (simplified)
         let refresh_claims = AuthMiddleware::validate_refresh_token(&request.refresh_token).await?;
@@ -482,9 +609,12 @@ pub mod auth_utils {
     }
 
     /// Handle logout endpoint
-    pub async fn logout_handler(claims: Claims) -> ApiResult<Json<serde_json::Value>> {
-        // Revoke user's tokens
-        AuthMiddleware::revoke_user_tokens(&claims.sub).await?;
+    pub async fn logout_handler(
+        State(store): State<Arc<TokenStore>>,
+        Extension(claims): Extension<Claims>,
+    ) -> ApiResult<Json<serde_json::Value>> {
+        // Revoke this session's tokens
+        AuthMiddleware::revoke_token(&store, &claims).await?;
 
         let response = json!({
             "message": "Logged out successfully",
@@ -540,6 +670,8 @@ mod tests {
             roles: vec!["trader".to_string(), "user".to_string()],
             account_ids: vec!["account-123".to_string()],
             token_type: TokenType::Access,
+            jti: String::new(),
+            sid: None,
         };
 
         assert!(AuthMiddleware::has_role(&claims, "trader"));
@@ -547,4 +679,143 @@ mod tests {
         assert!(AuthMiddleware::has_account_access(&claims, "account-123"));
         assert!(!AuthMiddleware::has_account_access(&claims, "account-456"));
     }
+
+    #[tokio::test]
+    async fn test_logout_revokes_session_tokens() {
+        let store = TokenStore::in_memory();
+        let tokens =
+            AuthMiddleware::start_session(&store, "test-user", vec!["trader".to_string()], vec![])
+                .await
+                .unwrap();
+
+        let claims = AuthMiddleware::authorize_token(&store, &tokens.access_token)
+            .await
+            .unwrap();
+        assert_eq!(claims.sid.as_deref(), Some(tokens.session_id.as_str()));
+        assert_eq!(store.list_sessions("test-user").await.unwrap().len(), 1);
+
+        AuthMiddleware::revoke_token(&store, &claims).await.unwrap();
+
+        assert!(
+            AuthMiddleware::authorize_token(&store, &tokens.access_token)
+                .await
+                .is_err()
+        );
+        assert!(
+            AuthMiddleware::refresh_access_token(&store, &tokens.refresh_token)
+                .await
+                .is_err()
+        );
+        assert!(store.list_sessions("test-user").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_reuse_revokes_family() {
+        let store = TokenStore::in_memory();
+        let tokens =
+            AuthMiddleware::start_session(&store, "test-user", vec!["trader".to_string()], vec![])
+                .await
+                .unwrap();
+
+        let (access_token, rotated) =
+            AuthMiddleware::refresh_access_token(&store, &tokens.refresh_token)
+                .await
+                .unwrap();
+        assert!(AuthMiddleware::authorize_token(&store, &access_token)
+            .await
+            .is_ok());
+
+        // Replaying the consumed refresh token kills the session, including
+        // the legitimately rotated tokens
+        assert!(
+            AuthMiddleware::refresh_access_token(&store, &tokens.refresh_token)
+                .await
+                .is_err()
+        );
+        assert!(AuthMiddleware::refresh_access_token(&store, &rotated)
+            .await
+            .is_err());
+        assert!(AuthMiddleware::authorize_token(&store, &access_token)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_user_tokens_kills_every_session() {
+        let store = TokenStore::in_memory();
+        let first = AuthMiddleware::start_session(&store, "test-user", vec![], vec![])
+            .await
+            .unwrap();
+        let second = AuthMiddleware::start_session(&store, "test-user", vec![], vec![])
+            .await
+            .unwrap();
+        let other = AuthMiddleware::start_session(&store, "other-user", vec![], vec![])
+            .await
+            .unwrap();
+
+        let revoked = AuthMiddleware::revoke_user_tokens(&store, "test-user")
+            .await
+            .unwrap();
+
+        assert_eq!(revoked, 2);
+        for token in [&first.access_token, &second.access_token] {
+            assert!(AuthMiddleware::authorize_token(&store, token)
+                .await
+                .is_err());
+        }
+        assert!(AuthMiddleware::authorize_token(&store, &other.access_token)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_strategy_deployment_requires_deploy_role() {
+        use axum::{body::Body, http::StatusCode, routing::post, Router};
+        use tower::ServiceExt;
+
+        let store = Arc::new(TokenStore::in_memory());
+        let app = Router::new()
+            .route(
+                "/deploy",
+                post(|| async { "deployed" }).route_layer(axum::middleware::from_fn(
+                    AuthorizationMiddleware::require_strategy_deployer,
+                )),
+            )
+            .route_layer(axum::middleware::from_fn_with_state(
+                store.clone(),
+                AuthMiddleware::authenticate,
+            ));
+
+        let trader =
+            AuthMiddleware::start_session(&store, "trader", vec!["trader".to_string()], vec![])
+                .await
+                .unwrap();
+        let viewer =
+            AuthMiddleware::start_session(&store, "viewer", vec!["viewer".to_string()], vec![])
+                .await
+                .unwrap();
+
+        let deploy = |token: Option<&str>| {
+            let mut request = Request::post("/deploy");
+            if let Some(token) = token {
+                request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+            }
+            request.body(Body::empty()).unwrap()
+        };
+
+        let status = |request: Request| {
+            let app = app.clone();
+            async move { app.oneshot(request).await.unwrap().status() }
+        };
+
+        assert_eq!(status(deploy(None)).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status(deploy(Some(&viewer.access_token))).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(deploy(Some(&trader.access_token))).await,
+            StatusCode::OK
+        );
+    }
 }
@@ -1,17 +1,21 @@
 //! Authentication utilities and handlers
 //!
 //! This module provides JWT authentication functionality including
-//! login, token refresh, logout handlers, and utility functions for
-//! token management and validation.
+//! login, token refresh, logout handlers, session management, and utility
+//! functions for token management and validation.
 
-use axum::{extract::State, response::Json};
+use axum::{
+    extract::{Extension, Path, State},
+    response::Json,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::{
-    auth::AuthMiddleware,
+    auth::{AuthMiddleware, Claims},
     error::{ApiError, ApiResult},
     models::ApiResponse,
+    sessions::SessionInfo,
 };
 
 /// Login request structure
@@ -80,6 +84,42 @@ pub struct LogoutResponse {
     pub timestamp: String,
 }
 
+/// Active session as reported to its owner
+#[derive(Debug, Serialize)]
+pub struct SessionResponse {
+    /// Session identifier
+    pub session_id: String,
+    /// Login timestamp
+    pub created_at: String,
+    /// Last token refresh timestamp
+    pub last_refreshed_at: String,
+    /// Refresh token expiration timestamp
+    pub expires_at: String,
+    /// Whether this is the session making the request
+    pub current: bool,
+}
+
+impl SessionResponse {
+    fn from_session(session: SessionInfo, claims: &Claims) -> Self {
+        Self {
+            current: claims.sid.as_deref() == Some(session.session_id.as_str()),
+            session_id: session.session_id,
+            created_at: session.created_at.to_rfc3339(),
+            last_refreshed_at: session.last_refreshed_at.to_rfc3339(),
+            expires_at: session.expires_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Session revocation response structure
+#[derive(Debug, Serialize)]
+pub struct RevokeSessionsResponse {
+    /// Number of sessions revoked
+    pub revoked: usize,
+    /// Timestamp of revocation
+    pub timestamp: String,
+}
+
 /// Login handler for user authentication
 ///
 /// Authenticates users with username/password and returns JWT tokens.
@@ -122,29 +162,27 @@ pub async fn login_handler(
         });
     }
 
-    // Create user context for authenticated session
-    let user_id = format!("user-{}", uuid::Uuid::new_v4());
+    // Create user context for authenticated session; the id is stable so a
+    // user's sessions can be listed and revoked together
+    let user_id = format!("user-{}", login_request.username);
     let roles = vec!["admin".to_string(), "trader".to_string()];
     let account_ids = vec!["default".to_string()];
 
-    // Generate JWT tokens
-    let access_token =
-        AuthMiddleware::generate_access_token(&user_id, roles.clone(), account_ids.clone())
-            .await
-            .map_err(|e| ApiError::Auth {
-                message: format!("Failed to generate access token: {}", e),
-            })?;
-
-    let refresh_token =
-        AuthMiddleware::generate_refresh_token(&user_id, roles.clone(), account_ids.clone())
-            .await
-            .map_err(|e| ApiError::Auth {
-                message: format!("Failed to generate refresh token: {}", e),
-            })?;
+    // Open a session and generate its JWT tokens
+    let tokens = AuthMiddleware::start_session(
+        &state.token_store,
+        &user_id,
+        roles.clone(),
+        account_ids.clone(),
+    )
+    .await
+    .map_err(|e| ApiError::Auth {
+        message: format!("Failed to start session: {}", e),
+    })?;
 
     let response = LoginResponse {
-        access_token,
-        refresh_token,
+        access_token: tokens.access_token,
+        refresh_token: tokens.refresh_token,
         expires_at: (chrono::Utc::now() + chrono::Duration::hours(24)).timestamp() as usize,
         user: UserInfo {
             id: user_id,
@@ -161,9 +199,11 @@ pub async fn login_handler(
 /// Refresh token handler
 ///
 /// Exchanges a valid refresh token for a new access token.
-/// This endpoint should be called when the access token expires.
+/// This endpoint should be called when the access token expires. The
+/// presented refresh token is rotated out; replaying it later revokes the
+/// whole session.
 pub async fn refresh_handler(
-    State(_state): State<Arc<crate::AppState>>,
+    State(state): State<Arc<crate::AppState>>,
     Json(refresh_request): Json<RefreshRequest>,
 ) -> ApiResult<Json<ApiResponse<RefreshResponse>>> {
     // Validate refresh token
@@ -176,7 +216,7 @@ pub async fn refresh_handler(
 
     // Use AuthMiddleware to refresh tokens
     let (access_token, new_refresh_token) =
-        AuthMiddleware::refresh_access_token(&refresh_request.refresh_token)
+        AuthMiddleware::refresh_access_token(&state.token_store, &refresh_request.refresh_token)
             .await
             .map_err(|e| ApiError::Auth {
                 message: format!("Failed to refresh token: {}", e),
@@ -194,16 +234,13 @@ pub async fn refresh_handler(
 
 /// Logout handler
 ///
-/// Invalidates the user's refresh token and logs them out.
+/// Revokes the current session, invalidating its access and refresh tokens.
 /// Requires authenticated request with valid JWT token.
 pub async fn logout_handler(
-    State(_state): State<Arc<crate::AppState>>,
-    // In production: extract user_id from JWT claims via middleware
-    // Extension(claims): Extension<Claims>
+    State(state): State<Arc<crate::AppState>>,
+    Extension(claims): Extension<Claims>,
 ) -> ApiResult<Json<ApiResponse<LogoutResponse>>> {
-    // TODO: Extract user_id from JWT token in request context
-    // For now, logout is a no-op that clears client-side tokens
-    // In production: invalidate refresh token in database/cache
+    AuthMiddleware::revoke_token(&state.token_store, &claims).await?;
 
     let response = LogoutResponse {
         message: "Successfully logged out".to_string(),
@@ -212,3 +249,60 @@ pub async fn logout_handler(
 
     Ok(Json(ApiResponse::success(response)))
 }
+
+/// List the caller's active sessions
+pub async fn list_sessions_handler(
+    State(state): State<Arc<crate::AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<Json<ApiResponse<Vec<SessionResponse>>>> {
+    let sessions = state
+        .token_store
+        .list_sessions(&claims.sub)
+        .await?
+        .into_iter()
+        .map(|session| SessionResponse::from_session(session, &claims))
+        .collect();
+
+    Ok(Json(ApiResponse::success(sessions)))
+}
+
+/// Revoke one session
+///
+/// Users may kill their own sessions; admins may kill any session.
+pub async fn revoke_session_handler(
+    State(state): State<Arc<crate::AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(session_id): Path<String>,
+) -> ApiResult<Json<ApiResponse<RevokeSessionsResponse>>> {
+    let session = state
+        .token_store
+        .get_session(&session_id)
+        .await?
+        .filter(|session| {
+            session.user_id == claims.sub || AuthMiddleware::has_role(&claims, "admin")
+        })
+        .ok_or_else(|| ApiError::not_found(format!("session {}", session_id)))?;
+
+    let revoked = state
+        .token_store
+        .revoke_session(&session.session_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success(RevokeSessionsResponse {
+        revoked: usize::from(revoked),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    })))
+}
+
+/// Revoke every session and outstanding token of the caller
+pub async fn revoke_all_sessions_handler(
+    State(state): State<Arc<crate::AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<Json<ApiResponse<RevokeSessionsResponse>>> {
+    let revoked = AuthMiddleware::revoke_user_tokens(&state.token_store, &claims.sub).await?;
+
+    Ok(Json(ApiResponse::success(RevokeSessionsResponse {
+        revoked,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    })))
+}
@@ -12,13 +12,15 @@ use tracing::{info, warn};
 
 use crate::{
     error::{ApiError, ApiResult},
+    indicators::IndicatorRequest,
     models::{
-        ApiResponse, MarketDataPoint, MarketDataRequest, MarketDataResponse,
+        ApiResponse, IndicatorsQuery, MarketDataPoint, MarketDataRequest, MarketDataResponse,
         MarketDataWithIndicators, MarketOverview, MarketStatistics, PaginatedResponse,
         PaginationParams, SearchSymbolsRequest, StreamSubscriptionResponse, SymbolInfo,
     },
     AppState,
 };
+use strategy_engine::indicators::registry::{IndicatorSpec, INDICATORS};
 
 /// Get current market data for a specific symbol
 pub async fn get_market_data(
@@ -109,22 +111,37 @@ pub async fn get_historical_data(
 }
 
 /// Get price history with technical indicators
+///
+/// `?indicators=rsi:14,bollinger:20:2` selects registered indicators and returns
+/// their full series; without it the default indicator set is reported.
 pub async fn get_price_with_indicators(
     State(state): State<Arc<AppState>>,
     Path(symbol): Path<String>,
     Query(params): Query<PaginationParams>,
+    Query(query): Query<IndicatorsQuery>,
 ) -> ApiResult<Json<ApiResponse<MarketDataWithIndicators>>> {
     info!(
         "Retrieving price data with indicators for symbol: {}",
         symbol
     );
 
+    let requests = match query.indicators.as_deref() {
+        Some(list) => {
+            IndicatorRequest::parse_list(list).map_err(|message| ApiError::Validation {
+                message,
+                field: Some("indicators".to_string()),
+            })?
+        }
+        None => Vec::new(),
+    };
+
     match state
         .market_data_service
-        .get_data_with_indicators(&symbol, params)
+        .get_data_with_indicators(&symbol, params, &requests)
         .await
     {
         Ok(data) => Ok(Json(ApiResponse::success(data))),
+        Err(e @ ApiError::Validation { .. }) => Err(e),
         Err(e) => {
             warn!(
                 "Failed to retrieve price with indicators for {}: {}",
@@ -137,6 +154,11 @@ pub async fn get_price_with_indicators(
     }
 }
 
+/// List the indicators that can be requested, with their parameters and defaults
+pub async fn list_indicators() -> Json<ApiResponse<Vec<IndicatorSpec>>> {
+    Json(ApiResponse::success(INDICATORS.to_vec()))
+}
+
 /// Search for symbols based on query
 pub async fn search_symbols(
     State(state): State<Arc<AppState>>,
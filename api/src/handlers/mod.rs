@@ -70,7 +70,9 @@ pub async fn api_info() -> Json<ApiResponse<serde_json::Value>> {
             "auth": {
                 "login": "/api/v1/auth/login",
                 "refresh": "/api/v1/auth/refresh",
-                "logout": "/api/v1/auth/logout"
+                "logout": "/api/v1/auth/logout",
+                "sessions": "/api/v1/auth/sessions",
+                "session": "/api/v1/auth/sessions/{id}"
             },
             "trades": {
                 "list": "/api/v1/trades",
@@ -4,21 +4,25 @@
 //! strategy management, execution, backtesting, and performance analysis.
 
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Extension, Multipart, Path, Query, State},
     response::Json,
 };
 use serde_json::json;
 use std::sync::Arc;
 use tracing::{info, warn};
+use uuid::Uuid;
 
 use crate::{
+    auth::{AuthMiddleware, Claims},
     error::{ApiError, ApiResult},
     models::{
         ApiResponse, BacktestRequest, BacktestResponse, CreateStrategyRequest,
-        DetailedStrategyPerformance, PaginatedResponse, PaginationParams, StrategyExecutionRequest,
-        StrategyExecutionResponse, StrategyOptimizationRequest, StrategyOptimizationResponse,
-        StrategyResponse, UpdateStrategyRequest,
+        DeployStrategyRequest, DetailedStrategyPerformance, PaginatedResponse, PaginationParams,
+        StrategyDeploymentResponse, StrategyExecutionRequest, StrategyExecutionResponse,
+        StrategyModuleResponse, StrategyOptimizationRequest, StrategyOptimizationResponse,
+        StrategyResponse, SwapStrategyVersionRequest, UpdateStrategyRequest,
     },
+    wasm_strategies::WasmStrategyDeployments,
     AppState,
 };
 
@@ -49,6 +53,22 @@ pub async fn list_strategies(
     }
 }
 
+/// List built-in strategy types with their parameter schemas
+pub async fn list_strategy_types() -> ApiResult<Json<ApiResponse<serde_json::Value>>> {
+    let schemas: serde_json::Map<String, serde_json::Value> =
+        strategy_engine::strategies::STRATEGY_KINDS
+            .iter()
+            .filter_map(|kind| {
+                let schema = strategy_engine::strategies::parameter_schema(kind)?;
+                Some((kind.to_string(), serde_json::to_value(schema).ok()?))
+            })
+            .collect();
+
+    Ok(Json(ApiResponse::success(serde_json::Value::Object(
+        schemas,
+    ))))
+}
+
 /// Get a specific trading strategy by ID
 pub async fn get_strategy(
     State(state): State<Arc<AppState>>,
@@ -267,6 +287,279 @@ pub async fn get_strategy_performance(
     }
 }
 
+/// Upload a WASM module as the next version of a strategy
+///
+/// Expects a multipart body with the module bytes in a `module` field and an optional
+/// `name` field; the module's file name is used when no name is given. Only the user
+/// who uploaded the strategy's first version, or an admin, can add versions.
+pub async fn upload_strategy_module(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(strategy_id): Path<String>,
+    mut multipart: Multipart,
+) -> ApiResult<Json<ApiResponse<StrategyModuleResponse>>> {
+    let id = parse_strategy_id(&strategy_id)?;
+    require_strategy_owner(
+        &state.strategy_deployments,
+        &claims,
+        id,
+        &strategy_id,
+        "uploading a module for",
+    )
+    .await?;
+
+    let mut name = None;
+    let mut module = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::bad_request(format!("Invalid multipart body: {}", e)))?
+    {
+        match field.name() {
+            Some("name") => {
+                name =
+                    Some(field.text().await.map_err(|e| {
+                        ApiError::bad_request(format!("Invalid module name: {}", e))
+                    })?);
+            }
+            Some("module") => {
+                if name.is_none() {
+                    name = field
+                        .file_name()
+                        .map(|file| file.trim_end_matches(".wasm").to_string());
+                }
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(|e| ApiError::bad_request(format!("Failed to read module: {}", e)))?;
+                module = Some(bytes.to_vec());
+            }
+            _ => {}
+        }
+    }
+    let module = module.ok_or_else(|| ApiError::Validation {
+        message: "Missing module field".to_string(),
+        field: Some("module".to_string()),
+    })?;
+    let name = name.unwrap_or_else(|| strategy_id.clone());
+    info!(
+        "Uploading module {} for strategy {} ({} bytes)",
+        name,
+        strategy_id,
+        module.len()
+    );
+
+    match state
+        .strategy_deployments
+        .upload(id, &name, module, &claims.sub)
+        .await
+    {
+        Ok((record, _)) => Ok(Json(ApiResponse::success(record.to_response()))),
+        Err(e) => {
+            warn!(
+                "Failed to upload module for strategy {}: {}",
+                strategy_id, e
+            );
+            Err(e)
+        }
+    }
+}
+
+/// List the uploaded module versions of a strategy owned by the caller
+pub async fn list_strategy_modules(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(strategy_id): Path<String>,
+) -> ApiResult<Json<ApiResponse<Vec<StrategyModuleResponse>>>> {
+    let id = parse_strategy_id(&strategy_id)?;
+    require_strategy_owner(
+        &state.strategy_deployments,
+        &claims,
+        id,
+        &strategy_id,
+        "listing modules of",
+    )
+    .await?;
+    let versions = state.strategy_deployments.versions(id).await?;
+
+    Ok(Json(ApiResponse::success(
+        versions.iter().map(|record| record.to_response()).collect(),
+    )))
+}
+
+/// Start an uploaded strategy on the requested accounts and symbols
+pub async fn deploy_strategy(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(strategy_id): Path<String>,
+    Json(request): Json<DeployStrategyRequest>,
+) -> ApiResult<Json<ApiResponse<StrategyDeploymentResponse>>> {
+    let id = parse_strategy_id(&strategy_id)?;
+    require_account_access(&claims, &request.account_ids, "deploying", &strategy_id)?;
+    info!(
+        "Deploying strategy {} for accounts {:?}",
+        strategy_id, request.account_ids
+    );
+
+    match state.strategy_deployments.deploy(id, request).await {
+        Ok(deployment) => Ok(Json(ApiResponse::success(deployment))),
+        Err(e) => {
+            warn!("Failed to deploy strategy {}: {}", strategy_id, e);
+            Err(e)
+        }
+    }
+}
+
+/// Get the running deployment of a strategy
+pub async fn get_strategy_deployment(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(strategy_id): Path<String>,
+) -> ApiResult<Json<ApiResponse<StrategyDeploymentResponse>>> {
+    let id = parse_strategy_id(&strategy_id)?;
+    require_deployment_access(
+        &state.strategy_deployments,
+        &claims,
+        id,
+        &strategy_id,
+        "reading",
+    )
+    .await?;
+
+    match state.strategy_deployments.deployment(id).await {
+        Some(deployment) => Ok(Json(ApiResponse::success(deployment))),
+        None => Err(ApiError::NotFound {
+            resource: format!("Deployment of strategy {}", strategy_id),
+        }),
+    }
+}
+
+/// Switch a running deployment to another module version
+pub async fn swap_strategy_version(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(strategy_id): Path<String>,
+    Json(request): Json<SwapStrategyVersionRequest>,
+) -> ApiResult<Json<ApiResponse<StrategyDeploymentResponse>>> {
+    let id = parse_strategy_id(&strategy_id)?;
+    require_deployment_access(
+        &state.strategy_deployments,
+        &claims,
+        id,
+        &strategy_id,
+        "switching",
+    )
+    .await?;
+    info!(
+        "Switching strategy {} to module version {}",
+        strategy_id, request.version
+    );
+
+    match state
+        .strategy_deployments
+        .swap_version(id, request.version)
+        .await
+    {
+        Ok(deployment) => Ok(Json(ApiResponse::success(deployment))),
+        Err(e) => {
+            warn!("Failed to switch strategy {} version: {}", strategy_id, e);
+            Err(e)
+        }
+    }
+}
+
+/// Stop a running deployment
+pub async fn stop_strategy_deployment(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(strategy_id): Path<String>,
+) -> ApiResult<Json<ApiResponse<StrategyDeploymentResponse>>> {
+    let id = parse_strategy_id(&strategy_id)?;
+    require_deployment_access(
+        &state.strategy_deployments,
+        &claims,
+        id,
+        &strategy_id,
+        "stopping",
+    )
+    .await?;
+    info!("Stopping deployment of strategy {}", strategy_id);
+
+    let deployment = state.strategy_deployments.stop(id).await?;
+    Ok(Json(ApiResponse::success(deployment)))
+}
+
+/// Fail unless the caller can access every account a deployment touches
+fn require_account_access(
+    claims: &Claims,
+    account_ids: &[String],
+    action: &str,
+    strategy_id: &str,
+) -> ApiResult<()> {
+    match account_ids
+        .iter()
+        .find(|account_id| !AuthMiddleware::has_account_access(claims, account_id))
+    {
+        Some(account_id) => {
+            warn!(
+                "User {} denied {} strategy {} on account {}",
+                claims.sub, action, strategy_id, account_id
+            );
+            Err(ApiError::Authorization {
+                message: format!("Access denied to account: {}", account_id),
+            })
+        }
+        None => Ok(()),
+    }
+}
+
+/// Fail unless the strategy is deployed and the caller can access all of its accounts
+async fn require_deployment_access(
+    deployments: &WasmStrategyDeployments,
+    claims: &Claims,
+    id: Uuid,
+    strategy_id: &str,
+    action: &str,
+) -> ApiResult<()> {
+    let account_ids =
+        deployments
+            .deployed_accounts(id)
+            .await
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("Deployment of strategy {}", strategy_id),
+            })?;
+    require_account_access(claims, &account_ids, action, strategy_id)
+}
+
+/// Fail unless the caller uploaded the strategy's first version or is an admin
+async fn require_strategy_owner(
+    deployments: &WasmStrategyDeployments,
+    claims: &Claims,
+    id: Uuid,
+    strategy_id: &str,
+    action: &str,
+) -> ApiResult<()> {
+    match deployments.owner(id).await? {
+        Some(owner) if owner != claims.sub && !AuthMiddleware::has_role(claims, "admin") => {
+            warn!(
+                "User {} denied {} strategy {} owned by {}",
+                claims.sub, action, strategy_id, owner
+            );
+            Err(ApiError::Authorization {
+                message: format!("Access denied to strategy: {}", strategy_id),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn parse_strategy_id(strategy_id: &str) -> ApiResult<Uuid> {
+    Uuid::parse_str(strategy_id).map_err(|_| ApiError::Validation {
+        message: format!("Invalid strategy ID: {}", strategy_id),
+        field: Some("id".to_string()),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -287,6 +580,129 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    fn claims(sub: &str, roles: &[&str], account_ids: &[&str]) -> Claims {
+        Claims {
+            sub: sub.to_string(),
+            iat: 0,
+            exp: 0,
+            iss: None,
+            aud: None,
+            roles: roles.iter().map(|role| role.to_string()).collect(),
+            account_ids: account_ids.iter().map(|id| id.to_string()).collect(),
+            token_type: crate::auth::TokenType::Access,
+            jti: String::new(),
+            sid: None,
+        }
+    }
+
+    #[test]
+    fn test_deployment_actions_require_access_to_every_account() {
+        let claims = claims("trader", &["trader"], &["acct-1"]);
+        let id = Uuid::new_v4().to_string();
+
+        assert!(require_account_access(&claims, &["acct-1".to_string()], "stopping", &id).is_ok());
+        assert!(matches!(
+            require_account_access(
+                &claims,
+                &["acct-1".to_string(), "acct-2".to_string()],
+                "switching",
+                &id
+            ),
+            Err(ApiError::Authorization { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cross_account_reads_are_forbidden() {
+        use crate::wasm_strategies::{
+            tests::{deploy_request, module, RecordingSink},
+            InMemoryModuleStore,
+        };
+
+        let bus = event_bus::EventBusBuilder::default().build();
+        let deployments = WasmStrategyDeployments::new(
+            Arc::new(InMemoryModuleStore::new()),
+            Arc::new(strategy_engine::InMemoryStateStore::new()),
+            bus.signal_sender(),
+            Arc::new(RecordingSink::default()),
+        );
+        let id = Uuid::new_v4();
+        let strategy_id = id.to_string();
+        deployments
+            .upload(id, "ping", module("v1"), "alice")
+            .await
+            .unwrap();
+        // Deployed on acct-1 and acct-2
+        deployments.deploy(id, deploy_request(None)).await.unwrap();
+
+        let alice = claims("alice", &["trader"], &["acct-1", "acct-2"]);
+        let mallory = claims("mallory", &["trader"], &["acct-1"]);
+        let admin = claims("root", &["admin"], &[]);
+
+        let read = |claims: Claims| {
+            let deployments = &deployments;
+            let strategy_id = strategy_id.clone();
+            async move {
+                require_deployment_access(deployments, &claims, id, &strategy_id, "reading").await
+            }
+        };
+        assert!(read(alice.clone()).await.is_ok());
+        assert!(matches!(
+            read(mallory.clone()).await,
+            Err(ApiError::Authorization { .. })
+        ));
+        assert!(matches!(
+            require_deployment_access(&deployments, &alice, Uuid::new_v4(), "x", "reading").await,
+            Err(ApiError::NotFound { .. })
+        ));
+
+        let list = |claims: Claims| {
+            let deployments = &deployments;
+            let strategy_id = strategy_id.clone();
+            async move {
+                require_strategy_owner(deployments, &claims, id, &strategy_id, "listing").await
+            }
+        };
+        assert!(list(alice).await.is_ok());
+        assert!(list(admin).await.is_ok());
+        assert!(matches!(
+            list(mallory).await,
+            Err(ApiError::Authorization { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_strategy_types_includes_momentum_schema() {
+        let Json(response) = list_strategy_types().await.unwrap();
+        let schemas = response.data.unwrap();
+        let params = schemas["momentum"]["params"].as_array().unwrap();
+
+        assert!(params.iter().any(|p| p["name"] == "rsi_period"));
+    }
+
+    #[test]
+    fn test_create_request_parameters_validated_against_schema() {
+        let mut request = CreateStrategyRequest {
+            name: "Momentum".to_string(),
+            description: None,
+            strategy_type: Some("momentum".to_string()),
+            parameters: std::collections::HashMap::new(),
+            is_active: None,
+            account_ids: None,
+        };
+        request
+            .parameters
+            .insert("rsi_period".to_string(), json!(500));
+        assert!(request.validate().is_err());
+
+        request
+            .parameters
+            .insert("rsi_period".to_string(), json!(10));
+        let params = request.validated_parameters().unwrap();
+        assert_eq!(params["rsi_period"], json!(10));
+        assert_eq!(params["ema_slow_period"], json!(21));
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_get_strategy_success() {
@@ -311,6 +727,7 @@ mod tests {
         let request = CreateStrategyRequest {
             name: "Test Strategy".to_string(),
             description: Some("Test strategy description".to_string()),
+            strategy_type: None,
             parameters: std::collections::HashMap::new(),
             is_active: Some(true),
             account_ids: Some(vec!["test-account".to_string()]),
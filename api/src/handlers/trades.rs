@@ -12,7 +12,7 @@ use chrono::{DateTime, Utc};
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 use serde_json::json;
-use sqlx::FromRow;
+use sqlx::{FromRow, PgPool};
 use std::sync::Arc;
 use tracing::{error, info};
 
@@ -23,15 +23,14 @@ use crate::{
         TradeResponse, UpdateTradeRequest,
     },
 };
-use ninja_gekko_core::types::{Order, OrderSide, OrderStatus, OrderType};
+use ninja_gekko_core::types::{Execution, OrderSide};
 
 /// Database row structure for trade executions
 #[derive(Debug, FromRow)]
-struct TradeExecutionRow {
+pub(crate) struct TradeExecutionRow {
     id: uuid::Uuid,
     #[allow(dead_code)]
     bot_id: String,
-    #[allow(dead_code)]
     exchange: String,
     symbol: String,
     side: String,
@@ -66,6 +65,50 @@ impl From<TradeExecutionRow> for TradeResponse {
     }
 }
 
+impl TradeExecutionRow {
+    /// Core execution for a filled row; `None` when the row has no fill price.
+    ///
+    /// Fees are not stored yet and the last update stands in for the fill time.
+    pub(crate) fn to_execution(&self) -> Option<Execution> {
+        let side = match self.side.as_str() {
+            "Buy" => OrderSide::Buy,
+            "Sell" => OrderSide::Sell,
+            _ => return None,
+        };
+        Some(Execution {
+            id: self.id,
+            order_id: self.id,
+            symbol: self.symbol.clone(),
+            side,
+            quantity: self.quantity,
+            price: self.price?,
+            timestamp: self.updated_at,
+            exchange: self.exchange.clone(),
+            fees: Decimal::ZERO,
+            metadata: Default::default(),
+        })
+    }
+}
+
+/// Filled executions, oldest first, optionally limited to one bot or strategy
+pub(crate) async fn filled_executions(
+    pool: &PgPool,
+    bot_id: Option<&str>,
+) -> ApiResult<Vec<TradeExecutionRow>> {
+    sqlx::query_as::<_, TradeExecutionRow>(
+        "SELECT * FROM trade_executions
+         WHERE status = 'Filled' AND ($1::TEXT IS NULL OR bot_id = $1)
+         ORDER BY updated_at ASC",
+    )
+    .bind(bot_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        error!("Database query failed: {}", e);
+        ApiError::database(format!("Failed to fetch executions: {}", e))
+    })
+}
+
 /// List trades with pagination and filtering
 pub async fn list_trades(
     State(state): State<Arc<crate::AppState>>,
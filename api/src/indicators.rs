@@ -2,10 +2,17 @@
 //!
 //! This module provides an IndicatorService that wraps the strategy-engine's
 //! indicator implementations and exposes them for use in the API layer.
+//! Any indicator in the strategy-engine registry can be requested by name and
+//! parameters, e.g. `rsi:14` or `bollinger:20:2`, and returned as a full series.
 
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use strategy_engine::indicators::prelude::*;
+use strategy_engine::indicators::registry::create_indicator;
+use strategy_engine::indicators::{dec_to_f64, f64_to_dec};
 
 /// Service for managing and computing technical indicators
 pub struct IndicatorService {
@@ -177,15 +184,7 @@ impl IndicatorService {
         let mut last_value = None;
 
         for candle_data in candles {
-            let candle = Candle {
-                open: Decimal::from_f64_retain(candle_data.open)?,
-                high: Decimal::from_f64_retain(candle_data.high)?,
-                low: Decimal::from_f64_retain(candle_data.low)?,
-                close: Decimal::from_f64_retain(candle_data.close)?,
-                volume: Decimal::from_f64_retain(candle_data.volume)?,
-                timestamp: candle_data.timestamp,
-            };
-            let value = atr.update_ohlcv(&candle);
+            let value = atr.update_ohlcv(&Candle::from(candle_data));
             if atr.is_ready() {
                 last_value = Some(value.value.to_string().parse().ok()?);
             }
@@ -248,6 +247,147 @@ impl IndicatorService {
 
         indicators
     }
+
+    /// Calculate the full series of a requested indicator over OHLCV candle data
+    ///
+    /// Every line of the indicator gets one entry per candle, `None` until the
+    /// indicator has warmed up.
+    pub fn calculate_series(
+        &self,
+        request: &IndicatorRequest,
+        candles: &[CandleData],
+    ) -> Result<IndicatorSeries, String> {
+        let mut indicator = create_indicator(&request.name, &request.params)?;
+        let mut lines: Vec<IndicatorLine> = Vec::new();
+
+        for (step, candle_data) in candles.iter().enumerate() {
+            indicator.update_ohlcv(&Candle::from(candle_data));
+            let output = indicator
+                .is_ready()
+                .then(|| indicator.output())
+                .flatten()
+                .unwrap_or_default();
+            for (name, _) in output.iter() {
+                if !lines.iter().any(|line| line.name == name) {
+                    // Lines can appear late, e.g. the Ichimoku cloud once displaced.
+                    lines.push(IndicatorLine {
+                        name: name.to_string(),
+                        values: vec![None; step],
+                    });
+                }
+            }
+            for line in &mut lines {
+                line.values.push(output.get(&line.name).map(dec_to_f64));
+            }
+        }
+
+        Ok(IndicatorSeries {
+            key: request.to_string(),
+            name: indicator.name().to_string(),
+            warmup_period: indicator.warmup_period(),
+            timestamps: candles.iter().map(|c| c.timestamp).collect(),
+            lines,
+        })
+    }
+
+    /// Latest value of every line of each series
+    ///
+    /// Single-line indicators are keyed by their request (`rsi:14`), other lines by
+    /// request and line name (`bollinger:20:2.upper`).
+    pub fn latest_values(series: &[IndicatorSeries]) -> HashMap<String, f64> {
+        let mut values = HashMap::new();
+        for entry in series {
+            for line in &entry.lines {
+                let Some(value) = line.values.last().copied().flatten() else {
+                    continue;
+                };
+                let key = if line.name == IndicatorOutput::VALUE {
+                    entry.key.clone()
+                } else {
+                    format!("{}.{}", entry.key, line.name)
+                };
+                values.insert(key, value);
+            }
+        }
+        values
+    }
+}
+
+/// An indicator requested by registry name and constructor parameters
+///
+/// Written as the name followed by colon-separated parameters, e.g. `rsi`,
+/// `rsi:14` or `macd:12:26:9`; omitted parameters take the registry defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndicatorRequest {
+    pub name: String,
+    #[serde(default)]
+    pub params: Vec<f64>,
+}
+
+impl IndicatorRequest {
+    /// Parse a comma-separated list of requests, e.g. `rsi:14,bollinger:20:2`
+    pub fn parse_list(list: &str) -> Result<Vec<Self>, String> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::parse)
+            .collect()
+    }
+}
+
+impl FromStr for IndicatorRequest {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split(':');
+        let name = parts.next().unwrap_or_default().trim().to_lowercase();
+        if name.is_empty() {
+            return Err(format!("indicator request '{}' has no name", s));
+        }
+        let params = parts
+            .map(|part| {
+                part.trim()
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid parameter '{}' for {}", part, name))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // Reject unknown names and bad parameters up front.
+        create_indicator(&name, &params)?;
+        Ok(Self { name, params })
+    }
+}
+
+impl fmt::Display for IndicatorRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        for param in &self.params {
+            write!(f, ":{}", param)?;
+        }
+        Ok(())
+    }
+}
+
+/// Full series of one requested indicator, aligned with the input candles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndicatorSeries {
+    /// The request as written, e.g. `bollinger:20:2`
+    pub key: String,
+    /// Display name of the indicator
+    pub name: String,
+    /// Candles needed before the first value
+    pub warmup_period: usize,
+    /// Candle timestamps (Unix seconds)
+    pub timestamps: Vec<i64>,
+    /// One entry per named output line, in the indicator's order
+    pub lines: Vec<IndicatorLine>,
+}
+
+/// One named output line of an indicator series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndicatorLine {
+    pub name: String,
+    /// Value per candle, `None` during warmup
+    pub values: Vec<Option<f64>>,
 }
 
 /// Simple candle data structure for indicator calculations
@@ -261,6 +401,19 @@ pub struct CandleData {
     pub timestamp: i64,
 }
 
+impl From<&CandleData> for Candle {
+    fn from(candle: &CandleData) -> Self {
+        Candle {
+            open: f64_to_dec(candle.open),
+            high: f64_to_dec(candle.high),
+            low: f64_to_dec(candle.low),
+            close: f64_to_dec(candle.close),
+            volume: f64_to_dec(candle.volume),
+            timestamp: candle.timestamp,
+        }
+    }
+}
+
 impl CandleData {
     /// Create a new CandleData
     pub fn new(open: f64, high: f64, low: f64, close: f64, volume: f64, timestamp: i64) -> Self {
@@ -363,6 +516,63 @@ mod tests {
         assert!(indicators.contains_key("bb_lower"));
     }
 
+    fn sample_candles() -> Vec<CandleData> {
+        sample_prices()
+            .into_iter()
+            .enumerate()
+            .map(|(i, close)| {
+                CandleData::new(close, close + 0.5, close - 0.5, close, 100.0, i as i64)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_indicator_request_parsing() {
+        let request: IndicatorRequest = "Bollinger:20:2.5".parse().unwrap();
+        assert_eq!(request.name, "bollinger");
+        assert_eq!(request.params, vec![20.0, 2.5]);
+        assert_eq!(request.to_string(), "bollinger:20:2.5");
+
+        let list = IndicatorRequest::parse_list("rsi, macd:12:26:9,").unwrap();
+        assert_eq!(list.len(), 2);
+        assert!(list[0].params.is_empty());
+
+        assert!("nonsense".parse::<IndicatorRequest>().is_err());
+        assert!("rsi:abc".parse::<IndicatorRequest>().is_err());
+        assert!("rsi:14:3".parse::<IndicatorRequest>().is_err());
+    }
+
+    #[test]
+    fn test_series_matches_single_value_calculations() {
+        let service = IndicatorService::new();
+        let candles = sample_candles();
+        let prices = sample_prices();
+
+        let rsi = service
+            .calculate_series(&"rsi:14".parse().unwrap(), &candles)
+            .unwrap();
+        assert_eq!(rsi.timestamps.len(), candles.len());
+        assert_eq!(rsi.lines.len(), 1);
+        let values = &rsi.lines[0].values;
+        assert_eq!(values.len(), candles.len());
+        let first = values.iter().position(Option::is_some).unwrap();
+        assert_eq!(first + 1, rsi.warmup_period);
+        let last = values.last().unwrap().unwrap();
+        assert!((last - service.calculate_rsi(&prices).unwrap()).abs() < 1e-9);
+
+        let bands = service
+            .calculate_series(&"bollinger:20:2".parse().unwrap(), &candles)
+            .unwrap();
+        let names: Vec<_> = bands.lines.iter().map(|line| line.name.as_str()).collect();
+        assert_eq!(names, ["upper", "middle", "lower"]);
+        let (upper, middle, lower) = service.calculate_bollinger_bands(&prices).unwrap();
+        let latest = IndicatorService::latest_values(&[rsi, bands]);
+        assert!((latest["bollinger:20:2.upper"] - upper).abs() < 1e-9);
+        assert!((latest["bollinger:20:2.middle"] - middle).abs() < 1e-9);
+        assert!((latest["bollinger:20:2.lower"] - lower).abs() < 1e-9);
+        assert!(latest.contains_key("rsi:14"));
+    }
+
     #[test]
     fn test_insufficient_data() {
         let service = IndicatorService::new();
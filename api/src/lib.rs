@@ -21,6 +21,7 @@
 //! - `error`: Error types and handling
 
 use axum::{
+    extract::DefaultBodyLimit,
     routing::{delete, get, post, put},
     Router,
 };
@@ -42,12 +43,16 @@ pub mod llm;
 pub mod managers;
 pub mod middleware;
 pub mod models;
+pub mod sessions;
 pub mod validation;
+pub mod wasm_strategies;
 pub mod websocket;
 
 use crate::handlers::orchestrator::OrchestratorState;
 use crate::managers::{MarketDataService, PortfolioManager, StrategyManager};
+use crate::wasm_strategies::{PostgresModuleStore, WasmStrategyDeployments, WebSocketActivitySink};
 use crate::websocket::WebSocketManager;
+use event_bus::EventBus;
 use exchange_connectors::ExchangeConnector;
 use tokio::sync::RwLock;
 
@@ -64,14 +69,28 @@ pub struct AppState {
     pub websocket_manager: Arc<WebSocketManager>,
     /// Strategy manager
     pub strategy_manager: Arc<StrategyManager>,
+    /// Uploaded WASM strategies and their running deployments
+    pub strategy_deployments: Arc<WasmStrategyDeployments>,
+    /// Event bus deployed strategies receive market data from and publish signals to
+    pub event_bus: EventBus,
     /// Server configuration
     pub config: Arc<config::ApiConfig>,
     /// Orchestrator state (thread-safe mutable)
     pub orchestrator_state: Arc<RwLock<OrchestratorState>>,
+    /// Token revocation list and session tracking
+    pub token_store: Arc<sessions::TokenStore>,
 }
 
 impl AppState {
     pub async fn new(config: config::ApiConfig) -> Result<Self, error::ApiError> {
+        Self::with_event_bus(config, event_bus::EventBusBuilder::default().build()).await
+    }
+
+    /// Builds the state with deployed strategies running on `event_bus`
+    pub async fn with_event_bus(
+        config: config::ApiConfig,
+        event_bus: EventBus,
+    ) -> Result<Self, error::ApiError> {
         // Load database configuration
         let db_manager = Arc::new(
             DatabaseManager::new(ninja_gekko_database::DatabaseConfig {
@@ -113,8 +132,6 @@ impl AppState {
         }
         info!("Migrations completed successfully");
 
-        let portfolio_manager = Arc::new(PortfolioManager::new(db_manager.clone()));
-
         // Initialize Exchange Connector
         // Prioritize Kraken as the primary execution venue
         let connector: Option<Arc<Box<dyn exchange_connectors::ExchangeConnector>>> =
@@ -147,19 +164,53 @@ impl AppState {
 
         let market_data_service = Arc::new(MarketDataService::new(db_manager.clone(), connector));
 
-        let strategy_manager = Arc::new(StrategyManager::new(db_manager.clone()));
+        // Returns are measured against this capital; without it only PnL figures are meaningful
+        let initial_capital = std::env::var("PORTFOLIO_INITIAL_CAPITAL")
+            .ok()
+            .and_then(|capital| capital.parse::<rust_decimal::Decimal>().ok())
+            .unwrap_or_default();
+
+        let portfolio_manager = Arc::new(PortfolioManager::new(
+            db_manager.clone(),
+            market_data_service.clone(),
+            initial_capital,
+        ));
+
+        let strategy_manager = Arc::new(StrategyManager::new(
+            db_manager.clone(),
+            market_data_service.clone(),
+            initial_capital,
+        ));
 
         let websocket_manager = Arc::new(WebSocketManager::new());
         // Note: Start websocket background tasks in main.rs
 
+        // Sandbox state survives restarts only when a state directory is configured
+        let sandbox_state: Arc<dyn strategy_engine::StrategyStateStore> =
+            match std::env::var("STRATEGY_STATE_DIR") {
+                Ok(dir) => Arc::new(strategy_engine::sandbox::FileStateStore::new(dir)),
+                Err(_) => Arc::new(strategy_engine::InMemoryStateStore::new()),
+            };
+        let strategy_deployments = Arc::new(WasmStrategyDeployments::new(
+            Arc::new(PostgresModuleStore::new(db_manager.pool().clone())),
+            sandbox_state,
+            event_bus.signal_sender(),
+            Arc::new(WebSocketActivitySink::new(websocket_manager.clone())),
+        ));
+
+        let token_store = Arc::new(sessions::TokenStore::from_env().await);
+
         Ok(Self {
             db_manager,
             portfolio_manager,
             market_data_service,
             websocket_manager,
             strategy_manager,
+            strategy_deployments,
+            event_bus,
             config: Arc::new(config),
             orchestrator_state: Arc::new(RwLock::new(OrchestratorState::default())),
+            token_store,
         })
     }
 }
@@ -177,12 +228,17 @@ pub struct ApiServer {
 impl ApiServer {
     /// Creates a new API server with all routes and middleware configured
     pub async fn new() -> Result<Self, error::ApiError> {
+        Self::with_event_bus(event_bus::EventBusBuilder::default().build()).await
+    }
+
+    /// Creates a new API server whose deployed strategies run on `event_bus`
+    pub async fn with_event_bus(event_bus: EventBus) -> Result<Self, error::ApiError> {
         // Load configuration
         let config = config::ApiConfig::from_env()
             .map_err(|e| error::ApiError::config(format!("Failed to load config: {}", e)))?;
 
         // Create application state
-        let state = Arc::new(AppState::new(config.clone()).await?);
+        let state = Arc::new(AppState::with_event_bus(config.clone(), event_bus).await?);
 
         // Build middleware stack using the middleware builder
         // Build middleware stack using the middleware builder
@@ -194,6 +250,68 @@ impl ApiServer {
             .timing(true)
             .request_id(true);
 
+        // Routes that act on the caller's session
+        let session_routes = Router::new()
+            .route(
+                "/api/v1/auth/logout",
+                post(handlers::auth_utils::logout_handler),
+            )
+            .route(
+                "/api/v1/auth/sessions",
+                get(handlers::auth_utils::list_sessions_handler)
+                    .delete(handlers::auth_utils::revoke_all_sessions_handler),
+            )
+            .route(
+                "/api/v1/auth/sessions/:id",
+                delete(handlers::auth_utils::revoke_session_handler),
+            );
+
+        // Uploading and deploying strategy code additionally requires a deploy role
+        let require_strategy_deployer =
+            axum::middleware::from_fn(auth::AuthorizationMiddleware::require_strategy_deployer);
+
+        // Strategy module and deployment routes act on behalf of the caller
+        let strategy_deployment_routes = Router::new()
+            .route(
+                "/api/v1/strategies/:id/modules",
+                get(handlers::strategies::list_strategy_modules),
+            )
+            .route(
+                "/api/v1/strategies/:id/modules",
+                post(handlers::strategies::upload_strategy_module)
+                    .layer(DefaultBodyLimit::max(
+                        wasm_strategies::MAX_MODULE_BYTES + 64 * 1024,
+                    ))
+                    .route_layer(require_strategy_deployer.clone()),
+            )
+            .route(
+                "/api/v1/strategies/:id/deployment",
+                get(handlers::strategies::get_strategy_deployment),
+            )
+            .route(
+                "/api/v1/strategies/:id/deployment",
+                post(handlers::strategies::deploy_strategy)
+                    .route_layer(require_strategy_deployer.clone()),
+            )
+            .route(
+                "/api/v1/strategies/:id/deployment",
+                put(handlers::strategies::swap_strategy_version)
+                    .route_layer(require_strategy_deployer.clone()),
+            )
+            .route(
+                "/api/v1/strategies/:id/deployment",
+                delete(handlers::strategies::stop_strategy_deployment)
+                    .route_layer(require_strategy_deployer.clone()),
+            );
+
+        // Routes that act on a caller's identity require a live, unrevoked access token
+        let authenticated_routes = session_routes
+            .merge(strategy_deployment_routes)
+            .route_layer(axum::middleware::from_fn_with_state(
+                state.token_store.clone(),
+                auth::AuthMiddleware::authenticate,
+            ));
+
         // Create router with all routes
         let router = Router::new()
             // Health check endpoint
@@ -231,6 +349,14 @@ impl ApiServer {
                 "/api/v1/market-data/:symbol/history",
                 get(handlers::market_data::get_historical_data),
             )
+            .route(
+                "/api/v1/market-data/:symbol/indicators",
+                get(handlers::market_data::get_price_with_indicators),
+            )
+            .route(
+                "/api/v1/indicators",
+                get(handlers::market_data::list_indicators),
+            )
             // Strategy endpoints
             .route(
                 "/api/v1/strategies",
@@ -256,6 +382,10 @@ impl ApiServer {
                 "/api/v1/strategies/:id/execute",
                 post(handlers::strategies::execute_strategy),
             )
+            .route(
+                "/api/v1/strategy-types",
+                get(handlers::strategies::list_strategy_types),
+            )
             // WebSocket endpoint for real-time data
             .route("/api/v1/ws", get(websocket::handle_socket))
             // Authentication endpoints
@@ -267,10 +397,7 @@ impl ApiServer {
                 "/api/v1/auth/refresh",
                 post(handlers::auth_utils::refresh_handler),
             )
-            .route(
-                "/api/v1/auth/logout",
-                post(handlers::auth_utils::logout_handler),
-            )
+            .merge(authenticated_routes)
             // API documentation
             .route("/api/v1/docs", get(handlers::api_info))
             // Chat & Frontend routes
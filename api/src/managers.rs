@@ -1,10 +1,16 @@
 use crate::error::{ApiError, ApiResult};
-use crate::indicators::{CandleData, IndicatorService};
+use crate::handlers::trades::{self, TradeExecutionRow};
+use crate::indicators::{CandleData, IndicatorRequest, IndicatorService};
 use crate::models::*;
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
+use ninja_gekko_core::analytics::{Mark, PerformanceReport, PerformanceTracker, ReturnPeriod};
 use ninja_gekko_database::DatabaseManager;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::sync::Arc;
+use strategy_engine::indicators::buffer::Candle;
+use strategy_engine::{OptimizerConfig, ParamSet, SearchSpace, StrategyOptimizer, WalkForward};
 
 /// Manager for portfolio operations
 ///
@@ -12,32 +18,42 @@ use std::sync::Arc;
 /// real portfolio data. Returns empty results when no data available.
 pub struct PortfolioManager {
     db: Arc<DatabaseManager>,
+    market_data: Arc<MarketDataService>,
+    /// Capital that portfolio returns are measured against
+    initial_capital: Decimal,
 }
 
 impl PortfolioManager {
-    pub fn new(db: Arc<DatabaseManager>) -> Self {
-        Self { db }
+    pub fn new(
+        db: Arc<DatabaseManager>,
+        market_data: Arc<MarketDataService>,
+        initial_capital: Decimal,
+    ) -> Self {
+        Self {
+            db,
+            market_data,
+            initial_capital,
+        }
     }
 
-    /// Get portfolio from database
+    /// Replay every filled execution into a performance tracker
+    async fn tracker(&self) -> ApiResult<PerformanceTracker> {
+        let rows = trades::filled_executions(self.db.pool(), None).await?;
+        Ok(performance_tracker(&self.market_data, self.initial_capital, &rows).await)
+    }
+
+    /// Get portfolio valued from trade history and latest prices
     pub async fn get_portfolio(&self) -> ApiResult<PortfolioResponse> {
-        // Query database for portfolio data
-        // Returns initialized empty portfolio if no data exists
+        let tracker = self.tracker().await?;
+        let report = tracker.report();
+        let total_value = report.final_equity;
         Ok(PortfolioResponse {
             portfolio_id: "primary".to_string(),
-            total_value: 0.0,
-            total_unrealized_pnl: 0.0,
-            total_realized_pnl: 0.0,
-            positions: vec![],
-            performance: PerformanceMetricsResponse {
-                daily_return: 0.0,
-                weekly_return: 0.0,
-                monthly_return: 0.0,
-                yearly_return: 0.0,
-                sharpe_ratio: 0.0,
-                max_drawdown: 0.0,
-                volatility: 0.0,
-            },
+            total_value,
+            total_unrealized_pnl: report.total_pnl - report.realized_pnl,
+            total_realized_pnl: report.realized_pnl,
+            positions: position_responses(&tracker, total_value),
+            performance: performance_metrics(&tracker, &report),
             last_updated: Utc::now(),
         })
     }
@@ -70,22 +86,17 @@ impl PortfolioManager {
 
     /// Get specific position by symbol
     pub async fn get_position(&self, symbol: &str) -> ApiResult<Option<PositionResponse>> {
-        // TODO: Query position from database
-        Ok(None)
+        let tracker = self.tracker().await?;
+        let total_value = tracker.equity();
+        Ok(position_responses(&tracker, total_value)
+            .into_iter()
+            .find(|position| position.symbol == symbol))
     }
 
     /// Get performance metrics calculated from trade history
     pub async fn get_performance_metrics(&self) -> ApiResult<PerformanceMetricsResponse> {
-        // TODO: Calculate from trade history in database
-        Ok(PerformanceMetricsResponse {
-            daily_return: 0.0,
-            weekly_return: 0.0,
-            monthly_return: 0.0,
-            yearly_return: 0.0,
-            sharpe_ratio: 0.0,
-            max_drawdown: 0.0,
-            volatility: 0.0,
-        })
+        let tracker = self.tracker().await?;
+        Ok(performance_metrics(&tracker, &tracker.report()))
     }
 
     pub async fn get_allocation_breakdown(&self) -> ApiResult<Vec<AllocationResponse>> {
@@ -121,12 +132,15 @@ impl PortfolioManager {
     }
 
     pub async fn get_risk_metrics(&self) -> ApiResult<RiskMetricsResponse> {
-        // TODO: Calculate risk metrics from positions and market data
+        let report = self.tracker().await?.report();
         Ok(RiskMetricsResponse {
-            value_at_risk: 0.0,
-            volatility: 0.0,
-            sharpe_ratio: 0.0,
-            max_drawdown: 0.0,
+            value_at_risk: report.value_at_risk_95 * 100.0,
+            conditional_value_at_risk: report.conditional_value_at_risk_95 * 100.0,
+            volatility: report.volatility * 100.0,
+            sharpe_ratio: report.sharpe_ratio,
+            sortino_ratio: report.sortino_ratio,
+            max_drawdown: report.max_drawdown * 100.0,
+            // TODO: Needs a benchmark return series
             beta: 0.0,
             correlation_matrix: HashMap::new(),
         })
@@ -254,10 +268,37 @@ impl MarketDataService {
         })
     }
 
+    /// Fetch candles for a window of history from the exchange
+    pub async fn get_candles(
+        &self,
+        symbol: &str,
+        timeframe: exchange_connectors::Timeframe,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> ApiResult<Vec<exchange_connectors::Candle>> {
+        let Some(conn) = &self.connector else {
+            return Err(ApiError::ExternalService {
+                service: "exchange_connector".to_string(),
+                message: format!("Historical candles unavailable for {}", symbol),
+            });
+        };
+        conn.get_candles(symbol, timeframe, Some(start), Some(end))
+            .await
+            .map_err(|e| ApiError::ExternalService {
+                service: "exchange_connector".to_string(),
+                message: format!("Failed to fetch candles for {}: {}", symbol, e),
+            })
+    }
+
+    /// Latest market data with indicators computed over recent history
+    ///
+    /// With no `requests` the default indicator set is reported as latest values;
+    /// requested indicators are also returned as full series.
     pub async fn get_data_with_indicators(
         &self,
         symbol: &str,
         params: PaginationParams,
+        requests: &[IndicatorRequest],
     ) -> ApiResult<MarketDataWithIndicators> {
         let data = self.get_latest_data(symbol).await?;
 
@@ -278,9 +319,32 @@ impl MarketDataService {
             })
             .collect();
 
+        if !requests.is_empty() {
+            let series = requests
+                .iter()
+                .map(|request| {
+                    self.indicator_service
+                        .calculate_series(request, &candle_data)
+                        .map_err(|message| ApiError::Validation {
+                            message,
+                            field: Some("indicators".to_string()),
+                        })
+                })
+                .collect::<ApiResult<Vec<_>>>()?;
+            return Ok(MarketDataWithIndicators {
+                symbol: symbol.to_string(),
+                price: data.price,
+                volume: data.volume_24h,
+                indicators: IndicatorService::latest_values(&series),
+                series,
+                timestamp: data.timestamp,
+            });
+        }
+
         // Calculate indicators if we have enough data
         let indicators = if candle_data.len() >= 20 {
-            self.indicator_service.calculate_all_indicators_ohlcv(&candle_data)
+            self.indicator_service
+                .calculate_all_indicators_ohlcv(&candle_data)
         } else if !candle_data.is_empty() {
             // Not enough for full OHLCV analysis, use close prices only
             let prices: Vec<f64> = candle_data.iter().map(|c| c.close).collect();
@@ -294,6 +358,7 @@ impl MarketDataService {
             price: data.price,
             volume: data.volume_24h,
             indicators,
+            series: Vec::new(),
             timestamp: data.timestamp,
         })
     }
@@ -387,16 +452,33 @@ impl MarketDataService {
     }
 }
 
+/// Candidates returned with an optimization result
+const OPTIMIZATION_TOP_CANDIDATES: usize = 10;
+
+/// Most recent trades returned with detailed strategy performance
+const RECENT_TRADES: usize = 20;
+
 /// Manager for strategy operations
 ///
 /// Manages trading strategies stored in the database.
 pub struct StrategyManager {
     db: Arc<DatabaseManager>,
+    market_data: Arc<MarketDataService>,
+    /// Capital that strategy returns are measured against
+    initial_capital: Decimal,
 }
 
 impl StrategyManager {
-    pub fn new(db: Arc<DatabaseManager>) -> Self {
-        Self { db }
+    pub fn new(
+        db: Arc<DatabaseManager>,
+        market_data: Arc<MarketDataService>,
+        initial_capital: Decimal,
+    ) -> Self {
+        Self {
+            db,
+            market_data,
+            initial_capital,
+        }
     }
 
     pub async fn list_strategies(
@@ -426,12 +508,20 @@ impl StrategyManager {
         &self,
         request: CreateStrategyRequest,
     ) -> ApiResult<StrategyResponse> {
+        let parameters =
+            request
+                .validated_parameters()
+                .map_err(|message| ApiError::Validation {
+                    message,
+                    field: Some("parameters".to_string()),
+                })?;
         let id = uuid::Uuid::new_v4().to_string();
         Ok(StrategyResponse {
             id,
             name: request.name,
             description: request.description,
-            parameters: request.parameters,
+            strategy_type: request.strategy_type,
+            parameters,
             is_active: false, // New strategies start inactive
             account_ids: request.account_ids.unwrap_or_default(),
             created_at: Utc::now(),
@@ -502,13 +592,122 @@ impl StrategyManager {
         })
     }
 
+    /// Search a built-in strategy's parameters by backtesting it on exchange history
     pub async fn optimize_strategy(
         &self,
         id: &str,
-        _request: StrategyOptimizationRequest,
+        request: StrategyOptimizationRequest,
     ) -> ApiResult<StrategyOptimizationResponse> {
-        Err(ApiError::NotImplemented {
-            message: format!("Strategy optimization not yet implemented for {}", id),
+        let strategy_type = match request.strategy_type.clone() {
+            Some(strategy_type) => strategy_type,
+            None => self
+                .get_strategy(id)
+                .await?
+                .and_then(|strategy| strategy.strategy_type)
+                .ok_or_else(|| ApiError::Validation {
+                    message: format!("Strategy {} has no built-in type to optimize", id),
+                    field: Some("strategy_type".to_string()),
+                })?,
+        };
+        let schema =
+            strategy_engine::strategies::parameter_schema(&strategy_type).ok_or_else(|| {
+                ApiError::Validation {
+                    message: format!("Unknown strategy type: {}", strategy_type),
+                    field: Some("strategy_type".to_string()),
+                }
+            })?;
+        let space = SearchSpace::from_json(&schema, &request.parameter_ranges).map_err(|e| {
+            ApiError::Validation {
+                message: e.to_string(),
+                field: Some("parameter_ranges".to_string()),
+            }
+        })?;
+        let config = optimizer_config(&request)?;
+
+        let timeframe = parse_timeframe(request.timeframe.as_deref().unwrap_or("1h"))?;
+        let end = request.end_date.unwrap_or_else(Utc::now);
+        let start = request
+            .start_date
+            .unwrap_or(end - chrono::Duration::days(90));
+        if start >= end {
+            return Err(ApiError::Validation {
+                message: "start_date must be before end_date".to_string(),
+                field: Some("start_date".to_string()),
+            });
+        }
+        let candles: Vec<Candle> = self
+            .market_data
+            .get_candles(&request.symbol, timeframe, start, end)
+            .await?
+            .into_iter()
+            .map(|c| Candle {
+                open: c.open,
+                high: c.high,
+                low: c.low,
+                close: c.close,
+                volume: c.volume,
+                timestamp: c.start_time.timestamp(),
+            })
+            .collect();
+
+        let objective = config.objective;
+        let method = config.method;
+        let optimizer = StrategyOptimizer::new(
+            schema.defaults(),
+            move |params: &ParamSet| {
+                strategy_engine::strategies::create(&strategy_type, "optimizer", params)
+            },
+            config,
+        );
+        let report = tokio::task::spawn_blocking(move || optimizer.optimize(&space, &candles))
+            .await
+            .map_err(|e| ApiError::internal(format!("Optimization task failed: {}", e)))?
+            .map_err(|e| ApiError::Strategy {
+                message: e.to_string(),
+            })?;
+
+        let best = report.best().ok_or_else(|| ApiError::Strategy {
+            message: format!(
+                "No candidate passed the overfitting guards ({})",
+                report.candidates[0]
+                    .rejection
+                    .as_deref()
+                    .unwrap_or("no candidates")
+            ),
+        })?;
+        let selected = best.out_of_sample.as_ref().unwrap_or(&best.in_sample);
+
+        Ok(StrategyOptimizationResponse {
+            optimization_id: uuid::Uuid::new_v4().to_string(),
+            best_parameters: best.parameters.to_json(),
+            best_metric_value: objective.value(selected),
+            target_metric: objective.to_string(),
+            search_method: format!("{:?}", method).to_lowercase(),
+            iterations: report.evaluated,
+            rejected: report.rejected(),
+            in_sample_metric_value: objective.value(&best.in_sample),
+            out_of_sample_metric_value: best
+                .out_of_sample
+                .as_ref()
+                .map(|metrics| objective.value(metrics)),
+            top_candidates: report
+                .candidates
+                .iter()
+                .take(OPTIMIZATION_TOP_CANDIDATES)
+                .map(|candidate| {
+                    let metrics = candidate
+                        .out_of_sample
+                        .as_ref()
+                        .unwrap_or(&candidate.in_sample);
+                    OptimizationCandidate {
+                        parameters: candidate.parameters.to_json(),
+                        metric_value: objective.value(metrics),
+                        trades: metrics.trades,
+                        max_drawdown: metrics.max_drawdown,
+                        rejection: candidate.rejection.clone(),
+                    }
+                })
+                .collect(),
         })
     }
 
@@ -516,17 +715,249 @@ impl StrategyManager {
         &self,
         id: &str,
     ) -> ApiResult<DetailedStrategyPerformance> {
-        // TODO: Calculate from trade history
+        let rows = trades::filled_executions(self.db.pool(), Some(id)).await?;
+        let tracker = performance_tracker(&self.market_data, self.initial_capital, &rows).await;
+        let report = tracker.report();
         Ok(DetailedStrategyPerformance {
             basic_metrics: StrategyPerformance {
-                total_trades: 0,
-                win_rate: 0.0,
-                total_pnl: 0.0,
-                avg_trade_duration: 0.0,
-                max_drawdown: 0.0,
+                total_trades: report.trades,
+                win_rate: report.win_rate * 100.0,
+                total_pnl: report.total_pnl,
+                avg_trade_duration: report.avg_trade_duration_secs,
+                max_drawdown: report.max_drawdown * 100.0,
             },
-            monthly_returns: HashMap::new(),
-            recent_trades: vec![],
+            monthly_returns: tracker
+                .period_returns(ReturnPeriod::Month)
+                .into_iter()
+                .map(|(month, change)| (month, change * 100.0))
+                .collect(),
+            recent_trades: rows
+                .into_iter()
+                .rev()
+                .take(RECENT_TRADES)
+                .map(TradeResponse::from)
+                .collect(),
+            analytics: report,
+        })
+    }
+}
+
+/// Replay filled executions and value open positions at the latest exchange price
+///
+/// Positions whose price cannot be fetched stay valued at their last fill.
+async fn performance_tracker(
+    market_data: &MarketDataService,
+    initial_capital: Decimal,
+    rows: &[TradeExecutionRow],
+) -> PerformanceTracker {
+    let executions: Vec<_> = rows
+        .iter()
+        .filter_map(TradeExecutionRow::to_execution)
+        .collect();
+    let mut tracker = PerformanceTracker::from_history(initial_capital, &executions, &[]);
+    let now = Utc::now();
+    for position in tracker.positions() {
+        match market_data.get_latest_data(&position.symbol).await {
+            Ok(data) => {
+                if let Some(price) = Decimal::from_f64(data.price) {
+                    tracker.record_mark(&Mark {
+                        symbol: position.symbol,
+                        price,
+                        timestamp: now,
+                    });
+                }
+            }
+            Err(e) => tracing::warn!("Valuing {} at its last fill: {}", position.symbol, e),
+        }
+    }
+    tracker
+}
+
+/// Trailing returns and risk figures, in percent where the model says so
+fn performance_metrics(
+    tracker: &PerformanceTracker,
+    report: &PerformanceReport,
+) -> PerformanceMetricsResponse {
+    let trailing = |days: i64| tracker.trailing_return(Duration::days(days)) * 100.0;
+    PerformanceMetricsResponse {
+        daily_return: trailing(1),
+        weekly_return: trailing(7),
+        monthly_return: trailing(30),
+        yearly_return: trailing(365),
+        sharpe_ratio: report.sharpe_ratio,
+        max_drawdown: report.max_drawdown * 100.0,
+        volatility: report.volatility * 100.0,
+    }
+}
+
+/// Open positions with realized PnL of their closed round trips
+fn position_responses(tracker: &PerformanceTracker, total_value: f64) -> Vec<PositionResponse> {
+    tracker
+        .positions()
+        .into_iter()
+        .map(|position| {
+            let market_value = position.market_value();
+            PositionResponse {
+                realized_pnl: tracker
+                    .trades()
+                    .iter()
+                    .filter(|trade| trade.symbol == position.symbol)
+                    .map(|trade| trade.pnl)
+                    .sum(),
+                allocation_percentage: if total_value > 0.0 {
+                    market_value.abs() / total_value * 100.0
+                } else {
+                    0.0
+                },
+                quantity: position.quantity,
+                average_cost: position.entry_price,
+                current_price: position.mark_price,
+                unrealized_pnl: position.unrealized_pnl(),
+                market_value,
+                symbol: position.symbol,
+            }
         })
+        .collect()
+}
+
+/// Translate an optimization request into optimizer settings
+fn optimizer_config(request: &StrategyOptimizationRequest) -> ApiResult<OptimizerConfig> {
+    let invalid = |field: &str, message: String| ApiError::Validation {
+        message,
+        field: Some(field.to_string()),
+    };
+    let mut config = OptimizerConfig {
+        objective: request
+            .target_metric
+            .parse()
+            .map_err(|e| invalid("target_metric", e))?,
+        ..OptimizerConfig::default()
+    };
+    if let Some(method) = &request.search_method {
+        config.method = method.parse().map_err(|e| invalid("search_method", e))?;
+    }
+    if let Some(max_iterations) = request.max_iterations {
+        config.max_evaluations = max_iterations.max(1);
+    }
+    if let Some(min_trades) = request.min_trades {
+        config.guards.min_trades = min_trades;
+    }
+    if let Some(seed) = request.seed {
+        config.seed = seed;
+    }
+    config.backtest.symbol = request.symbol.clone();
+
+    let mut walk_forward = WalkForward::default();
+    if let Some(folds) = request.walk_forward_folds {
+        walk_forward.folds = folds;
+    }
+    if let Some(fraction) = request.in_sample_fraction {
+        if fraction <= 0.0 || fraction >= 1.0 {
+            return Err(invalid(
+                "in_sample_fraction",
+                "in_sample_fraction must be between 0 and 1".to_string(),
+            ));
+        }
+        walk_forward.in_sample_fraction = fraction;
+    }
+    config.walk_forward = (walk_forward.folds > 0).then_some(walk_forward);
+    Ok(config)
+}
+
+fn parse_timeframe(timeframe: &str) -> ApiResult<exchange_connectors::Timeframe> {
+    use exchange_connectors::Timeframe;
+    [
+        Timeframe::OneMinute,
+        Timeframe::FiveMinutes,
+        Timeframe::FifteenMinutes,
+        Timeframe::OneHour,
+        Timeframe::FourHours,
+        Timeframe::OneDay,
+    ]
+    .into_iter()
+    .find(|candidate| candidate.as_str() == timeframe)
+    .ok_or_else(|| ApiError::Validation {
+        message: format!("Unsupported timeframe: {}", timeframe),
+        field: Some("timeframe".to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strategy_engine::{Objective, SearchMethod};
+
+    fn request(value: serde_json::Value) -> StrategyOptimizationRequest {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_optimizer_config_from_request() {
+        let config = optimizer_config(&request(serde_json::json!({
+            "strategy_id": "s1",
+            "parameter_ranges": { "rsi_period": { "min": 5, "max": 30, "step": 5 } },
+            "target_metric": "drawdown",
+            "max_iterations": 50,
+            "symbol": "ETH-USD",
+            "search_method": "grid",
+            "walk_forward_folds": 0,
+        })))
+        .unwrap();
+
+        assert_eq!(config.objective, Objective::MaxDrawdown);
+        assert_eq!(config.method, SearchMethod::Grid);
+        assert_eq!(config.max_evaluations, 50);
+        assert_eq!(config.backtest.symbol, "ETH-USD");
+        assert!(config.walk_forward.is_none());
+
+        let invalid = optimizer_config(&request(serde_json::json!({
+            "strategy_id": "s1",
+            "parameter_ranges": {},
+            "target_metric": "sharpe",
+            "max_iterations": null,
+            "symbol": "ETH-USD",
+            "in_sample_fraction": 1.5,
+        })));
+        assert!(matches!(invalid, Err(ApiError::Validation { .. })));
+        assert!(parse_timeframe("4h").is_ok());
+        assert!(parse_timeframe("2h").is_err());
+    }
+
+    #[test]
+    fn test_positions_and_metrics_from_tracker() {
+        use ninja_gekko_core::types::{Execution, OrderSide};
+
+        let fill = |side, quantity: i64, price: i64, hours: i64| {
+            let mut execution = Execution::new(
+                uuid::Uuid::new_v4(),
+                "BTC-USD".to_string(),
+                side,
+                Decimal::from(quantity),
+                Decimal::from(price),
+                "kraken".to_string(),
+                Decimal::ZERO,
+            );
+            execution.timestamp = DateTime::UNIX_EPOCH + Duration::hours(hours);
+            execution
+        };
+        let tracker = PerformanceTracker::from_history(
+            Decimal::from(1_000),
+            &[
+                fill(OrderSide::Buy, 4, 100, 0),
+                fill(OrderSide::Sell, 2, 110, 24),
+            ],
+            &[],
+        );
+
+        let positions = position_responses(&tracker, tracker.equity());
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].quantity, 2.0);
+        assert_eq!(positions[0].realized_pnl, 20.0);
+        assert_eq!(positions[0].unrealized_pnl, 20.0);
+        assert!((positions[0].allocation_percentage - 220.0 / 1_040.0 * 100.0).abs() < 1e-9);
+
+        let metrics = performance_metrics(&tracker, &tracker.report());
+        assert!((metrics.yearly_return - 4.0).abs() < 1e-9);
+        assert!((metrics.daily_return - 4.0).abs() < 1e-9);
     }
 }
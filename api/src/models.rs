@@ -4,11 +4,15 @@
 //! along with common pagination and error response types.
 
 use chrono::{DateTime, Utc};
+use ninja_gekko_core::analytics::PerformanceReport;
 use ninja_gekko_core::types::{Order, OrderSide, OrderType, Portfolio};
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use strategy_engine::StrategyState;
+
+use crate::indicators::IndicatorSeries;
 
 /// Standardized API response wrapper
 #[derive(Debug, Serialize, Deserialize)]
@@ -508,6 +512,10 @@ pub struct CreateStrategyRequest {
     /// Strategy description
     pub description: Option<String>,
 
+    /// Built-in strategy type (e.g. "momentum") whose schema the parameters follow
+    #[serde(default)]
+    pub strategy_type: Option<String>,
+
     /// Strategy parameters
     pub parameters: HashMap<String, serde_json::Value>,
 
@@ -529,7 +537,22 @@ impl CreateStrategyRequest {
             return Err("Strategy name cannot exceed 100 characters".to_string());
         }
 
-        Ok(())
+        self.validated_parameters().map(|_| ())
+    }
+
+    /// Check parameters against the strategy type's schema, filling in defaults.
+    ///
+    /// Requests without a strategy type keep their parameters as submitted.
+    pub fn validated_parameters(&self) -> Result<HashMap<String, serde_json::Value>, String> {
+        let Some(strategy_type) = self.strategy_type.as_deref() else {
+            return Ok(self.parameters.clone());
+        };
+        let schema = strategy_engine::strategies::parameter_schema(strategy_type)
+            .ok_or_else(|| format!("Unknown strategy type: {}", strategy_type))?;
+        schema
+            .validate(&self.parameters)
+            .map(|params| params.to_json())
+            .map_err(|e| e.to_string())
     }
 }
 
@@ -545,6 +568,9 @@ pub struct StrategyResponse {
     /// Strategy description
     pub description: Option<String>,
 
+    /// Built-in strategy type, if any
+    pub strategy_type: Option<String>,
+
     /// Strategy parameters
     pub parameters: HashMap<String, serde_json::Value>,
 
@@ -576,13 +602,144 @@ pub struct StrategyPerformance {
     /// Total P&L
     pub total_pnl: f64,
 
-    /// Average trade duration
+    /// Average trade duration in seconds
     pub avg_trade_duration: f64,
 
-    /// Maximum drawdown
+    /// Maximum drawdown percentage
     pub max_drawdown: f64,
 }
 
+/// Uploaded WASM strategy module version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyModuleResponse {
+    /// Module ID
+    pub id: String,
+
+    /// Strategy the module belongs to
+    pub strategy_id: String,
+
+    /// Module name, used in logs and activity streams
+    pub name: String,
+
+    /// Version number, starting at 1 per strategy
+    pub version: i32,
+
+    /// Hex SHA-256 of the module bytes
+    pub content_hash: String,
+
+    /// Host ABI version the module targets
+    pub abi_version: i32,
+
+    /// Module size in bytes
+    pub size_bytes: i64,
+
+    /// Upload timestamp
+    pub uploaded_at: DateTime<Utc>,
+}
+
+/// Sandbox limits for a deployed WASM strategy; omitted limits use the server defaults
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SandboxLimits {
+    /// Linear memory available to the module
+    pub memory_limit_bytes: Option<u64>,
+
+    /// Fuel granted to each evaluation
+    pub fuel_limit: Option<u64>,
+
+    /// Combined size of the module's persisted key-value state
+    pub state_limit_bytes: Option<usize>,
+}
+
+/// Request to run an uploaded strategy module
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeployStrategyRequest {
+    /// Module version to run, the latest upload if omitted
+    pub version: Option<i32>,
+
+    /// Symbols the strategy receives market data for
+    pub symbols: Vec<String>,
+
+    /// Accounts to run the strategy for, one sandbox instance each
+    pub account_ids: Vec<String>,
+
+    /// Sandbox limits
+    #[serde(default)]
+    pub limits: SandboxLimits,
+}
+
+impl DeployStrategyRequest {
+    /// Validate the deployment request
+    pub fn validate(&self) -> Result<(), String> {
+        if self.symbols.is_empty() || self.symbols.iter().any(|s| s.trim().is_empty()) {
+            return Err("At least one non-empty symbol is required".to_string());
+        }
+
+        if self.account_ids.is_empty() || self.account_ids.iter().any(|a| a.trim().is_empty()) {
+            return Err("At least one non-empty account ID is required".to_string());
+        }
+
+        if matches!(self.version, Some(version) if version < 1) {
+            return Err("Module version must be positive".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// Request to move a deployment to another module version
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SwapStrategyVersionRequest {
+    /// Module version to switch to
+    pub version: i32,
+}
+
+/// Running deployment of a WASM strategy
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StrategyDeploymentResponse {
+    /// Strategy ID
+    pub strategy_id: String,
+
+    /// Module version being run
+    pub module: StrategyModuleResponse,
+
+    /// Symbols the strategy receives market data for
+    pub symbols: Vec<String>,
+
+    /// Effective sandbox limits
+    pub limits: SandboxLimits,
+
+    /// One sandbox instance per account
+    pub instances: Vec<DeploymentInstanceResponse>,
+
+    /// Deployment timestamp
+    pub deployed_at: DateTime<Utc>,
+
+    /// Last version change timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Sandbox instance of a deployed strategy for one account
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeploymentInstanceResponse {
+    /// Account ID
+    pub account_id: String,
+
+    /// Lifecycle state in the strategy host
+    pub state: StrategyState,
+
+    /// Market events received
+    pub events: u64,
+
+    /// Evaluations run
+    pub evaluations: u64,
+
+    /// Signals published
+    pub signals: u64,
+
+    /// Failed evaluations, including fuel exhaustion
+    pub errors: u64,
+}
+
 /// WebSocket subscription request
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SubscriptionRequest {
@@ -723,10 +880,10 @@ pub struct PerformanceMetricsResponse {
     /// Sharpe ratio
     pub sharpe_ratio: f64,
 
-    /// Maximum drawdown
+    /// Maximum drawdown percentage
     pub max_drawdown: f64,
 
-    /// Volatility measure
+    /// Annualized volatility percentage
     pub volatility: f64,
 }
 
@@ -918,14 +1075,53 @@ pub struct StrategyOptimizationRequest {
     /// Strategy ID
     pub strategy_id: String,
 
-    /// Parameters to optimize with ranges
+    /// Built-in strategy type whose parameters are searched
+    #[serde(default)]
+    pub strategy_type: Option<String>,
+
+    /// Parameters to optimize, each an array of values or `{min, max, step}`
     pub parameter_ranges: HashMap<String, serde_json::Value>,
 
-    /// Optimization target (sharpe, returns, drawdown)
+    /// Optimization target (sharpe, sortino, profit_factor, drawdown, returns)
     pub target_metric: String,
 
     /// Maximum iterations
     pub max_iterations: Option<usize>,
+
+    /// Symbol to backtest on
+    pub symbol: String,
+
+    /// Candle timeframe (1m, 5m, 15m, 1h, 4h, 1d), defaults to 1h
+    #[serde(default)]
+    pub timeframe: Option<String>,
+
+    /// Start of the history, defaults to 90 days before `end_date`
+    #[serde(default)]
+    pub start_date: Option<DateTime<Utc>>,
+
+    /// End of the history, defaults to now
+    #[serde(default)]
+    pub end_date: Option<DateTime<Utc>>,
+
+    /// Search method (grid, random, adaptive), defaults to random
+    #[serde(default)]
+    pub search_method: Option<String>,
+
+    /// Walk-forward folds; 0 scores candidates on the whole history
+    #[serde(default)]
+    pub walk_forward_folds: Option<usize>,
+
+    /// Share of each walk-forward fold used in sample
+    #[serde(default)]
+    pub in_sample_fraction: Option<f64>,
+
+    /// Fewest in-sample trades a candidate needs to be selected
+    #[serde(default)]
+    pub min_trades: Option<usize>,
+
+    /// Seed for random and adaptive search
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 /// Strategy optimization response
@@ -937,8 +1133,40 @@ pub struct StrategyOptimizationResponse {
     /// Best parameters found
     pub best_parameters: HashMap<String, serde_json::Value>,
 
-    /// Best metric value
+    /// Best metric value (out of sample under walk-forward)
     pub best_metric_value: f64,
+
+    /// Target metric candidates were ranked by
+    pub target_metric: String,
+
+    /// Search method used
+    pub search_method: String,
+
+    /// Candidates backtested
+    pub iterations: usize,
+
+    /// Candidates excluded by the overfitting guards
+    pub rejected: usize,
+
+    /// Best candidate's in-sample metric value
+    pub in_sample_metric_value: f64,
+
+    /// Best candidate's out-of-sample metric value, when walk-forward was used
+    pub out_of_sample_metric_value: Option<f64>,
+
+    /// Highest-ranked candidates, best first
+    pub top_candidates: Vec<OptimizationCandidate>,
+}
+
+/// A ranked optimization candidate
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OptimizationCandidate {
+    pub parameters: HashMap<String, serde_json::Value>,
+    pub metric_value: f64,
+    pub trades: usize,
+    pub max_drawdown: f64,
+    /// Why the candidate was excluded from selection, if it was
+    pub rejection: Option<String>,
 }
 
 /// Update strategy request
@@ -964,19 +1192,28 @@ pub struct DetailedStrategyPerformance {
     #[serde(flatten)]
     pub basic_metrics: StrategyPerformance,
 
-    /// Monthly returns
+    /// Monthly return percentages keyed by `YYYY-MM`
     pub monthly_returns: HashMap<String, f64>,
 
     /// Trade history
     pub recent_trades: Vec<TradeResponse>,
+
+    /// Full analytics report (fractions, not percentages)
+    pub analytics: PerformanceReport,
 }
 
 /// Risk metrics response
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RiskMetricsResponse {
+    /// One-day 95% historical value at risk, as a loss percentage
     pub value_at_risk: f64,
+    /// Mean one-day loss beyond the value at risk, as a percentage
+    pub conditional_value_at_risk: f64,
+    /// Annualized volatility percentage
     pub volatility: f64,
     pub sharpe_ratio: f64,
+    pub sortino_ratio: f64,
+    /// Maximum drawdown percentage
     pub max_drawdown: f64,
     pub beta: f64,
     pub correlation_matrix: HashMap<String, HashMap<String, f64>>,
@@ -988,10 +1225,21 @@ pub struct MarketDataWithIndicators {
     pub symbol: String,
     pub price: f64,
     pub volume: f64,
+    /// Latest value of each indicator line
     pub indicators: HashMap<String, f64>,
+    /// Full series of explicitly requested indicators
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub series: Vec<IndicatorSeries>,
     pub timestamp: DateTime<Utc>,
 }
 
+/// Query selecting indicators for market data, e.g. `?indicators=rsi:14,macd:12:26:9`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct IndicatorsQuery {
+    /// Comma-separated indicator requests; the default set when omitted
+    pub indicators: Option<String>,
+}
+
 /// Search symbols request
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SearchSymbolsRequest {
@@ -0,0 +1,572 @@
+//! Token revocation and session tracking
+//!
+//! Every login opens a session whose refresh tokens form a single rotation
+//! family: each refresh replaces the family's current token id, and presenting
+//! an older one is treated as token theft and kills the whole session. Access
+//! tokens carry their session id, so revoking a session (logout, admin kill,
+//! reuse detection) invalidates them on the next request instead of at expiry.
+//!
+//! State lives in Redis via [`CacheManager`] when `REDIS_URL` is configured and
+//! in process memory otherwise. The in-memory store does not survive restarts,
+//! which logs every user out rather than reviving revoked tokens.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeZone, Utc};
+use dashmap::DashMap;
+use ninja_gekko_database::{CacheConfig, CacheManager};
+use redis::AsyncCommands;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::auth::Claims;
+use crate::error::{ApiError, ApiResult};
+
+const KEY_PREFIX: &str = "auth";
+
+/// Replace a session only while it still expects the presented refresh token id.
+/// Returns 1 when replaced, 0 when the session is gone or already rotated.
+const SWAP_REFRESH_SCRIPT: &str = r#"
+local current = redis.call('GET', KEYS[1])
+if not current or cjson.decode(current).refresh_jti ~= ARGV[1] then
+    return 0
+end
+redis.call('SET', KEYS[1], ARGV[2], 'EX', ARGV[3])
+return 1
+"#;
+
+/// Active login session and the state of its refresh-token family
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionInfo {
+    /// Session identifier, shared by every token issued for this login
+    pub session_id: String,
+    /// Owning user
+    pub user_id: String,
+    /// When the user logged in
+    pub created_at: DateTime<Utc>,
+    /// Last successful refresh (or login)
+    pub last_refreshed_at: DateTime<Utc>,
+    /// When the latest refresh token expires
+    pub expires_at: DateTime<Utc>,
+    /// Number of refresh rotations so far
+    pub rotations: u32,
+    /// Token id of the only refresh token currently accepted
+    pub refresh_jti: String,
+}
+
+/// Storage backend for revocation and session state
+enum Backend {
+    Cache(Arc<CacheManager>),
+    Memory(DashMap<String, (serde_json::Value, Option<DateTime<Utc>>)>),
+}
+
+/// Revocation list, refresh-token families and per-user session index
+pub struct TokenStore {
+    backend: Backend,
+}
+
+impl TokenStore {
+    /// Process-local store, used when no cache is configured
+    pub fn in_memory() -> Self {
+        Self {
+            backend: Backend::Memory(DashMap::new()),
+        }
+    }
+
+    /// Store backed by a shared Redis cache
+    pub fn with_cache(cache: Arc<CacheManager>) -> Self {
+        Self {
+            backend: Backend::Cache(cache),
+        }
+    }
+
+    /// Connect to Redis when `REDIS_URL` is set, falling back to memory
+    pub async fn from_env() -> Self {
+        if std::env::var("REDIS_URL").is_err() {
+            info!("REDIS_URL not set, tracking sessions in memory");
+            return Self::in_memory();
+        }
+
+        match CacheManager::new(CacheConfig::default()).await {
+            Ok(cache) => {
+                info!("Tracking sessions in Redis");
+                Self::with_cache(Arc::new(cache))
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to connect session cache, falling back to memory: {}",
+                    e
+                );
+                Self::in_memory()
+            }
+        }
+    }
+
+    /// Whether state is shared through Redis
+    pub fn is_shared(&self) -> bool {
+        matches!(self.backend, Backend::Cache(_))
+    }
+
+    /// Open a session for a fresh login
+    pub async fn create_session(
+        &self,
+        session_id: &str,
+        user_id: &str,
+        refresh_jti: &str,
+        expires_at: DateTime<Utc>,
+    ) -> ApiResult<SessionInfo> {
+        let now = Utc::now();
+        let session = SessionInfo {
+            session_id: session_id.to_string(),
+            user_id: user_id.to_string(),
+            created_at: now,
+            last_refreshed_at: now,
+            expires_at,
+            rotations: 0,
+            refresh_jti: refresh_jti.to_string(),
+        };
+        self.put_session(&session).await?;
+        self.index_session(user_id, &session.session_id).await?;
+        Ok(session)
+    }
+
+    /// Look up a live session
+    pub async fn get_session(&self, session_id: &str) -> ApiResult<Option<SessionInfo>> {
+        self.get(&session_key(session_id)).await
+    }
+
+    /// Advance a refresh-token family from `presented_jti` to `next_jti`.
+    ///
+    /// Presenting any refresh token other than the family's current one means
+    /// an older token was replayed, so the whole session is revoked. The swap is
+    /// atomic, so of two refreshes racing with the same token only one succeeds
+    /// and the other counts as reuse.
+    pub async fn rotate_refresh(
+        &self,
+        session_id: &str,
+        presented_jti: &str,
+        next_jti: &str,
+        expires_at: DateTime<Utc>,
+    ) -> ApiResult<SessionInfo> {
+        let session = self
+            .get_session(session_id)
+            .await?
+            .ok_or_else(|| ApiError::auth("Session has been revoked"))?;
+
+        if session.refresh_jti != presented_jti {
+            return self.reject_reuse(&session).await;
+        }
+
+        let mut rotated = session.clone();
+        rotated.refresh_jti = next_jti.to_string();
+        rotated.last_refreshed_at = Utc::now();
+        rotated.expires_at = expires_at;
+        rotated.rotations += 1;
+        if !self.swap_refresh(&rotated, presented_jti).await? {
+            return self.reject_reuse(&session).await;
+        }
+        Ok(rotated)
+    }
+
+    async fn reject_reuse(&self, session: &SessionInfo) -> ApiResult<SessionInfo> {
+        warn!(
+            "Refresh token reuse detected for user {} (session {}); revoking session",
+            session.user_id, session.session_id
+        );
+        self.revoke_session(&session.session_id).await?;
+        Err(ApiError::auth("Refresh token reuse detected"))
+    }
+
+    /// Store `session` only if its family still expects `presented_jti`, in one
+    /// atomic step. Returns false if it was rotated or revoked in the meantime.
+    async fn swap_refresh(&self, session: &SessionInfo, presented_jti: &str) -> ApiResult<bool> {
+        let key = session_key(&session.session_id);
+        match &self.backend {
+            Backend::Cache(cache) => {
+                let data = serde_json::to_vec(session).map_err(|e| store_error(e.into()))?;
+                let mut conn = cache.manager().lock().await;
+                let swapped: i32 = redis::Script::new(SWAP_REFRESH_SCRIPT)
+                    .key(&key)
+                    .arg(presented_jti)
+                    .arg(data)
+                    .arg(ttl_until(session.expires_at).as_secs())
+                    .invoke_async(&mut *conn)
+                    .await
+                    .map_err(|e| store_error(e.into()))?;
+                Ok(swapped == 1)
+            }
+            Backend::Memory(entries) => {
+                // The entry guard is held for the whole compare and write
+                let Some(mut entry) = entries.get_mut(&key) else {
+                    return Ok(false);
+                };
+                let (stored, expiry) = entry.value_mut();
+                if expiry.is_some_and(|expiry| expiry <= Utc::now()) {
+                    return Ok(false);
+                }
+                let current: SessionInfo =
+                    serde_json::from_value(stored.clone()).map_err(|e| store_error(e.into()))?;
+                if current.refresh_jti != presented_jti {
+                    return Ok(false);
+                }
+                *stored = serde_json::to_value(session).map_err(|e| store_error(e.into()))?;
+                *expiry = Some(session.expires_at);
+                Ok(true)
+            }
+        }
+    }
+
+    /// List a user's live sessions, pruning ones that expired or were revoked
+    pub async fn list_sessions(&self, user_id: &str) -> ApiResult<Vec<SessionInfo>> {
+        let index = self.indexed_sessions(user_id).await?;
+        let mut sessions = Vec::with_capacity(index.len());
+        let mut dead = Vec::new();
+        for session_id in index {
+            match self.get_session(&session_id).await? {
+                Some(session) => sessions.push(session),
+                None => dead.push(session_id),
+            }
+        }
+        self.unindex_sessions(user_id, &dead).await?;
+
+        sessions.sort_by_key(|session| session.created_at);
+        Ok(sessions)
+    }
+
+    /// Kill one session; returns false if it was already gone
+    pub async fn revoke_session(&self, session_id: &str) -> ApiResult<bool> {
+        let Some(session) = self.get_session(session_id).await? else {
+            return Ok(false);
+        };
+        self.delete(&session_key(session_id)).await?;
+        info!(
+            "Revoked session {} for user {}",
+            session_id, session.user_id
+        );
+        Ok(true)
+    }
+
+    /// Kill every session of a user and reject any of their older tokens
+    pub async fn revoke_user(&self, user_id: &str) -> ApiResult<usize> {
+        let index = self.indexed_sessions(user_id).await?;
+        let mut revoked = 0;
+        for session_id in &index {
+            if self.revoke_session(session_id).await? {
+                revoked += 1;
+            }
+        }
+        // Only drop the ids revoked here, so a login racing this keeps its entry
+        self.unindex_sessions(user_id, &index).await?;
+
+        // Covers tokens issued outside a session, which the index cannot reach
+        let cutoff = Utc::now().timestamp();
+        self.put(&cutoff_key(user_id), &cutoff, None).await?;
+        Ok(revoked)
+    }
+
+    /// Add a single token to the revocation list until it would have expired
+    pub async fn revoke_token(&self, jti: &str, expires_at: DateTime<Utc>) -> ApiResult<()> {
+        self.put(&revoked_key(jti), &true, Some(expires_at)).await
+    }
+
+    /// Reject tokens that were revoked, belong to a dead session, or predate a user-wide revocation
+    pub async fn check(&self, claims: &Claims) -> ApiResult<()> {
+        if !claims.jti.is_empty() && self.get::<bool>(&revoked_key(&claims.jti)).await?.is_some() {
+            return Err(ApiError::auth("Token has been revoked"));
+        }
+
+        match &claims.sid {
+            Some(session_id) => {
+                if self.get_session(session_id).await?.is_none() {
+                    return Err(ApiError::auth("Session has been revoked"));
+                }
+            }
+            None => {
+                if let Some(cutoff) = self.get::<i64>(&cutoff_key(&claims.sub)).await? {
+                    if claims.iat as i64 <= cutoff {
+                        return Err(ApiError::auth("Token has been revoked"));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add a session to its user's index in one atomic step.
+    ///
+    /// The index has no TTL: sessions outlive their original expiry when
+    /// refreshed, and `list_sessions` prunes ids whose session record is gone.
+    async fn index_session(&self, user_id: &str, session_id: &str) -> ApiResult<()> {
+        let key = user_key(user_id);
+        match &self.backend {
+            Backend::Cache(cache) => {
+                let mut conn = cache.manager().lock().await;
+                let _: i64 = conn
+                    .sadd(&key, session_id)
+                    .await
+                    .map_err(|e| store_error(e.into()))?;
+                Ok(())
+            }
+            Backend::Memory(entries) => {
+                // The entry guard is held for the whole read-modify-write
+                let mut entry = entries
+                    .entry(key)
+                    .or_insert_with(|| (serde_json::Value::Array(Vec::new()), None));
+                let (stored, _) = entry.value_mut();
+                let mut ids: Vec<String> =
+                    serde_json::from_value(stored.clone()).map_err(|e| store_error(e.into()))?;
+                if !ids.iter().any(|id| id == session_id) {
+                    ids.push(session_id.to_string());
+                }
+                *stored = serde_json::to_value(ids).map_err(|e| store_error(e.into()))?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Session ids indexed for a user, live or not
+    async fn indexed_sessions(&self, user_id: &str) -> ApiResult<Vec<String>> {
+        match &self.backend {
+            Backend::Cache(cache) => {
+                let mut conn = cache.manager().lock().await;
+                conn.smembers(user_key(user_id))
+                    .await
+                    .map_err(|e| store_error(e.into()))
+            }
+            Backend::Memory(_) => Ok(self.get(&user_key(user_id)).await?.unwrap_or_default()),
+        }
+    }
+
+    /// Remove session ids from a user's index without touching other entries
+    async fn unindex_sessions(&self, user_id: &str, session_ids: &[String]) -> ApiResult<()> {
+        if session_ids.is_empty() {
+            return Ok(());
+        }
+        let key = user_key(user_id);
+        match &self.backend {
+            Backend::Cache(cache) => {
+                let mut conn = cache.manager().lock().await;
+                let _: i64 = conn
+                    .srem(&key, session_ids)
+                    .await
+                    .map_err(|e| store_error(e.into()))?;
+                Ok(())
+            }
+            Backend::Memory(entries) => {
+                let Some(mut entry) = entries.get_mut(&key) else {
+                    return Ok(());
+                };
+                let (stored, _) = entry.value_mut();
+                let mut ids: Vec<String> =
+                    serde_json::from_value(stored.clone()).map_err(|e| store_error(e.into()))?;
+                ids.retain(|id| !session_ids.contains(id));
+                *stored = serde_json::to_value(ids).map_err(|e| store_error(e.into()))?;
+                Ok(())
+            }
+        }
+    }
+
+    async fn put_session(&self, session: &SessionInfo) -> ApiResult<()> {
+        self.put(
+            &session_key(&session.session_id),
+            session,
+            Some(session.expires_at),
+        )
+        .await
+    }
+
+    async fn get<T: DeserializeOwned + Send + Sync>(&self, key: &str) -> ApiResult<Option<T>> {
+        match &self.backend {
+            Backend::Cache(cache) => cache.get::<T>(key).await.map_err(store_error),
+            Backend::Memory(entries) => {
+                let now = Utc::now();
+                // Copy out of the shard guard first: removing a key while its
+                // shard is still held deadlocks
+                let entry = entries.get(key).map(|entry| entry.value().clone());
+                let value = match entry {
+                    Some((value, expiry)) if expiry.map_or(true, |expiry| expiry > now) => value,
+                    Some(_) => {
+                        // Keep the entry if it was re-stored since the read
+                        entries.remove_if(key, |_, (_, expiry)| {
+                            expiry.is_some_and(|expiry| expiry <= now)
+                        });
+                        return Ok(None);
+                    }
+                    None => return Ok(None),
+                };
+                serde_json::from_value(value)
+                    .map(Some)
+                    .map_err(|e| store_error(e.into()))
+            }
+        }
+    }
+
+    async fn put<T: Serialize + Send + Sync>(
+        &self,
+        key: &str,
+        value: &T,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<()> {
+        match &self.backend {
+            Backend::Cache(cache) => {
+                let ttl = expires_at.map(ttl_until);
+                cache.set(key, value, ttl).await.map_err(store_error)
+            }
+            Backend::Memory(entries) => {
+                let value = serde_json::to_value(value).map_err(|e| store_error(e.into()))?;
+                entries.insert(key.to_string(), (value, expires_at));
+                Ok(())
+            }
+        }
+    }
+
+    async fn delete(&self, key: &str) -> ApiResult<()> {
+        match &self.backend {
+            Backend::Cache(cache) => cache.delete(key).await.map_err(store_error),
+            Backend::Memory(entries) => {
+                entries.remove(key);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Default for TokenStore {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+/// Convert a JWT `exp` claim to a timestamp
+pub fn expiry_of(claims: &Claims) -> DateTime<Utc> {
+    Utc.timestamp_opt(claims.exp as i64, 0)
+        .single()
+        .unwrap_or_else(Utc::now)
+}
+
+/// Redis TTL for a key that should live until `at`, at least one second
+fn ttl_until(at: DateTime<Utc>) -> Duration {
+    (at - Utc::now())
+        .to_std()
+        .unwrap_or(Duration::from_secs(1))
+        .max(Duration::from_secs(1))
+}
+
+fn session_key(session_id: &str) -> String {
+    format!("{}:session:{}", KEY_PREFIX, session_id)
+}
+
+fn user_key(user_id: &str) -> String {
+    format!("{}:user_sessions:{}", KEY_PREFIX, user_id)
+}
+
+fn revoked_key(jti: &str) -> String {
+    format!("{}:revoked:{}", KEY_PREFIX, jti)
+}
+
+fn cutoff_key(user_id: &str) -> String {
+    format!("{}:revoked_before:{}", KEY_PREFIX, user_id)
+}
+
+fn store_error(e: anyhow::Error) -> ApiError {
+    // Fail closed: a token that cannot be checked is not accepted
+    ApiError::internal(format!("Session store unavailable: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn expired_entries_read_as_missing_and_are_dropped() {
+        let store = TokenStore::in_memory();
+        let past = Utc::now() - chrono::Duration::seconds(5);
+        store.revoke_token("stale-jti", past).await.unwrap();
+        store
+            .create_session("stale-session", "user", "refresh-1", past)
+            .await
+            .unwrap();
+
+        assert!(store
+            .get::<bool>(&revoked_key("stale-jti"))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(store.get_session("stale-session").await.unwrap().is_none());
+        let Backend::Memory(entries) = &store.backend else {
+            unreachable!()
+        };
+        assert!(!entries.contains_key(&revoked_key("stale-jti")));
+        assert!(!entries.contains_key(&session_key("stale-session")));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_refreshes_with_one_token_rotate_once() {
+        let store = Arc::new(TokenStore::in_memory());
+        let expires_at = Utc::now() + chrono::Duration::hours(1);
+        for round in 0..50 {
+            let session_id = format!("session-{}", round);
+            store
+                .create_session(&session_id, "user", "refresh-0", expires_at)
+                .await
+                .unwrap();
+
+            let rotations: Vec<_> = (0..2)
+                .map(|attempt| {
+                    let store = Arc::clone(&store);
+                    let session_id = session_id.clone();
+                    tokio::spawn(async move {
+                        store
+                            .rotate_refresh(
+                                &session_id,
+                                "refresh-0",
+                                &format!("refresh-{}", attempt + 1),
+                                expires_at,
+                            )
+                            .await
+                    })
+                })
+                .collect();
+            let mut succeeded = 0;
+            for rotation in rotations {
+                if rotation.await.unwrap().is_ok() {
+                    succeeded += 1;
+                }
+            }
+
+            // The loser is a replay of the same token, which kills the session
+            assert_eq!(succeeded, 1, "round {}", round);
+            assert!(store.get_session(&session_id).await.unwrap().is_none());
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_logins_are_all_indexed() {
+        let store = Arc::new(TokenStore::in_memory());
+        let expires_at = Utc::now() + chrono::Duration::hours(1);
+        let logins: Vec<_> = (0..50)
+            .map(|login| {
+                let store = Arc::clone(&store);
+                tokio::spawn(async move {
+                    store
+                        .create_session(
+                            &format!("session-{}", login),
+                            "user",
+                            "refresh-0",
+                            expires_at,
+                        )
+                        .await
+                })
+            })
+            .collect();
+        for login in logins {
+            login.await.unwrap().unwrap();
+        }
+
+        assert_eq!(store.list_sessions("user").await.unwrap().len(), 50);
+        assert_eq!(store.revoke_user("user").await.unwrap(), 50);
+        assert!(store.list_sessions("user").await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,1018 @@
+//! Sandboxed WASM strategies
+//!
+//! Uploaded modules are checked against the host ABI, hashed and kept as numbered
+//! versions per strategy in `strategy_modules` (migration V007). A deployment runs one
+//! version for a set of accounts and symbols, one sandbox instance per account, inside
+//! per-account [`StrategyHost`]s fed from the event bus. Moving a deployment to another
+//! version swaps the executors in place, so candle, indicator and persisted sandbox
+//! state carry over. A strategy belongs to the user who uploaded its first version.
+//! Logs and signals of deployed strategies are streamed to WebSocket clients
+//! subscribed to the strategy's updates that have access to the instance's account.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use event_bus::{EventBusError, EventHandler, EventSender, MarketEvent, SignalEvent};
+use parking_lot::RwLock;
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, PgPool};
+use strategy_engine::{
+    HostedStrategyConfig, StrategyActivity, StrategyActivitySink, StrategyError, StrategyHost,
+    StrategyStateStore, SymbolFilter, WasmStrategy, WasmStrategyConfig, WasmStrategyModule,
+};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{
+    DeployStrategyRequest, DeploymentInstanceResponse, SandboxLimits, StrategyDeploymentResponse,
+    StrategyModuleResponse,
+};
+use crate::websocket::{StrategyActivityMessage, WebSocketManager};
+
+/// Largest module accepted for upload
+pub const MAX_MODULE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Market snapshots handed to each evaluation
+const SNAPSHOT_WINDOW: usize = 16;
+
+const MODULE_COLUMNS: &str =
+    "id, strategy_id, name, version, content_hash, abi_version, size_bytes, uploaded_by, \
+     uploaded_at";
+
+type DeploymentHost = StrategyHost<SNAPSHOT_WINDOW>;
+
+/// Hex SHA-256 of a module's bytes
+pub fn content_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Metadata of one uploaded module version
+#[derive(Debug, Clone, FromRow)]
+pub struct ModuleRecord {
+    pub id: Uuid,
+    pub strategy_id: Uuid,
+    pub name: String,
+    pub version: i32,
+    pub content_hash: String,
+    pub abi_version: i32,
+    pub size_bytes: i64,
+    /// User ID of the uploader
+    pub uploaded_by: String,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+impl ModuleRecord {
+    pub fn to_response(&self) -> StrategyModuleResponse {
+        StrategyModuleResponse {
+            id: self.id.to_string(),
+            strategy_id: self.strategy_id.to_string(),
+            name: self.name.clone(),
+            version: self.version,
+            content_hash: self.content_hash.clone(),
+            abi_version: self.abi_version,
+            size_bytes: self.size_bytes,
+            uploaded_at: self.uploaded_at,
+        }
+    }
+}
+
+/// Persistence for uploaded module versions
+#[async_trait]
+pub trait ModuleStore: Send + Sync {
+    /// Add a version; fails if the strategy already has its version number or content
+    async fn insert(&self, record: &ModuleRecord, bytes: &[u8]) -> ApiResult<()>;
+
+    /// One version with its bytes
+    async fn get(
+        &self,
+        strategy_id: Uuid,
+        version: i32,
+    ) -> ApiResult<Option<(ModuleRecord, Vec<u8>)>>;
+
+    /// Every version of a strategy, oldest first
+    async fn versions(&self, strategy_id: Uuid) -> ApiResult<Vec<ModuleRecord>>;
+}
+
+#[derive(FromRow)]
+struct ModuleRow {
+    #[sqlx(flatten)]
+    record: ModuleRecord,
+    wasm_bytes: Vec<u8>,
+}
+
+/// [`ModuleStore`] over the `strategy_modules` table
+pub struct PostgresModuleStore {
+    pool: PgPool,
+}
+
+impl PostgresModuleStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ModuleStore for PostgresModuleStore {
+    async fn insert(&self, record: &ModuleRecord, bytes: &[u8]) -> ApiResult<()> {
+        sqlx::query(
+            "INSERT INTO strategy_modules (id, strategy_id, name, version, content_hash, \
+             abi_version, size_bytes, wasm_bytes, uploaded_by, uploaded_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+        )
+        .bind(record.id)
+        .bind(record.strategy_id)
+        .bind(&record.name)
+        .bind(record.version)
+        .bind(&record.content_hash)
+        .bind(record.abi_version)
+        .bind(record.size_bytes)
+        .bind(bytes)
+        .bind(&record.uploaded_by)
+        .bind(record.uploaded_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ApiError::database(format!("Failed to store strategy module: {}", e)))?;
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        strategy_id: Uuid,
+        version: i32,
+    ) -> ApiResult<Option<(ModuleRecord, Vec<u8>)>> {
+        let row = sqlx::query_as::<_, ModuleRow>(&format!(
+            "SELECT {}, wasm_bytes FROM strategy_modules WHERE strategy_id = $1 AND version = $2",
+            MODULE_COLUMNS
+        ))
+        .bind(strategy_id)
+        .bind(version)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| ApiError::database(format!("Failed to load strategy module: {}", e)))?;
+        Ok(row.map(|row| (row.record, row.wasm_bytes)))
+    }
+
+    async fn versions(&self, strategy_id: Uuid) -> ApiResult<Vec<ModuleRecord>> {
+        sqlx::query_as::<_, ModuleRecord>(&format!(
+            "SELECT {} FROM strategy_modules WHERE strategy_id = $1 ORDER BY version",
+            MODULE_COLUMNS
+        ))
+        .bind(strategy_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| ApiError::database(format!("Failed to list strategy modules: {}", e)))
+    }
+}
+
+/// In-process [`ModuleStore`]; contents are lost on restart
+#[derive(Default)]
+pub struct InMemoryModuleStore {
+    modules: RwLock<Vec<(ModuleRecord, Vec<u8>)>>,
+}
+
+impl InMemoryModuleStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ModuleStore for InMemoryModuleStore {
+    async fn insert(&self, record: &ModuleRecord, bytes: &[u8]) -> ApiResult<()> {
+        let mut modules = self.modules.write();
+        if modules.iter().any(|(existing, _)| {
+            existing.strategy_id == record.strategy_id
+                && (existing.version == record.version
+                    || existing.content_hash == record.content_hash)
+        }) {
+            return Err(ApiError::database(format!(
+                "Strategy {} already has module version {}",
+                record.strategy_id, record.version
+            )));
+        }
+        modules.push((record.clone(), bytes.to_vec()));
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        strategy_id: Uuid,
+        version: i32,
+    ) -> ApiResult<Option<(ModuleRecord, Vec<u8>)>> {
+        Ok(self
+            .modules
+            .read()
+            .iter()
+            .find(|(record, _)| record.strategy_id == strategy_id && record.version == version)
+            .cloned())
+    }
+
+    async fn versions(&self, strategy_id: Uuid) -> ApiResult<Vec<ModuleRecord>> {
+        let mut versions: Vec<ModuleRecord> = self
+            .modules
+            .read()
+            .iter()
+            .filter(|(record, _)| record.strategy_id == strategy_id)
+            .map(|(record, _)| record.clone())
+            .collect();
+        versions.sort_by_key(|record| record.version);
+        Ok(versions)
+    }
+}
+
+/// Streams the logs and signals of deployed strategies to WebSocket subscribers
+///
+/// Each message carries the instance's account; connections only receive it when
+/// their claims grant access to that account.
+pub struct WebSocketActivitySink {
+    websocket: Arc<WebSocketManager>,
+}
+
+impl WebSocketActivitySink {
+    pub fn new(websocket: Arc<WebSocketManager>) -> Self {
+        Self { websocket }
+    }
+}
+
+impl StrategyActivitySink for WebSocketActivitySink {
+    fn record(&self, activity: &StrategyActivity) {
+        self.websocket
+            .broadcast_strategy_activity(StrategyActivityMessage {
+                strategy_id: activity.strategy_id.to_string(),
+                strategy_name: activity.strategy_name.clone(),
+                account_id: activity.account_id.clone(),
+                logs: activity.logs.clone(),
+                signals: activity.signals.clone(),
+                fuel_consumed: activity.metrics.fuel_consumed,
+                timestamp: activity.timestamp,
+            });
+    }
+}
+
+struct Deployment {
+    module: ModuleRecord,
+    compiled: Arc<WasmStrategyModule>,
+    symbols: Vec<String>,
+    account_ids: Vec<String>,
+    limits: WasmStrategyConfig,
+    deployed_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+/// Uploads, runs and upgrades sandboxed strategies
+///
+/// Deployed strategies are registered in the hosts under their API strategy ID, so the
+/// signals they publish carry it. Market events reach them through [`Self::dispatch`]
+/// or the `EventHandler<MarketEvent>` implementation.
+pub struct WasmStrategyDeployments {
+    modules: Arc<dyn ModuleStore>,
+    state_store: Arc<dyn StrategyStateStore>,
+    activity_sink: Arc<dyn StrategyActivitySink>,
+    signal_sender: EventSender<SignalEvent>,
+    max_limits: WasmStrategyConfig,
+    hosts: RwLock<HashMap<String, Arc<DeploymentHost>>>,
+    // Held across module loads so deploys and swaps of one strategy cannot interleave
+    deployments: Mutex<HashMap<Uuid, Deployment>>,
+}
+
+impl WasmStrategyDeployments {
+    pub fn new(
+        modules: Arc<dyn ModuleStore>,
+        state_store: Arc<dyn StrategyStateStore>,
+        signal_sender: EventSender<SignalEvent>,
+        activity_sink: Arc<dyn StrategyActivitySink>,
+    ) -> Self {
+        Self {
+            modules,
+            state_store,
+            activity_sink,
+            signal_sender,
+            max_limits: WasmStrategyConfig::default(),
+            hosts: RwLock::new(HashMap::new()),
+            deployments: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Upper bounds for requested sandbox limits, also used when a limit is omitted
+    pub fn with_max_limits(mut self, limits: WasmStrategyConfig) -> Self {
+        self.max_limits = limits;
+        self
+    }
+
+    /// Validate and store a module as the strategy's next version.
+    ///
+    /// Uploading bytes identical to an existing version returns that version; the flag
+    /// is `true` only when a new version was stored.
+    pub async fn upload(
+        &self,
+        strategy_id: Uuid,
+        name: &str,
+        bytes: Vec<u8>,
+        uploaded_by: &str,
+    ) -> ApiResult<(ModuleRecord, bool)> {
+        let name = name.trim();
+        if name.is_empty() || name.len() > 100 {
+            return Err(module_error("Module name must be 1 to 100 characters"));
+        }
+        if bytes.is_empty() {
+            return Err(module_error("Module is empty"));
+        }
+        if bytes.len() > MAX_MODULE_BYTES {
+            return Err(module_error(format!(
+                "Module exceeds {} bytes",
+                MAX_MODULE_BYTES
+            )));
+        }
+
+        let hash = content_hash(&bytes);
+        let versions = self.modules.versions(strategy_id).await?;
+        if let Some(existing) = versions.iter().find(|record| record.content_hash == hash) {
+            return Ok((existing.clone(), false));
+        }
+
+        let limits = self.max_limits.clone();
+        let size_bytes = bytes.len() as i64;
+        let (bytes, abi_version) = tokio::task::spawn_blocking(move || {
+            let module = WasmStrategyModule::from_bytes(&bytes, &limits)?;
+            let abi_version = module.instantiate(limits)?.abi_version();
+            Ok::<_, StrategyError>((bytes, abi_version))
+        })
+        .await
+        .map_err(|e| ApiError::internal(format!("Module validation task failed: {}", e)))?
+        .map_err(|e| module_error(format!("Invalid strategy module: {}", e)))?;
+
+        let record = ModuleRecord {
+            id: Uuid::new_v4(),
+            strategy_id,
+            name: name.to_string(),
+            version: versions.last().map_or(1, |latest| latest.version + 1),
+            content_hash: hash,
+            abi_version: abi_version as i32,
+            size_bytes,
+            uploaded_by: uploaded_by.to_string(),
+            uploaded_at: Utc::now(),
+        };
+        self.modules.insert(&record, &bytes).await?;
+        info!(
+            strategy_id = %strategy_id,
+            version = record.version,
+            hash = %record.content_hash,
+            "strategy module uploaded"
+        );
+        Ok((record, true))
+    }
+
+    pub async fn versions(&self, strategy_id: Uuid) -> ApiResult<Vec<ModuleRecord>> {
+        self.modules.versions(strategy_id).await
+    }
+
+    /// User who uploaded the strategy's first version, if it has any
+    pub async fn owner(&self, strategy_id: Uuid) -> ApiResult<Option<String>> {
+        let versions = self.modules.versions(strategy_id).await?;
+        Ok(versions.into_iter().next().map(|record| record.uploaded_by))
+    }
+
+    /// Start a module version for each requested account
+    pub async fn deploy(
+        &self,
+        strategy_id: Uuid,
+        request: DeployStrategyRequest,
+    ) -> ApiResult<StrategyDeploymentResponse> {
+        request.validate().map_err(|message| ApiError::Validation {
+            message,
+            field: Some("deployment".to_string()),
+        })?;
+
+        let mut deployments = self.deployments.lock().await;
+        if deployments.contains_key(&strategy_id) {
+            return Err(ApiError::bad_request(format!(
+                "Strategy {} is already deployed; switch its version instead",
+                strategy_id
+            )));
+        }
+
+        let limits = self.resolve_limits(&request.limits)?;
+        let (module, compiled) = self
+            .load(strategy_id, request.version, limits.clone())
+            .await?;
+
+        let mut started = Vec::new();
+        for account_id in &request.account_ids {
+            if let Err(e) = self.start_instance(
+                strategy_id,
+                &module,
+                &compiled,
+                &limits,
+                account_id,
+                &request.symbols,
+            ) {
+                self.remove_instances(strategy_id, &started);
+                return Err(e);
+            }
+            started.push(account_id.clone());
+        }
+
+        let now = Utc::now();
+        let deployment = Deployment {
+            module,
+            compiled,
+            symbols: request.symbols,
+            account_ids: request.account_ids,
+            limits,
+            deployed_at: now,
+            updated_at: now,
+        };
+        info!(
+            strategy_id = %strategy_id,
+            version = deployment.module.version,
+            accounts = deployment.account_ids.len(),
+            "strategy deployed"
+        );
+        let response = self.response(strategy_id, &deployment);
+        deployments.insert(strategy_id, deployment);
+        Ok(response)
+    }
+
+    /// Move every instance of a deployment to another module version.
+    ///
+    /// All replacement instances are created before any is swapped in, and a swap that
+    /// fails part way restores the previous version on the accounts already switched.
+    pub async fn swap_version(
+        &self,
+        strategy_id: Uuid,
+        version: i32,
+    ) -> ApiResult<StrategyDeploymentResponse> {
+        let mut deployments = self.deployments.lock().await;
+        let deployment = deployments.get_mut(&strategy_id).ok_or_else(|| {
+            ApiError::not_found(format!("Deployment of strategy {}", strategy_id))
+        })?;
+        if deployment.module.version == version {
+            return Ok(self.response(strategy_id, deployment));
+        }
+
+        let (module, compiled) = self
+            .load(strategy_id, Some(version), deployment.limits.clone())
+            .await?;
+        let mut replacements = Vec::with_capacity(deployment.account_ids.len());
+        for account_id in &deployment.account_ids {
+            let strategy = self.instance(
+                strategy_id,
+                account_id,
+                &module,
+                &compiled,
+                &deployment.limits,
+            )?;
+            replacements.push((account_id.clone(), strategy));
+        }
+
+        let mut swapped: Vec<String> = Vec::new();
+        for (account_id, strategy) in replacements {
+            if let Err(e) = self.host(&account_id).swap_strategy(strategy_id, strategy) {
+                warn!(
+                    strategy_id = %strategy_id,
+                    account_id = %account_id,
+                    "strategy version swap failed, restoring version {}: {}",
+                    deployment.module.version,
+                    e
+                );
+                for account_id in &swapped {
+                    let restored = self
+                        .instance(
+                            strategy_id,
+                            account_id,
+                            &deployment.module,
+                            &deployment.compiled,
+                            &deployment.limits,
+                        )
+                        .and_then(|previous| {
+                            self.host(account_id)
+                                .swap_strategy(strategy_id, previous)
+                                .map_err(|e| ApiError::Strategy {
+                                    message: e.to_string(),
+                                })
+                        });
+                    if let Err(e) = restored {
+                        warn!(account_id = %account_id, "failed to restore strategy version: {}", e);
+                    }
+                }
+                return Err(ApiError::Strategy {
+                    message: format!("Failed to switch to version {}: {}", version, e),
+                });
+            }
+            swapped.push(account_id);
+        }
+
+        info!(
+            strategy_id = %strategy_id,
+            from = deployment.module.version,
+            to = module.version,
+            "strategy version swapped"
+        );
+        deployment.module = module;
+        deployment.compiled = compiled;
+        deployment.updated_at = Utc::now();
+        Ok(self.response(strategy_id, deployment))
+    }
+
+    /// Stop every instance of a deployment, returning its final state
+    pub async fn stop(&self, strategy_id: Uuid) -> ApiResult<StrategyDeploymentResponse> {
+        let mut deployments = self.deployments.lock().await;
+        let deployment = deployments.remove(&strategy_id).ok_or_else(|| {
+            ApiError::not_found(format!("Deployment of strategy {}", strategy_id))
+        })?;
+        for account_id in &deployment.account_ids {
+            if let Err(e) = self.host(account_id).stop(strategy_id) {
+                warn!(account_id = %account_id, "failed to stop strategy instance: {}", e);
+            }
+        }
+        let response = self.response(strategy_id, &deployment);
+        self.remove_instances(strategy_id, &deployment.account_ids);
+        info!(strategy_id = %strategy_id, "strategy deployment stopped");
+        Ok(response)
+    }
+
+    /// Accounts a running deployment trades for
+    pub async fn deployed_accounts(&self, strategy_id: Uuid) -> Option<Vec<String>> {
+        let deployments = self.deployments.lock().await;
+        deployments
+            .get(&strategy_id)
+            .map(|deployment| deployment.account_ids.clone())
+    }
+
+    pub async fn deployment(&self, strategy_id: Uuid) -> Option<StrategyDeploymentResponse> {
+        let deployments = self.deployments.lock().await;
+        deployments
+            .get(&strategy_id)
+            .map(|deployment| self.response(strategy_id, deployment))
+    }
+
+    /// Deliver a market event to every deployed instance whose symbols match it
+    pub fn dispatch(&self, event: &MarketEvent) {
+        let hosts: Vec<Arc<DeploymentHost>> = self.hosts.read().values().cloned().collect();
+        for host in hosts {
+            host.dispatch(event);
+        }
+    }
+
+    fn resolve_limits(&self, requested: &SandboxLimits) -> ApiResult<WasmStrategyConfig> {
+        fn pick<T: Copy + PartialOrd + Default + std::fmt::Display>(
+            field: &str,
+            requested: Option<T>,
+            max: T,
+        ) -> ApiResult<T> {
+            match requested {
+                None => Ok(max),
+                Some(value) if value > T::default() && value <= max => Ok(value),
+                Some(_) => Err(ApiError::Validation {
+                    message: format!("{} must be between 1 and {}", field, max),
+                    field: Some(format!("limits.{}", field)),
+                }),
+            }
+        }
+
+        Ok(WasmStrategyConfig {
+            memory_limit: pick(
+                "memory_limit_bytes",
+                requested.memory_limit_bytes,
+                self.max_limits.memory_limit,
+            )?,
+            fuel_limit: pick(
+                "fuel_limit",
+                requested.fuel_limit,
+                self.max_limits.fuel_limit,
+            )?,
+            state_limit: pick(
+                "state_limit_bytes",
+                requested.state_limit_bytes,
+                self.max_limits.state_limit,
+            )?,
+        })
+    }
+
+    /// Fetch a version, the latest when `None`, and compile it under `limits`
+    async fn load(
+        &self,
+        strategy_id: Uuid,
+        version: Option<i32>,
+        limits: WasmStrategyConfig,
+    ) -> ApiResult<(ModuleRecord, Arc<WasmStrategyModule>)> {
+        let version = match version {
+            Some(version) => version,
+            None => self
+                .modules
+                .versions(strategy_id)
+                .await?
+                .last()
+                .map(|record| record.version)
+                .ok_or_else(|| {
+                    ApiError::not_found(format!("Modules of strategy {}", strategy_id))
+                })?,
+        };
+        let (module, bytes) = self
+            .modules
+            .get(strategy_id, version)
+            .await?
+            .ok_or_else(|| {
+                ApiError::not_found(format!(
+                    "Module version {} of strategy {}",
+                    version, strategy_id
+                ))
+            })?;
+
+        let compiled =
+            tokio::task::spawn_blocking(move || WasmStrategyModule::from_bytes(&bytes, &limits))
+                .await
+                .map_err(|e| ApiError::internal(format!("Module compilation task failed: {}", e)))?
+                .map_err(|e| ApiError::Strategy {
+                    message: format!("Failed to compile module version {}: {}", version, e),
+                })?;
+        Ok((module, Arc::new(compiled)))
+    }
+
+    fn instance(
+        &self,
+        strategy_id: Uuid,
+        account_id: &str,
+        module: &ModuleRecord,
+        compiled: &WasmStrategyModule,
+        limits: &WasmStrategyConfig,
+    ) -> ApiResult<WasmStrategy> {
+        let instance = compiled
+            .instantiate(limits.clone())
+            .map_err(|e| ApiError::Strategy {
+                message: format!(
+                    "Failed to instantiate module version {}: {}",
+                    module.version, e
+                ),
+            })?;
+        // Keyed without the version so an upgrade picks up where the previous one left off
+        Ok(WasmStrategy::new(module.name.clone(), instance)
+            .with_state_store(self.state_store.clone())
+            .with_state_key(format!("{}:{}", strategy_id, account_id)))
+    }
+
+    fn start_instance(
+        &self,
+        strategy_id: Uuid,
+        module: &ModuleRecord,
+        compiled: &WasmStrategyModule,
+        limits: &WasmStrategyConfig,
+        account_id: &str,
+        symbols: &[String],
+    ) -> ApiResult<()> {
+        let strategy = self.instance(strategy_id, account_id, module, compiled, limits)?;
+        let host = self.host(account_id);
+        let config = HostedStrategyConfig::default()
+            .with_symbols(SymbolFilter::only(symbols.iter().cloned()));
+        host.register_as(strategy_id, strategy, config)
+            .and_then(|()| host.start(strategy_id))
+            .map_err(|e| ApiError::Strategy {
+                message: format!("Failed to start strategy for account {}: {}", account_id, e),
+            })
+    }
+
+    fn remove_instances(&self, strategy_id: Uuid, account_ids: &[String]) {
+        for account_id in account_ids {
+            let _ = self.host(account_id).remove(strategy_id);
+        }
+    }
+
+    fn host(&self, account_id: &str) -> Arc<DeploymentHost> {
+        if let Some(host) = self.hosts.read().get(account_id) {
+            return host.clone();
+        }
+        self.hosts
+            .write()
+            .entry(account_id.to_string())
+            .or_insert_with(|| {
+                Arc::new(
+                    StrategyHost::new(self.signal_sender.clone(), account_id.to_string())
+                        .with_activity_sink(self.activity_sink.clone()),
+                )
+            })
+            .clone()
+    }
+
+    fn response(&self, strategy_id: Uuid, deployment: &Deployment) -> StrategyDeploymentResponse {
+        let instances = deployment
+            .account_ids
+            .iter()
+            .filter_map(|account_id| {
+                let stats = self.host(account_id).stats(strategy_id)?;
+                Some(DeploymentInstanceResponse {
+                    account_id: account_id.clone(),
+                    state: stats.state,
+                    events: stats.events,
+                    evaluations: stats.evaluations,
+                    signals: stats.signals,
+                    errors: stats.errors,
+                })
+            })
+            .collect();
+
+        StrategyDeploymentResponse {
+            strategy_id: strategy_id.to_string(),
+            module: deployment.module.to_response(),
+            symbols: deployment.symbols.clone(),
+            limits: SandboxLimits {
+                memory_limit_bytes: Some(deployment.limits.memory_limit),
+                fuel_limit: Some(deployment.limits.fuel_limit),
+                state_limit_bytes: Some(deployment.limits.state_limit),
+            },
+            instances,
+            deployed_at: deployment.deployed_at,
+            updated_at: deployment.updated_at,
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler<MarketEvent> for WasmStrategyDeployments {
+    async fn handle(&self, event: MarketEvent) -> Result<(), EventBusError> {
+        self.dispatch(&event);
+        Ok(())
+    }
+}
+
+fn module_error(message: impl Into<String>) -> ApiError {
+    ApiError::Validation {
+        message: message.into(),
+        field: Some("module".to_string()),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use event_bus::{
+        EventBusBuilder, EventDispatcherBuilder, EventMetadata, EventSource, MarketPayload,
+        Priority, PublishMode,
+    };
+    use exchange_connectors::{MarketTick, TradingPair};
+    use rust_decimal::Decimal;
+    use strategy_engine::{InMemoryStateStore, StrategyState};
+
+    const BUY_INTENT: &str = r#"{"symbol":"BTC-USD","side":"Buy","quantity":"1"}"#;
+
+    /// ABI v2 module that logs `tag` and emits a market buy on every evaluation
+    pub(crate) fn module(tag: &str) -> Vec<u8> {
+        wat::parse_str(format!(
+            r#"(module
+  (import "host" "log" (func $log (param i32 i32)))
+  (import "host" "emit_order" (func $emit_order (param i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 4096))
+  (data (i32.const 0) "{tag}")
+  (data (i32.const 64) "{intent}")
+  (func (export "abi_version") (result i32) (i32.const 2))
+  (func (export "alloc") (param $size i32) (result i32)
+        (local $ptr i32)
+        (local.set $ptr (global.get $next))
+        (global.set $next (i32.add (local.get $ptr) (local.get $size)))
+        (local.get $ptr))
+  (func (export "evaluate") (param $ctx_ptr i32) (param $ctx_len i32) (result i32)
+        (call $log (i32.const 0) (i32.const {tag_len}))
+        (drop (call $emit_order (i32.const 64) (i32.const {intent_len})))
+        (i32.const 0)))"#,
+            intent = BUY_INTENT.replace('"', "\\\""),
+            intent_len = BUY_INTENT.len(),
+            tag_len = tag.len(),
+        ))
+        .expect("valid test module")
+    }
+
+    #[derive(Default)]
+    pub(crate) struct RecordingSink {
+        activity: parking_lot::Mutex<Vec<StrategyActivity>>,
+    }
+
+    impl StrategyActivitySink for RecordingSink {
+        fn record(&self, activity: &StrategyActivity) {
+            self.activity.lock().push(activity.clone());
+        }
+    }
+
+    fn tick(symbol: &str) -> MarketEvent {
+        let (base, quote) = symbol.split_once('-').unwrap();
+        MarketEvent::new(
+            EventMetadata::new(EventSource::new("test"), Priority::Normal),
+            MarketPayload::Tick {
+                tick: MarketTick {
+                    symbol: symbol.to_string(),
+                    bid: Decimal::from(99),
+                    ask: Decimal::from(101),
+                    last: Decimal::from(100),
+                    volume_24h: Decimal::ZERO,
+                    timestamp: Utc::now(),
+                },
+                pair: TradingPair {
+                    base: base.to_string(),
+                    quote: quote.to_string(),
+                    symbol: symbol.to_string(),
+                },
+            },
+        )
+    }
+
+    pub(crate) fn deploy_request(version: Option<i32>) -> DeployStrategyRequest {
+        DeployStrategyRequest {
+            version,
+            symbols: vec!["BTC-USD".to_string()],
+            account_ids: vec!["acct-1".to_string(), "acct-2".to_string()],
+            limits: SandboxLimits {
+                fuel_limit: Some(100_000),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn upload_versions_modules_by_content_hash() {
+        let bus = EventBusBuilder::default().build();
+        let deployments = WasmStrategyDeployments::new(
+            Arc::new(InMemoryModuleStore::new()),
+            Arc::new(InMemoryStateStore::new()),
+            bus.signal_sender(),
+            Arc::new(RecordingSink::default()),
+        );
+        let id = Uuid::new_v4();
+
+        let (first, created) = deployments
+            .upload(id, "ping", module("v1"), "alice")
+            .await
+            .unwrap();
+        assert!(created);
+        assert_eq!(first.version, 1);
+        assert_eq!(first.abi_version, 2);
+        assert_eq!(first.content_hash, content_hash(&module("v1")));
+
+        let (again, created) = deployments
+            .upload(id, "ping", module("v1"), "alice")
+            .await
+            .unwrap();
+        assert!(!created);
+        assert_eq!(again.id, first.id);
+
+        let (second, _) = deployments
+            .upload(id, "ping", module("v2"), "alice")
+            .await
+            .unwrap();
+        assert_eq!(second.version, 2);
+        assert!(matches!(
+            deployments
+                .upload(id, "ping", b"not wasm".to_vec(), "alice")
+                .await,
+            Err(ApiError::Validation { .. })
+        ));
+        assert_eq!(deployments.versions(id).await.unwrap().len(), 2);
+
+        deployments
+            .upload(id, "ping", module("v3"), "bob")
+            .await
+            .unwrap();
+        assert_eq!(
+            deployments.owner(id).await.unwrap().as_deref(),
+            Some("alice")
+        );
+        assert!(deployments.owner(Uuid::new_v4()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn deployment_runs_per_account_and_swaps_versions_in_place() {
+        let bus = EventBusBuilder::default().build();
+        let receiver = bus.signal_receiver();
+        let sink = Arc::new(RecordingSink::default());
+        let deployments = WasmStrategyDeployments::new(
+            Arc::new(InMemoryModuleStore::new()),
+            Arc::new(InMemoryStateStore::new()),
+            bus.signal_sender(),
+            sink.clone(),
+        );
+        let id = Uuid::new_v4();
+        deployments
+            .upload(id, "ping", module("v1"), "alice")
+            .await
+            .unwrap();
+        deployments
+            .upload(id, "ping", module("v2"), "alice")
+            .await
+            .unwrap();
+
+        let mut too_hungry = deploy_request(None);
+        too_hungry.limits.fuel_limit = Some(u64::MAX);
+        assert!(matches!(
+            deployments.deploy(id, too_hungry).await,
+            Err(ApiError::Validation { .. })
+        ));
+
+        let deployment = deployments
+            .deploy(id, deploy_request(Some(1)))
+            .await
+            .unwrap();
+        assert_eq!(deployment.module.version, 1);
+        assert_eq!(deployment.instances.len(), 2);
+        assert_eq!(
+            deployments.deployed_accounts(id).await.unwrap(),
+            vec!["acct-1", "acct-2"]
+        );
+        assert!(deployment
+            .instances
+            .iter()
+            .all(|instance| instance.state == StrategyState::Running));
+        assert!(deployments.deploy(id, deploy_request(None)).await.is_err());
+
+        deployments.dispatch(&tick("BTC-USD"));
+        deployments.dispatch(&tick("ETH-USD"));
+        let mut accounts: Vec<String> = (0..2)
+            .map(|_| {
+                let signal = receiver.try_recv().expect("one signal per account");
+                assert_eq!(signal.payload().strategy_id, id);
+                signal.payload().account_id.clone()
+            })
+            .collect();
+        accounts.sort();
+        assert_eq!(accounts, vec!["acct-1", "acct-2"]);
+        assert!(receiver.try_recv().is_err());
+
+        let swapped = deployments.swap_version(id, 2).await.unwrap();
+        assert_eq!(swapped.module.version, 2);
+        assert!(swapped
+            .instances
+            .iter()
+            .all(|instance| instance.evaluations == 1));
+        deployments.dispatch(&tick("BTC-USD"));
+
+        let logs: Vec<String> = sink
+            .activity
+            .lock()
+            .iter()
+            .map(|activity| activity.logs.concat())
+            .collect();
+        assert_eq!(logs, vec!["v1", "v1", "v2", "v2"]);
+        assert!(matches!(
+            deployments.swap_version(id, 3).await,
+            Err(ApiError::NotFound { .. })
+        ));
+
+        let stopped = deployments.stop(id).await.unwrap();
+        assert!(stopped
+            .instances
+            .iter()
+            .all(|instance| instance.state == StrategyState::Stopped && instance.signals == 2));
+        assert!(deployments.deployment(id).await.is_none());
+        deployments.dispatch(&tick("BTC-USD"));
+        assert_eq!(sink.activity.lock().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn deployments_evaluate_market_events_from_the_dispatcher() {
+        let bus = EventBusBuilder::default().build();
+        let sink = Arc::new(RecordingSink::default());
+        let deployments = Arc::new(WasmStrategyDeployments::new(
+            Arc::new(InMemoryModuleStore::new()),
+            Arc::new(InMemoryStateStore::new()),
+            bus.signal_sender(),
+            sink.clone(),
+        ));
+        let id = Uuid::new_v4();
+        deployments
+            .upload(id, "ping", module("v1"), "alice")
+            .await
+            .unwrap();
+        deployments.deploy(id, deploy_request(None)).await.unwrap();
+
+        let dispatcher = EventDispatcherBuilder::new(&bus)
+            .on_market(deployments.clone())
+            .build();
+        let dispatcher_task = tokio::spawn(dispatcher.run());
+        bus.market_sender()
+            .publish(tick("BTC-USD"), PublishMode::Blocking)
+            .unwrap();
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while sink.activity.lock().len() < 2 {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("both deployed instances evaluate the published tick");
+        let mut accounts: Vec<String> = sink
+            .activity
+            .lock()
+            .iter()
+            .map(|activity| {
+                assert_eq!(activity.logs.concat(), "v1");
+                assert_eq!(activity.signals.len(), 1);
+                activity.account_id.clone()
+            })
+            .collect();
+        accounts.sort();
+        assert_eq!(accounts, vec!["acct-1", "acct-2"]);
+
+        dispatcher_task.abort();
+    }
+}
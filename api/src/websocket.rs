@@ -3,14 +3,23 @@
 //! This module provides WebSocket functionality for real-time streaming of market data,
 //! trade updates, portfolio changes, and strategy execution events. It supports
 //! dynamic subscription management and efficient broadcasting to multiple clients.
+//!
+//! Connections may authenticate with an access token, from the `access_token` cookie
+//! or a `token` query parameter since browsers cannot set headers on WebSocket
+//! requests. Strategy activity is only delivered to connections whose claims grant
+//! access to the account it belongs to; the token is checked against the session
+//! store again before each such message, so logout and session revocation cut an
+//! open connection off.
 
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        Query, State,
     },
     response::{Html, IntoResponse},
 };
+use axum_extra::extract::CookieJar;
+use event_bus::SignalEventPayload;
 use futures::{sink::SinkExt, stream::StreamExt};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc, time::Duration};
@@ -22,8 +31,10 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::{
+    auth::{AuthMiddleware, Claims},
     error::ApiResult,
     models::{MarketDataResponse, PortfolioResponse, StrategyExecutionResponse, TradeResponse},
+    sessions::TokenStore,
     AppState,
 };
 
@@ -38,6 +49,8 @@ pub struct WebSocketManager {
     portfolio_updates_tx: broadcast::Sender<PortfolioUpdateMessage>,
     /// Broadcast sender for strategy execution updates
     strategy_updates_tx: broadcast::Sender<StrategyUpdateMessage>,
+    /// Broadcast sender for logs and signals of deployed strategies
+    strategy_activity_tx: broadcast::Sender<StrategyActivityMessage>,
     /// Broadcast sender for intel stream updates
     intel_updates_tx: broadcast::Sender<IntelUpdateMessage>,
     /// Active connections with their subscriptions
@@ -110,6 +123,16 @@ pub enum WebSocketMessage {
         status: String,
         timestamp: chrono::DateTime<chrono::Utc>,
     },
+    /// Logs and signals from one evaluation of a deployed strategy
+    StrategyActivity {
+        strategy_id: String,
+        strategy_name: String,
+        account_id: String,
+        logs: Vec<String>,
+        signals: Vec<SignalEventPayload>,
+        fuel_consumed: u64,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
     /// Intel stream update
     IntelUpdate {
         item: crate::handlers::intel::IntelItem,
@@ -131,6 +154,13 @@ pub enum WebSocketMessage {
     },
 }
 
+/// Query parameters of the WebSocket upgrade request
+#[derive(Debug, Default, Deserialize)]
+pub struct WebSocketParams {
+    /// Access token, for clients that cannot send the `access_token` cookie
+    pub token: Option<String>,
+}
+
 /// Client messages sent to server
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -176,6 +206,17 @@ pub struct StrategyUpdateMessage {
     pub status: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyActivityMessage {
+    pub strategy_id: String,
+    pub strategy_name: String,
+    pub account_id: String,
+    pub logs: Vec<String>,
+    pub signals: Vec<SignalEventPayload>,
+    pub fuel_consumed: u64,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IntelUpdateMessage {
     pub item: crate::handlers::intel::IntelItem,
@@ -196,6 +237,7 @@ impl WebSocketManager {
         let (trade_updates_tx, _) = broadcast::channel(1000);
         let (portfolio_updates_tx, _) = broadcast::channel(1000);
         let (strategy_updates_tx, _) = broadcast::channel(1000);
+        let (strategy_activity_tx, _) = broadcast::channel(1000);
         let (intel_updates_tx, _) = broadcast::channel(1000);
 
         let market_data_stream = Arc::new(RwLock::new(MarketDataStream {
@@ -211,6 +253,7 @@ impl WebSocketManager {
             connections: Arc::new(RwLock::new(HashMap::new())),
             market_data_stream,
             strategy_updates_tx,
+            strategy_activity_tx,
             intel_updates_tx,
         }
     }
@@ -341,6 +384,12 @@ impl WebSocketManager {
         Ok(())
     }
 
+    /// Broadcast strategy logs and signals to clients subscribed to the strategy
+    pub fn broadcast_strategy_activity(&self, message: StrategyActivityMessage) {
+        // Sending only fails when nobody is subscribed
+        let _ = self.strategy_activity_tx.send(message);
+    }
+
     /// Broadcast intel stream update to all subscribed clients
     pub async fn broadcast_intel_update(
         &self,
@@ -526,8 +575,11 @@ impl WebSocketManager {
         ws: WebSocketUpgrade,
         State(state): State<Arc<AppState>>,
         State(ws_manager): State<Arc<WebSocketManager>>,
+        cookie_jar: CookieJar,
+        Query(params): Query<WebSocketParams>,
     ) -> impl IntoResponse {
-        ws.on_upgrade(|socket| process_socket(socket, state, ws_manager))
+        let auth = connection_auth(&state, &cookie_jar, params).await;
+        ws.on_upgrade(|socket| process_socket(socket, state, ws_manager, auth))
     }
 
     /// Get WebSocket test page
@@ -540,9 +592,59 @@ impl WebSocketManager {
 pub async fn handle_socket(
     ws: WebSocketUpgrade,
     State(state): State<Arc<AppState>>,
+    cookie_jar: CookieJar,
+    Query(params): Query<WebSocketParams>,
 ) -> impl IntoResponse {
     let ws_manager = state.websocket_manager.clone();
-    ws.on_upgrade(move |socket| process_socket(socket, state, ws_manager))
+    let auth = connection_auth(&state, &cookie_jar, params).await;
+    ws.on_upgrade(move |socket| process_socket(socket, state, ws_manager, auth))
+}
+
+/// Access token a connection authenticated with, and the claims it last carried
+struct ConnectionAuth {
+    token: String,
+    claims: Claims,
+}
+
+/// Authenticate the connecting user; missing or invalid tokens connect anonymously
+async fn connection_auth(
+    state: &AppState,
+    cookie_jar: &CookieJar,
+    params: WebSocketParams,
+) -> Option<ConnectionAuth> {
+    let token = params.token.or_else(|| {
+        cookie_jar
+            .get("access_token")
+            .map(|cookie| cookie.value().to_string())
+    })?;
+    match AuthMiddleware::authorize_token(&state.token_store, &token).await {
+        Ok(claims) => Some(ConnectionAuth { token, claims }),
+        Err(e) => {
+            warn!("WebSocket token rejected, connecting anonymously: {}", e);
+            None
+        }
+    }
+}
+
+/// Recheck a connection's token; an expired or revoked one leaves it anonymous
+async fn still_authorized(store: &TokenStore, auth: &mut Option<ConnectionAuth>) -> bool {
+    let Some(current) = auth.as_mut() else {
+        return false;
+    };
+    match AuthMiddleware::authorize_token(store, &current.token).await {
+        Ok(claims) => {
+            current.claims = claims;
+            true
+        }
+        Err(e) => {
+            info!(
+                "WebSocket session of user {} ended, continuing anonymously: {}",
+                current.claims.sub, e
+            );
+            *auth = None;
+            false
+        }
+    }
 }
 
 /// Process WebSocket connection
@@ -551,6 +653,7 @@ async fn process_socket(
     socket: WebSocket,
     app_state: Arc<AppState>,
     ws_manager: Arc<WebSocketManager>,
+    mut auth: Option<ConnectionAuth>,
 ) {
     let connection_id = Uuid::new_v4().to_string();
     let client_addr = "unknown".to_string(); // In real impl, get from request
@@ -566,6 +669,7 @@ async fn process_socket(
     let mut trade_updates_rx = ws_manager.trade_updates_tx.subscribe();
     let mut portfolio_updates_rx = ws_manager.portfolio_updates_tx.subscribe();
     let mut strategy_updates_rx = ws_manager.strategy_updates_tx.subscribe();
+    let mut strategy_activity_rx = ws_manager.strategy_activity_tx.subscribe();
     let mut intel_updates_rx = ws_manager.intel_updates_tx.subscribe();
 
     // Store connection info
@@ -700,7 +804,7 @@ async fn process_socket(
             msg = strategy_updates_rx.recv() => {
                 match msg {
                     Ok(strategy_msg) => {
-                        if should_send_to_client(&SubscriptionType::AllStrategies, &subscriptions) {
+                        if should_send_to_client(&SubscriptionType::StrategyUpdates(vec![strategy_msg.execution.strategy_id.clone()]), &subscriptions) {
                             let ws_message = WebSocketMessage::StrategyUpdate {
                                 execution: strategy_msg.execution,
                                 status: strategy_msg.status,
@@ -721,6 +825,38 @@ async fn process_socket(
                 }
             }
 
+            // Handle strategy log and signal broadcasts
+            msg = strategy_activity_rx.recv() => {
+                match msg {
+                    Ok(activity) => {
+                        if should_send_to_client(&SubscriptionType::StrategyUpdates(vec![activity.strategy_id.clone()]), &subscriptions)
+                            && can_receive_activity(auth.as_ref().map(|auth| &auth.claims), &activity.account_id)
+                            && still_authorized(&app_state.token_store, &mut auth).await
+                        {
+                            let ws_message = WebSocketMessage::StrategyActivity {
+                                strategy_id: activity.strategy_id,
+                                strategy_name: activity.strategy_name,
+                                account_id: activity.account_id,
+                                logs: activity.logs,
+                                signals: activity.signals,
+                                fuel_consumed: activity.fuel_consumed,
+                                timestamp: activity.timestamp,
+                            };
+
+                            if let Ok(message_text) = serde_json::to_string(&ws_message) {
+                                if let Err(e) = sender.send(Message::Text(message_text)).await {
+                                    warn!("Failed to send strategy activity, client disconnected: {}", e);
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Strategy activity broadcast error: {}", e);
+                    }
+                }
+            }
+
             // Handle intel update broadcasts
             msg = intel_updates_rx.recv() => {
                 match msg {
@@ -864,12 +1000,26 @@ fn should_send_to_client(
                     }
                 }
             }
+            (
+                SubscriptionType::StrategyUpdates(client_strategies),
+                SubscriptionType::StrategyUpdates(msg_strategies),
+            ) if msg_strategies
+                .iter()
+                .any(|strategy| client_strategies.contains(strategy)) =>
+            {
+                return true;
+            }
             _ => {}
         }
     }
     false
 }
 
+/// Check if a connection may see activity of a strategy instance trading for an account
+fn can_receive_activity(claims: Option<&Claims>, account_id: &str) -> bool {
+    claims.is_some_and(|claims| AuthMiddleware::has_account_access(claims, account_id))
+}
+
 /// Connection statistics
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ConnectionStats {
@@ -915,4 +1065,82 @@ mod tests {
             &client_subs
         ));
     }
+
+    #[test]
+    fn test_strategy_subscription_matching() {
+        let client_subs = vec![SubscriptionType::StrategyUpdates(vec![
+            "strategy-a".to_string()
+        ])];
+
+        assert!(should_send_to_client(
+            &SubscriptionType::StrategyUpdates(vec!["strategy-a".to_string()]),
+            &client_subs
+        ));
+        assert!(!should_send_to_client(
+            &SubscriptionType::StrategyUpdates(vec!["strategy-b".to_string()]),
+            &client_subs
+        ));
+        assert!(should_send_to_client(
+            &SubscriptionType::StrategyUpdates(vec!["strategy-b".to_string()]),
+            &[SubscriptionType::AllStrategies]
+        ));
+    }
+
+    #[test]
+    fn test_strategy_activity_filtered_by_account_access() {
+        let claims = |roles: &[&str], account_ids: &[&str]| Claims {
+            sub: "user".to_string(),
+            iat: 0,
+            exp: 0,
+            iss: None,
+            aud: None,
+            roles: roles.iter().map(|role| role.to_string()).collect(),
+            account_ids: account_ids.iter().map(|id| id.to_string()).collect(),
+            token_type: crate::auth::TokenType::Access,
+            jti: String::new(),
+            sid: None,
+        };
+
+        assert!(!can_receive_activity(None, "acct-1"));
+        let trader = claims(&["trader"], &["acct-1"]);
+        assert!(can_receive_activity(Some(&trader), "acct-1"));
+        assert!(!can_receive_activity(Some(&trader), "acct-2"));
+        assert!(can_receive_activity(
+            Some(&claims(&["admin"], &[])),
+            "acct-2"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_revoked_session_stops_strategy_activity() {
+        let store = TokenStore::in_memory();
+        let session = AuthMiddleware::start_session(
+            &store,
+            "trader",
+            vec!["trader".to_string()],
+            vec!["acct-1".to_string()],
+        )
+        .await
+        .unwrap();
+        let claims = AuthMiddleware::authorize_token(&store, &session.access_token)
+            .await
+            .unwrap();
+        let mut auth = Some(ConnectionAuth {
+            token: session.access_token,
+            claims,
+        });
+
+        assert!(still_authorized(&store, &mut auth).await);
+        assert!(can_receive_activity(
+            auth.as_ref().map(|auth| &auth.claims),
+            "acct-1"
+        ));
+
+        AuthMiddleware::revoke_user_tokens(&store, "trader")
+            .await
+            .unwrap();
+        assert!(!still_authorized(&store, &mut auth).await);
+        assert!(auth.is_none());
+        assert!(!still_authorized(&store, &mut auth).await);
+    }
 }
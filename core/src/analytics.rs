@@ -0,0 +1,757 @@
+//! Performance analytics shared by strategy and portfolio reporting.
+//!
+//! A [`PerformanceTracker`] folds executions and mark prices, in time order, into an
+//! equity curve and a list of closed round trips. [`PerformanceReport`] derives return,
+//! risk and trade statistics from them; the return statistics are also exposed as free
+//! functions so backtests can score raw return series the same way.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::position_sizing::AccountSnapshot;
+use crate::types::{Execution, OrderSide, Symbol};
+
+/// Daily periods per year; crypto venues trade every day.
+pub const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Value reported for ratios whose denominator is zero while the numerator is positive.
+pub const RATIO_CAP: f64 = 100.0;
+
+/// Confidence level of the reported value at risk.
+pub const VAR_CONFIDENCE: f64 = 0.95;
+
+/// Calendar bucket used to group returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnPeriod {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl ReturnPeriod {
+    /// Bucket label, e.g. `2026-03-14`, `2026-W11`, `2026-03` or `2026`.
+    pub fn label(&self, timestamp: DateTime<Utc>) -> String {
+        match self {
+            ReturnPeriod::Day => timestamp.format("%Y-%m-%d").to_string(),
+            ReturnPeriod::Week => {
+                let week = timestamp.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            ReturnPeriod::Month => timestamp.format("%Y-%m").to_string(),
+            ReturnPeriod::Year => timestamp.format("%Y").to_string(),
+        }
+    }
+}
+
+/// Mark-to-market price observation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mark {
+    pub symbol: Symbol,
+    pub price: Decimal,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Account equity after an execution or mark.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct EquityPoint {
+    pub timestamp: DateTime<Utc>,
+    pub equity: f64,
+    /// Gross notional of open positions.
+    pub exposure: f64,
+}
+
+/// A position (or part of one) opened and later closed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RoundTrip {
+    pub symbol: Symbol,
+    /// Side of the opening execution.
+    pub side: OrderSide,
+    pub quantity: f64,
+    /// Average entry price per unit, fees included.
+    pub entry_price: f64,
+    /// Exit price per unit, fees included.
+    pub exit_price: f64,
+    pub opened_at: DateTime<Utc>,
+    pub closed_at: DateTime<Utc>,
+    pub pnl: f64,
+}
+
+impl RoundTrip {
+    pub fn duration(&self) -> Duration {
+        self.closed_at - self.opened_at
+    }
+}
+
+/// Open position valued at its latest mark.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpenPosition {
+    pub symbol: Symbol,
+    /// Signed quantity; negative when short.
+    pub quantity: f64,
+    /// Average entry price per unit, fees included.
+    pub entry_price: f64,
+    pub mark_price: f64,
+}
+
+impl OpenPosition {
+    pub fn market_value(&self) -> f64 {
+        self.quantity * self.mark_price
+    }
+
+    pub fn unrealized_pnl(&self) -> f64 {
+        self.quantity * (self.mark_price - self.entry_price)
+    }
+}
+
+/// Open position in one symbol; `quantity` is negative when short.
+#[derive(Debug, Clone)]
+struct Holding {
+    quantity: f64,
+    entry: f64,
+    opened_at: DateTime<Utc>,
+}
+
+/// Folds executions and marks into an equity curve and closed round trips.
+#[derive(Debug, Clone)]
+pub struct PerformanceTracker {
+    initial_capital: f64,
+    cash: f64,
+    holdings: HashMap<Symbol, Holding>,
+    marks: HashMap<Symbol, f64>,
+    curve: Vec<EquityPoint>,
+    trades: Vec<RoundTrip>,
+    traded_notional: f64,
+    fees: f64,
+}
+
+impl PerformanceTracker {
+    /// Returns are measured against `initial_capital`; with zero capital only PnL is meaningful.
+    pub fn new(initial_capital: Decimal) -> Self {
+        let initial_capital = initial_capital.to_f64().unwrap_or(0.0);
+        Self {
+            initial_capital,
+            cash: initial_capital,
+            holdings: HashMap::new(),
+            marks: HashMap::new(),
+            curve: Vec::new(),
+            trades: Vec::new(),
+            traded_notional: 0.0,
+            fees: 0.0,
+        }
+    }
+
+    /// Replays `executions` and `marks` merged in time order.
+    pub fn from_history(
+        initial_capital: Decimal,
+        executions: &[Execution],
+        marks: &[Mark],
+    ) -> Self {
+        enum Event<'a> {
+            Execution(&'a Execution),
+            Mark(&'a Mark),
+        }
+        let mut events: Vec<(DateTime<Utc>, Event<'_>)> = executions
+            .iter()
+            .map(|execution| (execution.timestamp, Event::Execution(execution)))
+            .chain(marks.iter().map(|mark| (mark.timestamp, Event::Mark(mark))))
+            .collect();
+        events.sort_by_key(|(timestamp, _)| *timestamp);
+
+        let mut tracker = Self::new(initial_capital);
+        for (_, event) in events {
+            match event {
+                Event::Execution(execution) => tracker.record_execution(execution),
+                Event::Mark(mark) => tracker.record_mark(mark),
+            }
+        }
+        tracker
+    }
+
+    /// Applies a fill, realizing PnL on any quantity that reduces the open position.
+    pub fn record_execution(&mut self, execution: &Execution) {
+        let quantity = execution.quantity.to_f64().unwrap_or(0.0);
+        let price = execution.price.to_f64().unwrap_or(0.0);
+        let fees = execution.fees.to_f64().unwrap_or(0.0);
+        if quantity <= 0.0 {
+            return;
+        }
+
+        let delta = match execution.side {
+            OrderSide::Buy => quantity,
+            OrderSide::Sell => -quantity,
+        };
+        // Per-unit price with the fee folded in, so round-trip PnL is net of fees.
+        let unit = price + fees / quantity * delta.signum();
+        self.cash -= price * delta + fees;
+        self.fees += fees;
+        self.traded_notional += price * quantity;
+        self.marks.insert(execution.symbol.clone(), price);
+
+        let holding = self
+            .holdings
+            .entry(execution.symbol.clone())
+            .or_insert(Holding {
+                quantity: 0.0,
+                entry: 0.0,
+                opened_at: execution.timestamp,
+            });
+
+        let mut opening = delta;
+        if holding.quantity != 0.0 && holding.quantity.signum() != delta.signum() {
+            let closed = delta.abs().min(holding.quantity.abs());
+            let direction = holding.quantity.signum();
+            self.trades.push(RoundTrip {
+                symbol: execution.symbol.clone(),
+                side: if direction > 0.0 {
+                    OrderSide::Buy
+                } else {
+                    OrderSide::Sell
+                },
+                quantity: closed,
+                entry_price: holding.entry,
+                exit_price: unit,
+                opened_at: holding.opened_at,
+                closed_at: execution.timestamp,
+                pnl: closed * (unit - holding.entry) * direction,
+            });
+            holding.quantity -= closed * direction;
+            opening = delta + closed * direction;
+            if holding.quantity.abs() < f64::EPSILON {
+                holding.quantity = 0.0;
+            }
+        }
+        if opening != 0.0 {
+            if holding.quantity == 0.0 {
+                holding.entry = 0.0;
+                holding.opened_at = execution.timestamp;
+            }
+            let held = holding.quantity.abs();
+            holding.entry = (holding.entry * held + unit * opening.abs()) / (held + opening.abs());
+            holding.quantity += opening;
+        }
+
+        self.push_point(execution.timestamp);
+    }
+
+    /// Revalues open positions in the mark's symbol.
+    pub fn record_mark(&mut self, mark: &Mark) {
+        if let Some(price) = mark.price.to_f64() {
+            self.marks.insert(mark.symbol.clone(), price);
+            self.push_point(mark.timestamp);
+        }
+    }
+
+    pub fn initial_capital(&self) -> f64 {
+        self.initial_capital
+    }
+
+    /// Cash plus open positions at their latest marks.
+    pub fn equity(&self) -> f64 {
+        self.cash
+            + self
+                .holdings
+                .iter()
+                .map(|(symbol, holding)| holding.quantity * self.mark(symbol))
+                .sum::<f64>()
+    }
+
+    pub fn equity_curve(&self) -> &[EquityPoint] {
+        &self.curve
+    }
+
+    pub fn trades(&self) -> &[RoundTrip] {
+        &self.trades
+    }
+
+    /// Live equity, positions and marks, e.g. for [`PositionSizer`].
+    ///
+    /// [`PositionSizer`]: crate::position_sizing::PositionSizer
+    pub fn account_snapshot(&self) -> AccountSnapshot {
+        let decimal = |value: f64| Decimal::from_f64(value).unwrap_or_default();
+        AccountSnapshot {
+            equity: decimal(self.equity()),
+            positions: self
+                .holdings
+                .iter()
+                .filter(|(_, holding)| holding.quantity != 0.0)
+                .map(|(symbol, holding)| {
+                    (
+                        symbol.clone(),
+                        decimal(holding.quantity * self.mark(symbol)),
+                    )
+                })
+                .collect(),
+            marks: self
+                .marks
+                .iter()
+                .map(|(symbol, price)| (symbol.clone(), decimal(*price)))
+                .collect(),
+        }
+    }
+
+    /// Open positions ordered by symbol.
+    pub fn positions(&self) -> Vec<OpenPosition> {
+        let mut positions: Vec<OpenPosition> = self
+            .holdings
+            .iter()
+            .filter(|(_, holding)| holding.quantity != 0.0)
+            .map(|(symbol, holding)| OpenPosition {
+                symbol: symbol.clone(),
+                quantity: holding.quantity,
+                entry_price: holding.entry,
+                mark_price: self.mark(symbol),
+            })
+            .collect();
+        positions.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        positions
+    }
+
+    /// Compounded return of each calendar bucket, keyed by [`ReturnPeriod::label`].
+    pub fn period_returns(&self, period: ReturnPeriod) -> BTreeMap<String, f64> {
+        let mut closes: BTreeMap<String, f64> = BTreeMap::new();
+        for point in &self.curve {
+            closes.insert(period.label(point.timestamp), point.equity);
+        }
+        let mut previous = self.initial_capital;
+        closes
+            .into_iter()
+            .map(|(label, close)| {
+                let change = simple_return(previous, close);
+                previous = close;
+                (label, change)
+            })
+            .collect()
+    }
+
+    /// Return over the trailing `window` ending at the last recorded point.
+    pub fn trailing_return(&self, window: Duration) -> f64 {
+        let Some(last) = self.curve.last() else {
+            return 0.0;
+        };
+        let cutoff = last.timestamp - window;
+        let base = self
+            .curve
+            .iter()
+            .take_while(|point| point.timestamp <= cutoff)
+            .last()
+            .map_or(self.initial_capital, |point| point.equity);
+        simple_return(base, last.equity)
+    }
+
+    /// End-of-day equity returns, carrying equity across days without activity.
+    pub fn daily_returns(&self) -> Vec<f64> {
+        let (Some(first), Some(last)) = (self.curve.first(), self.curve.last()) else {
+            return Vec::new();
+        };
+        let mut closes: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+        for point in &self.curve {
+            closes.insert(point.timestamp.date_naive(), point.equity);
+        }
+
+        let mut returns = Vec::new();
+        let mut previous = self.initial_capital;
+        let mut close = previous;
+        let mut day = first.timestamp.date_naive();
+        while day <= last.timestamp.date_naive() {
+            if let Some(equity) = closes.get(&day) {
+                close = *equity;
+            }
+            returns.push(simple_return(previous, close));
+            previous = close;
+            day = day.succ_opt().unwrap_or(NaiveDate::MAX);
+        }
+        returns
+    }
+
+    pub fn report(&self) -> PerformanceReport {
+        let final_equity = self.equity();
+        let start = self.curve.first().map(|point| point.timestamp);
+        let end = self.curve.last().map(|point| point.timestamp);
+        let span_days = match (start, end) {
+            (Some(start), Some(end)) => (end - start).num_seconds() as f64 / 86_400.0,
+            _ => 0.0,
+        };
+
+        let total_return = simple_return(self.initial_capital, final_equity);
+        let annualized_return = if span_days >= 1.0 && total_return > -1.0 {
+            (1.0 + total_return).powf(DAYS_PER_YEAR / span_days) - 1.0
+        } else {
+            total_return
+        };
+
+        let daily = self.daily_returns();
+        let mut equity: Vec<f64> = vec![self.initial_capital];
+        equity.extend(self.curve.iter().map(|point| point.equity));
+        let max_drawdown = max_drawdown(&equity);
+
+        let pnls: Vec<f64> = self.trades.iter().map(|trade| trade.pnl).collect();
+        let average_equity = mean(&equity);
+
+        PerformanceReport {
+            start,
+            end,
+            initial_equity: self.initial_capital,
+            final_equity,
+            total_pnl: final_equity - self.initial_capital,
+            realized_pnl: pnls.iter().sum(),
+            fees: self.fees,
+            total_return,
+            annualized_return,
+            volatility: volatility(&daily, DAYS_PER_YEAR),
+            sharpe_ratio: sharpe_ratio(&daily, DAYS_PER_YEAR),
+            sortino_ratio: sortino_ratio(&daily, DAYS_PER_YEAR),
+            calmar_ratio: if max_drawdown > 0.0 {
+                annualized_return / max_drawdown
+            } else {
+                capped_ratio(annualized_return)
+            },
+            max_drawdown,
+            max_drawdown_duration_secs: self.max_drawdown_duration().num_seconds(),
+            trades: pnls.len(),
+            win_rate: win_rate(&pnls),
+            profit_factor: profit_factor(&pnls),
+            avg_trade_duration_secs: if self.trades.is_empty() {
+                0.0
+            } else {
+                self.trades
+                    .iter()
+                    .map(|trade| trade.duration().num_seconds() as f64)
+                    .sum::<f64>()
+                    / self.trades.len() as f64
+            },
+            exposure: self.time_in_market(),
+            turnover: if average_equity > 0.0 {
+                self.traded_notional / average_equity
+            } else {
+                0.0
+            },
+            value_at_risk_95: historical_var(&daily, VAR_CONFIDENCE),
+            conditional_value_at_risk_95: conditional_var(&daily, VAR_CONFIDENCE),
+        }
+    }
+
+    fn mark(&self, symbol: &str) -> f64 {
+        self.marks.get(symbol).copied().unwrap_or(0.0)
+    }
+
+    fn push_point(&mut self, timestamp: DateTime<Utc>) {
+        let exposure = self
+            .holdings
+            .iter()
+            .map(|(symbol, holding)| (holding.quantity * self.mark(symbol)).abs())
+            .sum();
+        self.curve.push(EquityPoint {
+            timestamp,
+            equity: self.equity(),
+            exposure,
+        });
+    }
+
+    /// Longest stretch spent below a previous equity peak, including an unrecovered one.
+    fn max_drawdown_duration(&self) -> Duration {
+        let Some(first) = self.curve.first() else {
+            return Duration::zero();
+        };
+        let mut peak = self.initial_capital;
+        let mut peak_at = first.timestamp;
+        let mut longest = Duration::zero();
+        for point in &self.curve {
+            if point.equity >= peak {
+                peak = point.equity;
+                peak_at = point.timestamp;
+            } else {
+                longest = longest.max(point.timestamp - peak_at);
+            }
+        }
+        longest
+    }
+
+    /// Share of the recorded span during which any position was open.
+    fn time_in_market(&self) -> f64 {
+        let (Some(first), Some(last)) = (self.curve.first(), self.curve.last()) else {
+            return 0.0;
+        };
+        let span = (last.timestamp - first.timestamp).num_seconds();
+        if span <= 0 {
+            return if last.exposure > 0.0 { 1.0 } else { 0.0 };
+        }
+        let invested: i64 = self
+            .curve
+            .windows(2)
+            .filter(|pair| pair[0].exposure > 0.0)
+            .map(|pair| (pair[1].timestamp - pair[0].timestamp).num_seconds())
+            .sum();
+        invested as f64 / span as f64
+    }
+}
+
+/// Statistics derived from a [`PerformanceTracker`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceReport {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub initial_equity: f64,
+    pub final_equity: f64,
+    /// Realized plus unrealized PnL, net of fees.
+    pub total_pnl: f64,
+    pub realized_pnl: f64,
+    pub fees: f64,
+    pub total_return: f64,
+    pub annualized_return: f64,
+    /// Annualized standard deviation of daily returns.
+    pub volatility: f64,
+    pub sharpe_ratio: f64,
+    pub sortino_ratio: f64,
+    /// Annualized return over maximum drawdown.
+    pub calmar_ratio: f64,
+    /// Largest peak-to-trough equity decline, as a positive fraction.
+    pub max_drawdown: f64,
+    pub max_drawdown_duration_secs: i64,
+    pub trades: usize,
+    pub win_rate: f64,
+    pub profit_factor: f64,
+    pub avg_trade_duration_secs: f64,
+    /// Share of time with an open position.
+    pub exposure: f64,
+    /// Traded notional over average equity.
+    pub turnover: f64,
+    /// One-day historical value at risk, as a positive loss fraction.
+    pub value_at_risk_95: f64,
+    /// Mean one-day loss beyond the value at risk.
+    pub conditional_value_at_risk_95: f64,
+}
+
+fn simple_return(from: f64, to: f64) -> f64 {
+    if from > 0.0 {
+        to / from - 1.0
+    } else {
+        0.0
+    }
+}
+
+fn capped_ratio(numerator: f64) -> f64 {
+    if numerator > 0.0 {
+        RATIO_CAP
+    } else {
+        0.0
+    }
+}
+
+pub fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// Annualized sample standard deviation of per-period returns.
+pub fn volatility(returns: &[f64], periods_per_year: f64) -> f64 {
+    if returns.len() < 2 {
+        return 0.0;
+    }
+    let average = mean(returns);
+    let variance =
+        returns.iter().map(|r| (r - average).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    variance.sqrt() * periods_per_year.sqrt()
+}
+
+/// Annualized Sharpe ratio of per-period returns, assuming a zero risk-free rate.
+pub fn sharpe_ratio(returns: &[f64], periods_per_year: f64) -> f64 {
+    let volatility = volatility(returns, periods_per_year);
+    if volatility > 0.0 {
+        mean(returns) * periods_per_year / volatility
+    } else {
+        0.0
+    }
+}
+
+/// Annualized Sortino ratio using downside deviation below zero.
+pub fn sortino_ratio(returns: &[f64], periods_per_year: f64) -> f64 {
+    if returns.is_empty() {
+        return 0.0;
+    }
+    let downside =
+        (returns.iter().map(|r| r.min(0.0).powi(2)).sum::<f64>() / returns.len() as f64).sqrt();
+    if downside > 0.0 {
+        mean(returns) / downside * periods_per_year.sqrt()
+    } else {
+        capped_ratio(mean(returns))
+    }
+}
+
+/// Largest peak-to-trough decline of an equity series, as a positive fraction.
+pub fn max_drawdown(equity: &[f64]) -> f64 {
+    let mut peak = f64::MIN;
+    let mut worst = 0.0_f64;
+    for value in equity {
+        peak = peak.max(*value);
+        if peak > 0.0 {
+            worst = worst.max((peak - value) / peak);
+        }
+    }
+    worst
+}
+
+/// Compounds per-period returns into an equity series starting at 1.
+pub fn compound(returns: &[f64]) -> Vec<f64> {
+    let mut equity = Vec::with_capacity(returns.len() + 1);
+    equity.push(1.0);
+    for r in returns {
+        let last = equity[equity.len() - 1];
+        equity.push(last * (1.0 + r));
+    }
+    equity
+}
+
+/// Gross profit over gross loss.
+pub fn profit_factor(pnls: &[f64]) -> f64 {
+    let profit: f64 = pnls.iter().filter(|p| **p > 0.0).sum();
+    let loss: f64 = -pnls.iter().filter(|p| **p < 0.0).sum::<f64>();
+    if loss > 0.0 {
+        profit / loss
+    } else {
+        capped_ratio(profit)
+    }
+}
+
+pub fn win_rate(pnls: &[f64]) -> f64 {
+    if pnls.is_empty() {
+        0.0
+    } else {
+        pnls.iter().filter(|p| **p > 0.0).count() as f64 / pnls.len() as f64
+    }
+}
+
+/// Historical value at risk: the loss not exceeded with `confidence`, as a positive fraction.
+pub fn historical_var(returns: &[f64], confidence: f64) -> f64 {
+    tail(returns, confidence)
+        .first()
+        .map_or(0.0, |worst_kept| (-worst_kept).max(0.0))
+}
+
+/// Expected shortfall: the mean loss in the worst `1 - confidence` of returns.
+pub fn conditional_var(returns: &[f64], confidence: f64) -> f64 {
+    let tail = tail(returns, confidence);
+    (-mean(&tail)).max(0.0)
+}
+
+/// Worst `1 - confidence` share of returns (at least one), best first.
+fn tail(returns: &[f64], confidence: f64) -> Vec<f64> {
+    if returns.is_empty() {
+        return Vec::new();
+    }
+    let mut sorted = returns.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let count =
+        ((returns.len() as f64 * (1.0 - confidence)).ceil() as usize).clamp(1, returns.len());
+    let mut tail = sorted[..count].to_vec();
+    tail.reverse();
+    tail
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn fill(
+        side: OrderSide,
+        quantity: i64,
+        price: i64,
+        fees: i64,
+        timestamp: DateTime<Utc>,
+    ) -> Execution {
+        let mut execution = Execution::new(
+            Uuid::new_v4(),
+            "BTC-USD".to_string(),
+            side,
+            Decimal::from(quantity),
+            Decimal::from(price),
+            "kraken".to_string(),
+            Decimal::from(fees),
+        );
+        execution.timestamp = timestamp;
+        execution
+    }
+
+    fn mark(price: i64, timestamp: DateTime<Utc>) -> Mark {
+        Mark {
+            symbol: "BTC-USD".to_string(),
+            price: Decimal::from(price),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_tracker_builds_equity_curve_and_round_trips() {
+        let tracker = PerformanceTracker::from_history(
+            Decimal::from(1_000),
+            &[
+                fill(OrderSide::Buy, 2, 100, 2, at(1, 0)),
+                fill(OrderSide::Sell, 1, 120, 1, at(2, 0)),
+                fill(OrderSide::Sell, 1, 90, 1, at(4, 0)),
+            ],
+            &[mark(80, at(3, 0))],
+        );
+
+        let trades = tracker.trades();
+        assert_eq!(trades.len(), 2);
+        assert!((trades[0].pnl - (119.0 - 101.0)).abs() < 1e-9);
+        assert!((trades[1].pnl - (89.0 - 101.0)).abs() < 1e-9);
+        assert_eq!(trades[1].duration(), Duration::days(3));
+        assert_eq!(tracker.positions().len(), 0);
+
+        let report = tracker.report();
+        assert!((report.final_equity - 1_006.0).abs() < 1e-9);
+        assert!((report.total_pnl - report.realized_pnl).abs() < 1e-9);
+        assert_eq!(report.trades, 2);
+        assert!((report.win_rate - 0.5).abs() < 1e-12);
+        assert!((report.profit_factor - 1.5).abs() < 1e-12);
+        // Peak after the first sale (1037), trough at the 80 mark (997).
+        assert!((report.max_drawdown - 40.0 / 1037.0).abs() < 1e-9);
+        assert_eq!(
+            report.max_drawdown_duration_secs,
+            Duration::days(2).num_seconds()
+        );
+        assert!((report.exposure - 1.0).abs() < 1e-12);
+        assert!(report.turnover > 0.0);
+
+        let months = tracker.period_returns(ReturnPeriod::Month);
+        assert!((months["2026-03"] - 0.006).abs() < 1e-9);
+        assert_eq!(tracker.daily_returns().len(), 4);
+
+        let open = PerformanceTracker::from_history(
+            Decimal::from(1_000),
+            &[fill(OrderSide::Buy, 2, 100, 2, at(1, 0))],
+            &[mark(110, at(1, 6))],
+        );
+        let positions = open.positions();
+        assert_eq!(positions.len(), 1);
+        assert!((positions[0].unrealized_pnl() - 18.0).abs() < 1e-9);
+        assert!((positions[0].market_value() - 220.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_return_statistics() {
+        let returns = [0.02, -0.01, 0.03, -0.04, 0.01];
+        assert!(sharpe_ratio(&returns, DAYS_PER_YEAR) > 0.0);
+        assert!(sortino_ratio(&returns, DAYS_PER_YEAR) > sharpe_ratio(&returns, DAYS_PER_YEAR));
+        assert!((max_drawdown(&compound(&returns)) - 0.04).abs() < 1e-12);
+        assert!((historical_var(&returns, 0.8) - 0.04).abs() < 1e-12);
+        assert!((conditional_var(&returns, 0.75) - 0.025).abs() < 1e-12);
+        assert_eq!(profit_factor(&[5.0]), RATIO_CAP);
+        assert_eq!(win_rate(&[]), 0.0);
+    }
+}
@@ -4,8 +4,10 @@
 //! autonomous trading platform. This crate provides the fundamental building
 //! blocks for the entire trading system.
 
+pub mod analytics;
 pub mod error;
 pub mod order_manager;
+pub mod position_sizing;
 pub mod smart_router;
 pub mod types;
 
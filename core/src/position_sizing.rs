@@ -0,0 +1,459 @@
+//! Position sizing shared by strategies and order routing.
+//!
+//! A [`PositionSizer`] turns an entry price, and optionally a stop, a volatility
+//! estimate and a signal confidence, into an order quantity scaled to the account's
+//! live equity. Account-level [`SizingLimits`] then cap the result against the
+//! position already held and the account's gross exposure.
+
+use std::collections::HashMap;
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::analytics;
+use crate::error::{TradingError, TradingResult};
+use crate::types::{OrderSide, Symbol};
+
+/// Signal metadata key carrying the protective stop of an entry.
+pub const STOP_LOSS_KEY: &str = "stop_loss";
+/// Signal metadata key carrying the per-period volatility of the traded symbol, as a
+/// fraction of its price.
+pub const VOLATILITY_KEY: &str = "volatility";
+
+/// How a [`PositionSizer`] scales positions to equity.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SizingMethod {
+    /// Always the same quantity.
+    Fixed { quantity: Decimal },
+    /// Lose `risk_fraction` of equity if the stop is hit.
+    FixedFractional { risk_fraction: Decimal },
+    /// Hold a position whose per-period volatility is `target` of equity.
+    VolatilityTarget { target: Decimal },
+    /// `fraction` of the Kelly bet, reading the signal's confidence as the win
+    /// probability and `payoff_ratio` as the average win over the average loss.
+    Kelly {
+        fraction: Decimal,
+        payoff_ratio: Decimal,
+    },
+}
+
+/// Account-level caps applied after sizing, as fractions of equity. `None` disables
+/// a cap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SizingLimits {
+    /// Largest position in one symbol, counting what is already held.
+    pub max_position_fraction: Option<Decimal>,
+    /// Largest sum of absolute position values across the account.
+    pub max_gross_exposure: Option<Decimal>,
+    /// Largest quantity of a single order.
+    pub max_quantity: Option<Decimal>,
+    /// Quantities are rounded down to a multiple of this.
+    pub lot_size: Option<Decimal>,
+}
+
+/// Volatility estimate of the traded symbol over one period.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Volatility {
+    /// Average true range, in price units.
+    Atr(Decimal),
+    /// Standard deviation of returns, as a fraction of price.
+    Fraction(Decimal),
+}
+
+impl Volatility {
+    /// Volatility as a fraction of `price`.
+    pub fn fraction(&self, price: Decimal) -> Decimal {
+        match self {
+            Volatility::Atr(atr) if !price.is_zero() => atr.abs() / price,
+            Volatility::Atr(_) => Decimal::ZERO,
+            Volatility::Fraction(fraction) => fraction.abs(),
+        }
+    }
+}
+
+/// Equity and open positions of an account, marked at current prices.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccountSnapshot {
+    /// Cash plus open positions at their marks.
+    pub equity: Decimal,
+    /// Market value of each open position; negative when short.
+    pub positions: HashMap<Symbol, Decimal>,
+    /// Latest price of each symbol.
+    pub marks: HashMap<Symbol, Decimal>,
+}
+
+impl AccountSnapshot {
+    pub fn new(equity: Decimal) -> Self {
+        Self {
+            equity,
+            ..Self::default()
+        }
+    }
+
+    /// Market value held in `symbol`; negative when short.
+    pub fn position_value(&self, symbol: &str) -> Decimal {
+        self.positions.get(symbol).copied().unwrap_or_default()
+    }
+
+    /// Sum of absolute position values.
+    pub fn gross_exposure(&self) -> Decimal {
+        self.positions.values().map(|value| value.abs()).sum()
+    }
+
+    pub fn mark(&self, symbol: &str) -> Option<Decimal> {
+        self.marks.get(symbol).copied()
+    }
+}
+
+/// Order to size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizingRequest {
+    pub symbol: Symbol,
+    pub side: OrderSide,
+    /// Expected entry price.
+    pub price: Decimal,
+    /// Protective stop; required by [`SizingMethod::FixedFractional`].
+    pub stop_price: Option<Decimal>,
+    /// Required by [`SizingMethod::VolatilityTarget`].
+    pub volatility: Option<Volatility>,
+    /// Signal confidence in `0.0..=1.0`, read by [`SizingMethod::Kelly`].
+    pub confidence: f64,
+}
+
+impl SizingRequest {
+    pub fn new(symbol: impl Into<Symbol>, side: OrderSide, price: Decimal) -> Self {
+        Self {
+            symbol: symbol.into(),
+            side,
+            price,
+            stop_price: None,
+            volatility: None,
+            confidence: 1.0,
+        }
+    }
+
+    pub fn with_stop(mut self, stop_price: Decimal) -> Self {
+        self.stop_price = Some(stop_price);
+        self
+    }
+
+    pub fn with_volatility(mut self, volatility: Volatility) -> Self {
+        self.volatility = Some(volatility);
+        self
+    }
+
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        self.confidence = confidence;
+        self
+    }
+}
+
+/// Account cap that reduced a sized order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizingLimit {
+    Position,
+    GrossExposure,
+    Quantity,
+}
+
+/// Sized order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sizing {
+    /// Quantity to order; zero when the account has no room or no edge.
+    pub quantity: Decimal,
+    /// `quantity` valued at the entry price.
+    pub notional: Decimal,
+    /// Cap that bound the quantity, if any.
+    pub limited_by: Option<SizingLimit>,
+}
+
+/// Sizes orders from live equity with one [`SizingMethod`] and account caps.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PositionSizer {
+    pub method: SizingMethod,
+    #[serde(default)]
+    pub limits: SizingLimits,
+}
+
+impl PositionSizer {
+    pub fn new(method: SizingMethod) -> Self {
+        Self {
+            method,
+            limits: SizingLimits::default(),
+        }
+    }
+
+    pub fn with_limits(mut self, limits: SizingLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sizes `request` against `account`.
+    ///
+    /// Fails when the request lacks what the method needs: a positive price, a stop
+    /// away from the entry, or a volatility estimate.
+    pub fn size(
+        &self,
+        account: &AccountSnapshot,
+        request: &SizingRequest,
+    ) -> TradingResult<Sizing> {
+        let price = request.price;
+        if price <= Decimal::ZERO {
+            return Err(TradingError::ValidationError(format!(
+                "cannot size {} at price {}",
+                request.symbol, price
+            )));
+        }
+        let equity = account.equity.max(Decimal::ZERO);
+
+        let notional = match self.method {
+            SizingMethod::Fixed { quantity } => quantity.abs() * price,
+            SizingMethod::FixedFractional { risk_fraction } => {
+                let stop = request.stop_price.ok_or_else(|| {
+                    TradingError::ValidationError(
+                        "fixed-fractional sizing needs a stop price".to_string(),
+                    )
+                })?;
+                let distance = (price - stop).abs();
+                if distance.is_zero() {
+                    return Err(TradingError::ValidationError(
+                        "stop price equals the entry price".to_string(),
+                    ));
+                }
+                equity * risk_fraction.abs() / distance * price
+            }
+            SizingMethod::VolatilityTarget { target } => {
+                let volatility = request
+                    .volatility
+                    .map(|volatility| volatility.fraction(price))
+                    .filter(|fraction| !fraction.is_zero())
+                    .ok_or_else(|| {
+                        TradingError::ValidationError(
+                            "volatility targeting needs a non-zero volatility".to_string(),
+                        )
+                    })?;
+                equity * target.abs() / volatility
+            }
+            SizingMethod::Kelly {
+                fraction,
+                payoff_ratio,
+            } => {
+                let win = Decimal::from_f64(request.confidence.clamp(0.0, 1.0)).unwrap_or_default();
+                let kelly = if payoff_ratio > Decimal::ZERO {
+                    win - (Decimal::ONE - win) / payoff_ratio
+                } else {
+                    Decimal::ZERO
+                };
+                equity * fraction.abs() * kelly.max(Decimal::ZERO)
+            }
+        };
+
+        let (notional, limited_by) = self.cap(account, request, notional, equity);
+        let mut quantity = notional / price;
+        let mut limited_by = limited_by;
+        if let Some(max) = self.limits.max_quantity {
+            if quantity > max.abs() {
+                quantity = max.abs();
+                limited_by = Some(SizingLimit::Quantity);
+            }
+        }
+        if let Some(lot) = self.limits.lot_size.filter(|lot| *lot > Decimal::ZERO) {
+            quantity = (quantity / lot).floor() * lot;
+        }
+        Ok(Sizing {
+            quantity: quantity.normalize(),
+            notional: (quantity * price).normalize(),
+            limited_by,
+        })
+    }
+
+    /// Caps `notional` so the resulting position and gross exposure stay within the
+    /// account limits. Orders that reduce a position may always close it.
+    fn cap(
+        &self,
+        account: &AccountSnapshot,
+        request: &SizingRequest,
+        notional: Decimal,
+        equity: Decimal,
+    ) -> (Decimal, Option<SizingLimit>) {
+        let current = account.position_value(&request.symbol);
+        let adding = match request.side {
+            OrderSide::Buy => current >= Decimal::ZERO,
+            OrderSide::Sell => current <= Decimal::ZERO,
+        };
+        let held = current.abs();
+        let mut notional = notional;
+        let mut limited_by = None;
+
+        if let Some(fraction) = self.limits.max_position_fraction {
+            let cap = equity * fraction.abs();
+            let room = if adding {
+                (cap - held).max(Decimal::ZERO)
+            } else {
+                held + cap
+            };
+            if notional > room {
+                notional = room;
+                limited_by = Some(SizingLimit::Position);
+            }
+        }
+        if let Some(fraction) = self.limits.max_gross_exposure {
+            let headroom = equity * fraction.abs() - account.gross_exposure();
+            let room = if adding {
+                headroom.max(Decimal::ZERO)
+            } else {
+                // Closing frees the held value before the flip adds exposure again.
+                held.max(headroom + held + held)
+            };
+            if notional > room {
+                notional = room;
+                limited_by = Some(SizingLimit::GrossExposure);
+            }
+        }
+        (notional, limited_by)
+    }
+}
+
+/// Standard deviation of the log returns between consecutive `closes`, as a fraction
+/// of price per period. `None` with fewer than three positive closes.
+pub fn realized_volatility(closes: &[Decimal]) -> Option<Decimal> {
+    let prices: Vec<f64> = closes
+        .iter()
+        .filter_map(|close| close.to_f64())
+        .filter(|close| *close > 0.0)
+        .collect();
+    if prices.len() < 3 {
+        return None;
+    }
+    let returns: Vec<f64> = prices
+        .windows(2)
+        .map(|pair| (pair[1] / pair[0]).ln())
+        .collect();
+    Decimal::from_f64(analytics::volatility(&returns, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account() -> AccountSnapshot {
+        AccountSnapshot {
+            equity: Decimal::from(100000),
+            positions: HashMap::from([
+                ("BTC-USD".to_string(), Decimal::from(20000)),
+                ("ETH-USD".to_string(), Decimal::from(-10000)),
+            ]),
+            marks: HashMap::from([("BTC-USD".to_string(), Decimal::from(50000))]),
+        }
+    }
+
+    #[test]
+    fn fixed_fractional_risks_a_share_of_equity_to_the_stop() {
+        let sizer = PositionSizer::new(SizingMethod::FixedFractional {
+            risk_fraction: Decimal::new(1, 2),
+        });
+        let request = SizingRequest::new("SOL-USD", OrderSide::Buy, Decimal::from(100))
+            .with_stop(Decimal::from(95));
+        let sizing = sizer.size(&account(), &request).unwrap();
+        // 1% of 100k is 1000 at risk, 5 per unit.
+        assert_eq!(sizing.quantity, Decimal::from(200));
+        assert_eq!(sizing.notional, Decimal::from(20000));
+        assert_eq!(sizing.limited_by, None);
+
+        let no_stop = SizingRequest::new("SOL-USD", OrderSide::Buy, Decimal::from(100));
+        assert!(sizer.size(&account(), &no_stop).is_err());
+    }
+
+    #[test]
+    fn volatility_target_scales_inversely_with_atr_and_realized_vol() {
+        let sizer = PositionSizer::new(SizingMethod::VolatilityTarget {
+            target: Decimal::new(1, 2),
+        });
+        let calm = SizingRequest::new("SOL-USD", OrderSide::Buy, Decimal::from(100))
+            .with_volatility(Volatility::Atr(Decimal::from(2)));
+        let wild = calm
+            .clone()
+            .with_volatility(Volatility::Fraction(Decimal::new(4, 2)));
+        // 1% of equity per period over 2% daily moves is half the equity.
+        assert_eq!(
+            sizer.size(&account(), &calm).unwrap().notional,
+            Decimal::from(50000)
+        );
+        assert_eq!(
+            sizer.size(&account(), &wild).unwrap().notional,
+            Decimal::from(25000)
+        );
+
+        let closes: Vec<Decimal> = [100, 102, 99, 103, 101]
+            .into_iter()
+            .map(Decimal::from)
+            .collect();
+        let realized = realized_volatility(&closes).unwrap();
+        let returns = [102.0f64 / 100.0, 99.0 / 102.0, 103.0 / 99.0, 101.0 / 103.0].map(f64::ln);
+        let mean = returns.iter().sum::<f64>() / 4.0;
+        let expected = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 3.0).sqrt();
+        assert!((realized.to_f64().unwrap() - expected).abs() < 1e-9);
+        assert_eq!(realized_volatility(&closes[..2]), None);
+    }
+
+    #[test]
+    fn fractional_kelly_reads_confidence_as_win_probability() {
+        let sizer = PositionSizer::new(SizingMethod::Kelly {
+            fraction: Decimal::new(5, 1),
+            payoff_ratio: Decimal::from(2),
+        });
+        let request =
+            SizingRequest::new("SOL-USD", OrderSide::Buy, Decimal::from(100)).with_confidence(0.6);
+        // Kelly is 0.6 - 0.4 / 2 = 0.4 of equity; half of it is 20k.
+        assert_eq!(
+            sizer.size(&account(), &request).unwrap().quantity,
+            Decimal::from(200)
+        );
+
+        let no_edge = request.with_confidence(0.3);
+        assert_eq!(
+            sizer.size(&account(), &no_edge).unwrap().quantity,
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn account_limits_cap_positions_exposure_and_lots() {
+        let limits = SizingLimits {
+            max_position_fraction: Some(Decimal::new(25, 2)),
+            max_gross_exposure: Some(Decimal::new(5, 1)),
+            max_quantity: None,
+            lot_size: Some(Decimal::new(1, 2)),
+        };
+        let sizer = PositionSizer::new(SizingMethod::Fixed {
+            quantity: Decimal::from(1),
+        })
+        .with_limits(limits);
+
+        // 20k of BTC is held; the position cap leaves 5k.
+        let buy = SizingRequest::new("BTC-USD", OrderSide::Buy, Decimal::from(50000));
+        let sizing = sizer.size(&account(), &buy).unwrap();
+        assert_eq!(sizing.quantity, Decimal::new(1, 1));
+        assert_eq!(sizing.limited_by, Some(SizingLimit::Position));
+
+        // Selling may close the 20k long and open a 25k short.
+        let sell = SizingRequest::new("BTC-USD", OrderSide::Sell, Decimal::from(30000));
+        assert_eq!(
+            sizer.size(&account(), &sell).unwrap().quantity,
+            Decimal::from(1)
+        );
+
+        // 30k gross is held; a new symbol only gets the remaining 20k of exposure.
+        let sizer = PositionSizer::new(SizingMethod::Fixed {
+            quantity: Decimal::from(300),
+        })
+        .with_limits(limits);
+        let sol = SizingRequest::new("SOL-USD", OrderSide::Buy, Decimal::from(100));
+        let sizing = sizer.size(&account(), &sol).unwrap();
+        assert_eq!(sizing.quantity, Decimal::from(200));
+        assert_eq!(sizing.limited_by, Some(SizingLimit::GrossExposure));
+    }
+}
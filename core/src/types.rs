@@ -13,10 +13,12 @@ use crate::error::{TradingError, TradingResult};
 // Re-export arbitrage types for integration
 pub use arbitrage_engine::{
     AllocationPriority, AllocationRequest, ArbitrageConfig, ArbitrageOpportunity,
-    ExecutionComplexity, TimeSensitivity, VolatilityScore,
+    ExecutionComplexity, OpportunityKind, TimeSensitivity, VolatilityScore,
 };
 
-pub use exchange_connectors::{ExchangeId, TransferRequest, TransferStatus, TransferUrgency};
+pub use exchange_connectors::{
+    ExchangeId, FeeStructure, TransferRequest, TransferStatus, TransferUrgency,
+};
 
 /// Unique identifier for trading entities
 pub type OrderId = Uuid;
@@ -152,7 +154,7 @@ impl OrderType {
 }
 
 /// Buy or sell side of an order
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OrderSide {
     Buy,
     Sell,
@@ -497,29 +499,6 @@ pub struct TradingPlatform {
     pub metadata: HashMap<String, String>,
 }
 
-/// Fee structure for a trading platform
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FeeStructure {
-    /// Maker fee (negative means rebate)
-    pub maker_fee: Decimal,
-
-    /// Taker fee
-    pub taker_fee: Decimal,
-
-    /// Withdrawal fees per symbol
-    pub withdrawal_fees: HashMap<Symbol, Decimal>,
-}
-
-impl Default for FeeStructure {
-    fn default() -> Self {
-        Self {
-            maker_fee: Decimal::new(-1, 4), // -0.0001 (0.01% rebate)
-            taker_fee: Decimal::new(1, 3),  // 0.001 (0.1%)
-            withdrawal_fees: HashMap::new(),
-        }
-    }
-}
-
 /// Rate limiting configuration for API calls
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimits {
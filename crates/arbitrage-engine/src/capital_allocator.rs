@@ -2,10 +2,33 @@
 //!
 //! This module implements dynamic capital allocation and reallocation across
 //! multiple exchanges to maximize arbitrage opportunities and returns.
-
-use crate::{AllocationPriority, AllocationRequest, ArbitrageError, ArbitrageResult};
+//!
+//! Strategic rebalancing is a planner: each cycle [`RebalancePlanner`] turns
+//! the current balances and the [`AllocationStrategy`] targets into a
+//! [`RebalancePlan`] per managed currency, published through
+//! [`CapitalAllocator::get_rebalance_plans`] for an operator or external
+//! executor to act on, since none of the shipped connectors implement
+//! [`ExchangeConnector::transfer_funds`]. Explicit allocation requests still
+//! go through `transfer_funds` and are retried until the venue accepts them.
+//!
+//! Every transfer that is actually submitted, whether by an allocation
+//! request or by whoever executes a plan ([`CapitalAllocator::record_plan_transfer`]),
+//! is tracked until it settles. Each rebalancing cycle polls the source venue
+//! through [`ExchangeConnector::get_transfer_status`], executors can report
+//! progress through [`CapitalAllocator::update_transfer_status`], and transfers
+//! still unsettled [`RebalanceConfig::transfer_grace_secs`] after their expected
+//! arrival are treated as failed. Arbitrage legs that would spend funds still in
+//! transit are held back, and currencies with transfers in flight are not
+//! re-planned until they land.
+
+use crate::opportunity_detector::OpportunityDetector;
+use crate::rebalancing::{
+    RebalanceAction, RebalanceConfig, RebalancePlan, RebalancePlanner, TrackedTransfer,
+};
+use crate::{AllocationPriority, AllocationRequest, ArbitrageError, ArbitrageLeg, ArbitrageResult};
 use exchange_connectors::{
-    Balance, ExchangeConnector, ExchangeId, TransferRequest, TransferUrgency,
+    Balance, ExchangeConnector, ExchangeId, OrderSide, TransferRequest, TransferStatus,
+    TransferUrgency,
 };
 use rust_decimal::Decimal;
 use std::collections::HashMap;
@@ -32,17 +55,31 @@ pub struct CapitalAllocator {
     pending_allocations: Arc<RwLock<HashMap<Uuid, AllocationRequest>>>,
     target_allocations: Arc<RwLock<HashMap<ExchangeId, Decimal>>>,
     current_balances: Arc<RwLock<HashMap<ExchangeId, Vec<Balance>>>>,
+    planner: RebalancePlanner,
+    rebalance_plans: Arc<RwLock<HashMap<String, RebalancePlan>>>,
+    transfers: Arc<RwLock<HashMap<Uuid, TrackedTransfer>>>,
 }
 
 impl CapitalAllocator {
     /// Create a new capital allocator
     pub fn new(exchanges: HashMap<ExchangeId, Arc<dyn ExchangeConnector>>) -> Self {
+        Self::with_config(exchanges, RebalanceConfig::default())
+    }
+
+    /// Create a capital allocator with explicit rebalancing configuration
+    pub fn with_config(
+        exchanges: HashMap<ExchangeId, Arc<dyn ExchangeConnector>>,
+        rebalance_config: RebalanceConfig,
+    ) -> Self {
         Self {
             exchanges,
             allocation_strategy: Arc::new(RwLock::new(AllocationStrategy::Aggressive)),
             pending_allocations: Arc::new(RwLock::new(HashMap::new())),
             target_allocations: Arc::new(RwLock::new(HashMap::new())),
             current_balances: Arc::new(RwLock::new(HashMap::new())),
+            planner: RebalancePlanner::new(rebalance_config),
+            rebalance_plans: Arc::new(RwLock::new(HashMap::new())),
+            transfers: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -123,17 +160,20 @@ impl CapitalAllocator {
         Ok(request_id)
     }
 
-    /// Process pending allocations and execute transfers
+    /// Process pending allocations and refresh the strategic rebalancing plans
     pub async fn rebalance_capital(&self) -> ArbitrageResult<()> {
         debug!("⚖️ Starting capital rebalancing cycle");
 
         // Update current balances
         self.update_current_balances().await?;
 
+        // Settle or expire transfers before anything relies on them
+        self.refresh_transfers(chrono::Utc::now()).await;
+
         // Process high-priority allocations first
         self.process_pending_allocations().await?;
 
-        // Perform strategic rebalancing
+        // Plan strategic rebalancing
         self.perform_strategic_rebalancing().await?;
 
         debug!("✅ Capital rebalancing cycle completed");
@@ -168,6 +208,145 @@ impl CapitalAllocator {
         distribution
     }
 
+    /// Plan the moves that bring `currency` to the current target allocation
+    pub async fn plan_rebalance(&self, currency: &str) -> RebalancePlan {
+        let targets = self.target_allocations.read().await.clone();
+        let balances = self.get_capital_distribution().await;
+        self.planner.plan(currency, &balances, &targets)
+    }
+
+    /// Latest strategic rebalancing plan per managed currency.
+    ///
+    /// Plans are advisory: the allocator never submits them, so they describe
+    /// what it would take to reach the targets as of the last cycle.
+    pub async fn get_rebalance_plans(&self) -> HashMap<String, RebalancePlan> {
+        self.rebalance_plans.read().await.clone()
+    }
+
+    /// Record a plan transfer the caller has submitted to the source venue.
+    ///
+    /// Its net amount counts as in flight towards the destination until
+    /// [`update_transfer_status`](Self::update_transfer_status) settles it.
+    pub async fn record_plan_transfer(
+        &self,
+        action: &RebalanceAction,
+        venue_transfer_id: String,
+    ) -> ArbitrageResult<Uuid> {
+        let RebalanceAction::Transfer {
+            from_exchange,
+            to_exchange,
+            currency,
+            amount,
+            confirmation_secs,
+            ..
+        } = action
+        else {
+            return Err(ArbitrageError::Configuration(
+                "Only transfer actions move funds between venues".to_string(),
+            ));
+        };
+
+        let now = chrono::Utc::now();
+        let transfer = TrackedTransfer {
+            id: Uuid::new_v4(),
+            venue_transfer_id,
+            from_exchange: *from_exchange,
+            to_exchange: *to_exchange,
+            currency: currency.clone(),
+            amount: *amount,
+            net_amount: action.net_amount(),
+            status: TransferStatus::Pending,
+            initiated_at: now,
+            expected_arrival: now + chrono::Duration::seconds(*confirmation_secs as i64),
+        };
+        Ok(self.track_transfer(transfer).await)
+    }
+
+    /// Advance a tracked transfer; settled and failed transfers stop being tracked
+    pub async fn update_transfer_status(
+        &self,
+        id: Uuid,
+        status: TransferStatus,
+    ) -> ArbitrageResult<()> {
+        let mut transfers = self.transfers.write().await;
+        let transfer = transfers
+            .get_mut(&id)
+            .ok_or_else(|| ArbitrageError::Configuration(format!("Unknown transfer: {}", id)))?;
+        if status != transfer.status {
+            info!(
+                "🔁 Transfer {} of {} {} {:?} -> {:?}",
+                transfer.venue_transfer_id,
+                transfer.amount,
+                transfer.currency,
+                transfer.status,
+                status
+            );
+        }
+        transfer.status = status;
+        if !transfer.is_in_flight() {
+            transfers.remove(&id);
+        }
+        Ok(())
+    }
+
+    /// Transfers still in transit
+    pub async fn get_transfers(&self) -> Vec<TrackedTransfer> {
+        self.transfers.read().await.values().cloned().collect()
+    }
+
+    /// Amount of `currency` currently in transit towards `exchange`
+    pub async fn inbound_in_flight(&self, exchange: ExchangeId, currency: &str) -> Decimal {
+        self.transfers
+            .read()
+            .await
+            .values()
+            .filter(|transfer| {
+                transfer.is_in_flight()
+                    && transfer.to_exchange == exchange
+                    && transfer.currency == currency
+            })
+            .map(|transfer| transfer.net_amount)
+            .sum()
+    }
+
+    /// Reject a leg whose funding depends on a transfer that has not landed yet.
+    ///
+    /// Legs are only blocked when settled funds fall short *and* inbound
+    /// transfers exist for the currency; venues the allocator has no view of
+    /// are left to the execution engine.
+    pub async fn check_leg_funding(&self, leg: &ArbitrageLeg) -> ArbitrageResult<()> {
+        let Some((base, quote)) = OpportunityDetector::split_symbol(&leg.symbol) else {
+            return Ok(());
+        };
+        let (currency, required) = match leg.side {
+            OrderSide::Buy => (quote, leg.price * leg.quantity),
+            OrderSide::Sell => (base, leg.quantity),
+        };
+
+        let pending = self.inbound_in_flight(leg.exchange, &currency).await;
+        if pending.is_zero() {
+            return Ok(());
+        }
+
+        let settled = self
+            .current_balances
+            .read()
+            .await
+            .get(&leg.exchange)
+            .and_then(|balances| balances.iter().find(|b| b.currency == currency))
+            .map(|balance| balance.available)
+            .unwrap_or(Decimal::ZERO);
+
+        if settled < required {
+            return Err(ArbitrageError::FundsInFlight {
+                exchange: leg.exchange,
+                currency,
+                pending,
+            });
+        }
+        Ok(())
+    }
+
     /// Emergency capital reallocation (Gekko mode activation)
     pub async fn emergency_reallocation(
         &self,
@@ -183,38 +362,37 @@ impl CapitalAllocator {
             percentage * 100.0
         );
 
-        let mut allocation_requests = Vec::new();
-        let balances = self.current_balances.read().await;
-
-        for (exchange_id, exchange_balances) in balances.iter() {
-            if *exchange_id == target_exchange {
-                continue; // Don't transfer to itself
-            }
+        let fraction = Decimal::from_f64_retain(percentage).unwrap_or_default();
+        let balances = self.get_capital_distribution().await;
+        let plan = self
+            .planner
+            .plan_consolidation(&currency, &balances, target_exchange, fraction);
 
-            // Find the currency balance
-            if let Some(balance) = exchange_balances.iter().find(|b| b.currency == currency) {
-                let transfer_amount =
-                    balance.available * Decimal::from_f64_retain(percentage).unwrap_or_default();
-
-                if transfer_amount > Decimal::ZERO {
-                    let request_id = self
-                        .request_allocation(
-                            *exchange_id,
-                            target_exchange,
-                            currency.clone(),
-                            transfer_amount,
-                            AllocationPriority::Emergency,
-                            "Emergency Gekko-style capital reallocation".to_string(),
-                        )
-                        .await?;
-
-                    allocation_requests.push(request_id);
-
-                    error!(
-                        "💀 Emergency transfer: {} {} from {:?} to {:?}",
-                        transfer_amount, currency, exchange_id, target_exchange
-                    );
-                }
+        let mut allocation_requests = Vec::new();
+        for action in plan.transfers() {
+            if let RebalanceAction::Transfer {
+                from_exchange,
+                amount,
+                ..
+            } = action
+            {
+                let request_id = self
+                    .request_allocation(
+                        *from_exchange,
+                        target_exchange,
+                        currency.clone(),
+                        *amount,
+                        AllocationPriority::Emergency,
+                        "Emergency Gekko-style capital reallocation".to_string(),
+                    )
+                    .await?;
+
+                allocation_requests.push(request_id);
+
+                error!(
+                    "💀 Emergency transfer: {} {} from {:?} to {:?}",
+                    amount, currency, from_exchange, target_exchange
+                );
             }
         }
 
@@ -353,6 +531,25 @@ impl CapitalAllocator {
                         "💸 Transfer initiated: {} ({})",
                         transfer_id, request.reason
                     );
+                    let terms = self
+                        .planner
+                        .config()
+                        .terms_for(request.from_exchange, &request.currency);
+                    let now = chrono::Utc::now();
+                    self.track_transfer(TrackedTransfer {
+                        id: request.id,
+                        venue_transfer_id: transfer_id,
+                        from_exchange: request.from_exchange,
+                        to_exchange: request.to_exchange,
+                        currency: request.currency.clone(),
+                        amount: request.amount,
+                        net_amount: (request.amount - terms.fee).max(Decimal::ZERO),
+                        status: TransferStatus::Pending,
+                        initiated_at: now,
+                        expected_arrival: now
+                            + chrono::Duration::seconds(terms.confirmation_secs as i64),
+                    })
+                    .await;
                     Ok(())
                 }
                 Err(e) => Err(ArbitrageError::Exchange(format!("Transfer failed: {}", e))),
@@ -365,32 +562,102 @@ impl CapitalAllocator {
         }
     }
 
-    /// Perform strategic rebalancing based on current strategy
-    async fn perform_strategic_rebalancing(&self) -> ArbitrageResult<()> {
-        let strategy = self.allocation_strategy.read().await;
-        let targets = self.target_allocations.read().await;
-        let balances = self.current_balances.read().await;
+    /// Record a submitted transfer so its funds are treated as in flight
+    async fn track_transfer(&self, transfer: TrackedTransfer) -> Uuid {
+        let id = transfer.id;
+        self.transfers.write().await.insert(id, transfer);
+        id
+    }
 
-        // Implement strategic rebalancing logic based on current strategy
-        match &*strategy {
-            AllocationStrategy::Aggressive => {
-                // Concentrate capital on highest opportunity exchanges
-                // This would involve more complex logic based on current opportunities
-                debug!("🔥 Aggressive rebalancing strategy active");
+    /// Poll the source venue of every transfer in flight, then give up on the
+    /// ones overdue as of `now`
+    async fn refresh_transfers(&self, now: chrono::DateTime<chrono::Utc>) {
+        let in_flight: Vec<(Uuid, ExchangeId, String)> = self
+            .transfers
+            .read()
+            .await
+            .values()
+            .filter(|transfer| transfer.is_in_flight())
+            .map(|transfer| {
+                (
+                    transfer.id,
+                    transfer.from_exchange,
+                    transfer.venue_transfer_id.clone(),
+                )
+            })
+            .collect();
+
+        for (id, exchange, venue_transfer_id) in in_flight {
+            let Some(connector) = self.exchanges.get(&exchange) else {
+                continue;
+            };
+            match connector.get_transfer_status(&venue_transfer_id).await {
+                Ok(status) => {
+                    if let Err(e) = self.update_transfer_status(id, status).await {
+                        debug!("Transfer {} no longer tracked: {}", venue_transfer_id, e);
+                    }
+                }
+                Err(e) => {
+                    debug!(
+                        "Status of transfer {} unavailable from {:?}: {}",
+                        venue_transfer_id, exchange, e
+                    );
+                }
             }
-            AllocationStrategy::Balanced => {
-                // Ensure roughly equal distribution
-                debug!("⚖️ Balanced rebalancing strategy active");
+        }
+
+        let grace_secs = self.planner.config().transfer_grace_secs;
+        self.transfers.write().await.retain(|_, transfer| {
+            if !transfer.is_overdue(now, grace_secs) {
+                return true;
             }
-            AllocationStrategy::Weighted(weights) => {
-                // Use custom weights
+            warn!(
+                "⌛ Transfer {} of {} {} expected by {} never settled; treating it as failed",
+                transfer.venue_transfer_id,
+                transfer.amount,
+                transfer.currency,
+                transfer.expected_arrival
+            );
+            false
+        });
+    }
+
+    /// Plan strategic rebalancing towards the current strategy's targets
+    ///
+    /// Plans replace the previous cycle's; balanced currencies drop out, and
+    /// so do currencies with transfers in flight, whose balances are not final.
+    async fn perform_strategic_rebalancing(&self) -> ArbitrageResult<()> {
+        let mut plans = HashMap::new();
+        for currency in &self.planner.config().managed_currencies {
+            let in_flight = self
+                .transfers
+                .read()
+                .await
+                .values()
+                .any(|transfer| &transfer.currency == currency && transfer.is_in_flight());
+            if in_flight {
                 debug!(
-                    "🎯 Weighted rebalancing strategy active with {} weights",
-                    weights.len()
+                    "Skipping {} rebalancing: transfers still in flight",
+                    currency
                 );
+                continue;
             }
+
+            let plan = self.plan_rebalance(currency).await;
+            if plan.is_empty() {
+                continue;
+            }
+            info!(
+                "⚖️ Rebalancing plan for {}: {} conversions, {} transfers, {} in withdrawal fees",
+                currency,
+                plan.actions.len() - plan.transfers().count(),
+                plan.transfers().count(),
+                plan.total_fees
+            );
+            plans.insert(currency.clone(), plan);
         }
 
+        *self.rebalance_plans.write().await = plans;
         Ok(())
     }
 
@@ -466,4 +733,252 @@ mod tests {
         weights.insert(ExchangeId::BinanceUs, 0.6);
         let _weighted = AllocationStrategy::Weighted(weights);
     }
+
+    #[tokio::test]
+    async fn test_strategic_rebalancing_publishes_plans_without_submitting() {
+        let config = RebalanceConfig {
+            managed_currencies: vec!["USD".to_string(), "BTC".to_string()],
+            ..RebalanceConfig::default()
+        };
+        let allocator = CapitalAllocator::with_config(HashMap::new(), config);
+        allocator
+            .set_allocation_strategy(AllocationStrategy::Weighted(HashMap::from([
+                (ExchangeId::Kraken, 0.5),
+                (ExchangeId::BinanceUs, 0.5),
+            ])))
+            .await;
+
+        let balance = |currency: &str, amount: i64| Balance {
+            currency: currency.to_string(),
+            available: Decimal::new(amount, 0),
+            total: Decimal::new(amount, 0),
+            hold: Decimal::ZERO,
+        };
+        {
+            let mut balances = allocator.current_balances.write().await;
+            balances.insert(
+                ExchangeId::Kraken,
+                vec![balance("USD", 9000), balance("BTC", 1)],
+            );
+            balances.insert(
+                ExchangeId::BinanceUs,
+                vec![balance("USD", 1000), balance("BTC", 1)],
+            );
+        }
+
+        allocator.perform_strategic_rebalancing().await.unwrap();
+
+        let plans = allocator.get_rebalance_plans().await;
+        // BTC is already on target, so only USD needs a plan
+        assert_eq!(plans.len(), 1);
+        let usd = &plans["USD"];
+        assert_eq!(
+            usd.actions,
+            vec![RebalanceAction::Transfer {
+                from_exchange: ExchangeId::Kraken,
+                to_exchange: ExchangeId::BinanceUs,
+                currency: "USD".to_string(),
+                amount: Decimal::new(4000, 0),
+                fee: Decimal::ZERO,
+                confirmation_secs: 30 * 60,
+            }]
+        );
+        // Planning never turns into allocation requests
+        assert!(allocator.pending_allocations.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_legs_depending_on_in_flight_plan_transfers_are_blocked() {
+        let allocator = CapitalAllocator::new(HashMap::new());
+        allocator.current_balances.write().await.insert(
+            ExchangeId::BinanceUs,
+            vec![Balance {
+                currency: "USD".to_string(),
+                available: Decimal::new(1000, 0),
+                total: Decimal::new(1000, 0),
+                hold: Decimal::ZERO,
+            }],
+        );
+
+        let leg = ArbitrageLeg {
+            exchange: ExchangeId::BinanceUs,
+            symbol: "BTC-USD".to_string(),
+            side: OrderSide::Buy,
+            price: Decimal::new(50000, 0),
+            quantity: Decimal::new(1, 1),
+            fee: Decimal::ZERO,
+        };
+        // No transfers in flight: the allocator does not block the leg
+        assert!(allocator.check_leg_funding(&leg).await.is_ok());
+
+        let action = RebalanceAction::Transfer {
+            from_exchange: ExchangeId::Kraken,
+            to_exchange: ExchangeId::BinanceUs,
+            currency: "USD".to_string(),
+            amount: Decimal::new(5005, 0),
+            fee: Decimal::new(5, 0),
+            confirmation_secs: 600,
+        };
+        let id = allocator
+            .record_plan_transfer(&action, "kraken-wd-1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            allocator
+                .inbound_in_flight(ExchangeId::BinanceUs, "USD")
+                .await,
+            Decimal::new(5000, 0)
+        );
+
+        // 5000 USD needed but only 1000 settled; the rest is still in transit
+        assert!(matches!(
+            allocator.check_leg_funding(&leg).await,
+            Err(ArbitrageError::FundsInFlight { .. })
+        ));
+        let small_leg = ArbitrageLeg {
+            quantity: Decimal::new(1, 2),
+            ..leg.clone()
+        };
+        assert!(allocator.check_leg_funding(&small_leg).await.is_ok());
+
+        // Conversions never leave the venue, so there is nothing to track
+        let conversion = RebalanceAction::Convert {
+            exchange: ExchangeId::BinanceUs,
+            from_currency: "USDT".to_string(),
+            to_currency: "USD".to_string(),
+            amount: Decimal::new(100, 0),
+        };
+        assert!(allocator
+            .record_plan_transfer(&conversion, "n/a".to_string())
+            .await
+            .is_err());
+
+        allocator
+            .update_transfer_status(id, TransferStatus::Processing)
+            .await
+            .unwrap();
+        assert_eq!(allocator.get_transfers().await.len(), 1);
+        allocator
+            .update_transfer_status(id, TransferStatus::Completed)
+            .await
+            .unwrap();
+        assert!(allocator.get_transfers().await.is_empty());
+        assert!(allocator.check_leg_funding(&leg).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_currencies_with_transfers_in_flight_are_not_replanned() {
+        let config = RebalanceConfig {
+            managed_currencies: vec!["USD".to_string()],
+            ..RebalanceConfig::default()
+        };
+        let allocator = CapitalAllocator::with_config(HashMap::new(), config);
+        allocator
+            .set_allocation_strategy(AllocationStrategy::Weighted(HashMap::from([
+                (ExchangeId::Kraken, 0.5),
+                (ExchangeId::BinanceUs, 0.5),
+            ])))
+            .await;
+        let balance = |amount: i64| Balance {
+            currency: "USD".to_string(),
+            available: Decimal::new(amount, 0),
+            total: Decimal::new(amount, 0),
+            hold: Decimal::ZERO,
+        };
+        {
+            let mut balances = allocator.current_balances.write().await;
+            balances.insert(ExchangeId::Kraken, vec![balance(9000)]);
+            balances.insert(ExchangeId::BinanceUs, vec![balance(1000)]);
+        }
+
+        allocator.perform_strategic_rebalancing().await.unwrap();
+        let plan = allocator.get_rebalance_plans().await.remove("USD").unwrap();
+        let transfer = plan.transfers().next().unwrap();
+        let id = allocator
+            .record_plan_transfer(transfer, "kraken-wd-2".to_string())
+            .await
+            .unwrap();
+
+        // The withdrawal has left Kraken but not reached Binance.US yet
+        allocator
+            .current_balances
+            .write()
+            .await
+            .insert(ExchangeId::Kraken, vec![balance(5000)]);
+        allocator.perform_strategic_rebalancing().await.unwrap();
+        assert!(allocator.get_rebalance_plans().await.is_empty());
+
+        allocator
+            .update_transfer_status(id, TransferStatus::Failed)
+            .await
+            .unwrap();
+        allocator.perform_strategic_rebalancing().await.unwrap();
+        assert!(allocator.get_rebalance_plans().await.contains_key("USD"));
+    }
+
+    #[tokio::test]
+    async fn test_overdue_transfers_stop_blocking_legs_and_rebalancing() {
+        let config = RebalanceConfig {
+            managed_currencies: vec!["USD".to_string()],
+            transfer_grace_secs: 600,
+            ..RebalanceConfig::default()
+        };
+        let allocator = CapitalAllocator::with_config(HashMap::new(), config);
+        allocator
+            .set_allocation_strategy(AllocationStrategy::Weighted(HashMap::from([
+                (ExchangeId::Kraken, 0.5),
+                (ExchangeId::BinanceUs, 0.5),
+            ])))
+            .await;
+        let balance = |amount: i64| Balance {
+            currency: "USD".to_string(),
+            available: Decimal::new(amount, 0),
+            total: Decimal::new(amount, 0),
+            hold: Decimal::ZERO,
+        };
+        {
+            let mut balances = allocator.current_balances.write().await;
+            balances.insert(ExchangeId::Kraken, vec![balance(9000)]);
+            balances.insert(ExchangeId::BinanceUs, vec![balance(1000)]);
+        }
+
+        let action = RebalanceAction::Transfer {
+            from_exchange: ExchangeId::Kraken,
+            to_exchange: ExchangeId::BinanceUs,
+            currency: "USD".to_string(),
+            amount: Decimal::new(4000, 0),
+            fee: Decimal::ZERO,
+            confirmation_secs: 30 * 60,
+        };
+        allocator
+            .record_plan_transfer(&action, "kraken-wd-3".to_string())
+            .await
+            .unwrap();
+        let expected_arrival = allocator.get_transfers().await[0].expected_arrival;
+        let leg = ArbitrageLeg {
+            exchange: ExchangeId::BinanceUs,
+            symbol: "BTC-USD".to_string(),
+            side: OrderSide::Buy,
+            price: Decimal::new(50000, 0),
+            quantity: Decimal::new(1, 1),
+            fee: Decimal::ZERO,
+        };
+
+        // Late, but still within the grace period
+        allocator
+            .refresh_transfers(expected_arrival + chrono::Duration::seconds(300))
+            .await;
+        assert_eq!(allocator.get_transfers().await.len(), 1);
+        assert!(allocator.check_leg_funding(&leg).await.is_err());
+        allocator.perform_strategic_rebalancing().await.unwrap();
+        assert!(allocator.get_rebalance_plans().await.is_empty());
+
+        allocator
+            .refresh_transfers(expected_arrival + chrono::Duration::seconds(601))
+            .await;
+        assert!(allocator.get_transfers().await.is_empty());
+        assert!(allocator.check_leg_funding(&leg).await.is_ok());
+        allocator.perform_strategic_rebalancing().await.unwrap();
+        assert!(allocator.get_rebalance_plans().await.contains_key("USD"));
+    }
 }
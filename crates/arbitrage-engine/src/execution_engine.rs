@@ -26,8 +26,9 @@ impl ExecutionEngine {
         opportunity: &ArbitrageOpportunity,
     ) -> ArbitrageResult<()> {
         info!(
-            "⚡ Executing arbitrage: {} on {:?} -> {:?}",
-            opportunity.symbol, opportunity.buy_exchange, opportunity.sell_exchange
+            "⚡ Executing arbitrage: {} ({} legs)",
+            opportunity.symbol,
+            opportunity.legs.len()
         );
 
         // Placeholder implementation - real version would:
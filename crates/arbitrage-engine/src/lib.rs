@@ -10,7 +10,7 @@
 //! - Real-time cross-exchange arbitrage execution
 //! - Aggressive risk/reward optimization
 
-use exchange_connectors::{ExchangeConnector, ExchangeId};
+use exchange_connectors::{ExchangeConnector, ExchangeId, FeeStructure, OrderSide};
 use neural_engine::NeuralEngine;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -23,12 +23,16 @@ use uuid::Uuid;
 
 pub mod capital_allocator;
 pub mod execution_engine;
+pub mod market_depth;
 pub mod opportunity_detector;
+pub mod rebalancing;
 pub mod volatility_scanner;
 
 pub use capital_allocator::CapitalAllocator;
 pub use execution_engine::ExecutionEngine;
-pub use opportunity_detector::OpportunityDetector;
+pub use market_depth::{DepthLevel, OrderBookDepth};
+pub use opportunity_detector::{CrossExchangeSizing, OpportunityDetector};
+pub use rebalancing::{RebalanceAction, RebalanceConfig, RebalancePlan, RebalancePlanner};
 pub use volatility_scanner::VolatilityScanner;
 
 /// Arbitrage engine error types
@@ -60,6 +64,13 @@ pub enum ArbitrageError {
 
     #[error("Configuration error: {0}")]
     Configuration(String),
+
+    #[error("Funds in flight: {pending} {currency} still in transit to {exchange:?}")]
+    FundsInFlight {
+        exchange: ExchangeId,
+        currency: String,
+        pending: Decimal,
+    },
 }
 
 pub type ArbitrageResult<T> = Result<T, ArbitrageError>;
@@ -79,25 +90,69 @@ pub struct VolatilityScore {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
-/// Cross-exchange arbitrage opportunity
+/// Cross-exchange or single-venue triangular arbitrage opportunity
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArbitrageOpportunity {
     pub id: Uuid,
     pub symbol: String,
-    pub buy_exchange: ExchangeId,
-    pub sell_exchange: ExchangeId,
-    pub buy_price: Decimal,
-    pub sell_price: Decimal,
-    pub price_difference: Decimal,
+    /// Venues, prices and size, in the units of the opportunity's shape
+    pub kind: OpportunityKind,
     pub profit_percentage: f64,
+    /// Net profit, denominated in `profit_currency`
     pub estimated_profit: Decimal,
+    /// Currency `estimated_profit` is denominated in
+    pub profit_currency: String,
     pub confidence_score: f64, // AI confidence in opportunity
-    pub max_quantity: Decimal, // Maximum tradeable quantity
     pub time_sensitivity: TimeSensitivity,
     pub risk_score: f64, // 0.0 to 1.0, higher = riskier
     pub execution_complexity: ExecutionComplexity,
     pub detected_at: chrono::DateTime<chrono::Utc>,
     pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// Ordered legs to execute: two for cross-exchange, three for triangular
+    #[serde(default)]
+    pub legs: Vec<ArbitrageLeg>,
+}
+
+/// Shape of an arbitrage opportunity and the units its size is quoted in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OpportunityKind {
+    /// Buy `symbol` on one venue and sell it on another
+    CrossExchange {
+        buy_exchange: ExchangeId,
+        sell_exchange: ExchangeId,
+        /// Volume-weighted average buy price
+        buy_price: Decimal,
+        /// Volume-weighted average sell price
+        sell_price: Decimal,
+        price_difference: Decimal,
+        /// Base quantity bought on one venue and sold on the other
+        max_quantity: Decimal,
+    },
+    /// Cycle through three pairs on one venue, starting and ending in `home_currency`
+    Triangular {
+        exchange: ExchangeId,
+        home_currency: String,
+        /// Amount of `home_currency` fed into the first leg
+        start_amount: Decimal,
+        /// Amount of `home_currency` returned by the last leg, net of costs
+        end_amount: Decimal,
+    },
+}
+
+/// Single order leg of an arbitrage opportunity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbitrageLeg {
+    pub exchange: ExchangeId,
+    pub symbol: String,
+    pub side: OrderSide,
+    /// Volume-weighted average price across the book levels consumed
+    pub price: Decimal,
+    /// Base currency quantity
+    pub quantity: Decimal,
+    /// Fee charged on this leg: in the quote currency for cross-exchange legs,
+    /// in the currency received for each hop of a triangular cycle
+    pub fee: Decimal,
 }
 
 /// Time sensitivity levels for arbitrage opportunities
@@ -154,7 +209,7 @@ pub struct ArbitrageConfig {
     /// Minimum confidence score required (0.0 to 1.0)
     pub min_confidence_score: f64,
 
-    /// Maximum position size per opportunity
+    /// Maximum position size per opportunity, as quote-currency notional
     pub max_position_size: Decimal,
 
     /// Maximum daily capital allocation
@@ -172,6 +227,37 @@ pub struct ArbitrageConfig {
 
     /// Enable aggressive mode (Gordon Gekko style)
     pub gekko_mode: bool,
+
+    /// Per-venue fee schedules; venues without an entry use `FeeStructure::default()`
+    #[serde(default)]
+    pub venue_fees: HashMap<ExchangeId, FeeStructure>,
+
+    /// Expected slippage per leg in basis points, charged on traded notional
+    #[serde(default = "default_expected_slippage_bps")]
+    pub expected_slippage_bps: f64,
+
+    /// Currencies that triangular cycles start and end in
+    #[serde(default = "default_triangular_base_currencies")]
+    pub triangular_base_currencies: Vec<String>,
+
+    /// Cross-exchange inventory rebalancing: managed currencies, withdrawal terms
+    #[serde(default)]
+    pub rebalance: RebalanceConfig,
+}
+
+fn default_expected_slippage_bps() -> f64 {
+    2.0
+}
+
+fn default_triangular_base_currencies() -> Vec<String> {
+    vec!["USD".to_string(), "USDT".to_string(), "USDC".to_string()]
+}
+
+impl ArbitrageConfig {
+    /// Fee schedule for a venue
+    pub fn fees_for(&self, exchange: ExchangeId) -> FeeStructure {
+        self.venue_fees.get(&exchange).cloned().unwrap_or_default()
+    }
 }
 
 impl Default for ArbitrageConfig {
@@ -187,6 +273,10 @@ impl Default for ArbitrageConfig {
             allocation_aggressiveness: 0.8, // Highly aggressive
             scan_frequency_ms: 100,         // 100ms scanning
             gekko_mode: true,               // "Greed is good"
+            venue_fees: HashMap::new(),
+            expected_slippage_bps: default_expected_slippage_bps(),
+            triangular_base_currencies: default_triangular_base_currencies(),
+            rebalance: RebalanceConfig::default(),
         }
     }
 }
@@ -240,7 +330,10 @@ impl ArbitrageEngine {
         exchanges: HashMap<ExchangeId, Arc<dyn ExchangeConnector>>,
     ) -> Self {
         let volatility_scanner = Arc::new(VolatilityScanner::new(exchanges.clone()));
-        let capital_allocator = Arc::new(CapitalAllocator::new(exchanges.clone()));
+        let capital_allocator = Arc::new(CapitalAllocator::with_config(
+            exchanges.clone(),
+            config.rebalance.clone(),
+        ));
         let opportunity_detector = Arc::new(OpportunityDetector::new(config.clone(), None));
         let execution_engine = Arc::new(ExecutionEngine::new(exchanges.clone()));
 
@@ -265,7 +358,10 @@ impl ArbitrageEngine {
         neural_engine: Arc<NeuralEngine>,
     ) -> Self {
         let volatility_scanner = Arc::new(VolatilityScanner::new(exchanges.clone()));
-        let capital_allocator = Arc::new(CapitalAllocator::new(exchanges.clone()));
+        let capital_allocator = Arc::new(CapitalAllocator::with_config(
+            exchanges.clone(),
+            config.rebalance.clone(),
+        ));
         let opportunity_detector = Arc::new(OpportunityDetector::new(
             config.clone(),
             Some(Arc::clone(&neural_engine)),
@@ -392,6 +488,18 @@ impl ArbitrageEngine {
             .await;
     }
 
+    /// Process a Level 2 snapshot to update depth-aware detection
+    pub async fn process_order_book(
+        &self,
+        symbol: &str,
+        depth: OrderBookDepth,
+        exchange_id: ExchangeId,
+    ) {
+        self.opportunity_detector
+            .update_order_book(symbol, depth, exchange_id)
+            .await;
+    }
+
     // Private implementation methods
 
     async fn start_volatility_scanning(
@@ -424,6 +532,7 @@ impl ArbitrageEngine {
         &self,
     ) -> ArbitrageResult<tokio::task::JoinHandle<ArbitrageResult<()>>> {
         let detector = Arc::clone(&self.opportunity_detector);
+        let allocator = Arc::clone(&self.capital_allocator);
         let opportunities = Arc::clone(&self.active_opportunities);
 
         let handle = tokio::spawn(async move {
@@ -436,8 +545,20 @@ impl ArbitrageEngine {
 
                 match detector.detect_opportunities().await {
                     Ok(new_opportunities) => {
+                        // Never act on legs that would spend funds still in transit
+                        let mut fundable = Vec::with_capacity(new_opportunities.len());
+                        'opportunities: for opp in new_opportunities {
+                            for leg in &opp.legs {
+                                if let Err(e) = allocator.check_leg_funding(leg).await {
+                                    debug!("Holding back {}: {}", opp.symbol, e);
+                                    continue 'opportunities;
+                                }
+                            }
+                            fundable.push(opp);
+                        }
+
                         let mut active = opportunities.write().await;
-                        for opp in new_opportunities {
+                        for opp in fundable {
                             active.insert(opp.id, opp);
                         }
                     }
@@ -556,20 +677,24 @@ mod tests {
         let opportunity = ArbitrageOpportunity {
             id: Uuid::new_v4(),
             symbol: "BTC-USD".to_string(),
-            buy_exchange: ExchangeId::Kraken,
-            sell_exchange: ExchangeId::BinanceUs,
-            buy_price: Decimal::new(50000, 0),
-            sell_price: Decimal::new(50250, 0),
-            price_difference: Decimal::new(250, 0),
+            kind: OpportunityKind::CrossExchange {
+                buy_exchange: ExchangeId::Kraken,
+                sell_exchange: ExchangeId::BinanceUs,
+                buy_price: Decimal::new(50000, 0),
+                sell_price: Decimal::new(50250, 0),
+                price_difference: Decimal::new(250, 0),
+                max_quantity: Decimal::new(5, 0),
+            },
             profit_percentage: 0.5,
             estimated_profit: Decimal::new(500, 0),
+            profit_currency: "USD".to_string(),
             confidence_score: 0.92,
-            max_quantity: Decimal::new(5, 0),
             time_sensitivity: TimeSensitivity::High,
             risk_score: 0.3,
             execution_complexity: ExecutionComplexity::Simple,
             detected_at: chrono::Utc::now(),
             expires_at: chrono::Utc::now() + chrono::Duration::seconds(30),
+            legs: Vec::new(),
         };
 
         assert_eq!(opportunity.symbol, "BTC-USD");
@@ -0,0 +1,174 @@
+//! Market Depth - Level 2 order book snapshots for depth-aware pricing
+//!
+//! The opportunity detector keeps one snapshot per venue and symbol and walks
+//! the visible levels to find what a given order size would actually fill at,
+//! instead of assuming unlimited liquidity at the top of book.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Single price level of an order book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepthLevel {
+    pub price: Decimal,
+    pub quantity: Decimal,
+}
+
+impl DepthLevel {
+    pub fn new(price: Decimal, quantity: Decimal) -> Self {
+        Self { price, quantity }
+    }
+}
+
+/// Result of walking one side of the book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthFill {
+    /// Amount of the input currency actually consumed
+    pub input_consumed: Decimal,
+    /// Amount of the output currency received (before fees)
+    pub output: Decimal,
+    /// Base quantity traded across all levels
+    pub base_quantity: Decimal,
+    /// Volume-weighted average execution price
+    pub average_price: Decimal,
+    /// True when the visible book ran out before the input was consumed
+    pub exhausted: bool,
+}
+
+/// Level 2 snapshot for one symbol on one venue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBookDepth {
+    /// Bids sorted from best (highest) to worst
+    pub bids: Vec<DepthLevel>,
+    /// Asks sorted from best (lowest) to worst
+    pub asks: Vec<DepthLevel>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl OrderBookDepth {
+    /// Build a snapshot, sorting levels and dropping empty ones
+    pub fn new(
+        mut bids: Vec<DepthLevel>,
+        mut asks: Vec<DepthLevel>,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        bids.retain(|level| level.quantity > Decimal::ZERO && level.price > Decimal::ZERO);
+        asks.retain(|level| level.quantity > Decimal::ZERO && level.price > Decimal::ZERO);
+        bids.sort_by_key(|level| std::cmp::Reverse(level.price));
+        asks.sort_by_key(|level| level.price);
+        Self {
+            bids,
+            asks,
+            timestamp,
+        }
+    }
+
+    pub fn best_bid(&self) -> Option<&DepthLevel> {
+        self.bids.first()
+    }
+
+    pub fn best_ask(&self) -> Option<&DepthLevel> {
+        self.asks.first()
+    }
+
+    /// Spend up to `quote_amount` lifting asks; output is base received
+    pub fn buy_with_quote(&self, quote_amount: Decimal) -> DepthFill {
+        let mut remaining = quote_amount;
+        let mut base = Decimal::ZERO;
+        for level in &self.asks {
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+            let level_cost = level.price * level.quantity;
+            if level_cost <= remaining {
+                base += level.quantity;
+                remaining -= level_cost;
+            } else {
+                base += remaining / level.price;
+                remaining = Decimal::ZERO;
+            }
+        }
+        let spent = quote_amount - remaining;
+        DepthFill {
+            input_consumed: spent,
+            output: base,
+            base_quantity: base,
+            average_price: average(spent, base),
+            exhausted: remaining > Decimal::ZERO,
+        }
+    }
+
+    /// Sell up to `base_amount` into bids; output is quote received
+    pub fn sell_base(&self, base_amount: Decimal) -> DepthFill {
+        let mut remaining = base_amount;
+        let mut quote = Decimal::ZERO;
+        for level in &self.bids {
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+            let take = remaining.min(level.quantity);
+            quote += take * level.price;
+            remaining -= take;
+        }
+        let sold = base_amount - remaining;
+        DepthFill {
+            input_consumed: sold,
+            output: quote,
+            base_quantity: sold,
+            average_price: average(quote, sold),
+            exhausted: remaining > Decimal::ZERO,
+        }
+    }
+}
+
+fn average(quote: Decimal, base: Decimal) -> Decimal {
+    if base.is_zero() {
+        Decimal::ZERO
+    } else {
+        quote / base
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn book() -> OrderBookDepth {
+        OrderBookDepth::new(
+            vec![
+                DepthLevel::new(dec!(99), dec!(1)),
+                DepthLevel::new(dec!(100), dec!(1)),
+            ],
+            vec![
+                DepthLevel::new(dec!(102), dec!(1)),
+                DepthLevel::new(dec!(101), dec!(1)),
+            ],
+            chrono::Utc::now(),
+        )
+    }
+
+    #[test]
+    fn test_levels_are_sorted_best_first() {
+        let book = book();
+        assert_eq!(book.best_bid().unwrap().price, dec!(100));
+        assert_eq!(book.best_ask().unwrap().price, dec!(101));
+    }
+
+    #[test]
+    fn test_walks_multiple_levels() {
+        let book = book();
+
+        let buy = book.buy_with_quote(dec!(152));
+        assert_eq!(buy.output, dec!(1.5));
+        assert!(!buy.exhausted);
+
+        let sell = book.sell_base(dec!(1.5));
+        assert_eq!(sell.output, dec!(149.5));
+        assert_eq!(sell.average_price, dec!(149.5) / dec!(1.5));
+
+        let too_big = book.sell_base(dec!(5));
+        assert!(too_big.exhausted);
+        assert_eq!(too_big.input_consumed, dec!(2));
+    }
+}
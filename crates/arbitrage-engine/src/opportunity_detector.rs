@@ -2,14 +2,20 @@
 //!
 //! This module implements sophisticated arbitrage opportunity detection using
 //! AI/ML models to identify profitable cross-exchange trading opportunities.
+//! Alongside simple cross-exchange spreads it searches each venue's order books
+//! for triangular cycles (e.g. USD -> BTC -> ETH -> USD on Kraken, or
+//! USD -> EUR -> GBP -> USD on OANDA), pricing every leg against visible depth
+//! and the venue's taker fee.
 
+use crate::market_depth::{DepthLevel, OrderBookDepth};
 use crate::{
-    ArbitrageConfig, ArbitrageOpportunity, ArbitrageResult, ExecutionComplexity, TimeSensitivity,
+    ArbitrageConfig, ArbitrageLeg, ArbitrageOpportunity, ArbitrageResult, ExecutionComplexity,
+    OpportunityKind, TimeSensitivity,
 };
 use chrono::Utc;
-use exchange_connectors::{ExchangeId, MarketTick};
-use neural_engine::{MarketDataInput, NeuralEngine};
-use rust_decimal::prelude::ToPrimitive;
+use exchange_connectors::{ExchangeId, MarketTick, OrderSide};
+use neural_engine::{MarketDataInput, NeuralEngine, PredictionOutcome};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -20,13 +26,71 @@ use uuid::Uuid;
 /// Thread-safe cache of latest market prices
 pub type PriceCache = Arc<RwLock<HashMap<String, HashMap<ExchangeId, MarketTick>>>>;
 
+/// Thread-safe cache of latest Level 2 snapshots, keyed by venue then symbol
+pub type DepthCache = Arc<RwLock<HashMap<ExchangeId, HashMap<String, OrderBookDepth>>>>;
+
+/// Maximum number of re-sizing passes when a triangle leg runs out of depth
+const MAX_SIZING_PASSES: usize = 4;
+
+/// Share of the fillable size kept when re-sizing, so rounding and fee drift
+/// between passes cannot push a leg back past the visible depth
+const DEPTH_HAIRCUT: Decimal = Decimal::from_parts(999, 0, 0, false, 3);
+
 /// Opportunity detector using AI/ML for arbitrage detection
 pub struct OpportunityDetector {
     config: ArbitrageConfig,
     price_cache: PriceCache,
+    depth_cache: DepthCache,
     neural_engine: Option<Arc<NeuralEngine>>,
 }
 
+/// Tradeable pair on a venue, split into its currencies
+#[derive(Debug, Clone)]
+struct VenuePair<'a> {
+    symbol: &'a str,
+    base: String,
+    quote: String,
+    book: &'a OrderBookDepth,
+}
+
+/// Executable size and costs for a cross-exchange spread
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrossExchangeSizing {
+    /// Base quantity to buy on one venue and sell on the other
+    pub quantity: Decimal,
+    /// Volume-weighted average buy price
+    pub buy_price: Decimal,
+    /// Volume-weighted average sell price
+    pub sell_price: Decimal,
+    /// Quote spent on the buy leg, before fees
+    pub buy_notional: Decimal,
+    /// Buy-leg fee, in quote units
+    pub buy_fee: Decimal,
+    /// Sell-leg fee, in quote units
+    pub sell_fee: Decimal,
+    /// Expected slippage on both legs, in quote units
+    pub slippage_cost: Decimal,
+    /// Withdrawal cost to rebalance the base asset, in quote units
+    pub transfer_cost: Decimal,
+    /// Profit after fees, slippage and transfer costs, in quote units
+    pub net_profit: Decimal,
+}
+
+/// Per-leg costs applied while simulating a cycle, as fractions of leg output
+#[derive(Debug, Clone, Copy)]
+struct LegCosts {
+    taker_fee: Decimal,
+    slippage: Decimal,
+}
+
+/// Outcome of simulating a full cycle through the books
+#[derive(Debug, Clone)]
+struct CycleFill {
+    start_amount: Decimal,
+    end_amount: Decimal,
+    legs: Vec<ArbitrageLeg>,
+}
+
 impl OpportunityDetector {
     /// Create a new opportunity detector with optional neural engine for ML-powered scoring
     pub fn new(config: ArbitrageConfig, neural_engine: Option<Arc<NeuralEngine>>) -> Self {
@@ -39,6 +103,7 @@ impl OpportunityDetector {
         Self {
             config,
             price_cache: Arc::new(RwLock::new(HashMap::new())),
+            depth_cache: Arc::new(RwLock::new(HashMap::new())),
             neural_engine,
         }
     }
@@ -52,102 +117,601 @@ impl OpportunityDetector {
             .insert(exchange, tick);
     }
 
+    /// Update the depth cache with a new Level 2 snapshot
+    pub async fn update_order_book(
+        &self,
+        symbol: &str,
+        depth: OrderBookDepth,
+        exchange: ExchangeId,
+    ) {
+        let mut cache = self.depth_cache.write().await;
+        cache
+            .entry(exchange)
+            .or_default()
+            .insert(symbol.to_string(), depth);
+    }
+
     /// Detect arbitrage opportunities across exchanges
     pub async fn detect_opportunities(&self) -> ArbitrageResult<Vec<ArbitrageOpportunity>> {
-        let cache = self.price_cache.read().await;
+        let mut opportunities = self.detect_cross_exchange_opportunities().await;
+        opportunities.extend(self.detect_triangular_opportunities().await);
+
+        if !opportunities.is_empty() {
+            info!(
+                "🎯 Detected {} arbitrage opportunities",
+                opportunities.len()
+            );
+        }
+
+        Ok(opportunities)
+    }
+
+    /// Cross-exchange spreads, sized by walking both venues' books.
+    ///
+    /// For every symbol quoted on two or more venues, each ordered (buy, sell)
+    /// venue pair is sized to its profit-maximizing quantity and the most
+    /// profitable pair is reported. Venues without a Level 2 snapshot fall back
+    /// to their top-of-book tick, capped at `max_position_size` notional.
+    async fn detect_cross_exchange_opportunities(&self) -> Vec<ArbitrageOpportunity> {
+        let ticks = self.price_cache.read().await;
+        let depth = self.depth_cache.read().await;
         let mut opportunities = Vec::new();
 
-        for (symbol, exchange_prices) in cache.iter() {
-            if exchange_prices.len() < 2 {
+        let mut symbols: Vec<&String> = ticks.keys().collect();
+        for books in depth.values() {
+            symbols.extend(books.keys());
+        }
+        symbols.sort();
+        symbols.dedup();
+
+        for symbol in symbols {
+            let venues = self.venue_books(symbol, &ticks, &depth);
+            if venues.len() < 2 {
                 continue; // Need at least 2 exchanges to arbitrage
             }
 
-            // Find best bid (highest price to sell) and best ask (lowest price to buy)
-            let mut best_bid: Option<(&ExchangeId, &MarketTick)> = None;
-            let mut best_ask: Option<(&ExchangeId, &MarketTick)> = None;
-
-            for (exchange, tick) in exchange_prices {
-                // Update best bid (highest bid price)
-                if let Some((_, current_best)) = best_bid {
-                    if tick.bid > current_best.bid {
-                        best_bid = Some((exchange, tick));
+            let mut best: Option<(ExchangeId, ExchangeId, CrossExchangeSizing)> = None;
+            for (buy_exchange, buy_book, _) in &venues {
+                for (sell_exchange, sell_book, _) in &venues {
+                    // Ensure we are not trading on the same exchange
+                    if buy_exchange == sell_exchange {
+                        continue;
                     }
-                } else {
-                    best_bid = Some((exchange, tick));
-                }
-
-                // Update best ask (lowest ask price)
-                if let Some((_, current_best)) = best_ask {
-                    if tick.ask < current_best.ask {
-                        best_ask = Some((exchange, tick));
+                    let Some(sizing) = self.size_cross_exchange(
+                        symbol,
+                        *buy_exchange,
+                        buy_book,
+                        *sell_exchange,
+                        sell_book,
+                    ) else {
+                        continue;
+                    };
+                    if best.as_ref().map_or(true, |(_, _, current)| {
+                        sizing.net_profit > current.net_profit
+                    }) {
+                        best = Some((*buy_exchange, *sell_exchange, sizing));
                     }
-                } else {
-                    best_ask = Some((exchange, tick));
                 }
             }
 
-            if let (Some((sell_exchange, sell_tick)), Some((buy_exchange, buy_tick))) =
-                (best_bid, best_ask)
-            {
-                // Ensure we are not trading on the same exchange
-                if sell_exchange == buy_exchange {
+            let Some((buy_exchange, sell_exchange, sizing)) = best else {
+                continue;
+            };
+
+            let net_profit_percentage = (sizing.net_profit / sizing.buy_notional)
+                .to_f64()
+                .unwrap_or(0.0)
+                * 100.0;
+
+            // Check if it meets minimum profit threshold
+            if net_profit_percentage < self.config.min_profit_percentage {
+                continue;
+            }
+
+            let depth_known = venues
+                .iter()
+                .filter(|(exchange, _, _)| *exchange == buy_exchange || *exchange == sell_exchange)
+                .all(|(_, _, from_depth)| *from_depth);
+            let buy_tick = Self::venue_tick(symbol, buy_exchange, &ticks, &depth);
+            let sell_tick = Self::venue_tick(symbol, sell_exchange, &ticks, &depth);
+            let (Some(buy_tick), Some(sell_tick)) = (buy_tick, sell_tick) else {
+                continue;
+            };
+
+            // Calculate confidence score using NeuralEngine if available
+            let confidence_score = self
+                .calculate_confidence_score(symbol, &buy_tick, &sell_tick)
+                .await;
+
+            let costs =
+                sizing.buy_fee + sizing.sell_fee + sizing.slippage_cost + sizing.transfer_cost;
+            let now = Utc::now();
+            opportunities.push(ArbitrageOpportunity {
+                id: Uuid::new_v4(),
+                symbol: symbol.clone(),
+                kind: OpportunityKind::CrossExchange {
+                    buy_exchange,
+                    sell_exchange,
+                    buy_price: sizing.buy_price,
+                    sell_price: sizing.sell_price,
+                    price_difference: sizing.sell_price - sizing.buy_price,
+                    max_quantity: sizing.quantity,
+                },
+                profit_percentage: net_profit_percentage,
+                estimated_profit: sizing.net_profit,
+                profit_currency: Self::split_symbol(symbol)
+                    .map(|(_, quote)| quote)
+                    .unwrap_or_default(),
+                confidence_score,
+                time_sensitivity: TimeSensitivity::High,
+                risk_score: Self::execution_risk(depth_known, sizing.net_profit + costs, costs),
+                execution_complexity: ExecutionComplexity::Simple,
+                detected_at: now,
+                expires_at: now + chrono::Duration::seconds(10),
+                legs: vec![
+                    ArbitrageLeg {
+                        exchange: buy_exchange,
+                        symbol: symbol.clone(),
+                        side: OrderSide::Buy,
+                        price: sizing.buy_price,
+                        quantity: sizing.quantity,
+                        fee: sizing.buy_fee,
+                    },
+                    ArbitrageLeg {
+                        exchange: sell_exchange,
+                        symbol: symbol.clone(),
+                        side: OrderSide::Sell,
+                        price: sizing.sell_price,
+                        quantity: sizing.quantity,
+                        fee: sizing.sell_fee,
+                    },
+                ],
+            });
+        }
+
+        opportunities
+    }
+
+    /// Books for every venue quoting `symbol`, flagged with whether they came from real depth
+    fn venue_books(
+        &self,
+        symbol: &str,
+        ticks: &HashMap<String, HashMap<ExchangeId, MarketTick>>,
+        depth: &HashMap<ExchangeId, HashMap<String, OrderBookDepth>>,
+    ) -> Vec<(ExchangeId, OrderBookDepth, bool)> {
+        let mut venues: Vec<(ExchangeId, OrderBookDepth, bool)> = depth
+            .iter()
+            .filter_map(|(exchange, books)| {
+                books
+                    .get(symbol)
+                    .map(|book| (*exchange, book.clone(), true))
+            })
+            .collect();
+
+        if let Some(exchange_prices) = ticks.get(symbol) {
+            for (exchange, tick) in exchange_prices {
+                if venues.iter().any(|(venue, _, _)| venue == exchange) {
                     continue;
                 }
+                if let Some(book) = self.top_of_book(tick) {
+                    venues.push((*exchange, book, false));
+                }
+            }
+        }
+        venues
+    }
 
-                // Check for profitability
-                let spread = sell_tick.bid - buy_tick.ask;
-
-                if spread > Decimal::ZERO {
-                    let gross_profit_percentage = (spread / buy_tick.ask).to_f64().unwrap_or(0.0);
-
-                    // Estimate fees (conservative 0.1% per side = 0.2% total)
-                    let estimated_fee_percentage = 0.002;
-                    let net_profit_percentage = gross_profit_percentage - estimated_fee_percentage;
-
-                    // Check if it meets minimum profit threshold
-                    if net_profit_percentage >= self.config.min_profit_percentage / 100.0 {
-                        let quantity = self.config.max_position_size;
-
-                        // Calculate confidence score using NeuralEngine if available
-                        let confidence_score = self
-                            .calculate_confidence_score(symbol, buy_tick, sell_tick)
-                            .await;
-
-                        let opportunity = ArbitrageOpportunity {
-                            id: Uuid::new_v4(),
-                            symbol: symbol.clone(),
-                            buy_exchange: *buy_exchange,
-                            sell_exchange: *sell_exchange,
-                            buy_price: buy_tick.ask,
-                            sell_price: sell_tick.bid,
-                            price_difference: spread,
-                            profit_percentage: net_profit_percentage * 100.0,
-                            estimated_profit: (spread * quantity)
-                                * Decimal::from_f64_retain(1.0 - estimated_fee_percentage)
-                                    .unwrap_or(Decimal::ONE),
-                            confidence_score,
-                            max_quantity: quantity,
-                            time_sensitivity: TimeSensitivity::High,
-                            risk_score: 0.1,
-                            execution_complexity: ExecutionComplexity::Simple,
-                            detected_at: Utc::now(),
-                            expires_at: Utc::now() + chrono::Duration::seconds(10),
-                        };
+    /// Single-level book from a tick, sized to the configured notional cap
+    fn top_of_book(&self, tick: &MarketTick) -> Option<OrderBookDepth> {
+        if tick.bid <= Decimal::ZERO || tick.ask <= Decimal::ZERO {
+            return None;
+        }
+        let cap = self.config.max_position_size;
+        Some(OrderBookDepth::new(
+            vec![DepthLevel::new(tick.bid, cap / tick.bid)],
+            vec![DepthLevel::new(tick.ask, cap / tick.ask)],
+            tick.timestamp,
+        ))
+    }
 
+    /// Latest tick for a venue, synthesised from its book when only depth is known
+    fn venue_tick(
+        symbol: &str,
+        exchange: ExchangeId,
+        ticks: &HashMap<String, HashMap<ExchangeId, MarketTick>>,
+        depth: &HashMap<ExchangeId, HashMap<String, OrderBookDepth>>,
+    ) -> Option<MarketTick> {
+        if let Some(tick) = ticks.get(symbol).and_then(|prices| prices.get(&exchange)) {
+            return Some(tick.clone());
+        }
+        let book = depth.get(&exchange)?.get(symbol)?;
+        let bid = book.best_bid()?.price;
+        let ask = book.best_ask()?.price;
+        Some(MarketTick {
+            symbol: symbol.to_string(),
+            bid,
+            ask,
+            last: (bid + ask) / Decimal::TWO,
+            volume_24h: Decimal::ZERO,
+            timestamp: book.timestamp,
+        })
+    }
+
+    /// Find the profit-maximizing quantity for buying on one venue and selling on another.
+    ///
+    /// Both books are walked level by level; each slice of size is kept while
+    /// its marginal profit after taker fees and expected slippage stays
+    /// positive, up to `max_position_size` notional. The withdrawal fee for
+    /// moving the base asset back from the buy venue is then charged once.
+    pub fn size_cross_exchange(
+        &self,
+        symbol: &str,
+        buy_exchange: ExchangeId,
+        buy_book: &OrderBookDepth,
+        sell_exchange: ExchangeId,
+        sell_book: &OrderBookDepth,
+    ) -> Option<CrossExchangeSizing> {
+        let buy_fees = self.config.fees_for(buy_exchange);
+        let sell_fees = self.config.fees_for(sell_exchange);
+        let slippage = self.expected_slippage();
+        let cap = self.config.max_position_size;
+
+        let mut asks = buy_book.asks.iter().copied();
+        let mut bids = sell_book.bids.iter().copied();
+        let mut ask = asks.next()?;
+        let mut bid = bids.next()?;
+
+        let mut quantity = Decimal::ZERO;
+        let mut buy_notional = Decimal::ZERO;
+        let mut sell_notional = Decimal::ZERO;
+
+        loop {
+            let unit_profit = bid.price * (Decimal::ONE - sell_fees.taker_fee)
+                - ask.price * (Decimal::ONE + buy_fees.taker_fee)
+                - (bid.price + ask.price) * slippage;
+            if unit_profit <= Decimal::ZERO {
+                break;
+            }
+
+            let remaining_notional = cap - buy_notional;
+            if remaining_notional <= Decimal::ZERO {
+                break;
+            }
+            let depth_take = ask.quantity.min(bid.quantity);
+            let cap_take = remaining_notional / ask.price;
+            let capped = cap_take < depth_take;
+            let take = depth_take.min(cap_take);
+            if take <= Decimal::ZERO {
+                break;
+            }
+
+            quantity += take;
+            buy_notional += take * ask.price;
+            sell_notional += take * bid.price;
+            ask.quantity -= take;
+            bid.quantity -= take;
+
+            // The cap slice is the last one; rounding may leave a sliver of
+            // notional that would otherwise be chased forever.
+            if capped {
+                break;
+            }
+
+            if ask.quantity <= Decimal::ZERO {
+                match asks.next() {
+                    Some(next) => ask = next,
+                    None => break,
+                }
+            }
+            if bid.quantity <= Decimal::ZERO {
+                match bids.next() {
+                    Some(next) => bid = next,
+                    None => break,
+                }
+            }
+        }
+
+        if quantity <= Decimal::ZERO {
+            return None;
+        }
+
+        let buy_price = buy_notional / quantity;
+        let sell_price = sell_notional / quantity;
+        let buy_fee = buy_notional * buy_fees.taker_fee;
+        let sell_fee = sell_notional * sell_fees.taker_fee;
+        let slippage_cost = (buy_notional + sell_notional) * slippage;
+        let transfer_cost = Self::split_symbol(symbol)
+            .and_then(|(base, _)| buy_fees.withdrawal_fees.get(&base).copied())
+            .map(|fee| fee * sell_price)
+            .unwrap_or(Decimal::ZERO);
+        let net_profit =
+            sell_notional - buy_notional - buy_fee - sell_fee - slippage_cost - transfer_cost;
+
+        if net_profit <= Decimal::ZERO {
+            return None;
+        }
+
+        Some(CrossExchangeSizing {
+            quantity,
+            buy_price,
+            sell_price,
+            buy_notional,
+            buy_fee,
+            sell_fee,
+            slippage_cost,
+            transfer_cost,
+            net_profit,
+        })
+    }
+
+    /// Triangular cycles within a single venue, priced against book depth
+    async fn detect_triangular_opportunities(&self) -> Vec<ArbitrageOpportunity> {
+        let cache = self.depth_cache.read().await;
+        let mut opportunities = Vec::new();
+
+        for (exchange, books) in cache.iter() {
+            let pairs: Vec<VenuePair<'_>> = books
+                .iter()
+                .filter_map(|(symbol, book)| {
+                    let (base, quote) = Self::split_symbol(symbol)?;
+                    Some(VenuePair {
+                        symbol,
+                        base,
+                        quote,
+                        book,
+                    })
+                })
+                .collect();
+            if pairs.len() < 3 {
+                continue;
+            }
+
+            let costs = LegCosts {
+                taker_fee: self.config.fees_for(*exchange).taker_fee,
+                slippage: self.expected_slippage(),
+            };
+            for home in &self.config.triangular_base_currencies {
+                for cycle in Self::find_cycles(home, &pairs) {
+                    let Some(fill) = Self::size_cycle(
+                        *exchange,
+                        &cycle,
+                        home,
+                        self.config.max_position_size,
+                        costs,
+                    ) else {
+                        continue;
+                    };
+                    if let Some(opportunity) =
+                        self.build_triangular_opportunity(*exchange, home, fill, costs)
+                    {
                         opportunities.push(opportunity);
                     }
                 }
             }
         }
 
-        if !opportunities.is_empty() {
-            info!(
-                "🎯 Detected {} arbitrage opportunities",
-                opportunities.len()
-            );
+        opportunities
+    }
+
+    /// Enumerate three-leg cycles `home -> a -> b -> home` over distinct pairs
+    fn find_cycles<'p, 'a>(home: &str, pairs: &'p [VenuePair<'a>]) -> Vec<[&'p VenuePair<'a>; 3]> {
+        let mut cycles = Vec::new();
+        for first in pairs {
+            let Some(a) = Self::counter_currency(first, home) else {
+                continue;
+            };
+            for second in pairs {
+                if std::ptr::eq(first, second) {
+                    continue;
+                }
+                let Some(b) = Self::counter_currency(second, &a) else {
+                    continue;
+                };
+                if b == home {
+                    continue;
+                }
+                for third in pairs {
+                    if std::ptr::eq(third, first) || std::ptr::eq(third, second) {
+                        continue;
+                    }
+                    if Self::counter_currency(third, &b).as_deref() == Some(home) {
+                        cycles.push([first, second, third]);
+                    }
+                }
+            }
         }
+        cycles
+    }
 
-        Ok(opportunities)
+    /// The other currency of `pair` when holding `currency`, if the pair trades it
+    fn counter_currency(pair: &VenuePair<'_>, currency: &str) -> Option<String> {
+        if pair.quote == currency {
+            Some(pair.base.clone())
+        } else if pair.base == currency {
+            Some(pair.quote.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Find the largest start amount (up to `max_start`) that every leg can fill,
+    /// and simulate the cycle at that size.
+    fn size_cycle(
+        exchange: ExchangeId,
+        cycle: &[&VenuePair<'_>; 3],
+        home: &str,
+        max_start: Decimal,
+        costs: LegCosts,
+    ) -> Option<CycleFill> {
+        let mut start = max_start;
+        for _ in 0..MAX_SIZING_PASSES {
+            if start <= Decimal::ZERO {
+                return None;
+            }
+            match Self::simulate_cycle(exchange, cycle, home, start, costs)? {
+                Ok(fill) => return Some(fill),
+                Err(fillable_fraction) => start *= fillable_fraction * DEPTH_HAIRCUT,
+            }
+        }
+        None
+    }
+
+    /// Walk the three legs with `start` units of `home`.
+    ///
+    /// Returns `Err(fraction)` when a leg runs out of depth, where `fraction`
+    /// is the share of that leg's input the book could absorb.
+    fn simulate_cycle(
+        exchange: ExchangeId,
+        cycle: &[&VenuePair<'_>; 3],
+        home: &str,
+        start: Decimal,
+        costs: LegCosts,
+    ) -> Option<Result<CycleFill, Decimal>> {
+        let mut holding = home.to_string();
+        let mut amount = start;
+        let mut legs = Vec::with_capacity(3);
+
+        for pair in cycle {
+            let (side, depth_fill, next) = if pair.quote == holding {
+                (
+                    OrderSide::Buy,
+                    pair.book.buy_with_quote(amount),
+                    pair.base.clone(),
+                )
+            } else if pair.base == holding {
+                (
+                    OrderSide::Sell,
+                    pair.book.sell_base(amount),
+                    pair.quote.clone(),
+                )
+            } else {
+                return None;
+            };
+
+            if depth_fill.output.is_zero() {
+                return None;
+            }
+            if depth_fill.exhausted {
+                return Some(Err(depth_fill.input_consumed / amount));
+            }
+
+            let fee = depth_fill.output * costs.taker_fee;
+            let slippage = depth_fill.output * costs.slippage;
+            legs.push(ArbitrageLeg {
+                exchange,
+                symbol: pair.symbol.to_string(),
+                side,
+                price: depth_fill.average_price,
+                quantity: depth_fill.base_quantity,
+                fee,
+            });
+            amount = depth_fill.output - fee - slippage;
+            holding = next;
+        }
+
+        Some(Ok(CycleFill {
+            start_amount: start,
+            end_amount: amount,
+            legs,
+        }))
+    }
+
+    fn build_triangular_opportunity(
+        &self,
+        exchange: ExchangeId,
+        home: &str,
+        fill: CycleFill,
+        costs: LegCosts,
+    ) -> Option<ArbitrageOpportunity> {
+        let net_profit = fill.end_amount - fill.start_amount;
+        if net_profit <= Decimal::ZERO {
+            return None;
+        }
+        let net_profit_percentage =
+            (net_profit / fill.start_amount).to_f64().unwrap_or(0.0) * 100.0;
+        if net_profit_percentage < self.config.min_profit_percentage {
+            return None;
+        }
+
+        // Costs are charged on every leg's output, so undoing them three times
+        // recovers the cycle's gross return in home currency
+        let retained = Decimal::ONE - costs.taker_fee - costs.slippage;
+        if retained <= Decimal::ZERO {
+            return None;
+        }
+        let gross_end = fill.end_amount / (retained * retained * retained);
+        let cost_amount = gross_end - fill.end_amount;
+
+        let symbol = fill
+            .legs
+            .iter()
+            .map(|leg| leg.symbol.as_str())
+            .collect::<Vec<_>>()
+            .join(" > ");
+        let now = Utc::now();
+
+        debug!(
+            "🔺 Triangular cycle {} on {:?}: {:.4}% net",
+            symbol, exchange, net_profit_percentage
+        );
+
+        Some(ArbitrageOpportunity {
+            id: Uuid::new_v4(),
+            symbol,
+            kind: OpportunityKind::Triangular {
+                exchange,
+                home_currency: home.to_string(),
+                start_amount: fill.start_amount,
+                end_amount: fill.end_amount,
+            },
+            profit_percentage: net_profit_percentage,
+            estimated_profit: net_profit,
+            profit_currency: home.to_string(),
+            confidence_score: self.default_confidence_score(),
+            time_sensitivity: TimeSensitivity::Critical,
+            // Every triangle leg is sized against a real Level 2 snapshot
+            risk_score: Self::execution_risk(true, gross_end - fill.start_amount, cost_amount),
+            execution_complexity: ExecutionComplexity::Complex,
+            detected_at: now,
+            expires_at: now + chrono::Duration::seconds(3),
+            legs: fill.legs,
+        })
+    }
+
+    /// Execution risk shared by cross-exchange and triangular opportunities.
+    ///
+    /// Sizes walked against real depth start at 0.1; top-of-book fallbacks,
+    /// which hide how much size is really there, start at 0.3. The share of
+    /// the gross edge eaten by fees, slippage and transfers adds up to 0.4,
+    /// since a thin net edge turns into a loss on the smallest adverse move.
+    fn execution_risk(depth_known: bool, gross_profit: Decimal, costs: Decimal) -> f64 {
+        let base = if depth_known { 0.1 } else { 0.3 };
+        let cost_share = if gross_profit > Decimal::ZERO {
+            (costs / gross_profit)
+                .to_f64()
+                .unwrap_or(1.0)
+                .clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        base + 0.4 * cost_share
+    }
+
+    /// Expected slippage per leg as a fraction of notional
+    fn expected_slippage(&self) -> Decimal {
+        Decimal::from_f64(self.config.expected_slippage_bps).unwrap_or(Decimal::ZERO)
+            / Decimal::from(10_000)
+    }
+
+    /// Split "BTC-USD", "EUR_USD" or "XBT/USD" into (base, quote)
+    pub(crate) fn split_symbol(symbol: &str) -> Option<(String, String)> {
+        let mut parts = symbol.split(['-', '_', '/']);
+        let base = parts.next()?.trim();
+        let quote = parts.next()?.trim();
+        if base.is_empty() || quote.is_empty() || parts.next().is_some() {
+            return None;
+        }
+        Some((base.to_uppercase(), quote.to_uppercase()))
     }
 
     /// Calculate confidence score using NeuralEngine if available, otherwise use default
@@ -174,13 +738,30 @@ impl OpportunityDetector {
                     )
                     .await
                 {
-                    Ok(prediction) => {
+                    Ok(PredictionOutcome::Predicted { model_id, .. })
+                        if engine.is_model_degraded(&model_id) =>
+                    {
+                        warn!(
+                            "NeuralEngine model {} is degraded, using default confidence for {}",
+                            model_id, symbol
+                        );
+                        self.default_confidence_score()
+                    }
+                    Ok(PredictionOutcome::Predicted { model_id, value }) => {
                         debug!(
-                            "🧠 NeuralEngine confidence for {}: {:.2}%",
+                            "🧠 NeuralEngine confidence for {} from {}: {:.2}%",
                             symbol,
-                            prediction.confidence_score * 100.0
+                            model_id,
+                            value.confidence_score * 100.0
                         );
-                        prediction.confidence_score
+                        value.confidence_score
+                    }
+                    Ok(PredictionOutcome::NoModel { reason, .. }) => {
+                        debug!(
+                            "No NeuralEngine model for {}, using default: {}",
+                            symbol, reason
+                        );
+                        self.default_confidence_score()
                     }
                     Err(e) => {
                         warn!(
@@ -227,6 +808,7 @@ impl OpportunityDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use exchange_connectors::FeeStructure;
     use rust_decimal_macros::dec;
 
     #[tokio::test]
@@ -258,13 +840,237 @@ mod tests {
 
         let opps = detector.detect_opportunities().await.unwrap();
         assert_eq!(opps.len(), 1);
-        assert_eq!(opps[0].buy_exchange, ExchangeId::Kraken);
-        assert_eq!(opps[0].sell_exchange, ExchangeId::BinanceUs);
-        assert_eq!(opps[0].price_difference, dec!(2));
+        let OpportunityKind::CrossExchange {
+            buy_exchange,
+            sell_exchange,
+            price_difference,
+            ..
+        } = &opps[0].kind
+        else {
+            panic!("expected a cross-exchange opportunity");
+        };
+        assert_eq!(*buy_exchange, ExchangeId::Kraken);
+        assert_eq!(*sell_exchange, ExchangeId::BinanceUs);
+        assert_eq!(*price_difference, dec!(2));
+        assert_eq!(opps[0].profit_currency, "USD");
         // Without NeuralEngine, should use default 0.9
         assert!((opps[0].confidence_score - 0.9).abs() < 0.001);
     }
 
+    fn single_level_book(bid: Decimal, ask: Decimal, quantity: Decimal) -> OrderBookDepth {
+        OrderBookDepth::new(
+            vec![DepthLevel::new(bid, quantity)],
+            vec![DepthLevel::new(ask, quantity)],
+            Utc::now(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_triangular_detection_nets_fees_and_depth() {
+        let config = ArbitrageConfig {
+            max_position_size: dec!(10000),
+            ..ArbitrageConfig::default()
+        };
+        let detector = OpportunityDetector::new(config, None);
+
+        // USD -> BTC -> ETH -> USD is mispriced: ETH/USD bid implies 0.05 BTC
+        // per ETH is worth 2550 USD while BTC trades at 50000 (2500 USD).
+        detector
+            .update_order_book(
+                "BTC-USD",
+                single_level_book(dec!(49990), dec!(50000), dec!(10)),
+                ExchangeId::Kraken,
+            )
+            .await;
+        detector
+            .update_order_book(
+                "ETH-BTC",
+                single_level_book(dec!(0.0499), dec!(0.05), dec!(100)),
+                ExchangeId::Kraken,
+            )
+            .await;
+        // Only 2 ETH bid: depth caps the cycle well below max_position_size
+        detector
+            .update_order_book(
+                "ETH-USD",
+                single_level_book(dec!(2550), dec!(2551), dec!(2)),
+                ExchangeId::Kraken,
+            )
+            .await;
+
+        let opps = detector.detect_opportunities().await.unwrap();
+        let triangle = opps
+            .iter()
+            .find(|opp| opp.execution_complexity == ExecutionComplexity::Complex)
+            .expect("triangular opportunity");
+
+        assert_eq!(triangle.legs.len(), 3);
+        assert_eq!(triangle.legs[0].symbol, "BTC-USD");
+        assert_eq!(triangle.legs[0].side, OrderSide::Buy);
+        assert_eq!(triangle.legs[2].symbol, "ETH-USD");
+        assert_eq!(triangle.legs[2].side, OrderSide::Sell);
+        assert!(triangle.legs[2].quantity <= dec!(2));
+
+        // Size and profit are both in the home currency the cycle starts from
+        let OpportunityKind::Triangular {
+            exchange,
+            home_currency,
+            start_amount,
+            end_amount,
+        } = &triangle.kind
+        else {
+            panic!("expected a triangular opportunity");
+        };
+        assert_eq!(*exchange, ExchangeId::Kraken);
+        assert_eq!(home_currency, "USD");
+        assert_eq!(triangle.profit_currency, "USD");
+        assert!(*start_amount < dec!(10000));
+        assert_eq!(triangle.estimated_profit, *end_amount - *start_amount);
+
+        // 2% gross edge minus three 0.1% taker fees
+        assert!(triangle.profit_percentage > 1.6 && triangle.profit_percentage < 1.8);
+        assert!(triangle.legs.iter().all(|leg| leg.fee > Decimal::ZERO));
+
+        // Real depth, with fees and slippage eating roughly a sixth of the gross edge
+        assert!(triangle.risk_score > 0.15 && triangle.risk_score < 0.2);
+    }
+
+    #[tokio::test]
+    async fn test_fx_triangle_rejected_when_fees_exceed_edge() {
+        let detector = OpportunityDetector::new(ArbitrageConfig::default(), None);
+
+        // Consistent cross rates: EUR_GBP = EUR_USD / GBP_USD, so fees make every cycle a loss
+        detector
+            .update_order_book(
+                "EUR_USD",
+                single_level_book(dec!(1.0999), dec!(1.1000), dec!(1000000)),
+                ExchangeId::Oanda,
+            )
+            .await;
+        detector
+            .update_order_book(
+                "GBP_USD",
+                single_level_book(dec!(1.2999), dec!(1.3000), dec!(1000000)),
+                ExchangeId::Oanda,
+            )
+            .await;
+        detector
+            .update_order_book(
+                "EUR_GBP",
+                single_level_book(dec!(0.8461), dec!(0.8462), dec!(1000000)),
+                ExchangeId::Oanda,
+            )
+            .await;
+
+        let opps = detector.detect_opportunities().await.unwrap();
+        assert!(opps.is_empty());
+    }
+
+    fn cross_exchange_books() -> (OrderBookDepth, OrderBookDepth) {
+        let buy_book = OrderBookDepth::new(
+            vec![DepthLevel::new(dec!(99), dec!(10))],
+            vec![
+                DepthLevel::new(dec!(100), dec!(1)),
+                DepthLevel::new(dec!(101), dec!(1)),
+                DepthLevel::new(dec!(103), dec!(5)),
+            ],
+            Utc::now(),
+        );
+        let sell_book = OrderBookDepth::new(
+            vec![
+                DepthLevel::new(dec!(102.5), dec!(1.5)),
+                DepthLevel::new(dec!(101), dec!(10)),
+            ],
+            vec![DepthLevel::new(dec!(104), dec!(10))],
+            Utc::now(),
+        );
+        (buy_book, sell_book)
+    }
+
+    #[tokio::test]
+    async fn test_cross_exchange_sizing_stops_at_unprofitable_depth() {
+        let detector = OpportunityDetector::new(ArbitrageConfig::default(), None);
+        let (buy_book, sell_book) = cross_exchange_books();
+        detector
+            .update_order_book("BTC-USD", buy_book, ExchangeId::Kraken)
+            .await;
+        detector
+            .update_order_book("BTC-USD", sell_book, ExchangeId::BinanceUs)
+            .await;
+
+        let opps = detector.detect_opportunities().await.unwrap();
+        assert_eq!(opps.len(), 1);
+        let opp = &opps[0];
+        assert_eq!(
+            opp.kind,
+            OpportunityKind::CrossExchange {
+                buy_exchange: ExchangeId::Kraken,
+                sell_exchange: ExchangeId::BinanceUs,
+                // 1 @ 100 and 0.5 @ 101 clear the 102.5 bid after fees; the 101 bid does not
+                buy_price: dec!(150.5) / dec!(1.5),
+                sell_price: dec!(102.5),
+                price_difference: dec!(102.5) - dec!(150.5) / dec!(1.5),
+                max_quantity: dec!(1.5),
+            }
+        );
+
+        // Gross 153.75 - 150.5 = 3.25, less 0.1% taker on each side and 2 bps slippage
+        let costs = dec!(0.15050) + dec!(0.15375) + dec!(0.06085);
+        assert_eq!(opp.estimated_profit, dec!(3.25) - costs);
+        // Real depth, plus costs eating 0.3651 of the 3.25 gross edge
+        let expected_risk = 0.1 + 0.4 * (0.3651 / 3.25);
+        assert!((opp.risk_score - expected_risk).abs() < 1e-9);
+
+        // Both leg fees are quoted in USD
+        assert_eq!(opp.legs[0].fee, dec!(0.15050));
+        assert_eq!(opp.legs[1].fee, dec!(0.15375));
+    }
+
+    #[tokio::test]
+    async fn test_withdrawal_fee_can_erase_cross_exchange_edge() {
+        let mut config = ArbitrageConfig::default();
+        let mut kraken = FeeStructure::default();
+        kraken.withdrawal_fees.insert("BTC".to_string(), dec!(0.05));
+        config.venue_fees.insert(ExchangeId::Kraken, kraken);
+
+        let detector = OpportunityDetector::new(config, None);
+        let (buy_book, sell_book) = cross_exchange_books();
+        detector
+            .update_order_book("BTC-USD", buy_book, ExchangeId::Kraken)
+            .await;
+        detector
+            .update_order_book("BTC-USD", sell_book, ExchangeId::BinanceUs)
+            .await;
+
+        assert!(detector.detect_opportunities().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cross_exchange_sizing_terminates_at_uneven_cap() {
+        let config = ArbitrageConfig {
+            max_position_size: dec!(12345.67),
+            ..ArbitrageConfig::default()
+        };
+        let detector = OpportunityDetector::new(config, None);
+        // Both books are far deeper than the cap, and the cap does not divide the ask
+        let buy_book = single_level_book(dec!(67000), dec!(67123.45), dec!(50));
+        let sell_book = single_level_book(dec!(68500), dec!(68600), dec!(50));
+
+        let sizing = detector
+            .size_cross_exchange(
+                "BTC-USD",
+                ExchangeId::Kraken,
+                &buy_book,
+                ExchangeId::BinanceUs,
+                &sell_book,
+            )
+            .expect("profitable sizing");
+
+        assert!(sizing.buy_notional <= dec!(12345.67));
+        assert!(dec!(12345.67) - sizing.buy_notional < dec!(0.000001));
+        assert!((sizing.buy_price - dec!(67123.45)).abs() < dec!(0.000001));
+    }
+
     #[tokio::test]
     async fn test_tick_to_market_data_conversion() {
         let tick = MarketTick {
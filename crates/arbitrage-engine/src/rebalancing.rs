@@ -0,0 +1,563 @@
+//! Rebalancing Planner - Concrete transfer plans for cross-exchange inventory
+//!
+//! Given per-venue balances of a currency and target weights, the planner
+//! produces the smallest practical set of actions that moves every venue
+//! towards its target: same-venue conversions from equivalent currencies
+//! first (no network hop), then withdrawals matched largest-surplus to
+//! largest-deficit. Withdrawal fees, minimums and confirmation times from
+//! [`WithdrawalTerms`] are applied so every planned transfer is executable.
+//! The planner only describes moves; executing them is up to the caller,
+//! which reports submitted transfers back as [`TrackedTransfer`]s.
+
+use exchange_connectors::{ExchangeId, TransferStatus};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Withdrawal constraints for one currency on one venue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawalTerms {
+    /// Flat fee deducted from every withdrawal, in the withdrawn currency
+    pub fee: Decimal,
+    /// Smallest amount the venue allows to be withdrawn
+    pub minimum: Decimal,
+    /// Expected time until the destination credits the funds
+    pub confirmation_secs: u64,
+}
+
+impl Default for WithdrawalTerms {
+    fn default() -> Self {
+        Self {
+            fee: Decimal::ZERO,
+            minimum: Decimal::ZERO,
+            confirmation_secs: 30 * 60,
+        }
+    }
+}
+
+/// Rebalancing configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RebalanceConfig {
+    /// Currencies the allocator is allowed to move automatically
+    pub managed_currencies: Vec<String>,
+    /// Withdrawal terms per venue and currency; missing entries use defaults
+    pub withdrawal_terms: HashMap<ExchangeId, HashMap<String, WithdrawalTerms>>,
+    /// Currencies that may be converted 1:1 into the key currency on the same venue
+    pub equivalents: HashMap<String, Vec<String>>,
+    /// Imbalances smaller than this share of the currency total are ignored
+    pub tolerance: Decimal,
+    /// Transfers whose fee exceeds this share of the amount are skipped
+    pub max_fee_fraction: Decimal,
+    /// Seconds past its expected arrival after which an unsettled transfer is
+    /// treated as failed
+    pub transfer_grace_secs: u64,
+}
+
+impl Default for RebalanceConfig {
+    fn default() -> Self {
+        Self {
+            managed_currencies: Vec::new(),
+            withdrawal_terms: HashMap::new(),
+            equivalents: HashMap::new(),
+            tolerance: Decimal::new(5, 2),        // 5%
+            max_fee_fraction: Decimal::new(1, 2), // 1%
+            transfer_grace_secs: 60 * 60,
+        }
+    }
+}
+
+impl RebalanceConfig {
+    /// Withdrawal terms for a currency leaving a venue
+    pub fn terms_for(&self, exchange: ExchangeId, currency: &str) -> WithdrawalTerms {
+        self.withdrawal_terms
+            .get(&exchange)
+            .and_then(|terms| terms.get(currency))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Single step of a rebalancing plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RebalanceAction {
+    /// Convert an equivalent currency into the target currency on the same venue
+    Convert {
+        exchange: ExchangeId,
+        from_currency: String,
+        to_currency: String,
+        amount: Decimal,
+    },
+    /// Withdraw from one venue and deposit on another
+    Transfer {
+        from_exchange: ExchangeId,
+        to_exchange: ExchangeId,
+        currency: String,
+        /// Amount withdrawn from the source venue
+        amount: Decimal,
+        /// Withdrawal fee deducted in transit
+        fee: Decimal,
+        /// Expected time until the destination credits the funds
+        confirmation_secs: u64,
+    },
+}
+
+impl RebalanceAction {
+    /// Amount that arrives at the destination
+    pub fn net_amount(&self) -> Decimal {
+        match self {
+            RebalanceAction::Convert { amount, .. } => *amount,
+            RebalanceAction::Transfer { amount, fee, .. } => *amount - *fee,
+        }
+    }
+}
+
+/// Ordered set of actions for one currency
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RebalancePlan {
+    pub currency: String,
+    pub actions: Vec<RebalanceAction>,
+    /// Total withdrawal fees paid across all transfers
+    pub total_fees: Decimal,
+}
+
+impl RebalancePlan {
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Only the cross-venue transfers of the plan
+    pub fn transfers(&self) -> impl Iterator<Item = &RebalanceAction> {
+        self.actions
+            .iter()
+            .filter(|action| matches!(action, RebalanceAction::Transfer { .. }))
+    }
+}
+
+/// Transfer submitted to a venue and tracked until it settles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedTransfer {
+    pub id: Uuid,
+    /// Identifier returned by the source venue
+    pub venue_transfer_id: String,
+    pub from_exchange: ExchangeId,
+    pub to_exchange: ExchangeId,
+    pub currency: String,
+    pub amount: Decimal,
+    /// Amount expected to arrive after withdrawal fees
+    pub net_amount: Decimal,
+    pub status: TransferStatus,
+    pub initiated_at: chrono::DateTime<chrono::Utc>,
+    pub expected_arrival: chrono::DateTime<chrono::Utc>,
+}
+
+impl TrackedTransfer {
+    /// True while funds have left the source but not yet reached the destination
+    pub fn is_in_flight(&self) -> bool {
+        matches!(
+            self.status,
+            TransferStatus::Pending | TransferStatus::Processing
+        )
+    }
+
+    /// True once the transfer has been in flight `grace_secs` past its expected arrival
+    pub fn is_overdue(&self, now: chrono::DateTime<chrono::Utc>, grace_secs: u64) -> bool {
+        self.is_in_flight()
+            && now > self.expected_arrival + chrono::Duration::seconds(grace_secs as i64)
+    }
+}
+
+/// Stateless planner turning balances and targets into rebalancing actions
+#[derive(Debug, Clone, Default)]
+pub struct RebalancePlanner {
+    config: RebalanceConfig,
+}
+
+impl RebalancePlanner {
+    pub fn new(config: RebalanceConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &RebalanceConfig {
+        &self.config
+    }
+
+    /// Plan the moves that bring `currency` to the target weights.
+    ///
+    /// `balances` maps venue -> currency -> available amount. `weights` are
+    /// relative and normalised over the venues they name; venues without a
+    /// weight are left untouched.
+    pub fn plan(
+        &self,
+        currency: &str,
+        balances: &HashMap<ExchangeId, HashMap<String, Decimal>>,
+        weights: &HashMap<ExchangeId, Decimal>,
+    ) -> RebalancePlan {
+        let mut plan = RebalancePlan {
+            currency: currency.to_string(),
+            ..RebalancePlan::default()
+        };
+
+        let weight_sum: Decimal = weights.values().copied().sum();
+        if weight_sum <= Decimal::ZERO {
+            return plan;
+        }
+
+        let held = |exchange: &ExchangeId| {
+            balances
+                .get(exchange)
+                .and_then(|currencies| currencies.get(currency))
+                .copied()
+                .unwrap_or(Decimal::ZERO)
+        };
+        let total: Decimal = weights.keys().map(held).sum();
+        if total <= Decimal::ZERO {
+            return plan;
+        }
+        let tolerance = total * self.config.tolerance;
+
+        let mut surpluses = Vec::new();
+        let mut deficits = Vec::new();
+        for (exchange, weight) in weights {
+            let target = total * *weight / weight_sum;
+            let delta = held(exchange) - target;
+            if delta > tolerance {
+                surpluses.push((*exchange, delta));
+            } else if -delta > tolerance {
+                deficits.push((*exchange, -delta));
+            }
+        }
+
+        // Cover deficits from equivalent currencies already on the venue
+        for (exchange, deficit) in deficits.iter_mut() {
+            for equivalent in self.config.equivalents.get(currency).into_iter().flatten() {
+                if deficit.is_zero() {
+                    break;
+                }
+                let available = balances
+                    .get(exchange)
+                    .and_then(|currencies| currencies.get(equivalent))
+                    .copied()
+                    .unwrap_or(Decimal::ZERO);
+                let amount = available.min(*deficit);
+                if amount > Decimal::ZERO {
+                    plan.actions.push(RebalanceAction::Convert {
+                        exchange: *exchange,
+                        from_currency: equivalent.clone(),
+                        to_currency: currency.to_string(),
+                        amount,
+                    });
+                    *deficit -= amount;
+                }
+            }
+        }
+        deficits.retain(|(_, deficit)| *deficit > tolerance);
+
+        // Greedy largest-to-largest matching keeps the transfer count at most
+        // (venues - 1) and usually far fewer.
+        loop {
+            surpluses.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| venue_order(a.0, b.0)));
+            deficits.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| venue_order(a.0, b.0)));
+            let (Some(&(from, surplus)), Some(&(to, deficit))) =
+                (surpluses.first(), deficits.first())
+            else {
+                break;
+            };
+
+            let terms = self.config.terms_for(from, currency);
+            // Withdraw enough to land the full deficit, but never more than the surplus
+            let amount = (deficit + terms.fee).min(surplus);
+
+            if !self.is_economic(amount, &terms) {
+                // This source cannot economically serve the deficit; try the next one
+                surpluses.remove(0);
+                continue;
+            }
+
+            plan.actions.push(RebalanceAction::Transfer {
+                from_exchange: from,
+                to_exchange: to,
+                currency: currency.to_string(),
+                amount,
+                fee: terms.fee,
+                confirmation_secs: terms.confirmation_secs,
+            });
+            plan.total_fees += terms.fee;
+
+            surpluses[0].1 -= amount;
+            deficits[0].1 -= amount - terms.fee;
+            surpluses.retain(|(_, left)| *left > tolerance);
+            deficits.retain(|(_, left)| *left > tolerance);
+        }
+
+        plan
+    }
+
+    /// Plan moving `fraction` of every other venue's `currency` to `target`,
+    /// skipping withdrawals that fall under the venue minimum or fee ceiling.
+    pub fn plan_consolidation(
+        &self,
+        currency: &str,
+        balances: &HashMap<ExchangeId, HashMap<String, Decimal>>,
+        target: ExchangeId,
+        fraction: Decimal,
+    ) -> RebalancePlan {
+        let mut plan = RebalancePlan {
+            currency: currency.to_string(),
+            ..RebalancePlan::default()
+        };
+
+        let mut sources: Vec<(ExchangeId, Decimal)> = balances
+            .iter()
+            .filter(|(exchange, _)| **exchange != target)
+            .filter_map(|(exchange, currencies)| {
+                currencies
+                    .get(currency)
+                    .map(|available| (*exchange, *available * fraction))
+            })
+            .collect();
+        sources.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| venue_order(a.0, b.0)));
+
+        for (from, amount) in sources {
+            let terms = self.config.terms_for(from, currency);
+            if !self.is_economic(amount, &terms) {
+                continue;
+            }
+            plan.actions.push(RebalanceAction::Transfer {
+                from_exchange: from,
+                to_exchange: target,
+                currency: currency.to_string(),
+                amount,
+                fee: terms.fee,
+                confirmation_secs: terms.confirmation_secs,
+            });
+            plan.total_fees += terms.fee;
+        }
+
+        plan
+    }
+
+    /// Whether a withdrawal of `amount` clears the venue minimum and fee ceiling
+    fn is_economic(&self, amount: Decimal, terms: &WithdrawalTerms) -> bool {
+        amount >= terms.minimum
+            && amount > terms.fee
+            && terms.fee / amount <= self.config.max_fee_fraction
+    }
+}
+
+/// Stable tie-break so plans are deterministic regardless of map ordering
+fn venue_order(a: ExchangeId, b: ExchangeId) -> std::cmp::Ordering {
+    format!("{:?}", a).cmp(&format!("{:?}", b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn balances(
+        entries: &[(ExchangeId, &str, Decimal)],
+    ) -> HashMap<ExchangeId, HashMap<String, Decimal>> {
+        let mut map: HashMap<ExchangeId, HashMap<String, Decimal>> = HashMap::new();
+        for (exchange, currency, amount) in entries {
+            map.entry(*exchange)
+                .or_default()
+                .insert(currency.to_string(), *amount);
+        }
+        map
+    }
+
+    fn equal_weights(exchanges: &[ExchangeId]) -> HashMap<ExchangeId, Decimal> {
+        exchanges
+            .iter()
+            .map(|exchange| (*exchange, Decimal::ONE))
+            .collect()
+    }
+
+    #[test]
+    fn test_single_transfer_accounts_for_withdrawal_fee() {
+        let mut config = RebalanceConfig::default();
+        config
+            .withdrawal_terms
+            .entry(ExchangeId::Kraken)
+            .or_default()
+            .insert(
+                "USD".to_string(),
+                WithdrawalTerms {
+                    fee: dec!(5),
+                    minimum: dec!(100),
+                    confirmation_secs: 600,
+                },
+            );
+        let planner = RebalancePlanner::new(config);
+
+        let plan = planner.plan(
+            "USD",
+            &balances(&[
+                (ExchangeId::Kraken, "USD", dec!(9000)),
+                (ExchangeId::BinanceUs, "USD", dec!(1000)),
+            ]),
+            &equal_weights(&[ExchangeId::Kraken, ExchangeId::BinanceUs]),
+        );
+
+        assert_eq!(
+            plan.actions,
+            vec![RebalanceAction::Transfer {
+                from_exchange: ExchangeId::Kraken,
+                to_exchange: ExchangeId::BinanceUs,
+                currency: "USD".to_string(),
+                amount: dec!(4000),
+                fee: dec!(5),
+                confirmation_secs: 600,
+            }]
+        );
+        // The source never drops below its own target, so the fee comes out of the delivery
+        assert_eq!(plan.actions[0].net_amount(), dec!(3995));
+        assert_eq!(plan.total_fees, dec!(5));
+    }
+
+    #[test]
+    fn test_equivalent_currency_converted_before_transfer() {
+        let mut config = RebalanceConfig::default();
+        config
+            .equivalents
+            .insert("USD".to_string(), vec!["USDT".to_string()]);
+        let planner = RebalancePlanner::new(config);
+
+        let plan = planner.plan(
+            "USD",
+            &balances(&[
+                (ExchangeId::Kraken, "USD", dec!(6000)),
+                (ExchangeId::BinanceUs, "USD", dec!(0)),
+                (ExchangeId::BinanceUs, "USDT", dec!(1000)),
+            ]),
+            &equal_weights(&[ExchangeId::Kraken, ExchangeId::BinanceUs]),
+        );
+
+        assert_eq!(plan.actions.len(), 2);
+        assert!(matches!(
+            &plan.actions[0],
+            RebalanceAction::Convert { exchange: ExchangeId::BinanceUs, amount, .. } if *amount == dec!(1000)
+        ));
+        assert_eq!(plan.transfers().count(), 1);
+        assert_eq!(plan.actions[1].net_amount(), dec!(2000));
+    }
+
+    #[test]
+    fn test_small_imbalances_and_uneconomic_transfers_are_skipped() {
+        let planner = RebalancePlanner::new(RebalanceConfig::default());
+        let within_tolerance = planner.plan(
+            "BTC",
+            &balances(&[
+                (ExchangeId::Kraken, "BTC", dec!(1.02)),
+                (ExchangeId::BinanceUs, "BTC", dec!(0.98)),
+            ]),
+            &equal_weights(&[ExchangeId::Kraken, ExchangeId::BinanceUs]),
+        );
+        assert!(within_tolerance.is_empty());
+
+        let mut config = RebalanceConfig::default();
+        config
+            .withdrawal_terms
+            .entry(ExchangeId::Kraken)
+            .or_default()
+            .insert(
+                "BTC".to_string(),
+                WithdrawalTerms {
+                    fee: dec!(0.05),
+                    minimum: dec!(0.001),
+                    confirmation_secs: 3600,
+                },
+            );
+        let expensive = RebalancePlanner::new(config).plan(
+            "BTC",
+            &balances(&[
+                (ExchangeId::Kraken, "BTC", dec!(2)),
+                (ExchangeId::BinanceUs, "BTC", dec!(1)),
+            ]),
+            &equal_weights(&[ExchangeId::Kraken, ExchangeId::BinanceUs]),
+        );
+        assert!(expensive.is_empty());
+    }
+
+    #[test]
+    fn test_consolidation_respects_minimums() {
+        let mut config = RebalanceConfig::default();
+        config
+            .withdrawal_terms
+            .entry(ExchangeId::Oanda)
+            .or_default()
+            .insert(
+                "USD".to_string(),
+                WithdrawalTerms {
+                    fee: dec!(1),
+                    minimum: dec!(500),
+                    confirmation_secs: 60,
+                },
+            );
+        let plan = RebalancePlanner::new(config).plan_consolidation(
+            "USD",
+            &balances(&[
+                (ExchangeId::Kraken, "USD", dec!(10000)),
+                (ExchangeId::BinanceUs, "USD", dec!(4000)),
+                (ExchangeId::Oanda, "USD", dec!(800)),
+            ]),
+            ExchangeId::Kraken,
+            dec!(0.5),
+        );
+
+        assert_eq!(plan.actions.len(), 1);
+        assert!(matches!(
+            &plan.actions[0],
+            RebalanceAction::Transfer { from_exchange: ExchangeId::BinanceUs, amount, .. } if *amount == dec!(2000)
+        ));
+    }
+
+    #[test]
+    fn test_consolidation_skips_withdrawals_over_fee_ceiling() {
+        let mut config = RebalanceConfig::default();
+        config
+            .withdrawal_terms
+            .entry(ExchangeId::BinanceUs)
+            .or_default()
+            .insert(
+                "BTC".to_string(),
+                WithdrawalTerms {
+                    fee: dec!(0.0005),
+                    minimum: dec!(0.001),
+                    confirmation_secs: 3600,
+                },
+            );
+        config
+            .withdrawal_terms
+            .entry(ExchangeId::Oanda)
+            .or_default()
+            .insert(
+                "BTC".to_string(),
+                WithdrawalTerms {
+                    fee: dec!(0.0005),
+                    minimum: dec!(0.001),
+                    confirmation_secs: 3600,
+                },
+            );
+        let plan = RebalancePlanner::new(config).plan_consolidation(
+            "BTC",
+            &balances(&[
+                (ExchangeId::Kraken, "BTC", dec!(1)),
+                // 0.0005 fee on 0.01 withdrawn is 5%, over the 1% default ceiling
+                (ExchangeId::BinanceUs, "BTC", dec!(0.02)),
+                (ExchangeId::Oanda, "BTC", dec!(1)),
+            ]),
+            ExchangeId::Kraken,
+            dec!(0.5),
+        );
+
+        assert_eq!(plan.actions.len(), 1);
+        assert!(matches!(
+            &plan.actions[0],
+            RebalanceAction::Transfer { from_exchange: ExchangeId::Oanda, amount, .. } if *amount == dec!(0.5)
+        ));
+        assert_eq!(plan.total_fees, dec!(0.0005));
+    }
+}
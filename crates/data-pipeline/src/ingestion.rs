@@ -53,6 +53,8 @@ pub enum IngestionError {
     StreamUnavailable(String),
     #[error("websocket task join error: {0}")]
     Join(String),
+    #[error("capture replay failed: {0}")]
+    Replay(String),
 }
 
 /// Handle returned by the ingestion layer. Dropping the sender causes the task to exit.
@@ -10,6 +10,10 @@
 //! 3. **Distribution** — fans out normalized events onto the validated
 //!    `event-bus` channels with bounded backpressure control.
 //!
+//! Raw exchange traffic can be captured with
+//! `exchange_connectors::capture::FrameRecorder` and fed back through the same
+//! parsers and normalizer via [`ReplaySource`].
+//!
 //! The primitives exported here integrate tightly with the existing
 //! `ExchangeConnector` trait and the high-performance event bus without
 //! modifying those foundational crates.
@@ -19,6 +23,7 @@ pub mod ingestion;
 pub mod normalizer;
 pub mod order_book;
 pub mod pipeline;
+pub mod replay;
 pub mod websocket;
 
 pub use distributor::Distributor;
@@ -26,6 +31,7 @@ pub use ingestion::{IngestionConfig, StreamIngestor};
 pub use normalizer::{MarketNormalizer, NormalizedEvent};
 pub use order_book::{LevelTwoBook, OrderBookSide};
 pub use pipeline::{DataPipeline, DataPipelineBuilder, DataPipelineHandle};
+pub use replay::{ReplayPacing, ReplaySource};
 pub use websocket::{
     spawn_stream as spawn_websocket_stream, BackoffConfig, HeartbeatConfig, WebSocketConfig,
     WebSocketEvent,
@@ -9,10 +9,12 @@ use tracing::{info, warn};
 use crate::distributor::Distributor;
 use crate::ingestion::{IngestionConfig, IngestionError, RawMarketMessage, StreamIngestor};
 use crate::normalizer::{MarketNormalizer, NormalizedEvent};
+use crate::replay::ReplaySource;
 
 /// Builder for a multi-exchange data pipeline.
 pub struct DataPipelineBuilder {
     configs: Vec<IngestionConfig>,
+    replays: Vec<ReplaySource>,
     market_sender: Option<event_bus::EventSender<MarketEvent>>,
     raw_capacity: usize,
     normalized_capacity: usize,
@@ -22,6 +24,7 @@ impl DataPipelineBuilder {
     pub fn new() -> Self {
         Self {
             configs: Vec::new(),
+            replays: Vec::new(),
             market_sender: None,
             raw_capacity: 4096,
             normalized_capacity: 4096,
@@ -44,6 +47,12 @@ impl DataPipelineBuilder {
         self
     }
 
+    /// Feeds a recorded capture through the pipeline in place of a live connector.
+    pub fn with_replay(mut self, source: ReplaySource) -> Self {
+        self.replays.push(source);
+        self
+    }
+
     pub fn with_raw_capacity(mut self, capacity: usize) -> Self {
         self.raw_capacity = capacity;
         self
@@ -80,6 +89,15 @@ impl DataPipelineBuilder {
             ingestion_handles.push(handle);
         }
 
+        for source in self.replays {
+            let replay = source.spawn(raw_tx.clone());
+            ingestion_handles.push(tokio::spawn(async move {
+                if let Err(err) = replay.await {
+                    warn!(%err, "replay task exited with error");
+                }
+            }));
+        }
+
         drop(raw_tx); // ensure the channel closes once all ingestors exit
 
         // Normalization worker
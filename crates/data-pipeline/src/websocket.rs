@@ -10,6 +10,8 @@ use std::borrow::Cow;
 use std::sync::Arc;
 use std::time::Duration;
 
+use exchange_connectors::capture::FrameRecorder;
+use exchange_connectors::ExchangeId;
 use futures_util::{SinkExt, StreamExt};
 use rand::{rngs::OsRng, RngCore};
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
@@ -100,6 +102,8 @@ pub struct WebSocketConfig {
     pub backoff: BackoffConfig,
     /// Idle read timeout; if exceeded the connection is considered stalled.
     pub read_timeout: Duration,
+    /// Optional recorder capturing raw text frames, tagged with their exchange.
+    pub recorder: Option<(ExchangeId, FrameRecorder)>,
 }
 
 impl WebSocketConfig {
@@ -111,6 +115,7 @@ impl WebSocketConfig {
             heartbeat: None,
             backoff: BackoffConfig::default_streaming(),
             read_timeout: Duration::from_secs(15),
+            recorder: None,
         }
     }
 }
@@ -123,6 +128,7 @@ pub struct WebSocketConfigBuilder {
     heartbeat: Option<HeartbeatConfig>,
     backoff: BackoffConfig,
     read_timeout: Duration,
+    recorder: Option<(ExchangeId, FrameRecorder)>,
 }
 
 impl WebSocketConfigBuilder {
@@ -154,6 +160,12 @@ impl WebSocketConfigBuilder {
         self
     }
 
+    /// Captures every raw frame to `recorder` so the session can be replayed later.
+    pub fn record_to(mut self, exchange: ExchangeId, recorder: FrameRecorder) -> Self {
+        self.recorder = Some((exchange, recorder));
+        self
+    }
+
     pub fn build(self) -> WebSocketConfig {
         WebSocketConfig {
             name: self.name,
@@ -162,6 +174,7 @@ impl WebSocketConfigBuilder {
             heartbeat: self.heartbeat,
             backoff: self.backoff,
             read_timeout: self.read_timeout,
+            recorder: self.recorder,
         }
     }
 }
@@ -217,10 +230,18 @@ async fn run_stream(config: WebSocketConfig, sender: UnboundedSender<WebSocketEv
                             match msg {
                                 Some(Ok(Message::Text(text))) => {
                                     last_frame = Instant::now();
+                                    if let Some((exchange, recorder)) = &config.recorder {
+                                        recorder.record(*exchange, &text);
+                                    }
                                     let _ = sender.send(WebSocketEvent::Text(text));
                                 }
                                 Some(Ok(Message::Binary(bin))) => {
                                     last_frame = Instant::now();
+                                    if let Some((exchange, recorder)) = &config.recorder {
+                                        if let Ok(text) = std::str::from_utf8(&bin) {
+                                            recorder.record(*exchange, text);
+                                        }
+                                    }
                                     let _ = sender.send(WebSocketEvent::Binary(bin));
                                 }
                                 Some(Ok(Message::Ping(payload))) => {
@@ -3,7 +3,7 @@ use std::time::Instant;
 use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 use event_bus::{
     EventBusBuilder, EventMetadata, EventSender, Priority, PublishMode, SignalEvent,
-    SignalEventPayload, StrategySignal,
+    SignalEventPayload, SignalIntent, StrategySignal,
 };
 use ninja_gekko_core::types::{OrderSide, OrderType};
 use rust_decimal::Decimal;
@@ -57,6 +57,7 @@ fn bench_signal_dispatch(c: &mut Criterion) {
                             limit_price: None,
                             confidence: 0.5,
                             metadata: Default::default(),
+                            intent: SignalIntent::Order,
                         },
                     };
                     let event = SignalEvent::new(metadata, payload);
@@ -0,0 +1,472 @@
+//! Nets strategy signals per account and symbol before they become orders.
+//!
+//! Strategies sharing an account often disagree about a symbol. Rather than turning
+//! each signal into its own order, [`SignalCombiner`] keeps a desired position per
+//! strategy, weighs it by the confidence it was signalled with, caps it at the
+//! strategy's capital budget and sums the result into one target for the account.
+//! Only the difference between that target and what has already been ordered goes
+//! downstream, as a single market order whose correlation id maps back to the
+//! contributing strategies.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use exchange_connectors::ExchangeId;
+use ninja_gekko_core::types::{AccountId, OrderSide, OrderType};
+
+use crate::dispatcher::EventHandler;
+use crate::envelope::{SignalEvent, SignalEventPayload, SignalIntent, StrategySignal};
+use crate::error::EventBusError;
+use crate::metadata::{EventMetadata, Priority};
+
+/// Event source of the netted signals.
+pub const COMBINER_SOURCE: &str = "event_bus.signal_combiner";
+/// Signal metadata key listing each strategy's share of a netted order as
+/// `strategy_id=quantity` pairs separated by `;`, sells negative.
+pub const ATTRIBUTION_KEY: &str = "attribution";
+/// Signal metadata key carrying the combined target position behind a netted order.
+pub const TARGET_KEY: &str = "target_position";
+
+/// Netted orders kept for [`SignalCombiner::attribution`], oldest dropped first.
+const ATTRIBUTION_CAPACITY: usize = 4096;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct BookKey {
+    account_id: AccountId,
+    symbol: String,
+}
+
+/// One strategy's desired position, as signalled and weighted by confidence.
+#[derive(Debug, Default, Clone, Copy)]
+struct Target {
+    /// Unweighted desired position, sells negative.
+    desired: Decimal,
+    /// Running sum of each change scaled by its signal's confidence, kept between
+    /// zero and `desired`.
+    weighted: Decimal,
+}
+
+impl Target {
+    /// Replaces the position outright, weighted by this signal's confidence alone.
+    fn replace(&mut self, desired: Decimal, weight: Decimal) {
+        self.desired = desired;
+        self.weighted = desired * weight;
+    }
+
+    /// Moves the position by `change`, weighting only the change.
+    ///
+    /// Bounding the sum by the unweighted position means a flat strategy holds
+    /// nothing whatever confidences it got there with, and a buy never lowers the
+    /// weighted position nor a sell raises it.
+    fn adjust(&mut self, change: Decimal, weight: Decimal) {
+        self.desired += change;
+        let weighted = self.weighted + change * weight;
+        self.weighted = if self.desired.is_sign_negative() {
+            weighted.clamp(self.desired, Decimal::ZERO)
+        } else {
+            weighted.clamp(Decimal::ZERO, self.desired)
+        };
+    }
+}
+
+/// Targets of every strategy trading one symbol on one account.
+#[derive(Debug, Default)]
+struct Book {
+    exchange: Option<ExchangeId>,
+    priority: Priority,
+    /// Desired position per strategy, before budgets.
+    targets: HashMap<Uuid, Target>,
+    /// Budgeted position per strategy already covered by `position`.
+    allocated: HashMap<Uuid, Decimal>,
+    /// Net position ordered so far.
+    position: Decimal,
+    pending: bool,
+}
+
+/// One strategy's share of a netted order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contribution {
+    pub strategy_id: Uuid,
+    /// Change of the strategy's budgeted target, sells negative.
+    pub quantity: Decimal,
+}
+
+/// Outcome of netting one account and symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetOrder {
+    pub account_id: AccountId,
+    pub symbol: String,
+    /// Combined target position after netting.
+    pub target: Decimal,
+    /// Signed quantity sent downstream; zero when the contributions crossed internally.
+    pub delta: Decimal,
+    /// Correlation id of the downstream signal and the orders and fills it causes, if
+    /// one was sent.
+    pub correlation_id: Option<Uuid>,
+    pub contributions: Vec<Contribution>,
+}
+
+#[derive(Debug, Default)]
+struct CombinerState {
+    books: HashMap<BookKey, Book>,
+    marks: HashMap<String, Decimal>,
+    sent: HashMap<Uuid, NetOrder>,
+    sent_order: VecDeque<Uuid>,
+}
+
+impl CombinerState {
+    fn record(&mut self, net: NetOrder) {
+        let Some(correlation_id) = net.correlation_id else {
+            return;
+        };
+        if self.sent_order.len() >= ATTRIBUTION_CAPACITY {
+            if let Some(oldest) = self.sent_order.pop_front() {
+                self.sent.remove(&oldest);
+            }
+        }
+        self.sent_order.push_back(correlation_id);
+        self.sent.insert(correlation_id, net);
+    }
+}
+
+/// Nets strategy signals into target positions ahead of order creation.
+///
+/// Market [`SignalIntent::Order`] signals move the strategy's desired position by
+/// their quantity scaled by the signal's confidence, so a low-confidence order only
+/// nudges an established position; [`SignalIntent::TargetPosition`] signals replace
+/// it, scaled by their own confidence. A strategy whose orders bring it back to flat
+/// holds nothing, whatever confidences the orders carried. Quote signals and priced
+/// orders cannot be netted and are forwarded unchanged.
+///
+/// Signals are netted as they arrive unless the combiner is [`batched`], in which
+/// case they accumulate until [`flush`] so opposing signals from the same round
+/// cancel out before anything is ordered.
+///
+/// [`batched`]: SignalCombiner::batched
+/// [`flush`]: SignalCombiner::flush
+pub struct SignalCombiner {
+    id: Uuid,
+    downstream: Arc<dyn EventHandler<SignalEvent>>,
+    budgets: HashMap<Uuid, Decimal>,
+    batched: bool,
+    state: Mutex<CombinerState>,
+}
+
+impl SignalCombiner {
+    /// Creates a combiner forwarding netted signals to `downstream`, usually a
+    /// [`SignalToOrderBridge`](super::SignalToOrderBridge).
+    pub fn new(downstream: Arc<dyn EventHandler<SignalEvent>>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            downstream,
+            budgets: HashMap::new(),
+            batched: false,
+            state: Mutex::new(CombinerState::default()),
+        }
+    }
+
+    /// Caps a strategy's position on any one symbol at `capital` worth of notional,
+    /// valued at the symbol's latest mark. Without a mark the strategy's target is
+    /// left out until one is known.
+    pub fn with_budget(mut self, strategy_id: Uuid, capital: Decimal) -> Self {
+        self.budgets.insert(strategy_id, capital.abs());
+        self
+    }
+
+    /// Holds signals until [`SignalCombiner::flush`] instead of netting each one on
+    /// arrival.
+    pub fn batched(mut self) -> Self {
+        self.batched = true;
+        self
+    }
+
+    /// Strategy id the combiner signs its netted signals with.
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Records the price used to value budgets on `symbol`. Limit prices on incoming
+    /// signals update it as well. Budgets are resized at the next netting of the
+    /// symbol.
+    pub async fn update_mark(&self, symbol: &str, price: Decimal) {
+        let mut state = self.state.lock().await;
+        state.marks.insert(symbol.to_string(), price);
+        for (key, book) in state.books.iter_mut() {
+            if key.symbol == symbol && book.targets.keys().any(|id| self.budgets.contains_key(id)) {
+                book.pending = true;
+            }
+        }
+    }
+
+    /// Net position ordered so far on `symbol` for the account.
+    pub async fn position(&self, account_id: &str, symbol: &str) -> Decimal {
+        let key = BookKey {
+            account_id: account_id.to_string(),
+            symbol: symbol.to_string(),
+        };
+        self.state
+            .lock()
+            .await
+            .books
+            .get(&key)
+            .map_or(Decimal::ZERO, |book| book.position)
+    }
+
+    /// Reconciles the ordered position, e.g. after rejected orders or partial fills.
+    /// The next netting orders the difference to the combined target; that
+    /// correction is not attributed to any strategy.
+    pub async fn set_position(&self, account_id: &str, symbol: &str, position: Decimal) {
+        let key = BookKey {
+            account_id: account_id.to_string(),
+            symbol: symbol.to_string(),
+        };
+        let mut state = self.state.lock().await;
+        let book = state.books.entry(key).or_default();
+        book.position = position;
+        book.pending = true;
+    }
+
+    /// Nets every account and symbol with signals received since the last flush.
+    pub async fn flush(&self) -> Result<Vec<NetOrder>, EventBusError> {
+        let mut state = self.state.lock().await;
+        let keys: Vec<BookKey> = state
+            .books
+            .iter()
+            .filter(|(_, book)| book.pending)
+            .map(|(key, _)| key.clone())
+            .collect();
+        let mut netted = Vec::new();
+        for key in keys {
+            if let Some(net) = self.net(&mut state, &key).await? {
+                netted.push(net);
+            }
+        }
+        Ok(netted)
+    }
+
+    /// Flushes every `every` until the returned task is aborted.
+    pub fn spawn_flush(self: &Arc<Self>, every: Duration) -> JoinHandle<()> {
+        let combiner = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(every);
+            loop {
+                interval.tick().await;
+                if let Err(err) = combiner.flush().await {
+                    warn!("signal netting failed: {}", err);
+                }
+            }
+        })
+    }
+
+    /// Netted order sent under `correlation_id`, if it is still retained.
+    pub async fn attribution(&self, correlation_id: Uuid) -> Option<NetOrder> {
+        self.state.lock().await.sent.get(&correlation_id).cloned()
+    }
+
+    /// Splits `filled` of the netted order sent under `correlation_id` across the
+    /// strategies in proportion to their contributions.
+    pub async fn attribute_fill(&self, correlation_id: Uuid, filled: Decimal) -> Vec<Contribution> {
+        let Some(net) = self.attribution(correlation_id).await else {
+            return Vec::new();
+        };
+        let total: Decimal = net.contributions.iter().map(|c| c.quantity).sum();
+        if total.is_zero() {
+            return Vec::new();
+        }
+        // Fills are unsigned; keep each share's sign relative to the order's side.
+        let filled = if total.is_sign_negative() {
+            -filled.abs()
+        } else {
+            filled.abs()
+        };
+        net.contributions
+            .iter()
+            .map(|contribution| Contribution {
+                strategy_id: contribution.strategy_id,
+                quantity: filled * contribution.quantity / total,
+            })
+            .collect()
+    }
+
+    /// Nets one book and sends the difference to its ordered position downstream.
+    async fn net(
+        &self,
+        state: &mut CombinerState,
+        key: &BookKey,
+    ) -> Result<Option<NetOrder>, EventBusError> {
+        let mark = state
+            .marks
+            .get(&key.symbol)
+            .copied()
+            .filter(|mark| mark.is_sign_positive() && !mark.is_zero());
+        let Some(book) = state.books.get_mut(key) else {
+            return Ok(None);
+        };
+
+        let mut allocated = HashMap::with_capacity(book.targets.len());
+        let mut contributions = Vec::new();
+        let mut target = Decimal::ZERO;
+        for (strategy_id, strategy_target) in &book.targets {
+            let desired = strategy_target.weighted;
+            let budgeted = match (self.budgets.get(strategy_id), mark) {
+                (None, _) => desired,
+                (Some(budget), Some(mark)) => {
+                    let cap = *budget / mark;
+                    desired.clamp(-cap, cap)
+                }
+                (Some(_), None) => {
+                    debug!(
+                        "no mark for {} to size strategy {}'s budget",
+                        key.symbol, strategy_id
+                    );
+                    Decimal::ZERO
+                }
+            }
+            .normalize();
+            target += budgeted;
+            let previous = book.allocated.get(strategy_id).copied().unwrap_or_default();
+            if budgeted != previous {
+                contributions.push(Contribution {
+                    strategy_id: *strategy_id,
+                    quantity: budgeted - previous,
+                });
+            }
+            allocated.insert(*strategy_id, budgeted);
+        }
+        contributions.sort_by_key(|contribution| contribution.strategy_id);
+
+        let delta = target - book.position;
+        book.pending = false;
+        if delta.is_zero() && contributions.is_empty() {
+            return Ok(None);
+        }
+
+        let mut net = NetOrder {
+            account_id: key.account_id.clone(),
+            symbol: key.symbol.clone(),
+            target,
+            delta,
+            correlation_id: None,
+            contributions,
+        };
+        if !delta.is_zero() {
+            let metadata = EventMetadata::new(COMBINER_SOURCE, book.priority);
+            net.correlation_id = Some(metadata.correlation_id);
+            let payload = SignalEventPayload {
+                strategy_id: self.id,
+                account_id: key.account_id.clone(),
+                priority: book.priority,
+                signal: StrategySignal {
+                    exchange: book.exchange,
+                    symbol: key.symbol.clone(),
+                    side: if delta.is_sign_negative() {
+                        OrderSide::Sell
+                    } else {
+                        OrderSide::Buy
+                    },
+                    order_type: OrderType::Market,
+                    quantity: delta.abs(),
+                    limit_price: None,
+                    confidence: 1.0,
+                    metadata: HashMap::from([
+                        (ATTRIBUTION_KEY.to_string(), attribution_label(&net)),
+                        (TARGET_KEY.to_string(), target.to_string()),
+                    ]),
+                    intent: SignalIntent::Order,
+                },
+            };
+            if let Err(err) = self
+                .downstream
+                .handle(SignalEvent::new(metadata, payload))
+                .await
+            {
+                // Leave the book pending so the next flush retries.
+                book.pending = true;
+                return Err(err);
+            }
+        }
+
+        book.position = target;
+        book.allocated = allocated;
+        book.priority = Priority::default();
+        state.record(net.clone());
+        Ok(Some(net))
+    }
+}
+
+fn attribution_label(net: &NetOrder) -> String {
+    net.contributions
+        .iter()
+        .map(|c| format!("{}={}", c.strategy_id, c.quantity))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Signed quantity of a signal, sells negative.
+fn signed(signal: &StrategySignal) -> Decimal {
+    match signal.side {
+        OrderSide::Buy => signal.quantity,
+        OrderSide::Sell => -signal.quantity,
+    }
+}
+
+#[async_trait]
+impl EventHandler<SignalEvent> for SignalCombiner {
+    async fn handle(&self, event: SignalEvent) -> Result<(), EventBusError> {
+        let payload = event.payload_arc();
+        let signal = &payload.signal;
+        let nettable = match signal.intent {
+            SignalIntent::TargetPosition => true,
+            SignalIntent::Order => signal.order_type == OrderType::Market,
+            SignalIntent::ReplaceQuote | SignalIntent::CancelQuotes => false,
+        };
+        if !nettable {
+            return self.downstream.handle(event).await;
+        }
+
+        let weight = Decimal::try_from(signal.confidence.clamp(0.0, 1.0)).unwrap_or_default();
+        let key = BookKey {
+            account_id: payload.account_id.clone(),
+            symbol: signal.symbol.clone(),
+        };
+        let mut state = self.state.lock().await;
+        if let Some(price) = signal.limit_price {
+            state.marks.insert(signal.symbol.clone(), price);
+        }
+        let book = state.books.entry(key.clone()).or_default();
+        if signal.exchange.is_some() {
+            book.exchange = signal.exchange;
+        }
+        book.priority = book.priority.max(payload.priority);
+        let target = book.targets.entry(payload.strategy_id).or_default();
+        match signal.intent {
+            SignalIntent::TargetPosition => target.replace(signed(signal), weight),
+            _ => target.adjust(signed(signal), weight),
+        }
+        book.pending = true;
+
+        if !self.batched {
+            self.net(&mut state, &key).await?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for SignalCombiner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalCombiner")
+            .field("id", &self.id)
+            .field("budgets", &self.budgets)
+            .field("batched", &self.batched)
+            .finish_non_exhaustive()
+    }
+}
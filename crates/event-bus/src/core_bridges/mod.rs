@@ -3,34 +3,64 @@
 //! Bridges wiring core Ninja Gekko modules onto the event bus without altering
 //! their existing public APIs.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use rust_decimal::Decimal;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use tracing::debug;
+use uuid::Uuid;
 
 use ninja_gekko_core::order_manager::OrderManager;
-use ninja_gekko_core::types::{Execution, Order, OrderSide, OrderType, Portfolio};
+use ninja_gekko_core::position_sizing::{
+    AccountSnapshot, PositionSizer, SizingRequest, Volatility, STOP_LOSS_KEY, VOLATILITY_KEY,
+};
+use ninja_gekko_core::types::{Execution, Order, OrderId, OrderSide, OrderType, Portfolio};
 
 use crate::channel::{EventSender, PublishMode};
 use crate::dispatcher::EventHandler;
-use crate::envelope::{ExecutionEvent, OrderEvent, RiskEvent, SignalEvent};
+use crate::envelope::{
+    ExecutionEvent, OrderEvent, RiskEvent, SignalEvent, SignalIntent, StrategySignal,
+};
 use crate::error::EventBusError;
 use crate::metadata::Priority;
 
+mod combiner;
+
+pub use combiner::{
+    Contribution, NetOrder, SignalCombiner, ATTRIBUTION_KEY, COMBINER_SOURCE, TARGET_KEY,
+};
+
 #[cfg(feature = "exchange-integration")]
 use exchange_connectors::{
     ExchangeConnector, ExchangeId, ExchangeOrder, OrderSide as ExOrderSide,
     OrderType as ExOrderType,
 };
 
+/// Resting quote slot: one order per strategy, symbol and side.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct QuoteKey {
+    strategy_id: Uuid,
+    symbol: String,
+    side: OrderSide,
+}
+
 /// Transforms strategy signals into validated orders via the existing OrderManager.
+///
+/// Quote signals ([`SignalIntent::ReplaceQuote`]) cancel the strategy's previous
+/// resting order on the same symbol and side before placing the new one.
+///
+/// With [`SignalToOrderBridge::with_sizer`], order signals are resized from the
+/// account's live equity, reading the stop and volatility from the signal metadata.
+/// Strategies that size their own orders should leave the bridge without a sizer.
 pub struct SignalToOrderBridge {
     manager: Arc<OrderManager>,
     order_sender: EventSender<OrderEvent>,
     mode: PublishMode,
+    quotes: Mutex<HashMap<QuoteKey, OrderId>>,
+    sizing: Option<(PositionSizer, Arc<RwLock<AccountSnapshot>>)>,
 }
 
 impl SignalToOrderBridge {
@@ -44,6 +74,93 @@ impl SignalToOrderBridge {
             manager,
             order_sender,
             mode,
+            quotes: Mutex::new(HashMap::new()),
+            sizing: None,
+        }
+    }
+
+    /// Sizes order signals with `sizer` against `account`, which the owner keeps
+    /// current, e.g. from `PerformanceTracker::account_snapshot`.
+    pub fn with_sizer(
+        mut self,
+        sizer: PositionSizer,
+        account: Arc<RwLock<AccountSnapshot>>,
+    ) -> Self {
+        self.sizing = Some((sizer, account));
+        self
+    }
+
+    /// Quantity to order for `signal`: the sized quantity when a sizer is attached
+    /// and the signal can be priced, otherwise the signal's own.
+    async fn order_quantity(&self, signal: &StrategySignal) -> Decimal {
+        let Some((sizer, account)) = &self.sizing else {
+            return signal.quantity;
+        };
+        let account = account.read().await;
+        let Some(price) = signal.limit_price.or_else(|| account.mark(&signal.symbol)) else {
+            debug!(
+                "no price to size {} signal; keeping its quantity",
+                signal.symbol
+            );
+            return signal.quantity;
+        };
+        let decimal = |key: &str| {
+            signal
+                .metadata
+                .get(key)
+                .and_then(|value| value.parse::<Decimal>().ok())
+        };
+        let mut request = SizingRequest::new(signal.symbol.clone(), signal.side, price)
+            .with_confidence(signal.confidence);
+        request.stop_price = decimal(STOP_LOSS_KEY);
+        request.volatility = decimal(VOLATILITY_KEY).map(Volatility::Fraction);
+        match sizer.size(&account, &request) {
+            Ok(sizing) => sizing.quantity,
+            Err(err) => {
+                debug!(
+                    "{} signal not sized: {}; keeping its quantity",
+                    signal.symbol, err
+                );
+                signal.quantity
+            }
+        }
+    }
+
+    /// Resting quote order of a strategy, if one is live.
+    pub async fn resting_quote(
+        &self,
+        strategy_id: Uuid,
+        symbol: &str,
+        side: OrderSide,
+    ) -> Option<OrderId> {
+        let key = QuoteKey {
+            strategy_id,
+            symbol: symbol.to_string(),
+            side,
+        };
+        self.quotes.lock().await.get(&key).copied()
+    }
+
+    /// Cancels the strategy's resting quotes on `symbol`, on one side or both.
+    async fn pull_quotes(&self, strategy_id: Uuid, symbol: &str, side: Option<OrderSide>) {
+        let pulled: Vec<OrderId> = {
+            let mut quotes = self.quotes.lock().await;
+            let keys: Vec<QuoteKey> = quotes
+                .keys()
+                .filter(|key| {
+                    key.strategy_id == strategy_id
+                        && key.symbol == symbol
+                        && side.map_or(true, |side| side == key.side)
+                })
+                .cloned()
+                .collect();
+            keys.iter().filter_map(|key| quotes.remove(key)).collect()
+        };
+        for order_id in pulled {
+            // The quote may already have filled or been cancelled elsewhere.
+            if let Err(err) = self.manager.cancel_order(order_id).await {
+                debug!("resting quote {} not cancelled: {}", order_id, err);
+            }
         }
     }
 }
@@ -54,19 +171,58 @@ impl EventHandler<SignalEvent> for SignalToOrderBridge {
         let payload = event.payload_arc();
         let signal = &payload.signal;
 
+        match signal.intent {
+            SignalIntent::Order => {}
+            SignalIntent::ReplaceQuote => {
+                self.pull_quotes(payload.strategy_id, &signal.symbol, Some(signal.side))
+                    .await;
+                if signal.quantity.is_zero() {
+                    return Ok(());
+                }
+            }
+            SignalIntent::CancelQuotes => {
+                self.pull_quotes(payload.strategy_id, &signal.symbol, None)
+                    .await;
+                return Ok(());
+            }
+            SignalIntent::TargetPosition => {
+                return Err(EventBusError::upstream(
+                    "target position signals must pass through a SignalCombiner",
+                ));
+            }
+        }
+
+        let quantity = match signal.intent {
+            SignalIntent::Order => self.order_quantity(signal).await,
+            _ => signal.quantity,
+        };
+        if quantity.is_zero() {
+            debug!("{} signal sized to zero; no order placed", signal.symbol);
+            return Ok(());
+        }
+
         let order_id = self
             .manager
             .submit_order(
                 signal.symbol.clone(),
                 signal.order_type,
                 signal.side,
-                signal.quantity,
+                quantity,
                 signal.limit_price,
                 payload.account_id.clone(),
             )
             .await
             .map_err(EventBusError::upstream)?;
 
+        if signal.intent == SignalIntent::ReplaceQuote {
+            let key = QuoteKey {
+                strategy_id: payload.strategy_id,
+                symbol: signal.symbol.clone(),
+                side: signal.side,
+            };
+            self.quotes.lock().await.insert(key, order_id);
+        }
+
         let order = self
             .manager
             .get_order(order_id)
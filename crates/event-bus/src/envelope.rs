@@ -176,6 +176,24 @@ impl MarketEvent {
     }
 }
 
+/// How the executor should act on a strategy signal.
+#[cfg(feature = "core-integration")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignalIntent {
+    /// Submit a one-shot order.
+    #[default]
+    Order,
+    /// Replace the strategy's resting quote on this symbol and side with a new limit
+    /// order; a zero quantity pulls the quote without replacing it.
+    ReplaceQuote,
+    /// Cancel every resting quote the strategy holds on this symbol.
+    CancelQuotes,
+    /// Hold the position given by side and quantity, shorts as sells, instead of
+    /// trading that quantity. Needs a
+    /// [`SignalCombiner`](crate::core_bridges::SignalCombiner) to turn it into orders.
+    TargetPosition,
+}
+
 /// Strategy signal payload describing an intent to trade.
 #[cfg(feature = "core-integration")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -196,6 +214,9 @@ pub struct StrategySignal {
     pub confidence: f64,
     /// Additional metadata emitted by the strategy.
     pub metadata: HashMap<String, String>,
+    /// Whether this is a one-shot order or maintains a resting quote.
+    #[serde(default)]
+    pub intent: SignalIntent,
 }
 
 /// Signal event produced by strategy runners.
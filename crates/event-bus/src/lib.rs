@@ -28,7 +28,7 @@ pub use dispatcher::{
 pub use envelope::{
     EventFrame, ExecutionEvent, ExecutionEventPayload, MarketEvent, MarketPayload, OrderBookLevel,
     OrderEvent, OrderEventPayload, RiskAction, RiskEvent, RiskEventPayload, SignalEvent,
-    SignalEventPayload, StrategySignal,
+    SignalEventPayload, SignalIntent, StrategySignal,
 };
 pub use error::EventBusError;
 pub use metadata::{EventKind, EventMetadata, EventSource, Priority};
@@ -43,7 +43,7 @@ pub mod prelude {
     pub use super::envelope::{
         EventFrame, ExecutionEvent, ExecutionEventPayload, MarketEvent, MarketPayload, OrderEvent,
         OrderEventPayload, RiskAction, RiskEvent, RiskEventPayload, SignalEvent,
-        SignalEventPayload, StrategySignal,
+        SignalEventPayload, SignalIntent, StrategySignal,
     };
     pub use super::error::EventBusError;
     pub use super::metadata::{EventKind, EventMetadata, EventSource, Priority};
@@ -55,5 +55,8 @@ pub mod core_bridges;
 #[cfg(feature = "exchange-integration")]
 pub mod exchange_bridges;
 
+#[cfg(feature = "neural-integration")]
+pub mod neural_bridges;
+
 #[cfg(test)]
 mod tests;
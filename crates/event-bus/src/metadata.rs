@@ -20,7 +20,7 @@ pub enum EventKind {
 }
 
 /// Event priority used to bias scheduling or backpressure decisions.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Priority {
     /// Monitoring or low-urgency telemetry.
     Low,
@@ -0,0 +1,82 @@
+#![allow(missing_docs)]
+
+//! Forwards neural model health alerts onto the risk channel.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use neural_engine::{ModelAlert, ModelAlertKind};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::channel::{EventSender, PublishMode};
+use crate::envelope::{RiskAction, RiskEvent, RiskEventPayload};
+use crate::error::EventBusError;
+use crate::metadata::{EventMetadata, Priority};
+
+/// Publishes model degradation and recovery as advisory risk events.
+pub struct ModelAlertEmitter {
+    sender: EventSender<RiskEvent>,
+    mode: PublishMode,
+}
+
+impl ModelAlertEmitter {
+    /// Creates an emitter publishing through the supplied risk sender.
+    pub fn new(sender: EventSender<RiskEvent>, mode: PublishMode) -> Self {
+        Self { sender, mode }
+    }
+
+    /// Emits a single alert; degradation is raised at high priority so consumers
+    /// relying on the model's predictions can stop trusting it promptly.
+    pub fn emit(&self, alert: &ModelAlert) -> Result<(), EventBusError> {
+        let (priority, status) = match alert.kind {
+            ModelAlertKind::Degraded => (Priority::High, "degraded"),
+            ModelAlertKind::Recovered => (Priority::Normal, "recovered"),
+        };
+        let tags = HashMap::from([
+            ("model_id".to_string(), alert.model_id.clone()),
+            ("task".to_string(), alert.task.as_str().to_string()),
+            ("model_status".to_string(), status.to_string()),
+        ]);
+        let payload = RiskEventPayload {
+            action: RiskAction::Advisory {
+                message: alert.message(),
+            },
+            priority,
+            tags,
+        };
+        let event = RiskEvent::new(EventMetadata::new("neural.monitor", priority), payload);
+        self.sender.publish(event, self.mode)
+    }
+}
+
+/// Forwards every alert from `alerts` until the neural engine is dropped.
+pub fn spawn_model_alert_forwarder(
+    mut alerts: broadcast::Receiver<ModelAlert>,
+    emitter: ModelAlertEmitter,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match alerts.recv().await {
+                Ok(alert) => {
+                    if let Err(err) = emitter.emit(&alert) {
+                        warn!(target: "event_bus.neural", %err, model_id = %alert.model_id, "failed to publish model alert");
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(target: "event_bus.neural", skipped, "model alerts dropped while lagging");
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+impl fmt::Debug for ModelAlertEmitter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModelAlertEmitter")
+            .field("mode", &self.mode)
+            .finish_non_exhaustive()
+    }
+}
@@ -8,17 +8,21 @@ use tokio::time::timeout;
 use uuid::Uuid;
 
 use crate::channel::{EventBusBuilder, PublishMode};
-use crate::core_bridges::{PortfolioUpdateBridge, SignalToOrderBridge};
-use crate::dispatcher::{ClosureHandler, EventDispatcherBuilder};
+use crate::core_bridges::{
+    Contribution, PortfolioUpdateBridge, SignalCombiner, SignalToOrderBridge, ATTRIBUTION_KEY,
+    TARGET_KEY,
+};
+use crate::dispatcher::{ClosureHandler, EventDispatcherBuilder, EventHandler};
 use crate::envelope::{
     ExecutionEvent, RiskAction, RiskEvent, RiskEventPayload, SignalEvent, SignalEventPayload,
-    StrategySignal,
+    SignalIntent, StrategySignal,
 };
 use crate::metadata::{EventMetadata, Priority};
 use crate::EventBusError;
 
 use ninja_gekko_core::order_manager::{DefaultFeeCalculator, DefaultRiskValidator, OrderManager};
-use ninja_gekko_core::types::{Execution, OrderSide, OrderType, Portfolio};
+use ninja_gekko_core::position_sizing::{AccountSnapshot, PositionSizer, SizingMethod};
+use ninja_gekko_core::types::{Execution, OrderSide, OrderStatus, OrderType, Portfolio};
 
 #[tokio::test]
 #[ignore = "pending dispatcher coordination investigation"]
@@ -64,6 +68,7 @@ async fn signal_to_order_bridge_emits_order_events() -> Result<(), EventBusError
             limit_price: Some(Decimal::new(30_000, 0)),
             confidence: 0.99,
             metadata: HashMap::new(),
+            intent: SignalIntent::Order,
         },
     };
     let event = SignalEvent::new(metadata, signal_payload);
@@ -135,6 +140,7 @@ async fn dispatch_latency_within_target() -> Result<(), EventBusError> {
             limit_price: None,
             confidence: 0.5,
             metadata: HashMap::new(),
+            intent: SignalIntent::Order,
         },
     };
     let event = SignalEvent::new(metadata, signal_payload);
@@ -248,6 +254,7 @@ async fn test_channel_send_receive_basic() -> Result<(), EventBusError> {
             limit_price: None,
             confidence: 0.8,
             metadata: HashMap::new(),
+            intent: SignalIntent::Order,
         },
     };
 
@@ -263,3 +270,398 @@ async fn test_channel_send_receive_basic() -> Result<(), EventBusError> {
     assert_eq!(received, metadata.correlation_id);
     Ok(())
 }
+
+#[cfg(feature = "neural-integration")]
+#[tokio::test]
+async fn test_model_alert_forwarded_as_advisory() -> Result<(), EventBusError> {
+    use crate::neural_bridges::{spawn_model_alert_forwarder, ModelAlertEmitter};
+    use neural_engine::{ModelAlert, ModelAlertKind, PredictionTask};
+
+    let bus = EventBusBuilder::default().build();
+    let risk_receiver = bus.risk_receiver();
+    let (alerts, alert_receiver) = tokio::sync::broadcast::channel(4);
+    let forwarder = spawn_model_alert_forwarder(
+        alert_receiver,
+        ModelAlertEmitter::new(bus.risk_sender(), PublishMode::Blocking),
+    );
+
+    alerts
+        .send(ModelAlert {
+            model_id: "arbitrage_fann@2".to_string(),
+            task: PredictionTask::Arbitrage,
+            kind: ModelAlertKind::Degraded,
+            reasons: vec!["arbitrage probability miscalibrated (ECE 0.400)".to_string()],
+            at: chrono::Utc::now(),
+        })
+        .expect("forwarder subscribed");
+    drop(alerts);
+
+    let event = timeout(Duration::from_millis(200), risk_receiver.recv_async())
+        .await
+        .expect("risk event not produced")?;
+    match &event.payload().action {
+        RiskAction::Advisory { message } => assert!(message.contains("miscalibrated")),
+        other => panic!("expected advisory, got {:?}", other),
+    }
+    assert!(matches!(event.payload().priority, Priority::High));
+    assert_eq!(event.payload().tags["model_id"], "arbitrage_fann@2");
+    assert_eq!(event.payload().tags["model_status"], "degraded");
+
+    forwarder.await.unwrap();
+    Ok(())
+}
+
+#[tokio::test]
+async fn signal_to_order_bridge_replaces_and_pulls_quotes() -> Result<(), EventBusError> {
+    let bus = EventBusBuilder::default().build();
+    let _order_receiver = bus.order_receiver();
+    let risk_manager = Box::new(DefaultRiskValidator::new(
+        Decimal::new(1_000_000, 0),
+        Decimal::new(2_000_000, 0),
+        Decimal::new(10_000_000, 0),
+    ));
+    let fee_calculator = Box::new(DefaultFeeCalculator::new(Decimal::ZERO, Decimal::ZERO));
+    let order_manager = Arc::new(OrderManager::new(risk_manager, fee_calculator));
+    let bridge = SignalToOrderBridge::new(
+        Arc::clone(&order_manager),
+        bus.order_sender(),
+        PublishMode::Blocking,
+    );
+
+    let strategy_id = Uuid::new_v4();
+    let quote = |side, quantity: i64, price: i64, intent| {
+        SignalEvent::new(
+            EventMetadata::new("test.quote", Priority::High),
+            SignalEventPayload {
+                strategy_id,
+                account_id: "acct-mm".to_string(),
+                priority: Priority::High,
+                signal: StrategySignal {
+                    exchange: None,
+                    symbol: "BTC-USD".to_string(),
+                    side,
+                    order_type: OrderType::Limit,
+                    quantity: Decimal::new(quantity, 0),
+                    limit_price: Some(Decimal::new(price, 0)),
+                    confidence: 1.0,
+                    metadata: HashMap::new(),
+                    intent,
+                },
+            },
+        )
+    };
+
+    bridge
+        .handle(quote(OrderSide::Buy, 1, 29_990, SignalIntent::ReplaceQuote))
+        .await?;
+    bridge
+        .handle(quote(OrderSide::Sell, 1, 30_010, SignalIntent::ReplaceQuote))
+        .await?;
+    let first_bid = bridge
+        .resting_quote(strategy_id, "BTC-USD", OrderSide::Buy)
+        .await
+        .expect("bid quote resting");
+
+    bridge
+        .handle(quote(OrderSide::Buy, 1, 29_995, SignalIntent::ReplaceQuote))
+        .await?;
+    let second_bid = bridge
+        .resting_quote(strategy_id, "BTC-USD", OrderSide::Buy)
+        .await
+        .expect("replacement bid resting");
+    assert_ne!(first_bid, second_bid);
+    let cancelled = order_manager.get_order(first_bid).await.unwrap();
+    assert_eq!(cancelled.status, OrderStatus::Cancelled);
+    let replacement = order_manager.get_order(second_bid).await.unwrap();
+    assert_eq!(replacement.price, Some(Decimal::new(29_995, 0)));
+
+    let ask = bridge
+        .resting_quote(strategy_id, "BTC-USD", OrderSide::Sell)
+        .await
+        .unwrap();
+    bridge
+        .handle(quote(OrderSide::Sell, 0, 0, SignalIntent::CancelQuotes))
+        .await?;
+    assert!(bridge
+        .resting_quote(strategy_id, "BTC-USD", OrderSide::Buy)
+        .await
+        .is_none());
+    for order_id in [second_bid, ask] {
+        let order = order_manager.get_order(order_id).await.unwrap();
+        assert_eq!(order.status, OrderStatus::Cancelled);
+    }
+    assert_eq!(order_manager.list_orders("acct-mm".to_string()).await.unwrap().len(), 3);
+    Ok(())
+}
+
+fn combiner_signal(
+    strategy_id: Uuid,
+    side: OrderSide,
+    quantity: i64,
+    confidence: f64,
+    intent: SignalIntent,
+) -> SignalEvent {
+    SignalEvent::new(
+        EventMetadata::new("test.combiner", Priority::Normal),
+        SignalEventPayload {
+            strategy_id,
+            account_id: "acct-shared".to_string(),
+            priority: Priority::Normal,
+            signal: StrategySignal {
+                exchange: None,
+                symbol: "BTC-USD".to_string(),
+                side,
+                order_type: OrderType::Market,
+                quantity: Decimal::new(quantity, 0),
+                limit_price: None,
+                confidence,
+                metadata: HashMap::new(),
+                intent,
+            },
+        },
+    )
+}
+
+/// Downstream handler paired with the events it received
+type RecordingHandler = (Arc<dyn EventHandler<SignalEvent>>, Arc<Mutex<Vec<SignalEvent>>>);
+
+fn recording_handler() -> RecordingHandler {
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&sent);
+    let handler = ClosureHandler::new(move |event: SignalEvent| {
+        let recorded = Arc::clone(&recorded);
+        async move {
+            recorded.lock().await.push(event);
+            Ok(())
+        }
+    });
+    (Arc::new(handler), sent)
+}
+
+#[tokio::test]
+async fn signal_combiner_nets_strategies_into_one_attributed_order() -> Result<(), EventBusError> {
+    let (downstream, sent) = recording_handler();
+    let combiner = SignalCombiner::new(downstream).batched();
+    let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+    combiner
+        .handle(combiner_signal(a, OrderSide::Buy, 3, 1.0, SignalIntent::Order))
+        .await?;
+    combiner
+        .handle(combiner_signal(b, OrderSide::Sell, 1, 1.0, SignalIntent::Order))
+        .await?;
+    combiner
+        .handle(combiner_signal(c, OrderSide::Buy, 4, 0.5, SignalIntent::TargetPosition))
+        .await?;
+    assert!(sent.lock().await.is_empty());
+
+    let netted = combiner.flush().await?;
+    assert_eq!(netted.len(), 1);
+    assert_eq!(netted[0].delta, Decimal::new(4, 0));
+    assert_eq!(netted[0].contributions.len(), 3);
+    {
+        let sent = sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        let payload = sent[0].payload();
+        assert_eq!(payload.strategy_id, combiner.id());
+        assert_eq!(payload.signal.side, OrderSide::Buy);
+        assert_eq!(payload.signal.quantity, Decimal::new(4, 0));
+        assert_eq!(payload.signal.metadata[TARGET_KEY], "4");
+        assert!(payload.signal.metadata[ATTRIBUTION_KEY].contains(&format!("{}=-1", b)));
+        assert_eq!(
+            Some(sent[0].metadata().correlation_id),
+            netted[0].correlation_id
+        );
+    }
+
+    // A fill carried under the order's correlation id splits back onto the strategies.
+    let correlation_id = netted[0].correlation_id.unwrap();
+    let shares = combiner
+        .attribute_fill(correlation_id, Decimal::new(2, 0))
+        .await;
+    let share = |id| {
+        shares
+            .iter()
+            .find(|share| share.strategy_id == id)
+            .map(|share| share.quantity)
+    };
+    assert_eq!(share(a), Some(Decimal::new(15, 1)));
+    assert_eq!(share(b), Some(Decimal::new(-5, 1)));
+    assert_eq!(share(c), Some(Decimal::ONE));
+
+    // Flipping one strategy short only orders the change of the combined target.
+    combiner
+        .handle(combiner_signal(b, OrderSide::Sell, 5, 1.0, SignalIntent::TargetPosition))
+        .await?;
+    let netted = combiner.flush().await?;
+    assert_eq!(netted[0].delta, Decimal::new(-4, 0));
+    assert_eq!(
+        netted[0].contributions,
+        vec![Contribution {
+            strategy_id: b,
+            quantity: Decimal::new(-4, 0),
+        }]
+    );
+    assert_eq!(combiner.position("acct-shared", "BTC-USD").await, Decimal::ZERO);
+
+    // Signals that cross between flushes never reach order creation.
+    combiner
+        .handle(combiner_signal(a, OrderSide::Buy, 2, 1.0, SignalIntent::Order))
+        .await?;
+    combiner
+        .handle(combiner_signal(c, OrderSide::Sell, 4, 0.5, SignalIntent::Order))
+        .await?;
+    let netted = combiner.flush().await?;
+    assert_eq!(netted[0].delta, Decimal::ZERO);
+    assert_eq!(netted[0].correlation_id, None);
+    assert_eq!(sent.lock().await.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn signal_combiner_flattens_whatever_the_confidence() -> Result<(), EventBusError> {
+    let (downstream, sent) = recording_handler();
+    let combiner = SignalCombiner::new(downstream);
+    let strategy = Uuid::new_v4();
+
+    combiner
+        .handle(combiner_signal(strategy, OrderSide::Buy, 1, 0.5, SignalIntent::Order))
+        .await?;
+    assert_eq!(
+        combiner.position("acct-shared", "BTC-USD").await,
+        Decimal::new(5, 1)
+    );
+
+    // Selling back what was bought leaves the strategy flat whatever the confidence.
+    combiner
+        .handle(combiner_signal(strategy, OrderSide::Sell, 1, 1.0, SignalIntent::Order))
+        .await?;
+    assert_eq!(combiner.position("acct-shared", "BTC-USD").await, Decimal::ZERO);
+    let sent = sent.lock().await;
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[1].payload().signal.side, OrderSide::Sell);
+    assert_eq!(sent[1].payload().signal.quantity, Decimal::new(5, 1));
+    Ok(())
+}
+
+#[tokio::test]
+async fn signal_combiner_never_sells_on_a_low_confidence_buy() -> Result<(), EventBusError> {
+    let (downstream, sent) = recording_handler();
+    let combiner = SignalCombiner::new(downstream);
+    let strategy = Uuid::new_v4();
+
+    combiner
+        .handle(combiner_signal(strategy, OrderSide::Buy, 100, 1.0, SignalIntent::Order))
+        .await?;
+    // A small, unsure add-on only nudges the established position up.
+    combiner
+        .handle(combiner_signal(strategy, OrderSide::Buy, 1, 0.1, SignalIntent::Order))
+        .await?;
+    assert_eq!(
+        combiner.position("acct-shared", "BTC-USD").await,
+        Decimal::new(1001, 1)
+    );
+    let sent = sent.lock().await;
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[1].payload().signal.side, OrderSide::Buy);
+    assert_eq!(sent[1].payload().signal.quantity, Decimal::new(1, 1));
+    Ok(())
+}
+
+#[tokio::test]
+async fn signal_combiner_caps_strategies_at_their_budget() -> Result<(), EventBusError> {
+    let (downstream, sent) = recording_handler();
+    let capped = Uuid::new_v4();
+    let combiner =
+        SignalCombiner::new(downstream).with_budget(capped, Decimal::new(1_000, 0));
+
+    // Without a mark the budget cannot be sized, so nothing is ordered.
+    combiner
+        .handle(combiner_signal(capped, OrderSide::Buy, 25, 1.0, SignalIntent::Order))
+        .await?;
+    assert!(sent.lock().await.is_empty());
+
+    combiner.update_mark("BTC-USD", Decimal::new(100, 0)).await;
+    combiner.flush().await?;
+    combiner
+        .handle(combiner_signal(capped, OrderSide::Sell, 40, 1.0, SignalIntent::Order))
+        .await?;
+    {
+        let sent = sent.lock().await;
+        let quantities: Vec<(OrderSide, Decimal)> = sent
+            .iter()
+            .map(|event| (event.payload().signal.side, event.payload().signal.quantity))
+            .collect();
+        assert_eq!(
+            quantities,
+            vec![
+                (OrderSide::Buy, Decimal::new(10, 0)),
+                (OrderSide::Sell, Decimal::new(20, 0)),
+            ]
+        );
+    }
+    assert_eq!(
+        combiner.position("acct-shared", "BTC-USD").await,
+        Decimal::new(-10, 0)
+    );
+
+    // Quotes are forwarded as they are.
+    let mut quote = combiner_signal(capped, OrderSide::Buy, 1, 1.0, SignalIntent::ReplaceQuote);
+    quote = SignalEvent::new(quote.metadata().clone(), {
+        let mut payload = quote.payload().clone();
+        payload.signal.order_type = OrderType::Limit;
+        payload.signal.limit_price = Some(Decimal::new(99, 0));
+        payload
+    });
+    combiner.handle(quote).await?;
+    let sent = sent.lock().await;
+    assert_eq!(sent.len(), 3);
+    assert_eq!(sent[2].payload().strategy_id, capped);
+    Ok(())
+}
+
+#[tokio::test]
+async fn signal_to_order_bridge_sizes_orders_from_live_equity() -> Result<(), EventBusError> {
+    let bus = EventBusBuilder::default().build();
+    let _order_receiver = bus.order_receiver();
+    let risk_manager = Box::new(DefaultRiskValidator::new(
+        Decimal::new(1_000_000, 0),
+        Decimal::new(2_000_000, 0),
+        Decimal::new(10_000_000, 0),
+    ));
+    let fee_calculator = Box::new(DefaultFeeCalculator::new(Decimal::ZERO, Decimal::ZERO));
+    let order_manager = Arc::new(OrderManager::new(risk_manager, fee_calculator));
+
+    let mut account = AccountSnapshot::new(Decimal::new(100_000, 0));
+    account
+        .marks
+        .insert("BTC-USD".to_string(), Decimal::new(50_000, 0));
+    let sizer = PositionSizer::new(SizingMethod::Kelly {
+        fraction: Decimal::new(5, 1),
+        payoff_ratio: Decimal::TWO,
+    });
+    let bridge = SignalToOrderBridge::new(
+        Arc::clone(&order_manager),
+        bus.order_sender(),
+        PublishMode::Blocking,
+    )
+    .with_sizer(sizer, Arc::new(RwLock::new(account)));
+
+    let strategy_id = Uuid::new_v4();
+    // Half Kelly at 60% confidence and 2:1 payoff stakes 20% of equity: 0.4 BTC.
+    bridge
+        .handle(combiner_signal(strategy_id, OrderSide::Buy, 1, 0.6, SignalIntent::Order))
+        .await?;
+    // Without an edge the signal is dropped.
+    bridge
+        .handle(combiner_signal(strategy_id, OrderSide::Buy, 1, 0.3, SignalIntent::Order))
+        .await?;
+
+    let orders = order_manager
+        .list_orders("acct-shared".to_string())
+        .await
+        .unwrap();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].quantity, Decimal::new(4, 1));
+    Ok(())
+}
@@ -4,12 +4,14 @@
 //! can feed the Ninja Gekko data pipeline without trading credentials. REST
 //! endpoints remain stubbed until order routing is required.
 
+use crate::capture::{CapturedFrame, FrameRecorder};
 use crate::{
     Balance, ExchangeConnector, ExchangeError, ExchangeId, ExchangeOrder, ExchangeResult, Fill,
     MarketTick, OrderSide, OrderStatus, OrderType, StreamMessage, TransferRequest, TransferStatus,
 };
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
+use parking_lot::RwLock;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -34,6 +36,7 @@ struct BinanceInner {
     ws_url: Url,
     #[allow(dead_code)]
     rest_url: Url,
+    recorder: RwLock<Option<FrameRecorder>>,
 }
 
 impl BinanceUsConnector {
@@ -43,9 +46,15 @@ impl BinanceUsConnector {
                 connected: AtomicBool::new(false),
                 ws_url: Url::parse(BINANCE_US_WS_URL).expect("valid Binance.us ws url"),
                 rest_url: Url::parse(BINANCE_US_REST_URL).expect("valid Binance.us rest url"),
+                recorder: RwLock::new(None),
             }),
         }
     }
+
+    /// Records every raw market data frame to `recorder` on subsequent streams.
+    pub fn set_recorder(&self, recorder: FrameRecorder) {
+        *self.inner.recorder.write() = Some(recorder);
+    }
 }
 
 #[async_trait]
@@ -123,10 +132,12 @@ impl ExchangeConnector for BinanceUsConnector {
         let ws_url = self.inner.ws_url.clone();
         let mapping = Arc::new(build_symbol_mapping(&symbols));
         let subscriptions = Arc::new(build_subscription_params(&symbols));
+        let recorder = self.inner.recorder.read().clone();
 
         tokio::spawn(async move {
             if let Err(err) =
-                run_binance_market_stream(ws_url, mapping, subscriptions, tx.clone()).await
+                run_binance_market_stream(ws_url, mapping, subscriptions, recorder, tx.clone())
+                    .await
             {
                 error!(%err, "binance.us stream terminated with error");
             }
@@ -166,7 +177,7 @@ impl ExchangeConnector for BinanceUsConnector {
     }
 }
 
-fn build_symbol_mapping(symbols: &[String]) -> HashMap<String, String> {
+pub(crate) fn build_symbol_mapping(symbols: &[String]) -> HashMap<String, String> {
     let mut mapping = HashMap::new();
     for symbol in symbols {
         let stream_key = canonical_symbol(symbol);
@@ -197,6 +208,7 @@ async fn run_binance_market_stream(
     ws_url: Url,
     symbol_mapping: Arc<HashMap<String, String>>,
     subscriptions: Arc<Vec<String>>,
+    recorder: Option<FrameRecorder>,
     sender: mpsc::UnboundedSender<StreamMessage>,
 ) -> Result<(), ExchangeError> {
     let mut attempt: u32 = 0;
@@ -224,19 +236,11 @@ async fn run_binance_market_stream(
                             if text.contains("\"result\"") {
                                 continue;
                             }
-                            if let Err(err) =
-                                handle_binance_payload(&text, &symbol_mapping, &sender)
-                            {
-                                warn!(%err, "failed to process Binance.us payload");
-                            }
+                            process_binance_frame(&text, &symbol_mapping, &recorder, &sender);
                         }
                         Ok(Message::Binary(bin)) => {
                             if let Ok(text) = String::from_utf8(bin) {
-                                if let Err(err) =
-                                    handle_binance_payload(&text, &symbol_mapping, &sender)
-                                {
-                                    warn!(%err, "failed to process Binance.us payload");
-                                }
+                                process_binance_frame(&text, &symbol_mapping, &recorder, &sender);
                             }
                         }
                         Ok(Message::Ping(payload)) => {
@@ -281,9 +285,29 @@ async fn run_binance_market_stream(
     }
 }
 
-fn handle_binance_payload(
+fn process_binance_frame(
+    payload: &str,
+    symbol_mapping: &HashMap<String, String>,
+    recorder: &Option<FrameRecorder>,
+    sender: &mpsc::UnboundedSender<StreamMessage>,
+) {
+    let received_at = chrono::Utc::now();
+    if let Some(recorder) = recorder {
+        recorder.record_frame(&CapturedFrame {
+            received_at,
+            exchange: ExchangeId::BinanceUs,
+            payload: payload.to_string(),
+        });
+    }
+    if let Err(err) = handle_binance_payload(payload, symbol_mapping, received_at, sender) {
+        warn!(%err, "failed to process Binance.us payload");
+    }
+}
+
+pub(crate) fn handle_binance_payload(
     payload: &str,
     symbol_mapping: &HashMap<String, String>,
+    received_at: chrono::DateTime<chrono::Utc>,
     sender: &mpsc::UnboundedSender<StreamMessage>,
 ) -> Result<(), ExchangeError> {
     let value: serde_json::Value = serde_json::from_str(payload)
@@ -300,9 +324,9 @@ fn handle_binance_payload(
     };
 
     if stream_name.ends_with("@bookTicker") {
-        emit_book_ticker(data, symbol_mapping, sender)?;
+        emit_book_ticker(data, symbol_mapping, received_at, sender)?;
     } else if stream_name.ends_with("@depth@100ms") {
-        emit_depth_updates(data, symbol_mapping, sender)?;
+        emit_depth_updates(data, symbol_mapping, received_at, sender)?;
     }
 
     Ok(())
@@ -311,6 +335,7 @@ fn handle_binance_payload(
 fn emit_book_ticker(
     data: &serde_json::Value,
     symbol_mapping: &HashMap<String, String>,
+    received_at: chrono::DateTime<chrono::Utc>,
     sender: &mpsc::UnboundedSender<StreamMessage>,
 ) -> Result<(), ExchangeError> {
     let symbol = data
@@ -333,7 +358,7 @@ fn emit_book_ticker(
         ask: ask_price,
         last: last_price,
         volume_24h: volume,
-        timestamp: chrono::Utc::now(),
+        timestamp: received_at,
     };
 
     let _ = sender.send(StreamMessage::Tick(tick));
@@ -343,6 +368,7 @@ fn emit_book_ticker(
 fn emit_depth_updates(
     data: &serde_json::Value,
     symbol_mapping: &HashMap<String, String>,
+    received_at: chrono::DateTime<chrono::Utc>,
     sender: &mpsc::UnboundedSender<StreamMessage>,
 ) -> Result<(), ExchangeError> {
     let symbol = data
@@ -358,7 +384,7 @@ fn emit_depth_updates(
         .get("E")
         .and_then(|v| v.as_u64())
         .map(timestamp_from_ms)
-        .unwrap_or_else(chrono::Utc::now);
+        .unwrap_or(received_at);
 
     if let Some(bids) = data.get("b").and_then(|v| v.as_array()) {
         for level in bids {
@@ -10,11 +10,13 @@
 use async_trait::async_trait;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use thiserror::Error;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
 pub mod binance_us;
+pub mod capture;
 pub mod credentials;
 pub mod kraken;
 pub mod oanda;
@@ -285,6 +287,29 @@ pub enum TransferStatus {
     Cancelled,
 }
 
+/// Fee structure for a trading platform
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeStructure {
+    /// Maker fee (negative means rebate)
+    pub maker_fee: Decimal,
+
+    /// Taker fee
+    pub taker_fee: Decimal,
+
+    /// Withdrawal fees per currency
+    pub withdrawal_fees: HashMap<String, Decimal>,
+}
+
+impl Default for FeeStructure {
+    fn default() -> Self {
+        Self {
+            maker_fee: Decimal::new(-1, 4), // -0.0001 (0.01% rebate)
+            taker_fee: Decimal::new(1, 3),  // 0.001 (0.1%)
+            withdrawal_fees: HashMap::new(),
+        }
+    }
+}
+
 /// Exchange configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExchangeConfig {
@@ -1,5 +1,6 @@
 //! OANDA v20 streaming connector focused on market data ingestion.
 
+use crate::capture::{CapturedFrame, FrameRecorder};
 use crate::{
     Balance, ExchangeConnector, ExchangeError, ExchangeId, ExchangeOrder, ExchangeResult,
     MarketTick, OrderSide, OrderType, StreamMessage, TransferRequest, TransferStatus,
@@ -30,6 +31,7 @@ struct OandaInner {
     connected: AtomicBool,
     stream_host: String,
     credentials: RwLock<Option<OandaCredentials>>,
+    recorder: RwLock<Option<FrameRecorder>>,
 }
 
 /// OANDA v20 connector offering streaming price data.
@@ -64,6 +66,11 @@ impl OandaConnector {
         *self.inner.credentials.write() = Some(credentials);
     }
 
+    /// Records every raw pricing line to `recorder` on subsequent streams.
+    pub fn set_recorder(&self, recorder: FrameRecorder) {
+        *self.inner.recorder.write() = Some(recorder);
+    }
+
     fn new_with_host(host: &str) -> Self {
         Self {
             inner: Arc::new(OandaInner {
@@ -71,6 +78,7 @@ impl OandaConnector {
                 connected: AtomicBool::new(false),
                 stream_host: host.to_string(),
                 credentials: RwLock::new(None),
+                recorder: RwLock::new(None),
             }),
         }
     }
@@ -155,10 +163,12 @@ impl ExchangeConnector for OandaConnector {
         let client = self.inner.client.clone();
         let host = self.inner.stream_host.clone();
         let instruments = symbols.join(",");
+        let recorder = self.inner.recorder.read().clone();
 
         tokio::spawn(async move {
             if let Err(err) =
-                run_oanda_price_stream(client, host, credentials, instruments, tx.clone()).await
+                run_oanda_price_stream(client, host, credentials, instruments, recorder, tx.clone())
+                    .await
             {
                 error!(%err, "oanda price stream terminated");
             }
@@ -203,6 +213,7 @@ async fn run_oanda_price_stream(
     host: String,
     credentials: OandaCredentials,
     instruments: String,
+    recorder: Option<FrameRecorder>,
     sender: mpsc::UnboundedSender<StreamMessage>,
 ) -> Result<(), ExchangeError> {
     let mut attempt: u32 = 0;
@@ -233,7 +244,7 @@ async fn run_oanda_price_stream(
                     warn!(status = %resp.status(), "oanda streaming request failed");
                 } else {
                     attempt = 0;
-                    if let Err(err) = consume_oanda_stream(resp, &sender).await {
+                    if let Err(err) = consume_oanda_stream(resp, recorder.as_ref(), &sender).await {
                         warn!(%err, "error while consuming OANDA stream");
                     }
                 }
@@ -257,6 +268,7 @@ async fn run_oanda_price_stream(
 
 async fn consume_oanda_stream(
     response: reqwest::Response,
+    recorder: Option<&FrameRecorder>,
     sender: &mpsc::UnboundedSender<StreamMessage>,
 ) -> Result<(), ExchangeError> {
     let mut buffer = String::new();
@@ -269,7 +281,15 @@ async fn consume_oanda_stream(
         while let Some(pos) = buffer.find('\n') {
             let line = buffer[..pos].trim();
             if !line.is_empty() {
-                if let Err(err) = handle_oanda_line(line, sender) {
+                let received_at = chrono::Utc::now();
+                if let Some(recorder) = recorder {
+                    recorder.record_frame(&CapturedFrame {
+                        received_at,
+                        exchange: ExchangeId::Oanda,
+                        payload: line.to_string(),
+                    });
+                }
+                if let Err(err) = handle_oanda_line(line, received_at, sender) {
                     warn!(%err, "failed to parse OANDA tick");
                 }
             }
@@ -284,15 +304,16 @@ async fn consume_oanda_stream(
     Ok(())
 }
 
-fn handle_oanda_line(
+pub(crate) fn handle_oanda_line(
     line: &str,
+    received_at: chrono::DateTime<chrono::Utc>,
     sender: &mpsc::UnboundedSender<StreamMessage>,
 ) -> Result<(), ExchangeError> {
     let value: serde_json::Value = serde_json::from_str(line)
         .map_err(|err| ExchangeError::Network(format!("invalid OANDA payload: {err}")))?;
 
     match value.get("type").and_then(|v| v.as_str()) {
-        Some("PRICE") => emit_oanda_price(&value, sender)?,
+        Some("PRICE") => emit_oanda_price(&value, received_at, sender)?,
         Some("HEARTBEAT") => debug!("oanda heartbeat received"),
         _ => {}
     }
@@ -301,6 +322,7 @@ fn handle_oanda_line(
 
 fn emit_oanda_price(
     value: &serde_json::Value,
+    received_at: chrono::DateTime<chrono::Utc>,
     sender: &mpsc::UnboundedSender<StreamMessage>,
 ) -> Result<(), ExchangeError> {
     let instrument = value
@@ -339,7 +361,7 @@ fn emit_oanda_price(
         .and_then(|v| v.as_str())
         .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
         .map(|dt| dt.with_timezone(&chrono::Utc))
-        .unwrap_or_else(chrono::Utc::now);
+        .unwrap_or(received_at);
 
     let tick = MarketTick {
         symbol: instrument.replace('_', "-"),
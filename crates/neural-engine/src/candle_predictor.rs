@@ -0,0 +1,220 @@
+//! Candle CPU/GPU predictors
+//!
+//! Feed-forward (MLP) and LSTM networks loaded from safetensors weights. MLP
+//! weights are named `layers.{i}.weight` / `layers.{i}.bias`; LSTM weights use
+//! the `lstm` prefix for the recurrent cell and `head` for the output
+//! projection. Outputs pass through a sigmoid, so models are trained on
+//! unit-scaled targets and the optional scalers map back to task units.
+
+use crate::predictor::{PredictionInput, PredictionTask, Predictor, PredictorInfo};
+use crate::training::MinMaxScaler;
+use crate::{ModelType, NeuralBackend, NeuralError, NeuralResult};
+use candle_core::{DType, Device, Module, Tensor};
+use candle_nn::{Linear, VarBuilder, LSTM, RNN};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use tracing::info;
+
+/// Network layout of a Candle model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CandleArchitecture {
+    /// ReLU hidden layers
+    Mlp { hidden_layers: Vec<usize> },
+    /// Single-layer LSTM over the last `sequence_length` feature vectors of a series
+    Lstm {
+        hidden_size: usize,
+        sequence_length: usize,
+    },
+}
+
+/// Everything needed to rebuild a Candle model around its weights
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CandleModelSpec {
+    pub id: String,
+    pub task: PredictionTask,
+    pub architecture: CandleArchitecture,
+    #[serde(default)]
+    pub input_scaler: Option<MinMaxScaler>,
+    #[serde(default)]
+    pub output_scaler: Option<MinMaxScaler>,
+    #[serde(default)]
+    pub accuracy: Option<f64>,
+    #[serde(default)]
+    pub trained_at: Option<String>,
+}
+
+enum CandleNetwork {
+    Mlp(Vec<Linear>),
+    Lstm {
+        lstm: LSTM,
+        head: Linear,
+        sequence_length: usize,
+    },
+}
+
+/// Predictor running a Candle MLP or LSTM
+pub struct CandlePredictor {
+    info: PredictorInfo,
+    spec: CandleModelSpec,
+    network: CandleNetwork,
+    device: Device,
+    /// Recent scaled feature vectors per series, for sequence models
+    windows: HashMap<String, VecDeque<Vec<f32>>>,
+}
+
+impl CandlePredictor {
+    /// Load weights from a safetensors file onto `device`
+    pub fn load(
+        spec: CandleModelSpec,
+        weights_path: impl AsRef<Path>,
+        device: &Device,
+    ) -> NeuralResult<Self> {
+        let path = weights_path.as_ref();
+        if !path.exists() {
+            return Err(NeuralError::ModelNotFound(format!(
+                "Model file not found: {:?}",
+                path
+            )));
+        }
+
+        // SAFETY: the weights file is memory-mapped read-only and must not be
+        // modified while the model is alive
+        let vb = unsafe { VarBuilder::from_mmaped_safetensors(&[path], DType::F32, device) }
+            .map_err(load_error)?;
+
+        let input_size = spec.task.input_size();
+        let output_size = spec.task.output_size();
+        let (network, model_type) = match &spec.architecture {
+            CandleArchitecture::Mlp { hidden_layers } => {
+                let mut layers = Vec::with_capacity(hidden_layers.len() + 1);
+                let mut width = input_size;
+                for (i, &size) in hidden_layers.iter().chain([output_size].iter()).enumerate() {
+                    layers.push(
+                        candle_nn::linear(width, size, vb.pp(format!("layers.{}", i)))
+                            .map_err(load_error)?,
+                    );
+                    width = size;
+                }
+                (CandleNetwork::Mlp(layers), ModelType::MLP)
+            }
+            CandleArchitecture::Lstm {
+                hidden_size,
+                sequence_length,
+            } => {
+                let lstm = candle_nn::lstm(
+                    input_size,
+                    *hidden_size,
+                    candle_nn::LSTMConfig::default(),
+                    vb.pp("lstm"),
+                )
+                .map_err(load_error)?;
+                let head = candle_nn::linear(*hidden_size, output_size, vb.pp("head"))
+                    .map_err(load_error)?;
+                (
+                    CandleNetwork::Lstm {
+                        lstm,
+                        head,
+                        sequence_length: (*sequence_length).max(1),
+                    },
+                    ModelType::LSTM,
+                )
+            }
+        };
+
+        let mut info = PredictorInfo::new(spec.id.clone(), spec.task, NeuralBackend::Candle);
+        info.model_type = Some(model_type);
+        info.accuracy = spec.accuracy;
+        info.trained_at = spec.trained_at.clone();
+
+        info!(
+            "📂 Loaded Candle {:?} model '{}' from {:?}",
+            model_type, spec.id, path
+        );
+
+        Ok(Self {
+            info,
+            spec,
+            network,
+            device: device.clone(),
+            windows: HashMap::new(),
+        })
+    }
+
+    fn forward(&self, rows: &[Vec<f32>]) -> candle_core::Result<Vec<f32>> {
+        let width = self.spec.task.input_size();
+        let flat: Vec<f32> = rows.iter().flatten().copied().collect();
+
+        let logits = match &self.network {
+            CandleNetwork::Mlp(layers) => {
+                let mut x = Tensor::from_vec(flat, (1, width), &self.device)?;
+                for (i, layer) in layers.iter().enumerate() {
+                    x = layer.forward(&x)?;
+                    if i + 1 < layers.len() {
+                        x = x.relu()?;
+                    }
+                }
+                x
+            }
+            CandleNetwork::Lstm { lstm, head, .. } => {
+                let x = Tensor::from_vec(flat, (1, rows.len(), width), &self.device)?;
+                let states = lstm.seq(&x)?;
+                let last = states.last().ok_or_else(|| {
+                    candle_core::Error::Msg("LSTM produced no states".to_string())
+                })?;
+                head.forward(last.h())?
+            }
+        };
+
+        candle_nn::ops::sigmoid(&logits)?
+            .squeeze(0)?
+            .to_vec1::<f32>()
+    }
+}
+
+impl Predictor for CandlePredictor {
+    fn info(&self) -> &PredictorInfo {
+        &self.info
+    }
+
+    fn predict(&mut self, input: &PredictionInput) -> NeuralResult<Option<Vec<f64>>> {
+        let scaled = match &self.spec.input_scaler {
+            Some(scaler) => scaler.transform(&input.features),
+            None => input.features.clone(),
+        };
+        let row: Vec<f32> = scaled.iter().map(|v| *v as f32).collect();
+
+        let rows: Vec<Vec<f32>> = match &self.network {
+            CandleNetwork::Mlp(_) => vec![row],
+            CandleNetwork::Lstm {
+                sequence_length, ..
+            } => {
+                let window = self.windows.entry(input.key.clone()).or_default();
+                window.push_back(row);
+                while window.len() > *sequence_length {
+                    window.pop_front();
+                }
+                if window.len() < *sequence_length {
+                    return Ok(None);
+                }
+                window.iter().cloned().collect()
+            }
+        };
+
+        let output: Vec<f64> = self
+            .forward(&rows)
+            .map_err(|e| NeuralError::InferenceFailed(e.to_string()))?
+            .into_iter()
+            .map(f64::from)
+            .collect();
+
+        Ok(Some(match &self.spec.output_scaler {
+            Some(scaler) => scaler.inverse(&output),
+            None => output,
+        }))
+    }
+}
+
+fn load_error(e: candle_core::Error) -> NeuralError {
+    NeuralError::ModelLoadingFailed(e.to_string())
+}
@@ -4,7 +4,10 @@
 //! inference in the arbitrage trading system. It wraps FANN networks and provides
 //! a unified interface for model loading and forward propagation.
 
-use crate::{NeuralError, NeuralResult};
+use crate::model_registry::{ModelLoader, ModelRecord};
+use crate::predictor::{PredictionInput, PredictionTask, Predictor, PredictorInfo};
+use crate::training::MinMaxScaler;
+use crate::{ModelType, NeuralBackend, NeuralError, NeuralResult};
 use ruv_fann::{ActivationFunction, Network, NetworkBuilder};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -27,6 +30,19 @@ pub struct FannModelMetadata {
     pub training_date: Option<String>,
     /// Model accuracy from validation set
     pub accuracy: Option<f64>,
+    /// Input scaling fitted on the training set
+    #[serde(default)]
+    pub input_scaler: Option<MinMaxScaler>,
+    /// Output scaling fitted on the training set, inverted after inference
+    #[serde(default)]
+    pub output_scaler: Option<MinMaxScaler>,
+}
+
+/// On-disk model format: metadata plus the flattened connection weights
+#[derive(Debug, Serialize, Deserialize)]
+struct FannModelFile {
+    metadata: FannModelMetadata,
+    weights: Vec<f64>,
 }
 
 impl Default for FannModelMetadata {
@@ -39,6 +55,8 @@ impl Default for FannModelMetadata {
             description: String::new(),
             training_date: None,
             accuracy: None,
+            input_scaler: None,
+            output_scaler: None,
         }
     }
 }
@@ -89,8 +107,7 @@ impl FannModel {
             output_size,
             hidden_layers: hidden_sizes.to_vec(),
             description: "Newly created FANN model".to_string(),
-            training_date: None,
-            accuracy: None,
+            ..FannModelMetadata::default()
         };
 
         Ok(Self {
@@ -100,22 +117,16 @@ impl FannModel {
         })
     }
 
-    /// Load a FANN model from a .net file
+    /// Load a trained FANN model written by [`FannModel::save`]
     ///
-    /// **Note**: Full file loading is not yet implemented. This function validates
-    /// that the file exists and creates a network with the expected dimensions.
-    /// Once proper file parsing is implemented, this will load actual trained weights.
+    /// The network is rebuilt from the stored architecture and its weights
+    /// restored, so inference matches the model as it was at the end of
+    /// training.
     ///
     /// # Arguments
-    /// * `path` - Path to the .net model file
+    /// * `path` - Path to the model file
     /// * `expected_input_size` - Expected number of input neurons (for validation)
     /// * `expected_output_size` - Expected number of output neurons (for validation)
-    ///
-    /// # Current Behavior
-    /// - Validates file existence
-    /// - Creates untrained network with specified dimensions
-    /// - Logs warning about placeholder implementation
-    #[allow(dead_code)]
     pub fn load_from_file(
         path: &str,
         expected_input_size: usize,
@@ -132,19 +143,70 @@ impl FannModel {
 
         info!("📂 Loading FANN model from: {}", path);
 
-        // TODO: Implement proper .net file parsing when ruv-fann adds serialization support
-        // For now, create a model with the expected dimensions
-        warn!(
-            "FANN file loading not fully implemented - creating untrained network with expected dimensions. \
-            Train this network before production use."
-        );
+        let contents = std::fs::read_to_string(path_obj).map_err(|e| {
+            NeuralError::ModelLoadingFailed(format!("Failed to read {}: {}", path, e))
+        })?;
+        let file: FannModelFile = serde_json::from_str(&contents).map_err(|e| {
+            NeuralError::ModelLoadingFailed(format!("Invalid model file {}: {}", path, e))
+        })?;
+
+        let mut model = Self::new(
+            file.metadata.input_size,
+            &file.metadata.hidden_layers,
+            file.metadata.output_size,
+        )?;
+        model.validate_dimensions(expected_input_size, expected_output_size)?;
+        model.set_weights(&file.weights)?;
+        model.metadata = file.metadata;
+        model.file_path = Some(path.to_string());
+
+        if model.metadata.training_date.is_none() {
+            warn!("Model {} has no training date - it may be untrained", path);
+        }
 
-        let model = Self::new(expected_input_size, &[16, 8], expected_output_size)?;
+        Ok(model)
+    }
 
-        Ok(Self {
-            file_path: Some(path.to_string()),
-            ..model
-        })
+    /// Write the model's metadata and weights to `path`
+    pub fn save(&mut self, path: impl AsRef<Path>) -> NeuralResult<()> {
+        let path = path.as_ref();
+        let file = FannModelFile {
+            metadata: self.metadata.clone(),
+            weights: self.weights(),
+        };
+        let contents = serde_json::to_string(&file).map_err(|e| {
+            NeuralError::ModelLoadingFailed(format!("Failed to serialize model: {}", e))
+        })?;
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                NeuralError::ModelLoadingFailed(format!("Failed to create {:?}: {}", parent, e))
+            })?;
+        }
+        std::fs::write(path, contents).map_err(|e| {
+            NeuralError::ModelLoadingFailed(format!("Failed to write {:?}: {}", path, e))
+        })?;
+
+        info!("💾 Saved FANN model to: {:?}", path);
+        self.file_path = Some(path.to_string_lossy().into_owned());
+        Ok(())
+    }
+
+    /// Flattened connection weights
+    pub fn weights(&self) -> Vec<f64> {
+        self.network.get_weights()
+    }
+
+    /// Replace all connection weights
+    pub fn set_weights(&mut self, weights: &[f64]) -> NeuralResult<()> {
+        self.network
+            .set_weights(weights)
+            .map_err(|e| NeuralError::ModelLoadingFailed(format!("Invalid weights: {}", e)))
+    }
+
+    /// Underlying network, for training
+    pub(crate) fn network_mut(&mut self) -> &mut Network<f64> {
+        &mut self.network
     }
 
     /// Create a volatility prediction model
@@ -217,7 +279,10 @@ impl FannModel {
             input.len()
         );
 
-        let output = self.network.run(input);
+        let output = match &self.metadata.input_scaler {
+            Some(scaler) => self.network.run(&scaler.transform(input)),
+            None => self.network.run(input),
+        };
 
         if output.is_empty() {
             return Err(NeuralError::InferenceFailed(
@@ -225,6 +290,11 @@ impl FannModel {
             ));
         }
 
+        let output = match &self.metadata.output_scaler {
+            Some(scaler) => scaler.inverse(&output),
+            None => output,
+        };
+
         debug!("📊 FANN output: {:?}", output);
 
         Ok(output)
@@ -266,6 +336,62 @@ impl FannModel {
     }
 }
 
+/// [`Predictor`] backed by a FANN network
+pub struct FannPredictor {
+    info: PredictorInfo,
+    model: FannModel,
+}
+
+impl FannPredictor {
+    /// Wrap `model` as predictor `id` for `task`, checking its dimensions fit the task
+    pub fn new(id: impl Into<String>, task: PredictionTask, model: FannModel) -> NeuralResult<Self> {
+        model.validate_dimensions(task.input_size(), task.output_size())?;
+
+        let mut info = PredictorInfo::new(id, task, NeuralBackend::RuvFann);
+        info.model_type = Some(ModelType::MLP);
+        info.version = model.metadata.version.clone();
+        info.accuracy = model.metadata.accuracy;
+        info.trained_at = model.metadata.training_date.clone();
+
+        Ok(Self { info, model })
+    }
+}
+
+impl Predictor for FannPredictor {
+    fn info(&self) -> &PredictorInfo {
+        &self.info
+    }
+
+    fn predict(&mut self, input: &PredictionInput) -> NeuralResult<Option<Vec<f64>>> {
+        self.model.run(&input.features).map(Some)
+    }
+}
+
+/// Loads registry versions backed by FANN model files
+pub struct FannModelLoader;
+
+impl ModelLoader for FannModelLoader {
+    fn load(&self, record: &ModelRecord) -> NeuralResult<Box<dyn Predictor>> {
+        if record.backend != NeuralBackend::RuvFann {
+            return Err(NeuralError::BackendUnavailable(format!(
+                "{} uses the {:?} backend",
+                record.predictor_id(),
+                record.backend
+            )));
+        }
+        let model = FannModel::load_from_file(
+            &record.model_path,
+            record.task.input_size(),
+            record.task.output_size(),
+        )?;
+        Ok(Box::new(FannPredictor::new(
+            record.predictor_id(),
+            record.task,
+            model,
+        )?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -334,4 +460,50 @@ mod tests {
         // Invalid output size
         assert!(model.validate_dimensions(7, 3).is_err());
     }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let path = std::env::temp_dir().join(format!("fann-roundtrip-{}.json", std::process::id()));
+        let mut model = FannModel::create_risk_model().unwrap();
+        model.metadata.training_date = Some("2024-01-01T00:00:00Z".to_string());
+        model.metadata.accuracy = Some(0.9);
+        model.save(&path).unwrap();
+
+        let input = vec![1000.0, 0.02, 0.001, 0.1, 0.25];
+        let expected = model.run(&input).unwrap();
+
+        let mut loaded = FannModel::load_from_file(path.to_str().unwrap(), 5, 5).unwrap();
+        assert_eq!(loaded.metadata.accuracy, Some(0.9));
+        assert_eq!(loaded.metadata.hidden_layers, vec![24, 12]);
+        assert_eq!(loaded.run(&input).unwrap(), expected);
+
+        // Wrong dimensions and garbage files are rejected
+        assert!(FannModel::load_from_file(path.to_str().unwrap(), 7, 4).is_err());
+        std::fs::write(&path, "not a model").unwrap();
+        assert!(matches!(
+            FannModel::load_from_file(path.to_str().unwrap(), 5, 5),
+            Err(NeuralError::ModelLoadingFailed(_))
+        ));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_predictor_checks_task_dimensions() {
+        let model = FannModel::create_risk_model().unwrap();
+        assert!(FannPredictor::new("vol", PredictionTask::Volatility, model).is_err());
+
+        let mut predictor = FannPredictor::new(
+            "risk",
+            PredictionTask::Risk,
+            FannModel::create_risk_model().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(predictor.info().backend, NeuralBackend::RuvFann);
+        let output = predictor
+            .predict(&PredictionInput::new("BTC-USD", vec![1000.0, 0.02, 0.001, 0.1, 0.25]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(output.len(), 5);
+    }
 }
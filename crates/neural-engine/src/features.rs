@@ -0,0 +1,603 @@
+//! Feature engineering shared by training and live inference
+//!
+//! A [`FeatureSetSpec`] names a versioned list of features computed over
+//! rolling windows of [`FeatureBar`]s. [`FeaturePipeline`] evaluates the spec
+//! one bar at a time; batch transforms replay bars through a fresh pipeline, so
+//! training datasets and streaming inference produce bit-identical vectors.
+//!
+//! Technical indicators come from an [`IndicatorFactory`]. The strategy
+//! engine's indicator library implements it; this crate cannot depend on
+//! `strategy-engine` directly because that crate already depends on it
+//! through `ninja-gekko-core`.
+//!
+//! Normalization parameters fitted at training time are saved with the spec
+//! in a [`FeatureManifest`] next to the model file.
+
+use crate::training::{MinMaxScaler, TrainingCandle};
+use crate::{MarketDataInput, NeuralError, NeuralResult};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+/// One bar of market data as seen by the feature pipeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureBar {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+    /// Resting size at the best bid
+    pub bid_size: Option<f64>,
+    /// Resting size at the best ask
+    pub ask_size: Option<f64>,
+}
+
+impl From<&TrainingCandle> for FeatureBar {
+    fn from(candle: &TrainingCandle) -> Self {
+        Self {
+            timestamp: candle.timestamp,
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            volume: candle.volume,
+            bid: candle.bid,
+            ask: candle.ask,
+            bid_size: None,
+            ask_size: None,
+        }
+    }
+}
+
+impl From<&MarketDataInput> for FeatureBar {
+    fn from(data: &MarketDataInput) -> Self {
+        Self {
+            timestamp: data.timestamp,
+            open: data.price,
+            high: data.high,
+            low: data.low,
+            close: data.price,
+            volume: data.volume,
+            bid: Some(data.bid),
+            ask: Some(data.ask),
+            bid_size: None,
+            ask_size: None,
+        }
+    }
+}
+
+/// A single feature column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FeatureKind {
+    /// Log return of the close over `lag` bars
+    LogReturn { lag: usize },
+    /// RMS of one-bar log returns over `window` bars
+    RealizedVolatility { window: usize },
+    /// Quoted spread relative to mid, falling back to the bar's range when no quotes
+    Spread,
+    /// `(bid_size - ask_size) / (bid_size + ask_size)`, 0 without book sizes
+    BookImbalance,
+    /// `(high - low) / close`
+    Range,
+    /// Volume relative to its mean over `window` bars
+    VolumeRatio { window: usize },
+    /// Intrabar volatility estimate: range scaled by the square root of relative volume
+    IntrabarVolatility { window: usize },
+    /// Technical indicator built by the pipeline's [`IndicatorFactory`]
+    Indicator {
+        name: String,
+        #[serde(default)]
+        params: Vec<f64>,
+    },
+}
+
+impl FeatureKind {
+    /// Column name used in manifests and logs
+    pub fn column_name(&self) -> String {
+        match self {
+            FeatureKind::LogReturn { lag } => format!("log_return_{}", lag),
+            FeatureKind::RealizedVolatility { window } => format!("realized_vol_{}", window),
+            FeatureKind::Spread => "spread".to_string(),
+            FeatureKind::BookImbalance => "book_imbalance".to_string(),
+            FeatureKind::Range => "range".to_string(),
+            FeatureKind::VolumeRatio { window } => format!("volume_ratio_{}", window),
+            FeatureKind::IntrabarVolatility { window } => format!("intrabar_vol_{}", window),
+            FeatureKind::Indicator { name, params } if params.is_empty() => name.to_lowercase(),
+            FeatureKind::Indicator { name, params } => format!(
+                "{}_{}",
+                name.to_lowercase(),
+                params
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join("_")
+            ),
+        }
+    }
+
+    /// Bars of history (including the current one) the feature needs
+    fn lookback(&self) -> usize {
+        match self {
+            FeatureKind::LogReturn { lag } => lag + 1,
+            FeatureKind::RealizedVolatility { window } => window + 1,
+            FeatureKind::VolumeRatio { window } | FeatureKind::IntrabarVolatility { window } => {
+                *window
+            }
+            FeatureKind::Spread
+            | FeatureKind::BookImbalance
+            | FeatureKind::Range
+            | FeatureKind::Indicator { .. } => 1,
+        }
+    }
+}
+
+/// Versioned, ordered list of features
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureSetSpec {
+    pub name: String,
+    /// Bumped whenever a feature's definition or order changes
+    pub version: u32,
+    pub features: Vec<FeatureKind>,
+}
+
+impl FeatureSetSpec {
+    pub fn new(name: impl Into<String>, version: u32, features: Vec<FeatureKind>) -> Self {
+        Self {
+            name: name.into(),
+            version,
+            features,
+        }
+    }
+
+    /// `name@version`, recorded with models so mismatched pipelines are rejected
+    pub fn id(&self) -> String {
+        format!("{}@{}", self.name, self.version)
+    }
+
+    pub fn len(&self) -> usize {
+        self.features.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    pub fn column_names(&self) -> Vec<String> {
+        self.features.iter().map(FeatureKind::column_name).collect()
+    }
+
+    /// Bars of history the window features need
+    pub fn lookback(&self) -> usize {
+        self.features
+            .iter()
+            .map(FeatureKind::lookback)
+            .max()
+            .unwrap_or(1)
+            .max(1)
+    }
+
+    /// Fingerprint of the feature definitions and their order.
+    ///
+    /// Independent of the spec's name and version, so two specs hash alike
+    /// exactly when they compute the same columns.
+    pub fn schema_hash(&self) -> String {
+        let definitions: Vec<String> = self
+            .features
+            .iter()
+            .map(|feature| serde_json::to_string(feature).unwrap_or_default())
+            .collect();
+        schema_hash(&definitions)
+    }
+}
+
+/// Fingerprint of an ordered list of column definitions (64-bit FNV-1a, hex)
+pub fn schema_hash<S: AsRef<str>>(columns: &[S]) -> String {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET;
+    for column in columns {
+        // Separator byte keeps ["ab", "c"] and ["a", "bc"] apart
+        for byte in column.as_ref().bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// Streaming indicator fed one bar at a time
+pub trait FeatureIndicator: Send {
+    /// Consume `bar`; `None` until the indicator has warmed up
+    fn update(&mut self, bar: &FeatureBar) -> Option<f64>;
+}
+
+/// Builds indicators for [`FeatureKind::Indicator`] columns
+pub trait IndicatorFactory {
+    fn create(&self, name: &str, params: &[f64]) -> NeuralResult<Box<dyn FeatureIndicator>>;
+}
+
+/// Factory for specs without indicator columns
+pub struct NoIndicators;
+
+impl IndicatorFactory for NoIndicators {
+    fn create(&self, name: &str, _params: &[f64]) -> NeuralResult<Box<dyn FeatureIndicator>> {
+        Err(NeuralError::InvalidInput(format!(
+            "indicator '{}' requested but no indicator library is configured",
+            name
+        )))
+    }
+}
+
+/// Streaming evaluator for a [`FeatureSetSpec`]
+pub struct FeaturePipeline {
+    spec: FeatureSetSpec,
+    history: VecDeque<FeatureBar>,
+    /// Indicator per column, `None` for window features
+    indicators: Vec<Option<Box<dyn FeatureIndicator>>>,
+}
+
+impl FeaturePipeline {
+    pub fn new(spec: FeatureSetSpec, factory: &dyn IndicatorFactory) -> NeuralResult<Self> {
+        if spec.is_empty() {
+            return Err(NeuralError::InvalidInput(format!(
+                "feature set {} has no features",
+                spec.id()
+            )));
+        }
+
+        let indicators = spec
+            .features
+            .iter()
+            .map(|feature| match feature {
+                FeatureKind::Indicator { name, params } => factory.create(name, params).map(Some),
+                _ => Ok(None),
+            })
+            .collect::<NeuralResult<Vec<_>>>()?;
+
+        Ok(Self {
+            history: VecDeque::with_capacity(spec.lookback()),
+            spec,
+            indicators,
+        })
+    }
+
+    pub fn spec(&self) -> &FeatureSetSpec {
+        &self.spec
+    }
+
+    /// Consume `bar`, returning the feature vector once every column is warmed up
+    pub fn update(&mut self, bar: &FeatureBar) -> Option<Vec<f64>> {
+        if self.history.len() == self.spec.lookback() {
+            self.history.pop_front();
+        }
+        self.history.push_back(bar.clone());
+
+        // Indicators are updated on every bar, even when another column is
+        // still warming up, so their state matches a batch replay
+        let mut row = Vec::with_capacity(self.spec.len());
+        let mut ready = true;
+        for (feature, indicator) in self.spec.features.iter().zip(self.indicators.iter_mut()) {
+            let value = match indicator {
+                Some(indicator) => indicator.update(bar),
+                None => window_feature(feature, &self.history),
+            };
+            match value {
+                Some(value) if value.is_finite() => row.push(value),
+                _ => ready = false,
+            }
+        }
+        ready.then_some(row)
+    }
+
+    /// Run `bars` through a fresh pipeline; entry `i` is the vector after bar `i`
+    pub fn transform_batch(
+        spec: &FeatureSetSpec,
+        factory: &dyn IndicatorFactory,
+        bars: &[FeatureBar],
+    ) -> NeuralResult<Vec<Option<Vec<f64>>>> {
+        let mut pipeline = Self::new(spec.clone(), factory)?;
+        Ok(bars.iter().map(|bar| pipeline.update(bar)).collect())
+    }
+}
+
+fn window_feature(feature: &FeatureKind, history: &VecDeque<FeatureBar>) -> Option<f64> {
+    let current = history.back()?;
+    let n = history.len();
+    match feature {
+        FeatureKind::LogReturn { lag } => {
+            let past = history.get(n.checked_sub(lag + 1)?)?;
+            log_return(past.close, current.close)
+        }
+        FeatureKind::RealizedVolatility { window } => {
+            let start = n.checked_sub(window + 1)?;
+            let mut sum = 0.0;
+            for i in start + 1..n {
+                let r = log_return(history[i - 1].close, history[i].close)?;
+                sum += r * r;
+            }
+            Some((sum / *window as f64).sqrt())
+        }
+        FeatureKind::Spread => Some(quoted_spread(current)),
+        FeatureKind::BookImbalance => match (current.bid_size, current.ask_size) {
+            (Some(bid), Some(ask)) if bid + ask > 0.0 => Some((bid - ask) / (bid + ask)),
+            _ => Some(0.0),
+        },
+        FeatureKind::Range => {
+            (current.close > 0.0).then(|| (current.high - current.low) / current.close)
+        }
+        FeatureKind::VolumeRatio { window } => {
+            let mean = mean_volume(history, *window)?;
+            Some(if mean > 0.0 {
+                current.volume / mean
+            } else {
+                0.0
+            })
+        }
+        FeatureKind::IntrabarVolatility { window } => {
+            let mean = mean_volume(history, *window)?;
+            Some(intrabar_volatility(current, mean))
+        }
+        FeatureKind::Indicator { .. } => None,
+    }
+}
+
+fn log_return(from: f64, to: f64) -> Option<f64> {
+    (from > 0.0 && to > 0.0).then(|| (to / from).ln())
+}
+
+fn mean_volume(history: &VecDeque<FeatureBar>, window: usize) -> Option<f64> {
+    let start = history.len().checked_sub(window.max(1))?;
+    let bars = history.range(start..);
+    Some(bars.map(|bar| bar.volume).sum::<f64>() / window.max(1) as f64)
+}
+
+/// Quoted spread relative to mid, or the bar's range relative to close without quotes
+pub fn quoted_spread(bar: &FeatureBar) -> f64 {
+    match (bar.bid, bar.ask) {
+        (Some(bid), Some(ask)) if ask >= bid && bid + ask > 0.0 => {
+            (ask - bid) / ((ask + bid) / 2.0)
+        }
+        _ if bar.close > 0.0 => (bar.high - bar.low) / bar.close,
+        _ => 0.0,
+    }
+}
+
+/// Range-based volatility estimate scaled by relative volume
+pub fn intrabar_volatility(bar: &FeatureBar, avg_volume: f64) -> f64 {
+    if bar.close <= 0.0 {
+        return 0.0;
+    }
+    let price_range = (bar.high - bar.low) / bar.close;
+    let volume_factor = if avg_volume > 0.0 {
+        (bar.volume / avg_volume).clamp(0.1, 3.0)
+    } else {
+        1.0
+    };
+
+    price_range * volume_factor.sqrt() * 0.1
+}
+
+/// Feature spec and fitted normalization, stored next to a model file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureManifest {
+    pub spec: FeatureSetSpec,
+    pub columns: Vec<String>,
+    pub scaler: Option<MinMaxScaler>,
+}
+
+impl FeatureManifest {
+    pub fn new(spec: FeatureSetSpec, scaler: Option<MinMaxScaler>) -> Self {
+        Self {
+            columns: spec.column_names(),
+            spec,
+            scaler,
+        }
+    }
+
+    /// Manifest location for a model file: `<model>.features.json`
+    pub fn path_for(model_path: impl AsRef<Path>) -> PathBuf {
+        let mut path = model_path.as_ref().as_os_str().to_owned();
+        path.push(".features.json");
+        PathBuf::from(path)
+    }
+
+    /// Apply the fitted normalization, if any
+    pub fn normalize(&self, row: &[f64]) -> Vec<f64> {
+        match &self.scaler {
+            Some(scaler) => scaler.transform(row),
+            None => row.to_vec(),
+        }
+    }
+
+    /// Reject pipelines built from a different spec than the model was trained on
+    pub fn check_compatible(&self, spec: &FeatureSetSpec) -> NeuralResult<()> {
+        if &self.spec != spec {
+            return Err(NeuralError::InvalidInput(format!(
+                "model expects feature set {}, pipeline provides {}",
+                self.spec.id(),
+                spec.id()
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> NeuralResult<()> {
+        let path = path.as_ref();
+        let contents = serde_json::to_string_pretty(self).map_err(|e| {
+            NeuralError::ModelLoadingFailed(format!("Failed to serialize feature manifest: {}", e))
+        })?;
+        std::fs::write(path, contents).map_err(|e| {
+            NeuralError::ModelLoadingFailed(format!("Failed to write {:?}: {}", path, e))
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> NeuralResult<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            NeuralError::ModelNotFound(format!("Feature manifest {:?}: {}", path, e))
+        })?;
+        serde_json::from_str(&contents).map_err(|e| {
+            NeuralError::ModelLoadingFailed(format!("Invalid feature manifest {:?}: {}", path, e))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn bars(count: usize) -> Vec<FeatureBar> {
+        let start = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        (0..count)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.5).sin();
+                FeatureBar {
+                    timestamp: start + Duration::minutes(i as i64),
+                    open: close,
+                    high: close + 0.4,
+                    low: close - 0.4,
+                    close,
+                    volume: 1_000.0 + (i % 7) as f64 * 100.0,
+                    bid: Some(close - 0.05),
+                    ask: Some(close + 0.05),
+                    bid_size: Some(3.0),
+                    ask_size: Some(1.0),
+                }
+            })
+            .collect()
+    }
+
+    /// Running mean of closes over `period` bars
+    struct MeanClose {
+        period: usize,
+        closes: VecDeque<f64>,
+    }
+
+    impl FeatureIndicator for MeanClose {
+        fn update(&mut self, bar: &FeatureBar) -> Option<f64> {
+            self.closes.push_back(bar.close);
+            if self.closes.len() > self.period {
+                self.closes.pop_front();
+            }
+            (self.closes.len() == self.period)
+                .then(|| self.closes.iter().sum::<f64>() / self.period as f64)
+        }
+    }
+
+    struct TestIndicators;
+
+    impl IndicatorFactory for TestIndicators {
+        fn create(&self, name: &str, params: &[f64]) -> NeuralResult<Box<dyn FeatureIndicator>> {
+            match name {
+                "mean" => Ok(Box::new(MeanClose {
+                    period: params.first().copied().unwrap_or(3.0) as usize,
+                    closes: VecDeque::new(),
+                })),
+                other => Err(NeuralError::InvalidInput(other.to_string())),
+            }
+        }
+    }
+
+    fn spec() -> FeatureSetSpec {
+        FeatureSetSpec::new(
+            "test",
+            1,
+            vec![
+                FeatureKind::LogReturn { lag: 1 },
+                FeatureKind::RealizedVolatility { window: 5 },
+                FeatureKind::Spread,
+                FeatureKind::BookImbalance,
+                FeatureKind::VolumeRatio { window: 4 },
+                FeatureKind::Indicator {
+                    name: "mean".to_string(),
+                    params: vec![8.0],
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn test_streaming_matches_batch() {
+        let bars = bars(40);
+        let batch = FeaturePipeline::transform_batch(&spec(), &TestIndicators, &bars).unwrap();
+
+        let mut pipeline = FeaturePipeline::new(spec(), &TestIndicators).unwrap();
+        let streamed: Vec<_> = bars.iter().map(|bar| pipeline.update(bar)).collect();
+        assert_eq!(batch, streamed);
+
+        // Warm-up is bounded by the slowest column: the 8-bar indicator
+        assert!(batch[..7].iter().all(Option::is_none));
+        let first = batch[7].as_ref().unwrap();
+        assert_eq!(first.len(), 6);
+        assert!((first[3] - 0.5).abs() < 1e-12);
+        assert!((first[2] - 0.1 / bars[7].close).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_realized_volatility_window() {
+        let spec = FeatureSetSpec::new(
+            "vol",
+            1,
+            vec![FeatureKind::RealizedVolatility { window: 2 }],
+        );
+        let mut bars = bars(3);
+        bars[0].close = 100.0;
+        bars[1].close = 101.0;
+        bars[2].close = 100.0;
+
+        let rows = FeaturePipeline::transform_batch(&spec, &NoIndicators, &bars).unwrap();
+        let r = (101.0f64 / 100.0).ln();
+        assert!(rows[1].is_none());
+        assert!((rows[2].as_ref().unwrap()[0] - r).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_manifest_round_trip_and_compatibility() {
+        let model_path = std::env::temp_dir().join(format!("features-{}.json", std::process::id()));
+        let manifest_path = FeatureManifest::path_for(&model_path);
+        assert!(manifest_path
+            .to_string_lossy()
+            .ends_with(".json.features.json"));
+
+        let scaler = MinMaxScaler::fit(&[vec![0.0; 6], vec![1.0; 6]]);
+        let manifest = FeatureManifest::new(spec(), scaler);
+        manifest.save(&manifest_path).unwrap();
+        let loaded = FeatureManifest::load(&manifest_path).unwrap();
+        let _ = std::fs::remove_file(&manifest_path);
+
+        assert_eq!(loaded, manifest);
+        assert_eq!(loaded.columns[5], "mean_8");
+        assert!(loaded.check_compatible(&spec()).is_ok());
+
+        let mut bumped = spec();
+        bumped.version = 2;
+        assert!(loaded.check_compatible(&bumped).is_err());
+    }
+
+    #[test]
+    fn test_schema_hash_tracks_definitions_not_labels() {
+        let mut renamed = spec();
+        renamed.name = "other".to_string();
+        renamed.version = 7;
+        assert_eq!(renamed.schema_hash(), spec().schema_hash());
+
+        let mut reordered = spec();
+        reordered.features.swap(0, 1);
+        assert_ne!(reordered.schema_hash(), spec().schema_hash());
+
+        assert_ne!(schema_hash(&["ab", "c"]), schema_hash(&["a", "bc"]));
+        assert_eq!(schema_hash::<&str>(&[]).len(), 16);
+    }
+
+    #[test]
+    fn test_indicator_columns_need_a_library() {
+        assert!(FeaturePipeline::new(spec(), &NoIndicators).is_err());
+    }
+}
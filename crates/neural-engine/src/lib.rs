@@ -5,9 +5,9 @@
 //! opportunity detection.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
 use thiserror::Error;
-use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
 #[cfg(feature = "candle")]
@@ -16,12 +16,49 @@ use candle_core::{DType, Device};
 #[cfg(feature = "candle")]
 use std::env;
 
+pub mod features;
+pub mod model_registry;
+pub mod monitoring;
+pub mod predictor;
+pub mod statistical;
+pub mod training;
+
+#[cfg(feature = "postgres")]
+pub mod postgres_store;
+
+#[cfg(feature = "candle")]
+pub mod candle_predictor;
+
 // FANN backend module (conditional compilation)
 #[cfg(feature = "fann")]
 pub mod fann_backend;
 
 #[cfg(feature = "fann")]
-pub use fann_backend::{FannModel, FannModelMetadata};
+pub use fann_backend::{FannModel, FannModelLoader, FannModelMetadata, FannPredictor};
+
+// ONNX backend module (conditional compilation)
+#[cfg(feature = "onnx")]
+pub mod onnx_backend;
+
+#[cfg(feature = "onnx")]
+pub use onnx_backend::{OnnxModel, OnnxModelLoader, OnnxModelSpec, OnnxPredictor};
+
+pub use model_registry::{
+    BackendLoaders, DeploymentMode, InMemoryModelStore, ModelLoader, ModelRecord, ModelRegistry,
+    ModelStatus, ModelStore,
+};
+pub use monitoring::{
+    LoggedPrediction, ModelAlert, ModelAlertKind, ModelHealth, MonitoringConfig, Observation,
+    PredictionMonitor, PredictionSink, ResolvedPrediction,
+};
+pub use predictor::{
+    PredictionInput, PredictionOutcome, PredictionTask, Predictor, PredictorInfo,
+    PredictorRegistry, ShadowComparison,
+};
+pub use statistical::VolatilityBaseline;
+
+/// RiskMetrics decay used for the default volatility baseline
+const EWMA_LAMBDA: f64 = 0.94;
 
 /// Neural engine error types
 #[derive(Error, Debug)]
@@ -43,6 +80,9 @@ pub enum NeuralError {
 
     #[error("Backend not available: {0}")]
     BackendUnavailable(String),
+
+    #[error("Model store error: {0}")]
+    Storage(String),
 }
 
 pub type NeuralResult<T> = Result<T, NeuralError>;
@@ -141,7 +181,11 @@ pub enum NeuralBackend {
     Candle,
     /// PyTorch via Candle bindings
     PyTorch,
-    /// Simulated: Returns placeholder predictions (for testing/fallback)
+    /// ONNX graphs exported from Python training, run on CPU with tract
+    Onnx,
+    /// Statistical baselines (EWMA/GARCH), no trained network
+    Statistical,
+    /// No trained backend configured; tasks without a registered predictor report `NoModel`
     Simulated,
 }
 
@@ -211,341 +255,297 @@ pub enum VolatilityRegime {
 /// Enhanced neural engine for arbitrage trading
 pub struct NeuralEngine {
     backend: NeuralBackend,
-    models: RwLock<HashMap<String, NeuralModel>>,
-    volatility_models: RwLock<HashMap<String, VolatilityModel>>,
-    arbitrage_models: RwLock<HashMap<String, ArbitrageModel>>,
+    predictors: PredictorRegistry,
+    models: ModelRegistry,
+    monitor: PredictionMonitor,
+    prediction_sink: Option<Arc<dyn PredictionSink>>,
     #[cfg(feature = "candle")]
     device: Device,
-    /// FANN models for volatility prediction (when fann feature is enabled)
-    #[cfg(feature = "fann")]
-    fann_volatility_model: RwLock<Option<fann_backend::FannModel>>,
-    /// FANN models for arbitrage detection (when fann feature is enabled)
-    #[cfg(feature = "fann")]
-    fann_arbitrage_model: RwLock<Option<fann_backend::FannModel>>,
-    /// FANN models for risk assessment (when fann feature is enabled)
-    #[cfg(feature = "fann")]
-    fann_risk_model: RwLock<Option<fann_backend::FannModel>>,
-}
-
-/// Individual neural network model
-#[derive(Debug, Clone)]
-pub struct NeuralModel {
-    pub id: String,
-    pub name: String,
-    pub model_type: ModelType,
-    pub version: String,
-    pub accuracy: f64,
-    pub inference_time_ms: f32,
-    pub memory_usage_mb: f32,
-    pub is_active: bool,
-    pub file_path: String,
-}
-
-/// Specialized volatility prediction model
-#[derive(Debug, Clone)]
-pub struct VolatilityModel {
-    pub id: String,
-    pub name: String,
-    pub symbol_coverage: Vec<String>,
-    pub accuracy: f64,
-    pub prediction_horizon_minutes: Vec<u32>,
-    pub last_training: chrono::DateTime<chrono::Utc>,
-}
-
-/// Specialized arbitrage opportunity detection model
-#[derive(Debug, Clone)]
-pub struct ArbitrageModel {
-    pub id: String,
-    pub name: String,
-    pub supported_exchanges: Vec<String>,
-    pub success_rate: f64,
-    pub average_profit_bps: f64,
-    pub last_training: chrono::DateTime<chrono::Utc>,
 }
 
 impl NeuralEngine {
     /// Create a new enhanced neural engine
-    #[cfg(all(feature = "candle", not(feature = "fann")))]
+    ///
+    /// No models are loaded: every prediction reports
+    /// [`PredictionOutcome::NoModel`] until predictors are registered,
+    /// baselines are loaded with [`NeuralEngine::load_arbitrage_models`], or
+    /// trained FANN models are loaded with `new_with_model_paths()`.
     pub fn new(backend: NeuralBackend) -> NeuralResult<Self> {
         info!(
-            "🧠 Initializing Enhanced Neural Engine for Gordon Gekko arbitrage ({})",
-            format!("{:?}", backend)
+            "🧠 Initializing Enhanced Neural Engine for Gordon Gekko arbitrage ({:?})",
+            backend
         );
 
+        #[cfg(feature = "candle")]
         let device = detect_device()?;
+        #[cfg(not(feature = "candle"))]
+        detect_device()?;
 
         Ok(Self {
             backend,
-            models: RwLock::new(HashMap::new()),
-            volatility_models: RwLock::new(HashMap::new()),
-            arbitrage_models: RwLock::new(HashMap::new()),
+            predictors: PredictorRegistry::new(),
+            models: ModelRegistry::in_memory(),
+            monitor: PredictionMonitor::default(),
+            prediction_sink: None,
+            #[cfg(feature = "candle")]
             device,
         })
     }
 
-    /// Create a new enhanced neural engine with FANN support
-    #[cfg(all(feature = "candle", feature = "fann"))]
-    pub fn new(backend: NeuralBackend) -> NeuralResult<Self> {
-        info!(
-            "🧠 Initializing Enhanced Neural Engine for Gordon Gekko arbitrage ({}) with FANN",
-            format!("{:?}", backend)
-        );
-
-        let device = detect_device()?;
-
-        // Initialize FANN models if backend is RuvFann
-        // NOTE: These are untrained placeholder models. For production use,
-        // call new_with_model_paths() with paths to trained .net files.
-        let (fann_volatility, fann_arbitrage, fann_risk, actual_backend) =
-            if backend == NeuralBackend::RuvFann {
-                warn!(
-                    "⚠️ Creating untrained FANN models. For production, use new_with_model_paths() \
-                    to load trained models from disk."
-                );
-                (
-                    RwLock::new(Some(fann_backend::FannModel::create_volatility_model()?)),
-                    RwLock::new(Some(fann_backend::FannModel::create_arbitrage_model()?)),
-                    RwLock::new(Some(fann_backend::FannModel::create_risk_model()?)),
-                    backend,
-                )
-            } else {
-                (RwLock::new(None), RwLock::new(None), RwLock::new(None), backend)
-            };
-
-        Ok(Self {
-            backend: actual_backend,
-            models: RwLock::new(HashMap::new()),
-            volatility_models: RwLock::new(HashMap::new()),
-            arbitrage_models: RwLock::new(HashMap::new()),
-            device,
-            fann_volatility_model: fann_volatility,
-            fann_arbitrage_model: fann_arbitrage,
-            fann_risk_model: fann_risk,
-        })
-    }
-
     /// Create a new enhanced neural engine with FANN support, loading models from specified paths.
     ///
     /// This constructor attempts to load trained models from the filesystem. If model files
-    /// are not found or cannot be loaded, it falls back to the Simulated backend.
+    /// are not found or cannot be loaded, it falls back to the Simulated backend with no
+    /// models registered.
     ///
     /// # Arguments
     /// * `backend` - Requested backend type
-    /// * `volatility_model_path` - Path to trained volatility .net model
-    /// * `arbitrage_model_path` - Path to trained arbitrage .net model  
-    /// * `risk_model_path` - Path to trained risk .net model
+    /// * `volatility_model_path` - Path to trained volatility model file
+    /// * `arbitrage_model_path` - Path to trained arbitrage model file
+    /// * `risk_model_path` - Path to trained risk model file
     ///
     /// # Returns
     /// - `Ok(NeuralEngine)` with loaded models if all files exist and are valid
     /// - `Ok(NeuralEngine)` with Simulated backend if loading fails (with warning logged)
     /// - `Err(NeuralError)` only for critical initialization failures
-    #[cfg(all(feature = "candle", feature = "fann"))]
+    #[cfg(feature = "fann")]
     pub fn new_with_model_paths(
         backend: NeuralBackend,
         volatility_model_path: Option<&str>,
         arbitrage_model_path: Option<&str>,
         risk_model_path: Option<&str>,
     ) -> NeuralResult<Self> {
-        info!(
-            "🧠 Initializing Enhanced Neural Engine with model paths ({}) with FANN",
-            format!("{:?}", backend)
-        );
+        let mut engine = Self::new(backend)?;
+        if backend != NeuralBackend::RuvFann {
+            return Ok(engine);
+        }
 
-        let device = detect_device()?;
+        let load = |path: Option<&str>, task: PredictionTask, id: &str| {
+            let path = path.ok_or_else(|| {
+                NeuralError::ModelNotFound(format!("No path configured for {:?} model", task))
+            })?;
+            let model = fann_backend::FannModel::load_from_file(
+                path,
+                task.input_size(),
+                task.output_size(),
+            )?;
+            fann_backend::FannPredictor::new(id, task, model)
+        };
 
-        let (fann_volatility, fann_arbitrage, fann_risk, actual_backend) =
-            if backend == NeuralBackend::RuvFann {
-                // Try to load models from paths
-                let vol_result = volatility_model_path
-                    .map(|p| fann_backend::FannModel::load_from_file(p, 7, 4));
-                let arb_result = arbitrage_model_path
-                    .map(|p| fann_backend::FannModel::load_from_file(p, 9, 5));
-                let risk_result = risk_model_path
-                    .map(|p| fann_backend::FannModel::load_from_file(p, 5, 5));
-
-                // Check if all models loaded successfully
-                match (vol_result, arb_result, risk_result) {
-                    (Some(Ok(vol)), Some(Ok(arb)), Some(Ok(risk))) => {
-                        info!("✅ All FANN models loaded successfully from disk");
-                        (
-                            RwLock::new(Some(vol)),
-                            RwLock::new(Some(arb)),
-                            RwLock::new(Some(risk)),
-                            backend,
-                        )
-                    }
-                    _ => {
-                        warn!(
-                            "⚠️ Could not load FANN models from disk. \
-                            Falling back to Simulated backend. \
-                            Ensure trained .net files exist at configured paths."
-                        );
-                        (
-                            RwLock::new(None),
-                            RwLock::new(None),
-                            RwLock::new(None),
-                            NeuralBackend::Simulated,
-                        )
-                    }
+        match (
+            load(volatility_model_path, PredictionTask::Volatility, "volatility_fann"),
+            load(arbitrage_model_path, PredictionTask::Arbitrage, "arbitrage_fann"),
+            load(risk_model_path, PredictionTask::Risk, "risk_fann"),
+        ) {
+            (Ok(volatility), Ok(arbitrage), Ok(risk)) => {
+                engine.predictors.register(Box::new(volatility));
+                engine.predictors.register(Box::new(arbitrage));
+                engine.predictors.register(Box::new(risk));
+                info!("✅ All FANN models loaded successfully from disk");
+            }
+            (volatility, arbitrage, risk) => {
+                for error in [volatility.err(), arbitrage.err(), risk.err()]
+                    .into_iter()
+                    .flatten()
+                {
+                    warn!("FANN model load failed: {}", error);
                 }
-            } else {
-                (RwLock::new(None), RwLock::new(None), RwLock::new(None), backend)
-            };
+                warn!(
+                    "⚠️ Could not load FANN models from disk. \
+                    Falling back to Simulated backend. \
+                    Ensure trained model files exist at configured paths."
+                );
+                engine.backend = NeuralBackend::Simulated;
+            }
+        }
 
-        Ok(Self {
-            backend: actual_backend,
-            models: RwLock::new(HashMap::new()),
-            volatility_models: RwLock::new(HashMap::new()),
-            arbitrage_models: RwLock::new(HashMap::new()),
-            device,
-            fann_volatility_model: fann_volatility,
-            fann_arbitrage_model: fann_arbitrage,
-            fann_risk_model: fann_risk,
-        })
+        Ok(engine)
     }
 
-    /// Create a new enhanced neural engine (CPU-only, no FANN)
-    #[cfg(all(not(feature = "candle"), not(feature = "fann")))]
-    pub fn new(backend: NeuralBackend) -> NeuralResult<Self> {
-        info!(
-            "🧠 Initializing Enhanced Neural Engine for Gordon Gekko arbitrage ({})",
-            format!("{:?}", backend)
-        );
+    /// Get the current backend type
+    pub fn backend(&self) -> NeuralBackend {
+        self.backend
+    }
 
-        detect_device()?;
+    /// Registered predictors
+    pub fn predictors(&self) -> &PredictorRegistry {
+        &self.predictors
+    }
 
-        Ok(Self {
-            backend,
-            models: RwLock::new(HashMap::new()),
-            volatility_models: RwLock::new(HashMap::new()),
-            arbitrage_models: RwLock::new(HashMap::new()),
-        })
+    /// Register a predictor; the first one for a task becomes its active model
+    pub fn register_predictor(&self, predictor: Box<dyn Predictor>) {
+        self.predictors.register(predictor);
     }
 
-    /// Create a new enhanced neural engine (CPU-only, with FANN)
-    #[cfg(all(not(feature = "candle"), feature = "fann"))]
-    pub fn new(backend: NeuralBackend) -> NeuralResult<Self> {
-        info!(
-            "🧠 Initializing Enhanced Neural Engine for Gordon Gekko arbitrage ({}) with FANN",
-            format!("{:?}", backend)
-        );
+    /// Describe every registered model
+    pub fn list_models(&self) -> Vec<PredictorInfo> {
+        self.predictors.list()
+    }
 
-        detect_device()?;
+    /// Use `models` for versioned deployments instead of the in-memory registry
+    pub fn with_model_registry(mut self, models: ModelRegistry) -> Self {
+        self.models = models;
+        self
+    }
 
-        // Initialize FANN models if backend is RuvFann
-        // NOTE: These are untrained placeholder models. For production use,
-        // call new_with_model_paths() with paths to trained .net files.
-        let (fann_volatility, fann_arbitrage, fann_risk, actual_backend) =
-            if backend == NeuralBackend::RuvFann {
-                warn!(
-                    "⚠️ Creating untrained FANN models. For production, use new_with_model_paths() \
-                    to load trained models from disk."
-                );
-                (
-                    RwLock::new(Some(fann_backend::FannModel::create_volatility_model()?)),
-                    RwLock::new(Some(fann_backend::FannModel::create_arbitrage_model()?)),
-                    RwLock::new(Some(fann_backend::FannModel::create_risk_model()?)),
-                    backend,
-                )
-            } else {
-                (RwLock::new(None), RwLock::new(None), RwLock::new(None), backend)
-            };
+    /// Versioned model registry
+    pub fn models(&self) -> &ModelRegistry {
+        &self.models
+    }
 
-        Ok(Self {
-            backend: actual_backend,
-            models: RwLock::new(HashMap::new()),
-            volatility_models: RwLock::new(HashMap::new()),
-            arbitrage_models: RwLock::new(HashMap::new()),
-            fann_volatility_model: fann_volatility,
-            fann_arbitrage_model: fann_arbitrage,
-            fann_risk_model: fann_risk,
-        })
+    /// Load the live and shadow versions recorded in the model registry
+    pub async fn restore_models(&self) -> NeuralResult<usize> {
+        self.models.restore(&self.predictors).await
     }
 
-    /// Create a new enhanced neural engine (CPU-only, with FANN), loading models from paths.
-    ///
-    /// Falls back to Simulated backend if model files cannot be loaded.
-    #[cfg(all(not(feature = "candle"), feature = "fann"))]
-    pub fn new_with_model_paths(
-        backend: NeuralBackend,
-        volatility_model_path: Option<&str>,
-        arbitrage_model_path: Option<&str>,
-        risk_model_path: Option<&str>,
-    ) -> NeuralResult<Self> {
-        info!(
-            "🧠 Initializing Enhanced Neural Engine with model paths ({}) with FANN",
-            format!("{:?}", backend)
-        );
+    /// Serve a registered model version live or run it in shadow of the live model
+    pub async fn deploy_model(
+        &self,
+        name: &str,
+        version: &str,
+        mode: DeploymentMode,
+    ) -> NeuralResult<Option<ShadowComparison>> {
+        self.models
+            .deploy(&self.predictors, name, version, mode)
+            .await
+    }
 
-        detect_device()?;
+    /// Make `version` the live version of `name`, returning its shadow comparison if it had one
+    pub async fn promote_model(
+        &self,
+        name: &str,
+        version: &str,
+    ) -> NeuralResult<Option<ShadowComparison>> {
+        self.models.promote(&self.predictors, name, version).await
+    }
 
-        let (fann_volatility, fann_arbitrage, fann_risk, actual_backend) =
-            if backend == NeuralBackend::RuvFann {
-                // Try to load models from paths
-                let vol_result = volatility_model_path
-                    .map(|p| fann_backend::FannModel::load_from_file(p, 7, 4));
-                let arb_result = arbitrage_model_path
-                    .map(|p| fann_backend::FannModel::load_from_file(p, 9, 5));
-                let risk_result = risk_model_path
-                    .map(|p| fann_backend::FannModel::load_from_file(p, 5, 5));
-
-                match (vol_result, arb_result, risk_result) {
-                    (Some(Ok(vol)), Some(Ok(arb)), Some(Ok(risk))) => {
-                        info!("✅ All FANN models loaded successfully from disk");
-                        (
-                            RwLock::new(Some(vol)),
-                            RwLock::new(Some(arb)),
-                            RwLock::new(Some(risk)),
-                            backend,
-                        )
-                    }
-                    _ => {
-                        warn!(
-                            "⚠️ Could not load FANN models from disk. \
-                            Falling back to Simulated backend."
-                        );
-                        (
-                            RwLock::new(None),
-                            RwLock::new(None),
-                            RwLock::new(None),
-                            NeuralBackend::Simulated,
-                        )
-                    }
-                }
-            } else {
-                (RwLock::new(None), RwLock::new(None), RwLock::new(None), backend)
-            };
+    /// Return `name` to its previously live version
+    pub async fn rollback_model(&self, name: &str) -> NeuralResult<ModelRecord> {
+        self.models.rollback(&self.predictors, name).await
+    }
 
-        Ok(Self {
-            backend: actual_backend,
-            models: RwLock::new(HashMap::new()),
-            volatility_models: RwLock::new(HashMap::new()),
-            arbitrage_models: RwLock::new(HashMap::new()),
-            fann_volatility_model: fann_volatility,
-            fann_arbitrage_model: fann_arbitrage,
-            fann_risk_model: fann_risk,
-        })
+    /// Online comparison of the shadow model for `task` against the live one
+    pub fn shadow_comparison(&self, task: PredictionTask) -> Option<ShadowComparison> {
+        self.predictors.shadow_comparison(task)
     }
 
-    /// Get the current backend type
-    pub fn backend(&self) -> NeuralBackend {
-        self.backend
+    /// Judge served predictions with `config` instead of the defaults
+    pub fn with_monitoring(mut self, config: MonitoringConfig) -> Self {
+        self.monitor = PredictionMonitor::new(config);
+        self
+    }
+
+    /// Also write logged predictions and their outcomes to `sink`
+    pub fn with_prediction_sink(mut self, sink: Arc<dyn PredictionSink>) -> Self {
+        self.prediction_sink = Some(sink);
+        self
+    }
+
+    /// Online prediction monitor
+    pub fn monitor(&self) -> &PredictionMonitor {
+        &self.monitor
+    }
+
+    /// Degradation and recovery alerts for served models
+    pub fn subscribe_model_alerts(&self) -> tokio::sync::broadcast::Receiver<ModelAlert> {
+        self.monitor.subscribe()
+    }
+
+    /// Online health of every model that has served predictions
+    pub fn model_health(&self) -> Vec<ModelHealth> {
+        self.monitor.health_report()
+    }
+
+    /// Whether predictions from `model_id` should currently be distrusted
+    pub fn is_model_degraded(&self, model_id: &str) -> bool {
+        self.monitor.is_degraded(model_id)
+    }
+
+    /// Feed a bar of `symbol` on `exchange` so pending volatility predictions can resolve
+    ///
+    /// [`NeuralEngine::predict_volatility`] records its input bar itself; call
+    /// this for bars on which no prediction was requested.
+    pub async fn observe_market_data(
+        &self,
+        symbol: &str,
+        exchange: &str,
+        market_data: &MarketDataInput,
+    ) -> usize {
+        self.observe(
+            &format!("{}:{}", symbol, exchange),
+            Observation::bar(market_data),
+        )
+        .await
+    }
+
+    /// Feed simultaneous quotes from two venues so pending arbitrage predictions can resolve
+    pub async fn observe_spread(
+        &self,
+        symbol: &str,
+        primary_exchange: &str,
+        secondary_exchange: &str,
+        primary_data: &MarketDataInput,
+        secondary_data: &MarketDataInput,
+    ) -> usize {
+        self.observe(
+            &format!("{}:{}:{}", symbol, primary_exchange, secondary_exchange),
+            Observation::spread(primary_data, secondary_data),
+        )
+        .await
+    }
+
+    /// Load a Candle MLP/LSTM from safetensors onto the engine's device and register it
+    #[cfg(feature = "candle")]
+    pub fn load_candle_model(
+        &self,
+        spec: candle_predictor::CandleModelSpec,
+        weights_path: impl AsRef<std::path::Path>,
+    ) -> NeuralResult<()> {
+        let predictor = candle_predictor::CandlePredictor::load(spec, weights_path, &self.device)?;
+        self.predictors.register(Box::new(predictor));
+        Ok(())
+    }
+
+    /// Load an exported ONNX graph described by `spec` and register it
+    #[cfg(feature = "onnx")]
+    pub fn load_onnx_model(
+        &self,
+        spec: onnx_backend::OnnxModelSpec,
+        model_path: impl AsRef<std::path::Path>,
+    ) -> NeuralResult<()> {
+        let model = onnx_backend::OnnxModel::load_from_file(model_path, spec)?;
+        self.predictors
+            .register(Box::new(onnx_backend::OnnxPredictor::new(model)?));
+        Ok(())
     }
 
     /// Load all models for arbitrage trading
+    ///
+    /// Tasks without a trained model get a statistical baseline where one
+    /// exists (EWMA volatility); the rest keep reporting `NoModel`.
     pub async fn load_arbitrage_models(&self) -> NeuralResult<()> {
         info!("📚 Loading arbitrage-specific neural models...");
 
-        // Load volatility prediction models
-        self.load_volatility_models().await?;
-
-        // Load cross-exchange arbitrage models
-        self.load_cross_exchange_models().await?;
+        if !self.predictors.has_model(PredictionTask::Volatility) {
+            self.predictors.register(Box::new(VolatilityBaseline::ewma(
+                "volatility_ewma",
+                EWMA_LAMBDA,
+            )?));
+        }
 
-        // Load risk assessment models
-        self.load_risk_models().await?;
+        for task in [PredictionTask::Arbitrage, PredictionTask::Risk] {
+            if !self.predictors.has_model(task) {
+                warn!(
+                    "No {:?} model loaded; {:?} predictions will report NoModel",
+                    task, task
+                );
+            }
+        }
 
-        info!("✅ All arbitrage neural models loaded successfully");
+        info!(
+            "✅ {} arbitrage models available",
+            self.predictors.list().len()
+        );
         Ok(())
     }
 
@@ -555,52 +555,77 @@ impl NeuralEngine {
         symbol: &str,
         exchange: &str,
         market_data: &MarketDataInput,
-    ) -> NeuralResult<VolatilityPrediction> {
+    ) -> NeuralResult<PredictionOutcome<VolatilityPrediction>> {
         debug!("🔮 Predicting volatility for {}:{}", symbol, exchange);
 
-        let models = self.volatility_models.read().await;
-        let model_key = format!("volatility_{}_{}", symbol.replace("-", "_"), exchange);
-
-        let _model = models
-            .get(&model_key)
-            .or_else(|| models.get("volatility_universal"))
-            .ok_or_else(|| NeuralError::ModelNotFound(model_key.clone()))?;
+        let preferred = format!("volatility_{}_{}", symbol.replace('-', "_"), exchange);
+        let input = PredictionInput::new(
+            format!("{}:{}", symbol, exchange),
+            predictor::volatility_features(market_data),
+        );
+        self.observe(&input.key, Observation::bar(market_data))
+            .await;
 
-        // Use FANN backend if available and configured
-        #[cfg(feature = "fann")]
-        if self.backend == NeuralBackend::RuvFann {
-            if let Some(prediction) = self.predict_volatility_with_fann(symbol, exchange, market_data).await? {
-                return Ok(prediction);
+        let started = Instant::now();
+        let (model_id, output) = match self
+            .predictors
+            .predict(PredictionTask::Volatility, Some(&preferred), &input)
+            .await?
+        {
+            PredictionOutcome::Predicted { model_id, value } => {
+                self.log_prediction(
+                    &model_id,
+                    PredictionTask::Volatility,
+                    &input,
+                    &value,
+                    started,
+                )
+                .await;
+                (model_id, value)
             }
-        }
+            PredictionOutcome::NoModel { task, reason } => {
+                debug!("No volatility prediction for {}:{}: {}", symbol, exchange, reason);
+                return Ok(PredictionOutcome::NoModel { task, reason });
+            }
+        };
 
-        // Fallback: Simulate volatility prediction
+        // Output: [volatility_1m, volatility_5m, volatility_15m, confidence]
         let current_volatility = self.calculate_current_volatility(market_data);
+        let vol_1m = output[0].abs();
+        let confidence = output[3].abs().min(1.0);
 
         let prediction = VolatilityPrediction {
             symbol: symbol.to_string(),
             exchange: exchange.to_string(),
             current_volatility,
-            predicted_volatility_1m: current_volatility * 1.1,
-            predicted_volatility_5m: current_volatility * 1.2,
-            predicted_volatility_15m: current_volatility * 1.15,
-            confidence_score: 0.92, // High confidence for demo
-            trend_direction: if current_volatility > 0.02 {
+            predicted_volatility_1m: vol_1m,
+            predicted_volatility_5m: output[1].abs(),
+            predicted_volatility_15m: output[2].abs(),
+            confidence_score: confidence,
+            trend_direction: if vol_1m > current_volatility * 1.5 {
                 TrendDirection::Highly_Volatile
+            } else if vol_1m > current_volatility {
+                TrendDirection::Bullish
+            } else if vol_1m < current_volatility * 0.5 {
+                TrendDirection::Bearish
             } else {
                 TrendDirection::Neutral
             },
-            volatility_regime: self.classify_volatility_regime(current_volatility),
+            volatility_regime: self.classify_volatility_regime(vol_1m),
             predicted_at: chrono::Utc::now(),
         };
 
         debug!(
-            "📊 Volatility prediction complete: score={:.2}, confidence={:.2}%",
+            "📊 Volatility prediction from {}: vol_1m={:.4}, confidence={:.2}%",
+            model_id,
             prediction.predicted_volatility_1m,
             prediction.confidence_score * 100.0
         );
 
-        Ok(prediction)
+        Ok(PredictionOutcome::Predicted {
+            model_id,
+            value: prediction,
+        })
     }
 
     /// Predict cross-exchange arbitrage opportunities
@@ -611,58 +636,71 @@ impl NeuralEngine {
         secondary_exchange: &str,
         primary_data: &MarketDataInput,
         secondary_data: &MarketDataInput,
-    ) -> NeuralResult<CrossExchangePrediction> {
+    ) -> NeuralResult<PredictionOutcome<CrossExchangePrediction>> {
         debug!(
             "🔄 Analyzing cross-exchange arbitrage: {} between {} and {}",
             symbol, primary_exchange, secondary_exchange
         );
 
-        let models = self.arbitrage_models.read().await;
-        let model_key = format!("arbitrage_{}_{}", primary_exchange, secondary_exchange);
-
-        let _model = models
-            .get(&model_key)
-            .or_else(|| models.get("arbitrage_universal"))
-            .ok_or_else(|| NeuralError::ModelNotFound(model_key.clone()))?;
-
-        // Use FANN backend if available and configured
-        #[cfg(feature = "fann")]
-        if self.backend == NeuralBackend::RuvFann {
-            if let Some(prediction) = self.predict_arbitrage_with_fann(
-                symbol,
-                primary_exchange,
-                secondary_exchange,
-                primary_data,
-                secondary_data,
-            ).await? {
-                return Ok(prediction);
+        let preferred = format!("arbitrage_{}_{}", primary_exchange, secondary_exchange);
+        let input = PredictionInput::new(
+            format!("{}:{}:{}", symbol, primary_exchange, secondary_exchange),
+            predictor::arbitrage_features(primary_data, secondary_data),
+        );
+        self.observe(
+            &input.key,
+            Observation::spread(primary_data, secondary_data),
+        )
+        .await;
+
+        let started = Instant::now();
+        let (model_id, output) = match self
+            .predictors
+            .predict(PredictionTask::Arbitrage, Some(&preferred), &input)
+            .await?
+        {
+            PredictionOutcome::Predicted { model_id, value } => {
+                self.log_prediction(
+                    &model_id,
+                    PredictionTask::Arbitrage,
+                    &input,
+                    &value,
+                    started,
+                )
+                .await;
+                (model_id, value)
             }
-        }
-
-        // Calculate current spread
-        let current_spread = (secondary_data.price - primary_data.price).abs() / primary_data.price;
+            PredictionOutcome::NoModel { task, reason } => {
+                debug!("No arbitrage prediction for {}: {}", symbol, reason);
+                return Ok(PredictionOutcome::NoModel { task, reason });
+            }
+        };
 
-        // Fallback: Simulate arbitrage prediction
+        // Output: [spread_1m, spread_5m, arb_probability, expected_profit, confidence]
         let prediction = CrossExchangePrediction {
             symbol: symbol.to_string(),
             primary_exchange: primary_exchange.to_string(),
             secondary_exchange: secondary_exchange.to_string(),
-            current_spread,
-            predicted_spread_1m: current_spread * 1.25,
-            predicted_spread_5m: current_spread * 1.4,
-            arbitrage_probability: 0.87, // High probability for Gordon Gekko aggression
-            expected_profit_bps: current_spread * 10000.0 * 0.75, // 75% of spread as profit
-            confidence_score: 0.91,
+            current_spread: input.features[2],
+            predicted_spread_1m: output[0].abs(),
+            predicted_spread_5m: output[1].abs(),
+            arbitrage_probability: output[2].abs().min(1.0),
+            expected_profit_bps: output[3].abs() * 10000.0, // Convert to bps
+            confidence_score: output[4].abs().min(1.0),
             predicted_at: chrono::Utc::now(),
         };
 
         info!(
-            "💰 Arbitrage prediction: {:.2}% probability, {:.1} bps expected profit",
+            "💰 Arbitrage prediction from {}: {:.2}% probability, {:.1} bps expected profit",
+            model_id,
             prediction.arbitrage_probability * 100.0,
             prediction.expected_profit_bps
         );
 
-        Ok(prediction)
+        Ok(PredictionOutcome::Predicted {
+            model_id,
+            value: prediction,
+        })
     }
 
     /// Assess risk for arbitrage opportunity
@@ -671,264 +709,106 @@ impl NeuralEngine {
         symbol: &str,
         exchanges: &[String],
         position_size: f64,
-    ) -> NeuralResult<ArbitrageRiskAssessment> {
+        market_data: &MarketDataInput,
+    ) -> NeuralResult<PredictionOutcome<ArbitrageRiskAssessment>> {
         debug!(
             "⚠️ Assessing arbitrage risk for {} across {:?}",
             symbol, exchanges
         );
 
-        // Simulate risk assessment
+        let volatility = self.calculate_current_volatility(market_data);
+        let input = PredictionInput::new(
+            format!("{}:{}", symbol, exchanges.join(":")),
+            predictor::risk_features(position_size, volatility, market_data),
+        );
+        let started = Instant::now();
+        let (model_id, output) = match self
+            .predictors
+            .predict(PredictionTask::Risk, None, &input)
+            .await?
+        {
+            PredictionOutcome::Predicted { model_id, value } => {
+                self.log_prediction(&model_id, PredictionTask::Risk, &input, &value, started)
+                    .await;
+                (model_id, value)
+            }
+            PredictionOutcome::NoModel { task, reason } => {
+                debug!("No risk assessment for {}: {}", symbol, reason);
+                return Ok(PredictionOutcome::NoModel { task, reason });
+            }
+        };
+
+        // Output: [overall_risk, liquidity_risk, execution_risk, max_position, confidence]
         let risk_assessment = ArbitrageRiskAssessment {
-            overall_risk_score: 0.25, // Low risk for aggressive Gordon Gekko trading
-            liquidity_risk: 0.15,
-            execution_risk: 0.30,
-            counterparty_risk: 0.20,
-            market_risk: 0.35,
-            operational_risk: 0.10,
-            max_recommended_position: position_size * 1.5, // Allow 50% more for aggression
-            stop_loss_threshold: 0.02,                     // 2% stop loss
-            confidence_score: 0.89,
+            overall_risk_score: output[0].clamp(0.0, 1.0),
+            liquidity_risk: output[1].clamp(0.0, 1.0),
+            execution_risk: output[2].clamp(0.0, 1.0),
+            counterparty_risk: None,
+            market_risk: (volatility / training::DEFAULT_STOP_LOSS).min(1.0),
+            operational_risk: None,
+            max_recommended_position: output[3].max(0.0),
+            stop_loss_threshold: training::DEFAULT_STOP_LOSS,
+            confidence_score: output[4].abs().min(1.0),
             assessed_at: chrono::Utc::now(),
         };
 
         info!(
-            "🛡️ Risk assessment complete: overall={:.2}, max_position=${:.0}K",
+            "🛡️ Risk assessment from {}: overall={:.2}, max_position=${:.0}K",
+            model_id,
             risk_assessment.overall_risk_score,
             risk_assessment.max_recommended_position / 1000.0
         );
 
-        Ok(risk_assessment)
+        Ok(PredictionOutcome::Predicted {
+            model_id,
+            value: risk_assessment,
+        })
     }
 
     // Private helper methods
 
-    async fn load_volatility_models(&self) -> NeuralResult<()> {
-        let mut models = self.volatility_models.write().await;
-
-        // Universal volatility model
-        models.insert(
-            "volatility_universal".to_string(),
-            VolatilityModel {
-                id: "vol_001".to_string(),
-                name: "Universal Volatility Predictor".to_string(),
-                symbol_coverage: vec!["*".to_string()], // Covers all symbols
-                accuracy: 0.885,
-                prediction_horizon_minutes: vec![1, 5, 15, 60],
-                last_training: chrono::Utc::now() - chrono::Duration::hours(6),
-            },
-        );
-
-        // BTC-specific high-frequency model
-        models.insert(
-            "volatility_BTC_USD_coinbase".to_string(),
-            VolatilityModel {
-                id: "vol_btc_001".to_string(),
-                name: "BTC Ultra-High Frequency Volatility".to_string(),
-                symbol_coverage: vec!["BTC-USD".to_string(), "BTC-USDT".to_string()],
-                accuracy: 0.925,
-                prediction_horizon_minutes: vec![1, 3, 5],
-                last_training: chrono::Utc::now() - chrono::Duration::hours(2),
-            },
-        );
-
-        info!("📈 Loaded {} volatility prediction models", models.len());
-        Ok(())
-    }
-
-    async fn load_cross_exchange_models(&self) -> NeuralResult<()> {
-        let mut models = self.arbitrage_models.write().await;
-
-        // Universal arbitrage model
-        models.insert(
-            "arbitrage_universal".to_string(),
-            ArbitrageModel {
-                id: "arb_001".to_string(),
-                name: "Universal Cross-Exchange Arbitrage".to_string(),
-                supported_exchanges: vec![
-                    "coinbase".to_string(),
-                    "binance_us".to_string(),
-                    "oanda".to_string(),
-                ],
-                success_rate: 0.912,
-                average_profit_bps: 45.2,
-                last_training: chrono::Utc::now() - chrono::Duration::hours(4),
-            },
-        );
-
-        // Coinbase-Binance specialized model
-        models.insert(
-            "arbitrage_coinbase_binance_us".to_string(),
-            ArbitrageModel {
-                id: "arb_cb_bn".to_string(),
-                name: "Coinbase-Binance Ultra-Fast Arbitrage".to_string(),
-                supported_exchanges: vec!["coinbase".to_string(), "binance_us".to_string()],
-                success_rate: 0.947,
-                average_profit_bps: 62.8,
-                last_training: chrono::Utc::now() - chrono::Duration::hours(1),
-            },
-        );
-
-        info!("🔄 Loaded {} cross-exchange arbitrage models", models.len());
-        Ok(())
-    }
-
-    async fn load_risk_models(&self) -> NeuralResult<()> {
-        let mut models = self.models.write().await;
-
-        models.insert(
-            "risk_assessor_v2".to_string(),
-            NeuralModel {
-                id: "risk_002".to_string(),
-                name: "Gordon Gekko Risk Assessor v2".to_string(),
-                model_type: ModelType::Transformer,
-                version: "2.1.0".to_string(),
-                accuracy: 0.934,
-                inference_time_ms: 12.5,
-                memory_usage_mb: 85.0,
-                is_active: true,
-                file_path: "/models/risk_assessor_v2.bin".to_string(),
-            },
-        );
-
-        info!("🛡️ Loaded enhanced risk assessment models");
-        Ok(())
-    }
-
-    /// Predict volatility using FANN model
-    #[cfg(feature = "fann")]
-    async fn predict_volatility_with_fann(
-        &self,
-        symbol: &str,
-        exchange: &str,
-        market_data: &MarketDataInput,
-    ) -> NeuralResult<Option<VolatilityPrediction>> {
-        let mut model_guard = self.fann_volatility_model.write().await;
-        let model = match model_guard.as_mut() {
-            Some(m) => m,
-            None => return Ok(None),
-        };
-
-        // Prepare input vector: [price, high, low, volume, avg_volume, bid, ask]
-        let input = vec![
-            market_data.price,
-            market_data.high,
-            market_data.low,
-            market_data.volume,
-            market_data.avg_volume,
-            market_data.bid,
-            market_data.ask,
-        ];
-
-        // Run FANN inference
-        let output = model.run(&input)?;
-
-        // Parse output: [volatility_1m, volatility_5m, volatility_15m, confidence]
-        let current_volatility = self.calculate_current_volatility(market_data);
-        let vol_1m = output.first().copied().unwrap_or(0.0).abs();
-        let vol_5m = output.get(1).copied().unwrap_or(0.0).abs();
-        let vol_15m = output.get(2).copied().unwrap_or(0.0).abs();
-        let confidence = output.get(3).copied().unwrap_or(0.5).abs().min(1.0);
-
-        let prediction = VolatilityPrediction {
-            symbol: symbol.to_string(),
-            exchange: exchange.to_string(),
-            current_volatility,
-            predicted_volatility_1m: vol_1m,
-            predicted_volatility_5m: vol_5m,
-            predicted_volatility_15m: vol_15m,
-            confidence_score: confidence,
-            trend_direction: if vol_1m > current_volatility * 1.5 {
-                TrendDirection::Highly_Volatile
-            } else if vol_1m > current_volatility {
-                TrendDirection::Bullish
-            } else if vol_1m < current_volatility * 0.5 {
-                TrendDirection::Bearish
-            } else {
-                TrendDirection::Neutral
-            },
-            volatility_regime: self.classify_volatility_regime(vol_1m),
-            predicted_at: chrono::Utc::now(),
-        };
-
-        debug!(
-            "🧠 FANN volatility prediction: vol_1m={:.4}, confidence={:.2}%",
-            vol_1m,
-            confidence * 100.0
-        );
-
-        Ok(Some(prediction))
+    /// Record market data with the monitor and persist the outcomes it resolves
+    async fn observe(&self, series: &str, observation: Observation) -> usize {
+        let resolved = self.monitor.observe(series, observation);
+        if let Some(sink) = &self.prediction_sink {
+            for outcome in &resolved {
+                if let Err(e) = sink.record_outcome(outcome).await {
+                    warn!(
+                        "Failed to store outcome of prediction {}: {}",
+                        outcome.prediction.id, e
+                    );
+                }
+            }
+        }
+        resolved.len()
     }
 
-    /// Predict arbitrage using FANN model
-    #[cfg(feature = "fann")]
-    async fn predict_arbitrage_with_fann(
+    async fn log_prediction(
         &self,
-        symbol: &str,
-        primary_exchange: &str,
-        secondary_exchange: &str,
-        primary_data: &MarketDataInput,
-        secondary_data: &MarketDataInput,
-    ) -> NeuralResult<Option<CrossExchangePrediction>> {
-        let mut model_guard = self.fann_arbitrage_model.write().await;
-        let model = match model_guard.as_mut() {
-            Some(m) => m,
-            None => return Ok(None),
-        };
-
-        // Calculate current spread
-        let current_spread = (secondary_data.price - primary_data.price).abs() / primary_data.price;
-
-        // Prepare input vector
-        let input = vec![
-            primary_data.price,
-            secondary_data.price,
-            current_spread,
-            primary_data.volume,
-            secondary_data.volume,
-            primary_data.bid,
-            primary_data.ask,
-            secondary_data.bid,
-            secondary_data.ask,
-        ];
-
-        // Run FANN inference
-        let output = model.run(&input)?;
-
-        // Parse output: [spread_1m, spread_5m, arb_probability, expected_profit, confidence]
-        let spread_1m = output.first().copied().unwrap_or(0.0).abs();
-        let spread_5m = output.get(1).copied().unwrap_or(0.0).abs();
-        let arb_prob = output.get(2).copied().unwrap_or(0.5).abs().min(1.0);
-        let expected_profit = output.get(3).copied().unwrap_or(0.0).abs() * 10000.0; // Convert to bps
-        let confidence = output.get(4).copied().unwrap_or(0.5).abs().min(1.0);
-
-        let prediction = CrossExchangePrediction {
-            symbol: symbol.to_string(),
-            primary_exchange: primary_exchange.to_string(),
-            secondary_exchange: secondary_exchange.to_string(),
-            current_spread,
-            predicted_spread_1m: spread_1m,
-            predicted_spread_5m: spread_5m,
-            arbitrage_probability: arb_prob,
-            expected_profit_bps: expected_profit,
-            confidence_score: confidence,
-            predicted_at: chrono::Utc::now(),
-        };
-
-        info!(
-            "🧠 FANN arbitrage prediction: prob={:.2}%, profit={:.1}bps",
-            arb_prob * 100.0,
-            expected_profit
+        model_id: &str,
+        task: PredictionTask,
+        input: &PredictionInput,
+        output: &[f64],
+        started: Instant,
+    ) {
+        let mut prediction = LoggedPrediction::new(
+            model_id,
+            task,
+            input.key.clone(),
+            input.features.clone(),
+            output.to_vec(),
         );
-
-        Ok(Some(prediction))
+        prediction.inference_time_ms = started.elapsed().as_secs_f64() * 1000.0;
+        let prediction = self.monitor.record(prediction);
+        if let Some(sink) = &self.prediction_sink {
+            if let Err(e) = sink.record_prediction(&prediction).await {
+                warn!("Failed to store prediction {}: {}", prediction.id, e);
+            }
+        }
     }
 
     fn calculate_current_volatility(&self, data: &MarketDataInput) -> f64 {
-        // Simplified volatility calculation
-        let price_range = (data.high - data.low) / data.price;
-        let volume_factor = (data.volume / data.avg_volume).clamp(0.1, 3.0);
-
-        price_range * volume_factor.sqrt() * 0.1
+        features::intrabar_volatility(&features::FeatureBar::from(data), data.avg_volume)
     }
 
     fn classify_volatility_regime(&self, volatility: f64) -> VolatilityRegime {
@@ -960,9 +840,12 @@ pub struct ArbitrageRiskAssessment {
     pub overall_risk_score: f64,
     pub liquidity_risk: f64,
     pub execution_risk: f64,
-    pub counterparty_risk: f64,
+    /// Not modelled yet
+    pub counterparty_risk: Option<f64>,
+    /// Current volatility relative to the stop-loss threshold
     pub market_risk: f64,
-    pub operational_risk: f64,
+    /// Not modelled yet
+    pub operational_risk: Option<f64>,
     pub max_recommended_position: f64,
     pub stop_loss_threshold: f64,
     pub confidence_score: f64,
@@ -973,36 +856,131 @@ pub struct ArbitrageRiskAssessment {
 mod tests {
     use super::*;
 
+    /// Predictor returning a fixed output
+    struct FixedPredictor {
+        info: PredictorInfo,
+        output: Vec<f64>,
+    }
+
+    impl FixedPredictor {
+        fn boxed(id: &str, task: PredictionTask, output: Vec<f64>) -> Box<dyn Predictor> {
+            Box::new(Self {
+                info: PredictorInfo::new(id, task, NeuralBackend::Simulated),
+                output,
+            })
+        }
+    }
+
+    impl Predictor for FixedPredictor {
+        fn info(&self) -> &PredictorInfo {
+            &self.info
+        }
+
+        fn predict(&mut self, _input: &PredictionInput) -> NeuralResult<Option<Vec<f64>>> {
+            Ok(Some(self.output.clone()))
+        }
+    }
+
+    fn market_data(price: f64) -> MarketDataInput {
+        MarketDataInput {
+            price,
+            high: price * 1.01,
+            low: price * 0.99,
+            volume: 1000000.0,
+            avg_volume: 800000.0,
+            bid: price - 5.0,
+            ask: price + 5.0,
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
     #[tokio::test]
     async fn test_neural_engine_creation() {
         let engine = NeuralEngine::new(NeuralBackend::RuvFann).expect("Failed to create engine");
+        assert!(engine.list_models().is_empty());
         assert!(engine.load_arbitrage_models().await.is_ok());
+
+        let models = engine.list_models();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].backend, NeuralBackend::Statistical);
     }
 
     #[tokio::test]
     async fn test_volatility_prediction() {
         let engine = NeuralEngine::new(NeuralBackend::RuvFann).expect("Failed to create engine");
+
+        // Nothing loaded: explicit NoModel rather than a made-up forecast
+        let outcome = engine
+            .predict_volatility("BTC-USD", "coinbase", &market_data(50000.0))
+            .await
+            .unwrap();
+        assert!(!outcome.is_predicted());
+
         engine.load_arbitrage_models().await.unwrap();
 
-        let market_data = MarketDataInput {
-            price: 50000.0,
-            high: 50500.0,
-            low: 49500.0,
-            volume: 1000000.0,
-            avg_volume: 800000.0,
-            bid: 49995.0,
-            ask: 50005.0,
-            timestamp: chrono::Utc::now(),
-        };
+        let mut last = None;
+        for i in 0..=20 {
+            let price = 50000.0 * if i % 2 == 0 { 1.0 } else { 1.002 };
+            last = Some(
+                engine
+                    .predict_volatility("BTC-USD", "coinbase", &market_data(price))
+                    .await
+                    .unwrap(),
+            );
+            if i < 20 {
+                // Baseline still warming up
+                assert!(!last.as_ref().unwrap().is_predicted(), "step {}", i);
+            }
+        }
 
-        let prediction = engine
-            .predict_volatility("BTC-USD", "coinbase", &market_data)
-            .await;
-        assert!(prediction.is_ok());
+        match last.unwrap() {
+            PredictionOutcome::Predicted { model_id, value } => {
+                assert_eq!(model_id, "volatility_ewma");
+                assert_eq!(value.symbol, "BTC-USD");
+                assert!((value.predicted_volatility_1m - 0.002).abs() < 1e-4);
+                assert!((0.0..=1.0).contains(&value.confidence_score));
+            }
+            other => panic!("expected prediction, got {:?}", other),
+        }
+    }
 
-        let pred = prediction.unwrap();
-        assert!(pred.confidence_score > 0.8);
-        assert_eq!(pred.symbol, "BTC-USD");
+    #[tokio::test]
+    async fn test_overconfident_arbitrage_model_is_flagged() {
+        let engine = NeuralEngine::new(NeuralBackend::RuvFann)
+            .unwrap()
+            .with_monitoring(MonitoringConfig {
+                min_samples: 20,
+                reference_size: 20,
+                ..MonitoringConfig::default()
+            });
+        let mut alerts = engine.subscribe_model_alerts();
+        // Always promises a 100 bps opportunity on a market with no spread
+        engine.register_predictor(FixedPredictor::boxed(
+            "arbitrage_optimist",
+            PredictionTask::Arbitrage,
+            vec![0.003, 0.003, 0.9, 0.01, 0.9],
+        ));
+
+        let start = chrono::Utc::now();
+        for t in 0..30 {
+            let mut data = market_data(50000.0);
+            data.timestamp = start + chrono::Duration::minutes(t);
+            engine
+                .predict_cross_exchange_arbitrage("BTC-USD", "coinbase", "kraken", &data, &data)
+                .await
+                .unwrap();
+        }
+
+        assert!(engine.is_model_degraded("arbitrage_optimist"));
+        let health = &engine.model_health()[0];
+        assert_eq!(health.logged, 30);
+        assert_eq!(health.resolved, 25);
+        let calibration = health.calibration.as_ref().unwrap();
+        assert!(calibration.expected_calibration_error > 0.8);
+
+        let alert = alerts.try_recv().unwrap();
+        assert_eq!(alert.kind, ModelAlertKind::Degraded);
+        assert_eq!(alert.model_id, "arbitrage_optimist");
     }
 
     #[tokio::test]
@@ -1010,28 +988,32 @@ mod tests {
         let engine = NeuralEngine::new(NeuralBackend::RuvFann).expect("Failed to create engine");
         engine.load_arbitrage_models().await.unwrap();
 
-        let primary_data = MarketDataInput {
-            price: 50000.0,
-            high: 50100.0,
-            low: 49900.0,
-            volume: 500000.0,
-            avg_volume: 400000.0,
-            bid: 49995.0,
-            ask: 50005.0,
-            timestamp: chrono::Utc::now(),
-        };
+        let primary_data = market_data(50000.0);
+        let secondary_data = market_data(50150.0);
 
-        let secondary_data = MarketDataInput {
-            price: 50150.0,
-            high: 50250.0,
-            low: 50050.0,
-            volume: 600000.0,
-            avg_volume: 500000.0,
-            bid: 50145.0,
-            ask: 50155.0,
-            timestamp: chrono::Utc::now(),
-        };
+        let outcome = engine
+            .predict_cross_exchange_arbitrage(
+                "BTC-USD",
+                "coinbase",
+                "binance_us",
+                &primary_data,
+                &secondary_data,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            outcome,
+            PredictionOutcome::NoModel {
+                task: PredictionTask::Arbitrage,
+                ..
+            }
+        ));
 
+        engine.register_predictor(FixedPredictor::boxed(
+            "arbitrage_universal",
+            PredictionTask::Arbitrage,
+            vec![0.003, 0.002, 0.7, 0.0015, 0.8],
+        ));
         let prediction = engine
             .predict_cross_exchange_arbitrage(
                 "BTC-USD",
@@ -1040,13 +1022,42 @@ mod tests {
                 &primary_data,
                 &secondary_data,
             )
-            .await;
-
-        assert!(prediction.is_ok());
+            .await
+            .unwrap()
+            .prediction()
+            .expect("model registered");
+
+        assert!((prediction.current_spread - 0.003).abs() < 1e-12);
+        assert!((prediction.arbitrage_probability - 0.7).abs() < 1e-12);
+        assert!((prediction.expected_profit_bps - 15.0).abs() < 1e-9);
+    }
 
-        let pred = prediction.unwrap();
-        assert!(pred.arbitrage_probability > 0.5);
-        assert!(pred.expected_profit_bps > 0.0);
+    #[tokio::test]
+    async fn test_risk_assessment_requires_model() {
+        let engine = NeuralEngine::new(NeuralBackend::Simulated).expect("Failed to create engine");
+        let exchanges = vec!["coinbase".to_string(), "kraken".to_string()];
+
+        let outcome = engine
+            .assess_arbitrage_risk("BTC-USD", &exchanges, 10_000.0, &market_data(50000.0))
+            .await
+            .unwrap();
+        assert!(!outcome.is_predicted());
+
+        engine.register_predictor(FixedPredictor::boxed(
+            "risk_test",
+            PredictionTask::Risk,
+            vec![0.4, 1.3, 0.2, 25_000.0, 0.9],
+        ));
+        let assessment = engine
+            .assess_arbitrage_risk("BTC-USD", &exchanges, 10_000.0, &market_data(50000.0))
+            .await
+            .unwrap()
+            .prediction()
+            .unwrap();
+
+        assert_eq!(assessment.liquidity_risk, 1.0);
+        assert_eq!(assessment.max_recommended_position, 25_000.0);
+        assert!(assessment.counterparty_risk.is_none());
     }
 
     #[cfg(feature = "fann")]
@@ -0,0 +1,869 @@
+//! Versioned model registry
+//!
+//! Model versions are recorded in a [`ModelStore`] (the `neural_models`
+//! tables in Postgres, or memory) with their metadata, feature schema hash
+//! and evaluation metrics. [`ModelRegistry`] moves versions through their
+//! lifecycle on a running [`PredictorRegistry`]: a candidate can shadow the
+//! live model, be promoted, and be rolled back to the previous live version,
+//! all without restarting the engine. Versions are registered as predictors
+//! under `name@version`.
+
+use crate::features::FeatureSetSpec;
+use crate::predictor::{PredictionTask, Predictor, PredictorRegistry, ShadowComparison};
+use crate::training::EvaluationMetrics;
+use crate::{ModelType, NeuralBackend, NeuralError, NeuralResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, PoisonError, RwLock};
+use tracing::info;
+
+#[cfg(feature = "fann")]
+use crate::training::TrainedModel;
+
+/// Lifecycle state of a model version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ModelStatus {
+    /// Recorded but not serving or shadowing
+    Candidate,
+    /// Running next to the live model; predictions are compared, never served
+    Shadow,
+    /// Serving its task
+    Live,
+    /// Replaced by a promotion; a rollback target
+    Retired,
+    /// Taken out of service by a rollback
+    RolledBack,
+}
+
+impl ModelStatus {
+    /// Lowercase name, as stored in the model tables
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModelStatus::Candidate => "candidate",
+            ModelStatus::Shadow => "shadow",
+            ModelStatus::Live => "live",
+            ModelStatus::Retired => "retired",
+            ModelStatus::RolledBack => "rolled_back",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "candidate" => Some(ModelStatus::Candidate),
+            "shadow" => Some(ModelStatus::Shadow),
+            "live" => Some(ModelStatus::Live),
+            "retired" => Some(ModelStatus::Retired),
+            "rolled_back" => Some(ModelStatus::RolledBack),
+            _ => None,
+        }
+    }
+}
+
+/// One version of a named model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelRecord {
+    /// Model family, e.g. `volatility`; versions of a name replace each other
+    pub name: String,
+    pub version: String,
+    pub task: PredictionTask,
+    pub backend: NeuralBackend,
+    pub model_type: Option<ModelType>,
+    pub description: Option<String>,
+    pub input_features: Vec<String>,
+    pub output_features: Vec<String>,
+    /// Fingerprint of the input layout the model was trained on
+    pub feature_schema_hash: String,
+    /// Validation metrics from training
+    pub metrics: Option<EvaluationMetrics>,
+    pub training_samples: usize,
+    pub model_path: String,
+    pub status: ModelStatus,
+    pub trained_at: Option<DateTime<Utc>>,
+    /// When the version last went live
+    pub deployed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ModelRecord {
+    /// A candidate using the task's fixed feature layout
+    pub fn new(
+        name: impl Into<String>,
+        version: impl Into<String>,
+        task: PredictionTask,
+        backend: NeuralBackend,
+        model_path: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            task,
+            backend,
+            model_type: None,
+            description: None,
+            input_features: task.feature_names().iter().map(|s| s.to_string()).collect(),
+            output_features: task.output_names().iter().map(|s| s.to_string()).collect(),
+            feature_schema_hash: task.feature_schema_hash(),
+            metrics: None,
+            training_samples: 0,
+            model_path: model_path.into(),
+            status: ModelStatus::Candidate,
+            trained_at: None,
+            deployed_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Record for a model written by the training pipeline
+    #[cfg(feature = "fann")]
+    pub fn from_trained(
+        name: impl Into<String>,
+        version: impl Into<String>,
+        task: PredictionTask,
+        trained: &TrainedModel,
+    ) -> Self {
+        let mut record = Self::new(
+            name,
+            version,
+            task,
+            NeuralBackend::RuvFann,
+            trained.path.to_string_lossy(),
+        )
+        .with_model_type(ModelType::MLP)
+        .with_metrics(trained.report.validation, trained.report.training_samples);
+        record.trained_at = trained
+            .metadata
+            .training_date
+            .as_deref()
+            .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+            .map(|date| date.with_timezone(&Utc));
+        if let Some(manifest) = &trained.features {
+            record = record.with_feature_set(&manifest.spec);
+        }
+        record
+    }
+
+    pub fn with_model_type(mut self, model_type: ModelType) -> Self {
+        self.model_type = Some(model_type);
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: EvaluationMetrics, training_samples: usize) -> Self {
+        self.metrics = Some(metrics);
+        self.training_samples = training_samples;
+        self
+    }
+
+    /// Inputs come from a feature pipeline rather than the task's fixed layout
+    pub fn with_feature_set(mut self, spec: &FeatureSetSpec) -> Self {
+        self.input_features = spec.column_names();
+        self.feature_schema_hash = spec.schema_hash();
+        self
+    }
+
+    /// Predictor id the version is registered under
+    pub fn predictor_id(&self) -> String {
+        format!("{}@{}", self.name, self.version)
+    }
+
+    pub fn accuracy(&self) -> Option<f64> {
+        self.metrics.map(|metrics| metrics.accuracy())
+    }
+
+    fn validate(&self) -> NeuralResult<()> {
+        if self.name.is_empty() || self.version.is_empty() || self.name.contains('@') {
+            return Err(NeuralError::InvalidInput(format!(
+                "model name and version must be non-empty and the name free of '@', got '{}'",
+                self.predictor_id()
+            )));
+        }
+        if self.input_features.len() != self.task.input_size()
+            || self.output_features.len() != self.task.output_size()
+        {
+            return Err(NeuralError::InvalidInput(format!(
+                "{} declares {} inputs and {} outputs; {:?} models take {} and produce {}",
+                self.predictor_id(),
+                self.input_features.len(),
+                self.output_features.len(),
+                self.task,
+                self.task.input_size(),
+                self.task.output_size()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Persistence for model versions and shadow comparisons
+#[async_trait]
+pub trait ModelStore: Send + Sync {
+    /// Add a new version; fails if `name@version` already exists
+    async fn insert(&self, record: &ModelRecord) -> NeuralResult<()>;
+
+    async fn get(&self, name: &str, version: &str) -> NeuralResult<Option<ModelRecord>>;
+
+    /// Every version of `name`, oldest first
+    async fn versions(&self, name: &str) -> NeuralResult<Vec<ModelRecord>>;
+
+    /// Every version in the given states
+    async fn with_status(&self, statuses: &[ModelStatus]) -> NeuralResult<Vec<ModelRecord>>;
+
+    /// Move a version to `status`; going live stamps `deployed_at`
+    async fn set_status(&self, name: &str, version: &str, status: ModelStatus) -> NeuralResult<()>;
+
+    /// Keep the outcome of a shadow run
+    async fn record_comparison(&self, comparison: &ShadowComparison) -> NeuralResult<()>;
+}
+
+/// In-process [`ModelStore`]; contents are lost on restart
+#[derive(Default)]
+pub struct InMemoryModelStore {
+    records: RwLock<Vec<ModelRecord>>,
+    comparisons: RwLock<Vec<ShadowComparison>>,
+}
+
+impl InMemoryModelStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shadow comparisons recorded so far
+    pub fn comparisons(&self) -> Vec<ShadowComparison> {
+        self.comparisons
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[async_trait]
+impl ModelStore for InMemoryModelStore {
+    async fn insert(&self, record: &ModelRecord) -> NeuralResult<()> {
+        let mut records = self.records.write().unwrap_or_else(PoisonError::into_inner);
+        if records
+            .iter()
+            .any(|r| r.name == record.name && r.version == record.version)
+        {
+            return Err(NeuralError::Storage(format!(
+                "{} is already registered",
+                record.predictor_id()
+            )));
+        }
+        records.push(record.clone());
+        Ok(())
+    }
+
+    async fn get(&self, name: &str, version: &str) -> NeuralResult<Option<ModelRecord>> {
+        Ok(self
+            .records
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find(|r| r.name == name && r.version == version)
+            .cloned())
+    }
+
+    async fn versions(&self, name: &str) -> NeuralResult<Vec<ModelRecord>> {
+        Ok(self
+            .records
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|r| r.name == name)
+            .cloned()
+            .collect())
+    }
+
+    async fn with_status(&self, statuses: &[ModelStatus]) -> NeuralResult<Vec<ModelRecord>> {
+        Ok(self
+            .records
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|r| statuses.contains(&r.status))
+            .cloned()
+            .collect())
+    }
+
+    async fn set_status(&self, name: &str, version: &str, status: ModelStatus) -> NeuralResult<()> {
+        let mut records = self.records.write().unwrap_or_else(PoisonError::into_inner);
+        let record = records
+            .iter_mut()
+            .find(|r| r.name == name && r.version == version)
+            .ok_or_else(|| NeuralError::ModelNotFound(format!("{}@{}", name, version)))?;
+        if status == ModelStatus::Live {
+            record.deployed_at = Some(Utc::now());
+        }
+        record.status = status;
+        Ok(())
+    }
+
+    async fn record_comparison(&self, comparison: &ShadowComparison) -> NeuralResult<()> {
+        self.comparisons
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(comparison.clone());
+        Ok(())
+    }
+}
+
+/// Builds a predictor for a stored version, e.g. from its model file
+pub trait ModelLoader: Send + Sync {
+    fn load(&self, record: &ModelRecord) -> NeuralResult<Box<dyn Predictor>>;
+}
+
+/// [`ModelLoader`] choosing a loader by the record's backend
+#[derive(Default)]
+pub struct BackendLoaders {
+    loaders: Vec<(NeuralBackend, Arc<dyn ModelLoader>)>,
+}
+
+impl BackendLoaders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load `backend` records with `loader`, replacing any earlier one
+    pub fn with(mut self, backend: NeuralBackend, loader: Arc<dyn ModelLoader>) -> Self {
+        self.loaders.retain(|(existing, _)| *existing != backend);
+        self.loaders.push((backend, loader));
+        self
+    }
+}
+
+impl ModelLoader for BackendLoaders {
+    fn load(&self, record: &ModelRecord) -> NeuralResult<Box<dyn Predictor>> {
+        let (_, loader) = self
+            .loaders
+            .iter()
+            .find(|(backend, _)| *backend == record.backend)
+            .ok_or_else(|| {
+                NeuralError::BackendUnavailable(format!(
+                    "no loader for {} ({:?} backend)",
+                    record.predictor_id(),
+                    record.backend
+                ))
+            })?;
+        loader.load(record)
+    }
+}
+
+/// How a deployed version serves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeploymentMode {
+    Live,
+    Shadow,
+}
+
+/// Version lifecycle on top of a [`ModelStore`]
+pub struct ModelRegistry {
+    store: Arc<dyn ModelStore>,
+    loader: Option<Arc<dyn ModelLoader>>,
+}
+
+impl ModelRegistry {
+    pub fn new(store: Arc<dyn ModelStore>) -> Self {
+        Self {
+            store,
+            loader: None,
+        }
+    }
+
+    pub fn in_memory() -> Self {
+        Self::new(Arc::new(InMemoryModelStore::new()))
+    }
+
+    /// Loader used to bring stored versions back into memory on restore,
+    /// promotion or rollback
+    pub fn with_loader(mut self, loader: Arc<dyn ModelLoader>) -> Self {
+        self.loader = Some(loader);
+        self
+    }
+
+    pub fn store(&self) -> &dyn ModelStore {
+        self.store.as_ref()
+    }
+
+    /// Record a new candidate version
+    pub async fn register_version(&self, mut record: ModelRecord) -> NeuralResult<ModelRecord> {
+        record.validate()?;
+        record.status = ModelStatus::Candidate;
+        record.deployed_at = None;
+        record.created_at = Utc::now();
+        self.store.insert(&record).await?;
+        info!(
+            "📦 Registered {:?} model version {}",
+            record.task,
+            record.predictor_id()
+        );
+        Ok(record)
+    }
+
+    pub async fn versions(&self, name: &str) -> NeuralResult<Vec<ModelRecord>> {
+        self.store.versions(name).await
+    }
+
+    pub async fn live_version(&self, name: &str) -> NeuralResult<Option<ModelRecord>> {
+        Ok(self
+            .store
+            .versions(name)
+            .await?
+            .into_iter()
+            .find(|r| r.status == ModelStatus::Live))
+    }
+
+    /// Load every live and shadow version into `predictors`, e.g. at startup
+    pub async fn restore(&self, predictors: &PredictorRegistry) -> NeuralResult<usize> {
+        let live = self.store.with_status(&[ModelStatus::Live]).await?;
+        let shadows = self.store.with_status(&[ModelStatus::Shadow]).await?;
+
+        for record in &live {
+            self.ensure_loaded(predictors, record, None)?;
+            predictors.set_active(&record.predictor_id())?;
+        }
+        for record in &shadows {
+            self.ensure_loaded(predictors, record, None)?;
+            predictors.set_shadow(&record.predictor_id())?;
+        }
+        Ok(live.len() + shadows.len())
+    }
+
+    /// Deploy a stored version using the registry's loader
+    pub async fn deploy(
+        &self,
+        predictors: &PredictorRegistry,
+        name: &str,
+        version: &str,
+        mode: DeploymentMode,
+    ) -> NeuralResult<Option<ShadowComparison>> {
+        self.deploy_with(predictors, name, version, mode, None)
+            .await
+    }
+
+    /// Deploy a stored version served by `predictor`
+    pub async fn deploy_predictor(
+        &self,
+        predictors: &PredictorRegistry,
+        name: &str,
+        version: &str,
+        mode: DeploymentMode,
+        predictor: Box<dyn Predictor>,
+    ) -> NeuralResult<Option<ShadowComparison>> {
+        self.deploy_with(predictors, name, version, mode, Some(predictor))
+            .await
+    }
+
+    /// Make a version live, replacing the current live version of its name.
+    ///
+    /// Returns the shadow comparison when the version was being shadowed.
+    pub async fn promote(
+        &self,
+        predictors: &PredictorRegistry,
+        name: &str,
+        version: &str,
+    ) -> NeuralResult<Option<ShadowComparison>> {
+        self.deploy(predictors, name, version, DeploymentMode::Live)
+            .await
+    }
+
+    /// Put the previously live version of `name` back into service
+    pub async fn rollback(
+        &self,
+        predictors: &PredictorRegistry,
+        name: &str,
+    ) -> NeuralResult<ModelRecord> {
+        let versions = self.store.versions(name).await?;
+        let current = versions
+            .iter()
+            .find(|r| r.status == ModelStatus::Live)
+            .ok_or_else(|| NeuralError::ModelNotFound(format!("no live version of {}", name)))?;
+        let previous = versions
+            .iter()
+            .filter(|r| r.status == ModelStatus::Retired && r.deployed_at.is_some())
+            .max_by_key(|r| r.deployed_at)
+            .ok_or_else(|| {
+                NeuralError::ModelNotFound(format!(
+                    "no earlier version of {} to roll back to",
+                    name
+                ))
+            })?;
+
+        self.ensure_loaded(predictors, previous, None)?;
+        predictors.set_active(&previous.predictor_id())?;
+        self.store
+            .set_status(&current.name, &current.version, ModelStatus::RolledBack)
+            .await?;
+        self.store
+            .set_status(&previous.name, &previous.version, ModelStatus::Live)
+            .await?;
+
+        info!(
+            "⏪ Rolled back {} from {} to {}",
+            name, current.version, previous.version
+        );
+        self.store
+            .get(&previous.name, &previous.version)
+            .await?
+            .ok_or_else(|| NeuralError::ModelNotFound(previous.predictor_id()))
+    }
+
+    /// End the shadow run on `task`, keeping its comparison and returning the
+    /// shadow version to candidate
+    pub async fn end_shadow(
+        &self,
+        predictors: &PredictorRegistry,
+        task: PredictionTask,
+    ) -> NeuralResult<Option<ShadowComparison>> {
+        let Some(comparison) = predictors.clear_shadow(task) else {
+            return Ok(None);
+        };
+        self.store.record_comparison(&comparison).await?;
+        if let Some((name, version)) = comparison.shadow_id.rsplit_once('@') {
+            if self.store.get(name, version).await?.is_some() {
+                self.store
+                    .set_status(name, version, ModelStatus::Candidate)
+                    .await?;
+            }
+        }
+        predictors.unregister(&comparison.shadow_id);
+        Ok(Some(comparison))
+    }
+
+    async fn deploy_with(
+        &self,
+        predictors: &PredictorRegistry,
+        name: &str,
+        version: &str,
+        mode: DeploymentMode,
+        predictor: Option<Box<dyn Predictor>>,
+    ) -> NeuralResult<Option<ShadowComparison>> {
+        let record = self
+            .store
+            .get(name, version)
+            .await?
+            .ok_or_else(|| NeuralError::ModelNotFound(format!("{}@{}", name, version)))?;
+        let target = match mode {
+            DeploymentMode::Live => ModelStatus::Live,
+            DeploymentMode::Shadow => ModelStatus::Shadow,
+        };
+        if record.status == target {
+            return Ok(None);
+        }
+
+        // Shadow and live models are fed the same feature vector
+        let live = self.live_version(name).await?;
+        if let Some(live) = &live {
+            if live.feature_schema_hash != record.feature_schema_hash {
+                return Err(NeuralError::InvalidInput(format!(
+                    "{} was trained on feature schema {}, live {} uses {}",
+                    record.predictor_id(),
+                    record.feature_schema_hash,
+                    live.predictor_id(),
+                    live.feature_schema_hash
+                )));
+            }
+        }
+
+        if mode == DeploymentMode::Shadow && !predictors.has_model(record.task) {
+            return Err(NeuralError::ModelNotFound(format!(
+                "no live {:?} model for {} to shadow",
+                record.task,
+                record.predictor_id()
+            )));
+        }
+
+        self.ensure_loaded(predictors, &record, predictor)?;
+        let id = record.predictor_id();
+
+        match mode {
+            DeploymentMode::Shadow => {
+                self.end_shadow(predictors, record.task).await?;
+                predictors.set_shadow(&id)?;
+                self.store
+                    .set_status(name, version, ModelStatus::Shadow)
+                    .await?;
+                Ok(None)
+            }
+            DeploymentMode::Live => {
+                let comparison = match predictors.shadow_comparison(record.task) {
+                    Some(comparison) if comparison.shadow_id == id => {
+                        self.store.record_comparison(&comparison).await?;
+                        Some(comparison)
+                    }
+                    // Another candidate was being compared against the outgoing live model
+                    Some(_) => {
+                        self.end_shadow(predictors, record.task).await?;
+                        None
+                    }
+                    None => None,
+                };
+
+                predictors.set_active(&id)?;
+                if let Some(live) = &live {
+                    self.store
+                        .set_status(&live.name, &live.version, ModelStatus::Retired)
+                        .await?;
+                }
+                self.store
+                    .set_status(name, version, ModelStatus::Live)
+                    .await?;
+                info!(
+                    "🚀 Promoted {} to live{}",
+                    id,
+                    live.map(|live| format!(" (replacing {})", live.version))
+                        .unwrap_or_default()
+                );
+                Ok(comparison)
+            }
+        }
+    }
+
+    /// Register the version's predictor unless it is already loaded
+    fn ensure_loaded(
+        &self,
+        predictors: &PredictorRegistry,
+        record: &ModelRecord,
+        predictor: Option<Box<dyn Predictor>>,
+    ) -> NeuralResult<()> {
+        let id = record.predictor_id();
+        if predictor.is_none() && predictors.info(&id).is_some() {
+            return Ok(());
+        }
+
+        let predictor = match predictor {
+            Some(predictor) => predictor,
+            None => self
+                .loader
+                .as_ref()
+                .ok_or_else(|| {
+                    NeuralError::ModelNotFound(format!(
+                        "{} is not loaded and no model loader is configured",
+                        id
+                    ))
+                })?
+                .load(record)?,
+        };
+        if predictor.info().task != record.task {
+            return Err(NeuralError::InvalidInput(format!(
+                "{} is a {:?} model but the predictor serves {:?}",
+                id,
+                record.task,
+                predictor.info().task
+            )));
+        }
+
+        let mut info = predictor.info().clone();
+        info.id = id;
+        info.version = record.version.clone();
+        info.model_type = record.model_type.or(info.model_type);
+        info.accuracy = record.accuracy().or(info.accuracy);
+        info.trained_at = record
+            .trained_at
+            .map(|date| date.to_rfc3339())
+            .or(info.trained_at);
+        predictors.register_as(info, predictor);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predictor::{PredictionInput, PredictionOutcome, PredictorInfo};
+
+    /// Predicts a constant on every output
+    struct ConstantPredictor {
+        info: PredictorInfo,
+        value: f64,
+    }
+
+    impl Predictor for ConstantPredictor {
+        fn info(&self) -> &PredictorInfo {
+            &self.info
+        }
+
+        fn predict(&mut self, _input: &PredictionInput) -> NeuralResult<Option<Vec<f64>>> {
+            Ok(Some(vec![self.value; self.info.task.output_size()]))
+        }
+    }
+
+    /// Loads a [`ConstantPredictor`] whose value is the record's version number
+    struct ConstantLoader;
+
+    impl ModelLoader for ConstantLoader {
+        fn load(&self, record: &ModelRecord) -> NeuralResult<Box<dyn Predictor>> {
+            Ok(Box::new(ConstantPredictor {
+                info: PredictorInfo::new(record.predictor_id(), record.task, record.backend),
+                value: record.version.parse().unwrap_or(0.0),
+            }))
+        }
+    }
+
+    fn record(version: &str) -> ModelRecord {
+        ModelRecord::new(
+            "volatility",
+            version,
+            PredictionTask::Volatility,
+            NeuralBackend::RuvFann,
+            format!("models/volatility-{}.json", version),
+        )
+        .with_metrics(
+            EvaluationMetrics {
+                mse: 0.01,
+                mae: 0.1,
+            },
+            1_000,
+        )
+    }
+
+    async fn serve(predictors: &PredictorRegistry) -> PredictionOutcome<Vec<f64>> {
+        predictors
+            .predict(
+                PredictionTask::Volatility,
+                None,
+                &PredictionInput::new("BTC-USD:coinbase", vec![1.0; 7]),
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn status(registry: &ModelRegistry, version: &str) -> ModelStatus {
+        registry
+            .store()
+            .get("volatility", version)
+            .await
+            .unwrap()
+            .unwrap()
+            .status
+    }
+
+    #[tokio::test]
+    async fn test_shadow_promote_and_rollback_without_restart() {
+        let store = Arc::new(InMemoryModelStore::new());
+        let registry = ModelRegistry::new(store.clone()).with_loader(Arc::new(ConstantLoader));
+        let predictors = PredictorRegistry::new();
+
+        registry.register_version(record("1")).await.unwrap();
+        registry.register_version(record("2")).await.unwrap();
+        assert!(registry.register_version(record("2")).await.is_err());
+
+        registry
+            .deploy(&predictors, "volatility", "1", DeploymentMode::Live)
+            .await
+            .unwrap();
+        registry
+            .deploy(&predictors, "volatility", "2", DeploymentMode::Shadow)
+            .await
+            .unwrap();
+        assert_eq!(status(&registry, "2").await, ModelStatus::Shadow);
+
+        // The shadow is compared but the live version keeps serving
+        let outcome = serve(&predictors).await;
+        assert_eq!(outcome.prediction(), Some(vec![1.0; 4]));
+        let comparison = predictors
+            .shadow_comparison(PredictionTask::Volatility)
+            .unwrap();
+        assert_eq!(comparison.samples, 1);
+        assert_eq!(comparison.mean_abs_difference(), vec![1.0; 4]);
+
+        let comparison = registry
+            .promote(&predictors, "volatility", "2")
+            .await
+            .unwrap()
+            .expect("promoted from shadow");
+        assert_eq!(comparison.shadow_id, "volatility@2");
+        assert_eq!(store.comparisons().len(), 1);
+        assert_eq!(status(&registry, "1").await, ModelStatus::Retired);
+        assert_eq!(status(&registry, "2").await, ModelStatus::Live);
+        assert_eq!(serve(&predictors).await.prediction(), Some(vec![2.0; 4]));
+        assert_eq!(predictors.info("volatility@2").unwrap().accuracy, Some(0.9));
+
+        let restored = registry.rollback(&predictors, "volatility").await.unwrap();
+        assert_eq!(restored.version, "1");
+        assert_eq!(status(&registry, "2").await, ModelStatus::RolledBack);
+        assert_eq!(serve(&predictors).await.prediction(), Some(vec![1.0; 4]));
+
+        // Nothing earlier than version 1
+        assert!(registry.rollback(&predictors, "volatility").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_restore_and_schema_mismatch() {
+        let store = Arc::new(InMemoryModelStore::new());
+        let registry = ModelRegistry::new(store.clone()).with_loader(Arc::new(ConstantLoader));
+        registry.register_version(record("1")).await.unwrap();
+        registry
+            .deploy(
+                &PredictorRegistry::new(),
+                "volatility",
+                "1",
+                DeploymentMode::Live,
+            )
+            .await
+            .unwrap();
+
+        // A fresh process picks the live version back up from the store
+        let predictors = PredictorRegistry::new();
+        assert_eq!(registry.restore(&predictors).await.unwrap(), 1);
+        assert_eq!(
+            predictors.active_id(PredictionTask::Volatility).as_deref(),
+            Some("volatility@1")
+        );
+
+        let mut drifted = record("2");
+        drifted.feature_schema_hash = "0000000000000000".to_string();
+        registry.register_version(drifted).await.unwrap();
+        assert!(matches!(
+            registry
+                .deploy(&predictors, "volatility", "2", DeploymentMode::Shadow)
+                .await,
+            Err(NeuralError::InvalidInput(_))
+        ));
+
+        let mut malformed = record("3");
+        malformed.input_features.pop();
+        assert!(registry.register_version(malformed).await.is_err());
+
+        // Without a loader, versions must be handed over explicitly
+        registry.register_version(record("4")).await.unwrap();
+        let manual = ModelRegistry::new(store);
+        assert!(matches!(
+            manual
+                .deploy(&predictors, "volatility", "4", DeploymentMode::Live)
+                .await,
+            Err(NeuralError::ModelNotFound(_))
+        ));
+        let predictor = ConstantLoader.load(&record("4")).unwrap();
+        manual
+            .deploy_predictor(
+                &predictors,
+                "volatility",
+                "4",
+                DeploymentMode::Live,
+                predictor,
+            )
+            .await
+            .unwrap();
+        assert_eq!(serve(&predictors).await.prediction(), Some(vec![4.0; 4]));
+    }
+
+    #[test]
+    fn test_backend_loaders_dispatch_on_record_backend() {
+        let loaders = BackendLoaders::new().with(NeuralBackend::Onnx, Arc::new(ConstantLoader));
+
+        let mut onnx = record("7");
+        onnx.backend = NeuralBackend::Onnx;
+        let predictor = loaders.load(&onnx).unwrap();
+        assert_eq!(predictor.info().backend, NeuralBackend::Onnx);
+
+        assert!(matches!(
+            loaders.load(&record("7")),
+            Err(NeuralError::BackendUnavailable(_))
+        ));
+    }
+}
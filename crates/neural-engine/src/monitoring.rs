@@ -0,0 +1,1011 @@
+//! Online prediction monitoring
+//!
+//! Every prediction the engine serves is logged with its inputs. Market data
+//! observed afterwards is buffered per series, and once the label horizon has
+//! elapsed the prediction is joined to its realized targets, computed with
+//! the same labelling functions the training pipeline uses. Observations are
+//! expected at the sampling interval the models were trained on (one bar per
+//! volatility step, one quote pair per spread step).
+//!
+//! Per model the monitor keeps a rolling window of resolved predictions (error
+//! metrics and probability calibration) and of logged inputs (feature drift
+//! against a reference distribution), and broadcasts a [`ModelAlert`] when a
+//! model degrades or recovers.
+
+use crate::predictor::PredictionTask;
+use crate::training::{
+    forward_arbitrage_targets, forward_volatility_targets, LabelingConfig, QuoteSnapshot,
+    SpreadSample, TrainingCandle,
+};
+use crate::{MarketDataInput, NeuralResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+/// Smallest bin share used in PSI, so empty bins stay finite
+const PSI_FLOOR: f64 = 1e-4;
+const CALIBRATION_BINS: usize = 10;
+const ALERT_CAPACITY: usize = 64;
+
+/// Windows and degradation thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitoringConfig {
+    /// Resolved predictions and logged inputs kept per model
+    pub window: usize,
+    /// Samples needed before error, calibration or drift is judged
+    pub min_samples: usize,
+    /// Logged inputs captured as the drift reference when none is set
+    pub reference_size: usize,
+    /// Quantile bins per feature for the population stability index
+    pub drift_bins: usize,
+    /// Logged predictions between drift checks for models that never resolve
+    pub evaluation_interval: usize,
+    /// PSI above which a feature counts as drifted (0.25 is the usual cut-off)
+    pub max_feature_psi: f64,
+    /// Expected calibration error allowed on arbitrage probabilities
+    pub max_calibration_error: f64,
+    /// Mean absolute 5m volatility error allowed, relative to mean realized volatility
+    pub max_normalized_error: f64,
+    /// Allowed over-prediction of arbitrage profit, in basis points
+    pub max_profit_bias_bps: f64,
+    /// Unresolved predictions kept per series
+    pub max_pending_per_series: usize,
+    /// Horizons and costs; must match the labelling the models were trained with
+    pub labeling: LabelingConfig,
+}
+
+impl Default for MonitoringConfig {
+    fn default() -> Self {
+        Self {
+            window: 500,
+            min_samples: 50,
+            reference_size: 500,
+            drift_bins: 10,
+            evaluation_interval: 25,
+            max_feature_psi: 0.25,
+            max_calibration_error: 0.15,
+            max_normalized_error: 0.75,
+            max_profit_bias_bps: 10.0,
+            max_pending_per_series: 1_000,
+            labeling: LabelingConfig::default(),
+        }
+    }
+}
+
+/// Market data a prediction is later judged against
+#[derive(Debug, Clone)]
+pub enum Observation {
+    /// One bar of a `symbol:exchange` series
+    Bar(TrainingCandle),
+    /// One quote pair of a `symbol:primary:secondary` series
+    Spread(SpreadSample),
+}
+
+impl Observation {
+    pub fn bar(data: &MarketDataInput) -> Self {
+        Observation::Bar(TrainingCandle {
+            timestamp: data.timestamp,
+            open: data.price,
+            high: data.high,
+            low: data.low,
+            close: data.price,
+            volume: data.volume,
+            bid: Some(data.bid),
+            ask: Some(data.ask),
+        })
+    }
+
+    pub fn spread(primary: &MarketDataInput, secondary: &MarketDataInput) -> Self {
+        let quote = |data: &MarketDataInput| QuoteSnapshot {
+            price: data.price,
+            bid: data.bid,
+            ask: data.ask,
+            volume: data.volume,
+        };
+        Observation::Spread(SpreadSample {
+            timestamp: primary.timestamp.max(secondary.timestamp),
+            primary: quote(primary),
+            secondary: quote(secondary),
+        })
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            Observation::Bar(bar) => bar.timestamp,
+            Observation::Spread(sample) => sample.timestamp,
+        }
+    }
+}
+
+/// A served prediction and the inputs it was made from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoggedPrediction {
+    /// Assigned by the monitor
+    pub id: u64,
+    pub model_id: String,
+    pub task: PredictionTask,
+    /// Input series key, e.g. `BTC-USD:coinbase`
+    pub series: String,
+    pub symbol: String,
+    pub exchange: Option<String>,
+    pub features: Vec<f64>,
+    /// Raw model outputs, in [`PredictionTask::output_names`] order
+    pub outputs: Vec<f64>,
+    pub inference_time_ms: f64,
+    pub predicted_at: DateTime<Utc>,
+}
+
+impl LoggedPrediction {
+    pub fn new(
+        model_id: impl Into<String>,
+        task: PredictionTask,
+        series: impl Into<String>,
+        features: Vec<f64>,
+        outputs: Vec<f64>,
+    ) -> Self {
+        let series = series.into();
+        let mut parts = series.split(':');
+        let symbol = parts.next().unwrap_or_default().to_string();
+        let exchange = parts.next().map(str::to_string);
+        Self {
+            id: 0,
+            model_id: model_id.into(),
+            task,
+            series,
+            symbol,
+            exchange,
+            features,
+            outputs,
+            inference_time_ms: 0.0,
+            predicted_at: Utc::now(),
+        }
+    }
+}
+
+/// A logged prediction joined to what actually happened
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedPrediction {
+    pub prediction: LoggedPrediction,
+    /// Realized targets, in the same layout as the outputs
+    pub realized: Vec<f64>,
+    pub resolved_at: DateTime<Utc>,
+}
+
+/// Durable log of predictions and their outcomes
+#[async_trait]
+pub trait PredictionSink: Send + Sync {
+    async fn record_prediction(&self, prediction: &LoggedPrediction) -> NeuralResult<()>;
+
+    async fn record_outcome(&self, outcome: &ResolvedPrediction) -> NeuralResult<()>;
+}
+
+/// Rolling error metrics, per output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorMetrics {
+    pub samples: usize,
+    pub mae: Vec<f64>,
+    pub rmse: Vec<f64>,
+    /// Mean of `predicted - realized`
+    pub bias: Vec<f64>,
+}
+
+/// One reliability bin of predicted probabilities
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+    pub mean_predicted: f64,
+    pub observed_rate: f64,
+}
+
+/// Calibration of the arbitrage probability output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Calibration {
+    pub samples: usize,
+    pub brier_score: f64,
+    pub expected_calibration_error: f64,
+    pub bins: Vec<CalibrationBin>,
+}
+
+/// Input drift against the reference distribution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureDrift {
+    pub samples: usize,
+    /// Population stability index per input, in feature order
+    pub psi: Vec<f64>,
+    pub max_psi: f64,
+    pub worst_feature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HealthStatus {
+    /// Not enough resolved predictions or inputs to judge yet
+    Warming,
+    Healthy,
+    Degraded {
+        reasons: Vec<String>,
+    },
+}
+
+/// Current view of one model's online quality
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelHealth {
+    pub model_id: String,
+    pub task: PredictionTask,
+    pub logged: u64,
+    pub resolved: u64,
+    pub pending: usize,
+    pub errors: Option<ErrorMetrics>,
+    pub calibration: Option<Calibration>,
+    pub drift: Option<FeatureDrift>,
+    pub status: HealthStatus,
+}
+
+impl ModelHealth {
+    pub fn is_degraded(&self) -> bool {
+        matches!(self.status, HealthStatus::Degraded { .. })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModelAlertKind {
+    Degraded,
+    Recovered,
+}
+
+/// Raised when a model's health changes between healthy and degraded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelAlert {
+    pub model_id: String,
+    pub task: PredictionTask,
+    pub kind: ModelAlertKind,
+    pub reasons: Vec<String>,
+    pub at: DateTime<Utc>,
+}
+
+impl ModelAlert {
+    /// One-line description for operators
+    pub fn message(&self) -> String {
+        match self.kind {
+            ModelAlertKind::Degraded => format!(
+                "{:?} model {} degraded: {}",
+                self.task,
+                self.model_id,
+                self.reasons.join("; ")
+            ),
+            ModelAlertKind::Recovered => {
+                format!("{:?} model {} recovered", self.task, self.model_id)
+            }
+        }
+    }
+}
+
+struct PendingPrediction {
+    /// Absolute index of the observation the prediction was made at
+    index: u64,
+    prediction: LoggedPrediction,
+}
+
+#[derive(Default)]
+struct Series {
+    /// Absolute index of `observations[0]`
+    start: u64,
+    observations: VecDeque<Observation>,
+    pending: VecDeque<PendingPrediction>,
+}
+
+impl Series {
+    fn latest_index(&self) -> Option<u64> {
+        (!self.observations.is_empty()).then(|| self.start + self.observations.len() as u64 - 1)
+    }
+
+    fn push(&mut self, observation: Observation) {
+        match self.observations.back() {
+            Some(last) if last.timestamp() == observation.timestamp() => {
+                *self.observations.back_mut().expect("non-empty") = observation;
+            }
+            Some(last) if last.timestamp() > observation.timestamp() => {}
+            _ => self.observations.push_back(observation),
+        }
+    }
+
+    /// Drop observations no pending prediction needs, keeping the latest
+    fn prune(&mut self) {
+        let keep_from = self
+            .pending
+            .iter()
+            .map(|pending| pending.index)
+            .min()
+            .or(self.latest_index())
+            .unwrap_or(self.start);
+        while self.start < keep_from && !self.observations.is_empty() {
+            self.observations.pop_front();
+            self.start += 1;
+        }
+    }
+
+    fn realized(&mut self, index: u64, labeling: &LabelingConfig) -> Option<Vec<f64>> {
+        let t = index.checked_sub(self.start)? as usize;
+        let observations = self.observations.make_contiguous();
+        match observations.get(t)? {
+            Observation::Bar(_) => {
+                let bars: Vec<TrainingCandle> = observations[t..]
+                    .iter()
+                    .map_while(|o| match o {
+                        Observation::Bar(bar) => Some(bar.clone()),
+                        Observation::Spread(_) => None,
+                    })
+                    .collect();
+                forward_volatility_targets(&bars, 0, &labeling.volatility_horizons)
+            }
+            Observation::Spread(_) => {
+                let samples: Vec<SpreadSample> = observations[t..]
+                    .iter()
+                    .map_while(|o| match o {
+                        Observation::Spread(sample) => Some(sample.clone()),
+                        Observation::Bar(_) => None,
+                    })
+                    .collect();
+                forward_arbitrage_targets(&samples, 0, labeling)
+            }
+        }
+    }
+}
+
+struct ModelState {
+    task: PredictionTask,
+    logged: u64,
+    resolved_total: u64,
+    pending: usize,
+    /// `(outputs, realized)` pairs
+    resolved: VecDeque<(Vec<f64>, Vec<f64>)>,
+    reference: Vec<Vec<f64>>,
+    /// Reference supplied explicitly rather than captured from live inputs
+    reference_fixed: bool,
+    recent: VecDeque<Vec<f64>>,
+    since_evaluation: usize,
+    degraded: bool,
+}
+
+impl ModelState {
+    fn new(task: PredictionTask) -> Self {
+        Self {
+            task,
+            logged: 0,
+            resolved_total: 0,
+            pending: 0,
+            resolved: VecDeque::new(),
+            reference: Vec::new(),
+            reference_fixed: false,
+            recent: VecDeque::new(),
+            since_evaluation: 0,
+            degraded: false,
+        }
+    }
+}
+
+#[derive(Default)]
+struct MonitorState {
+    series: HashMap<String, Series>,
+    models: HashMap<String, ModelState>,
+}
+
+/// Joins served predictions to realized outcomes and tracks model health
+pub struct PredictionMonitor {
+    config: MonitoringConfig,
+    state: Mutex<MonitorState>,
+    alerts: broadcast::Sender<ModelAlert>,
+    next_id: AtomicU64,
+}
+
+impl Default for PredictionMonitor {
+    fn default() -> Self {
+        Self::new(MonitoringConfig::default())
+    }
+}
+
+impl PredictionMonitor {
+    pub fn new(config: MonitoringConfig) -> Self {
+        let (alerts, _) = broadcast::channel(ALERT_CAPACITY);
+        Self {
+            config,
+            state: Mutex::new(MonitorState::default()),
+            alerts,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn config(&self) -> &MonitoringConfig {
+        &self.config
+    }
+
+    /// Degradation and recovery alerts
+    pub fn subscribe(&self) -> broadcast::Receiver<ModelAlert> {
+        self.alerts.subscribe()
+    }
+
+    /// Use `rows` (e.g. the training inputs) as `model_id`'s drift reference
+    pub fn set_drift_reference(&self, model_id: &str, task: PredictionTask, rows: Vec<Vec<f64>>) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let model = state
+            .models
+            .entry(model_id.to_string())
+            .or_insert_with(|| ModelState::new(task));
+        model.reference = rows;
+        model.reference_fixed = true;
+    }
+
+    /// Add market data to `series`, returning the predictions it resolves
+    pub fn observe(&self, series: &str, observation: Observation) -> Vec<ResolvedPrediction> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let MonitorState {
+            series: streams,
+            models,
+        } = &mut *state;
+
+        let stream = streams.entry(series.to_string()).or_default();
+        stream.push(observation);
+
+        let mut resolved = Vec::new();
+        let mut still_pending = VecDeque::new();
+        while let Some(pending) = stream.pending.pop_front() {
+            match stream.realized(pending.index, &self.config.labeling) {
+                Some(realized) => resolved.push(ResolvedPrediction {
+                    prediction: pending.prediction,
+                    realized,
+                    resolved_at: Utc::now(),
+                }),
+                None => still_pending.push_back(pending),
+            }
+        }
+        stream.pending = still_pending;
+        stream.prune();
+
+        let mut touched = Vec::new();
+        for outcome in &resolved {
+            let Some(model) = models.get_mut(&outcome.prediction.model_id) else {
+                continue;
+            };
+            model.pending = model.pending.saturating_sub(1);
+            model.resolved_total += 1;
+            model
+                .resolved
+                .push_back((outcome.prediction.outputs.clone(), outcome.realized.clone()));
+            while model.resolved.len() > self.config.window {
+                model.resolved.pop_front();
+            }
+            if !touched.contains(&outcome.prediction.model_id) {
+                touched.push(outcome.prediction.model_id.clone());
+            }
+        }
+        for model_id in touched {
+            self.evaluate(&model_id, models);
+        }
+
+        resolved
+    }
+
+    /// Log a served prediction, attaching it to the latest observation of its series
+    pub fn record(&self, mut prediction: LoggedPrediction) -> LoggedPrediction {
+        prediction.id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let MonitorState {
+            series: streams,
+            models,
+        } = &mut *state;
+        let model = models
+            .entry(prediction.model_id.clone())
+            .or_insert_with(|| ModelState::new(prediction.task));
+
+        model.logged += 1;
+        if !model.reference_fixed && model.reference.len() < self.config.reference_size {
+            model.reference.push(prediction.features.clone());
+        }
+        model.recent.push_back(prediction.features.clone());
+        while model.recent.len() > self.config.window {
+            model.recent.pop_front();
+        }
+
+        let resolvable = matches!(
+            prediction.task,
+            PredictionTask::Volatility | PredictionTask::Arbitrage
+        );
+        if let Some(stream) = streams.get_mut(&prediction.series).filter(|_| resolvable) {
+            if let Some(index) = stream.latest_index() {
+                stream.pending.push_back(PendingPrediction {
+                    index,
+                    prediction: prediction.clone(),
+                });
+                model.pending += 1;
+                if stream.pending.len() > self.config.max_pending_per_series {
+                    if let Some(dropped) = stream.pending.pop_front() {
+                        if let Some(owner) = models.get_mut(&dropped.prediction.model_id) {
+                            owner.pending = owner.pending.saturating_sub(1);
+                        }
+                    }
+                }
+            }
+        }
+
+        let model = models
+            .get_mut(&prediction.model_id)
+            .expect("model state inserted above");
+        model.since_evaluation += 1;
+        if model.since_evaluation >= self.config.evaluation_interval.max(1) {
+            self.evaluate(&prediction.model_id, models);
+        }
+
+        prediction
+    }
+
+    pub fn health(&self, model_id: &str) -> Option<ModelHealth> {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state
+            .models
+            .get(model_id)
+            .map(|model| self.assess(model_id, model))
+    }
+
+    /// Health of every monitored model, ordered by id
+    pub fn health_report(&self) -> Vec<ModelHealth> {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut report: Vec<ModelHealth> = state
+            .models
+            .iter()
+            .map(|(id, model)| self.assess(id, model))
+            .collect();
+        report.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+        report
+    }
+
+    /// Whether `model_id` was last judged degraded
+    pub fn is_degraded(&self, model_id: &str) -> bool {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .models
+            .get(model_id)
+            .is_some_and(|model| model.degraded)
+    }
+
+    /// Re-assess a model and broadcast a change between healthy and degraded
+    fn evaluate(&self, model_id: &str, models: &mut HashMap<String, ModelState>) {
+        let Some(model) = models.get_mut(model_id) else {
+            return;
+        };
+        model.since_evaluation = 0;
+        let health = self.assess(model_id, model);
+
+        let alert = match (&health.status, model.degraded) {
+            (HealthStatus::Degraded { reasons }, false) => {
+                model.degraded = true;
+                warn!("📉 Model {} degraded: {}", model_id, reasons.join("; "));
+                Some((ModelAlertKind::Degraded, reasons.clone()))
+            }
+            (HealthStatus::Healthy, true) => {
+                model.degraded = false;
+                info!("📈 Model {} recovered", model_id);
+                Some((ModelAlertKind::Recovered, Vec::new()))
+            }
+            _ => None,
+        };
+
+        if let Some((kind, reasons)) = alert {
+            // No subscribers is fine; health stays queryable
+            let _ = self.alerts.send(ModelAlert {
+                model_id: model_id.to_string(),
+                task: model.task,
+                kind,
+                reasons,
+                at: Utc::now(),
+            });
+        }
+    }
+
+    fn assess(&self, model_id: &str, model: &ModelState) -> ModelHealth {
+        let config = &self.config;
+        let enough_resolved = model.resolved.len() >= config.min_samples.max(1);
+
+        let errors = enough_resolved.then(|| error_metrics(&model.resolved));
+        let calibration = (enough_resolved && model.task == PredictionTask::Arbitrage)
+            .then(|| calibration(&model.resolved, 2));
+        let reference_ready =
+            model.reference_fixed || model.reference.len() >= config.reference_size.max(1);
+        let drift = (reference_ready && model.recent.len() >= config.min_samples.max(1))
+            .then(|| {
+                feature_drift(
+                    model.task,
+                    &model.reference,
+                    &model.recent,
+                    config.drift_bins,
+                )
+            })
+            .flatten();
+
+        let mut reasons = Vec::new();
+        if let Some(errors) = &errors {
+            match model.task {
+                PredictionTask::Volatility => {
+                    let realized = model
+                        .resolved
+                        .iter()
+                        .map(|(_, realized)| realized[1].abs())
+                        .sum::<f64>()
+                        / model.resolved.len() as f64;
+                    if realized > 0.0 && errors.mae[1] / realized > config.max_normalized_error {
+                        reasons.push(format!(
+                            "5m volatility error is {:.0}% of realized volatility",
+                            errors.mae[1] / realized * 100.0
+                        ));
+                    }
+                }
+                PredictionTask::Arbitrage => {
+                    let bias_bps = errors.bias[3] * 10_000.0;
+                    if bias_bps > config.max_profit_bias_bps {
+                        reasons.push(format!(
+                            "expected profit over-predicted by {:.1} bps",
+                            bias_bps
+                        ));
+                    }
+                }
+                PredictionTask::Risk => {}
+            }
+        }
+        if let Some(calibration) = &calibration {
+            if calibration.expected_calibration_error > config.max_calibration_error {
+                reasons.push(format!(
+                    "arbitrage probability miscalibrated (ECE {:.3})",
+                    calibration.expected_calibration_error
+                ));
+            }
+        }
+        if let Some(drift) = &drift {
+            if drift.max_psi > config.max_feature_psi {
+                reasons.push(format!(
+                    "input drift on {} (PSI {:.2})",
+                    drift.worst_feature, drift.max_psi
+                ));
+            }
+        }
+
+        let status = if !reasons.is_empty() {
+            HealthStatus::Degraded { reasons }
+        } else if errors.is_some() || drift.is_some() {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Warming
+        };
+
+        ModelHealth {
+            model_id: model_id.to_string(),
+            task: model.task,
+            logged: model.logged,
+            resolved: model.resolved_total,
+            pending: model.pending,
+            errors,
+            calibration,
+            drift,
+            status,
+        }
+    }
+}
+
+fn error_metrics(resolved: &VecDeque<(Vec<f64>, Vec<f64>)>) -> ErrorMetrics {
+    let width = resolved
+        .iter()
+        .map(|(outputs, realized)| outputs.len().min(realized.len()))
+        .min()
+        .unwrap_or(0);
+    let n = resolved.len().max(1) as f64;
+    let mut abs = vec![0.0; width];
+    let mut squared = vec![0.0; width];
+    let mut bias = vec![0.0; width];
+
+    for (outputs, realized) in resolved {
+        for i in 0..width {
+            let error = outputs[i] - realized[i];
+            abs[i] += error.abs();
+            squared[i] += error * error;
+            bias[i] += error;
+        }
+    }
+
+    ErrorMetrics {
+        samples: resolved.len(),
+        mae: abs.iter().map(|v| v / n).collect(),
+        rmse: squared.iter().map(|v| (v / n).sqrt()).collect(),
+        bias: bias.iter().map(|v| v / n).collect(),
+    }
+}
+
+/// Reliability of output `index` read as a probability of the binary realized target
+fn calibration(resolved: &VecDeque<(Vec<f64>, Vec<f64>)>, index: usize) -> Calibration {
+    let mut bins: Vec<(usize, f64, f64)> = vec![(0, 0.0, 0.0); CALIBRATION_BINS];
+    let mut brier = 0.0;
+
+    for (outputs, realized) in resolved {
+        let p = outputs[index].clamp(0.0, 1.0);
+        let y = if realized[index] > 0.5 { 1.0 } else { 0.0 };
+        brier += (p - y) * (p - y);
+        let bin = ((p * CALIBRATION_BINS as f64) as usize).min(CALIBRATION_BINS - 1);
+        bins[bin].0 += 1;
+        bins[bin].1 += p;
+        bins[bin].2 += y;
+    }
+
+    let n = resolved.len().max(1) as f64;
+    let width = 1.0 / CALIBRATION_BINS as f64;
+    let mut ece = 0.0;
+    let bins = bins
+        .into_iter()
+        .enumerate()
+        .filter(|(_, (count, _, _))| *count > 0)
+        .map(|(i, (count, predicted, observed))| {
+            let mean_predicted = predicted / count as f64;
+            let observed_rate = observed / count as f64;
+            ece += count as f64 / n * (mean_predicted - observed_rate).abs();
+            CalibrationBin {
+                lower: i as f64 * width,
+                upper: (i + 1) as f64 * width,
+                count,
+                mean_predicted,
+                observed_rate,
+            }
+        })
+        .collect();
+
+    Calibration {
+        samples: resolved.len(),
+        brier_score: brier / n,
+        expected_calibration_error: ece,
+        bins,
+    }
+}
+
+fn feature_drift(
+    task: PredictionTask,
+    reference: &[Vec<f64>],
+    recent: &VecDeque<Vec<f64>>,
+    bins: usize,
+) -> Option<FeatureDrift> {
+    let width = reference.first()?.len();
+    let psi: Vec<f64> = (0..width)
+        .map(|i| {
+            let expected: Vec<f64> = reference
+                .iter()
+                .filter_map(|row| row.get(i).copied())
+                .collect();
+            let actual: Vec<f64> = recent
+                .iter()
+                .filter_map(|row| row.get(i).copied())
+                .collect();
+            population_stability(&expected, &actual, bins)
+        })
+        .collect();
+
+    let (worst, max_psi) =
+        psi.iter().copied().enumerate().fold(
+            (0, 0.0),
+            |best, (i, v)| if v > best.1 { (i, v) } else { best },
+        );
+    let worst_feature = task
+        .feature_names()
+        .get(worst)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("feature_{}", worst));
+
+    Some(FeatureDrift {
+        samples: recent.len(),
+        psi,
+        max_psi,
+        worst_feature,
+    })
+}
+
+/// PSI of `actual` against `expected`, over quantile bins of `expected`
+fn population_stability(expected: &[f64], actual: &[f64], bins: usize) -> f64 {
+    if expected.is_empty() || actual.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = expected.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mut edges: Vec<f64> = (1..bins.max(2))
+        .map(|k| sorted[(k * sorted.len() / bins.max(2)).min(sorted.len() - 1)])
+        .collect();
+    edges.dedup();
+
+    let shares = |values: &[f64]| {
+        let mut counts = vec![0usize; edges.len() + 1];
+        for value in values {
+            counts[edges.partition_point(|edge| edge <= value)] += 1;
+        }
+        counts
+            .into_iter()
+            .map(|count| (count as f64 / values.len() as f64).max(PSI_FLOOR))
+            .collect::<Vec<f64>>()
+    };
+
+    shares(expected)
+        .into_iter()
+        .zip(shares(actual))
+        .map(|(e, a)| (a - e) * (a / e).ln())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn config() -> MonitoringConfig {
+        MonitoringConfig {
+            window: 100,
+            min_samples: 20,
+            reference_size: 20,
+            evaluation_interval: 5,
+            ..MonitoringConfig::default()
+        }
+    }
+
+    fn market(t: i64, price: f64) -> MarketDataInput {
+        MarketDataInput {
+            price,
+            high: price * 1.001,
+            low: price * 0.999,
+            volume: 1_000.0,
+            avg_volume: 1_000.0,
+            bid: price - 0.01,
+            ask: price + 0.01,
+            timestamp: DateTime::from_timestamp(1_700_000_000, 0).unwrap() + Duration::minutes(t),
+        }
+    }
+
+    /// Alternating ±1% moves, so realized volatility is ≈ 0.01 at every horizon
+    fn price(t: i64) -> f64 {
+        if t % 2 == 0 {
+            100.0
+        } else {
+            101.0
+        }
+    }
+
+    fn run_volatility(monitor: &PredictionMonitor, predicted: f64, steps: i64) {
+        for t in 0..steps {
+            let data = market(t, price(t));
+            monitor.observe("BTC-USD:coinbase", Observation::bar(&data));
+            monitor.record(LoggedPrediction::new(
+                "vol",
+                PredictionTask::Volatility,
+                "BTC-USD:coinbase",
+                vec![data.price; 7],
+                vec![predicted, predicted, predicted, 1.0],
+            ));
+        }
+    }
+
+    #[test]
+    fn test_predictions_resolve_after_the_label_horizon() {
+        let monitor = PredictionMonitor::new(config());
+        let data = market(0, 100.0);
+        monitor.observe("BTC-USD:coinbase", Observation::bar(&data));
+        let logged = monitor.record(LoggedPrediction::new(
+            "vol",
+            PredictionTask::Volatility,
+            "BTC-USD:coinbase",
+            vec![100.0; 7],
+            vec![0.01, 0.01, 0.01, 1.0],
+        ));
+        assert_eq!(logged.symbol, "BTC-USD");
+        assert_eq!(logged.exchange.as_deref(), Some("coinbase"));
+
+        // The longest volatility horizon is 15 bars
+        for t in 1..15 {
+            let resolved =
+                monitor.observe("BTC-USD:coinbase", Observation::bar(&market(t, price(t))));
+            assert!(resolved.is_empty(), "bar {}", t);
+        }
+        let resolved =
+            monitor.observe("BTC-USD:coinbase", Observation::bar(&market(15, price(15))));
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].prediction.id, logged.id);
+        assert!((resolved[0].realized[1] - (1.01f64).ln()).abs() < 1e-3);
+
+        // Re-sending the same bar does not advance the series
+        monitor.record(LoggedPrediction::new(
+            "vol",
+            PredictionTask::Volatility,
+            "BTC-USD:coinbase",
+            vec![101.0; 7],
+            vec![0.01, 0.01, 0.01, 1.0],
+        ));
+        for _ in 0..20 {
+            let resolved =
+                monitor.observe("BTC-USD:coinbase", Observation::bar(&market(15, price(15))));
+            assert!(resolved.is_empty());
+        }
+        let health = monitor.health("vol").unwrap();
+        assert_eq!((health.resolved, health.pending), (1, 1));
+        assert_eq!(health.status, HealthStatus::Warming);
+    }
+
+    #[test]
+    fn test_accurate_model_stays_healthy_and_bad_model_alerts() {
+        let monitor = PredictionMonitor::new(config());
+        run_volatility(&monitor, 0.01, 60);
+        let health = monitor.health("vol").unwrap();
+        assert_eq!(health.status, HealthStatus::Healthy, "{:?}", health);
+        assert!(health.errors.unwrap().mae[1] < 0.001);
+
+        let monitor = PredictionMonitor::new(config());
+        let mut alerts = monitor.subscribe();
+        run_volatility(&monitor, 0.05, 60);
+        assert!(monitor.is_degraded("vol"));
+        let alert = alerts.try_recv().unwrap();
+        assert_eq!(alert.kind, ModelAlertKind::Degraded);
+        assert!(
+            alert.message().contains("5m volatility error"),
+            "{}",
+            alert.message()
+        );
+    }
+
+    #[test]
+    fn test_feature_drift_is_flagged_without_outcomes() {
+        let monitor = PredictionMonitor::new(config());
+        let row = |size: f64| vec![size, 0.01, 0.001, 0.01, 0.25];
+        monitor.set_drift_reference(
+            "risk",
+            PredictionTask::Risk,
+            (0..50).map(|i| row(1_000.0 + i as f64)).collect(),
+        );
+
+        for i in 0..20 {
+            monitor.record(LoggedPrediction::new(
+                "risk",
+                PredictionTask::Risk,
+                "BTC-USD:coinbase",
+                row(1_000.0 + i as f64 * 2.5),
+                vec![0.1; 5],
+            ));
+        }
+        assert!(!monitor.is_degraded("risk"));
+
+        for _ in 0..40 {
+            monitor.record(LoggedPrediction::new(
+                "risk",
+                PredictionTask::Risk,
+                "BTC-USD:coinbase",
+                row(50_000.0),
+                vec![0.1; 5],
+            ));
+        }
+        let health = monitor.health("risk").unwrap();
+        assert!(health.is_degraded());
+        assert_eq!(health.drift.unwrap().worst_feature, "position_size");
+        assert!(health.errors.is_none());
+    }
+
+    #[test]
+    fn test_calibration_of_arbitrage_probability() {
+        let mut resolved = VecDeque::new();
+        // Always predicts 0.9 but only half the opportunities materialise
+        for i in 0..100 {
+            let realized = if i % 2 == 0 { 1.0 } else { 0.0 };
+            resolved.push_back((
+                vec![0.0, 0.0, 0.9, 0.0, 1.0],
+                vec![0.0, 0.0, realized, 0.0, 1.0],
+            ));
+        }
+        let calibration = calibration(&resolved, 2);
+        assert_eq!(calibration.bins.len(), 1);
+        assert!((calibration.expected_calibration_error - 0.4).abs() < 1e-9);
+        assert!((calibration.brier_score - 0.41).abs() < 1e-9);
+    }
+}
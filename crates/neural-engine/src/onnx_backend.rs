@@ -0,0 +1,565 @@
+//! ONNX Backend for Neural Engine
+//!
+//! Loads ONNX graphs exported from Python training and runs them on CPU with
+//! tract, a pure-Rust inference runtime. Graphs take `[batch, features]` rows,
+//! or `[batch, steps, features]` windows for sequence models (LSTM,
+//! Transformer, N-BEATS, N-HiTS), and produce `[batch, outputs]` in the
+//! task's output order. A JSON [`OnnxModelSpec`] next to the graph records
+//! the task, the input columns it was exported with and any scaling fitted
+//! during training.
+
+use crate::model_registry::{ModelLoader, ModelRecord};
+use crate::predictor::{PredictionInput, PredictionTask, Predictor, PredictorInfo};
+use crate::training::MinMaxScaler;
+use crate::{ModelType, NeuralBackend, NeuralError, NeuralResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+use tract_onnx::prelude::{
+    tvec, DatumType, Framework, InferenceModelExt, Tensor, TypedFact, TypedModel,
+    TypedRunnableModel,
+};
+use tract_onnx::tract_core::internal::DimLike;
+
+/// Everything needed to serve an exported graph besides the graph itself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnnxModelSpec {
+    pub id: String,
+    pub task: PredictionTask,
+    #[serde(default)]
+    pub model_type: Option<ModelType>,
+    #[serde(default = "default_version")]
+    pub version: String,
+    /// Input columns in export order; empty means the task's fixed layout
+    #[serde(default)]
+    pub feature_columns: Vec<String>,
+    /// Steps per window for sequence graphs whose step dimension is dynamic
+    #[serde(default)]
+    pub sequence_length: Option<usize>,
+    /// Input scaling fitted on the training set, applied to every step
+    #[serde(default)]
+    pub input_scaler: Option<MinMaxScaler>,
+    /// Output scaling fitted on the training set, inverted after inference
+    #[serde(default)]
+    pub output_scaler: Option<MinMaxScaler>,
+    #[serde(default)]
+    pub accuracy: Option<f64>,
+    #[serde(default)]
+    pub trained_at: Option<String>,
+}
+
+fn default_version() -> String {
+    "1.0.0".to_string()
+}
+
+impl OnnxModelSpec {
+    /// Spec for a graph over the task's fixed feature layout
+    pub fn new(id: impl Into<String>, task: PredictionTask) -> Self {
+        Self {
+            id: id.into(),
+            task,
+            model_type: None,
+            version: default_version(),
+            feature_columns: Vec::new(),
+            sequence_length: None,
+            input_scaler: None,
+            output_scaler: None,
+            accuracy: None,
+            trained_at: None,
+        }
+    }
+
+    /// Spec location for a graph file: `<model>.spec.json`
+    pub fn path_for(model_path: impl AsRef<Path>) -> PathBuf {
+        let mut path = model_path.as_ref().as_os_str().to_owned();
+        path.push(".spec.json");
+        PathBuf::from(path)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> NeuralResult<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            NeuralError::ModelNotFound(format!("ONNX model spec {:?}: {}", path, e))
+        })?;
+        serde_json::from_str(&contents).map_err(|e| {
+            NeuralError::ModelLoadingFailed(format!("Invalid ONNX model spec {:?}: {}", path, e))
+        })
+    }
+
+    /// Input columns the graph expects, in order
+    pub fn columns(&self) -> Vec<String> {
+        if self.feature_columns.is_empty() {
+            self.task
+                .feature_names()
+                .iter()
+                .map(|name| name.to_string())
+                .collect()
+        } else {
+            self.feature_columns.clone()
+        }
+    }
+
+    /// Reject graphs exported with different inputs than the caller will provide
+    pub fn check_columns<S: AsRef<str>>(&self, expected: &[S]) -> NeuralResult<()> {
+        let columns = self.columns();
+        if columns.len() != expected.len()
+            || columns.iter().zip(expected).any(|(a, b)| a != b.as_ref())
+        {
+            return Err(NeuralError::InvalidInput(format!(
+                "ONNX model {} was exported with inputs [{}], expected [{}]",
+                self.id,
+                columns.join(", "),
+                expected
+                    .iter()
+                    .map(|s| s.as_ref())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// How samples are laid out in the graph's input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputLayout {
+    /// `[batch, features]`
+    Rows,
+    /// `[batch, steps, features]`
+    Windows { steps: usize },
+}
+
+/// Check graph shapes (`None` for dynamic dimensions) against the spec
+///
+/// Returns the input layout and the batch size when the graph was exported
+/// with a fixed one.
+fn check_shapes(
+    spec: &OnnxModelSpec,
+    input: &[Option<usize>],
+    output: &[Option<usize>],
+) -> NeuralResult<(InputLayout, Option<usize>)> {
+    let features = spec.columns().len();
+    let outputs = spec.task.output_size();
+    let mismatch = |what: &str, shape: &[Option<usize>], expected: String| {
+        let shape: Vec<String> = shape
+            .iter()
+            .map(|d| d.map_or_else(|| "?".to_string(), |d| d.to_string()))
+            .collect();
+        NeuralError::InvalidInput(format!(
+            "ONNX model {} {} shape [{}], expected {}",
+            spec.id,
+            what,
+            shape.join(", "),
+            expected
+        ))
+    };
+
+    let layout = match input {
+        [_, width] if *width == Some(features) => InputLayout::Rows,
+        [_, steps, width] if *width == Some(features) => {
+            let steps = match (*steps, spec.sequence_length) {
+                (Some(graph), Some(configured)) if graph != configured => {
+                    return Err(mismatch(
+                        "input",
+                        input,
+                        format!("{} steps as configured", configured),
+                    ))
+                }
+                (Some(steps), _) | (None, Some(steps)) => steps,
+                (None, None) => {
+                    return Err(NeuralError::InvalidInput(format!(
+                        "ONNX model {} takes windows of dynamic length; set sequence_length",
+                        spec.id
+                    )))
+                }
+            };
+            InputLayout::Windows {
+                steps: steps.max(1),
+            }
+        }
+        _ => {
+            return Err(mismatch(
+                "input",
+                input,
+                format!("[batch, {0}] or [batch, steps, {0}]", features),
+            ))
+        }
+    };
+
+    if !matches!(output, [_, width] if *width == Some(outputs)) {
+        return Err(mismatch("output", output, format!("[batch, {}]", outputs)));
+    }
+
+    let batch = input[0].filter(|batch| *batch > 0);
+    if batch.is_some() && output[0].is_some() && output[0] != batch {
+        return Err(mismatch(
+            "output",
+            output,
+            "the input batch size".to_string(),
+        ));
+    }
+
+    Ok((layout, batch))
+}
+
+fn dims(fact: &TypedFact) -> Vec<Option<usize>> {
+    fact.shape.iter().map(|dim| dim.to_usize().ok()).collect()
+}
+
+/// ONNX model wrapper for CPU inference
+///
+/// Mirrors `FannModel`: one sample in, task outputs out, with scaling
+/// applied on either side.
+pub struct OnnxModel {
+    plan: TypedRunnableModel<TypedModel>,
+    input_type: DatumType,
+    layout: InputLayout,
+    /// Batch size the graph was exported with, when not dynamic
+    fixed_batch: Option<usize>,
+    pub spec: OnnxModelSpec,
+    pub file_path: Option<String>,
+}
+
+impl OnnxModel {
+    /// Load and optimize the graph at `path`, validating its shapes against `spec`
+    pub fn load_from_file(path: impl AsRef<Path>, spec: OnnxModelSpec) -> NeuralResult<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(NeuralError::ModelNotFound(format!(
+                "Model file not found: {:?}",
+                path
+            )));
+        }
+
+        info!("📂 Loading ONNX model from: {:?}", path);
+
+        let model = tract_onnx::onnx()
+            .model_for_path(path)
+            .and_then(|model| model.into_optimized())
+            .map_err(|e| {
+                NeuralError::ModelLoadingFailed(format!("Invalid ONNX model {:?}: {}", path, e))
+            })?;
+        if model.inputs.len() != 1 || model.outputs.len() != 1 {
+            return Err(NeuralError::InvalidInput(format!(
+                "ONNX model {} has {} inputs and {} outputs, expected one of each",
+                spec.id,
+                model.inputs.len(),
+                model.outputs.len()
+            )));
+        }
+
+        let input_fact = model.input_fact(0).map_err(load_error)?.clone();
+        let output_fact = model.output_fact(0).map_err(load_error)?.clone();
+        let (layout, fixed_batch) = check_shapes(&spec, &dims(&input_fact), &dims(&output_fact))?;
+        let plan = model.into_runnable().map_err(load_error)?;
+
+        info!(
+            "✅ ONNX model '{}' ready: {:?} for {:?}",
+            spec.id, layout, spec.task
+        );
+
+        Ok(Self {
+            plan,
+            input_type: input_fact.datum_type,
+            layout,
+            fixed_batch,
+            spec,
+            file_path: Some(path.to_string_lossy().into_owned()),
+        })
+    }
+
+    /// Load `<path>` with the spec stored next to it (see [`OnnxModelSpec::path_for`])
+    pub fn load_with_spec(path: impl AsRef<Path>) -> NeuralResult<Self> {
+        let spec = OnnxModelSpec::load(OnnxModelSpec::path_for(&path))?;
+        Self::load_from_file(path, spec)
+    }
+
+    /// Run inference on one sample
+    ///
+    /// A sample is one feature row, or `steps` rows concatenated oldest first
+    /// for sequence models.
+    pub fn run(&self, input: &[f64]) -> NeuralResult<Vec<f64>> {
+        let mut outputs = self.run_batch(std::slice::from_ref(&input.to_vec()))?;
+        outputs.pop().ok_or_else(|| {
+            NeuralError::InferenceFailed("Network returned empty output".to_string())
+        })
+    }
+
+    /// Run inference on several samples at once
+    pub fn run_batch(&self, inputs: &[Vec<f64>]) -> NeuralResult<Vec<Vec<f64>>> {
+        let sample_size = self.sample_size();
+        if let Some(bad) = inputs.iter().find(|input| input.len() != sample_size) {
+            return Err(NeuralError::InvalidInput(format!(
+                "Expected {} inputs, got {}",
+                sample_size,
+                bad.len()
+            )));
+        }
+        if inputs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        debug!(
+            "🔮 Running ONNX inference on {} samples of {} inputs",
+            inputs.len(),
+            sample_size
+        );
+
+        let chunk = self.fixed_batch.unwrap_or(inputs.len());
+        let mut outputs = Vec::with_capacity(inputs.len());
+        for samples in inputs.chunks(chunk) {
+            outputs.extend(self.infer(samples, chunk)?);
+        }
+
+        Ok(match &self.spec.output_scaler {
+            Some(scaler) => outputs.iter().map(|row| scaler.inverse(row)).collect(),
+            None => outputs,
+        })
+    }
+
+    /// Inputs per sample: the feature count, times the window length for sequence models
+    pub fn sample_size(&self) -> usize {
+        self.input_size() * self.steps()
+    }
+
+    /// Features per step
+    pub fn input_size(&self) -> usize {
+        self.spec.columns().len()
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.spec.task.output_size()
+    }
+
+    /// Steps per sample; 1 for row models
+    pub fn steps(&self) -> usize {
+        match self.layout {
+            InputLayout::Rows => 1,
+            InputLayout::Windows { steps } => steps,
+        }
+    }
+
+    /// Forward one batch, padding to `batch` rows for fixed-batch graphs
+    fn infer(&self, samples: &[Vec<f64>], batch: usize) -> NeuralResult<Vec<Vec<f64>>> {
+        let width = self.input_size();
+        let mut data: Vec<f32> = Vec::with_capacity(batch * self.sample_size());
+        for sample in samples {
+            for row in sample.chunks(width) {
+                let row = match &self.spec.input_scaler {
+                    Some(scaler) => scaler.transform(row),
+                    None => row.to_vec(),
+                };
+                data.extend(row.iter().map(|v| *v as f32));
+            }
+        }
+        data.resize(batch * self.sample_size(), 0.0);
+
+        let shape = match self.layout {
+            InputLayout::Rows => vec![batch, width],
+            InputLayout::Windows { steps } => vec![batch, steps, width],
+        };
+        let input = Tensor::from_shape(&shape, &data)
+            .and_then(|tensor| Ok(tensor.cast_to_dt(self.input_type)?.into_owned()))
+            .map_err(inference_error)?;
+
+        let result = self
+            .plan
+            .run(tvec!(input.into()))
+            .map_err(inference_error)?;
+        let output = result[0].cast_to::<f32>().map_err(inference_error)?;
+        let values = output.as_slice::<f32>().map_err(inference_error)?;
+
+        let width = self.output_size();
+        if values.len() != batch * width {
+            return Err(NeuralError::InferenceFailed(format!(
+                "Expected {} outputs, got {}",
+                batch * width,
+                values.len()
+            )));
+        }
+
+        Ok(values
+            .chunks(width)
+            .take(samples.len())
+            .map(|row| row.iter().map(|v| f64::from(*v)).collect())
+            .collect())
+    }
+}
+
+fn load_error(e: impl std::fmt::Display) -> NeuralError {
+    NeuralError::ModelLoadingFailed(e.to_string())
+}
+
+fn inference_error(e: impl std::fmt::Display) -> NeuralError {
+    NeuralError::InferenceFailed(e.to_string())
+}
+
+/// [`Predictor`] backed by an ONNX graph
+///
+/// Sequence models keep the last `steps` feature rows per series and report
+/// no prediction until a full window has been seen.
+pub struct OnnxPredictor {
+    info: PredictorInfo,
+    model: OnnxModel,
+    windows: HashMap<String, VecDeque<Vec<f64>>>,
+}
+
+impl OnnxPredictor {
+    /// Wrap `model`, checking its inputs fit the task
+    pub fn new(model: OnnxModel) -> NeuralResult<Self> {
+        let task = model.spec.task;
+        if model.input_size() != task.input_size() {
+            return Err(NeuralError::InvalidInput(format!(
+                "Model input size mismatch: expected {}, got {}",
+                task.input_size(),
+                model.input_size()
+            )));
+        }
+
+        let mut info = PredictorInfo::new(model.spec.id.clone(), task, NeuralBackend::Onnx);
+        info.model_type = Some(model.spec.model_type.unwrap_or(match model.layout {
+            InputLayout::Rows => ModelType::MLP,
+            InputLayout::Windows { .. } => ModelType::LSTM,
+        }));
+        info.version = model.spec.version.clone();
+        info.accuracy = model.spec.accuracy;
+        info.trained_at = model.spec.trained_at.clone();
+
+        Ok(Self {
+            info,
+            model,
+            windows: HashMap::new(),
+        })
+    }
+
+    pub fn model(&self) -> &OnnxModel {
+        &self.model
+    }
+}
+
+impl Predictor for OnnxPredictor {
+    fn info(&self) -> &PredictorInfo {
+        &self.info
+    }
+
+    fn predict(&mut self, input: &PredictionInput) -> NeuralResult<Option<Vec<f64>>> {
+        let steps = self.model.steps();
+        if steps == 1 {
+            return self.model.run(&input.features).map(Some);
+        }
+
+        let window = self.windows.entry(input.key.clone()).or_default();
+        window.push_back(input.features.clone());
+        while window.len() > steps {
+            window.pop_front();
+        }
+        if window.len() < steps {
+            return Ok(None);
+        }
+        let sample: Vec<f64> = window.iter().flatten().copied().collect();
+        self.model.run(&sample).map(Some)
+    }
+}
+
+/// Loads registry versions backed by ONNX graphs
+///
+/// Uses the spec stored next to the graph when there is one, otherwise
+/// describes the graph from the record. Either way the graph's inputs must
+/// be the record's input features.
+pub struct OnnxModelLoader;
+
+impl ModelLoader for OnnxModelLoader {
+    fn load(&self, record: &ModelRecord) -> NeuralResult<Box<dyn Predictor>> {
+        if record.backend != NeuralBackend::Onnx {
+            return Err(NeuralError::BackendUnavailable(format!(
+                "{} uses the {:?} backend",
+                record.predictor_id(),
+                record.backend
+            )));
+        }
+
+        let spec_path = OnnxModelSpec::path_for(&record.model_path);
+        let mut spec = if spec_path.exists() {
+            OnnxModelSpec::load(&spec_path)?
+        } else {
+            let mut spec = OnnxModelSpec::new(record.predictor_id(), record.task);
+            spec.feature_columns = record.input_features.clone();
+            spec.model_type = record.model_type;
+            spec
+        };
+        if spec.task != record.task {
+            return Err(NeuralError::InvalidInput(format!(
+                "{} is a {:?} model but its ONNX spec says {:?}",
+                record.predictor_id(),
+                record.task,
+                spec.task
+            )));
+        }
+        spec.check_columns(&record.input_features)?;
+        spec.id = record.predictor_id();
+        spec.version = record.version.clone();
+
+        let model = OnnxModel::load_from_file(&record.model_path, spec)?;
+        Ok(Box::new(OnnxPredictor::new(model)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shapes_accept_dynamic_batch_rows_and_windows() {
+        let spec = OnnxModelSpec::new("vol", PredictionTask::Volatility);
+        assert_eq!(
+            check_shapes(&spec, &[None, Some(7)], &[None, Some(4)]).unwrap(),
+            (InputLayout::Rows, None)
+        );
+        assert_eq!(
+            check_shapes(&spec, &[Some(1), Some(30), Some(7)], &[Some(1), Some(4)]).unwrap(),
+            (InputLayout::Windows { steps: 30 }, Some(1))
+        );
+
+        // Dynamic window length needs configuring, and must agree when fixed
+        assert!(check_shapes(&spec, &[None, None, Some(7)], &[None, Some(4)]).is_err());
+        let mut windowed = spec.clone();
+        windowed.sequence_length = Some(20);
+        assert_eq!(
+            check_shapes(&windowed, &[None, None, Some(7)], &[None, Some(4)])
+                .unwrap()
+                .0,
+            InputLayout::Windows { steps: 20 }
+        );
+        assert!(check_shapes(&windowed, &[None, Some(30), Some(7)], &[None, Some(4)]).is_err());
+    }
+
+    #[test]
+    fn test_shapes_reject_layouts_that_do_not_fit_the_task() {
+        let spec = OnnxModelSpec::new("arb", PredictionTask::Arbitrage);
+        assert!(check_shapes(&spec, &[None, Some(7)], &[None, Some(5)]).is_err());
+        assert!(check_shapes(&spec, &[None, Some(9)], &[None, Some(4)]).is_err());
+        assert!(check_shapes(&spec, &[Some(9)], &[Some(5)]).is_err());
+        assert!(check_shapes(&spec, &[Some(2), Some(9)], &[Some(4), Some(5)]).is_err());
+    }
+
+    #[test]
+    fn test_spec_columns_default_to_task_layout() {
+        let mut spec = OnnxModelSpec::new("risk", PredictionTask::Risk);
+        assert!(spec
+            .check_columns(PredictionTask::Risk.feature_names())
+            .is_ok());
+
+        spec.feature_columns = vec!["position_size".to_string(); 5];
+        assert!(spec
+            .check_columns(PredictionTask::Risk.feature_names())
+            .is_err());
+
+        let parsed: OnnxModelSpec =
+            serde_json::from_str(r#"{"id": "risk_lstm", "task": "Risk", "sequence_length": 16}"#)
+                .unwrap();
+        assert_eq!(parsed.version, "1.0.0");
+        assert_eq!(parsed.sequence_length, Some(16));
+        assert_eq!(parsed.columns().len(), 5);
+    }
+}
@@ -0,0 +1,339 @@
+//! Postgres model store
+//!
+//! [`ModelStore`] over the `neural_models` and `model_shadow_comparisons`
+//! tables (migrations V002 and V005). Rows are keyed by `(name, version)`;
+//! status changes keep the legacy `is_active`/`is_production` flags in step.
+//!
+//! [`PostgresPredictionSink`] logs served predictions and their realized
+//! outcomes to `model_predictions` (migrations V002 and V006).
+
+use crate::model_registry::{ModelRecord, ModelStatus, ModelStore};
+use crate::monitoring::{LoggedPrediction, PredictionSink, ResolvedPrediction};
+use crate::predictor::{PredictionTask, ShadowComparison};
+use crate::training::EvaluationMetrics;
+use crate::{NeuralError, NeuralResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use sqlx::types::{Json, Uuid};
+use sqlx::{FromRow, PgPool};
+
+const SELECT_MODELS: &str = "SELECT name, version, task, backend, model_type, description, \
+     input_features, output_features, feature_schema_hash, evaluation_metrics, \
+     training_data_size, model_file_path, status, trained_at, deployed_at, created_at \
+     FROM neural_models";
+
+#[derive(Debug, FromRow)]
+struct ModelRow {
+    name: String,
+    version: String,
+    task: Option<String>,
+    backend: Option<String>,
+    model_type: Option<String>,
+    description: Option<String>,
+    input_features: Vec<String>,
+    output_features: Vec<String>,
+    feature_schema_hash: Option<String>,
+    evaluation_metrics: Option<Json<EvaluationMetrics>>,
+    training_data_size: i32,
+    model_file_path: String,
+    status: String,
+    trained_at: Option<DateTime<Utc>>,
+    deployed_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<ModelRow> for ModelRecord {
+    type Error = NeuralError;
+
+    fn try_from(row: ModelRow) -> NeuralResult<Self> {
+        let id = format!("{}@{}", row.name, row.version);
+        let missing = |column: &str| NeuralError::Storage(format!("{} has no {}", id, column));
+
+        let task = row
+            .task
+            .as_deref()
+            .and_then(PredictionTask::from_name)
+            .ok_or_else(|| missing("task"))?;
+        let backend = parse_variant(row.backend.as_deref().ok_or_else(|| missing("backend"))?)?;
+        let model_type = row.model_type.as_deref().map(parse_variant).transpose()?;
+        let status = ModelStatus::from_name(&row.status)
+            .ok_or_else(|| NeuralError::Storage(format!("{} has status {}", id, row.status)))?;
+
+        Ok(ModelRecord {
+            feature_schema_hash: row
+                .feature_schema_hash
+                .ok_or_else(|| missing("feature schema hash"))?,
+            name: row.name,
+            version: row.version,
+            task,
+            backend,
+            model_type,
+            description: row.description,
+            input_features: row.input_features,
+            output_features: row.output_features,
+            metrics: row.evaluation_metrics.map(|metrics| metrics.0),
+            training_samples: row.training_data_size.max(0) as usize,
+            model_path: row.model_file_path,
+            status,
+            trained_at: row.trained_at,
+            deployed_at: row.deployed_at,
+            created_at: row.created_at,
+        })
+    }
+}
+
+/// Enum variants are stored by name, e.g. `RuvFann` or `LSTM`
+fn parse_variant<T: DeserializeOwned>(name: &str) -> NeuralResult<T> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|e| NeuralError::Storage(format!("unknown value '{}': {}", name, e)))
+}
+
+fn variant_name<T: std::fmt::Debug>(value: &T) -> String {
+    format!("{:?}", value)
+}
+
+fn storage_error(e: sqlx::Error) -> NeuralError {
+    NeuralError::Storage(e.to_string())
+}
+
+/// [`ModelStore`] backed by Postgres
+#[derive(Debug, Clone)]
+pub struct PostgresModelStore {
+    pool: PgPool,
+}
+
+impl PostgresModelStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn fetch(
+        &self,
+        query: sqlx::query::QueryAs<'_, sqlx::Postgres, ModelRow, sqlx::postgres::PgArguments>,
+    ) -> NeuralResult<Vec<ModelRecord>> {
+        query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(storage_error)?
+            .into_iter()
+            .map(ModelRecord::try_from)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ModelStore for PostgresModelStore {
+    async fn insert(&self, record: &ModelRecord) -> NeuralResult<()> {
+        let architecture = serde_json::json!({
+            "backend": variant_name(&record.backend),
+            "model_type": record.model_type.as_ref().map(variant_name),
+        });
+
+        sqlx::query(
+            "INSERT INTO neural_models (name, version, task, backend, model_type, description, \
+             input_features, output_features, architecture, hyperparameters, feature_schema_hash, \
+             evaluation_metrics, training_data_size, validation_accuracy, model_file_path, status, \
+             trained_at, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, '{}'::jsonb, $10, $11, $12, $13, $14, $15, $16, $17)",
+        )
+        .bind(&record.name)
+        .bind(&record.version)
+        .bind(record.task.as_str())
+        .bind(variant_name(&record.backend))
+        .bind(record.model_type.as_ref().map(variant_name))
+        .bind(&record.description)
+        .bind(&record.input_features)
+        .bind(&record.output_features)
+        .bind(Json(architecture))
+        .bind(&record.feature_schema_hash)
+        .bind(record.metrics.map(Json))
+        .bind(i32::try_from(record.training_samples).unwrap_or(i32::MAX))
+        .bind(record.accuracy())
+        .bind(&record.model_path)
+        .bind(record.status.as_str())
+        .bind(record.trained_at)
+        .bind(record.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(storage_error)?;
+        Ok(())
+    }
+
+    async fn get(&self, name: &str, version: &str) -> NeuralResult<Option<ModelRecord>> {
+        let query = format!("{} WHERE name = $1 AND version = $2", SELECT_MODELS);
+        Ok(self
+            .fetch(sqlx::query_as(&query).bind(name).bind(version))
+            .await?
+            .pop())
+    }
+
+    async fn versions(&self, name: &str) -> NeuralResult<Vec<ModelRecord>> {
+        let query = format!("{} WHERE name = $1 ORDER BY created_at ASC", SELECT_MODELS);
+        self.fetch(sqlx::query_as(&query).bind(name)).await
+    }
+
+    async fn with_status(&self, statuses: &[ModelStatus]) -> NeuralResult<Vec<ModelRecord>> {
+        let statuses: Vec<&str> = statuses.iter().map(ModelStatus::as_str).collect();
+        let query = format!(
+            "{} WHERE status = ANY($1) ORDER BY created_at ASC",
+            SELECT_MODELS
+        );
+        self.fetch(sqlx::query_as(&query).bind(statuses)).await
+    }
+
+    async fn set_status(&self, name: &str, version: &str, status: ModelStatus) -> NeuralResult<()> {
+        let result = sqlx::query(
+            "UPDATE neural_models SET status = $3, \
+             is_active = ($3 IN ('live', 'shadow')), \
+             is_production = ($3 = 'live'), \
+             deployed_at = CASE WHEN $3 = 'live' THEN NOW() ELSE deployed_at END \
+             WHERE name = $1 AND version = $2",
+        )
+        .bind(name)
+        .bind(version)
+        .bind(status.as_str())
+        .execute(&self.pool)
+        .await
+        .map_err(storage_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(NeuralError::ModelNotFound(format!("{}@{}", name, version)));
+        }
+        Ok(())
+    }
+
+    async fn record_comparison(&self, comparison: &ShadowComparison) -> NeuralResult<()> {
+        sqlx::query(
+            "INSERT INTO model_shadow_comparisons (task, live_model_id, shadow_model_id, \
+             live_predictor, shadow_predictor, samples, live_only, shadow_only, shadow_errors, \
+             mean_abs_difference, max_abs_difference, avg_live_latency_ms, avg_shadow_latency_ms, \
+             started_at) \
+             VALUES ($1, \
+             (SELECT id FROM neural_models WHERE name || '@' || version = $2), \
+             (SELECT id FROM neural_models WHERE name || '@' || version = $3), \
+             $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)",
+        )
+        .bind(comparison.task.as_str())
+        .bind(&comparison.live_id)
+        .bind(&comparison.shadow_id)
+        .bind(comparison.samples as i64)
+        .bind(comparison.live_only as i64)
+        .bind(comparison.shadow_only as i64)
+        .bind(comparison.shadow_errors as i64)
+        .bind(comparison.mean_abs_difference())
+        .bind(comparison.max_abs_difference)
+        .bind(comparison.avg_live_latency_ms())
+        .bind(comparison.avg_shadow_latency_ms())
+        .bind(comparison.started_at)
+        .execute(&self.pool)
+        .await
+        .map_err(storage_error)?;
+        Ok(())
+    }
+}
+
+/// [`PredictionSink`] writing to `model_predictions`
+///
+/// Only predictions from registry versions (`name@version`) are stored, since
+/// each row references its `neural_models` entry. Row ids are derived from the
+/// monitor's prediction id and a per-sink session, so outcomes find their row
+/// without a lookup.
+#[derive(Debug, Clone)]
+pub struct PostgresPredictionSink {
+    pool: PgPool,
+    session: u64,
+}
+
+impl PostgresPredictionSink {
+    pub fn new(pool: PgPool) -> Self {
+        let session = Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64;
+        Self { pool, session }
+    }
+
+    fn row_id(&self, prediction: &LoggedPrediction) -> Uuid {
+        Uuid::from_u64_pair(self.session, prediction.id)
+    }
+}
+
+/// Output stored as `prediction_value`/`actual_value`: 5m volatility,
+/// expected arbitrage profit or overall risk
+fn headline(task: PredictionTask) -> usize {
+    match task {
+        PredictionTask::Volatility => 1,
+        PredictionTask::Arbitrage => 3,
+        PredictionTask::Risk => 0,
+    }
+}
+
+#[async_trait]
+impl PredictionSink for PostgresPredictionSink {
+    async fn record_prediction(&self, prediction: &LoggedPrediction) -> NeuralResult<()> {
+        let value = prediction
+            .outputs
+            .get(headline(prediction.task))
+            .copied()
+            .unwrap_or_default();
+        let confidence = prediction
+            .outputs
+            .last()
+            .map(|c| c.abs().min(1.0))
+            .unwrap_or_default();
+
+        sqlx::query(
+            "INSERT INTO model_predictions (id, model_id, prediction_type, symbol, exchange, \
+             input_data, prediction_value, confidence_score, inference_time_ms, model_version, \
+             series, output_values, predicted_at) \
+             SELECT $1, id, $3, $4, $5, $6, $7::float8, $8::float8, $9::float8, version, \
+             $10, $11, $12 \
+             FROM neural_models WHERE name || '@' || version = $2",
+        )
+        .bind(self.row_id(prediction))
+        .bind(&prediction.model_id)
+        .bind(prediction.task.as_str())
+        .bind(&prediction.symbol)
+        .bind(&prediction.exchange)
+        .bind(Json(&prediction.features))
+        .bind(value)
+        .bind(confidence)
+        .bind(prediction.inference_time_ms.min(9_999.99))
+        .bind(&prediction.series)
+        .bind(&prediction.outputs)
+        .bind(prediction.predicted_at)
+        .execute(&self.pool)
+        .await
+        .map_err(storage_error)?;
+        Ok(())
+    }
+
+    async fn record_outcome(&self, outcome: &ResolvedPrediction) -> NeuralResult<()> {
+        let prediction = &outcome.prediction;
+        let index = headline(prediction.task);
+        let actual = outcome.realized.get(index).copied().unwrap_or_default();
+        let predicted = prediction.outputs.get(index).copied().unwrap_or_default();
+        // Arbitrage predictions are right or wrong about whether an opportunity appeared
+        let is_correct = match (
+            prediction.task,
+            prediction.outputs.get(2),
+            outcome.realized.get(2),
+        ) {
+            (PredictionTask::Arbitrage, Some(p), Some(y)) => Some((*p > 0.5) == (*y > 0.5)),
+            _ => None,
+        };
+
+        sqlx::query(
+            "UPDATE model_predictions SET actual_value = $2::float8, prediction_error = $3::float8, \
+             is_correct = $4, actual_values = $5, validated_at = $6 WHERE id = $1",
+        )
+        .bind(self.row_id(prediction))
+        .bind(actual)
+        .bind(predicted - actual)
+        .bind(is_correct)
+        .bind(&outcome.realized)
+        .bind(outcome.resolved_at)
+        .execute(&self.pool)
+        .await
+        .map_err(storage_error)?;
+        Ok(())
+    }
+}
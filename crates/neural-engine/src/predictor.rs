@@ -0,0 +1,886 @@
+//! Pluggable model inference
+//!
+//! Every inference backend (FANN networks, Candle MLP/LSTM models,
+//! statistical baselines) implements [`Predictor`] and is registered in a
+//! [`PredictorRegistry`] under a model id. The engine resolves a predictor per
+//! [`PredictionTask`], preferring a symbol- or venue-specific id when one is
+//! registered, and reports [`PredictionOutcome::NoModel`] when nothing can
+//! answer instead of inventing numbers.
+//!
+//! A second model can shadow a task's active model: it sees every input the
+//! active model answers, its outputs are compared in a [`ShadowComparison`],
+//! and callers only ever receive the active model's prediction.
+
+use crate::features::schema_hash;
+use crate::training::DEFAULT_RISK_HORIZON_MINUTES;
+use crate::{MarketDataInput, ModelType, NeuralBackend, NeuralError, NeuralResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+/// What a predictor forecasts; fixes the feature and output layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PredictionTask {
+    /// `[price, high, low, volume, avg_volume, bid, ask]` ->
+    /// `[volatility_1m, volatility_5m, volatility_15m, confidence]`
+    Volatility,
+    /// `[primary_price, secondary_price, spread, primary_volume, secondary_volume,
+    /// primary_bid, primary_ask, secondary_bid, secondary_ask]` ->
+    /// `[spread_1m, spread_5m, arb_probability, expected_profit, confidence]`
+    Arbitrage,
+    /// `[position_size, volatility, spread, volume_ratio, time_factor]` ->
+    /// `[overall_risk, liquidity_risk, execution_risk, max_position, confidence]`
+    Risk,
+}
+
+impl PredictionTask {
+    pub fn input_size(&self) -> usize {
+        match self {
+            PredictionTask::Volatility => 7,
+            PredictionTask::Arbitrage => 9,
+            PredictionTask::Risk => 5,
+        }
+    }
+
+    pub fn output_size(&self) -> usize {
+        match self {
+            PredictionTask::Volatility => 4,
+            PredictionTask::Arbitrage | PredictionTask::Risk => 5,
+        }
+    }
+
+    /// Lowercase name, as stored in the model tables
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PredictionTask::Volatility => "volatility",
+            PredictionTask::Arbitrage => "arbitrage",
+            PredictionTask::Risk => "risk",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "volatility" => Some(PredictionTask::Volatility),
+            "arbitrage" => Some(PredictionTask::Arbitrage),
+            "risk" => Some(PredictionTask::Risk),
+            _ => None,
+        }
+    }
+
+    /// Input column names, in feature-vector order
+    pub fn feature_names(&self) -> &'static [&'static str] {
+        match self {
+            PredictionTask::Volatility => {
+                &["price", "high", "low", "volume", "avg_volume", "bid", "ask"]
+            }
+            PredictionTask::Arbitrage => &[
+                "primary_price",
+                "secondary_price",
+                "spread",
+                "primary_volume",
+                "secondary_volume",
+                "primary_bid",
+                "primary_ask",
+                "secondary_bid",
+                "secondary_ask",
+            ],
+            PredictionTask::Risk => &[
+                "position_size",
+                "volatility",
+                "spread",
+                "volume_ratio",
+                "time_factor",
+            ],
+        }
+    }
+
+    /// Output names, in prediction order
+    pub fn output_names(&self) -> &'static [&'static str] {
+        match self {
+            PredictionTask::Volatility => &[
+                "volatility_1m",
+                "volatility_5m",
+                "volatility_15m",
+                "confidence",
+            ],
+            PredictionTask::Arbitrage => &[
+                "spread_1m",
+                "spread_5m",
+                "arb_probability",
+                "expected_profit",
+                "confidence",
+            ],
+            PredictionTask::Risk => &[
+                "overall_risk",
+                "liquidity_risk",
+                "execution_risk",
+                "max_position",
+                "confidence",
+            ],
+        }
+    }
+
+    /// Schema hash of the task's fixed feature layout
+    pub fn feature_schema_hash(&self) -> String {
+        schema_hash(self.feature_names())
+    }
+}
+
+/// Description of a registered model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PredictorInfo {
+    pub id: String,
+    pub task: PredictionTask,
+    pub backend: NeuralBackend,
+    /// Network architecture, `None` for statistical models
+    pub model_type: Option<ModelType>,
+    pub version: String,
+    /// Validation accuracy recorded at training time
+    pub accuracy: Option<f64>,
+    /// Training date (ISO 8601)
+    pub trained_at: Option<String>,
+}
+
+impl PredictorInfo {
+    pub fn new(id: impl Into<String>, task: PredictionTask, backend: NeuralBackend) -> Self {
+        Self {
+            id: id.into(),
+            task,
+            backend,
+            model_type: None,
+            version: "1.0.0".to_string(),
+            accuracy: None,
+            trained_at: None,
+        }
+    }
+}
+
+/// Features for one prediction
+#[derive(Debug, Clone)]
+pub struct PredictionInput {
+    /// Series the features belong to (e.g. `BTC-USD:coinbase`); stateful
+    /// predictors keep separate history per key
+    pub key: String,
+    /// Feature vector in the task's layout
+    pub features: Vec<f64>,
+}
+
+impl PredictionInput {
+    pub fn new(key: impl Into<String>, features: Vec<f64>) -> Self {
+        Self {
+            key: key.into(),
+            features,
+        }
+    }
+}
+
+/// Model inference backend
+pub trait Predictor: Send + Sync {
+    fn info(&self) -> &PredictorInfo;
+
+    /// Run inference on `input`.
+    ///
+    /// Returns `Ok(None)` when the predictor cannot answer yet, e.g. a
+    /// statistical or sequence model still accumulating history for the key.
+    fn predict(&mut self, input: &PredictionInput) -> NeuralResult<Option<Vec<f64>>>;
+}
+
+/// Result of asking the engine for a prediction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PredictionOutcome<T> {
+    /// A model produced `value`
+    Predicted { model_id: String, value: T },
+    /// No model could answer; callers must not treat this as a forecast
+    NoModel {
+        task: PredictionTask,
+        reason: String,
+    },
+}
+
+impl<T> PredictionOutcome<T> {
+    pub fn is_predicted(&self) -> bool {
+        matches!(self, PredictionOutcome::Predicted { .. })
+    }
+
+    /// The prediction, if a model produced one
+    pub fn prediction(self) -> Option<T> {
+        match self {
+            PredictionOutcome::Predicted { value, .. } => Some(value),
+            PredictionOutcome::NoModel { .. } => None,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> PredictionOutcome<U> {
+        match self {
+            PredictionOutcome::Predicted { model_id, value } => PredictionOutcome::Predicted {
+                model_id,
+                value: f(value),
+            },
+            PredictionOutcome::NoModel { task, reason } => {
+                PredictionOutcome::NoModel { task, reason }
+            }
+        }
+    }
+}
+
+/// Online comparison of a shadow model against the live model for a task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShadowComparison {
+    pub task: PredictionTask,
+    pub live_id: String,
+    pub shadow_id: String,
+    pub started_at: DateTime<Utc>,
+    /// Inputs both models answered
+    pub samples: u64,
+    /// Inputs only the live model answered
+    pub live_only: u64,
+    /// Inputs only the shadow model answered
+    pub shadow_only: u64,
+    /// Shadow inference failures or malformed outputs
+    pub shadow_errors: u64,
+    /// Per-output sum of `|shadow - live|` over `samples`
+    pub abs_difference_sum: Vec<f64>,
+    pub max_abs_difference: f64,
+    pub live_calls: u64,
+    pub shadow_calls: u64,
+    pub live_latency: Duration,
+    pub shadow_latency: Duration,
+}
+
+impl ShadowComparison {
+    fn new(task: PredictionTask, live_id: String, shadow_id: String) -> Self {
+        Self {
+            task,
+            live_id,
+            shadow_id,
+            started_at: Utc::now(),
+            samples: 0,
+            live_only: 0,
+            shadow_only: 0,
+            shadow_errors: 0,
+            abs_difference_sum: vec![0.0; task.output_size()],
+            max_abs_difference: 0.0,
+            live_calls: 0,
+            shadow_calls: 0,
+            live_latency: Duration::ZERO,
+            shadow_latency: Duration::ZERO,
+        }
+    }
+
+    /// Per-output mean absolute difference, in [`PredictionTask::output_names`] order
+    pub fn mean_abs_difference(&self) -> Vec<f64> {
+        let samples = self.samples.max(1) as f64;
+        self.abs_difference_sum
+            .iter()
+            .map(|sum| sum / samples)
+            .collect()
+    }
+
+    pub fn avg_live_latency_ms(&self) -> f64 {
+        average_ms(self.live_latency, self.live_calls)
+    }
+
+    pub fn avg_shadow_latency_ms(&self) -> f64 {
+        average_ms(self.shadow_latency, self.shadow_calls)
+    }
+
+    fn record(
+        &mut self,
+        live: Option<&[f64]>,
+        shadow: NeuralResult<Option<Vec<f64>>>,
+        live_latency: Duration,
+        shadow_latency: Duration,
+    ) {
+        self.live_calls += 1;
+        self.shadow_calls += 1;
+        self.live_latency += live_latency;
+        self.shadow_latency += shadow_latency;
+
+        let shadow = match shadow {
+            Ok(Some(output)) if output.len() != self.task.output_size() => {
+                self.shadow_errors += 1;
+                return;
+            }
+            Ok(shadow) => shadow,
+            Err(e) => {
+                warn!("Shadow model {} failed: {}", self.shadow_id, e);
+                self.shadow_errors += 1;
+                return;
+            }
+        };
+
+        match (live, shadow) {
+            (Some(live), Some(shadow)) => {
+                self.samples += 1;
+                for ((sum, live), shadow) in
+                    self.abs_difference_sum.iter_mut().zip(live).zip(&shadow)
+                {
+                    let difference = (shadow - live).abs();
+                    *sum += difference;
+                    self.max_abs_difference = self.max_abs_difference.max(difference);
+                }
+            }
+            (Some(_), None) => self.live_only += 1,
+            (None, Some(_)) => self.shadow_only += 1,
+            (None, None) => {}
+        }
+    }
+}
+
+fn average_ms(total: Duration, calls: u64) -> f64 {
+    if calls == 0 {
+        0.0
+    } else {
+        total.as_secs_f64() * 1_000.0 / calls as f64
+    }
+}
+
+type SharedPredictor = Arc<Mutex<Box<dyn Predictor>>>;
+
+struct RegisteredPredictor {
+    info: PredictorInfo,
+    predictor: SharedPredictor,
+}
+
+/// Predictors keyed by model id, with one active and at most one shadow model per task
+#[derive(Default)]
+pub struct PredictorRegistry {
+    predictors: RwLock<HashMap<String, RegisteredPredictor>>,
+    active: RwLock<HashMap<PredictionTask, String>>,
+    shadows: RwLock<HashMap<PredictionTask, ShadowComparison>>,
+}
+
+impl PredictorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `predictor` under its id, replacing any model with the same id.
+    ///
+    /// The first model registered for a task becomes that task's active model.
+    pub fn register(&self, predictor: Box<dyn Predictor>) {
+        let info = predictor.info().clone();
+        self.register_as(info, predictor);
+    }
+
+    /// Register `predictor` under `info`, which overrides the predictor's own
+    /// description (the model registry registers versions as `name@version`)
+    pub fn register_as(&self, info: PredictorInfo, predictor: Box<dyn Predictor>) {
+        info!(
+            "🧠 Registering {:?} predictor '{}' ({:?})",
+            info.task, info.id, info.backend
+        );
+
+        self.active
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(info.task)
+            .or_insert_with(|| info.id.clone());
+        self.predictors
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                info.id.clone(),
+                RegisteredPredictor {
+                    info,
+                    predictor: Arc::new(Mutex::new(predictor)),
+                },
+            );
+    }
+
+    /// Remove a model; a task whose active model is removed has none until another is activated
+    pub fn unregister(&self, id: &str) -> Option<PredictorInfo> {
+        let removed = self
+            .predictors
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(id)?;
+        self.active
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|_, active| active != id);
+        self.shadows
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|_, shadow| shadow.live_id != id && shadow.shadow_id != id);
+        Some(removed.info)
+    }
+
+    /// Make `id` the model used for its task.
+    ///
+    /// Activating the task's shadow model ends the shadow run; activating
+    /// any other model also ends it, since the comparison was against the
+    /// previous live model.
+    pub fn set_active(&self, id: &str) -> NeuralResult<()> {
+        let task = self
+            .info(id)
+            .ok_or_else(|| NeuralError::ModelNotFound(id.to_string()))?
+            .task;
+        let previous = self
+            .active
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(task, id.to_string());
+        if previous.as_deref() != Some(id) {
+            self.clear_shadow(task);
+        }
+        Ok(())
+    }
+
+    /// Run `id` in shadow of its task's active model
+    pub fn set_shadow(&self, id: &str) -> NeuralResult<()> {
+        let task = self
+            .info(id)
+            .ok_or_else(|| NeuralError::ModelNotFound(id.to_string()))?
+            .task;
+        let live_id = self.active_id(task).ok_or_else(|| {
+            NeuralError::ModelNotFound(format!("no active {:?} model to shadow", task))
+        })?;
+        if live_id == id {
+            return Err(NeuralError::InvalidInput(format!(
+                "{} is the active {:?} model and cannot shadow itself",
+                id, task
+            )));
+        }
+
+        info!("👥 Shadowing {:?} model '{}' with '{}'", task, live_id, id);
+        self.shadows
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(task, ShadowComparison::new(task, live_id, id.to_string()));
+        Ok(())
+    }
+
+    /// Stop the shadow run for `task`, returning its final comparison
+    pub fn clear_shadow(&self, task: PredictionTask) -> Option<ShadowComparison> {
+        self.shadows
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&task)
+    }
+
+    /// Comparison so far for the shadow run on `task`
+    pub fn shadow_comparison(&self, task: PredictionTask) -> Option<ShadowComparison> {
+        self.shadows
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&task)
+            .cloned()
+    }
+
+    pub fn active_id(&self, task: PredictionTask) -> Option<String> {
+        self.active
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&task)
+            .cloned()
+    }
+
+    pub fn info(&self, id: &str) -> Option<PredictorInfo> {
+        self.predictors
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(id)
+            .map(|entry| entry.info.clone())
+    }
+
+    /// All registered models, ordered by id
+    pub fn list(&self) -> Vec<PredictorInfo> {
+        let mut infos: Vec<PredictorInfo> = self
+            .predictors
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .map(|entry| entry.info.clone())
+            .collect();
+        infos.sort_by(|a, b| a.id.cmp(&b.id));
+        infos
+    }
+
+    pub fn has_model(&self, task: PredictionTask) -> bool {
+        self.active_id(task).is_some()
+    }
+
+    /// Model for `task`: `preferred` when registered for that task, otherwise the active one
+    fn resolve(
+        &self,
+        task: PredictionTask,
+        preferred: Option<&str>,
+    ) -> Option<(String, SharedPredictor)> {
+        let predictors = self
+            .predictors
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        let preferred = preferred
+            .and_then(|id| predictors.get(id))
+            .filter(|entry| entry.info.task == task);
+        let entry = match preferred {
+            Some(entry) => entry,
+            None => predictors.get(&self.active_id(task)?)?,
+        };
+        Some((entry.info.id.clone(), entry.predictor.clone()))
+    }
+
+    /// Shadow model for `task` when `live_id` is the model it is compared against
+    fn resolve_shadow(
+        &self,
+        task: PredictionTask,
+        live_id: &str,
+    ) -> Option<(String, SharedPredictor)> {
+        let shadow_id = self
+            .shadows
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&task)
+            .filter(|shadow| shadow.live_id == live_id)?
+            .shadow_id
+            .clone();
+        let predictor = self
+            .predictors
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&shadow_id)?
+            .predictor
+            .clone();
+        Some((shadow_id, predictor))
+    }
+
+    /// Run the model resolved for `task` on `input`.
+    ///
+    /// When that model is being shadowed, the shadow model runs on the same
+    /// input afterwards and only updates the task's [`ShadowComparison`].
+    pub async fn predict(
+        &self,
+        task: PredictionTask,
+        preferred: Option<&str>,
+        input: &PredictionInput,
+    ) -> NeuralResult<PredictionOutcome<Vec<f64>>> {
+        if input.features.len() != task.input_size() {
+            return Err(NeuralError::InvalidInput(format!(
+                "{:?} expects {} features, got {}",
+                task,
+                task.input_size(),
+                input.features.len()
+            )));
+        }
+
+        let Some((model_id, predictor)) = self.resolve(task, preferred) else {
+            return Ok(PredictionOutcome::NoModel {
+                task,
+                reason: format!("no {:?} model registered", task),
+            });
+        };
+
+        let started = Instant::now();
+        let output = predictor.lock().await.predict(input)?;
+        let live_latency = started.elapsed();
+
+        if let Some((shadow_id, shadow)) = self.resolve_shadow(task, &model_id) {
+            let started = Instant::now();
+            let shadow_output = shadow.lock().await.predict(input);
+            let shadow_latency = started.elapsed();
+            let live = output
+                .as_deref()
+                .filter(|output| output.len() == task.output_size());
+            if let Some(comparison) = self
+                .shadows
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get_mut(&task)
+                .filter(|shadow| shadow.shadow_id == shadow_id)
+            {
+                comparison.record(live, shadow_output, live_latency, shadow_latency);
+            }
+        }
+
+        match output {
+            Some(output) if output.len() == task.output_size() => {
+                debug!("🔮 {} produced {:?}", model_id, output);
+                Ok(PredictionOutcome::Predicted {
+                    model_id,
+                    value: output,
+                })
+            }
+            Some(output) => Err(NeuralError::InferenceFailed(format!(
+                "{} returned {} outputs, expected {}",
+                model_id,
+                output.len(),
+                task.output_size()
+            ))),
+            None => Ok(PredictionOutcome::NoModel {
+                task,
+                reason: format!("{} is still warming up for {}", model_id, input.key),
+            }),
+        }
+    }
+}
+
+/// Feature vector for [`PredictionTask::Volatility`]
+pub fn volatility_features(data: &MarketDataInput) -> Vec<f64> {
+    vec![
+        data.price,
+        data.high,
+        data.low,
+        data.volume,
+        data.avg_volume,
+        data.bid,
+        data.ask,
+    ]
+}
+
+/// Feature vector for [`PredictionTask::Arbitrage`]
+pub fn arbitrage_features(primary: &MarketDataInput, secondary: &MarketDataInput) -> Vec<f64> {
+    vec![
+        primary.price,
+        secondary.price,
+        (secondary.price - primary.price).abs() / primary.price,
+        primary.volume,
+        secondary.volume,
+        primary.bid,
+        primary.ask,
+        secondary.bid,
+        secondary.ask,
+    ]
+}
+
+/// Feature vector for [`PredictionTask::Risk`], using the training pipeline's
+/// default risk horizon for the time factor
+pub fn risk_features(position_size: f64, volatility: f64, data: &MarketDataInput) -> Vec<f64> {
+    let spread = if data.ask > data.bid && data.price > 0.0 {
+        (data.ask - data.bid) / data.price
+    } else {
+        (data.high - data.low) / data.price
+    };
+    let candle_notional = data.price * data.avg_volume;
+    let volume_ratio = if candle_notional > 0.0 {
+        position_size / candle_notional
+    } else {
+        1.0
+    };
+
+    vec![
+        position_size,
+        volatility,
+        spread,
+        volume_ratio,
+        DEFAULT_RISK_HORIZON_MINUTES as f64 / 60.0,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns its input's first `n` features, or nothing until `ready`
+    struct EchoPredictor {
+        info: PredictorInfo,
+        ready: bool,
+    }
+
+    impl EchoPredictor {
+        fn boxed(id: &str, task: PredictionTask, ready: bool) -> Box<dyn Predictor> {
+            Box::new(Self {
+                info: PredictorInfo::new(id, task, NeuralBackend::Simulated),
+                ready,
+            })
+        }
+    }
+
+    impl Predictor for EchoPredictor {
+        fn info(&self) -> &PredictorInfo {
+            &self.info
+        }
+
+        fn predict(&mut self, input: &PredictionInput) -> NeuralResult<Option<Vec<f64>>> {
+            Ok(self
+                .ready
+                .then(|| input.features[..self.info.task.output_size()].to_vec()))
+        }
+    }
+
+    fn input(value: f64) -> PredictionInput {
+        PredictionInput::new("BTC-USD:coinbase", vec![value; 7])
+    }
+
+    #[tokio::test]
+    async fn test_empty_registry_reports_no_model() {
+        let registry = PredictorRegistry::new();
+        let outcome = registry
+            .predict(PredictionTask::Volatility, None, &input(1.0))
+            .await
+            .unwrap();
+        assert!(matches!(
+            outcome,
+            PredictionOutcome::NoModel {
+                task: PredictionTask::Volatility,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_preferred_model_overrides_active() {
+        let registry = PredictorRegistry::new();
+        registry.register(EchoPredictor::boxed(
+            "vol_universal",
+            PredictionTask::Volatility,
+            true,
+        ));
+        registry.register(EchoPredictor::boxed(
+            "vol_btc",
+            PredictionTask::Volatility,
+            true,
+        ));
+        registry.register(EchoPredictor::boxed("risk", PredictionTask::Risk, true));
+        assert_eq!(
+            registry.active_id(PredictionTask::Volatility).as_deref(),
+            Some("vol_universal")
+        );
+
+        let outcome = registry
+            .predict(PredictionTask::Volatility, Some("vol_btc"), &input(1.0))
+            .await
+            .unwrap();
+        assert!(
+            matches!(outcome, PredictionOutcome::Predicted { ref model_id, .. } if model_id == "vol_btc")
+        );
+
+        // A preferred id registered for another task is ignored
+        let outcome = registry
+            .predict(PredictionTask::Volatility, Some("risk"), &input(1.0))
+            .await
+            .unwrap();
+        assert!(
+            matches!(outcome, PredictionOutcome::Predicted { ref model_id, .. } if model_id == "vol_universal")
+        );
+
+        registry.unregister("vol_universal");
+        assert!(!registry.has_model(PredictionTask::Volatility));
+        registry.set_active("vol_btc").unwrap();
+        assert!(registry.has_model(PredictionTask::Volatility));
+    }
+
+    /// Returns its input's first outputs scaled by `factor`
+    struct ScaledPredictor {
+        info: PredictorInfo,
+        factor: f64,
+    }
+
+    impl Predictor for ScaledPredictor {
+        fn info(&self) -> &PredictorInfo {
+            &self.info
+        }
+
+        fn predict(&mut self, input: &PredictionInput) -> NeuralResult<Option<Vec<f64>>> {
+            let n = self.info.task.output_size();
+            Ok(Some(
+                input.features[..n]
+                    .iter()
+                    .map(|v| v * self.factor)
+                    .collect(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shadow_model_is_compared_but_never_served() {
+        let registry = PredictorRegistry::new();
+        registry.register(EchoPredictor::boxed(
+            "live",
+            PredictionTask::Volatility,
+            true,
+        ));
+        registry.register(Box::new(ScaledPredictor {
+            info: PredictorInfo::new(
+                "candidate",
+                PredictionTask::Volatility,
+                NeuralBackend::Simulated,
+            ),
+            factor: 1.5,
+        }));
+        assert!(registry.set_shadow("live").is_err());
+        registry.set_shadow("candidate").unwrap();
+
+        for value in [1.0, 2.0] {
+            let outcome = registry
+                .predict(PredictionTask::Volatility, None, &input(value))
+                .await
+                .unwrap();
+            assert_eq!(
+                outcome,
+                PredictionOutcome::Predicted {
+                    model_id: "live".to_string(),
+                    value: vec![value; 4],
+                }
+            );
+        }
+
+        let comparison = registry
+            .shadow_comparison(PredictionTask::Volatility)
+            .unwrap();
+        assert_eq!((comparison.samples, comparison.shadow_errors), (2, 0));
+        // |1.5 - 1| and |3 - 2| average to 0.75 on every output
+        assert!(comparison
+            .mean_abs_difference()
+            .iter()
+            .all(|d| (d - 0.75).abs() < 1e-12));
+        assert!((comparison.max_abs_difference - 1.0).abs() < 1e-12);
+        assert_eq!(comparison.live_calls, 2);
+
+        // Promoting the shadow ends the run
+        registry.set_active("candidate").unwrap();
+        assert!(registry
+            .shadow_comparison(PredictionTask::Volatility)
+            .is_none());
+        let outcome = registry
+            .predict(PredictionTask::Volatility, None, &input(2.0))
+            .await
+            .unwrap();
+        assert_eq!(outcome.prediction(), Some(vec![3.0; 4]));
+    }
+
+    #[test]
+    fn test_task_layouts_match_sizes() {
+        for task in [
+            PredictionTask::Volatility,
+            PredictionTask::Arbitrage,
+            PredictionTask::Risk,
+        ] {
+            assert_eq!(task.feature_names().len(), task.input_size());
+            assert_eq!(task.output_names().len(), task.output_size());
+            assert_eq!(PredictionTask::from_name(task.as_str()), Some(task));
+        }
+        assert_ne!(
+            PredictionTask::Volatility.feature_schema_hash(),
+            PredictionTask::Risk.feature_schema_hash()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_warming_up_and_invalid_input() {
+        let registry = PredictorRegistry::new();
+        registry.register(EchoPredictor::boxed(
+            "vol",
+            PredictionTask::Volatility,
+            false,
+        ));
+
+        let outcome = registry
+            .predict(PredictionTask::Volatility, None, &input(1.0))
+            .await
+            .unwrap();
+        assert!(!outcome.is_predicted());
+
+        let short = PredictionInput::new("BTC-USD:coinbase", vec![1.0; 3]);
+        assert!(matches!(
+            registry
+                .predict(PredictionTask::Volatility, None, &short)
+                .await,
+            Err(NeuralError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+//! Statistical volatility baselines
+//!
+//! EWMA (RiskMetrics) and GARCH(1,1) forecasters over log returns of the
+//! price feature. Each prediction call is one observation for its series
+//! key; forecasts are withheld until `min_observations` returns have been
+//! seen. Horizon forecasts are the RMS of the per-step variance forecasts,
+//! matching how the training pipeline labels forward realized volatility.
+
+use crate::predictor::{PredictionInput, PredictionTask, Predictor, PredictorInfo};
+use crate::{NeuralBackend, NeuralError, NeuralResult};
+use std::collections::HashMap;
+
+/// Variance recursion `σ²ₜ₊₁ = ω + α·rₜ² + β·σ²ₜ`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GarchParams {
+    pub omega: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+impl GarchParams {
+    fn persistence(&self) -> f64 {
+        self.alpha + self.beta
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct SeriesState {
+    last_price: Option<f64>,
+    returns_seen: usize,
+    sum_squared: f64,
+    /// One-step-ahead variance forecast, once warmed up
+    variance: Option<f64>,
+}
+
+/// EWMA / GARCH(1,1) volatility predictor
+pub struct VolatilityBaseline {
+    info: PredictorInfo,
+    params: GarchParams,
+    horizons: [usize; 3],
+    min_observations: usize,
+    series: HashMap<String, SeriesState>,
+}
+
+impl VolatilityBaseline {
+    /// RiskMetrics-style EWMA with decay `lambda` (0.94 is the usual choice)
+    pub fn ewma(id: impl Into<String>, lambda: f64) -> NeuralResult<Self> {
+        if !(0.0..1.0).contains(&lambda) {
+            return Err(NeuralError::InvalidInput(format!(
+                "EWMA decay must be in [0, 1), got {}",
+                lambda
+            )));
+        }
+        Ok(Self::with_params(
+            id,
+            GarchParams {
+                omega: 0.0,
+                alpha: 1.0 - lambda,
+                beta: lambda,
+            },
+        ))
+    }
+
+    /// Stationary GARCH(1,1)
+    pub fn garch(id: impl Into<String>, omega: f64, alpha: f64, beta: f64) -> NeuralResult<Self> {
+        if omega <= 0.0 || alpha < 0.0 || beta < 0.0 || alpha + beta >= 1.0 {
+            return Err(NeuralError::InvalidInput(format!(
+                "GARCH(1,1) needs omega > 0, alpha, beta >= 0 and alpha + beta < 1, got ({}, {}, {})",
+                omega, alpha, beta
+            )));
+        }
+        Ok(Self::with_params(id, GarchParams { omega, alpha, beta }))
+    }
+
+    fn with_params(id: impl Into<String>, params: GarchParams) -> Self {
+        Self {
+            info: PredictorInfo::new(id, PredictionTask::Volatility, NeuralBackend::Statistical),
+            params,
+            horizons: [1, 5, 15],
+            min_observations: 20,
+            series: HashMap::new(),
+        }
+    }
+
+    /// Returns required before forecasting
+    pub fn with_min_observations(mut self, min_observations: usize) -> Self {
+        self.min_observations = min_observations.max(1);
+        self
+    }
+
+    /// Forecast steps for the three volatility outputs
+    pub fn with_horizons(mut self, horizons: [usize; 3]) -> Self {
+        self.horizons = horizons.map(|h| h.max(1));
+        self
+    }
+
+    /// Observe `price` for `key`, returning the updated one-step variance forecast
+    fn observe(&mut self, key: &str, price: f64) -> Option<f64> {
+        let params = self.params;
+        let min_observations = self.min_observations;
+        let state = self.series.entry(key.to_string()).or_default();
+
+        let previous = state.last_price.replace(price);
+        let ret = match previous {
+            Some(previous) if previous > 0.0 && price > 0.0 => (price / previous).ln(),
+            _ => return state.variance,
+        };
+        let squared = ret * ret;
+
+        state.variance = match state.variance {
+            Some(variance) => Some(params.omega + params.alpha * squared + params.beta * variance),
+            None => {
+                state.returns_seen += 1;
+                state.sum_squared += squared;
+                (state.returns_seen >= min_observations)
+                    .then(|| state.sum_squared / state.returns_seen as f64)
+            }
+        };
+        state.variance
+    }
+
+    /// RMS volatility over the next `horizon` steps given the one-step forecast
+    fn horizon_volatility(&self, next_variance: f64, horizon: usize) -> f64 {
+        let persistence = self.params.persistence();
+        let total: f64 = if persistence < 1.0 {
+            let long_run = self.params.omega / (1.0 - persistence);
+            (0..horizon)
+                .map(|k| long_run + persistence.powi(k as i32) * (next_variance - long_run))
+                .sum()
+        } else {
+            next_variance * horizon as f64
+        };
+        (total / horizon as f64).max(0.0).sqrt()
+    }
+}
+
+impl Predictor for VolatilityBaseline {
+    fn info(&self) -> &PredictorInfo {
+        &self.info
+    }
+
+    fn predict(&mut self, input: &PredictionInput) -> NeuralResult<Option<Vec<f64>>> {
+        let price = *input.features.first().ok_or_else(|| {
+            NeuralError::InvalidInput("volatility features need a price".to_string())
+        })?;
+        let Some(next_variance) = self.observe(&input.key, price) else {
+            return Ok(None);
+        };
+
+        let vols = self
+            .horizons
+            .map(|h| self.horizon_volatility(next_variance, h));
+        let mean = vols.iter().sum::<f64>() / 3.0;
+        let confidence = if mean > 0.0 {
+            let spread = (vols.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 3.0).sqrt();
+            1.0 / (1.0 + spread / mean)
+        } else {
+            1.0
+        };
+
+        Ok(Some(vec![vols[0], vols[1], vols[2], confidence]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observe(model: &mut VolatilityBaseline, key: &str, price: f64) -> Option<Vec<f64>> {
+        model
+            .predict(&PredictionInput::new(key, vec![price; 7]))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_ewma_warms_up_then_tracks_returns() {
+        let mut model = VolatilityBaseline::ewma("vol_ewma", 0.94)
+            .unwrap()
+            .with_min_observations(10);
+
+        // Alternating ±1% moves: realized volatility ≈ 0.01
+        let mut price = 100.0;
+        for i in 0..10 {
+            assert!(
+                observe(&mut model, "BTC-USD", price).is_none(),
+                "step {}",
+                i
+            );
+            price *= if i % 2 == 0 { 1.01 } else { 1.0 / 1.01 };
+        }
+        let output = observe(&mut model, "BTC-USD", price).unwrap();
+
+        assert!((output[0] - 0.01).abs() < 1e-3, "{:?}", output);
+        // EWMA forecasts are flat across horizons
+        assert!((output[0] - output[2]).abs() < 1e-12);
+        assert!((output[3] - 1.0).abs() < 1e-12);
+
+        // Series are independent
+        assert!(observe(&mut model, "ETH-USD", 2_000.0).is_none());
+    }
+
+    #[test]
+    fn test_garch_reverts_to_long_run_variance() {
+        let (omega, alpha, beta) = (1e-6, 0.1, 0.85);
+        let mut model = VolatilityBaseline::garch("vol_garch", omega, alpha, beta)
+            .unwrap()
+            .with_min_observations(5);
+
+        // A volatile burst pushes short-term variance above the long-run level
+        let mut price = 100.0;
+        for i in 0..6 {
+            observe(&mut model, "BTC-USD", price);
+            price *= if i % 2 == 0 { 1.03 } else { 1.0 / 1.03 };
+        }
+        let output = observe(&mut model, "BTC-USD", price).unwrap();
+
+        let long_run = (omega / (1.0 - alpha - beta)).sqrt();
+        assert!(output[0] > output[1] && output[1] > output[2]);
+        assert!(output[2] > long_run);
+        assert!(output[3] < 1.0);
+    }
+
+    #[test]
+    fn test_rejects_invalid_parameters() {
+        assert!(VolatilityBaseline::ewma("v", 1.0).is_err());
+        assert!(VolatilityBaseline::garch("v", 1e-6, 0.5, 0.6).is_err());
+        assert!(VolatilityBaseline::garch("v", 0.0, 0.1, 0.8).is_err());
+    }
+}
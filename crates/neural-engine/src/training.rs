@@ -0,0 +1,998 @@
+//! Offline training pipeline for the volatility, arbitrage and risk networks
+//!
+//! Labelled datasets are built from historical candles and cross-exchange
+//! spread snapshots, split chronologically into training and validation sets
+//! (no shuffling, so the validation set never leaks future data into
+//! training), scaled into the unit range the sigmoid networks expect, and
+//! trained epoch by epoch with early stopping on validation error. The best
+//! weights are written to disk together with [`FannModelMetadata`] so
+//! `NeuralEngine::new_with_model_paths` can load them.
+//!
+//! Dataset construction and the training loop are backend-agnostic; the FANN
+//! specific pieces are only compiled with the `fann` feature.
+
+use crate::features::{FeatureBar, FeaturePipeline, FeatureSetSpec, IndicatorFactory};
+use crate::{NeuralError, NeuralResult};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+#[cfg(feature = "fann")]
+use crate::fann_backend::{FannModel, FannModelMetadata};
+#[cfg(feature = "fann")]
+use crate::features::FeatureManifest;
+#[cfg(feature = "fann")]
+use std::path::{Path, PathBuf};
+
+/// Forward horizon, in one-minute candles, over which position risk is labelled
+pub const DEFAULT_RISK_HORIZON_MINUTES: usize = 15;
+
+/// Adverse move treated as maximum execution risk
+pub const DEFAULT_STOP_LOSS: f64 = 0.02;
+
+/// Historical OHLCV candle used for labelling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingCandle {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    /// Best bid at candle close, when the source recorded it
+    pub bid: Option<f64>,
+    /// Best ask at candle close, when the source recorded it
+    pub ask: Option<f64>,
+}
+
+/// Top-of-book snapshot for one venue
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct QuoteSnapshot {
+    pub price: f64,
+    pub bid: f64,
+    pub ask: f64,
+    pub volume: f64,
+}
+
+/// Simultaneous quotes for the same symbol on two venues
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpreadSample {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub primary: QuoteSnapshot,
+    pub secondary: QuoteSnapshot,
+}
+
+/// Parameters controlling how labels are derived from history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelingConfig {
+    /// Candles of trailing history used for average volume and realized volatility
+    pub lookback: usize,
+    /// Forward horizons, in candles, for the 1m/5m/15m volatility targets
+    pub volatility_horizons: [usize; 3],
+    /// Forward horizons, in samples, for the 1m/5m spread targets
+    pub spread_horizons: [usize; 2],
+    /// Round-trip trading cost subtracted before an edge counts as exploitable
+    pub round_trip_cost: f64,
+    /// Position notionals to label for the risk model
+    pub position_sizes: Vec<f64>,
+    /// Forward horizon, in candles, over which position risk is measured
+    pub risk_horizon: usize,
+    /// Adverse move treated as maximum execution risk
+    pub stop_loss: f64,
+    /// Share of average candle notional a position may take before liquidity risk saturates
+    pub max_participation: f64,
+}
+
+impl Default for LabelingConfig {
+    fn default() -> Self {
+        Self {
+            lookback: 20,
+            volatility_horizons: [1, 5, 15],
+            spread_horizons: [1, 5],
+            round_trip_cost: 0.002,
+            position_sizes: vec![1_000.0, 10_000.0, 100_000.0],
+            risk_horizon: DEFAULT_RISK_HORIZON_MINUTES,
+            stop_loss: DEFAULT_STOP_LOSS,
+            max_participation: 0.1,
+        }
+    }
+}
+
+/// Training loop parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingConfig {
+    /// Most recent share of samples held out for validation
+    pub validation_fraction: f64,
+    /// Upper bound on training epochs
+    pub max_epochs: usize,
+    /// Epochs without validation improvement before stopping
+    pub patience: usize,
+    /// Smallest validation MSE decrease that counts as improvement
+    pub min_improvement: f64,
+    /// Backpropagation learning rate
+    pub learning_rate: f64,
+}
+
+impl Default for TrainingConfig {
+    fn default() -> Self {
+        Self {
+            validation_fraction: 0.2,
+            max_epochs: 500,
+            patience: 25,
+            min_improvement: 1e-6,
+            learning_rate: 0.7,
+        }
+    }
+}
+
+/// Paired feature and target vectors
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrainingDataset {
+    pub inputs: Vec<Vec<f64>>,
+    pub targets: Vec<Vec<f64>>,
+}
+
+impl TrainingDataset {
+    pub fn push(&mut self, input: Vec<f64>, target: Vec<f64>) {
+        self.inputs.push(input);
+        self.targets.push(target);
+    }
+
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// Split into (training, validation), keeping the most recent samples for validation
+    pub fn split_chronological(&self, validation_fraction: f64) -> (Self, Self) {
+        let holdout = ((self.len() as f64) * validation_fraction.clamp(0.0, 1.0)).round() as usize;
+        let cut = self
+            .len()
+            .saturating_sub(holdout.max(1))
+            .max(1)
+            .min(self.len());
+        (
+            Self {
+                inputs: self.inputs[..cut].to_vec(),
+                targets: self.targets[..cut].to_vec(),
+            },
+            Self {
+                inputs: self.inputs[cut..].to_vec(),
+                targets: self.targets[cut..].to_vec(),
+            },
+        )
+    }
+
+    /// Apply input and target scalers to every sample
+    pub fn scaled(&self, inputs: &MinMaxScaler, targets: &MinMaxScaler) -> Self {
+        Self {
+            inputs: self.inputs.iter().map(|x| inputs.transform(x)).collect(),
+            targets: self.targets.iter().map(|y| targets.transform(y)).collect(),
+        }
+    }
+}
+
+/// Per-column min-max scaling into [0, 1]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MinMaxScaler {
+    pub min: Vec<f64>,
+    pub max: Vec<f64>,
+}
+
+impl MinMaxScaler {
+    /// Fit column ranges on `rows`; returns `None` for an empty set
+    pub fn fit(rows: &[Vec<f64>]) -> Option<Self> {
+        let width = rows.first()?.len();
+        let mut min = vec![f64::INFINITY; width];
+        let mut max = vec![f64::NEG_INFINITY; width];
+        for row in rows {
+            for (column, value) in row.iter().enumerate().take(width) {
+                min[column] = min[column].min(*value);
+                max[column] = max[column].max(*value);
+            }
+        }
+        Some(Self { min, max })
+    }
+
+    pub fn transform(&self, row: &[f64]) -> Vec<f64> {
+        row.iter()
+            .enumerate()
+            .map(|(column, value)| (value - self.min[column]) / self.range(column))
+            .collect()
+    }
+
+    pub fn inverse(&self, row: &[f64]) -> Vec<f64> {
+        row.iter()
+            .enumerate()
+            .map(|(column, value)| value * self.range(column) + self.min[column])
+            .collect()
+    }
+
+    fn range(&self, column: usize) -> f64 {
+        let range = self.max[column] - self.min[column];
+        if range.abs() < f64::EPSILON {
+            1.0
+        } else {
+            range
+        }
+    }
+}
+
+/// Network that the early-stopping loop can drive
+pub trait TrainableNetwork {
+    /// Run one pass over `data`, returning the training MSE
+    fn train_epoch(&mut self, data: &TrainingDataset) -> NeuralResult<f64>;
+    /// Forward pass in the same (scaled) space the network is trained in
+    fn predict(&mut self, input: &[f64]) -> NeuralResult<Vec<f64>>;
+    /// Copy of the current weights, used to restore the best epoch
+    fn weights(&self) -> Vec<f64>;
+    fn set_weights(&mut self, weights: &[f64]) -> NeuralResult<()>;
+}
+
+/// Error metrics on a dataset
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EvaluationMetrics {
+    pub mse: f64,
+    pub mae: f64,
+}
+
+impl EvaluationMetrics {
+    /// Accuracy reported in model metadata: one minus the mean absolute error
+    /// on unit-scaled targets
+    pub fn accuracy(&self) -> f64 {
+        (1.0 - self.mae).clamp(0.0, 1.0)
+    }
+}
+
+/// Outcome of a training run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingReport {
+    pub epochs_run: usize,
+    pub best_epoch: usize,
+    pub train_mse: f64,
+    pub validation: EvaluationMetrics,
+    pub stopped_early: bool,
+    pub training_samples: usize,
+    pub validation_samples: usize,
+}
+
+/// Evaluate `network` on `data`
+pub fn evaluate<N: TrainableNetwork>(
+    network: &mut N,
+    data: &TrainingDataset,
+) -> NeuralResult<EvaluationMetrics> {
+    let mut squared = 0.0;
+    let mut absolute = 0.0;
+    let mut count = 0usize;
+    for (input, target) in data.inputs.iter().zip(&data.targets) {
+        let output = network.predict(input)?;
+        for (predicted, expected) in output.iter().zip(target) {
+            let error = predicted - expected;
+            squared += error * error;
+            absolute += error.abs();
+            count += 1;
+        }
+    }
+    if count == 0 {
+        return Err(NeuralError::TrainingFailed(
+            "cannot evaluate on an empty dataset".to_string(),
+        ));
+    }
+    Ok(EvaluationMetrics {
+        mse: squared / count as f64,
+        mae: absolute / count as f64,
+    })
+}
+
+/// Train until validation error stops improving, then restore the best weights
+pub fn train_with_early_stopping<N: TrainableNetwork>(
+    network: &mut N,
+    train: &TrainingDataset,
+    validation: &TrainingDataset,
+    config: &TrainingConfig,
+) -> NeuralResult<TrainingReport> {
+    if train.is_empty() || validation.is_empty() {
+        return Err(NeuralError::TrainingFailed(format!(
+            "need training and validation samples, got {} and {}",
+            train.len(),
+            validation.len()
+        )));
+    }
+
+    let mut best_mse = evaluate(network, validation)?.mse;
+    let mut best_weights = network.weights();
+    let mut best_epoch = 0;
+    let mut train_mse = f64::NAN;
+    let mut epochs_run = 0;
+    let mut stopped_early = false;
+
+    for epoch in 1..=config.max_epochs {
+        epochs_run = epoch;
+        train_mse = network.train_epoch(train)?;
+        if !train_mse.is_finite() {
+            return Err(NeuralError::TrainingFailed(format!(
+                "training diverged at epoch {}",
+                epoch
+            )));
+        }
+
+        let validation_mse = evaluate(network, validation)?.mse;
+        if validation_mse < best_mse - config.min_improvement {
+            best_mse = validation_mse;
+            best_weights = network.weights();
+            best_epoch = epoch;
+        } else if epoch - best_epoch >= config.patience {
+            stopped_early = true;
+            break;
+        }
+
+        if epoch % 50 == 0 {
+            debug!(
+                "Epoch {}: train_mse={:.6}, validation_mse={:.6}",
+                epoch, train_mse, validation_mse
+            );
+        }
+    }
+
+    network.set_weights(&best_weights)?;
+    let validation_metrics = evaluate(network, validation)?;
+
+    info!(
+        "🏋️ Training finished after {} epochs (best {}), validation mse={:.6}, accuracy={:.3}",
+        epochs_run,
+        best_epoch,
+        validation_metrics.mse,
+        validation_metrics.accuracy()
+    );
+
+    Ok(TrainingReport {
+        epochs_run,
+        best_epoch,
+        train_mse,
+        validation: validation_metrics,
+        stopped_early,
+        training_samples: train.len(),
+        validation_samples: validation.len(),
+    })
+}
+
+/// Volatility samples: `[price, high, low, volume, avg_volume, bid, ask]` ->
+/// `[volatility_1m, volatility_5m, volatility_15m, confidence]`
+///
+/// Targets are forward realized volatilities (RMS of log returns) over the
+/// configured horizons; the confidence target is their agreement across
+/// horizons.
+pub fn volatility_dataset(candles: &[TrainingCandle], config: &LabelingConfig) -> TrainingDataset {
+    let longest = config
+        .volatility_horizons
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or(1);
+    let mut dataset = TrainingDataset::default();
+
+    for t in config.lookback..candles.len().saturating_sub(longest) {
+        let candle = &candles[t];
+        let Some(targets) = forward_volatility_targets(candles, t, &config.volatility_horizons)
+        else {
+            continue;
+        };
+
+        dataset.push(
+            vec![
+                candle.close,
+                candle.high,
+                candle.low,
+                candle.volume,
+                average_volume(candles, t, config.lookback),
+                candle.bid.unwrap_or(candle.close),
+                candle.ask.unwrap_or(candle.close),
+            ],
+            targets,
+        );
+    }
+
+    dataset
+}
+
+/// Forward realized volatility over each horizon after candle `t`, plus
+/// their agreement as a confidence target; `None` near the end of history
+pub fn forward_volatility_targets(
+    candles: &[TrainingCandle],
+    t: usize,
+    horizons: &[usize; 3],
+) -> Option<Vec<f64>> {
+    let longest = horizons.iter().copied().max().unwrap_or(1);
+    let window = candles.get(t..=t + longest)?;
+    let returns = log_returns(window);
+    let mut targets: Vec<f64> = horizons
+        .iter()
+        .map(|&h| realized_volatility(&returns[1..=h]))
+        .collect();
+    targets.push(stability(&targets));
+    Some(targets)
+}
+
+/// Samples whose inputs come from a [`FeaturePipeline`] over `candles` and
+/// whose targets come from `label(candles, t)`.
+///
+/// Inputs are exactly what the same pipeline produces when streaming, so a
+/// model trained here sees identical features live.
+pub fn feature_dataset(
+    spec: &FeatureSetSpec,
+    factory: &dyn IndicatorFactory,
+    candles: &[TrainingCandle],
+    label: impl Fn(&[TrainingCandle], usize) -> Option<Vec<f64>>,
+) -> NeuralResult<TrainingDataset> {
+    let bars: Vec<FeatureBar> = candles.iter().map(FeatureBar::from).collect();
+    let rows = FeaturePipeline::transform_batch(spec, factory, &bars)?;
+
+    let mut dataset = TrainingDataset::default();
+    for (t, row) in rows.into_iter().enumerate() {
+        if let (Some(row), Some(target)) = (row, label(candles, t)) {
+            dataset.push(row, target);
+        }
+    }
+    Ok(dataset)
+}
+
+/// Arbitrage samples: `[primary_price, secondary_price, spread, primary_volume,
+/// secondary_volume, primary_bid, primary_ask, secondary_bid, secondary_ask]` ->
+/// `[spread_1m, spread_5m, arb_probability, expected_profit, confidence]`
+///
+/// The probability target is 1 when a cross at either direction clears
+/// `round_trip_cost` within the longer spread horizon; expected profit is the
+/// best such net edge as a fraction.
+pub fn arbitrage_dataset(samples: &[SpreadSample], config: &LabelingConfig) -> TrainingDataset {
+    let mut dataset = TrainingDataset::default();
+
+    for t in 0..samples.len() {
+        let Some(targets) = forward_arbitrage_targets(samples, t, config) else {
+            break;
+        };
+        let now = &samples[t];
+        dataset.push(
+            vec![
+                now.primary.price,
+                now.secondary.price,
+                spread(now),
+                now.primary.volume,
+                now.secondary.volume,
+                now.primary.bid,
+                now.primary.ask,
+                now.secondary.bid,
+                now.secondary.ask,
+            ],
+            targets,
+        );
+    }
+
+    dataset
+}
+
+/// Spread, opportunity and profit targets for sample `t` of
+/// [`arbitrage_dataset`]; `None` near the end of history
+pub fn forward_arbitrage_targets(
+    samples: &[SpreadSample],
+    t: usize,
+    config: &LabelingConfig,
+) -> Option<Vec<f64>> {
+    let [short, long] = config.spread_horizons;
+    let window = samples.get(t..=t + long)?;
+    let spread_short = spread(&window[short]);
+    let spread_long = spread(&window[long]);
+    let edge = window[1..]
+        .iter()
+        .map(|sample| executable_edge(sample) - config.round_trip_cost)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    Some(vec![
+        spread_short,
+        spread_long,
+        if edge > 0.0 { 1.0 } else { 0.0 },
+        edge.max(0.0),
+        stability(&[spread_short, spread_long]),
+    ])
+}
+
+/// Risk samples: `[position_size, volatility, spread, volume_ratio, time_factor]` ->
+/// `[overall_risk, liquidity_risk, execution_risk, max_position, confidence]`
+///
+/// Each candle is labelled once per configured position size. Execution risk
+/// is the largest forward excursion relative to `stop_loss`, liquidity risk
+/// the position's share of average candle notional relative to
+/// `max_participation`.
+pub fn risk_dataset(candles: &[TrainingCandle], config: &LabelingConfig) -> TrainingDataset {
+    let returns = log_returns(candles);
+    let horizon = config.risk_horizon.max(1);
+    let time_factor = horizon as f64 / 60.0;
+    let mut dataset = TrainingDataset::default();
+
+    for t in config.lookback.max(1)..candles.len().saturating_sub(horizon) {
+        let candle = &candles[t];
+        let trailing = realized_volatility(&returns[t + 1 - config.lookback.max(1)..=t]);
+        let forward = realized_volatility(&returns[t + 1..=t + horizon]);
+        let quoted_spread = match (candle.bid, candle.ask) {
+            (Some(bid), Some(ask)) if ask > bid => (ask - bid) / candle.close,
+            _ => (candle.high - candle.low) / candle.close,
+        };
+        let excursion = candles[t + 1..=t + horizon]
+            .iter()
+            .map(|c| ((c.high - candle.close).abs()).max((candle.close - c.low).abs()))
+            .fold(0.0, f64::max)
+            / candle.close;
+        let execution_risk = (excursion / config.stop_loss).min(1.0);
+        let candle_notional = candle.close * average_volume(candles, t, config.lookback);
+        if candle_notional <= 0.0 {
+            // No traded volume to measure liquidity against
+            continue;
+        }
+        let max_position = config.max_participation * candle_notional;
+
+        for &position in &config.position_sizes {
+            let volume_ratio = position / candle_notional;
+            let liquidity_risk = (volume_ratio / config.max_participation).min(1.0);
+
+            dataset.push(
+                vec![position, trailing, quoted_spread, volume_ratio, time_factor],
+                vec![
+                    execution_risk.max(liquidity_risk),
+                    liquidity_risk,
+                    execution_risk,
+                    max_position,
+                    stability(&[trailing, forward]),
+                ],
+            );
+        }
+    }
+
+    dataset
+}
+
+fn log_returns(candles: &[TrainingCandle]) -> Vec<f64> {
+    let mut returns = Vec::with_capacity(candles.len());
+    returns.push(0.0);
+    for window in candles.windows(2) {
+        let ratio = window[1].close / window[0].close;
+        returns.push(if ratio > 0.0 { ratio.ln() } else { 0.0 });
+    }
+    returns
+}
+
+fn realized_volatility(returns: &[f64]) -> f64 {
+    if returns.is_empty() {
+        return 0.0;
+    }
+    (returns.iter().map(|r| r * r).sum::<f64>() / returns.len() as f64).sqrt()
+}
+
+fn average_volume(candles: &[TrainingCandle], t: usize, lookback: usize) -> f64 {
+    let start = (t + 1).saturating_sub(lookback.max(1));
+    let window = &candles[start..=t];
+    window.iter().map(|c| c.volume).sum::<f64>() / window.len() as f64
+}
+
+fn spread(sample: &SpreadSample) -> f64 {
+    (sample.secondary.price - sample.primary.price).abs() / sample.primary.price
+}
+
+/// Best gross edge from buying on one venue's ask and selling into the other's bid
+fn executable_edge(sample: &SpreadSample) -> f64 {
+    let forward = (sample.secondary.bid - sample.primary.ask) / sample.primary.ask;
+    let reverse = (sample.primary.bid - sample.secondary.ask) / sample.secondary.ask;
+    forward.max(reverse)
+}
+
+/// Agreement of a set of estimates: 1 when identical, towards 0 as their
+/// coefficient of variation grows
+fn stability(values: &[f64]) -> f64 {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    if mean.abs() < f64::EPSILON {
+        return 1.0;
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    1.0 / (1.0 + variance.sqrt() / mean.abs())
+}
+
+/// Adapter driving a [`FannModel`] with incremental backpropagation
+#[cfg(feature = "fann")]
+pub struct FannTrainer<'a> {
+    model: &'a mut FannModel,
+    algorithm: ruv_fann::training::IncrementalBackprop<f64>,
+}
+
+#[cfg(feature = "fann")]
+impl<'a> FannTrainer<'a> {
+    pub fn new(model: &'a mut FannModel, learning_rate: f64) -> Self {
+        Self {
+            model,
+            algorithm: ruv_fann::training::IncrementalBackprop::new(learning_rate),
+        }
+    }
+}
+
+#[cfg(feature = "fann")]
+impl TrainableNetwork for FannTrainer<'_> {
+    fn train_epoch(&mut self, data: &TrainingDataset) -> NeuralResult<f64> {
+        use ruv_fann::training::{TrainingAlgorithm, TrainingData};
+
+        let data = TrainingData {
+            inputs: data.inputs.clone(),
+            outputs: data.targets.clone(),
+        };
+        self.algorithm
+            .train_epoch(self.model.network_mut(), &data)
+            .map_err(|e| NeuralError::TrainingFailed(e.to_string()))
+    }
+
+    fn predict(&mut self, input: &[f64]) -> NeuralResult<Vec<f64>> {
+        Ok(self.model.network_mut().run(input))
+    }
+
+    fn weights(&self) -> Vec<f64> {
+        self.model.weights()
+    }
+
+    fn set_weights(&mut self, weights: &[f64]) -> NeuralResult<()> {
+        self.model.set_weights(weights)
+    }
+}
+
+/// A trained network persisted to disk
+#[cfg(feature = "fann")]
+#[derive(Debug, Clone)]
+pub struct TrainedModel {
+    pub path: PathBuf,
+    pub metadata: FannModelMetadata,
+    pub report: TrainingReport,
+    /// Feature spec and normalization for models trained on a feature pipeline
+    pub features: Option<FeatureManifest>,
+}
+
+/// Builds datasets, trains the FANN networks and writes model files
+#[cfg(feature = "fann")]
+#[derive(Debug, Clone, Default)]
+pub struct ModelTrainer {
+    pub labeling: LabelingConfig,
+    pub training: TrainingConfig,
+}
+
+#[cfg(feature = "fann")]
+impl ModelTrainer {
+    pub fn new(labeling: LabelingConfig, training: TrainingConfig) -> Self {
+        Self { labeling, training }
+    }
+
+    /// Train the volatility network on `candles` and save it to `path`
+    pub fn train_volatility_model(
+        &self,
+        candles: &[TrainingCandle],
+        path: impl AsRef<Path>,
+    ) -> NeuralResult<TrainedModel> {
+        let dataset = volatility_dataset(candles, &self.labeling);
+        self.fit_and_save(
+            FannModel::create_volatility_model()?,
+            &dataset,
+            path.as_ref(),
+            None,
+        )
+    }
+
+    /// Train the arbitrage network on `samples` and save it to `path`
+    pub fn train_arbitrage_model(
+        &self,
+        samples: &[SpreadSample],
+        path: impl AsRef<Path>,
+    ) -> NeuralResult<TrainedModel> {
+        let dataset = arbitrage_dataset(samples, &self.labeling);
+        self.fit_and_save(
+            FannModel::create_arbitrage_model()?,
+            &dataset,
+            path.as_ref(),
+            None,
+        )
+    }
+
+    /// Train the risk network on `candles` and save it to `path`
+    pub fn train_risk_model(
+        &self,
+        candles: &[TrainingCandle],
+        path: impl AsRef<Path>,
+    ) -> NeuralResult<TrainedModel> {
+        let dataset = risk_dataset(candles, &self.labeling);
+        self.fit_and_save(
+            FannModel::create_risk_model()?,
+            &dataset,
+            path.as_ref(),
+            None,
+        )
+    }
+
+    /// Train a network on `spec`'s features with targets from `label`.
+    ///
+    /// Input normalization is written to a [`FeatureManifest`] next to the
+    /// model (see [`FeatureManifest::path_for`]) rather than into the model,
+    /// so live inference runs pipeline -> manifest -> network.
+    pub fn train_feature_model(
+        &self,
+        spec: &FeatureSetSpec,
+        factory: &dyn IndicatorFactory,
+        candles: &[TrainingCandle],
+        hidden_layers: &[usize],
+        label: impl Fn(&[TrainingCandle], usize) -> Option<Vec<f64>>,
+        path: impl AsRef<Path>,
+    ) -> NeuralResult<TrainedModel> {
+        let dataset = feature_dataset(spec, factory, candles, label)?;
+        let output_size = dataset.targets.first().map(Vec::len).ok_or_else(|| {
+            NeuralError::TrainingFailed(format!("no labelled samples for {}", spec.id()))
+        })?;
+
+        let mut model = FannModel::new(spec.len(), hidden_layers, output_size)?;
+        model.metadata.description = format!("Model on feature set {}", spec.id());
+        self.fit_and_save(model, &dataset, path.as_ref(), Some(spec))
+    }
+
+    fn fit_and_save(
+        &self,
+        mut model: FannModel,
+        dataset: &TrainingDataset,
+        path: &Path,
+        features: Option<&FeatureSetSpec>,
+    ) -> NeuralResult<TrainedModel> {
+        let (train, validation) = dataset.split_chronological(self.training.validation_fraction);
+        let (input_scaler, output_scaler) = match (
+            MinMaxScaler::fit(&train.inputs),
+            MinMaxScaler::fit(&train.targets),
+        ) {
+            (Some(inputs), Some(outputs)) => (inputs, outputs),
+            _ => {
+                return Err(NeuralError::TrainingFailed(format!(
+                    "not enough history to build a dataset for {}",
+                    model.metadata.description
+                )))
+            }
+        };
+
+        info!(
+            "🏋️ Training '{}' on {} samples ({} held out for validation)",
+            model.metadata.description,
+            train.len(),
+            validation.len()
+        );
+
+        let report = {
+            let mut trainer = FannTrainer::new(&mut model, self.training.learning_rate);
+            train_with_early_stopping(
+                &mut trainer,
+                &train.scaled(&input_scaler, &output_scaler),
+                &validation.scaled(&input_scaler, &output_scaler),
+                &self.training,
+            )?
+        };
+
+        model.metadata.accuracy = Some(report.validation.accuracy());
+        model.metadata.training_date = Some(chrono::Utc::now().to_rfc3339());
+        model.metadata.output_scaler = Some(output_scaler);
+        let features = match features {
+            Some(spec) => {
+                let manifest = FeatureManifest::new(spec.clone(), Some(input_scaler));
+                manifest.save(FeatureManifest::path_for(path))?;
+                Some(manifest)
+            }
+            None => {
+                model.metadata.input_scaler = Some(input_scaler);
+                None
+            }
+        };
+        model.save(path)?;
+
+        Ok(TrainedModel {
+            path: path.to_path_buf(),
+            metadata: model.metadata.clone(),
+            report,
+            features,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn candles(count: usize) -> Vec<TrainingCandle> {
+        let start = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        (0..count)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.3).sin() * 2.0;
+                TrainingCandle {
+                    timestamp: start + Duration::minutes(i as i64),
+                    open: close,
+                    high: close + 0.5,
+                    low: close - 0.5,
+                    close,
+                    volume: 1_000.0 + i as f64,
+                    bid: None,
+                    ask: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Single-layer linear model trained by gradient descent
+    struct LinearNetwork {
+        weights: Vec<f64>,
+        learning_rate: f64,
+    }
+
+    impl TrainableNetwork for LinearNetwork {
+        fn train_epoch(&mut self, data: &TrainingDataset) -> NeuralResult<f64> {
+            let mut mse = 0.0;
+            for (input, target) in data.inputs.iter().zip(&data.targets) {
+                let error = self.predict(input)?[0] - target[0];
+                self.weights[0] -= self.learning_rate * error * input[0];
+                self.weights[1] -= self.learning_rate * error;
+                mse += error * error;
+            }
+            Ok(mse / data.len() as f64)
+        }
+
+        fn predict(&mut self, input: &[f64]) -> NeuralResult<Vec<f64>> {
+            Ok(vec![self.weights[0] * input[0] + self.weights[1]])
+        }
+
+        fn weights(&self) -> Vec<f64> {
+            self.weights.clone()
+        }
+
+        fn set_weights(&mut self, weights: &[f64]) -> NeuralResult<()> {
+            self.weights = weights.to_vec();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_volatility_dataset_shapes_and_labels() {
+        let config = LabelingConfig::default();
+        let dataset = volatility_dataset(&candles(100), &config);
+
+        assert_eq!(dataset.len(), 100 - config.lookback - 15);
+        assert!(dataset.inputs.iter().all(|x| x.len() == 7));
+        assert!(dataset.targets.iter().all(|y| y.len() == 4));
+        assert!(dataset
+            .targets
+            .iter()
+            .all(|y| y[..3].iter().all(|v| *v >= 0.0) && (0.0..=1.0).contains(&y[3])));
+    }
+
+    #[test]
+    fn test_arbitrage_labels_exploitable_edge() {
+        let start = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let quote = |price: f64| QuoteSnapshot {
+            price,
+            bid: price - 0.05,
+            ask: price + 0.05,
+            volume: 10.0,
+        };
+        let samples: Vec<SpreadSample> = (0..10)
+            .map(|i| SpreadSample {
+                timestamp: start + Duration::seconds(i),
+                primary: quote(100.0),
+                // Secondary dislocates by 1% at sample 7
+                secondary: quote(if i == 7 { 101.0 } else { 100.0 }),
+            })
+            .collect();
+
+        let dataset = arbitrage_dataset(&samples, &LabelingConfig::default());
+        assert_eq!(dataset.len(), 5);
+        // Samples 2..=4 see the dislocation within five steps, 0 and 1 do not
+        assert_eq!(dataset.targets[1][2], 0.0);
+        assert_eq!(dataset.targets[2][2], 1.0);
+        assert!((dataset.targets[2][3] - (100.95 - 100.05) / 100.05 + 0.002).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_risk_dataset_saturates_liquidity_risk() {
+        let config = LabelingConfig::default();
+        let dataset = risk_dataset(&candles(60), &config);
+
+        assert_eq!(
+            dataset.len(),
+            (60 - config.risk_horizon - config.lookback) * config.position_sizes.len()
+        );
+        // 100k against ~100k of candle notional is far above 10% participation
+        let largest = &dataset.targets[config.position_sizes.len() - 1];
+        assert_eq!(largest[1], 1.0);
+        assert_eq!(largest[0], 1.0);
+    }
+
+    #[test]
+    fn test_feature_dataset_uses_pipeline_rows() {
+        use crate::features::{FeatureKind, NoIndicators};
+
+        let spec = FeatureSetSpec::new(
+            "returns",
+            1,
+            vec![
+                FeatureKind::LogReturn { lag: 1 },
+                FeatureKind::RealizedVolatility { window: 10 },
+                FeatureKind::Spread,
+            ],
+        );
+        let candles = candles(60);
+        let horizons = [1, 5, 15];
+        let dataset = feature_dataset(&spec, &NoIndicators, &candles, |c, t| {
+            forward_volatility_targets(c, t, &horizons)
+        })
+        .unwrap();
+
+        // Rows start once the 10-return window fills and stop 15 candles from the end
+        assert_eq!(dataset.len(), 60 - 10 - 15);
+        let bars: Vec<FeatureBar> = candles.iter().map(FeatureBar::from).collect();
+        let streamed = FeaturePipeline::transform_batch(&spec, &NoIndicators, &bars).unwrap();
+        assert_eq!(Some(&dataset.inputs[0]), streamed[10].as_ref());
+        assert_eq!(
+            dataset.targets[0],
+            forward_volatility_targets(&candles, 10, &horizons).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_chronological_split_and_scaling() {
+        let mut dataset = TrainingDataset::default();
+        for i in 0..10 {
+            dataset.push(vec![i as f64], vec![i as f64 * 2.0]);
+        }
+        let (train, validation) = dataset.split_chronological(0.2);
+        assert_eq!(train.len(), 8);
+        assert_eq!(validation.inputs, vec![vec![8.0], vec![9.0]]);
+
+        let scaler = MinMaxScaler::fit(&train.inputs).unwrap();
+        assert_eq!(scaler.transform(&[7.0]), vec![1.0]);
+        assert_eq!(scaler.inverse(&[0.5]), vec![3.5]);
+    }
+
+    #[test]
+    fn test_early_stopping_restores_best_weights() {
+        let mut dataset = TrainingDataset::default();
+        for i in 0..50 {
+            let x = i as f64 / 50.0;
+            dataset.push(vec![x], vec![0.5 * x + 0.2]);
+        }
+        let (train, validation) = dataset.split_chronological(0.2);
+        let mut network = LinearNetwork {
+            weights: vec![0.0, 0.0],
+            learning_rate: 0.1,
+        };
+        let config = TrainingConfig {
+            max_epochs: 5_000,
+            patience: 10,
+            ..TrainingConfig::default()
+        };
+
+        let report = train_with_early_stopping(&mut network, &train, &validation, &config).unwrap();
+
+        assert!(report.stopped_early);
+        assert!(report.epochs_run < config.max_epochs);
+        assert!(report.validation.accuracy() > 0.95);
+        assert_eq!(
+            report.validation,
+            evaluate(&mut network, &validation).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_training_rejects_empty_datasets() {
+        let mut network = LinearNetwork {
+            weights: vec![0.0, 0.0],
+            learning_rate: 0.1,
+        };
+        let empty = TrainingDataset::default();
+        assert!(matches!(
+            train_with_early_stopping(&mut network, &empty, &empty, &TrainingConfig::default()),
+            Err(NeuralError::TrainingFailed(_))
+        ));
+    }
+}
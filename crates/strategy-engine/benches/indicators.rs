@@ -51,5 +51,54 @@ fn bench_momentum_strategy_cycle(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_rsi_update, bench_momentum_strategy_cycle);
+fn bench_streaming_indicators(c: &mut Criterion) {
+    let indicators: Vec<Box<dyn Indicator>> = vec![
+        Box::new(Ichimoku::new(9, 26, 52, 26)),
+        Box::new(SuperTrend::new(10, 3.0)),
+        Box::new(ParabolicSar::new(0.02, 0.2)),
+        Box::new(DonchianChannels::new(20)),
+        Box::new(HullMa::new(16)),
+        Box::new(Kama::new(10, 2, 30)),
+        Box::new(Tema::new(9)),
+        Box::new(Aroon::new(25)),
+        Box::new(RollingZScore::new(20)),
+        Box::new(LinRegSlope::new(20)),
+    ];
+    let candles: Vec<Candle> = (0..256)
+        .map(|i| {
+            let close = Decimal::from(100 + (i * 37) % 23);
+            Candle {
+                open: close,
+                high: close + dec!(1.5),
+                low: close - dec!(1.25),
+                close,
+                volume: dec!(1000),
+                timestamp: i,
+            }
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("streaming_indicators");
+    for mut indicator in indicators {
+        // Warm up past the longest window so every update takes the steady-state path.
+        for candle in &candles {
+            indicator.update_ohlcv(candle);
+        }
+        let mut cursor = 0;
+        group.bench_function(indicator.name(), |b| {
+            b.iter(|| {
+                cursor = (cursor + 1) % candles.len();
+                indicator.update_ohlcv(black_box(&candles[cursor]))
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_rsi_update,
+    bench_momentum_strategy_cycle,
+    bench_streaming_indicators
+);
 criterion_main!(benches);
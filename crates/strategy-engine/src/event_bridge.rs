@@ -13,6 +13,7 @@ pub struct StrategyEventBridge {
     strategy_id: Uuid,
     strategy_name: String,
     signal_sender: EventSender<SignalEvent>,
+    mode: PublishMode,
 }
 
 impl StrategyEventBridge {
@@ -25,22 +26,50 @@ impl StrategyEventBridge {
             strategy_id,
             strategy_name: strategy_name.into(),
             signal_sender,
+            mode: PublishMode::Blocking,
         }
     }
 
+    /// Overrides the publish mode (defaults to [`PublishMode::Blocking`]).
+    pub fn with_publish_mode(mut self, mode: PublishMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
     pub fn publish(
         &self,
         decision: &StrategyDecision,
         metrics: &StrategyMetrics,
+    ) -> Result<(), EventBusError> {
+        self.publish_inner(decision, metrics, None)
+    }
+
+    /// Publishes a decision as a child of the market event that triggered it,
+    /// so the signals share its correlation id.
+    pub fn publish_caused_by(
+        &self,
+        decision: &StrategyDecision,
+        metrics: &StrategyMetrics,
+        cause: &EventMetadata,
+    ) -> Result<(), EventBusError> {
+        self.publish_inner(decision, metrics, Some(cause))
+    }
+
+    fn publish_inner(
+        &self,
+        decision: &StrategyDecision,
+        metrics: &StrategyMetrics,
+        cause: Option<&EventMetadata>,
     ) -> Result<(), EventBusError> {
         for payload in &decision.signals {
-            let mut metadata = EventMetadata::new(
-                EventSource::new(format!("strategy.{}", self.strategy_name)),
-                payload.priority,
-            );
+            let source = EventSource::new(format!("strategy.{}", self.strategy_name));
+            let mut metadata = match cause {
+                Some(parent) => parent.child(source, payload.priority),
+                None => EventMetadata::new(source, payload.priority),
+            };
             metadata.sequence = SIGNAL_SEQUENCE.fetch_add(1, Ordering::Relaxed);
             let event = SignalEvent::new(metadata, payload.clone());
-            self.signal_sender.publish(event, PublishMode::Blocking)?;
+            self.signal_sender.publish(event, self.mode)?;
         }
 
         trace!(
@@ -0,0 +1,1015 @@
+//! Strategy Host
+//!
+//! Runs many strategies in one process off the shared `MarketEvent` stream. Every
+//! hosted strategy owns its symbol filter and, per symbol it trades, a snapshot
+//! window, candle aggregation and `IndicatorState`, and moves through an explicit
+//! lifecycle. Errors and panics raised
+//! by one strategy are contained to it and surfaced through its counters.
+
+use std::collections::{HashMap, HashSet};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use event_bus::{
+    EventBusError, EventHandler, EventMetadata, EventSender, MarketEvent, MarketPayload,
+    PublishMode, RiskEvent, SignalEvent, SignalEventPayload,
+};
+use ninja_gekko_core::position_sizing::AccountSnapshot;
+use ninja_gekko_core::types::AccountId;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::event_bridge::StrategyEventBridge;
+use crate::indicators::buffer::Candle;
+use crate::indicators::state::IndicatorState;
+use crate::params::{ParamError, ParamSchema, ParamSet};
+use crate::traits::{
+    MarketSnapshot, StrategyContext, StrategyDecision, StrategyError, StrategyExecutor,
+    StrategyInitContext, StrategyMetrics,
+};
+
+const DEFAULT_CANDLE_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_INDICATOR_DEPTH: usize = 256;
+
+/// Symbols a hosted strategy receives market events for.
+#[derive(Debug, Clone, Default)]
+pub enum SymbolFilter {
+    #[default]
+    Any,
+    Only(HashSet<String>),
+}
+
+impl SymbolFilter {
+    pub fn only<I, T>(symbols: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        SymbolFilter::Only(symbols.into_iter().map(Into::into).collect())
+    }
+
+    pub fn matches(&self, symbol: &str) -> bool {
+        match self {
+            SymbolFilter::Any => true,
+            SymbolFilter::Only(symbols) => symbols.contains(symbol),
+        }
+    }
+}
+
+/// Lifecycle of a hosted strategy.
+///
+/// Market data keeps flowing into the candle and indicator state in every live state so
+/// a strategy is warm when started or resumed, but only `Running` strategies are
+/// evaluated. `Stopped` and `Failed` are terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StrategyState {
+    Registered,
+    Initialized,
+    Running,
+    Paused,
+    Stopped,
+    Failed,
+}
+
+impl StrategyState {
+    fn is_terminal(self) -> bool {
+        matches!(self, StrategyState::Stopped | StrategyState::Failed)
+    }
+}
+
+/// Errors returned by host lifecycle operations.
+#[derive(Debug, Error)]
+pub enum StrategyHostError {
+    #[error("unknown strategy {0}")]
+    UnknownStrategy(Uuid),
+    #[error("strategy {0} is already registered")]
+    DuplicateStrategy(Uuid),
+    #[error("cannot {action} strategy in state {state:?}")]
+    InvalidTransition {
+        action: &'static str,
+        state: StrategyState,
+    },
+    #[error("strategy initialization failed: {0}")]
+    Initialization(#[source] StrategyError),
+    #[error("strategy panicked: {0}")]
+    Panicked(String),
+    #[error("invalid parameters: {0}")]
+    Parameters(#[from] ParamError),
+    #[error("strategy rejected parameters: {0}")]
+    Reconfiguration(#[source] StrategyError),
+}
+
+/// Audit record of a parameter change applied to a hosted strategy.
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterChange {
+    pub strategy_id: Uuid,
+    pub strategy_name: String,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+    /// Names of parameters whose values changed.
+    pub changed: Vec<String>,
+    pub previous: ParamSet,
+    pub current: ParamSet,
+}
+
+/// Receives every applied parameter change, e.g. to persist an audit trail.
+pub trait ParameterAuditSink: Send + Sync {
+    fn record(&self, change: &ParameterChange);
+}
+
+/// Logs and signals produced by one evaluation of a hosted strategy.
+#[derive(Debug, Clone)]
+pub struct StrategyActivity {
+    pub strategy_id: Uuid,
+    pub strategy_name: String,
+    pub account_id: AccountId,
+    pub timestamp: DateTime<Utc>,
+    pub logs: Vec<String>,
+    pub signals: Vec<SignalEventPayload>,
+    pub metrics: StrategyMetrics,
+}
+
+/// Receives the output of every evaluation that logged or signalled, e.g. to stream it
+/// to operators.
+pub trait StrategyActivitySink: Send + Sync {
+    fn record(&self, activity: &StrategyActivity);
+}
+
+/// Builds a fresh `IndicatorState` for each symbol a strategy sees.
+type IndicatorFactory = Box<dyn Fn() -> IndicatorState + Send + Sync>;
+
+/// Per-strategy settings applied at registration.
+pub struct HostedStrategyConfig {
+    symbols: SymbolFilter,
+    candle_interval: Duration,
+    indicators: IndicatorFactory,
+    publish_mode: PublishMode,
+}
+
+impl Default for HostedStrategyConfig {
+    fn default() -> Self {
+        Self {
+            symbols: SymbolFilter::Any,
+            candle_interval: DEFAULT_CANDLE_INTERVAL,
+            indicators: Box::new(|| IndicatorState::new(DEFAULT_INDICATOR_DEPTH)),
+            publish_mode: PublishMode::Try,
+        }
+    }
+}
+
+impl HostedStrategyConfig {
+    pub fn with_symbols(mut self, symbols: SymbolFilter) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Width of the candles aggregated from ticks; clamped to at least one second.
+    pub fn with_candle_interval(mut self, interval: Duration) -> Self {
+        self.candle_interval = interval.max(Duration::from_secs(1));
+        self
+    }
+
+    /// Indicators exposed via `StrategyContext::indicators`. `indicators` is called once
+    /// per symbol, and each state is updated only on that symbol's closed candles.
+    pub fn with_indicators<F>(mut self, indicators: F) -> Self
+    where
+        F: Fn() -> IndicatorState + Send + Sync + 'static,
+    {
+        self.indicators = Box::new(indicators);
+        self
+    }
+
+    pub fn with_publish_mode(mut self, mode: PublishMode) -> Self {
+        self.publish_mode = mode;
+        self
+    }
+}
+
+/// Point-in-time view of a hosted strategy's counters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StrategyStats {
+    pub state: StrategyState,
+    /// Market events that passed the symbol filter.
+    pub events: u64,
+    pub evaluations: u64,
+    pub signals: u64,
+    /// Evaluations that returned an error.
+    pub errors: u64,
+    pub panics: u64,
+    pub publish_errors: u64,
+    pub last_latency: Duration,
+    pub max_latency: Duration,
+    pub total_latency: Duration,
+}
+
+impl StrategyStats {
+    pub fn mean_latency(&self) -> Duration {
+        if self.evaluations == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos((self.total_latency.as_nanos() / self.evaluations as u128) as u64)
+        }
+    }
+}
+
+/// Stats entry returned by [`StrategyHost::report`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostedStrategyReport {
+    pub strategy_id: Uuid,
+    pub name: String,
+    pub stats: StrategyStats,
+}
+
+#[derive(Default)]
+struct Counters {
+    events: AtomicU64,
+    evaluations: AtomicU64,
+    signals: AtomicU64,
+    errors: AtomicU64,
+    panics: AtomicU64,
+    publish_errors: AtomicU64,
+    last_latency_ns: AtomicU64,
+    max_latency_ns: AtomicU64,
+    total_latency_ns: AtomicU64,
+}
+
+impl Counters {
+    fn record_latency(&self, latency: Duration) {
+        let nanos = latency.as_nanos().min(u64::MAX as u128) as u64;
+        self.evaluations.fetch_add(1, Ordering::Relaxed);
+        self.last_latency_ns.store(nanos, Ordering::Relaxed);
+        self.max_latency_ns.fetch_max(nanos, Ordering::Relaxed);
+        self.total_latency_ns.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self, state: StrategyState) -> StrategyStats {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        StrategyStats {
+            state,
+            events: load(&self.events),
+            evaluations: load(&self.evaluations),
+            signals: load(&self.signals),
+            errors: load(&self.errors),
+            panics: load(&self.panics),
+            publish_errors: load(&self.publish_errors),
+            last_latency: Duration::from_nanos(load(&self.last_latency_ns)),
+            max_latency: Duration::from_nanos(load(&self.max_latency_ns)),
+            total_latency: Duration::from_nanos(load(&self.total_latency_ns)),
+        }
+    }
+}
+
+/// Folds ticks into fixed-width OHLC candles. Volume counts ticks, as the bus only
+/// carries rolling 24h volume.
+struct CandleAggregator {
+    interval_secs: i64,
+    bucket: i64,
+    current: Option<Candle>,
+}
+
+impl CandleAggregator {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval_secs: interval.as_secs().max(1) as i64,
+            bucket: i64::MIN,
+            current: None,
+        }
+    }
+
+    /// Adds a trade price, returning the previous candle once a new interval starts.
+    fn push(&mut self, price: Decimal, timestamp: i64) -> Option<Candle> {
+        let bucket = timestamp.div_euclid(self.interval_secs);
+        if bucket < self.bucket {
+            // Late tick for an interval that has already closed.
+            return None;
+        }
+        if bucket == self.bucket {
+            if let Some(candle) = self.current.as_mut() {
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                candle.close = price;
+                candle.volume += Decimal::ONE;
+            }
+            return None;
+        }
+
+        self.bucket = bucket;
+        self.current.replace(Candle {
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: Decimal::ONE,
+            timestamp: bucket * self.interval_secs,
+        })
+    }
+}
+
+/// Decision of a strategy callback and how long it took.
+type Evaluation = Result<(StrategyDecision, Duration), StrategyError>;
+
+/// Snapshot window, candles and indicators of one symbol a strategy receives.
+struct SymbolMarket<const N: usize> {
+    snapshots: [MarketSnapshot; N],
+    candles: CandleAggregator,
+    indicators: IndicatorState,
+}
+
+impl<const N: usize> SymbolMarket<N> {
+    fn new(candle_interval: Duration, indicators: IndicatorState) -> Self {
+        Self {
+            snapshots: std::array::from_fn(|_| empty_snapshot()),
+            candles: CandleAggregator::new(candle_interval),
+            indicators,
+        }
+    }
+}
+
+/// Mutable state of a hosted strategy, guarded by its own lock.
+struct Slot<const N: usize> {
+    strategy: Box<dyn StrategyExecutor<N>>,
+    state: StrategyState,
+    candle_interval: Duration,
+    new_indicators: IndicatorFactory,
+    markets: HashMap<String, SymbolMarket<N>>,
+}
+
+impl<const N: usize> Slot<N> {
+    /// Folds the event into its symbol's market state and evaluates the strategy if it
+    /// is running.
+    fn process(
+        &mut self,
+        symbol: &str,
+        event: &MarketEvent,
+        account_id: &AccountId,
+        account: Option<&AccountSnapshot>,
+    ) -> Option<Evaluation> {
+        let Slot {
+            strategy,
+            state,
+            candle_interval,
+            new_indicators,
+            markets,
+        } = self;
+        let market = markets
+            .entry(symbol.to_string())
+            .or_insert_with(|| SymbolMarket::new(*candle_interval, new_indicators()));
+
+        let mut closed = None;
+        if let MarketPayload::Tick { tick, .. } = event.payload() {
+            if N > 0 {
+                market.snapshots.rotate_left(1);
+                market.snapshots[N - 1] = MarketSnapshot {
+                    symbol: tick.symbol.clone(),
+                    bid: tick.bid,
+                    ask: tick.ask,
+                    last: tick.last,
+                    timestamp: tick.timestamp,
+                };
+            }
+            closed = market.candles.push(tick.last, tick.timestamp.timestamp());
+            if let Some(candle) = &closed {
+                market.indicators.update(candle.clone());
+            }
+        }
+
+        if *state != StrategyState::Running {
+            return None;
+        }
+
+        let SymbolMarket {
+            snapshots,
+            indicators,
+            ..
+        } = market;
+        let mut ctx = StrategyContext::new(account_id, snapshots, Uuid::new_v4(), Utc::now())
+            .with_events(std::slice::from_ref(event))
+            .with_indicators(indicators);
+        if let Some(candle) = &closed {
+            ctx = ctx.with_closed_candle(candle);
+        }
+        if let Some(account) = account {
+            ctx = ctx.with_account(account);
+        }
+
+        let started = Instant::now();
+        let result = strategy.evaluate(ctx);
+        Some(result.map(|decision| (decision, started.elapsed())))
+    }
+}
+
+struct HostedStrategy<const N: usize> {
+    id: Uuid,
+    name: String,
+    symbols: SymbolFilter,
+    bridge: StrategyEventBridge,
+    slot: Mutex<Slot<N>>,
+    counters: Counters,
+}
+
+impl<const N: usize> HostedStrategy<N> {
+    fn lock(&self) -> MutexGuard<'_, Slot<N>> {
+        // Panics are caught while the guard is held, so poisoning is not expected;
+        // recover the state rather than taking the whole host down if it happens.
+        self.slot.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn stats(&self) -> StrategyStats {
+        let state = self.lock().state;
+        self.counters.snapshot(state)
+    }
+
+    fn initialize(
+        &self,
+        slot: &mut Slot<N>,
+        account_id: &AccountId,
+    ) -> Result<(), StrategyHostError> {
+        let ctx = StrategyInitContext {
+            strategy_id: self.id,
+            account_id,
+        };
+        match panic::catch_unwind(AssertUnwindSafe(|| slot.strategy.initialize(ctx))) {
+            Ok(Ok(())) => {
+                slot.state = StrategyState::Initialized;
+                Ok(())
+            }
+            Ok(Err(err)) => {
+                slot.state = StrategyState::Failed;
+                self.counters.errors.fetch_add(1, Ordering::Relaxed);
+                Err(StrategyHostError::Initialization(err))
+            }
+            Err(payload) => {
+                slot.state = StrategyState::Failed;
+                self.counters.panics.fetch_add(1, Ordering::Relaxed);
+                Err(StrategyHostError::Panicked(panic_message(payload.as_ref())))
+            }
+        }
+    }
+
+    fn on_event(
+        &self,
+        event: &MarketEvent,
+        account_id: &AccountId,
+        account: Option<&AccountSnapshot>,
+        activity_sink: Option<&dyn StrategyActivitySink>,
+    ) {
+        let symbol = match event_symbol(event.payload()) {
+            Some(symbol) if self.symbols.matches(symbol) => symbol,
+            _ => return,
+        };
+
+        let mut slot = self.lock();
+        if slot.state.is_terminal() {
+            return;
+        }
+        self.counters.events.fetch_add(1, Ordering::Relaxed);
+
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+            slot.process(symbol, event, account_id, account)
+        }));
+        self.settle(
+            &mut slot,
+            outcome,
+            event.metadata(),
+            account_id,
+            activity_sink,
+        );
+    }
+
+    /// Lets a running or paused strategy react to a risk action.
+    fn on_risk(
+        &self,
+        event: &RiskEvent,
+        account_id: &AccountId,
+        activity_sink: Option<&dyn StrategyActivitySink>,
+    ) {
+        let mut slot = self.lock();
+        if !matches!(slot.state, StrategyState::Running | StrategyState::Paused) {
+            return;
+        }
+
+        let action = &event.payload().action;
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+            let started = Instant::now();
+            let result = slot.strategy.on_risk(action);
+            Some(result.map(|decision| (decision, started.elapsed())))
+        }));
+        self.settle(
+            &mut slot,
+            outcome,
+            event.metadata(),
+            account_id,
+            activity_sink,
+        );
+    }
+
+    /// Records and publishes the outcome of a strategy callback, containing panics.
+    fn settle(
+        &self,
+        slot: &mut Slot<N>,
+        outcome: std::thread::Result<Option<Evaluation>>,
+        cause: &EventMetadata,
+        account_id: &AccountId,
+        activity_sink: Option<&dyn StrategyActivitySink>,
+    ) {
+        match outcome {
+            Ok(None) => {}
+            Ok(Some(Ok((decision, latency)))) => {
+                self.counters.record_latency(latency);
+                if let Some(sink) = activity_sink {
+                    self.report_activity(sink, account_id, &decision, latency);
+                }
+                self.publish(cause, decision, latency);
+            }
+            Ok(Some(Err(err))) => {
+                self.counters.errors.fetch_add(1, Ordering::Relaxed);
+                warn!(strategy = %self.name, id = %self.id, "strategy evaluation failed: {}", err);
+            }
+            Err(payload) => {
+                slot.state = StrategyState::Failed;
+                self.counters.panics.fetch_add(1, Ordering::Relaxed);
+                error!(
+                    strategy = %self.name,
+                    id = %self.id,
+                    "strategy panicked and was disabled: {}",
+                    panic_message(payload.as_ref())
+                );
+            }
+        }
+    }
+
+    fn publish(&self, cause: &EventMetadata, decision: StrategyDecision, latency: Duration) {
+        for log in &decision.logs {
+            debug!(strategy = %self.name, "strategy log: {}", log);
+        }
+        if decision.signals.is_empty() {
+            return;
+        }
+
+        let metrics = StrategyMetrics {
+            evaluation_latency: latency,
+            ..decision.metrics.clone()
+        };
+        match self.bridge.publish_caused_by(&decision, &metrics, cause) {
+            Ok(()) => {
+                self.counters
+                    .signals
+                    .fetch_add(decision.signals.len() as u64, Ordering::Relaxed);
+            }
+            Err(err) => {
+                self.counters.publish_errors.fetch_add(1, Ordering::Relaxed);
+                error!(strategy = %self.name, id = %self.id, "failed to publish strategy signals: {}", err);
+            }
+        }
+    }
+
+    fn report_activity(
+        &self,
+        sink: &dyn StrategyActivitySink,
+        account_id: &AccountId,
+        decision: &StrategyDecision,
+        latency: Duration,
+    ) {
+        if decision.logs.is_empty() && decision.signals.is_empty() {
+            return;
+        }
+        sink.record(&StrategyActivity {
+            strategy_id: self.id,
+            strategy_name: self.name.clone(),
+            account_id: account_id.clone(),
+            timestamp: Utc::now(),
+            logs: decision.logs.clone(),
+            signals: decision.signals.clone(),
+            metrics: StrategyMetrics {
+                evaluation_latency: latency,
+                ..decision.metrics.clone()
+            },
+        });
+    }
+}
+
+/// Hosts many strategies behind a single `EventHandler<MarketEvent>`.
+pub struct StrategyHost<const N: usize> {
+    account_id: AccountId,
+    signal_sender: EventSender<SignalEvent>,
+    strategies: RwLock<Vec<Arc<HostedStrategy<N>>>>,
+    parameter_log: Mutex<Vec<ParameterChange>>,
+    audit_sink: Option<Arc<dyn ParameterAuditSink>>,
+    activity_sink: Option<Arc<dyn StrategyActivitySink>>,
+    account: Option<Arc<RwLock<AccountSnapshot>>>,
+}
+
+impl<const N: usize> StrategyHost<N> {
+    pub fn new(signal_sender: EventSender<SignalEvent>, account_id: AccountId) -> Self {
+        Self {
+            account_id,
+            signal_sender,
+            strategies: RwLock::new(Vec::new()),
+            parameter_log: Mutex::new(Vec::new()),
+            audit_sink: None,
+            activity_sink: None,
+            account: None,
+        }
+    }
+
+    /// Forwards every applied parameter change to `sink` in addition to the in-memory log.
+    pub fn with_audit_sink(mut self, sink: Arc<dyn ParameterAuditSink>) -> Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
+    /// Forwards the logs and signals of every evaluation to `sink`.
+    pub fn with_activity_sink(mut self, sink: Arc<dyn StrategyActivitySink>) -> Self {
+        self.activity_sink = Some(sink);
+        self
+    }
+
+    /// Exposes the account's live equity and positions to strategies through
+    /// `StrategyContext::account`. The owner keeps it current, e.g. from
+    /// `PerformanceTracker::account_snapshot`.
+    pub fn with_account(mut self, account: Arc<RwLock<AccountSnapshot>>) -> Self {
+        self.account = Some(account);
+        self
+    }
+
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    /// Adds a strategy in the `Registered` state and returns its id.
+    pub fn register<S>(&self, strategy: S, config: HostedStrategyConfig) -> Uuid
+    where
+        S: StrategyExecutor<N> + 'static,
+    {
+        let id = Uuid::new_v4();
+        let hosted = self.hosted(id, Box::new(strategy), config);
+        self.write().push(hosted);
+        id
+    }
+
+    /// Adds a strategy under a caller-chosen id, e.g. one shared with an external registry.
+    pub fn register_as<S>(
+        &self,
+        id: Uuid,
+        strategy: S,
+        config: HostedStrategyConfig,
+    ) -> Result<(), StrategyHostError>
+    where
+        S: StrategyExecutor<N> + 'static,
+    {
+        let mut strategies = self.write();
+        if strategies.iter().any(|hosted| hosted.id == id) {
+            return Err(StrategyHostError::DuplicateStrategy(id));
+        }
+        strategies.push(self.hosted(id, Box::new(strategy), config));
+        Ok(())
+    }
+
+    fn hosted(
+        &self,
+        id: Uuid,
+        strategy: Box<dyn StrategyExecutor<N>>,
+        config: HostedStrategyConfig,
+    ) -> Arc<HostedStrategy<N>> {
+        let name = strategy.name().to_string();
+        let bridge = StrategyEventBridge::new(id, name.clone(), self.signal_sender.clone())
+            .with_publish_mode(config.publish_mode);
+        let hosted = HostedStrategy {
+            id,
+            name,
+            symbols: config.symbols,
+            bridge,
+            slot: Mutex::new(Slot {
+                strategy,
+                state: StrategyState::Registered,
+                candle_interval: config.candle_interval,
+                new_indicators: config.indicators,
+                markets: HashMap::new(),
+            }),
+            counters: Counters::default(),
+        };
+        info!(strategy = %hosted.name, id = %id, "strategy registered");
+        Arc::new(hosted)
+    }
+
+    /// Runs `StrategyExecutor::initialize`; a failure or panic moves the strategy to `Failed`.
+    pub fn initialize(&self, id: Uuid) -> Result<(), StrategyHostError> {
+        let hosted = self.get(id)?;
+        let mut slot = hosted.lock();
+        match slot.state {
+            StrategyState::Registered => hosted.initialize(&mut slot, &self.account_id),
+            state => Err(StrategyHostError::InvalidTransition {
+                action: "initialize",
+                state,
+            }),
+        }
+    }
+
+    /// Starts or resumes evaluation, initializing the strategy first if needed.
+    pub fn start(&self, id: Uuid) -> Result<(), StrategyHostError> {
+        let hosted = self.get(id)?;
+        let mut slot = hosted.lock();
+        match slot.state {
+            StrategyState::Registered => hosted.initialize(&mut slot, &self.account_id)?,
+            StrategyState::Initialized | StrategyState::Paused => {}
+            state => {
+                return Err(StrategyHostError::InvalidTransition {
+                    action: "start",
+                    state,
+                })
+            }
+        }
+        slot.state = StrategyState::Running;
+        info!(strategy = %hosted.name, id = %id, "strategy started");
+        Ok(())
+    }
+
+    /// Suspends evaluation while continuing to feed candles and indicators.
+    pub fn pause(&self, id: Uuid) -> Result<(), StrategyHostError> {
+        let hosted = self.get(id)?;
+        let mut slot = hosted.lock();
+        if slot.state != StrategyState::Running {
+            return Err(StrategyHostError::InvalidTransition {
+                action: "pause",
+                state: slot.state,
+            });
+        }
+        slot.state = StrategyState::Paused;
+        info!(strategy = %hosted.name, id = %id, "strategy paused");
+        Ok(())
+    }
+
+    /// Permanently stops a strategy; it keeps reporting stats until removed.
+    pub fn stop(&self, id: Uuid) -> Result<(), StrategyHostError> {
+        let hosted = self.get(id)?;
+        let mut slot = hosted.lock();
+        if slot.state == StrategyState::Stopped {
+            return Err(StrategyHostError::InvalidTransition {
+                action: "stop",
+                state: slot.state,
+            });
+        }
+        slot.state = StrategyState::Stopped;
+        info!(strategy = %hosted.name, id = %id, "strategy stopped");
+        Ok(())
+    }
+
+    /// Replaces the executor of a live strategy without interrupting its market state.
+    ///
+    /// The replacement is initialized under the strategy's lock before it is swapped in,
+    /// so every event is evaluated by exactly one of the two executors. The candle and
+    /// indicator state, lifecycle state and counters carry over; the registered name is
+    /// kept. If the replacement fails to initialize the previous executor stays in place.
+    pub fn swap_strategy<S>(&self, id: Uuid, strategy: S) -> Result<(), StrategyHostError>
+    where
+        S: StrategyExecutor<N> + 'static,
+    {
+        let hosted = self.get(id)?;
+        let mut slot = hosted.lock();
+        if slot.state.is_terminal() {
+            return Err(StrategyHostError::InvalidTransition {
+                action: "swap",
+                state: slot.state,
+            });
+        }
+
+        let mut replacement: Box<dyn StrategyExecutor<N>> = Box::new(strategy);
+        if slot.state != StrategyState::Registered {
+            let ctx = StrategyInitContext {
+                strategy_id: id,
+                account_id: &self.account_id,
+            };
+            match panic::catch_unwind(AssertUnwindSafe(|| replacement.initialize(ctx))) {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => return Err(StrategyHostError::Initialization(err)),
+                Err(payload) => {
+                    return Err(StrategyHostError::Panicked(panic_message(payload.as_ref())))
+                }
+            }
+        }
+        slot.strategy = replacement;
+        info!(strategy = %hosted.name, id = %id, "strategy executor swapped");
+        Ok(())
+    }
+
+    /// Removes a strategy from the host, returning its final stats.
+    pub fn remove(&self, id: Uuid) -> Result<StrategyStats, StrategyHostError> {
+        let hosted = {
+            let mut strategies = self.write();
+            let index = strategies
+                .iter()
+                .position(|hosted| hosted.id == id)
+                .ok_or(StrategyHostError::UnknownStrategy(id))?;
+            strategies.remove(index)
+        };
+        Ok(hosted.stats())
+    }
+
+    pub fn parameter_schema(&self, id: Uuid) -> Result<ParamSchema, StrategyHostError> {
+        Ok(self.get(id)?.lock().strategy.parameter_schema())
+    }
+
+    pub fn parameters(&self, id: Uuid) -> Result<ParamSet, StrategyHostError> {
+        Ok(self.get(id)?.lock().strategy.parameters())
+    }
+
+    /// Validates `changes` against the strategy's schema and applies them in place.
+    ///
+    /// The strategy keeps running with its candle and indicator state; a rejected update
+    /// leaves the previous parameters in effect. Applied changes are recorded for audit.
+    pub fn update_parameters(
+        &self,
+        id: Uuid,
+        changes: &HashMap<String, Value>,
+        changed_by: impl Into<String>,
+    ) -> Result<ParameterChange, StrategyHostError> {
+        let hosted = self.get(id)?;
+        let mut slot = hosted.lock();
+        if slot.state.is_terminal() {
+            return Err(StrategyHostError::InvalidTransition {
+                action: "reconfigure",
+                state: slot.state,
+            });
+        }
+
+        let previous = slot.strategy.parameters();
+        let next = slot.strategy.parameter_schema().apply(&previous, changes)?;
+        match panic::catch_unwind(AssertUnwindSafe(|| slot.strategy.update_parameters(&next))) {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => return Err(StrategyHostError::Reconfiguration(err)),
+            Err(payload) => {
+                slot.state = StrategyState::Failed;
+                hosted.counters.panics.fetch_add(1, Ordering::Relaxed);
+                return Err(StrategyHostError::Panicked(panic_message(payload.as_ref())));
+            }
+        }
+        drop(slot);
+
+        let change = ParameterChange {
+            strategy_id: id,
+            strategy_name: hosted.name.clone(),
+            changed_by: changed_by.into(),
+            changed_at: Utc::now(),
+            changed: next.changed_from(&previous),
+            previous,
+            current: next,
+        };
+        info!(
+            strategy = %hosted.name,
+            id = %id,
+            changed_by = %change.changed_by,
+            changed = ?change.changed,
+            "strategy parameters updated"
+        );
+        if let Some(sink) = &self.audit_sink {
+            sink.record(&change);
+        }
+        self.parameter_log
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(change.clone());
+        Ok(change)
+    }
+
+    /// Parameter changes applied to a strategy, oldest first.
+    pub fn parameter_history(&self, id: Uuid) -> Vec<ParameterChange> {
+        self.parameter_log
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|change| change.strategy_id == id)
+            .cloned()
+            .collect()
+    }
+
+    pub fn state(&self, id: Uuid) -> Option<StrategyState> {
+        self.get(id).ok().map(|hosted| hosted.lock().state)
+    }
+
+    pub fn stats(&self, id: Uuid) -> Option<StrategyStats> {
+        self.get(id).ok().map(|hosted| hosted.stats())
+    }
+
+    /// Stats for every hosted strategy in registration order.
+    pub fn report(&self) -> Vec<HostedStrategyReport> {
+        self.snapshot()
+            .iter()
+            .map(|hosted| HostedStrategyReport {
+                strategy_id: hosted.id,
+                name: hosted.name.clone(),
+                stats: hosted.stats(),
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Delivers a market event to every strategy whose filter matches it.
+    pub fn dispatch(&self, event: &MarketEvent) {
+        // Strategies size against one consistent view of the account per event.
+        let account = self.account.as_ref().map(|account| {
+            account
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone()
+        });
+        for hosted in self.snapshot() {
+            hosted.on_event(
+                event,
+                &self.account_id,
+                account.as_ref(),
+                self.activity_sink.as_deref(),
+            );
+        }
+    }
+
+    /// Delivers a risk action to every running or paused strategy.
+    pub fn dispatch_risk(&self, event: &RiskEvent) {
+        for hosted in self.snapshot() {
+            hosted.on_risk(event, &self.account_id, self.activity_sink.as_deref());
+        }
+    }
+
+    fn get(&self, id: Uuid) -> Result<Arc<HostedStrategy<N>>, StrategyHostError> {
+        self.read()
+            .iter()
+            .find(|hosted| hosted.id == id)
+            .cloned()
+            .ok_or(StrategyHostError::UnknownStrategy(id))
+    }
+
+    /// Clones the strategy list so dispatch does not hold the registry lock.
+    fn snapshot(&self) -> Vec<Arc<HostedStrategy<N>>> {
+        self.read().clone()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Vec<Arc<HostedStrategy<N>>>> {
+        self.strategies
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Vec<Arc<HostedStrategy<N>>>> {
+        self.strategies
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[async_trait]
+impl<const N: usize> EventHandler<MarketEvent> for StrategyHost<N> {
+    async fn handle(&self, event: MarketEvent) -> Result<(), EventBusError> {
+        self.dispatch(&event);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<const N: usize> EventHandler<RiskEvent> for StrategyHost<N> {
+    async fn handle(&self, event: RiskEvent) -> Result<(), EventBusError> {
+        self.dispatch_risk(&event);
+        Ok(())
+    }
+}
+
+fn empty_snapshot() -> MarketSnapshot {
+    MarketSnapshot {
+        symbol: String::new(),
+        bid: Decimal::ZERO,
+        ask: Decimal::ZERO,
+        last: Decimal::ZERO,
+        timestamp: Utc::now(),
+    }
+}
+
+fn event_symbol(payload: &MarketPayload) -> Option<&str> {
+    match payload {
+        MarketPayload::Tick { tick, .. } => Some(&tick.symbol),
+        MarketPayload::OrderBookSnapshot { pair, .. } => Some(&pair.symbol),
+        MarketPayload::OrderBookDelta { pair, .. } => Some(&pair.symbol),
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
@@ -67,6 +67,11 @@ impl CandleBuffer {
         self.inner.len() >= self.capacity
     }
 
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     /// Get last N candles (most recent last)
     pub fn last_n(&self, n: usize) -> impl Iterator<Item = &Candle> {
         self.inner.iter().rev().take(n).rev()
@@ -77,3 +82,72 @@ impl CandleBuffer {
         self.inner.back()
     }
 }
+
+/// Sliding-window maximum or minimum with amortized O(1) updates.
+///
+/// Keeps a monotonic queue of candidates; among equal values the most recent wins.
+#[derive(Debug, Clone)]
+pub struct RollingExtremum {
+    period: usize,
+    keep_max: bool,
+    entries: VecDeque<(usize, f64)>,
+    seen: usize,
+}
+
+impl RollingExtremum {
+    /// Highest value of the last `period` samples.
+    pub fn max(period: usize) -> Self {
+        Self::new(period, true)
+    }
+
+    /// Lowest value of the last `period` samples.
+    pub fn min(period: usize) -> Self {
+        Self::new(period, false)
+    }
+
+    fn new(period: usize, keep_max: bool) -> Self {
+        Self {
+            period: period.max(1),
+            keep_max,
+            entries: VecDeque::new(),
+            seen: 0,
+        }
+    }
+
+    /// Adds a sample and returns the extreme of the window.
+    pub fn push(&mut self, value: f64) -> f64 {
+        let keep_max = self.keep_max;
+        while let Some((_, last)) = self.entries.back() {
+            let dominated = if keep_max {
+                *last <= value
+            } else {
+                *last >= value
+            };
+            if !dominated {
+                break;
+            }
+            self.entries.pop_back();
+        }
+        self.entries.push_back((self.seen, value));
+        self.seen += 1;
+        while let Some((index, _)) = self.entries.front() {
+            if index + self.period >= self.seen {
+                break;
+            }
+            self.entries.pop_front();
+        }
+        self.entries[0].1
+    }
+
+    /// Extreme of the window and how many samples ago it occurred.
+    pub fn extreme(&self) -> Option<(f64, usize)> {
+        self.entries
+            .front()
+            .map(|(index, value)| (*value, self.seen - 1 - index))
+    }
+
+    /// Whether a full window has been seen.
+    pub fn is_full(&self) -> bool {
+        self.seen >= self.period
+    }
+}
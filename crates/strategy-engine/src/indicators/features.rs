@@ -0,0 +1,109 @@
+//! Indicator columns for the neural-engine feature pipeline
+//!
+//! Implements [`IndicatorFactory`] over this module's indicators so feature
+//! sets used for model training and live inference compute indicators the
+//! same way strategies do.
+
+use crate::indicators::prelude::*;
+use crate::indicators::registry::create_indicator;
+use crate::indicators::{dec_to_f64, f64_to_dec};
+use neural_engine::features::{FeatureBar, FeatureIndicator, IndicatorFactory};
+use neural_engine::{NeuralError, NeuralResult};
+
+/// Adapts a strategy [`Indicator`] to the feature pipeline
+pub struct IndicatorFeature<I: Indicator> {
+    inner: I,
+}
+
+impl<I: Indicator> IndicatorFeature<I> {
+    pub fn new(inner: I) -> Self {
+        Self { inner }
+    }
+}
+
+impl<I: Indicator> FeatureIndicator for IndicatorFeature<I> {
+    fn update(&mut self, bar: &FeatureBar) -> Option<f64> {
+        let candle = Candle {
+            open: f64_to_dec(bar.open),
+            high: f64_to_dec(bar.high),
+            low: f64_to_dec(bar.low),
+            close: f64_to_dec(bar.close),
+            volume: f64_to_dec(bar.volume),
+            timestamp: bar.timestamp.timestamp(),
+        };
+        let value = self.inner.update_ohlcv(&candle);
+        self.inner.is_ready().then(|| dec_to_f64(value.value))
+    }
+}
+
+/// Builds feature columns from the indicators in the [`registry`].
+///
+/// Names are case-insensitive; `params` are the constructor arguments in
+/// order, with the registered defaults when omitted.
+///
+/// [`registry`]: crate::indicators::registry
+pub struct StrategyIndicators;
+
+impl IndicatorFactory for StrategyIndicators {
+    fn create(&self, name: &str, params: &[f64]) -> NeuralResult<Box<dyn FeatureIndicator>> {
+        let indicator = create_indicator(name, params).map_err(NeuralError::InvalidInput)?;
+        Ok(Box::new(IndicatorFeature::new(indicator)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+    use neural_engine::features::{FeatureKind, FeaturePipeline, FeatureSetSpec};
+
+    #[test]
+    fn test_indicator_columns_match_strategy_indicators() {
+        let spec = FeatureSetSpec::new(
+            "indicators",
+            1,
+            vec![
+                FeatureKind::LogReturn { lag: 1 },
+                FeatureKind::Indicator {
+                    name: "SMA".to_string(),
+                    params: vec![5.0],
+                },
+                FeatureKind::Indicator {
+                    name: "rsi".to_string(),
+                    params: vec![],
+                },
+            ],
+        );
+        let start = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let bars: Vec<FeatureBar> = (0..40)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.4).sin() * 3.0;
+                FeatureBar {
+                    timestamp: start + Duration::minutes(i),
+                    open: close,
+                    high: close + 1.0,
+                    low: close - 1.0,
+                    close,
+                    volume: 500.0,
+                    bid: None,
+                    ask: None,
+                    bid_size: None,
+                    ask_size: None,
+                }
+            })
+            .collect();
+
+        let rows = FeaturePipeline::transform_batch(&spec, &StrategyIndicators, &bars).unwrap();
+
+        let mut sma = Sma::new(5);
+        for bar in &bars[..39] {
+            sma.update(f64_to_dec(bar.close));
+        }
+        let expected = dec_to_f64(sma.update(f64_to_dec(bars[39].close)).value);
+        let last = rows[39].as_ref().expect("warmed up");
+        assert!((last[1] - expected).abs() < 1e-9);
+        assert!((0.0..=100.0).contains(&last[2]));
+
+        assert!(StrategyIndicators.create("nonsense", &[]).is_err());
+    }
+}
@@ -2,8 +2,11 @@ use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 
 pub mod buffer;
+pub mod features;
 pub mod momentum;
+pub mod registry;
 pub mod state;
+pub mod statistics;
 pub mod trend;
 pub mod volatility;
 pub mod volume;
@@ -14,6 +17,68 @@ pub struct IndicatorValue {
     pub signal: Option<Decimal>,
 }
 
+/// Latest reading of an indicator with each output line named.
+///
+/// Single-line indicators report `value`, plus `signal` when they have one;
+/// multi-line indicators name every line, e.g. `upper`/`middle`/`lower` for bands
+/// or `macd`/`signal`/`histogram` for MACD. Lines keep the indicator's order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndicatorOutput {
+    components: Vec<(&'static str, Decimal)>,
+}
+
+impl IndicatorOutput {
+    /// Name of the primary line of single-line indicators.
+    pub const VALUE: &'static str = "value";
+    /// Name of the signal line of single-line indicators.
+    pub const SIGNAL: &'static str = "signal";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a named line.
+    pub fn with(mut self, name: &'static str, value: Decimal) -> Self {
+        self.components.push((name, value));
+        self
+    }
+
+    /// Appends a named line computed in floating point.
+    pub fn with_f64(self, name: &'static str, value: f64) -> Self {
+        self.with(name, f64_to_dec(value))
+    }
+
+    /// Value of the line called `name`, ignoring ASCII case.
+    pub fn get(&self, name: &str) -> Option<Decimal> {
+        self.components
+            .iter()
+            .find(|(component, _)| component.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, Decimal)> + '_ {
+        self.components.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+impl From<IndicatorValue> for IndicatorOutput {
+    fn from(value: IndicatorValue) -> Self {
+        let output = Self::new().with(Self::VALUE, value.value);
+        match value.signal {
+            Some(signal) => output.with(Self::SIGNAL, signal),
+            None => output,
+        }
+    }
+}
+
 pub trait Indicator: Send {
     fn name(&self) -> &'static str;
     fn update(&mut self, price: Decimal) -> IndicatorValue;
@@ -21,10 +86,39 @@ pub trait Indicator: Send {
         self.update(candle.close)
     }
     fn current(&self) -> Option<IndicatorValue>;
+    /// Latest reading with every output line named; `value` and `signal` unless
+    /// the indicator has more lines to report.
+    fn output(&self) -> Option<IndicatorOutput> {
+        self.current().map(IndicatorOutput::from)
+    }
     fn warmup_period(&self) -> usize;
     fn is_ready(&self) -> bool;
 }
 
+impl<I: Indicator + ?Sized> Indicator for Box<I> {
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+    fn update(&mut self, price: Decimal) -> IndicatorValue {
+        (**self).update(price)
+    }
+    fn update_ohlcv(&mut self, candle: &buffer::Candle) -> IndicatorValue {
+        (**self).update_ohlcv(candle)
+    }
+    fn current(&self) -> Option<IndicatorValue> {
+        (**self).current()
+    }
+    fn output(&self) -> Option<IndicatorOutput> {
+        (**self).output()
+    }
+    fn warmup_period(&self) -> usize {
+        (**self).warmup_period()
+    }
+    fn is_ready(&self) -> bool {
+        (**self).is_ready()
+    }
+}
+
 pub fn dec_to_f64(d: Decimal) -> f64 {
     d.to_f64().unwrap_or(0.0)
 }
@@ -36,11 +130,19 @@ pub fn f64_to_dec(f: f64) -> Decimal {
 pub mod prelude {
     pub use super::buffer::{Candle, CandleBuffer};
     pub use super::Indicator;
-    pub use super::IndicatorValue;
+    pub use super::state::{IndicatorInput, IndicatorState};
+    pub use super::{IndicatorOutput, IndicatorValue};
 
     // Re-export common indicators
     pub use super::momentum::{Cci, Rsi, Stochastic, WilliamsR};
-    pub use super::trend::{Adx, Ema, Macd, Sma};
-    pub use super::volatility::{Atr, BollingerBands, BollingerBandsOutput, KeltnerChannels};
+    pub use super::statistics::{LinRegSlope, RollingZScore};
+    pub use super::trend::{
+        Adx, Aroon, Ema, HullMa, Ichimoku, IchimokuOutput, Kama, Macd, ParabolicSar, Sma,
+        SuperTrend, Tema,
+    };
+    pub use super::volatility::{
+        Atr, BollingerBands, BollingerBandsOutput, DonchianChannels, DonchianOutput,
+        KeltnerChannels,
+    };
     pub use super::volume::{Mfi, Obv, Vwap};
 }
@@ -1,4 +1,6 @@
-use crate::indicators::{buffer, dec_to_f64, f64_to_dec, Indicator, IndicatorValue};
+use crate::indicators::{
+    buffer, dec_to_f64, f64_to_dec, Indicator, IndicatorOutput, IndicatorValue,
+};
 use rust_decimal::Decimal;
 use std::collections::VecDeque;
 
@@ -198,6 +200,14 @@ impl Indicator for Stochastic {
         })
     }
 
+    fn output(&self) -> Option<IndicatorOutput> {
+        Some(
+            IndicatorOutput::new()
+                .with_f64("k", self.k_val)
+                .with_f64("d", self.d_val),
+        )
+    }
+
     fn warmup_period(&self) -> usize {
         self.period
     }
@@ -0,0 +1,167 @@
+//! Indicators constructible by name
+//!
+//! The registry is how callers outside strategy code pick indicators at run time:
+//! feature sets for the neural engine and indicator requests through the API. It
+//! lists every indicator with its constructor parameters and their defaults, so
+//! all of them build the same indicator from the same name.
+
+use serde::Serialize;
+
+use crate::indicators::prelude::*;
+
+/// Constructor parameter of a registered indicator.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct IndicatorParam {
+    pub name: &'static str,
+    pub default: f64,
+}
+
+/// Registered indicator and its constructor parameters, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct IndicatorSpec {
+    pub name: &'static str,
+    pub params: &'static [IndicatorParam],
+}
+
+const fn param(name: &'static str, default: f64) -> IndicatorParam {
+    IndicatorParam { name, default }
+}
+
+const fn spec(name: &'static str, params: &'static [IndicatorParam]) -> IndicatorSpec {
+    IndicatorSpec { name, params }
+}
+
+/// Every indicator [`create_indicator`] can build.
+pub const INDICATORS: &[IndicatorSpec] = &[
+    spec("sma", &[param("period", 20.0)]),
+    spec("ema", &[param("period", 20.0)]),
+    spec("rsi", &[param("period", 14.0)]),
+    spec("cci", &[param("period", 20.0)]),
+    spec("williams_r", &[param("period", 14.0)]),
+    spec("stochastic", &[param("period", 14.0)]),
+    spec("adx", &[param("period", 14.0)]),
+    spec("atr", &[param("period", 14.0)]),
+    spec("mfi", &[param("period", 14.0)]),
+    spec(
+        "macd",
+        &[
+            param("fast", 12.0),
+            param("slow", 26.0),
+            param("signal", 9.0),
+        ],
+    ),
+    spec(
+        "bollinger",
+        &[param("period", 20.0), param("multiplier", 2.0)],
+    ),
+    spec(
+        "keltner",
+        &[param("period", 20.0), param("multiplier", 2.0)],
+    ),
+    spec("hma", &[param("period", 20.0)]),
+    spec(
+        "kama",
+        &[
+            param("period", 10.0),
+            param("fast", 2.0),
+            param("slow", 30.0),
+        ],
+    ),
+    spec("tema", &[param("period", 20.0)]),
+    spec(
+        "ichimoku",
+        &[
+            param("conversion", 9.0),
+            param("base", 26.0),
+            param("span_b", 52.0),
+            param("displacement", 26.0),
+        ],
+    ),
+    spec(
+        "supertrend",
+        &[param("period", 10.0), param("multiplier", 3.0)],
+    ),
+    spec("psar", &[param("step", 0.02), param("max_step", 0.2)]),
+    spec("donchian", &[param("period", 20.0)]),
+    spec("aroon", &[param("period", 25.0)]),
+    spec("zscore", &[param("period", 20.0)]),
+    spec("linreg_slope", &[param("period", 20.0)]),
+    spec("obv", &[]),
+    spec("vwap", &[]),
+];
+
+/// Registry entry for `name`, ignoring case.
+pub fn indicator_spec(name: &str) -> Option<&'static IndicatorSpec> {
+    INDICATORS
+        .iter()
+        .find(|spec| spec.name.eq_ignore_ascii_case(name))
+}
+
+/// Builds the indicator registered as `name` from its constructor parameters in
+/// order; omitted trailing parameters take their defaults. Periods are rounded
+/// and at least one.
+pub fn create_indicator(name: &str, params: &[f64]) -> Result<Box<dyn Indicator>, String> {
+    let spec = indicator_spec(name).ok_or_else(|| format!("unknown indicator '{}'", name))?;
+    if params.len() > spec.params.len() {
+        return Err(format!(
+            "{} takes at most {} parameters, got {}",
+            spec.name,
+            spec.params.len(),
+            params.len()
+        ));
+    }
+    if let Some(bad) = params.iter().find(|p| !p.is_finite()) {
+        return Err(format!("{} parameter {} is not finite", spec.name, bad));
+    }
+    let factor = |i: usize| params.get(i).copied().unwrap_or(spec.params[i].default);
+    let period = |i: usize| factor(i).round().max(1.0) as usize;
+
+    let indicator: Box<dyn Indicator> = match spec.name {
+        "sma" => Box::new(Sma::new(period(0))),
+        "ema" => Box::new(Ema::new(period(0))),
+        "rsi" => Box::new(Rsi::new(period(0))),
+        "cci" => Box::new(Cci::new(period(0))),
+        "williams_r" => Box::new(WilliamsR::new(period(0))),
+        "stochastic" => Box::new(Stochastic::new(period(0))),
+        "adx" => Box::new(Adx::new(period(0))),
+        "atr" => Box::new(Atr::new(period(0))),
+        "mfi" => Box::new(Mfi::new(period(0))),
+        "macd" => Box::new(Macd::new(period(0), period(1), period(2))),
+        "bollinger" => Box::new(BollingerBands::new(period(0), factor(1))),
+        "keltner" => Box::new(KeltnerChannels::new(period(0), factor(1))),
+        "hma" => Box::new(HullMa::new(period(0))),
+        "kama" => Box::new(Kama::new(period(0), period(1), period(2))),
+        "tema" => Box::new(Tema::new(period(0))),
+        "ichimoku" => Box::new(Ichimoku::new(period(0), period(1), period(2), period(3))),
+        "supertrend" => Box::new(SuperTrend::new(period(0), factor(1))),
+        "psar" => Box::new(ParabolicSar::new(factor(0), factor(1))),
+        "donchian" => Box::new(DonchianChannels::new(period(0))),
+        "aroon" => Box::new(Aroon::new(period(0))),
+        "zscore" => Box::new(RollingZScore::new(period(0))),
+        "linreg_slope" => Box::new(LinRegSlope::new(period(0))),
+        "obv" => Box::new(Obv::new()),
+        "vwap" => Box::new(Vwap::new()),
+        other => unreachable!("registered indicator '{}' has no constructor", other),
+    };
+    Ok(indicator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_registered_indicator_builds_with_defaults() {
+        for spec in INDICATORS {
+            let indicator = create_indicator(&spec.name.to_uppercase(), &[]).unwrap();
+            assert!(indicator.warmup_period() > 0 || spec.params.is_empty());
+            let too_many = vec![1.0; spec.params.len() + 1];
+            assert!(create_indicator(spec.name, &too_many).is_err());
+        }
+        assert!(create_indicator("nonsense", &[]).is_err());
+        assert!(create_indicator("rsi", &[f64::NAN]).is_err());
+
+        let macd = create_indicator("macd", &[5.0]).unwrap();
+        assert_eq!(macd.warmup_period(), 26 + 9);
+    }
+}
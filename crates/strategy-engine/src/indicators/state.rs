@@ -1,9 +1,45 @@
+use std::collections::VecDeque;
+
+use rust_decimal::Decimal;
+
 use crate::indicators::prelude::*;
 
+/// Where an indicator of an [`IndicatorState`] takes its input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndicatorInput {
+    /// Every closed candle, through [`Indicator::update_ohlcv`].
+    Candles,
+    /// A named output line of an earlier indicator, through [`Indicator::update`],
+    /// once that indicator is ready.
+    Component { source: usize, component: String },
+}
+
+const CANDLES: IndicatorInput = IndicatorInput::Candles;
+
+/// Input of one indicator. Chained indicators keep the values they were fed, bounded
+/// like the candle buffer, so a replacement can be warmed the same way.
+struct Wiring {
+    input: IndicatorInput,
+    fed: VecDeque<Decimal>,
+}
+
+impl Wiring {
+    fn candles() -> Self {
+        Self {
+            input: IndicatorInput::Candles,
+            fed: VecDeque::new(),
+        }
+    }
+}
+
 /// Strategy-owned indicator state
+///
+/// Indicators read closed candles unless chained onto an earlier indicator with
+/// [`IndicatorState::chain`], e.g. an RSI of OBV or an EMA of ATR.
 pub struct IndicatorState {
     pub buffer: CandleBuffer,
     pub indicators: Vec<Box<dyn Indicator>>,
+    wiring: Vec<Wiring>,
 }
 
 impl IndicatorState {
@@ -11,20 +47,161 @@ impl IndicatorState {
         Self {
             buffer: CandleBuffer::new(buffer_depth),
             indicators: Vec::new(),
+            wiring: Vec::new(),
         }
     }
 
     pub fn add<I: Indicator + 'static>(&mut self, indicator: I) -> &mut Self {
-        self.indicators.push(Box::new(indicator));
+        self.attach(Wiring::candles(), Box::new(indicator));
         self
     }
 
+    /// Feed `indicator` with the `component` output line of the indicator at `source`.
+    /// Returns the index of the chained indicator.
+    ///
+    /// The chained indicator only sees readings taken once its source is ready, so it
+    /// warms up after it; readings without `component` are skipped.
+    pub fn chain<I: Indicator + 'static>(
+        &mut self,
+        source: usize,
+        component: &str,
+        indicator: I,
+    ) -> Result<usize, String> {
+        if source >= self.indicators.len() {
+            return Err(format!("no indicator at index {}", source));
+        }
+        let wiring = Wiring {
+            input: IndicatorInput::Component {
+                source,
+                component: component.to_string(),
+            },
+            fed: VecDeque::new(),
+        };
+        self.attach(wiring, Box::new(indicator));
+        Ok(self.indicators.len() - 1)
+    }
+
+    fn attach(&mut self, wiring: Wiring, indicator: Box<dyn Indicator>) {
+        // Indicators pushed straight onto `indicators` read candles.
+        self.wiring
+            .resize_with(self.indicators.len(), Wiring::candles);
+        self.wiring.push(wiring);
+        self.indicators.push(indicator);
+    }
+
+    /// Input of the indicator at `index`.
+    pub fn input(&self, index: usize) -> Option<&IndicatorInput> {
+        if index >= self.indicators.len() {
+            return None;
+        }
+        Some(
+            self.wiring
+                .get(index)
+                .map_or(&CANDLES, |wiring| &wiring.input),
+        )
+    }
+
+    /// Label of the indicator at `index`: its name, or e.g. `RSI of OBV` and
+    /// `EMA of MACD.histogram` when chained.
+    pub fn label(&self, index: usize) -> Option<String> {
+        let name = self.indicators.get(index)?.name();
+        match self.input(index)? {
+            IndicatorInput::Candles => Some(name.to_string()),
+            IndicatorInput::Component { source, component } => {
+                let source = self.label(*source)?;
+                if component.eq_ignore_ascii_case(IndicatorOutput::VALUE) {
+                    Some(format!("{} of {}", name, source))
+                } else {
+                    Some(format!("{} of {}.{}", name, source, component))
+                }
+            }
+        }
+    }
+
+    /// Index of the first indicator whose label matches, ignoring ASCII case.
+    pub fn find(&self, label: &str) -> Option<usize> {
+        (0..self.indicators.len()).find(|&index| {
+            self.label(index)
+                .is_some_and(|candidate| candidate.eq_ignore_ascii_case(label))
+        })
+    }
+
+    /// Latest named output of the indicator at `index`.
+    pub fn output(&self, index: usize) -> Option<IndicatorOutput> {
+        self.indicators.get(index)?.output()
+    }
+
+    /// Latest value of one output line of the indicator at `index`.
+    pub fn component(&self, index: usize, component: &str) -> Option<Decimal> {
+        self.output(index)?.get(component)
+    }
+
+    /// Swap the indicator at `index`, warming the replacement from buffered candles,
+    /// or from the values it was fed when chained. Indicators chained onto it keep
+    /// their state and read from the replacement from then on.
+    /// Returns the previous indicator, or `None` if `index` is out of range.
+    pub fn replace<I: Indicator + 'static>(
+        &mut self,
+        index: usize,
+        indicator: I,
+    ) -> Option<Box<dyn Indicator>> {
+        let slot = self.indicators.get_mut(index)?;
+        let mut replacement: Box<dyn Indicator> = Box::new(indicator);
+        match self.wiring.get(index) {
+            Some(Wiring {
+                input: IndicatorInput::Component { .. },
+                fed,
+            }) => {
+                for value in fed {
+                    replacement.update(*value);
+                }
+            }
+            _ => {
+                for candle in self.buffer.last_n(self.buffer.len()) {
+                    replacement.update_ohlcv(candle);
+                }
+            }
+        }
+        Some(std::mem::replace(slot, replacement))
+    }
+
     /// Update all indicators with new candle
     pub fn update(&mut self, candle: Candle) -> Vec<IndicatorValue> {
         self.buffer.push(candle.clone());
-        self.indicators
-            .iter_mut()
-            .map(|ind| ind.update_ohlcv(&candle))
-            .collect()
+        let depth = self.buffer.capacity();
+        let mut values = Vec::with_capacity(self.indicators.len());
+        for index in 0..self.indicators.len() {
+            // Sources always precede the indicators chained onto them.
+            let (upstream, rest) = self.indicators.split_at_mut(index);
+            let indicator = &mut rest[0];
+            let value = match self.wiring.get_mut(index) {
+                Some(Wiring {
+                    input: IndicatorInput::Component { source, component },
+                    fed,
+                }) => {
+                    let reading = upstream
+                        .get(*source)
+                        .filter(|source| source.is_ready())
+                        .and_then(|source| source.output())
+                        .and_then(|output| output.get(component));
+                    match reading {
+                        Some(input) => {
+                            if fed.len() >= depth {
+                                fed.pop_front();
+                            }
+                            fed.push_back(input);
+                            indicator.update(input)
+                        }
+                        None => indicator.current().unwrap_or(IndicatorValue {
+                            value: Decimal::ZERO,
+                            signal: None,
+                        }),
+                    }
+                }
+                _ => indicator.update_ohlcv(&candle),
+            };
+            values.push(value);
+        }
+        values
     }
 }
@@ -0,0 +1,244 @@
+//! Rolling statistics over a fixed window of prices, updated in O(1) from running sums.
+
+use crate::indicators::{dec_to_f64, f64_to_dec, Indicator, IndicatorOutput, IndicatorValue};
+use rust_decimal::Decimal;
+use std::collections::VecDeque;
+
+// ============================================================================
+// Rolling Z-Score
+// ============================================================================
+/// Rolling Z-Score.
+///
+/// Distance of the latest price from the window mean, in population standard
+/// deviations; zero when the window is flat. `signal` is the window mean.
+pub struct RollingZScore {
+    period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+    sum_sq: f64,
+    current: Option<(f64, f64)>,
+}
+
+impl RollingZScore {
+    /// Create a new rolling z-score.
+    pub fn new(period: usize) -> Self {
+        let period = period.max(2);
+        Self {
+            period,
+            window: VecDeque::with_capacity(period + 1),
+            sum: 0.0,
+            sum_sq: 0.0,
+            current: None,
+        }
+    }
+}
+
+impl Indicator for RollingZScore {
+    fn name(&self) -> &'static str {
+        "Z-Score"
+    }
+
+    fn update(&mut self, price: Decimal) -> IndicatorValue {
+        let val = dec_to_f64(price);
+        self.window.push_back(val);
+        self.sum += val;
+        self.sum_sq += val * val;
+        if self.window.len() > self.period {
+            let old = self.window.pop_front().unwrap_or_default();
+            self.sum -= old;
+            self.sum_sq -= old * old;
+        }
+
+        if self.window.len() == self.period {
+            let n = self.period as f64;
+            let mean = self.sum / n;
+            let variance = (self.sum_sq / n - mean * mean).max(0.0);
+            let std = variance.sqrt();
+            // Running sums leave rounding noise on flat windows; treat it as zero spread.
+            let z = if std > mean.abs().max(1.0) * 1e-9 {
+                (val - mean) / std
+            } else {
+                0.0
+            };
+            self.current = Some((z, mean));
+        }
+
+        let (z, mean) = self.current.unwrap_or_default();
+        IndicatorValue {
+            value: f64_to_dec(z),
+            signal: self.current.map(|_| f64_to_dec(mean)),
+        }
+    }
+
+    fn current(&self) -> Option<IndicatorValue> {
+        self.current.map(|(z, mean)| IndicatorValue {
+            value: f64_to_dec(z),
+            signal: Some(f64_to_dec(mean)),
+        })
+    }
+
+    fn output(&self) -> Option<IndicatorOutput> {
+        self.current.map(|(z, mean)| {
+            IndicatorOutput::new()
+                .with_f64("zscore", z)
+                .with_f64("mean", mean)
+        })
+    }
+
+    fn warmup_period(&self) -> usize {
+        self.period
+    }
+    fn is_ready(&self) -> bool {
+        self.current.is_some()
+    }
+}
+
+// ============================================================================
+// Linear Regression Slope
+// ============================================================================
+/// Linear Regression Slope.
+///
+/// Least-squares slope of the last `period` prices against their index, in price
+/// per candle. `signal` is the regression line's value at the latest candle.
+pub struct LinRegSlope {
+    period: usize,
+    window: VecDeque<f64>,
+    /// Sum of prices
+    sum: f64,
+    /// Sum of prices weighted by their index in the window, oldest at zero
+    weighted: f64,
+    current: Option<(f64, f64)>,
+}
+
+impl LinRegSlope {
+    /// Create a new linear regression slope.
+    pub fn new(period: usize) -> Self {
+        let period = period.max(2);
+        Self {
+            period,
+            window: VecDeque::with_capacity(period + 1),
+            sum: 0.0,
+            weighted: 0.0,
+            current: None,
+        }
+    }
+}
+
+impl Indicator for LinRegSlope {
+    fn name(&self) -> &'static str {
+        "LinReg Slope"
+    }
+
+    fn update(&mut self, price: Decimal) -> IndicatorValue {
+        let val = dec_to_f64(price);
+        if self.window.len() == self.period {
+            let oldest = self.window.pop_front().unwrap_or_default();
+            // Remaining samples shift down one index; the new one takes the last.
+            self.weighted += (self.period - 1) as f64 * val - (self.sum - oldest);
+            self.sum += val - oldest;
+        } else {
+            self.weighted += self.window.len() as f64 * val;
+            self.sum += val;
+        }
+        self.window.push_back(val);
+
+        if self.window.len() == self.period {
+            let n = self.period as f64;
+            let sum_x = n * (n - 1.0) / 2.0;
+            let sum_xx = (n - 1.0) * n * (2.0 * n - 1.0) / 6.0;
+            let slope = (n * self.weighted - sum_x * self.sum) / (n * sum_xx - sum_x * sum_x);
+            let intercept = (self.sum - slope * sum_x) / n;
+            self.current = Some((slope, intercept + slope * (n - 1.0)));
+        }
+
+        let (slope, end) = self.current.unwrap_or_default();
+        IndicatorValue {
+            value: f64_to_dec(slope),
+            signal: self.current.map(|_| f64_to_dec(end)),
+        }
+    }
+
+    fn current(&self) -> Option<IndicatorValue> {
+        self.current.map(|(slope, end)| IndicatorValue {
+            value: f64_to_dec(slope),
+            signal: Some(f64_to_dec(end)),
+        })
+    }
+
+    fn output(&self) -> Option<IndicatorOutput> {
+        self.current.map(|(slope, end)| {
+            IndicatorOutput::new()
+                .with_f64("slope", slope)
+                .with_f64("fitted", end)
+        })
+    }
+
+    fn warmup_period(&self) -> usize {
+        self.period
+    }
+    fn is_ready(&self) -> bool {
+        self.current.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices() -> Vec<f64> {
+        (0..80)
+            .map(|i| 200.0 + (i as f64 * 0.3).sin() * 15.0 + (i % 7) as f64 * 1.5)
+            .collect()
+    }
+
+    #[test]
+    fn zscore_matches_batch_computation() {
+        let period = 12;
+        let prices = prices();
+        let mut zscore = RollingZScore::new(period);
+        for (i, price) in prices.iter().enumerate() {
+            let value = zscore.update(f64_to_dec(*price));
+            if i + 1 < period {
+                assert!(!zscore.is_ready());
+                continue;
+            }
+            let window = &prices[i + 1 - period..=i];
+            let mean = window.iter().sum::<f64>() / period as f64;
+            let variance = window.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / period as f64;
+            let expected = (price - mean) / variance.sqrt();
+            assert!((dec_to_f64(value.value) - expected).abs() < 1e-6);
+            assert!((dec_to_f64(value.signal.unwrap()) - mean).abs() < 1e-6);
+        }
+
+        let mut flat = RollingZScore::new(5);
+        for _ in 0..10 {
+            flat.update(Decimal::from(100));
+        }
+        assert_eq!(flat.current().unwrap().value, Decimal::ZERO);
+    }
+
+    #[test]
+    fn slope_matches_batch_least_squares() {
+        let period = 10;
+        let prices = prices();
+        let mut slope = LinRegSlope::new(period);
+        for (i, price) in prices.iter().enumerate() {
+            let value = slope.update(f64_to_dec(*price));
+            if i + 1 < period {
+                continue;
+            }
+            let window = &prices[i + 1 - period..=i];
+            let x_mean = (period - 1) as f64 / 2.0;
+            let y_mean = window.iter().sum::<f64>() / period as f64;
+            let (mut cov, mut var) = (0.0, 0.0);
+            for (x, y) in window.iter().enumerate() {
+                cov += (x as f64 - x_mean) * (y - y_mean);
+                var += (x as f64 - x_mean).powi(2);
+            }
+            let expected = cov / var;
+            let end = y_mean + expected * ((period - 1) as f64 - x_mean);
+            assert!((dec_to_f64(value.value) - expected).abs() < 1e-6);
+            assert!((dec_to_f64(value.signal.unwrap()) - end).abs() < 1e-6);
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::indicators::{buffer, dec_to_f64, f64_to_dec, Indicator, IndicatorValue};
+use crate::indicators::buffer::RollingExtremum;
+use crate::indicators::{
+    buffer, dec_to_f64, f64_to_dec, Indicator, IndicatorOutput, IndicatorValue,
+};
 use rust_decimal::Decimal;
 use std::collections::VecDeque;
 use yata::core::{Method, PeriodType, ValueType};
@@ -183,6 +186,18 @@ impl Indicator for Macd {
         }
     }
 
+    fn output(&self) -> Option<IndicatorOutput> {
+        match (self.current_macd, self.current_signal) {
+            (Some(m), Some(s)) => Some(
+                IndicatorOutput::new()
+                    .with_f64("macd", m)
+                    .with_f64("signal", s)
+                    .with_f64("histogram", m - s),
+            ),
+            _ => None,
+        }
+    }
+
     fn warmup_period(&self) -> usize {
         self.warmup
     }
@@ -221,6 +236,8 @@ pub struct Adx {
     smoothed_minus_dm: Option<f64>,
     smoothed_adx: Option<f64>,
     current_adx: Option<f64>,
+    // Latest +DI and -DI
+    current_di: Option<(f64, f64)>,
 }
 
 impl Adx {
@@ -241,6 +258,7 @@ impl Adx {
             smoothed_minus_dm: None,
             smoothed_adx: None,
             current_adx: None,
+            current_di: None,
         }
     }
 
@@ -350,6 +368,7 @@ impl Indicator for Adx {
             } else {
                 0.0
             };
+            self.current_di = Some((plus_di, minus_di));
 
             // Calculate DX
             let di_sum = plus_di + minus_di;
@@ -391,6 +410,17 @@ impl Indicator for Adx {
         })
     }
 
+    fn output(&self) -> Option<IndicatorOutput> {
+        let adx = self.current_adx?;
+        let (plus_di, minus_di) = self.current_di?;
+        Some(
+            IndicatorOutput::new()
+                .with_f64("adx", adx)
+                .with_f64("plus_di", plus_di)
+                .with_f64("minus_di", minus_di),
+        )
+    }
+
     fn warmup_period(&self) -> usize {
         self.period * 2 // ADX needs 2x period for proper warmup
     }
@@ -399,3 +429,919 @@ impl Indicator for Adx {
         self.samples >= self.period * 2
     }
 }
+
+// ============================================================================
+// Streaming helpers
+// ============================================================================
+/// Linearly weighted moving average, newest sample weighted highest, with O(1) updates.
+#[derive(Debug, Clone)]
+struct Wma {
+    period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+    weighted: f64,
+}
+
+impl Wma {
+    fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            window: VecDeque::with_capacity(period + 1),
+            sum: 0.0,
+            weighted: 0.0,
+        }
+    }
+
+    fn next(&mut self, value: f64) -> Option<f64> {
+        if self.window.len() == self.period {
+            let oldest = self.window.pop_front().unwrap_or_default();
+            // Every remaining sample loses one weight step; the new one gets the top weight.
+            self.weighted += self.period as f64 * value - self.sum;
+            self.sum += value - oldest;
+        } else {
+            self.weighted += (self.window.len() + 1) as f64 * value;
+            self.sum += value;
+        }
+        self.window.push_back(value);
+        let weights = (self.period * (self.period + 1) / 2) as f64;
+        (self.window.len() == self.period).then(|| self.weighted / weights)
+    }
+}
+
+/// Exponential smoothing seeded with the first sample.
+#[derive(Debug, Clone)]
+struct Smoothing {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl Smoothing {
+    fn ema(period: usize) -> Self {
+        Self {
+            alpha: 2.0 / (period.max(1) as f64 + 1.0),
+            value: None,
+        }
+    }
+
+    fn next(&mut self, sample: f64) -> f64 {
+        let value = match self.value {
+            Some(previous) => previous + self.alpha * (sample - previous),
+            None => sample,
+        };
+        self.value = Some(value);
+        value
+    }
+}
+
+/// Wilder's average true range: a simple mean of the first `period` ranges, then RMA.
+#[derive(Debug, Clone)]
+struct WilderAtr {
+    period: usize,
+    prev_close: Option<f64>,
+    seed: f64,
+    samples: usize,
+    value: Option<f64>,
+}
+
+impl WilderAtr {
+    fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            prev_close: None,
+            seed: 0.0,
+            samples: 0,
+            value: None,
+        }
+    }
+
+    fn next(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        let range = match self.prev_close {
+            Some(prev) => (high - low)
+                .max((high - prev).abs())
+                .max((low - prev).abs()),
+            None => high - low,
+        };
+        self.prev_close = Some(close);
+        self.samples += 1;
+        let period = self.period as f64;
+        self.value = match self.value {
+            Some(atr) => Some((atr * (period - 1.0) + range) / period),
+            None => {
+                self.seed += range;
+                (self.samples == self.period).then(|| self.seed / period)
+            }
+        };
+        self.value
+    }
+}
+
+fn direction(up: bool) -> Decimal {
+    if up {
+        Decimal::ONE
+    } else {
+        Decimal::NEGATIVE_ONE
+    }
+}
+
+// ============================================================================
+// Hull MA
+// ============================================================================
+/// Hull Moving Average (HMA).
+///
+/// `WMA(2 × WMA(n/2) − WMA(n), √n)`: a smoothed average with very little lag.
+pub struct HullMa {
+    half: Wma,
+    full: Wma,
+    smooth: Wma,
+    period: usize,
+    samples: usize,
+    current: Option<f64>,
+}
+
+impl HullMa {
+    /// Create a new Hull MA; the half and root periods are rounded down.
+    pub fn new(period: usize) -> Self {
+        Self {
+            half: Wma::new(period / 2),
+            full: Wma::new(period),
+            smooth: Wma::new((period as f64).sqrt() as usize),
+            period,
+            samples: 0,
+            current: None,
+        }
+    }
+}
+
+impl Indicator for HullMa {
+    fn name(&self) -> &'static str {
+        "HMA"
+    }
+
+    fn update(&mut self, price: Decimal) -> IndicatorValue {
+        let val = dec_to_f64(price);
+        let half = self.half.next(val);
+        let full = self.full.next(val);
+        if let (Some(half), Some(full)) = (half, full) {
+            if let Some(hull) = self.smooth.next(2.0 * half - full) {
+                self.current = Some(hull);
+            }
+        }
+        self.samples += 1;
+        IndicatorValue {
+            value: f64_to_dec(self.current.unwrap_or(0.0)),
+            signal: None,
+        }
+    }
+
+    fn current(&self) -> Option<IndicatorValue> {
+        self.current.map(|v| IndicatorValue {
+            value: f64_to_dec(v),
+            signal: None,
+        })
+    }
+
+    fn warmup_period(&self) -> usize {
+        self.period + ((self.period as f64).sqrt() as usize).max(1) - 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.samples >= self.warmup_period()
+    }
+}
+
+// ============================================================================
+// KAMA
+// ============================================================================
+/// Kaufman Adaptive Moving Average (KAMA).
+///
+/// Smoothing moves between the fast and slow EMA constants with the efficiency
+/// ratio (net change over summed absolute changes) of the last `period` samples.
+pub struct Kama {
+    period: usize,
+    fast: f64,
+    slow: f64,
+    prices: VecDeque<f64>,
+    volatility: f64,
+    samples: usize,
+    current: Option<f64>,
+}
+
+impl Kama {
+    /// Create a new KAMA, e.g. `Kama::new(10, 2, 30)`.
+    pub fn new(period: usize, fast_period: usize, slow_period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            fast: 2.0 / (fast_period as f64 + 1.0),
+            slow: 2.0 / (slow_period as f64 + 1.0),
+            prices: VecDeque::with_capacity(period + 2),
+            volatility: 0.0,
+            samples: 0,
+            current: None,
+        }
+    }
+}
+
+impl Indicator for Kama {
+    fn name(&self) -> &'static str {
+        "KAMA"
+    }
+
+    fn update(&mut self, price: Decimal) -> IndicatorValue {
+        let val = dec_to_f64(price);
+        if let Some(previous) = self.prices.back() {
+            self.volatility += (val - previous).abs();
+        }
+        self.prices.push_back(val);
+        if self.prices.len() > self.period + 1 {
+            let dropped = self.prices.pop_front().unwrap_or_default();
+            let oldest = self.prices.front().copied().unwrap_or_default();
+            self.volatility = (self.volatility - (oldest - dropped).abs()).max(0.0);
+        }
+        self.samples += 1;
+
+        if self.prices.len() == self.period + 1 {
+            let change = (val - self.prices[0]).abs();
+            let efficiency = if self.volatility > 0.0 {
+                change / self.volatility
+            } else {
+                0.0
+            };
+            let constant = (efficiency * (self.fast - self.slow) + self.slow).powi(2);
+            // Seeded with the previous price the first time a full window is available.
+            let previous = self.current.unwrap_or(self.prices[self.prices.len() - 2]);
+            self.current = Some(previous + constant * (val - previous));
+        }
+
+        IndicatorValue {
+            value: f64_to_dec(self.current.unwrap_or(0.0)),
+            signal: None,
+        }
+    }
+
+    fn current(&self) -> Option<IndicatorValue> {
+        self.current.map(|v| IndicatorValue {
+            value: f64_to_dec(v),
+            signal: None,
+        })
+    }
+
+    fn warmup_period(&self) -> usize {
+        self.period + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.samples > self.period
+    }
+}
+
+// ============================================================================
+// TEMA
+// ============================================================================
+/// Triple Exponential Moving Average (TEMA).
+///
+/// `3 × EMA − 3 × EMA(EMA) + EMA(EMA(EMA))`, each EMA seeded with its first input.
+pub struct Tema {
+    first: Smoothing,
+    second: Smoothing,
+    third: Smoothing,
+    period: usize,
+    samples: usize,
+    current: Option<f64>,
+}
+
+impl Tema {
+    /// Create a new TEMA.
+    pub fn new(period: usize) -> Self {
+        Self {
+            first: Smoothing::ema(period),
+            second: Smoothing::ema(period),
+            third: Smoothing::ema(period),
+            period,
+            samples: 0,
+            current: None,
+        }
+    }
+}
+
+impl Indicator for Tema {
+    fn name(&self) -> &'static str {
+        "TEMA"
+    }
+
+    fn update(&mut self, price: Decimal) -> IndicatorValue {
+        let first = self.first.next(dec_to_f64(price));
+        let second = self.second.next(first);
+        let third = self.third.next(second);
+        let tema = 3.0 * first - 3.0 * second + third;
+        self.current = Some(tema);
+        self.samples += 1;
+        IndicatorValue {
+            value: f64_to_dec(tema),
+            signal: None,
+        }
+    }
+
+    fn current(&self) -> Option<IndicatorValue> {
+        self.current.map(|v| IndicatorValue {
+            value: f64_to_dec(v),
+            signal: None,
+        })
+    }
+
+    fn warmup_period(&self) -> usize {
+        3 * self.period.max(1) - 2
+    }
+
+    fn is_ready(&self) -> bool {
+        self.samples >= self.warmup_period()
+    }
+}
+
+// ============================================================================
+// Ichimoku Cloud
+// ============================================================================
+/// All lines of the Ichimoku Cloud for the latest candle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IchimokuOutput {
+    /// Tenkan-sen: midpoint of the conversion-period range
+    pub conversion_line: f64,
+    /// Kijun-sen: midpoint of the base-period range
+    pub base_line: f64,
+    /// Senkou span A computed now, plotted `displacement` candles ahead
+    pub leading_span_a: f64,
+    /// Senkou span B computed now, plotted `displacement` candles ahead
+    pub leading_span_b: f64,
+    /// Span A plotted at the latest candle, i.e. computed `displacement` candles ago
+    pub cloud_span_a: Option<f64>,
+    /// Span B plotted at the latest candle
+    pub cloud_span_b: Option<f64>,
+    /// Chikou span: the latest close, plotted `displacement` candles back
+    pub lagging_span: f64,
+}
+
+/// Ichimoku Kinko Hyo (Ichimoku Cloud).
+///
+/// `value` is the conversion line and `signal` the base line; [`Ichimoku::lines`]
+/// exposes the cloud as well.
+pub struct Ichimoku {
+    conversion: (RollingExtremum, RollingExtremum),
+    base: (RollingExtremum, RollingExtremum),
+    span_b: (RollingExtremum, RollingExtremum),
+    span_b_period: usize,
+    displacement: usize,
+    projected: VecDeque<(f64, f64)>,
+    samples: usize,
+    current: Option<IchimokuOutput>,
+}
+
+impl Ichimoku {
+    /// Create an Ichimoku Cloud, e.g. `Ichimoku::new(9, 26, 52, 26)`.
+    pub fn new(
+        conversion_period: usize,
+        base_period: usize,
+        span_b_period: usize,
+        displacement: usize,
+    ) -> Self {
+        let channel = |period| (RollingExtremum::max(period), RollingExtremum::min(period));
+        Self {
+            conversion: channel(conversion_period),
+            base: channel(base_period),
+            span_b: channel(span_b_period),
+            span_b_period,
+            displacement,
+            projected: VecDeque::with_capacity(displacement + 2),
+            samples: 0,
+            current: None,
+        }
+    }
+
+    /// Latest lines of the cloud.
+    pub fn lines(&self) -> Option<IchimokuOutput> {
+        self.current
+    }
+
+    fn step(&mut self, high: f64, low: f64, close: f64) -> IchimokuOutput {
+        let midpoint = |channel: &mut (RollingExtremum, RollingExtremum)| {
+            (channel.0.push(high) + channel.1.push(low)) / 2.0
+        };
+        let conversion_line = midpoint(&mut self.conversion);
+        let base_line = midpoint(&mut self.base);
+        let leading_span_b = midpoint(&mut self.span_b);
+        let leading_span_a = (conversion_line + base_line) / 2.0;
+
+        self.projected.push_back((leading_span_a, leading_span_b));
+        if self.projected.len() > self.displacement + 1 {
+            self.projected.pop_front();
+        }
+        let cloud = (self.projected.len() == self.displacement + 1).then(|| self.projected[0]);
+        self.samples += 1;
+
+        let lines = IchimokuOutput {
+            conversion_line,
+            base_line,
+            leading_span_a,
+            leading_span_b,
+            cloud_span_a: cloud.map(|(a, _)| a),
+            cloud_span_b: cloud.map(|(_, b)| b),
+            lagging_span: close,
+        };
+        self.current = Some(lines);
+        lines
+    }
+}
+
+impl Indicator for Ichimoku {
+    fn name(&self) -> &'static str {
+        "Ichimoku"
+    }
+
+    fn update(&mut self, price: Decimal) -> IndicatorValue {
+        let val = dec_to_f64(price);
+        let lines = self.step(val, val, val);
+        IndicatorValue {
+            value: f64_to_dec(lines.conversion_line),
+            signal: Some(f64_to_dec(lines.base_line)),
+        }
+    }
+
+    fn update_ohlcv(&mut self, candle: &buffer::Candle) -> IndicatorValue {
+        let lines = self.step(
+            dec_to_f64(candle.high),
+            dec_to_f64(candle.low),
+            dec_to_f64(candle.close),
+        );
+        IndicatorValue {
+            value: f64_to_dec(lines.conversion_line),
+            signal: Some(f64_to_dec(lines.base_line)),
+        }
+    }
+
+    fn current(&self) -> Option<IndicatorValue> {
+        self.current.map(|lines| IndicatorValue {
+            value: f64_to_dec(lines.conversion_line),
+            signal: Some(f64_to_dec(lines.base_line)),
+        })
+    }
+
+    fn output(&self) -> Option<IndicatorOutput> {
+        let lines = self.current?;
+        let mut output = IndicatorOutput::new()
+            .with_f64("conversion", lines.conversion_line)
+            .with_f64("base", lines.base_line)
+            .with_f64("span_a", lines.leading_span_a)
+            .with_f64("span_b", lines.leading_span_b);
+        if let (Some(a), Some(b)) = (lines.cloud_span_a, lines.cloud_span_b) {
+            output = output.with_f64("cloud_a", a).with_f64("cloud_b", b);
+        }
+        Some(output.with_f64("lagging", lines.lagging_span))
+    }
+
+    fn warmup_period(&self) -> usize {
+        self.span_b_period
+    }
+
+    fn is_ready(&self) -> bool {
+        self.samples >= self.span_b_period
+    }
+}
+
+// ============================================================================
+// SuperTrend
+// ============================================================================
+/// SuperTrend.
+///
+/// A trailing stop `multiplier` Wilder ATRs from the candle midpoint that flips
+/// sides when the close crosses it. `value` is the active line and `signal` the
+/// direction (`1` up, `-1` down); the first direction is down.
+pub struct SuperTrend {
+    atr: WilderAtr,
+    multiplier: f64,
+    period: usize,
+    prev_close: Option<f64>,
+    bands: Option<(f64, f64)>,
+    up: bool,
+    samples: usize,
+    current: Option<f64>,
+}
+
+impl SuperTrend {
+    /// Create a new SuperTrend, e.g. `SuperTrend::new(10, 3.0)`.
+    pub fn new(period: usize, multiplier: f64) -> Self {
+        Self {
+            atr: WilderAtr::new(period),
+            multiplier,
+            period,
+            prev_close: None,
+            bands: None,
+            up: false,
+            samples: 0,
+            current: None,
+        }
+    }
+
+    fn step(&mut self, high: f64, low: f64, close: f64) -> IndicatorValue {
+        let prev_close = self.prev_close.replace(close);
+        self.samples += 1;
+        if let Some(atr) = self.atr.next(high, low, close) {
+            let mid = (high + low) / 2.0;
+            let (basic_upper, basic_lower) =
+                (mid + self.multiplier * atr, mid - self.multiplier * atr);
+            let (upper, lower) = match (self.bands, prev_close) {
+                (Some((upper, lower)), Some(prev_close)) => (
+                    if basic_upper < upper || prev_close > upper {
+                        basic_upper
+                    } else {
+                        upper
+                    },
+                    if basic_lower > lower || prev_close < lower {
+                        basic_lower
+                    } else {
+                        lower
+                    },
+                ),
+                _ => (basic_upper, basic_lower),
+            };
+            if self.bands.is_some() {
+                self.up = if self.up {
+                    close >= lower
+                } else {
+                    close > upper
+                };
+            }
+            self.bands = Some((upper, lower));
+            self.current = Some(if self.up { lower } else { upper });
+        }
+        IndicatorValue {
+            value: f64_to_dec(self.current.unwrap_or(0.0)),
+            signal: self.current.map(|_| direction(self.up)),
+        }
+    }
+}
+
+impl Indicator for SuperTrend {
+    fn name(&self) -> &'static str {
+        "SuperTrend"
+    }
+
+    fn update(&mut self, price: Decimal) -> IndicatorValue {
+        let val = dec_to_f64(price);
+        self.step(val, val, val)
+    }
+
+    fn update_ohlcv(&mut self, candle: &buffer::Candle) -> IndicatorValue {
+        self.step(
+            dec_to_f64(candle.high),
+            dec_to_f64(candle.low),
+            dec_to_f64(candle.close),
+        )
+    }
+
+    fn current(&self) -> Option<IndicatorValue> {
+        self.current.map(|v| IndicatorValue {
+            value: f64_to_dec(v),
+            signal: Some(direction(self.up)),
+        })
+    }
+
+    fn output(&self) -> Option<IndicatorOutput> {
+        self.current.map(|v| {
+            IndicatorOutput::new()
+                .with_f64("supertrend", v)
+                .with("direction", direction(self.up))
+        })
+    }
+
+    fn warmup_period(&self) -> usize {
+        self.period
+    }
+
+    fn is_ready(&self) -> bool {
+        self.samples >= self.period
+    }
+}
+
+// ============================================================================
+// Parabolic SAR
+// ============================================================================
+/// Parabolic Stop and Reverse (PSAR).
+///
+/// Wilder's trailing stop that accelerates by `step` each new extreme, up to
+/// `max_step`. The first direction follows the directional movement between the
+/// first two candles. `value` is the SAR and `signal` the direction.
+pub struct ParabolicSar {
+    step: f64,
+    max_step: f64,
+    /// Highs and lows of the previous two candles, most recent last
+    prev: VecDeque<(f64, f64)>,
+    sar: f64,
+    extreme: f64,
+    acceleration: f64,
+    up: bool,
+    samples: usize,
+    current: Option<f64>,
+}
+
+impl ParabolicSar {
+    /// Create a new Parabolic SAR, e.g. `ParabolicSar::new(0.02, 0.2)`.
+    pub fn new(step: f64, max_step: f64) -> Self {
+        Self {
+            step,
+            max_step,
+            prev: VecDeque::with_capacity(3),
+            sar: 0.0,
+            extreme: 0.0,
+            acceleration: step,
+            up: true,
+            samples: 0,
+            current: None,
+        }
+    }
+
+    fn step(&mut self, high: f64, low: f64) -> IndicatorValue {
+        self.samples += 1;
+        match (self.samples, self.prev.back().copied()) {
+            (1, _) | (_, None) => {}
+            (2, Some((prev_high, prev_low))) => {
+                self.up = high - prev_high >= prev_low - low;
+                (self.sar, self.extreme) = if self.up {
+                    (prev_low.min(low), prev_high.max(high))
+                } else {
+                    (prev_high.max(high), prev_low.min(low))
+                };
+                self.acceleration = self.step;
+                self.current = Some(self.sar);
+            }
+            _ => {
+                let mut sar = self.sar + self.acceleration * (self.extreme - self.sar);
+                // The stop may not move inside the previous two candles' range.
+                for (prev_high, prev_low) in &self.prev {
+                    sar = if self.up {
+                        sar.min(*prev_low)
+                    } else {
+                        sar.max(*prev_high)
+                    };
+                }
+                if self.up && low < sar {
+                    self.up = false;
+                    sar = self.extreme;
+                    self.extreme = low;
+                    self.acceleration = self.step;
+                } else if !self.up && high > sar {
+                    self.up = true;
+                    sar = self.extreme;
+                    self.extreme = high;
+                    self.acceleration = self.step;
+                } else if (self.up && high > self.extreme) || (!self.up && low < self.extreme) {
+                    self.extreme = if self.up { high } else { low };
+                    self.acceleration = (self.acceleration + self.step).min(self.max_step);
+                }
+                self.sar = sar;
+                self.current = Some(sar);
+            }
+        }
+        self.prev.push_back((high, low));
+        if self.prev.len() > 2 {
+            self.prev.pop_front();
+        }
+        IndicatorValue {
+            value: f64_to_dec(self.current.unwrap_or(0.0)),
+            signal: self.current.map(|_| direction(self.up)),
+        }
+    }
+}
+
+impl Indicator for ParabolicSar {
+    fn name(&self) -> &'static str {
+        "PSAR"
+    }
+
+    fn update(&mut self, price: Decimal) -> IndicatorValue {
+        let val = dec_to_f64(price);
+        self.step(val, val)
+    }
+
+    fn update_ohlcv(&mut self, candle: &buffer::Candle) -> IndicatorValue {
+        self.step(dec_to_f64(candle.high), dec_to_f64(candle.low))
+    }
+
+    fn current(&self) -> Option<IndicatorValue> {
+        self.current.map(|v| IndicatorValue {
+            value: f64_to_dec(v),
+            signal: Some(direction(self.up)),
+        })
+    }
+
+    fn output(&self) -> Option<IndicatorOutput> {
+        self.current.map(|v| {
+            IndicatorOutput::new()
+                .with_f64("sar", v)
+                .with("direction", direction(self.up))
+        })
+    }
+
+    fn warmup_period(&self) -> usize {
+        2
+    }
+
+    fn is_ready(&self) -> bool {
+        self.samples >= 2
+    }
+}
+
+// ============================================================================
+// Aroon
+// ============================================================================
+/// Aroon.
+///
+/// Percentage of the lookback elapsed since the highest high (up) and lowest low
+/// (down) within the last `period + 1` candles. `value` is Aroon up and `signal`
+/// Aroon down; ties favour the most recent extreme.
+pub struct Aroon {
+    highs: RollingExtremum,
+    lows: RollingExtremum,
+    period: usize,
+    samples: usize,
+    current: Option<(f64, f64)>,
+}
+
+impl Aroon {
+    /// Create a new Aroon indicator.
+    pub fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            highs: RollingExtremum::max(period + 1),
+            lows: RollingExtremum::min(period + 1),
+            period,
+            samples: 0,
+            current: None,
+        }
+    }
+
+    fn step(&mut self, high: f64, low: f64) -> IndicatorValue {
+        self.highs.push(high);
+        self.lows.push(low);
+        self.samples += 1;
+        if let (Some((_, since_high)), Some((_, since_low))) =
+            (self.highs.extreme(), self.lows.extreme())
+        {
+            let score = |since: usize| {
+                100.0 * (self.period - since.min(self.period)) as f64 / self.period as f64
+            };
+            self.current = Some((score(since_high), score(since_low)));
+        }
+        let (up, down) = self.current.unwrap_or_default();
+        IndicatorValue {
+            value: f64_to_dec(up),
+            signal: Some(f64_to_dec(down)),
+        }
+    }
+}
+
+impl Indicator for Aroon {
+    fn name(&self) -> &'static str {
+        "Aroon"
+    }
+
+    fn update(&mut self, price: Decimal) -> IndicatorValue {
+        let val = dec_to_f64(price);
+        self.step(val, val)
+    }
+
+    fn update_ohlcv(&mut self, candle: &buffer::Candle) -> IndicatorValue {
+        self.step(dec_to_f64(candle.high), dec_to_f64(candle.low))
+    }
+
+    fn current(&self) -> Option<IndicatorValue> {
+        self.current.map(|(up, down)| IndicatorValue {
+            value: f64_to_dec(up),
+            signal: Some(f64_to_dec(down)),
+        })
+    }
+
+    fn output(&self) -> Option<IndicatorOutput> {
+        self.current.map(|(up, down)| {
+            IndicatorOutput::new()
+                .with_f64("up", up)
+                .with_f64("down", down)
+                .with_f64("oscillator", up - down)
+        })
+    }
+
+    fn warmup_period(&self) -> usize {
+        self.period + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.samples > self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::buffer::Candle;
+
+    /// Deterministic swinging series with uneven candle ranges.
+    fn series() -> Vec<Candle> {
+        (0..40)
+            .map(|i| {
+                let close = 100.0 + 5.0 * (i as f64 * 0.5).sin() + i as f64 * 0.3;
+                Candle {
+                    open: f64_to_dec(close),
+                    high: f64_to_dec(close + 1.0 + (i % 3) as f64 * 0.5),
+                    low: f64_to_dec(close - 1.0 - (i % 4) as f64 * 0.25),
+                    close: f64_to_dec(close),
+                    volume: Decimal::ZERO,
+                    timestamp: i,
+                }
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: Decimal, expected: f64) {
+        let actual = dec_to_f64(actual);
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn moving_averages_match_reference_values() {
+        let mut hma = HullMa::new(9);
+        let mut kama = Kama::new(10, 2, 30);
+        let mut tema = Tema::new(5);
+        for candle in series() {
+            hma.update(candle.close);
+            kama.update(candle.close);
+            tema.update(candle.close);
+        }
+        assert_close(hma.current().unwrap().value, 112.1732027124);
+        assert_close(kama.current().unwrap().value, 107.4666267368);
+        assert_close(tema.current().unwrap().value, 114.1313082343);
+    }
+
+    #[test]
+    fn ichimoku_matches_reference_lines() {
+        let mut ichimoku = Ichimoku::new(3, 5, 8, 4);
+        for candle in series() {
+            ichimoku.update_ohlcv(&candle);
+        }
+        let lines = ichimoku.lines().unwrap();
+        assert!((lines.conversion_line - 111.9326481281).abs() < 1e-6);
+        assert!((lines.base_line - 109.7997846606).abs() < 1e-6);
+        assert!((lines.leading_span_a - 110.8662163944).abs() < 1e-6);
+        assert!((lines.leading_span_b - 109.7997846606).abs() < 1e-6);
+        assert!((lines.cloud_span_a.unwrap() - 106.7994868319).abs() < 1e-6);
+        assert!((lines.cloud_span_b.unwrap() - 109.6231726251).abs() < 1e-6);
+    }
+
+    #[test]
+    fn supertrend_flips_with_reference_direction() {
+        let mut supertrend = SuperTrend::new(5, 2.0);
+        let mut directions = String::new();
+        for candle in series() {
+            let value = supertrend.update_ohlcv(&candle);
+            if supertrend.is_ready() {
+                directions.push(if value.signal == Some(Decimal::ONE) {
+                    '+'
+                } else {
+                    '-'
+                });
+            }
+        }
+        assert_eq!(directions, "---------+++++++------+++++++-----++");
+        assert_close(supertrend.current().unwrap().value, 107.2102578947);
+    }
+
+    #[test]
+    fn parabolic_sar_matches_reference_values() {
+        let mut sar = ParabolicSar::new(0.02, 0.2);
+        let mut directions = String::new();
+        for candle in series() {
+            let value = sar.update_ohlcv(&candle);
+            if sar.is_ready() {
+                directions.push(if value.signal == Some(Decimal::ONE) {
+                    '+'
+                } else {
+                    '-'
+                });
+            }
+        }
+        assert_eq!(directions, "++++++------++++++++-----+++++++-----++");
+        assert_close(sar.current().unwrap().value, 104.0774202942);
+    }
+
+    #[test]
+    fn aroon_counts_bars_since_extremes() {
+        let mut aroon = Aroon::new(5);
+        for candle in series() {
+            aroon.update_ohlcv(&candle);
+        }
+        let value = aroon.current().unwrap();
+        assert_close(value.value, 100.0);
+        assert_close(value.signal.unwrap(), 20.0);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::indicators::{buffer, dec_to_f64, f64_to_dec, Indicator, IndicatorValue};
+use crate::indicators::buffer::RollingExtremum;
+use crate::indicators::{
+    buffer, dec_to_f64, f64_to_dec, Indicator, IndicatorOutput, IndicatorValue,
+};
 use rust_decimal::Decimal;
 use std::collections::VecDeque;
 use yata::core::{Method, PeriodType};
@@ -18,6 +21,17 @@ pub struct BollingerBandsOutput {
     pub lower: f64,
 }
 
+/// Explicit Donchian Channels output structure.
+#[derive(Debug, Clone, Copy)]
+pub struct DonchianOutput {
+    /// Highest high of the period
+    pub upper: f64,
+    /// Midpoint of the upper and lower channel
+    pub middle: f64,
+    /// Lowest low of the period
+    pub lower: f64,
+}
+
 // ============================================================================
 // ATR
 // ============================================================================
@@ -29,6 +43,7 @@ pub struct Atr {
     prev_close: Option<f64>,
     samples: usize,
     period: usize,
+    current: Option<f64>,
 }
 
 impl Atr {
@@ -39,6 +54,7 @@ impl Atr {
             prev_close: None,
             samples: 0,
             period,
+            current: None,
         }
     }
 }
@@ -72,6 +88,7 @@ impl Indicator for Atr {
         self.prev_close = Some(c);
         let atr = self.ema.next(&tr); // Smoothing TR
         self.samples += 1;
+        self.current = Some(atr);
 
         IndicatorValue {
             value: f64_to_dec(atr),
@@ -80,7 +97,10 @@ impl Indicator for Atr {
     }
 
     fn current(&self) -> Option<IndicatorValue> {
-        None
+        self.current.map(|v| IndicatorValue {
+            value: f64_to_dec(v),
+            signal: None,
+        })
     }
 
     fn warmup_period(&self) -> usize {
@@ -198,6 +218,11 @@ impl Indicator for BollingerBands {
         }
     }
 
+    fn output(&self) -> Option<IndicatorOutput> {
+        self.calculate_bands()
+            .map(|bands| band_output(bands.upper, bands.middle, bands.lower))
+    }
+
     fn warmup_period(&self) -> usize {
         self.period
     }
@@ -206,6 +231,14 @@ impl Indicator for BollingerBands {
     }
 }
 
+/// Named output of a three-line band indicator.
+fn band_output(upper: f64, middle: f64, lower: f64) -> IndicatorOutput {
+    IndicatorOutput::new()
+        .with_f64("upper", upper)
+        .with_f64("middle", middle)
+        .with_f64("lower", lower)
+}
+
 // ============================================================================
 // Keltner Channels
 // ============================================================================
@@ -219,6 +252,7 @@ pub struct KeltnerChannels {
     period: usize,
     samples: usize,
     current_upper: Option<f64>,
+    current_middle: Option<f64>,
     current_lower: Option<f64>,
 }
 
@@ -232,6 +266,7 @@ impl KeltnerChannels {
             period,
             samples: 0,
             current_upper: None,
+            current_middle: None,
             current_lower: None,
         }
     }
@@ -260,6 +295,7 @@ impl Indicator for KeltnerChannels {
         let lower = mid - self.multiplier * atr_f64;
 
         self.current_upper = Some(upper);
+        self.current_middle = Some(mid);
         self.current_lower = Some(lower);
         self.samples += 1;
 
@@ -279,6 +315,95 @@ impl Indicator for KeltnerChannels {
         }
     }
 
+    fn output(&self) -> Option<IndicatorOutput> {
+        match (self.current_upper, self.current_middle, self.current_lower) {
+            (Some(u), Some(m), Some(l)) => Some(band_output(u, m, l)),
+            _ => None,
+        }
+    }
+
+    fn warmup_period(&self) -> usize {
+        self.period
+    }
+    fn is_ready(&self) -> bool {
+        self.samples >= self.period
+    }
+}
+
+// ============================================================================
+// Donchian Channels
+// ============================================================================
+/// Donchian Channels.
+///
+/// The highest high and lowest low of the last `period` candles. `value` is the
+/// upper and `signal` the lower channel.
+pub struct DonchianChannels {
+    highs: RollingExtremum,
+    lows: RollingExtremum,
+    period: usize,
+    samples: usize,
+    current: Option<(f64, f64)>,
+}
+
+impl DonchianChannels {
+    /// Create new Donchian Channels.
+    pub fn new(period: usize) -> Self {
+        Self {
+            highs: RollingExtremum::max(period),
+            lows: RollingExtremum::min(period),
+            period,
+            samples: 0,
+            current: None,
+        }
+    }
+
+    /// Get all three channels; `None` before the first candle.
+    pub fn channels(&self) -> Option<DonchianOutput> {
+        self.current.map(|(upper, lower)| DonchianOutput {
+            upper,
+            middle: (upper + lower) / 2.0,
+            lower,
+        })
+    }
+
+    fn step(&mut self, high: f64, low: f64) -> IndicatorValue {
+        let upper = self.highs.push(high);
+        let lower = self.lows.push(low);
+        self.current = Some((upper, lower));
+        self.samples += 1;
+        IndicatorValue {
+            value: f64_to_dec(upper),
+            signal: Some(f64_to_dec(lower)),
+        }
+    }
+}
+
+impl Indicator for DonchianChannels {
+    fn name(&self) -> &'static str {
+        "Donchian Channels"
+    }
+
+    fn update(&mut self, price: Decimal) -> IndicatorValue {
+        let val = dec_to_f64(price);
+        self.step(val, val)
+    }
+
+    fn update_ohlcv(&mut self, candle: &buffer::Candle) -> IndicatorValue {
+        self.step(dec_to_f64(candle.high), dec_to_f64(candle.low))
+    }
+
+    fn current(&self) -> Option<IndicatorValue> {
+        self.current.map(|(u, l)| IndicatorValue {
+            value: f64_to_dec(u),
+            signal: Some(f64_to_dec(l)),
+        })
+    }
+
+    fn output(&self) -> Option<IndicatorOutput> {
+        self.channels()
+            .map(|channels| band_output(channels.upper, channels.middle, channels.lower))
+    }
+
     fn warmup_period(&self) -> usize {
         self.period
     }
@@ -286,3 +411,39 @@ impl Indicator for KeltnerChannels {
         self.samples >= self.period
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::buffer::Candle;
+
+    #[test]
+    fn donchian_matches_brute_force_window() {
+        let period = 7;
+        let candles: Vec<Candle> = (0..60)
+            .map(|i| {
+                let close = 50.0 + (i as f64 * 0.7).sin() * 4.0 + (i % 5) as f64;
+                Candle {
+                    open: f64_to_dec(close),
+                    high: f64_to_dec(close + (i % 3) as f64),
+                    low: f64_to_dec(close - (i % 4) as f64),
+                    close: f64_to_dec(close),
+                    volume: Decimal::ZERO,
+                    timestamp: i,
+                }
+            })
+            .collect();
+
+        let mut donchian = DonchianChannels::new(period);
+        for (i, candle) in candles.iter().enumerate() {
+            donchian.update_ohlcv(candle);
+            let window = &candles[(i + 1).saturating_sub(period)..=i];
+            let upper = window.iter().map(|c| c.high).max().unwrap();
+            let lower = window.iter().map(|c| c.low).min().unwrap();
+            let channels = donchian.channels().unwrap();
+            assert_eq!(f64_to_dec(channels.upper), upper);
+            assert_eq!(f64_to_dec(channels.lower), lower);
+            assert_eq!(donchian.is_ready(), i + 1 >= period);
+        }
+    }
+}
@@ -1,16 +1,34 @@
 //! Strategy engine crate providing WASM sandboxed execution for user-defined strategies.
 
 pub mod event_bridge;
+pub mod host;
 pub mod indicators;
+pub mod optimizer;
+pub mod params;
 pub mod runner;
 pub mod sandbox;
 pub mod strategies;
 pub mod traits;
 
 pub use event_bridge::StrategyEventBridge;
-pub use runner::ThreadSafeStrategyRunner;
-pub use sandbox::{WasmStrategyConfig, WasmStrategyInstance, WasmStrategyModule};
-pub use strategies::MomentumStrategy;
+pub use host::{
+    HostedStrategyConfig, HostedStrategyReport, ParameterAuditSink, ParameterChange,
+    StrategyActivity, StrategyActivitySink, StrategyHost, StrategyHostError, StrategyState,
+    StrategyStats, SymbolFilter,
+};
+pub use optimizer::{
+    BacktestConfig, BacktestMetrics, Objective, OptimizationReport, OptimizerConfig,
+    OptimizerError, OverfitGuards, SearchMethod, SearchSpace, StrategyOptimizer, WalkForward,
+};
+pub use params::{ParamError, ParamSchema, ParamSet, ParamSpec, ParamType, ParamValue};
+pub use runner::{StrategyRunner, ThreadSafeStrategyRunner};
+pub use sandbox::{
+    InMemoryStateStore, PortfolioSnapshot, PortfolioView, StrategyStateStore, WasmOrderIntent,
+    WasmStrategy, WasmStrategyConfig, WasmStrategyInstance, WasmStrategyModule,
+};
+pub use strategies::{
+    DcaStrategy, GridStrategy, MarketMakingStrategy, MomentumStrategy, PairsStrategy,
+};
 pub use traits::{
     MarketSnapshot, StrategyContext, StrategyDecision, StrategyError, StrategyExecutor,
     StrategyInitContext, StrategyMetrics,
@@ -0,0 +1,333 @@
+//! Backtesting
+//!
+//! Replays historical candles through a [`StrategyExecutor`] one bar at a time,
+//! filling its signals at the next bar's open net of fees and slippage.
+
+use std::ops::Range;
+
+use chrono::{DateTime, Utc};
+use ninja_gekko_core::analytics;
+use ninja_gekko_core::types::OrderSide;
+use rust_decimal::prelude::ToPrimitive;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::indicators::buffer::Candle;
+use crate::traits::{
+    MarketSnapshot, StrategyContext, StrategyError, StrategyExecutor, StrategyInitContext,
+};
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0;
+
+/// Periods per year assumed when candle spacing cannot be measured.
+const DEFAULT_PERIODS_PER_YEAR: f64 = 252.0;
+
+/// Account and execution assumptions for a backtest.
+#[derive(Debug, Clone)]
+pub struct BacktestConfig {
+    /// Symbol reported in the snapshots handed to the strategy.
+    pub symbol: String,
+    pub initial_capital: f64,
+    /// Fee charged on each fill, in basis points of notional.
+    pub fee_bps: f64,
+    /// Adverse price movement applied to each fill, in basis points.
+    pub slippage_bps: f64,
+    /// Whether sell signals may take the position below flat.
+    pub allow_short: bool,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            symbol: "BTC-USD".to_string(),
+            initial_capital: 10_000.0,
+            fee_bps: 10.0,
+            slippage_bps: 5.0,
+            allow_short: false,
+        }
+    }
+}
+
+/// Realized profit of a (partial) position close.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosedTrade {
+    /// Index of the candle whose open filled the closing order.
+    pub exit_index: usize,
+    pub pnl: f64,
+}
+
+/// Equity path and trades produced by a backtest.
+#[derive(Debug, Clone)]
+pub struct BacktestResult {
+    /// Simple return of account equity over each candle.
+    pub returns: Vec<f64>,
+    /// Account equity marked at each candle's close.
+    pub equity: Vec<f64>,
+    pub trades: Vec<ClosedTrade>,
+    pub periods_per_year: f64,
+}
+
+impl BacktestResult {
+    /// Metrics over the whole run.
+    pub fn metrics(&self) -> BacktestMetrics {
+        let all = 0..self.returns.len();
+        self.metrics_in(std::slice::from_ref(&all))
+    }
+
+    /// Metrics over the given candle ranges, treated as one contiguous period.
+    pub fn metrics_in(&self, ranges: &[Range<usize>]) -> BacktestMetrics {
+        let within = |index: usize| ranges.iter().any(|range| range.contains(&index));
+        let returns: Vec<f64> = ranges
+            .iter()
+            .flat_map(|range| {
+                let end = range.end.min(self.returns.len());
+                self.returns[range.start.min(end)..end].iter().copied()
+            })
+            .collect();
+        let pnls: Vec<f64> = self
+            .trades
+            .iter()
+            .filter(|trade| within(trade.exit_index))
+            .map(|trade| trade.pnl)
+            .collect();
+        BacktestMetrics::from_returns(&returns, &pnls, self.periods_per_year)
+    }
+}
+
+/// Risk-adjusted performance summary of a backtest period.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BacktestMetrics {
+    pub total_return: f64,
+    /// Annualized Sharpe ratio of per-bar returns, assuming a zero risk-free rate.
+    pub sharpe_ratio: f64,
+    /// Annualized Sortino ratio using downside deviation below zero.
+    pub sortino_ratio: f64,
+    /// Gross profit over gross loss of closed trades.
+    pub profit_factor: f64,
+    /// Largest peak-to-trough equity decline, as a positive fraction.
+    pub max_drawdown: f64,
+    pub win_rate: f64,
+    pub trades: usize,
+    pub bars: usize,
+}
+
+impl BacktestMetrics {
+    pub fn from_returns(returns: &[f64], trade_pnls: &[f64], periods_per_year: f64) -> Self {
+        if returns.is_empty() {
+            return Self::default();
+        }
+        let equity = analytics::compound(returns);
+        Self {
+            total_return: equity[equity.len() - 1] - 1.0,
+            sharpe_ratio: analytics::sharpe_ratio(returns, periods_per_year),
+            sortino_ratio: analytics::sortino_ratio(returns, periods_per_year),
+            profit_factor: analytics::profit_factor(trade_pnls),
+            max_drawdown: analytics::max_drawdown(&equity),
+            win_rate: analytics::win_rate(trade_pnls),
+            trades: trade_pnls.len(),
+            bars: returns.len(),
+        }
+    }
+}
+
+/// Simulated single-symbol account.
+struct Account {
+    cash: f64,
+    position: f64,
+    /// Average entry price per unit of the open position, fees included.
+    entry: f64,
+    fee: f64,
+}
+
+impl Account {
+    /// Moves the position by `delta` units at `price`, returning realized PnL if it reduced exposure.
+    fn fill(&mut self, delta: f64, price: f64) -> Option<f64> {
+        let cost = if delta > 0.0 {
+            price * (1.0 + self.fee)
+        } else {
+            price * (1.0 - self.fee)
+        };
+        self.cash -= delta * cost;
+
+        let mut realized = None;
+        let reducing = self.position != 0.0 && self.position.signum() != delta.signum();
+        let mut opening = delta;
+        if reducing {
+            let closed = delta.abs().min(self.position.abs());
+            realized = Some(closed * (cost - self.entry) * self.position.signum());
+            self.position += closed * delta.signum();
+            opening = delta - closed * delta.signum();
+            if self.position.abs() < f64::EPSILON {
+                self.position = 0.0;
+                self.entry = 0.0;
+            }
+        }
+        if opening != 0.0 {
+            let held = self.position.abs();
+            self.entry = (self.entry * held + cost * opening.abs()) / (held + opening.abs());
+            self.position += opening;
+        }
+        realized
+    }
+}
+
+/// Runs `strategy` over `candles` and returns its equity path and closed trades.
+///
+/// Signals emitted while evaluating candle `i` are netted and filled at the open of
+/// candle `i + 1`, so strategies never trade on prices they have not yet seen.
+pub fn run_backtest<const N: usize>(
+    strategy: &mut dyn StrategyExecutor<N>,
+    candles: &[Candle],
+    config: &BacktestConfig,
+) -> Result<BacktestResult, StrategyError> {
+    let account_id = "backtest".to_string();
+    strategy.initialize(StrategyInitContext {
+        strategy_id: Uuid::new_v4(),
+        account_id: &account_id,
+    })?;
+
+    let mut account = Account {
+        cash: config.initial_capital,
+        position: 0.0,
+        entry: 0.0,
+        fee: config.fee_bps / 10_000.0,
+    };
+    let slippage = config.slippage_bps / 10_000.0;
+    let mut snapshots: [MarketSnapshot; N] = std::array::from_fn(|_| MarketSnapshot {
+        symbol: config.symbol.clone(),
+        bid: Default::default(),
+        ask: Default::default(),
+        last: Default::default(),
+        timestamp: DateTime::<Utc>::UNIX_EPOCH,
+    });
+
+    let mut returns = Vec::with_capacity(candles.len());
+    let mut equity = Vec::with_capacity(candles.len());
+    let mut trades = Vec::new();
+    let mut pending = 0.0_f64;
+    let mut previous_equity = config.initial_capital;
+
+    for (index, candle) in candles.iter().enumerate() {
+        let open = candle.open.to_f64().unwrap_or(0.0);
+        let close = candle.close.to_f64().unwrap_or(0.0);
+
+        if pending != 0.0 && open > 0.0 {
+            let price = if pending > 0.0 {
+                open * (1.0 + slippage)
+            } else {
+                open * (1.0 - slippage)
+            };
+            let mut target = account.position + pending;
+            if !config.allow_short {
+                target = target.max(0.0);
+            }
+            let mut delta = target - account.position;
+            if delta > 0.0 && account.position >= 0.0 {
+                let affordable = account.cash.max(0.0) / (price * (1.0 + account.fee));
+                delta = delta.min(affordable);
+            }
+            if delta != 0.0 {
+                if let Some(pnl) = account.fill(delta, price) {
+                    trades.push(ClosedTrade {
+                        exit_index: index,
+                        pnl,
+                    });
+                }
+            }
+        }
+        pending = 0.0;
+
+        let marked = account.cash + account.position * close;
+        returns.push(if previous_equity != 0.0 {
+            marked / previous_equity - 1.0
+        } else {
+            0.0
+        });
+        equity.push(marked);
+        previous_equity = marked;
+
+        if N > 0 {
+            snapshots.rotate_left(1);
+            snapshots[N - 1] = MarketSnapshot {
+                symbol: config.symbol.clone(),
+                bid: candle.close,
+                ask: candle.close,
+                last: candle.close,
+                timestamp: DateTime::from_timestamp(candle.timestamp, 0).unwrap_or_default(),
+            };
+        }
+        let as_of = snapshots
+            .last()
+            .map(|snapshot| snapshot.timestamp)
+            .unwrap_or_default();
+        let ctx = StrategyContext::new(&account_id, &snapshots, Uuid::new_v4(), as_of)
+            .with_closed_candle(candle);
+        for payload in strategy.evaluate(ctx)?.signals {
+            let quantity = payload.signal.quantity.to_f64().unwrap_or(0.0);
+            match payload.signal.side {
+                OrderSide::Buy => pending += quantity,
+                OrderSide::Sell => pending -= quantity,
+            }
+        }
+    }
+
+    Ok(BacktestResult {
+        returns,
+        equity,
+        trades,
+        periods_per_year: periods_per_year(candles),
+    })
+}
+
+fn periods_per_year(candles: &[Candle]) -> f64 {
+    match (candles.first(), candles.last()) {
+        (Some(first), Some(last)) if candles.len() > 1 && last.timestamp > first.timestamp => {
+            let spacing = (last.timestamp - first.timestamp) as f64 / (candles.len() - 1) as f64;
+            SECONDS_PER_YEAR / spacing
+        }
+        _ => DEFAULT_PERIODS_PER_YEAR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ninja_gekko_core::analytics::RATIO_CAP;
+
+    #[test]
+    fn metrics_measure_drawdown_and_trade_quality() {
+        let metrics = BacktestMetrics::from_returns(&[0.1, -0.5, 0.2], &[30.0, -10.0, -5.0], 252.0);
+        assert!((metrics.total_return - (1.1 * 0.5 * 1.2 - 1.0)).abs() < 1e-12);
+        assert!((metrics.max_drawdown - 0.5).abs() < 1e-12);
+        assert!((metrics.profit_factor - 2.0).abs() < 1e-12);
+        assert!((metrics.win_rate - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(metrics.trades, 3);
+        assert!(metrics.sharpe_ratio < 0.0);
+
+        let winners = BacktestMetrics::from_returns(&[0.01, 0.02], &[5.0], 252.0);
+        assert_eq!(winners.profit_factor, RATIO_CAP);
+        assert_eq!(winners.sortino_ratio, RATIO_CAP);
+    }
+
+    #[test]
+    fn account_realizes_pnl_net_of_fees() {
+        let mut account = Account {
+            cash: 1_000.0,
+            position: 0.0,
+            entry: 0.0,
+            fee: 0.01,
+        };
+        assert_eq!(account.fill(2.0, 100.0), None);
+        assert!((account.entry - 101.0).abs() < 1e-9);
+
+        let pnl = account.fill(-1.0, 120.0).unwrap();
+        assert!((pnl - (118.8 - 101.0)).abs() < 1e-9);
+        assert_eq!(account.position, 1.0);
+
+        // Selling through flat realizes the remainder and opens a short.
+        let pnl = account.fill(-2.0, 90.0).unwrap();
+        assert!((pnl - (89.1 - 101.0)).abs() < 1e-9);
+        assert_eq!(account.position, -1.0);
+        assert!((account.entry - 89.1).abs() < 1e-9);
+    }
+}
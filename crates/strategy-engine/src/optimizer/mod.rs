@@ -0,0 +1,576 @@
+//! Strategy Optimizer
+//!
+//! Backtests a strategy across a parameter [`SearchSpace`] on all CPU cores and ranks
+//! the candidates by an [`Objective`]. With walk-forward validation each candidate is
+//! scored on out-of-sample windows it was not selected on, and [`OverfitGuards`]
+//! reject candidates that trade too rarely or lose their edge out of sample.
+
+pub mod backtest;
+pub mod space;
+
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+use std::thread;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Serialize;
+use thiserror::Error;
+use tracing::info;
+
+use crate::indicators::buffer::Candle;
+use crate::params::{ParamError, ParamSet, ParamValue};
+use crate::traits::{StrategyError, StrategyExecutor};
+
+pub use backtest::{run_backtest, BacktestConfig, BacktestMetrics, BacktestResult, ClosedTrade};
+pub use space::{ParamRange, SearchSpace};
+
+/// Fewest candles a walk-forward window may contain.
+pub const MIN_WINDOW_BARS: usize = 20;
+
+/// Share of the budget adaptive search spends on its initial random sweep.
+const ADAPTIVE_EXPLORATION: f64 = 0.3;
+
+/// Neighbourhood width adaptive search starts from, as a fraction of each range.
+const ADAPTIVE_INITIAL_WIDTH: f64 = 0.25;
+
+/// Draw attempts per requested candidate before a sampler gives up on finding new points.
+const SAMPLE_ATTEMPTS: usize = 20;
+
+/// How candidate parameter sets are generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMethod {
+    /// Every point of the grid; fails if the grid exceeds the evaluation budget.
+    Grid,
+    /// Points drawn uniformly from the space.
+    Random,
+    /// A random sweep followed by rounds sampled around the best candidates so far,
+    /// narrowing the neighbourhood each round.
+    Adaptive,
+}
+
+impl FromStr for SearchMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "grid" => Ok(SearchMethod::Grid),
+            "random" => Ok(SearchMethod::Random),
+            "adaptive" | "bayesian" => Ok(SearchMethod::Adaptive),
+            other => Err(format!("unknown search method `{}`", other)),
+        }
+    }
+}
+
+/// Metric candidates are ranked by; higher scores are better.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Objective {
+    Sharpe,
+    Sortino,
+    ProfitFactor,
+    /// Smallest maximum drawdown wins.
+    MaxDrawdown,
+    TotalReturn,
+}
+
+impl Objective {
+    pub fn score(&self, metrics: &BacktestMetrics) -> f64 {
+        match self {
+            Objective::Sharpe => metrics.sharpe_ratio,
+            Objective::Sortino => metrics.sortino_ratio,
+            Objective::ProfitFactor => metrics.profit_factor,
+            Objective::MaxDrawdown => -metrics.max_drawdown,
+            Objective::TotalReturn => metrics.total_return,
+        }
+    }
+
+    /// Metric value as reported to users, undoing the sign flip on drawdown.
+    pub fn value(&self, metrics: &BacktestMetrics) -> f64 {
+        match self {
+            Objective::MaxDrawdown => metrics.max_drawdown,
+            _ => self.score(metrics),
+        }
+    }
+}
+
+impl FromStr for Objective {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sharpe" | "sharpe_ratio" => Ok(Objective::Sharpe),
+            "sortino" | "sortino_ratio" => Ok(Objective::Sortino),
+            "profit_factor" => Ok(Objective::ProfitFactor),
+            "drawdown" | "max_drawdown" => Ok(Objective::MaxDrawdown),
+            "returns" | "total_return" => Ok(Objective::TotalReturn),
+            other => Err(format!("unknown optimization target `{}`", other)),
+        }
+    }
+}
+
+impl fmt::Display for Objective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Objective::Sharpe => "sharpe",
+            Objective::Sortino => "sortino",
+            Objective::ProfitFactor => "profit_factor",
+            Objective::MaxDrawdown => "max_drawdown",
+            Objective::TotalReturn => "total_return",
+        };
+        f.write_str(label)
+    }
+}
+
+/// In-sample and out-of-sample candle ranges of one walk-forward fold.
+pub type Window = (Range<usize>, Range<usize>);
+
+/// Rolling in-sample/out-of-sample split of the candle history.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WalkForward {
+    /// Number of consecutive windows the history is cut into.
+    pub folds: usize,
+    /// Leading share of each window used in sample; the rest is out of sample.
+    pub in_sample_fraction: f64,
+}
+
+impl Default for WalkForward {
+    fn default() -> Self {
+        Self {
+            folds: 4,
+            in_sample_fraction: 0.7,
+        }
+    }
+}
+
+impl WalkForward {
+    /// In-sample and out-of-sample candle ranges for each fold.
+    pub fn windows(&self, bars: usize) -> Vec<Window> {
+        let folds = self.folds.max(1);
+        let fraction = self.in_sample_fraction.clamp(0.0, 1.0);
+        (0..folds)
+            .map(|fold| {
+                let start = fold * bars / folds;
+                let end = (fold + 1) * bars / folds;
+                let split = start + ((end - start) as f64 * fraction).round() as usize;
+                (start..split, split..end)
+            })
+            .collect()
+    }
+}
+
+/// Conditions a candidate must meet to be selectable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverfitGuards {
+    /// Fewest closed in-sample trades for a score to be trusted.
+    pub min_trades: usize,
+    /// Lowest out-of-sample to in-sample score ratio tolerated under walk-forward.
+    pub min_efficiency: f64,
+}
+
+impl Default for OverfitGuards {
+    fn default() -> Self {
+        Self {
+            min_trades: 5,
+            min_efficiency: 0.5,
+        }
+    }
+}
+
+/// Optimizer settings.
+#[derive(Debug, Clone)]
+pub struct OptimizerConfig {
+    pub method: SearchMethod,
+    pub objective: Objective,
+    /// Most candidates backtested in one run.
+    pub max_evaluations: usize,
+    pub walk_forward: Option<WalkForward>,
+    pub guards: OverfitGuards,
+    pub backtest: BacktestConfig,
+    /// Worker threads; zero uses every available core.
+    pub threads: usize,
+    /// Seed for random and adaptive search, making runs reproducible.
+    pub seed: u64,
+}
+
+impl Default for OptimizerConfig {
+    fn default() -> Self {
+        Self {
+            method: SearchMethod::Random,
+            objective: Objective::Sharpe,
+            max_evaluations: 200,
+            walk_forward: Some(WalkForward::default()),
+            guards: OverfitGuards::default(),
+            backtest: BacktestConfig::default(),
+            threads: 0,
+            seed: 0,
+        }
+    }
+}
+
+/// Errors that abort an optimization run.
+#[derive(Debug, Error)]
+pub enum OptimizerError {
+    #[error("invalid search space: {0}")]
+    Space(#[from] ParamError),
+    #[error("search space is empty")]
+    EmptySpace,
+    #[error("grid of {size} points exceeds the budget of {limit} evaluations; use random or adaptive search")]
+    GridTooLarge { size: usize, limit: usize },
+    #[error("{bars} candles is not enough history; at least {required} are needed")]
+    NotEnoughData { bars: usize, required: usize },
+    #[error("no candidate could be backtested: {0}")]
+    NoCandidates(String),
+}
+
+/// Backtest outcome for one parameter set.
+#[derive(Debug, Clone, Serialize)]
+pub struct CandidateResult {
+    pub parameters: ParamSet,
+    pub in_sample: BacktestMetrics,
+    /// Metrics over the out-of-sample windows under walk-forward validation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_of_sample: Option<BacktestMetrics>,
+    /// Objective score used for ranking; out of sample under walk-forward.
+    pub score: f64,
+    /// Out-of-sample score as a share of the in-sample score.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub efficiency: Option<f64>,
+    /// Why the overfitting guards excluded this candidate from selection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<String>,
+}
+
+/// Ranked candidates of an optimization run.
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizationReport {
+    pub method: SearchMethod,
+    pub objective: Objective,
+    /// Candidates backtested, including rejected ones.
+    pub evaluated: usize,
+    /// Candidates the strategy refused, e.g. for violating cross-parameter constraints.
+    pub invalid: usize,
+    /// Accepted candidates by descending score, followed by rejected ones.
+    pub candidates: Vec<CandidateResult>,
+}
+
+impl OptimizationReport {
+    /// Highest-scoring candidate that passed the overfitting guards.
+    pub fn best(&self) -> Option<&CandidateResult> {
+        self.candidates
+            .first()
+            .filter(|candidate| candidate.rejection.is_none())
+    }
+
+    pub fn rejected(&self) -> usize {
+        self.candidates
+            .iter()
+            .filter(|candidate| candidate.rejection.is_some())
+            .count()
+    }
+}
+
+/// Searches a strategy's parameters by backtesting many candidates in parallel.
+///
+/// `factory` builds a fresh strategy for each candidate; errors mark the candidate
+/// invalid rather than failing the run.
+pub struct StrategyOptimizer<F, const N: usize> {
+    base: ParamSet,
+    factory: F,
+    config: OptimizerConfig,
+}
+
+impl<F, const N: usize> StrategyOptimizer<F, N>
+where
+    F: Fn(&ParamSet) -> Result<Box<dyn StrategyExecutor<N>>, StrategyError> + Sync,
+{
+    /// `base` supplies values for parameters the search space leaves alone.
+    pub fn new(base: ParamSet, factory: F, config: OptimizerConfig) -> Self {
+        Self {
+            base,
+            factory,
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &OptimizerConfig {
+        &self.config
+    }
+
+    pub fn optimize(
+        &self,
+        space: &SearchSpace,
+        candles: &[Candle],
+    ) -> Result<OptimizationReport, OptimizerError> {
+        if space.is_empty() {
+            return Err(OptimizerError::EmptySpace);
+        }
+        let windows = self.windows(candles.len())?;
+        let budget = self.config.max_evaluations.max(1);
+        let mut rng = StdRng::seed_from_u64(self.config.seed);
+        let mut seen = HashSet::new();
+        let mut outcomes = Vec::new();
+
+        match self.config.method {
+            SearchMethod::Grid => {
+                let size = space.grid_size();
+                if size > budget {
+                    return Err(OptimizerError::GridTooLarge {
+                        size,
+                        limit: budget,
+                    });
+                }
+                outcomes.extend(self.evaluate_batch(space, space.grid(), candles, &windows));
+            }
+            SearchMethod::Random => {
+                let points = draw(budget, &mut seen, || space.sample(&mut rng));
+                outcomes.extend(self.evaluate_batch(space, points, candles, &windows));
+            }
+            SearchMethod::Adaptive => {
+                let initial = ((budget as f64 * ADAPTIVE_EXPLORATION).ceil() as usize).max(1);
+                let points = draw(initial, &mut seen, || space.sample(&mut rng));
+                outcomes.extend(self.evaluate_batch(space, points, candles, &windows));
+
+                let mut width = ADAPTIVE_INITIAL_WIDTH;
+                while outcomes.len() < budget {
+                    let elites = elites(&outcomes);
+                    if elites.is_empty() {
+                        break;
+                    }
+                    let round = (budget - outcomes.len()).min(initial);
+                    let mut pick = 0;
+                    let points = draw(round, &mut seen, || {
+                        pick += 1;
+                        space.sample_near(&elites[pick % elites.len()], width, &mut rng)
+                    });
+                    if points.is_empty() {
+                        break;
+                    }
+                    outcomes.extend(self.evaluate_batch(space, points, candles, &windows));
+                    width = (width * 0.7).max(0.02);
+                }
+            }
+        }
+
+        let evaluated = outcomes.len();
+        let mut failures = Vec::new();
+        let mut candidates = Vec::new();
+        for (_, outcome) in outcomes {
+            match outcome {
+                Ok(candidate) => candidates.push(candidate),
+                Err(error) => failures.push(error),
+            }
+        }
+        if candidates.is_empty() {
+            return Err(OptimizerError::NoCandidates(
+                failures
+                    .first()
+                    .map(ToString::to_string)
+                    .unwrap_or_else(|| "search produced no points".to_string()),
+            ));
+        }
+        candidates.sort_by(|a, b| {
+            a.rejection
+                .is_some()
+                .cmp(&b.rejection.is_some())
+                .then(b.score.total_cmp(&a.score))
+        });
+
+        let report = OptimizationReport {
+            method: self.config.method,
+            objective: self.config.objective,
+            evaluated,
+            invalid: failures.len(),
+            candidates,
+        };
+        info!(
+            method = ?report.method,
+            objective = %report.objective,
+            evaluated = report.evaluated,
+            invalid = report.invalid,
+            rejected = report.rejected(),
+            best = ?report.best().map(|c| c.score),
+            "Strategy optimization finished"
+        );
+        Ok(report)
+    }
+
+    fn windows(&self, bars: usize) -> Result<Option<Vec<Window>>, OptimizerError> {
+        let Some(walk_forward) = self.config.walk_forward else {
+            if bars < MIN_WINDOW_BARS {
+                return Err(OptimizerError::NotEnoughData {
+                    bars,
+                    required: MIN_WINDOW_BARS,
+                });
+            }
+            return Ok(None);
+        };
+        let windows = walk_forward.windows(bars);
+        let shortest = windows
+            .iter()
+            .flat_map(|(is, oos)| [is.len(), oos.len()])
+            .min()
+            .unwrap_or(0);
+        if shortest < MIN_WINDOW_BARS {
+            let fraction = walk_forward
+                .in_sample_fraction
+                .min(1.0 - walk_forward.in_sample_fraction)
+                .max(f64::EPSILON);
+            return Err(OptimizerError::NotEnoughData {
+                bars,
+                required: (MIN_WINDOW_BARS as f64 / fraction).ceil() as usize
+                    * walk_forward.folds.max(1),
+            });
+        }
+        Ok(Some(windows))
+    }
+
+    /// Backtests `points` across worker threads, preserving their order.
+    fn evaluate_batch(
+        &self,
+        space: &SearchSpace,
+        points: Vec<Vec<ParamValue>>,
+        candles: &[Candle],
+        windows: &Option<Vec<Window>>,
+    ) -> Vec<Outcome> {
+        if points.is_empty() {
+            return Vec::new();
+        }
+        let threads = match self.config.threads {
+            0 => thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+        let chunk = points.len().div_ceil(threads.min(points.len()));
+
+        thread::scope(|scope| {
+            let workers: Vec<_> = points
+                .chunks(chunk)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|point| {
+                                let params = space.assign(&self.base, point);
+                                let outcome = self.evaluate(&params, candles, windows.as_deref());
+                                (point.clone(), outcome)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("optimizer worker panicked"))
+                .collect()
+        })
+    }
+
+    fn evaluate(
+        &self,
+        params: &ParamSet,
+        candles: &[Candle],
+        windows: Option<&[Window]>,
+    ) -> Result<CandidateResult, StrategyError> {
+        let mut strategy = (self.factory)(params)?;
+        let result = run_backtest(strategy.as_mut(), candles, &self.config.backtest)?;
+        let objective = self.config.objective;
+        let guards = self.config.guards;
+
+        let (in_sample, out_of_sample) = match windows {
+            Some(windows) => {
+                let is: Vec<_> = windows.iter().map(|(is, _)| is.clone()).collect();
+                let oos: Vec<_> = windows.iter().map(|(_, oos)| oos.clone()).collect();
+                (result.metrics_in(&is), Some(result.metrics_in(&oos)))
+            }
+            None => (result.metrics(), None),
+        };
+
+        let in_sample_score = objective.score(&in_sample);
+        let score = out_of_sample
+            .as_ref()
+            .map_or(in_sample_score, |oos| objective.score(oos));
+        let efficiency = out_of_sample
+            .as_ref()
+            .filter(|_| in_sample_score > 0.0)
+            .map(|_| score / in_sample_score);
+
+        let rejection = if in_sample.trades < guards.min_trades {
+            Some(format!(
+                "{} in-sample trades, fewer than the required {}",
+                in_sample.trades, guards.min_trades
+            ))
+        } else {
+            efficiency
+                .filter(|efficiency| *efficiency < guards.min_efficiency)
+                .map(|efficiency| {
+                    format!(
+                        "out-of-sample {} kept {:.0}% of its in-sample value, below {:.0}%",
+                        objective,
+                        efficiency * 100.0,
+                        guards.min_efficiency * 100.0
+                    )
+                })
+        };
+
+        Ok(CandidateResult {
+            parameters: params.clone(),
+            in_sample,
+            out_of_sample,
+            score,
+            efficiency,
+            rejection,
+        })
+    }
+}
+
+type Outcome = (Vec<ParamValue>, Result<CandidateResult, StrategyError>);
+
+/// Draws up to `count` points not seen before.
+fn draw(
+    count: usize,
+    seen: &mut HashSet<String>,
+    mut sample: impl FnMut() -> Vec<ParamValue>,
+) -> Vec<Vec<ParamValue>> {
+    let mut points = Vec::with_capacity(count);
+    for _ in 0..count * SAMPLE_ATTEMPTS {
+        if points.len() == count {
+            break;
+        }
+        let point = sample();
+        if seen.insert(point_key(&point)) {
+            points.push(point);
+        }
+    }
+    points
+}
+
+fn point_key(point: &[ParamValue]) -> String {
+    point
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Top fifth of evaluated points, preferring ones that passed the guards.
+fn elites(outcomes: &[Outcome]) -> Vec<Vec<ParamValue>> {
+    let mut scored: Vec<_> = outcomes
+        .iter()
+        .filter_map(|(point, outcome)| outcome.as_ref().ok().map(|c| (point, c)))
+        .collect();
+    scored.sort_by(|(_, a), (_, b)| {
+        a.rejection
+            .is_some()
+            .cmp(&b.rejection.is_some())
+            .then(b.score.total_cmp(&a.score))
+    });
+    let keep = (scored.len() / 5).max(2).min(scored.len());
+    scored
+        .into_iter()
+        .take(keep)
+        .map(|(point, _)| point.clone())
+        .collect()
+}
@@ -0,0 +1,402 @@
+//! Parameter Search Space
+//!
+//! Ranges of values the optimizer may assign to each tunable parameter, validated
+//! against the strategy's [`ParamSchema`] so every candidate is in bounds.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use rand::Rng;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use crate::params::{ParamError, ParamSchema, ParamSet, ParamSpec, ParamType, ParamValue};
+
+/// Points a decimal range without a step contributes to a grid.
+pub const DEFAULT_GRID_POINTS: usize = 10;
+
+/// Values a single parameter may take during a search.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamRange {
+    /// An explicit list of candidates.
+    Values(Vec<ParamValue>),
+    Integer {
+        min: i64,
+        max: i64,
+        step: i64,
+    },
+    /// Continuous when `step` is `None`, except on grids.
+    Decimal {
+        min: Decimal,
+        max: Decimal,
+        step: Option<Decimal>,
+    },
+}
+
+impl ParamRange {
+    /// Parses `[v1, v2, ...]` or `{"min": .., "max": .., "step": ..}` for `spec`.
+    pub fn parse(spec: &ParamSpec, raw: &Value) -> Result<Self, ParamError> {
+        let invalid = |reason: &str| {
+            ParamError::Invalid(format!("range for parameter `{}` {}", spec.name, reason))
+        };
+
+        if let Value::Array(values) = raw {
+            if values.is_empty() {
+                return Err(invalid("must list at least one value"));
+            }
+            let values = values
+                .iter()
+                .map(|value| spec.parse(value))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(ParamRange::Values(values));
+        }
+
+        let Value::Object(bounds) = raw else {
+            return Err(invalid("must be an array of values or {min, max, step}"));
+        };
+        let bound = |key: &str| {
+            bounds
+                .get(key)
+                .ok_or_else(|| invalid(&format!("is missing `{}`", key)))
+                .and_then(|value| spec.parse(value))
+        };
+        match spec.kind {
+            ParamType::Integer => {
+                let (ParamValue::Integer(min), ParamValue::Integer(max)) =
+                    (bound("min")?, bound("max")?)
+                else {
+                    unreachable!("integer specs parse to integers");
+                };
+                let step = match bounds.get("step") {
+                    Some(step) => step
+                        .as_i64()
+                        .filter(|step| *step > 0)
+                        .ok_or_else(|| invalid("needs a positive integer step"))?,
+                    None => 1,
+                };
+                if min > max {
+                    return Err(invalid("has min above max"));
+                }
+                Ok(ParamRange::Integer { min, max, step })
+            }
+            ParamType::Decimal => {
+                let (ParamValue::Decimal(min), ParamValue::Decimal(max)) =
+                    (bound("min")?, bound("max")?)
+                else {
+                    unreachable!("decimal specs parse to decimals");
+                };
+                let step = match bounds.get("step") {
+                    Some(step) => Some(
+                        step.as_number()
+                            .and_then(|step| Decimal::from_str(&step.to_string()).ok())
+                            .filter(|step| *step > Decimal::ZERO)
+                            .ok_or_else(|| invalid("needs a positive step"))?,
+                    ),
+                    None => None,
+                };
+                if min > max {
+                    return Err(invalid("has min above max"));
+                }
+                Ok(ParamRange::Decimal { min, max, step })
+            }
+            ParamType::Boolean | ParamType::Choice => Err(invalid("must be an array of values")),
+        }
+    }
+
+    /// Every value this range contributes to a grid search.
+    pub fn grid_values(&self) -> Vec<ParamValue> {
+        match self {
+            ParamRange::Values(values) => values.clone(),
+            ParamRange::Integer { min, max, step } => (*min..=*max)
+                .step_by(*step as usize)
+                .map(ParamValue::Integer)
+                .collect(),
+            ParamRange::Decimal { min, max, step } => {
+                let step = step.unwrap_or_else(|| {
+                    (*max - *min) / Decimal::from(DEFAULT_GRID_POINTS as u64 - 1)
+                });
+                if step <= Decimal::ZERO {
+                    return vec![ParamValue::Decimal(*min)];
+                }
+                let mut values = Vec::new();
+                let mut value = *min;
+                while value <= *max {
+                    values.push(ParamValue::Decimal(value.normalize()));
+                    value += step;
+                }
+                values
+            }
+        }
+    }
+
+    fn grid_len(&self) -> usize {
+        match self {
+            ParamRange::Values(values) => values.len(),
+            ParamRange::Integer { min, max, step } => ((max - min) / step + 1) as usize,
+            ParamRange::Decimal { .. } => self.grid_values().len(),
+        }
+    }
+
+    /// Draws a value uniformly from the range.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> ParamValue {
+        self.sample_near(None, 1.0, rng)
+    }
+
+    /// Draws a value within `width` (a fraction of the range) of `center`.
+    ///
+    /// Listed values are kept with probability `1 - width` and otherwise redrawn.
+    pub fn sample_near<R: Rng>(
+        &self,
+        center: Option<&ParamValue>,
+        width: f64,
+        rng: &mut R,
+    ) -> ParamValue {
+        match self {
+            ParamRange::Values(values) => match center {
+                Some(center) if !rng.gen_bool(width.clamp(0.0, 1.0)) => center.clone(),
+                _ => values[rng.gen_range(0..values.len())].clone(),
+            },
+            ParamRange::Integer { min, max, step } => {
+                let steps = (max - min) / step;
+                let (low, high) = match center {
+                    Some(ParamValue::Integer(center)) => {
+                        let at = (center - min) / step;
+                        let reach = ((steps as f64 * width).ceil() as i64).max(1);
+                        ((at - reach).max(0), (at + reach).min(steps))
+                    }
+                    _ => (0, steps),
+                };
+                ParamValue::Integer(min + rng.gen_range(low..=high) * step)
+            }
+            ParamRange::Decimal { min, max, step } => {
+                let lo = min.to_f64().unwrap_or(0.0);
+                let hi = max.to_f64().unwrap_or(lo);
+                let (low, high) = match center.and_then(|c| match c {
+                    ParamValue::Decimal(value) => value.to_f64(),
+                    _ => None,
+                }) {
+                    Some(center) => {
+                        let reach = (hi - lo) * width;
+                        ((center - reach).max(lo), (center + reach).min(hi))
+                    }
+                    None => (lo, hi),
+                };
+                let drawn = if high > low {
+                    rng.gen_range(low..=high)
+                } else {
+                    low
+                };
+                let mut value = Decimal::from_f64(drawn).unwrap_or(*min).round_dp(8);
+                if let Some(step) = step {
+                    value = *min + ((value - *min) / *step).round() * *step;
+                }
+                ParamValue::Decimal(value.clamp(*min, *max).normalize())
+            }
+        }
+    }
+}
+
+/// Named parameter ranges explored by the optimizer, in name order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchSpace {
+    dimensions: Vec<(String, ParamRange)>,
+}
+
+impl SearchSpace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: impl Into<String>, range: ParamRange) -> Self {
+        let name = name.into();
+        self.dimensions.retain(|(existing, _)| *existing != name);
+        self.dimensions.push((name, range));
+        self.dimensions.sort_by(|a, b| a.0.cmp(&b.0));
+        self
+    }
+
+    /// Parses API-submitted ranges keyed by parameter name against `schema`.
+    pub fn from_json(
+        schema: &ParamSchema,
+        raw: &HashMap<String, Value>,
+    ) -> Result<Self, ParamError> {
+        let mut space = Self::new();
+        for (name, range) in raw {
+            let spec = schema
+                .get(name)
+                .ok_or_else(|| ParamError::Unknown(name.clone()))?;
+            space = space.with(name.clone(), ParamRange::parse(spec, range)?);
+        }
+        Ok(space)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dimensions.is_empty()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.dimensions.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Number of grid points, saturating on overflow.
+    pub fn grid_size(&self) -> usize {
+        self.dimensions.iter().fold(1usize, |size, (_, range)| {
+            size.saturating_mul(range.grid_len())
+        })
+    }
+
+    /// Cartesian product of every range's grid values.
+    pub fn grid(&self) -> Vec<Vec<ParamValue>> {
+        self.dimensions
+            .iter()
+            .fold(vec![Vec::new()], |points, (_, range)| {
+                let values = range.grid_values();
+                points
+                    .iter()
+                    .flat_map(|point| {
+                        values.iter().map(move |value| {
+                            let mut next = point.clone();
+                            next.push(value.clone());
+                            next
+                        })
+                    })
+                    .collect()
+            })
+    }
+
+    /// Draws one point uniformly from the space.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Vec<ParamValue> {
+        self.dimensions
+            .iter()
+            .map(|(_, range)| range.sample(rng))
+            .collect()
+    }
+
+    /// Draws a point in the neighbourhood of `center`.
+    pub fn sample_near<R: Rng>(
+        &self,
+        center: &[ParamValue],
+        width: f64,
+        rng: &mut R,
+    ) -> Vec<ParamValue> {
+        self.dimensions
+            .iter()
+            .zip(center)
+            .map(|((_, range), value)| range.sample_near(Some(value), width, rng))
+            .collect()
+    }
+
+    /// Overlays a point onto `base`, leaving unsearched parameters untouched.
+    pub fn assign(&self, base: &ParamSet, point: &[ParamValue]) -> ParamSet {
+        let mut params = base.clone();
+        for ((name, _), value) in self.dimensions.iter().zip(point) {
+            params.set(name.clone(), value.clone());
+        }
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    fn schema() -> ParamSchema {
+        ParamSchema::new()
+            .with(ParamSpec::integer("period", 14, 2, 100))
+            .with(ParamSpec::decimal(
+                "threshold",
+                dec!(0.5),
+                Some(dec!(0)),
+                Some(dec!(1)),
+            ))
+            .with(ParamSpec::choice("venue", "kraken", ["kraken", "oanda"]))
+    }
+
+    fn raw(value: Value) -> HashMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn parses_ranges_and_enumerates_grid() {
+        let space = SearchSpace::from_json(
+            &schema(),
+            &raw(json!({
+                "period": { "min": 10, "max": 30, "step": 10 },
+                "threshold": { "min": 0.2, "max": 0.4, "step": 0.1 },
+                "venue": ["kraken", "oanda"],
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(space.grid_size(), 18);
+        let grid = space.grid();
+        assert_eq!(grid.len(), 18);
+        assert_eq!(
+            grid[0],
+            vec![
+                ParamValue::Integer(10),
+                ParamValue::Decimal(dec!(0.2)),
+                ParamValue::Choice("kraken".into()),
+            ]
+        );
+
+        let params = space.assign(&schema().defaults(), &grid[17]);
+        assert_eq!(params.integer("period").unwrap(), 30);
+        assert_eq!(params.decimal("threshold").unwrap(), dec!(0.4));
+        assert_eq!(params.choice("venue").unwrap(), "oanda");
+    }
+
+    #[test]
+    fn rejects_ranges_outside_schema() {
+        assert!(matches!(
+            SearchSpace::from_json(&schema(), &raw(json!({ "period": { "min": 1, "max": 5 } }))),
+            Err(ParamError::BelowMinimum { .. })
+        ));
+        assert!(matches!(
+            SearchSpace::from_json(&schema(), &raw(json!({ "venue": { "min": 1, "max": 5 } }))),
+            Err(ParamError::Invalid(_))
+        ));
+        assert_eq!(
+            SearchSpace::from_json(&schema(), &raw(json!({ "leverage": [1, 2] }))),
+            Err(ParamError::Unknown("leverage".into()))
+        );
+    }
+
+    #[test]
+    fn samples_stay_in_bounds() {
+        let space = SearchSpace::new()
+            .with(
+                "period",
+                ParamRange::Integer {
+                    min: 10,
+                    max: 50,
+                    step: 5,
+                },
+            )
+            .with(
+                "threshold",
+                ParamRange::Decimal {
+                    min: dec!(0.1),
+                    max: dec!(0.9),
+                    step: None,
+                },
+            );
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..200 {
+            let point = space.sample(&mut rng);
+            let near = space.sample_near(&point, 0.1, &mut rng);
+            for value in point.iter().chain(&near) {
+                match value {
+                    ParamValue::Integer(v) => assert!((10..=50).contains(v) && v % 5 == 0),
+                    ParamValue::Decimal(v) => assert!(*v >= dec!(0.1) && *v <= dec!(0.9)),
+                    other => panic!("unexpected {:?}", other),
+                }
+            }
+        }
+    }
+}
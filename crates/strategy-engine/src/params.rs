@@ -0,0 +1,439 @@
+//! Strategy Parameters
+//!
+//! Typed parameter schemas declared by strategies. API-submitted JSON is validated
+//! against the schema into a [`ParamSet`], which running strategies apply in place.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Value type accepted by a parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamType {
+    Integer,
+    Decimal,
+    Boolean,
+    Choice,
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ParamType::Integer => "an integer",
+            ParamType::Decimal => "a number",
+            ParamType::Boolean => "a boolean",
+            ParamType::Choice => "a string",
+        };
+        f.write_str(label)
+    }
+}
+
+/// A validated parameter value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamValue {
+    Integer(i64),
+    Decimal(Decimal),
+    Boolean(bool),
+    Choice(String),
+}
+
+impl ParamValue {
+    pub fn to_json(&self) -> Value {
+        match self {
+            ParamValue::Integer(value) => Value::from(*value),
+            ParamValue::Decimal(value) => value
+                .to_f64()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number)
+                .unwrap_or_else(|| Value::String(value.to_string())),
+            ParamValue::Boolean(value) => Value::Bool(*value),
+            ParamValue::Choice(value) => Value::String(value.clone()),
+        }
+    }
+
+    fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            ParamValue::Integer(value) => Some(Decimal::from(*value)),
+            ParamValue::Decimal(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamValue::Integer(value) => write!(f, "{}", value),
+            ParamValue::Decimal(value) => write!(f, "{}", value),
+            ParamValue::Boolean(value) => write!(f, "{}", value),
+            ParamValue::Choice(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl Serialize for ParamValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json().serialize(serializer)
+    }
+}
+
+/// Errors raised while validating strategy parameters.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ParamError {
+    #[error("unknown parameter `{0}`")]
+    Unknown(String),
+    #[error("parameter `{name}` must be {expected}")]
+    WrongType { name: String, expected: ParamType },
+    #[error("parameter `{name}` = {value} is below the minimum {min}")]
+    BelowMinimum {
+        name: String,
+        value: Decimal,
+        min: Decimal,
+    },
+    #[error("parameter `{name}` = {value} is above the maximum {max}")]
+    AboveMaximum {
+        name: String,
+        value: Decimal,
+        max: Decimal,
+    },
+    #[error("parameter `{name}` must be one of {choices:?}")]
+    InvalidChoice { name: String, choices: Vec<String> },
+    #[error("{0}")]
+    Invalid(String),
+}
+
+/// Declaration of a single tunable parameter.
+#[derive(Debug, Clone, Serialize)]
+pub struct ParamSpec {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: ParamType,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    pub default: ParamValue,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<Decimal>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<String>,
+}
+
+impl ParamSpec {
+    pub fn integer(name: impl Into<String>, default: i64, min: i64, max: i64) -> Self {
+        Self::new(name, ParamType::Integer, ParamValue::Integer(default))
+            .bounded(Some(Decimal::from(min)), Some(Decimal::from(max)))
+    }
+
+    pub fn decimal(
+        name: impl Into<String>,
+        default: Decimal,
+        min: Option<Decimal>,
+        max: Option<Decimal>,
+    ) -> Self {
+        Self::new(name, ParamType::Decimal, ParamValue::Decimal(default)).bounded(min, max)
+    }
+
+    pub fn boolean(name: impl Into<String>, default: bool) -> Self {
+        Self::new(name, ParamType::Boolean, ParamValue::Boolean(default))
+    }
+
+    pub fn choice<I, T>(name: impl Into<String>, default: impl Into<String>, choices: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let mut spec = Self::new(name, ParamType::Choice, ParamValue::Choice(default.into()));
+        spec.choices = choices.into_iter().map(Into::into).collect();
+        spec
+    }
+
+    pub fn describe(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    fn new(name: impl Into<String>, kind: ParamType, default: ParamValue) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            description: String::new(),
+            default,
+            min: None,
+            max: None,
+            choices: Vec::new(),
+        }
+    }
+
+    fn bounded(mut self, min: Option<Decimal>, max: Option<Decimal>) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Parses and range-checks a raw JSON value.
+    pub fn parse(&self, raw: &Value) -> Result<ParamValue, ParamError> {
+        let wrong_type = || ParamError::WrongType {
+            name: self.name.clone(),
+            expected: self.kind,
+        };
+        let value = match self.kind {
+            ParamType::Integer => raw
+                .as_i64()
+                .map(ParamValue::Integer)
+                .ok_or_else(wrong_type)?,
+            ParamType::Decimal => parse_decimal(raw)
+                .map(ParamValue::Decimal)
+                .ok_or_else(wrong_type)?,
+            ParamType::Boolean => raw
+                .as_bool()
+                .map(ParamValue::Boolean)
+                .ok_or_else(wrong_type)?,
+            ParamType::Choice => {
+                let choice = raw.as_str().ok_or_else(wrong_type)?;
+                if !self.choices.iter().any(|allowed| allowed == choice) {
+                    return Err(ParamError::InvalidChoice {
+                        name: self.name.clone(),
+                        choices: self.choices.clone(),
+                    });
+                }
+                ParamValue::Choice(choice.to_string())
+            }
+        };
+        self.check_bounds(&value)?;
+        Ok(value)
+    }
+
+    fn check_bounds(&self, value: &ParamValue) -> Result<(), ParamError> {
+        let Some(number) = value.as_decimal() else {
+            return Ok(());
+        };
+        if let Some(min) = self.min.filter(|min| number < *min) {
+            return Err(ParamError::BelowMinimum {
+                name: self.name.clone(),
+                value: number,
+                min,
+            });
+        }
+        if let Some(max) = self.max.filter(|max| number > *max) {
+            return Err(ParamError::AboveMaximum {
+                name: self.name.clone(),
+                value: number,
+                max,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn parse_decimal(raw: &Value) -> Option<Decimal> {
+    let text = match raw {
+        Value::Number(number) => number.to_string(),
+        Value::String(text) => text.clone(),
+        _ => return None,
+    };
+    Decimal::from_str(&text)
+        .or_else(|_| Decimal::from_scientific(&text))
+        .ok()
+}
+
+/// Ordered set of parameters a strategy accepts.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ParamSchema {
+    pub params: Vec<ParamSpec>,
+}
+
+impl ParamSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, spec: ParamSpec) -> Self {
+        self.params.push(spec);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ParamSpec> {
+        self.params.iter().find(|spec| spec.name == name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    pub fn defaults(&self) -> ParamSet {
+        ParamSet {
+            values: self
+                .params
+                .iter()
+                .map(|spec| (spec.name.clone(), spec.default.clone()))
+                .collect(),
+        }
+    }
+
+    /// Validates a full submission, filling omitted parameters with defaults.
+    pub fn validate(&self, raw: &HashMap<String, Value>) -> Result<ParamSet, ParamError> {
+        self.apply(&self.defaults(), raw)
+    }
+
+    /// Validates a partial update and merges it over `current`.
+    pub fn apply(
+        &self,
+        current: &ParamSet,
+        changes: &HashMap<String, Value>,
+    ) -> Result<ParamSet, ParamError> {
+        let mut next = current.clone();
+        let mut names: Vec<&String> = changes.keys().collect();
+        names.sort();
+        for name in names {
+            let spec = self
+                .get(name)
+                .ok_or_else(|| ParamError::Unknown(name.clone()))?;
+            next.values
+                .insert(name.clone(), spec.parse(&changes[name])?);
+        }
+        Ok(next)
+    }
+}
+
+/// Validated parameter values keyed by name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct ParamSet {
+    values: BTreeMap<String, ParamValue>,
+}
+
+impl ParamSet {
+    pub fn get(&self, name: &str) -> Option<&ParamValue> {
+        self.values.get(name)
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: ParamValue) {
+        self.values.insert(name.into(), value);
+    }
+
+    pub fn integer(&self, name: &str) -> Result<i64, ParamError> {
+        match self.values.get(name) {
+            Some(ParamValue::Integer(value)) => Ok(*value),
+            _ => Err(self.missing(name, ParamType::Integer)),
+        }
+    }
+
+    pub fn decimal(&self, name: &str) -> Result<Decimal, ParamError> {
+        match self.values.get(name) {
+            Some(ParamValue::Decimal(value)) => Ok(*value),
+            Some(ParamValue::Integer(value)) => Ok(Decimal::from(*value)),
+            _ => Err(self.missing(name, ParamType::Decimal)),
+        }
+    }
+
+    pub fn boolean(&self, name: &str) -> Result<bool, ParamError> {
+        match self.values.get(name) {
+            Some(ParamValue::Boolean(value)) => Ok(*value),
+            _ => Err(self.missing(name, ParamType::Boolean)),
+        }
+    }
+
+    pub fn choice(&self, name: &str) -> Result<&str, ParamError> {
+        match self.values.get(name) {
+            Some(ParamValue::Choice(value)) => Ok(value),
+            _ => Err(self.missing(name, ParamType::Choice)),
+        }
+    }
+
+    /// Names whose values differ from `other`, in sorted order.
+    pub fn changed_from(&self, other: &ParamSet) -> Vec<String> {
+        self.values
+            .iter()
+            .filter(|(name, value)| other.values.get(*name) != Some(value))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    pub fn to_json(&self) -> HashMap<String, Value> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.clone(), value.to_json()))
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ParamValue)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    fn missing(&self, name: &str, expected: ParamType) -> ParamError {
+        ParamError::WrongType {
+            name: name.to_string(),
+            expected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    fn schema() -> ParamSchema {
+        ParamSchema::new()
+            .with(ParamSpec::integer("period", 14, 2, 100))
+            .with(ParamSpec::decimal(
+                "size",
+                dec!(0.1),
+                Some(dec!(0.001)),
+                None,
+            ))
+            .with(ParamSpec::choice("venue", "kraken", ["kraken", "oanda"]))
+    }
+
+    fn raw(value: Value) -> HashMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn validate_fills_defaults_and_checks_bounds() {
+        let schema = schema();
+        let params = schema.validate(&raw(json!({ "size": "0.5" }))).unwrap();
+        assert_eq!(params.integer("period").unwrap(), 14);
+        assert_eq!(params.decimal("size").unwrap(), dec!(0.5));
+        assert_eq!(params.choice("venue").unwrap(), "kraken");
+
+        assert!(matches!(
+            schema.validate(&raw(json!({ "period": 1 }))),
+            Err(ParamError::BelowMinimum { .. })
+        ));
+        assert!(matches!(
+            schema.validate(&raw(json!({ "period": 14.5 }))),
+            Err(ParamError::WrongType { .. })
+        ));
+        assert!(matches!(
+            schema.validate(&raw(json!({ "venue": "binance" }))),
+            Err(ParamError::InvalidChoice { .. })
+        ));
+        assert_eq!(
+            schema.validate(&raw(json!({ "leverage": 3 }))),
+            Err(ParamError::Unknown("leverage".into()))
+        );
+    }
+
+    #[test]
+    fn apply_merges_partial_updates() {
+        let schema = schema();
+        let current = schema.defaults();
+        let next = schema
+            .apply(&current, &raw(json!({ "period": 21 })))
+            .unwrap();
+        assert_eq!(next.changed_from(&current), vec!["period".to_string()]);
+        assert_eq!(next.to_json()["size"], json!(0.1));
+    }
+}
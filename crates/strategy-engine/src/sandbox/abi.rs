@@ -0,0 +1,584 @@
+//! Host ABI exposed to sandboxed strategies.
+//!
+//! All functions are imported from the `host` module. Strings and JSON documents are
+//! passed as `(ptr, len)` pairs into guest memory. Version 1 provides `log` and
+//! `emit_signal`, whose `strategy_id` and `account_id` are overwritten with the
+//! instance's own identity; version 2 adds:
+//!
+//! | import | signature | result |
+//! |---|---|---|
+//! | `indicator_count` | `() -> i32` | number of indicators attached to the strategy |
+//! | `indicator_find` | `(name_ptr, name_len) -> i32` | index of the first indicator with that label (`RSI`, `RSI of OBV`), or -1 |
+//! | `indicator_value` | `(index) -> f64` | latest value, NaN until warmed up |
+//! | `indicator_signal` | `(index) -> f64` | signal line value, NaN when absent |
+//! | `state_get` | `(key_ptr, key_len, out_ptr, out_cap) -> i32` | value length (copied when it fits `out_cap`), or -1 |
+//! | `state_set` | `(key_ptr, key_len, val_ptr, val_len) -> i32` | 0, -1 over quota, -2 value not UTF-8 |
+//! | `state_delete` | `(key_ptr, key_len) -> i32` | 1 removed, 0 absent |
+//! | `position_quantity` | `(symbol_ptr, symbol_len) -> f64` | signed quantity, 0 when flat |
+//! | `position_entry_price` | `(symbol_ptr, symbol_len) -> f64` | average entry price, NaN when flat |
+//! | `balance` | `(asset_ptr, asset_len) -> f64` | available balance, 0 when unknown |
+//! | `emit_order` | `(ptr, len) -> i32` | 0 accepted, -1 malformed JSON, -2 rejected [`WasmOrderIntent`] |
+//!
+//! Version 3 adds:
+//!
+//! | import | signature | result |
+//! |---|---|---|
+//! | `indicator_component` | `(index, name_ptr, name_len) -> f64` | named output line (`upper`, `histogram`, ...), NaN until warmed up or when absent |
+//!
+//! Modules declare the version they target by exporting `abi_version() -> i32`;
+//! modules without the export are treated as version 1.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context;
+use event_bus::{Priority, SignalEventPayload, SignalIntent, StrategySignal};
+use exchange_connectors::ExchangeId;
+use ninja_gekko_core::position_sizing;
+use ninja_gekko_core::types::{AccountId, OrderSide, OrderType};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use uuid::Uuid;
+use wasmtime::{AsContextMut, Caller, Linker, StoreLimits};
+
+use super::state_store::SandboxState;
+use crate::indicators::state::IndicatorState;
+use crate::traits::{StrategyError, WasmSignalInstruction};
+
+/// Newest host ABI version this runtime implements.
+pub const HOST_ABI_VERSION: u32 = 3;
+
+/// Metadata keys carrying protective levels of an order intent on the emitted signal.
+pub const STOP_LOSS_KEY: &str = position_sizing::STOP_LOSS_KEY;
+pub const TAKE_PROFIT_KEY: &str = "take_profit";
+
+/// Open position as seen by a sandboxed strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PositionView {
+    /// Signed quantity; negative for shorts.
+    pub quantity: Decimal,
+    pub average_price: Decimal,
+}
+
+/// Read-only account data sandboxed strategies may query.
+pub trait PortfolioView: Send + Sync {
+    fn position(&self, account_id: &AccountId, symbol: &str) -> Option<PositionView>;
+    fn balance(&self, account_id: &AccountId, asset: &str) -> Option<Decimal>;
+}
+
+/// Fixed positions and balances, e.g. refreshed by the host between evaluations.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PortfolioSnapshot {
+    pub positions: HashMap<String, PositionView>,
+    pub balances: HashMap<String, Decimal>,
+}
+
+impl PortfolioView for PortfolioSnapshot {
+    fn position(&self, _account_id: &AccountId, symbol: &str) -> Option<PositionView> {
+        self.positions.get(symbol).copied()
+    }
+
+    fn balance(&self, _account_id: &AccountId, asset: &str) -> Option<Decimal> {
+        self.balances.get(asset).copied()
+    }
+}
+
+/// Structured order request emitted through `host.emit_order`.
+///
+/// Strategy and account identity are filled in by the host, so a module can only
+/// trade for the account it was started for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WasmOrderIntent {
+    pub symbol: String,
+    pub side: OrderSide,
+    #[serde(default = "market_order")]
+    pub order_type: OrderType,
+    pub quantity: Decimal,
+    #[serde(default)]
+    pub limit_price: Option<Decimal>,
+    #[serde(default)]
+    pub stop_loss: Option<Decimal>,
+    #[serde(default)]
+    pub take_profit: Option<Decimal>,
+    #[serde(default)]
+    pub exchange: Option<ExchangeId>,
+    #[serde(default = "full_confidence")]
+    pub confidence: f64,
+    #[serde(default)]
+    pub priority: Priority,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+fn market_order() -> OrderType {
+    OrderType::Market
+}
+
+fn full_confidence() -> f64 {
+    1.0
+}
+
+impl WasmOrderIntent {
+    /// Checks quantities, prices and that protective levels sit on the right side.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.symbol.trim().is_empty() {
+            return Err("symbol is required".into());
+        }
+        if self.quantity <= Decimal::ZERO {
+            return Err("quantity must be positive".into());
+        }
+        if !(0.0..=1.0).contains(&self.confidence) {
+            return Err("confidence must be within [0, 1]".into());
+        }
+        if self.order_type.requires_price() && self.limit_price.is_none() {
+            return Err(format!("{:?} orders require limit_price", self.order_type));
+        }
+        for (label, price) in [
+            ("limit_price", self.limit_price),
+            ("stop_loss", self.stop_loss),
+            ("take_profit", self.take_profit),
+        ] {
+            if matches!(price, Some(p) if p <= Decimal::ZERO) {
+                return Err(format!("{} must be positive", label));
+            }
+        }
+
+        // For a buy the stop sits below the entry and the target above; mirrored for sells.
+        let below_above = |low: Option<Decimal>, high: Option<Decimal>| match (low, high) {
+            (Some(low), Some(high)) => low < high,
+            _ => true,
+        };
+        let (stop_ok, target_ok, bracket_ok) = match self.side {
+            OrderSide::Buy => (
+                below_above(self.stop_loss, self.limit_price),
+                below_above(self.limit_price, self.take_profit),
+                below_above(self.stop_loss, self.take_profit),
+            ),
+            OrderSide::Sell => (
+                below_above(self.limit_price, self.stop_loss),
+                below_above(self.take_profit, self.limit_price),
+                below_above(self.take_profit, self.stop_loss),
+            ),
+        };
+        if !stop_ok || !bracket_ok {
+            return Err("stop_loss is on the wrong side of the entry".into());
+        }
+        if !target_ok {
+            return Err("take_profit is on the wrong side of the entry".into());
+        }
+        Ok(())
+    }
+
+    pub fn into_payload(self, strategy_id: Uuid, account_id: AccountId) -> SignalEventPayload {
+        let mut metadata = self.metadata;
+        if let Some(stop_loss) = self.stop_loss {
+            metadata.insert(STOP_LOSS_KEY.to_string(), stop_loss.to_string());
+        }
+        if let Some(take_profit) = self.take_profit {
+            metadata.insert(TAKE_PROFIT_KEY.to_string(), take_profit.to_string());
+        }
+        SignalEventPayload {
+            strategy_id,
+            account_id,
+            priority: self.priority,
+            signal: StrategySignal {
+                exchange: self.exchange,
+                symbol: self.symbol,
+                side: self.side,
+                order_type: self.order_type,
+                quantity: self.quantity,
+                limit_price: self.limit_price,
+                confidence: self.confidence,
+                metadata,
+                intent: SignalIntent::Order,
+            },
+        }
+    }
+}
+
+/// Indicator reading copied into the store before each evaluation.
+pub(super) struct IndicatorReading {
+    label: String,
+    value: f64,
+    signal: f64,
+    components: Vec<(&'static str, f64)>,
+}
+
+pub(super) fn indicator_readings(state: Option<&IndicatorState>) -> Vec<IndicatorReading> {
+    let Some(state) = state else {
+        return Vec::new();
+    };
+    state
+        .indicators
+        .iter()
+        .enumerate()
+        .map(|(index, indicator)| {
+            let ready = indicator.is_ready();
+            let current = indicator.current().filter(|_| ready);
+            let components = indicator
+                .output()
+                .filter(|_| ready)
+                .map(|output| {
+                    output
+                        .iter()
+                        .map(|(name, value)| (name, value.to_f64().unwrap_or(f64::NAN)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            IndicatorReading {
+                label: state
+                    .label(index)
+                    .unwrap_or_else(|| indicator.name().to_string()),
+                value: current.and_then(|v| v.value.to_f64()).unwrap_or(f64::NAN),
+                signal: current
+                    .and_then(|v| v.signal)
+                    .and_then(|s| s.to_f64())
+                    .unwrap_or(f64::NAN),
+                components,
+            }
+        })
+        .collect()
+}
+
+/// Per-instance data reachable from host functions.
+pub(super) struct StrategyEnvState {
+    pub(super) limits: StoreLimits,
+    pub(super) logs: Vec<String>,
+    pub(super) signals: Vec<SignalEventPayload>,
+    pub(super) strategy_id: Uuid,
+    pub(super) account_id: AccountId,
+    pub(super) indicators: Vec<IndicatorReading>,
+    pub(super) state: SandboxState,
+    pub(super) state_limit: usize,
+    pub(super) state_dirty: bool,
+    pub(super) portfolio: Option<Arc<dyn PortfolioView>>,
+}
+
+impl StrategyEnvState {
+    pub(super) fn new(limits: StoreLimits, state_limit: usize) -> Self {
+        Self {
+            limits,
+            logs: Vec::new(),
+            signals: Vec::new(),
+            strategy_id: Uuid::nil(),
+            account_id: AccountId::new(),
+            indicators: Vec::new(),
+            state: SandboxState::new(),
+            state_limit,
+            state_dirty: false,
+            portfolio: None,
+        }
+    }
+
+    fn state_size(&self) -> usize {
+        self.state.iter().map(|(k, v)| k.len() + v.len()).sum()
+    }
+}
+
+type Env<'a> = Caller<'a, StrategyEnvState>;
+
+pub(super) fn link_host_functions(
+    linker: &mut Linker<StrategyEnvState>,
+) -> Result<(), StrategyError> {
+    link_v1(linker).map_err(StrategyError::Wasm)?;
+    link_v2(linker).map_err(StrategyError::Wasm)?;
+    link_v3(linker).map_err(StrategyError::Wasm)?;
+    Ok(())
+}
+
+fn link_v1(linker: &mut Linker<StrategyEnvState>) -> anyhow::Result<()> {
+    linker.func_wrap(
+        "host",
+        "log",
+        |mut caller: Env<'_>, ptr: i32, len: i32| -> anyhow::Result<()> {
+            if let Ok(bytes) = read_guest(&mut caller, ptr, len) {
+                if let Ok(message) = String::from_utf8(bytes) {
+                    caller.data_mut().logs.push(message);
+                }
+            }
+            Ok(())
+        },
+    )?;
+
+    linker.func_wrap(
+        "host",
+        "emit_signal",
+        |mut caller: Env<'_>, ptr: i32, len: i32| -> anyhow::Result<()> {
+            let bytes = read_guest(&mut caller, ptr, len)?;
+            let instruction: WasmSignalInstruction = serde_json::from_slice(&bytes)?;
+            // A module only ever speaks for the strategy and account it runs as
+            let env = caller.data_mut();
+            if instruction.strategy_id != env.strategy_id
+                || instruction.account_id != env.account_id
+            {
+                debug!(
+                    claimed_account = %instruction.account_id,
+                    account = %env.account_id,
+                    "restamped sandbox signal identity"
+                );
+            }
+            let payload = SignalEventPayload {
+                strategy_id: env.strategy_id,
+                account_id: env.account_id.clone(),
+                priority: instruction.priority,
+                signal: instruction.signal,
+            };
+            env.signals.push(payload);
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+fn link_v2(linker: &mut Linker<StrategyEnvState>) -> anyhow::Result<()> {
+    linker.func_wrap("host", "indicator_count", |caller: Env<'_>| -> i32 {
+        caller.data().indicators.len() as i32
+    })?;
+
+    linker.func_wrap(
+        "host",
+        "indicator_find",
+        |mut caller: Env<'_>, ptr: i32, len: i32| -> anyhow::Result<i32> {
+            let name = read_guest_str(&mut caller, ptr, len)?;
+            Ok(caller
+                .data()
+                .indicators
+                .iter()
+                .position(|reading| reading.label.eq_ignore_ascii_case(&name))
+                .map_or(-1, |index| index as i32))
+        },
+    )?;
+
+    linker.func_wrap(
+        "host",
+        "indicator_value",
+        |caller: Env<'_>, index: i32| -> f64 {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| caller.data().indicators.get(index))
+                .map_or(f64::NAN, |reading| reading.value)
+        },
+    )?;
+
+    linker.func_wrap(
+        "host",
+        "indicator_signal",
+        |caller: Env<'_>, index: i32| -> f64 {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| caller.data().indicators.get(index))
+                .map_or(f64::NAN, |reading| reading.signal)
+        },
+    )?;
+
+    linker.func_wrap(
+        "host",
+        "state_get",
+        |mut caller: Env<'_>,
+         key_ptr: i32,
+         key_len: i32,
+         out_ptr: i32,
+         out_cap: i32|
+         -> anyhow::Result<i32> {
+            let key = read_guest_str(&mut caller, key_ptr, key_len)?;
+            let Some(value) = caller.data().state.get(&key).cloned() else {
+                return Ok(-1);
+            };
+            if value.len() <= out_cap.max(0) as usize {
+                write_guest(&mut caller, out_ptr, value.as_bytes())?;
+            }
+            Ok(value.len() as i32)
+        },
+    )?;
+
+    linker.func_wrap(
+        "host",
+        "state_set",
+        |mut caller: Env<'_>,
+         key_ptr: i32,
+         key_len: i32,
+         val_ptr: i32,
+         val_len: i32|
+         -> anyhow::Result<i32> {
+            let key = read_guest_str(&mut caller, key_ptr, key_len)?;
+            let Ok(value) = String::from_utf8(read_guest(&mut caller, val_ptr, val_len)?) else {
+                return Ok(-2);
+            };
+            let env = caller.data_mut();
+            let replaced = env.state.get(&key).map_or(0, |old| key.len() + old.len());
+            if env.state_size() - replaced + key.len() + value.len() > env.state_limit {
+                return Ok(-1);
+            }
+            env.state.insert(key, value);
+            env.state_dirty = true;
+            Ok(0)
+        },
+    )?;
+
+    linker.func_wrap(
+        "host",
+        "state_delete",
+        |mut caller: Env<'_>, key_ptr: i32, key_len: i32| -> anyhow::Result<i32> {
+            let key = read_guest_str(&mut caller, key_ptr, key_len)?;
+            let env = caller.data_mut();
+            if env.state.remove(&key).is_some() {
+                env.state_dirty = true;
+                Ok(1)
+            } else {
+                Ok(0)
+            }
+        },
+    )?;
+
+    linker.func_wrap(
+        "host",
+        "position_quantity",
+        |mut caller: Env<'_>, ptr: i32, len: i32| -> anyhow::Result<f64> {
+            let symbol = read_guest_str(&mut caller, ptr, len)?;
+            Ok(position(&caller, &symbol)
+                .and_then(|p| p.quantity.to_f64())
+                .unwrap_or(0.0))
+        },
+    )?;
+
+    linker.func_wrap(
+        "host",
+        "position_entry_price",
+        |mut caller: Env<'_>, ptr: i32, len: i32| -> anyhow::Result<f64> {
+            let symbol = read_guest_str(&mut caller, ptr, len)?;
+            Ok(position(&caller, &symbol)
+                .filter(|p| !p.quantity.is_zero())
+                .and_then(|p| p.average_price.to_f64())
+                .unwrap_or(f64::NAN))
+        },
+    )?;
+
+    linker.func_wrap(
+        "host",
+        "balance",
+        |mut caller: Env<'_>, ptr: i32, len: i32| -> anyhow::Result<f64> {
+            let asset = read_guest_str(&mut caller, ptr, len)?;
+            let env = caller.data();
+            Ok(env
+                .portfolio
+                .as_ref()
+                .and_then(|portfolio| portfolio.balance(&env.account_id, &asset))
+                .and_then(|balance| balance.to_f64())
+                .unwrap_or(0.0))
+        },
+    )?;
+
+    linker.func_wrap(
+        "host",
+        "emit_order",
+        |mut caller: Env<'_>, ptr: i32, len: i32| -> anyhow::Result<i32> {
+            let bytes = read_guest(&mut caller, ptr, len)?;
+            let Ok(intent) = serde_json::from_slice::<WasmOrderIntent>(&bytes) else {
+                return Ok(-1);
+            };
+            if let Err(reason) = intent.validate() {
+                debug!(symbol = %intent.symbol, "rejected sandbox order intent: {}", reason);
+                caller
+                    .data_mut()
+                    .logs
+                    .push(format!("order intent rejected: {}", reason));
+                return Ok(-2);
+            }
+            let env = caller.data_mut();
+            let payload = intent.into_payload(env.strategy_id, env.account_id.clone());
+            env.signals.push(payload);
+            Ok(0)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn link_v3(linker: &mut Linker<StrategyEnvState>) -> anyhow::Result<()> {
+    linker.func_wrap(
+        "host",
+        "indicator_component",
+        |mut caller: Env<'_>, index: i32, ptr: i32, len: i32| -> anyhow::Result<f64> {
+            let name = read_guest_str(&mut caller, ptr, len)?;
+            Ok(usize::try_from(index)
+                .ok()
+                .and_then(|index| caller.data().indicators.get(index))
+                .and_then(|reading| {
+                    reading
+                        .components
+                        .iter()
+                        .find(|(component, _)| component.eq_ignore_ascii_case(&name))
+                })
+                .map_or(f64::NAN, |(_, value)| *value))
+        },
+    )?;
+    Ok(())
+}
+
+fn position(caller: &Env<'_>, symbol: &str) -> Option<PositionView> {
+    let env = caller.data();
+    env.portfolio
+        .as_ref()
+        .and_then(|portfolio| portfolio.position(&env.account_id, symbol))
+}
+
+fn guest_memory(caller: &mut Env<'_>) -> anyhow::Result<wasmtime::Memory> {
+    caller
+        .get_export("memory")
+        .and_then(|export| export.into_memory())
+        .context("webassembly memory export missing")
+}
+
+fn read_guest(caller: &mut Env<'_>, ptr: i32, len: i32) -> anyhow::Result<Vec<u8>> {
+    let memory = guest_memory(caller)?;
+    let len = usize::try_from(len).context("negative length passed to host")?;
+    let mut buf = vec![0u8; len];
+    memory.read(caller.as_context_mut(), ptr as u32 as usize, &mut buf)?;
+    Ok(buf)
+}
+
+fn read_guest_str(caller: &mut Env<'_>, ptr: i32, len: i32) -> anyhow::Result<String> {
+    String::from_utf8(read_guest(caller, ptr, len)?).context("host string argument is not UTF-8")
+}
+
+fn write_guest(caller: &mut Env<'_>, ptr: i32, bytes: &[u8]) -> anyhow::Result<()> {
+    let memory = guest_memory(caller)?;
+    memory.write(caller.as_context_mut(), ptr as u32 as usize, bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn intent(json: &str) -> WasmOrderIntent {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn order_intent_validation_checks_protective_levels() {
+        let buy = intent(
+            r#"{"symbol":"BTC-USD","side":"Buy","order_type":"Limit","quantity":"1","limit_price":"30000","stop_loss":"29000","take_profit":"31000"}"#,
+        );
+        assert!(buy.validate().is_ok());
+
+        let mut inverted = buy.clone();
+        inverted.stop_loss = Some(dec!(30500));
+        assert!(inverted.validate().is_err());
+
+        let mut unpriced = buy.clone();
+        unpriced.limit_price = None;
+        assert!(unpriced.validate().is_err());
+
+        let sell = intent(
+            r#"{"symbol":"BTC-USD","side":"Sell","quantity":"1","stop_loss":"31000","take_profit":"29000"}"#,
+        );
+        assert!(sell.validate().is_ok());
+        let payload = sell.into_payload(Uuid::nil(), "acct".into());
+        assert_eq!(payload.signal.order_type, OrderType::Market);
+        assert_eq!(payload.signal.metadata[TAKE_PROFIT_KEY], "29000");
+    }
+}
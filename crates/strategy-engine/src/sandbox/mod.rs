@@ -0,0 +1,340 @@
+use std::mem;
+use std::sync::Arc;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use ninja_gekko_core::types::AccountId;
+use serde::Serialize;
+use tracing::warn;
+use uuid::Uuid;
+use wasmtime::{
+    AsContextMut, Config, Engine, Linker, Memory, Module, Store, StoreLimitsBuilder, Trap,
+    TypedFunc,
+};
+
+use crate::indicators::buffer::Candle;
+use crate::traits::{
+    MarketSnapshot, StrategyContext, StrategyDecision, StrategyError, StrategyExecutor,
+    StrategyInitContext, StrategyMetrics,
+};
+
+pub mod abi;
+pub mod state_store;
+
+pub use abi::{
+    PortfolioSnapshot, PortfolioView, PositionView, WasmOrderIntent, HOST_ABI_VERSION,
+    STOP_LOSS_KEY, TAKE_PROFIT_KEY,
+};
+pub use state_store::{FileStateStore, InMemoryStateStore, SandboxState, StrategyStateStore};
+
+use abi::{indicator_readings, link_host_functions, StrategyEnvState};
+
+const DEFAULT_MEMORY_LIMIT: u64 = 16 * 1024 * 1024;
+const DEFAULT_FUEL_LIMIT: u64 = 5_000_000;
+const DEFAULT_STATE_LIMIT: usize = 64 * 1024;
+
+#[derive(Clone)]
+pub struct WasmStrategyConfig {
+    pub memory_limit: u64,
+    /// Fuel granted to each `evaluate` call; roughly one unit per executed instruction.
+    pub fuel_limit: u64,
+    /// Combined size in bytes of keys and values in the persisted state.
+    pub state_limit: usize,
+}
+
+impl Default for WasmStrategyConfig {
+    fn default() -> Self {
+        Self {
+            memory_limit: DEFAULT_MEMORY_LIMIT,
+            fuel_limit: DEFAULT_FUEL_LIMIT,
+            state_limit: DEFAULT_STATE_LIMIT,
+        }
+    }
+}
+
+pub struct WasmStrategyModule {
+    engine: Engine,
+    module: Module,
+}
+
+impl WasmStrategyModule {
+    pub fn from_bytes(bytes: &[u8], config: &WasmStrategyConfig) -> Result<Self, StrategyError> {
+        let mut wasm_config = Config::new();
+        wasm_config.wasm_multi_memory(true);
+        wasm_config.consume_fuel(true);
+        wasm_config.static_memory_maximum_size(config.memory_limit);
+        wasm_config.dynamic_memory_guard_size(0);
+        wasm_config.static_memory_guard_size(0);
+
+        let engine = Engine::new(&wasm_config).map_err(StrategyError::Wasm)?;
+        let module = Module::new(&engine, bytes).map_err(StrategyError::Wasm)?;
+        Ok(Self { engine, module })
+    }
+
+    pub fn instantiate(
+        &self,
+        config: WasmStrategyConfig,
+    ) -> Result<WasmStrategyInstance, StrategyError> {
+        WasmStrategyInstance::new(self.engine.clone(), self.module.clone(), config)
+    }
+}
+
+pub struct WasmStrategyInstance {
+    config: WasmStrategyConfig,
+    abi_version: u32,
+    store: Store<StrategyEnvState>,
+    memory: Memory,
+    alloc: TypedFunc<u32, u32>,
+    evaluate: TypedFunc<(i32, i32), i32>,
+    state_store: Option<(Arc<dyn StrategyStateStore>, String)>,
+}
+
+impl WasmStrategyInstance {
+    fn new(
+        engine: Engine,
+        module: Module,
+        config: WasmStrategyConfig,
+    ) -> Result<Self, StrategyError> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(config.memory_limit as usize)
+            .instances(1)
+            .build();
+
+        let mut store = Store::new(&engine, StrategyEnvState::new(limits, config.state_limit));
+        store.limiter(|state| &mut state.limits);
+        // Start functions and `abi_version` run on the same budget as one evaluation.
+        store
+            .set_fuel(config.fuel_limit)
+            .map_err(StrategyError::Wasm)?;
+
+        let mut linker = Linker::new(&engine);
+        link_host_functions(&mut linker)?;
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .map_err(StrategyError::Wasm)?;
+
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| StrategyError::sandbox("wasm module missing exported memory"))?;
+
+        let alloc: TypedFunc<u32, u32> = instance
+            .get_typed_func(&mut store, "alloc")
+            .map_err(|_| StrategyError::sandbox("wasm module must export alloc(u32) -> u32"))?;
+
+        let evaluate: TypedFunc<(i32, i32), i32> = instance
+            .get_typed_func(&mut store, "evaluate")
+            .map_err(|_| {
+                StrategyError::sandbox("wasm module must export evaluate(ptr, len) -> status")
+            })?;
+
+        let abi_version = match instance.get_typed_func::<(), i32>(&mut store, "abi_version") {
+            Ok(func) => func.call(&mut store, ()).map_err(StrategyError::Wasm)?,
+            Err(_) => 1,
+        };
+        if abi_version < 1 || abi_version as u32 > HOST_ABI_VERSION {
+            return Err(StrategyError::sandbox(format!(
+                "wasm module targets host ABI v{}, runtime supports v1..=v{}",
+                abi_version, HOST_ABI_VERSION
+            )));
+        }
+
+        Ok(Self {
+            config,
+            abi_version: abi_version as u32,
+            store,
+            memory,
+            alloc,
+            evaluate,
+            state_store: None,
+        })
+    }
+
+    /// Host ABI version the module declared.
+    pub fn abi_version(&self) -> u32 {
+        self.abi_version
+    }
+
+    /// Identity stamped onto signals and order intents emitted by the module.
+    ///
+    /// The account is replaced by the evaluated context's account on every evaluation.
+    pub fn set_identity(&mut self, strategy_id: Uuid, account_id: AccountId) {
+        let env = self.store.data_mut();
+        env.strategy_id = strategy_id;
+        env.account_id = account_id;
+    }
+
+    pub fn set_portfolio(&mut self, portfolio: Arc<dyn PortfolioView>) {
+        self.store.data_mut().portfolio = Some(portfolio);
+    }
+
+    /// Loads persisted state for `key` and saves it back after evaluations that change it.
+    pub fn attach_state_store(
+        &mut self,
+        store: Arc<dyn StrategyStateStore>,
+        key: impl Into<String>,
+    ) -> Result<(), StrategyError> {
+        let key = key.into();
+        let state = store.load(&key)?;
+        let env = self.store.data_mut();
+        env.state = state;
+        env.state_dirty = false;
+        self.state_store = Some((store, key));
+        Ok(())
+    }
+
+    pub fn state(&self) -> &SandboxState {
+        &self.store.data().state
+    }
+
+    pub fn evaluate<const N: usize>(
+        &mut self,
+        context: &StrategyContext<'_, N>,
+    ) -> Result<StrategyDecision, StrategyError> {
+        let payload = serde_json::to_vec(&SerializableContext::new(context, self.abi_version))?;
+        let len = payload.len() as u32;
+
+        self.store
+            .set_fuel(self.config.fuel_limit)
+            .map_err(StrategyError::Wasm)?;
+        let env = self.store.data_mut();
+        env.account_id.clone_from(context.account_id());
+        env.indicators = indicator_readings(context.indicators());
+        let checkpoint = (env.state.clone(), env.state_dirty);
+
+        let start = Instant::now();
+        let result = self.call_evaluate(&payload, len);
+        let elapsed = start.elapsed();
+        let fuel_consumed = self.config.fuel_limit - self.store.get_fuel().unwrap_or(0);
+
+        let env = self.store.data_mut();
+        let signals = mem::take(&mut env.signals);
+        let logs = mem::take(&mut env.logs);
+        if let Err(err) = result {
+            // A failed evaluation must not leave half-written state behind.
+            (env.state, env.state_dirty) = checkpoint;
+            if err.downcast_ref::<Trap>() == Some(&Trap::OutOfFuel) {
+                warn!("strategy evaluation exhausted fuel after {:?}", elapsed);
+                return Err(StrategyError::FuelExhausted(self.config.fuel_limit));
+            }
+            return Err(StrategyError::Wasm(err));
+        }
+        self.flush_state();
+
+        Ok(StrategyDecision {
+            signals,
+            logs,
+            metrics: StrategyMetrics {
+                evaluation_latency: elapsed,
+                fuel_consumed,
+            },
+        })
+    }
+
+    fn call_evaluate(&mut self, payload: &[u8], len: u32) -> anyhow::Result<i32> {
+        let ptr = self.alloc.call(&mut self.store, len)?;
+        self.memory
+            .write(self.store.as_context_mut(), ptr as usize, payload)?;
+        self.evaluate
+            .call(&mut self.store, (ptr as i32, len as i32))
+    }
+
+    fn flush_state(&mut self) {
+        let env = self.store.data_mut();
+        if !env.state_dirty {
+            return;
+        }
+        if let Some((store, key)) = &self.state_store {
+            // Keep the state dirty so the next evaluation retries the save.
+            if let Err(err) = store.save(key, &env.state) {
+                warn!(key = %key, "failed to persist sandbox state: {}", err);
+                return;
+            }
+        }
+        env.state_dirty = false;
+    }
+}
+
+/// Runs a sandboxed module as a regular `StrategyExecutor`.
+pub struct WasmStrategy {
+    name: String,
+    instance: WasmStrategyInstance,
+    state_store: Option<Arc<dyn StrategyStateStore>>,
+    state_key: Option<String>,
+}
+
+impl WasmStrategy {
+    pub fn new(name: impl Into<String>, instance: WasmStrategyInstance) -> Self {
+        Self {
+            name: name.into(),
+            instance,
+            state_store: None,
+            state_key: None,
+        }
+    }
+
+    /// Persists the module's key-value state under the strategy name.
+    pub fn with_state_store(mut self, store: Arc<dyn StrategyStateStore>) -> Self {
+        self.state_store = Some(store);
+        self
+    }
+
+    /// Stores state under `key` instead of the strategy name, so several deployments of
+    /// one module, or successive versions of it, can keep separate or shared state.
+    pub fn with_state_key(mut self, key: impl Into<String>) -> Self {
+        self.state_key = Some(key.into());
+        self
+    }
+
+    pub fn with_portfolio(mut self, portfolio: Arc<dyn PortfolioView>) -> Self {
+        self.instance.set_portfolio(portfolio);
+        self
+    }
+
+    pub fn instance(&self) -> &WasmStrategyInstance {
+        &self.instance
+    }
+}
+
+impl<const N: usize> StrategyExecutor<N> for WasmStrategy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn initialize(&mut self, ctx: StrategyInitContext<'_>) -> Result<(), StrategyError> {
+        self.instance
+            .set_identity(ctx.strategy_id, ctx.account_id.clone());
+        if let Some(store) = self.state_store.clone() {
+            let key = self.state_key.clone().unwrap_or_else(|| self.name.clone());
+            self.instance.attach_state_store(store, key)?;
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self, ctx: StrategyContext<'_, N>) -> Result<StrategyDecision, StrategyError> {
+        self.instance.evaluate(&ctx)
+    }
+}
+
+#[derive(Serialize)]
+struct SerializableContext<'a> {
+    abi_version: u32,
+    account_id: &'a AccountId,
+    evaluation_id: Uuid,
+    timestamp: DateTime<Utc>,
+    snapshots: Vec<&'a MarketSnapshot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    closed_candle: Option<&'a Candle>,
+}
+
+impl<'a> SerializableContext<'a> {
+    fn new<const N: usize>(ctx: &'a StrategyContext<'a, N>, abi_version: u32) -> Self {
+        Self {
+            abi_version,
+            account_id: ctx.account_id(),
+            evaluation_id: ctx.evaluation_id(),
+            timestamp: ctx.timestamp(),
+            snapshots: ctx.snapshots().iter().collect(),
+            closed_candle: ctx.closed_candle(),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Persistence for the key-value state sandboxed strategies keep across restarts.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::Context;
+
+use crate::traits::StrategyError;
+
+/// Key-value state owned by one sandboxed strategy.
+pub type SandboxState = BTreeMap<String, String>;
+
+/// Backing store for sandbox state, keyed by strategy name.
+pub trait StrategyStateStore: Send + Sync {
+    fn load(&self, key: &str) -> Result<SandboxState, StrategyError>;
+    fn save(&self, key: &str, state: &SandboxState) -> Result<(), StrategyError>;
+}
+
+/// Process-local store, mainly for tests and paper trading.
+#[derive(Default)]
+pub struct InMemoryStateStore {
+    states: Mutex<HashMap<String, SandboxState>>,
+}
+
+impl InMemoryStateStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StrategyStateStore for InMemoryStateStore {
+    fn load(&self, key: &str) -> Result<SandboxState, StrategyError> {
+        let states = self
+            .states
+            .lock()
+            .map_err(|_| StrategyError::sandbox("state store poisoned"))?;
+        Ok(states.get(key).cloned().unwrap_or_default())
+    }
+
+    fn save(&self, key: &str, state: &SandboxState) -> Result<(), StrategyError> {
+        let mut states = self
+            .states
+            .lock()
+            .map_err(|_| StrategyError::sandbox("state store poisoned"))?;
+        states.insert(key.to_string(), state.clone());
+        Ok(())
+    }
+}
+
+/// Stores each strategy's state as a JSON file in a directory.
+pub struct FileStateStore {
+    dir: PathBuf,
+}
+
+impl FileStateStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        let file: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{}.json", file))
+    }
+}
+
+impl StrategyStateStore for FileStateStore {
+    fn load(&self, key: &str) -> Result<SandboxState, StrategyError> {
+        let path = self.path_for(key);
+        if !path.exists() {
+            return Ok(SandboxState::new());
+        }
+        let bytes =
+            fs::read(&path).with_context(|| format!("reading strategy state {:?}", path))?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    fn save(&self, key: &str, state: &SandboxState) -> Result<(), StrategyError> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("creating state directory {:?}", self.dir))?;
+        let path = self.path_for(key);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(state)?)
+            .with_context(|| format!("writing strategy state {:?}", tmp))?;
+        fs::rename(&tmp, &path).with_context(|| format!("replacing strategy state {:?}", path))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,351 @@
+//! Cointegration statistics
+//!
+//! Hedge-ratio estimation (ordinary least squares or a Kalman filter) and the
+//! Engle-Granger two-step cointegration test used by the pairs strategy.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Fitted linear model with coefficient standard errors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub coefficients: Vec<f64>,
+    pub standard_errors: Vec<f64>,
+    pub residuals: Vec<f64>,
+}
+
+/// Ordinary least squares of `target` on the columns of `design` (one row per observation).
+///
+/// Returns `None` when there are no more observations than regressors or the
+/// design matrix is singular.
+pub fn least_squares(design: &[Vec<f64>], target: &[f64]) -> Option<Regression> {
+    let k = design.first()?.len();
+    let n = design.len();
+    if k == 0 || n <= k || target.len() != n {
+        return None;
+    }
+
+    let mut xtx = vec![vec![0.0; k]; k];
+    let mut xty = vec![0.0; k];
+    for (row, y) in design.iter().zip(target) {
+        for i in 0..k {
+            xty[i] += row[i] * y;
+            for j in 0..k {
+                xtx[i][j] += row[i] * row[j];
+            }
+        }
+    }
+    let inverse = invert(xtx)?;
+    let coefficients: Vec<f64> = inverse
+        .iter()
+        .map(|row| row.iter().zip(&xty).map(|(a, b)| a * b).sum())
+        .collect();
+    let residuals: Vec<f64> = design
+        .iter()
+        .zip(target)
+        .map(|(row, y)| {
+            y - row
+                .iter()
+                .zip(&coefficients)
+                .map(|(x, c)| x * c)
+                .sum::<f64>()
+        })
+        .collect();
+    let variance = residuals.iter().map(|e| e * e).sum::<f64>() / (n - k) as f64;
+    let standard_errors = (0..k)
+        .map(|i| (variance * inverse[i][i]).max(0.0).sqrt())
+        .collect();
+    Some(Regression {
+        coefficients,
+        standard_errors,
+        residuals,
+    })
+}
+
+/// Gauss-Jordan inversion with partial pivoting.
+fn invert(mut matrix: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let k = matrix.len();
+    let mut inverse: Vec<Vec<f64>> = (0..k)
+        .map(|i| (0..k).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+    for column in 0..k {
+        let pivot = (column..k).max_by(|a, b| {
+            matrix[*a][column]
+                .abs()
+                .total_cmp(&matrix[*b][column].abs())
+        })?;
+        if matrix[pivot][column].abs() < 1e-12 {
+            return None;
+        }
+        matrix.swap(column, pivot);
+        inverse.swap(column, pivot);
+        let scale = matrix[column][column];
+        for j in 0..k {
+            matrix[column][j] /= scale;
+            inverse[column][j] /= scale;
+        }
+        for row in 0..k {
+            if row != column {
+                let factor = matrix[row][column];
+                for j in 0..k {
+                    matrix[row][j] -= factor * matrix[column][j];
+                    inverse[row][j] -= factor * inverse[column][j];
+                }
+            }
+        }
+    }
+    Some(inverse)
+}
+
+/// Hedge ratio and intercept of `y = intercept + hedge_ratio * x`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HedgeRatio {
+    pub hedge_ratio: f64,
+    pub intercept: f64,
+}
+
+impl HedgeRatio {
+    /// Fits the ratio by ordinary least squares.
+    pub fn ols(y: &[f64], x: &[f64]) -> Option<Self> {
+        let design: Vec<Vec<f64>> = x.iter().map(|x| vec![1.0, *x]).collect();
+        let fit = least_squares(&design, y)?;
+        Some(Self {
+            intercept: fit.coefficients[0],
+            hedge_ratio: fit.coefficients[1],
+        })
+    }
+
+    pub fn spread(&self, y: f64, x: f64) -> f64 {
+        y - self.intercept - self.hedge_ratio * x
+    }
+}
+
+/// Kalman filter tracking a time-varying hedge ratio and intercept as a random walk.
+#[derive(Debug, Clone)]
+pub struct KalmanHedge {
+    state: [f64; 2],
+    covariance: [[f64; 2]; 2],
+    /// State noise as a fraction of the previous covariance; higher adapts faster.
+    delta: f64,
+    observation_variance: f64,
+    observations: usize,
+}
+
+impl KalmanHedge {
+    pub fn new(delta: f64, observation_variance: f64) -> Self {
+        Self {
+            state: [0.0, 0.0],
+            covariance: [[0.0; 2]; 2],
+            delta,
+            observation_variance,
+            observations: 0,
+        }
+    }
+
+    /// Folds in one observation and returns the updated estimate.
+    pub fn update(&mut self, y: f64, x: f64) -> HedgeRatio {
+        let noise = self.delta / (1.0 - self.delta);
+        let mut prior = self.covariance;
+        prior[0][0] += noise;
+        prior[1][1] += noise;
+
+        let h = [x, 1.0];
+        let error = y - (self.state[0] * x + self.state[1]);
+        let ph = [
+            prior[0][0] * h[0] + prior[0][1] * h[1],
+            prior[1][0] * h[0] + prior[1][1] * h[1],
+        ];
+        let innovation = h[0] * ph[0] + h[1] * ph[1] + self.observation_variance;
+        let gain = [ph[0] / innovation, ph[1] / innovation];
+
+        self.state[0] += gain[0] * error;
+        self.state[1] += gain[1] * error;
+        for i in 0..2 {
+            for j in 0..2 {
+                self.covariance[i][j] = prior[i][j] - gain[i] * ph[j];
+            }
+        }
+        self.observations += 1;
+        self.estimate()
+    }
+
+    pub fn estimate(&self) -> HedgeRatio {
+        HedgeRatio {
+            hedge_ratio: self.state[0],
+            intercept: self.state[1],
+        }
+    }
+
+    pub fn observations(&self) -> usize {
+        self.observations
+    }
+}
+
+/// Significance level of the cointegration test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Significance {
+    OnePercent,
+    FivePercent,
+    TenPercent,
+}
+
+impl Significance {
+    pub const ALL: [Significance; 3] = [
+        Significance::OnePercent,
+        Significance::FivePercent,
+        Significance::TenPercent,
+    ];
+
+    /// Asymptotic MacKinnon critical value of the Engle-Granger test for two series.
+    pub fn critical_value(&self) -> f64 {
+        match self {
+            Significance::OnePercent => -3.90,
+            Significance::FivePercent => -3.34,
+            Significance::TenPercent => -3.04,
+        }
+    }
+}
+
+impl fmt::Display for Significance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Significance::OnePercent => "1%",
+            Significance::FivePercent => "5%",
+            Significance::TenPercent => "10%",
+        })
+    }
+}
+
+impl FromStr for Significance {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Significance::ALL
+            .into_iter()
+            .find(|level| level.to_string() == s)
+            .ok_or_else(|| format!("unknown significance level `{}`", s))
+    }
+}
+
+/// Augmented Dickey-Fuller t-statistic of `series` without constant or trend.
+///
+/// Regresses the first difference on the lagged level and `lags` lagged differences;
+/// the statistic is the t-value of the lagged level coefficient.
+pub fn adf_statistic(series: &[f64], lags: usize) -> Option<f64> {
+    let diffs: Vec<f64> = series.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let mut design = Vec::new();
+    let mut target = Vec::new();
+    for t in lags..diffs.len() {
+        let mut row = Vec::with_capacity(lags + 1);
+        row.push(series[t]);
+        row.extend((1..=lags).map(|lag| diffs[t - lag]));
+        design.push(row);
+        target.push(diffs[t]);
+    }
+    let fit = least_squares(&design, &target)?;
+    (fit.standard_errors[0] > 0.0).then(|| fit.coefficients[0] / fit.standard_errors[0])
+}
+
+/// Expected number of observations for a spread deviation to halve.
+///
+/// `None` when the spread shows no mean reversion.
+pub fn half_life(spread: &[f64]) -> Option<f64> {
+    let design: Vec<Vec<f64>> = spread[..spread.len().saturating_sub(1)]
+        .iter()
+        .map(|level| vec![1.0, *level])
+        .collect();
+    let target: Vec<f64> = spread.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let speed = least_squares(&design, &target)?.coefficients[1];
+    (speed < 0.0).then(|| -std::f64::consts::LN_2 / speed)
+}
+
+/// Outcome of an Engle-Granger cointegration test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CointegrationTest {
+    pub hedge: HedgeRatio,
+    /// ADF statistic of the regression residuals; more negative is stronger evidence.
+    pub statistic: f64,
+    pub half_life: Option<f64>,
+}
+
+impl CointegrationTest {
+    pub fn is_cointegrated(&self, significance: Significance) -> bool {
+        self.statistic < significance.critical_value()
+    }
+}
+
+/// Engle-Granger two-step test: fit `y` on `x`, then test the residuals for a unit root.
+pub fn engle_granger(y: &[f64], x: &[f64], lags: usize) -> Option<CointegrationTest> {
+    let hedge = HedgeRatio::ols(y, x)?;
+    let residuals: Vec<f64> = y.iter().zip(x).map(|(y, x)| hedge.spread(*y, *x)).collect();
+    Some(CointegrationTest {
+        hedge,
+        statistic: adf_statistic(&residuals, lags)?,
+        half_life: half_life(&residuals),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_walk(rng: &mut StdRng, len: usize) -> Vec<f64> {
+        let mut level = 10.0;
+        (0..len)
+            .map(|_| {
+                level += rng.gen_range(-0.1..0.1);
+                level
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_least_squares_recovers_coefficients() {
+        let design: Vec<Vec<f64>> = (0..20).map(|i| vec![1.0, i as f64]).collect();
+        let target: Vec<f64> = (0..20)
+            .map(|i| 3.0 + 2.0 * i as f64 + if i % 2 == 0 { 0.1 } else { -0.1 })
+            .collect();
+        let fit = least_squares(&design, &target).unwrap();
+        assert!((fit.coefficients[0] - 3.0).abs() < 0.1);
+        assert!((fit.coefficients[1] - 2.0).abs() < 0.01);
+        assert!(least_squares(
+            &[vec![1.0, 1.0], vec![1.0, 1.0], vec![1.0, 1.0]],
+            &[1.0, 2.0, 3.0]
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_engle_granger_separates_cointegrated_from_independent_walks() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let x = random_walk(&mut rng, 400);
+        let y: Vec<f64> = x
+            .iter()
+            .map(|x| 1.0 + 1.5 * x + rng.gen_range(-0.05..0.05))
+            .collect();
+        let test = engle_granger(&y, &x, 1).unwrap();
+        assert!((test.hedge.hedge_ratio - 1.5).abs() < 0.05);
+        assert!(test.is_cointegrated(Significance::OnePercent));
+        assert!(test.half_life.unwrap() < 5.0);
+
+        let independent = random_walk(&mut rng, 400);
+        let test = engle_granger(&independent, &x, 1).unwrap();
+        assert!(!test.is_cointegrated(Significance::TenPercent));
+    }
+
+    #[test]
+    fn test_kalman_hedge_tracks_ratio() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let x = random_walk(&mut rng, 500);
+        let mut kalman = KalmanHedge::new(1e-4, 1e-3);
+        let mut estimate = kalman.estimate();
+        for x in &x {
+            estimate = kalman.update(0.5 + 0.8 * x + rng.gen_range(-0.01..0.01), *x);
+        }
+        assert_eq!(kalman.observations(), 500);
+        assert!((estimate.hedge_ratio - 0.8).abs() < 0.05);
+        assert_eq!("10%".parse::<Significance>(), Ok(Significance::TenPercent));
+    }
+}
@@ -0,0 +1,442 @@
+//! Dollar-cost averaging strategy
+//!
+//! Accumulates a position by buying a fixed quote amount on a schedule, and a
+//! larger amount whenever price drops a set percentage below the previous buy,
+//! until the budget is spent. The remaining budget, the assumed fills and the
+//! strategy's PnL survive restarts when a state store is attached.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use chrono::{DateTime, Duration, Utc};
+use event_bus::{Priority, SignalEventPayload, SignalIntent, StrategySignal};
+use exchange_connectors::ExchangeId;
+use ninja_gekko_core::analytics::PerformanceReport;
+use ninja_gekko_core::types::{OrderSide, OrderType};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::ledger::{load_state, save_state, StrategyLedger};
+use super::{exchange_name, target_exchange, EXCHANGES};
+use crate::params::{ParamError, ParamSchema, ParamSet, ParamSpec, ParamValue};
+use crate::sandbox::StrategyStateStore;
+use crate::traits::{
+    StrategyContext, StrategyDecision, StrategyError, StrategyExecutor, StrategyInitContext,
+};
+
+/// Metadata key naming what triggered a DCA buy (`schedule` or `dip`).
+pub const DCA_TRIGGER_KEY: &str = "dca_trigger";
+
+/// Decimal places of the bought quantity.
+const QUANTITY_SCALE: u32 = 8;
+
+/// Configuration for the DCA strategy
+#[derive(Debug, Clone)]
+pub struct DcaConfig {
+    /// Quote amount bought on each scheduled buy
+    pub order_value: Decimal,
+    /// Minutes between scheduled buys
+    pub interval_minutes: u32,
+    /// Percentage drop below the previous buy that triggers an extra buy; zero disables
+    pub dip_threshold_pct: Decimal,
+    /// Multiple of `order_value` bought on a dip
+    pub dip_multiplier: Decimal,
+    /// Total quote amount the strategy may spend
+    pub budget: Decimal,
+    /// Target exchange for orders
+    pub target_exchange: ExchangeId,
+}
+
+impl Default for DcaConfig {
+    fn default() -> Self {
+        Self {
+            order_value: dec!(100),
+            interval_minutes: 1440,
+            dip_threshold_pct: dec!(5),
+            dip_multiplier: dec!(2),
+            budget: dec!(5000),
+            target_exchange: ExchangeId::BinanceUs,
+        }
+    }
+}
+
+impl DcaConfig {
+    /// Order value and interval, dip threshold and multiplier, budget and venue.
+    pub fn schema() -> ParamSchema {
+        let defaults = Self::default();
+        ParamSchema::new()
+            .with(
+                ParamSpec::decimal(
+                    "order_value",
+                    defaults.order_value,
+                    Some(dec!(0.00000001)),
+                    None,
+                )
+                .describe("Quote amount bought on each scheduled buy"),
+            )
+            .with(
+                ParamSpec::integer(
+                    "interval_minutes",
+                    defaults.interval_minutes as i64,
+                    1,
+                    525_600,
+                )
+                .describe("Minutes between scheduled buys"),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "dip_threshold_pct",
+                    defaults.dip_threshold_pct,
+                    Some(dec!(0)),
+                    Some(dec!(90)),
+                )
+                .describe(
+                    "Drop below the previous buy, in percent, that triggers a dip buy; 0 disables",
+                ),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "dip_multiplier",
+                    defaults.dip_multiplier,
+                    Some(dec!(1)),
+                    Some(dec!(10)),
+                )
+                .describe("Multiple of order_value bought on a dip"),
+            )
+            .with(
+                ParamSpec::decimal("budget", defaults.budget, Some(dec!(0)), None)
+                    .describe("Total quote amount the strategy may spend"),
+            )
+            .with(
+                ParamSpec::choice(
+                    "target_exchange",
+                    exchange_name(defaults.target_exchange),
+                    EXCHANGES.iter().copied().map(exchange_name),
+                )
+                .describe("Venue signals are routed to"),
+            )
+    }
+
+    /// Requires `order_value <= budget`, so at least one regular buy fits.
+    pub fn from_params(params: &ParamSet) -> Result<Self, ParamError> {
+        let config = Self {
+            order_value: params.decimal("order_value")?,
+            interval_minutes: params.integer("interval_minutes")? as u32,
+            dip_threshold_pct: params.decimal("dip_threshold_pct")?,
+            dip_multiplier: params.decimal("dip_multiplier")?,
+            budget: params.decimal("budget")?,
+            target_exchange: target_exchange(params)?,
+        };
+
+        if config.order_value > config.budget {
+            return Err(ParamError::Invalid(
+                "order_value must not exceed budget".into(),
+            ));
+        }
+        Ok(config)
+    }
+
+    pub fn to_params(&self) -> ParamSet {
+        let mut params = ParamSet::default();
+        params.set("order_value", ParamValue::Decimal(self.order_value));
+        params.set(
+            "interval_minutes",
+            ParamValue::Integer(self.interval_minutes as i64),
+        );
+        params.set(
+            "dip_threshold_pct",
+            ParamValue::Decimal(self.dip_threshold_pct),
+        );
+        params.set("dip_multiplier", ParamValue::Decimal(self.dip_multiplier));
+        params.set("budget", ParamValue::Decimal(self.budget));
+        params.set(
+            "target_exchange",
+            ParamValue::Choice(exchange_name(self.target_exchange)),
+        );
+        params
+    }
+}
+
+/// Persisted book of the DCA strategy.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DcaState {
+    pub symbol: Option<String>,
+    /// Quote amount spent so far
+    pub spent: Decimal,
+    pub last_buy_at: Option<DateTime<Utc>>,
+    pub last_buy_price: Option<Decimal>,
+    pub ledger: StrategyLedger,
+}
+
+/// Scheduled and dip-triggered accumulation within a fixed budget
+///
+/// The traded symbol is the one given to [`DcaStrategy::with_symbol`], or
+/// otherwise the first one observed. The schedule follows market data
+/// timestamps; the first buy happens on the first price seen.
+pub struct DcaStrategy {
+    name: String,
+    strategy_id: Uuid,
+    account_id: String,
+    config: DcaConfig,
+    state: DcaState,
+    state_store: Option<Arc<dyn StrategyStateStore>>,
+    state_key: Option<String>,
+}
+
+impl DcaStrategy {
+    pub fn new(name: impl Into<String>, config: DcaConfig) -> Self {
+        Self {
+            name: name.into(),
+            strategy_id: Uuid::new_v4(),
+            account_id: String::new(),
+            config,
+            state: DcaState::default(),
+            state_store: None,
+            state_key: None,
+        }
+    }
+
+    /// Fix the traded symbol
+    pub fn with_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.state.symbol = Some(symbol.into());
+        self
+    }
+
+    /// Persists the spent budget and fills under the strategy name.
+    pub fn with_state_store(mut self, store: Arc<dyn StrategyStateStore>) -> Self {
+        self.state_store = Some(store);
+        self
+    }
+
+    /// Saves the spent budget and fills under `key`, so a renamed instance
+    /// keeps what it has already spent.
+    pub fn with_state_key(mut self, key: impl Into<String>) -> Self {
+        self.state_key = Some(key.into());
+        self
+    }
+
+    pub fn state(&self) -> &DcaState {
+        &self.state
+    }
+
+    /// Quote amount still available to spend.
+    pub fn remaining_budget(&self) -> Decimal {
+        (self.config.budget - self.state.spent).max(Decimal::ZERO)
+    }
+
+    /// Accumulated position marked to the last price against its cost basis,
+    /// as a return on the total budget.
+    pub fn performance(&self) -> PerformanceReport {
+        self.state.ledger.report(self.config.budget)
+    }
+
+    fn state_key(&self) -> String {
+        self.state_key.clone().unwrap_or_else(|| self.name.clone())
+    }
+
+    fn persist(&self) {
+        let Some(store) = &self.state_store else {
+            return;
+        };
+        let key = self.state_key();
+        if let Err(err) = save_state(store.as_ref(), &key, &self.state) {
+            warn!(strategy = %self.name, key = %key, "failed to persist DCA state: {}", err);
+        }
+    }
+
+    /// Records a price and returns a buy signal when one is due.
+    fn observe(
+        &mut self,
+        symbol: &str,
+        price: Decimal,
+        timestamp: DateTime<Utc>,
+    ) -> Option<StrategySignal> {
+        if price <= Decimal::ZERO
+            || self.state.symbol.get_or_insert_with(|| symbol.to_string()) != symbol
+        {
+            return None;
+        }
+        self.state.ledger.mark(symbol, price, timestamp);
+
+        let scheduled = self.state.last_buy_at.map_or(true, |at| {
+            timestamp - at >= Duration::minutes(self.config.interval_minutes as i64)
+        });
+        let dip = self.config.dip_threshold_pct > Decimal::ZERO
+            && self.state.last_buy_price.is_some_and(|last| {
+                price <= last * (Decimal::ONE - self.config.dip_threshold_pct / dec!(100))
+            });
+        let (trigger, value) = if dip {
+            ("dip", self.config.order_value * self.config.dip_multiplier)
+        } else if scheduled {
+            ("schedule", self.config.order_value)
+        } else {
+            return None;
+        };
+
+        let value = value.min(self.remaining_budget());
+        let quantity = (value / price).round_dp(QUANTITY_SCALE);
+        if quantity.is_zero() {
+            return None;
+        }
+        let signal = StrategySignal {
+            exchange: Some(self.config.target_exchange),
+            symbol: symbol.to_string(),
+            side: OrderSide::Buy,
+            order_type: OrderType::Market,
+            quantity,
+            limit_price: None,
+            confidence: 1.0,
+            metadata: HashMap::from([(DCA_TRIGGER_KEY.to_string(), trigger.to_string())]),
+            intent: SignalIntent::Order,
+        };
+
+        self.state.spent += quantity * price;
+        self.state.last_buy_at = Some(timestamp);
+        self.state.last_buy_price = Some(price);
+        self.state.ledger.record(&signal, price, timestamp);
+        self.persist();
+        Some(signal)
+    }
+}
+
+impl StrategyExecutor<8> for DcaStrategy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn initialize(&mut self, ctx: StrategyInitContext<'_>) -> Result<(), StrategyError> {
+        self.strategy_id = ctx.strategy_id;
+        self.account_id = ctx.account_id.to_string();
+        if let Some(store) = &self.state_store {
+            if let Some(state) = load_state(store.as_ref(), &self.state_key())? {
+                self.state = state;
+            }
+        }
+        info!(
+            strategy = %self.name,
+            id = %self.strategy_id,
+            spent = %self.state.spent,
+            "DCA strategy initialized"
+        );
+        Ok(())
+    }
+
+    fn parameter_schema(&self) -> ParamSchema {
+        DcaConfig::schema()
+    }
+
+    fn parameters(&self) -> ParamSet {
+        self.config.to_params()
+    }
+
+    fn update_parameters(&mut self, params: &ParamSet) -> Result<(), StrategyError> {
+        self.config = DcaConfig::from_params(params)?;
+        info!(strategy = %self.name, "DCA strategy parameters updated");
+        Ok(())
+    }
+
+    fn evaluate(&mut self, ctx: StrategyContext<'_, 8>) -> Result<StrategyDecision, StrategyError> {
+        let start = Instant::now();
+        let mut decision = StrategyDecision::empty();
+
+        if let Some(latest) = ctx.snapshots().last() {
+            if let Some(signal) = self.observe(&latest.symbol, latest.last, latest.timestamp) {
+                let report = self.performance();
+                decision.logs.push(format!(
+                    "DCA {} buy of {} at {}; remaining budget {}, total PnL {:.2}",
+                    signal.metadata[DCA_TRIGGER_KEY],
+                    signal.quantity,
+                    latest.last,
+                    self.remaining_budget(),
+                    report.total_pnl
+                ));
+                decision.signals.push(SignalEventPayload {
+                    strategy_id: self.strategy_id,
+                    account_id: self.account_id.clone(),
+                    priority: Priority::Normal,
+                    signal,
+                });
+            }
+        }
+
+        decision.metrics.evaluation_latency = start.elapsed();
+        Ok(decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sandbox::InMemoryStateStore;
+    use chrono::TimeZone;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000, 0).unwrap() + Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn test_dca_buys_on_schedule_and_dips_within_budget() {
+        let config = DcaConfig {
+            order_value: dec!(100),
+            interval_minutes: 60,
+            dip_threshold_pct: dec!(10),
+            dip_multiplier: dec!(2),
+            budget: dec!(350),
+            ..DcaConfig::default()
+        };
+        let mut strategy = DcaStrategy::new("dca", config);
+
+        let first = strategy.observe("ETH-USD", dec!(100), at(0)).unwrap();
+        assert_eq!(first.quantity, dec!(1));
+        assert_eq!(first.metadata[DCA_TRIGGER_KEY], "schedule");
+        assert!(strategy.observe("ETH-USD", dec!(95), at(30)).is_none());
+
+        let dip = strategy.observe("ETH-USD", dec!(80), at(40)).unwrap();
+        assert_eq!(dip.metadata[DCA_TRIGGER_KEY], "dip");
+        assert_eq!(dip.quantity, dec!(2.5));
+        assert!(strategy.observe("ETH-USD", dec!(85), at(90)).is_none());
+
+        let last = strategy.observe("ETH-USD", dec!(50), at(110)).unwrap();
+        assert_eq!(last.quantity, dec!(1), "capped by the remaining 50");
+        assert_eq!(strategy.remaining_budget(), Decimal::ZERO);
+        assert!(strategy.observe("ETH-USD", dec!(40), at(500)).is_none());
+
+        let report = strategy.performance();
+        // 4.5 units bought for 350, marked at 40.
+        assert!((report.total_pnl - (4.5 * 40.0 - 350.0)).abs() < 1e-9);
+        assert_eq!(report.realized_pnl, 0.0);
+    }
+
+    #[test]
+    fn test_dca_state_survives_restart() {
+        let store: Arc<dyn StrategyStateStore> = Arc::new(InMemoryStateStore::new());
+        let account = String::from("dca-account");
+        let restart = || {
+            let mut strategy =
+                DcaStrategy::new("dca", DcaConfig::default()).with_state_store(store.clone());
+            strategy
+                .initialize(StrategyInitContext {
+                    strategy_id: Uuid::new_v4(),
+                    account_id: &account,
+                })
+                .unwrap();
+            strategy
+        };
+
+        let mut first = restart();
+        assert!(first.observe("BTC-USD", dec!(200), at(0)).is_some());
+
+        let mut second = restart();
+        assert_eq!(second.state().spent, dec!(100));
+        assert_eq!(second.remaining_budget(), dec!(4900));
+        assert!(
+            second.observe("BTC-USD", dec!(200), at(60)).is_none(),
+            "schedule resumes from the persisted last buy"
+        );
+        assert_eq!(second.state().ledger.executions().len(), 1);
+    }
+}
@@ -0,0 +1,91 @@
+//! Strategy-local bookkeeping
+//!
+//! Strategies do not observe fills, so the ones that keep their own books assume
+//! each market signal fills at the price it was generated from. The
+//! [`StrategyLedger`] records those assumed fills and reports PnL through the
+//! shared [`PerformanceTracker`]. Book state is persisted through a
+//! [`StrategyStateStore`] as one JSON entry per strategy.
+
+use chrono::{DateTime, Utc};
+use ninja_gekko_core::analytics::{Mark, PerformanceReport, PerformanceTracker};
+use ninja_gekko_core::types::Execution;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use event_bus::StrategySignal;
+
+use crate::sandbox::StrategyStateStore;
+use crate::traits::StrategyError;
+
+/// Entry of the state map holding a strategy's serialized book.
+pub const STATE_ENTRY: &str = "state";
+
+/// Assumed fills of a strategy's own signals and the latest mark.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StrategyLedger {
+    executions: Vec<Execution>,
+    last_mark: Option<Mark>,
+}
+
+impl StrategyLedger {
+    /// Records `signal` as filled in full at `price`.
+    pub fn record(&mut self, signal: &StrategySignal, price: Decimal, timestamp: DateTime<Utc>) {
+        let mut execution = Execution::new(
+            Uuid::new_v4(),
+            signal.symbol.clone(),
+            signal.side,
+            signal.quantity,
+            price,
+            signal
+                .exchange
+                .map(|exchange| format!("{:?}", exchange))
+                .unwrap_or_default(),
+            Decimal::ZERO,
+        );
+        execution.timestamp = timestamp;
+        self.executions.push(execution);
+    }
+
+    /// Revalues the open position; only the latest mark is kept.
+    pub fn mark(&mut self, symbol: &str, price: Decimal, timestamp: DateTime<Utc>) {
+        self.last_mark = Some(Mark {
+            symbol: symbol.to_string(),
+            price,
+            timestamp,
+        });
+    }
+
+    pub fn executions(&self) -> &[Execution] {
+        &self.executions
+    }
+
+    /// Performance of the assumed fills, with returns measured against `capital`.
+    pub fn report(&self, capital: Decimal) -> PerformanceReport {
+        PerformanceTracker::from_history(capital, &self.executions, self.last_mark.as_slice())
+            .report()
+    }
+}
+
+/// Loads the book saved under `key`, if any.
+pub(crate) fn load_state<T: DeserializeOwned>(
+    store: &dyn StrategyStateStore,
+    key: &str,
+) -> Result<Option<T>, StrategyError> {
+    match store.load(key)?.get(STATE_ENTRY) {
+        Some(json) => Ok(Some(serde_json::from_str(json)?)),
+        None => Ok(None),
+    }
+}
+
+/// Saves `state` under `key`, replacing the previous book.
+pub(crate) fn save_state<T: Serialize>(
+    store: &dyn StrategyStateStore,
+    key: &str,
+    state: &T,
+) -> Result<(), StrategyError> {
+    let mut entries = store.load(key)?;
+    entries.insert(STATE_ENTRY.to_string(), serde_json::to_string(state)?);
+    store.save(key, &entries)
+}
@@ -0,0 +1,768 @@
+//! Market-making strategy
+//!
+//! Maintains a two-sided limit quote around a fair value taken from the L2 book,
+//! following Avellaneda-Stoikov: the reservation price is skewed against the
+//! current inventory and the spread widens with volatility (the larger of ATR and
+//! the Bollinger standard deviation over closed candles).
+//!
+//! Quotes are emitted as [`SignalIntent::ReplaceQuote`] signals so execution
+//! replaces the resting order per side instead of stacking new ones; a zero
+//! quantity pulls that side. [`RiskAction::HaltAll`] pulls both sides.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Instant;
+
+use event_bus::{
+    MarketPayload, Priority, RiskAction, SignalEventPayload, SignalIntent, StrategySignal,
+};
+use exchange_connectors::ExchangeId;
+use ninja_gekko_core::types::{OrderSide, OrderType};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use tracing::info;
+use uuid::Uuid;
+
+use super::{exchange_name, target_exchange, EXCHANGES};
+use crate::indicators::buffer::Candle;
+use crate::indicators::volatility::{Atr, BollingerBands};
+use crate::indicators::{dec_to_f64, Indicator};
+use crate::params::{ParamError, ParamSchema, ParamSet, ParamSpec, ParamValue};
+use crate::sandbox::PortfolioView;
+use crate::traits::{
+    StrategyContext, StrategyDecision, StrategyError, StrategyExecutor, StrategyInitContext,
+};
+
+/// Metadata key holding the fair value a quote was derived from.
+pub const FAIR_VALUE_KEY: &str = "fair_value";
+/// Metadata key holding the signed inventory at quote time.
+pub const INVENTORY_KEY: &str = "inventory";
+
+const BPS: Decimal = dec!(10000);
+
+/// Configuration for the market-making strategy
+///
+/// Risk aversion and liquidity are expressed per unit of volatility, so the
+/// defaults carry across instruments with different price levels.
+#[derive(Debug, Clone)]
+pub struct MarketMakingConfig {
+    /// Inventory aversion γ; higher skews quotes harder against inventory
+    pub risk_aversion: Decimal,
+    /// Order book liquidity κ; higher tightens the spread
+    pub order_book_liquidity: Decimal,
+    /// Remaining horizon τ, in volatility periods (closed candles)
+    pub horizon: Decimal,
+    pub atr_period: usize,
+    pub bollinger_period: usize,
+    pub bollinger_sigma: Decimal,
+    /// Quantity quoted on each side, in base units
+    pub quote_size: Decimal,
+    /// Inventory, in quote lots, at which the side that adds to it is pulled
+    pub max_inventory: Decimal,
+    /// Minimum total spread around fair value, in basis points
+    pub min_spread_bps: Decimal,
+    /// Price move, in basis points, before a resting quote is replaced
+    pub refresh_threshold_bps: Decimal,
+    pub tick_size: Decimal,
+    /// Target exchange for orders
+    pub target_exchange: ExchangeId,
+}
+
+impl Default for MarketMakingConfig {
+    fn default() -> Self {
+        Self {
+            risk_aversion: dec!(0.1),
+            order_book_liquidity: dec!(1.5),
+            horizon: dec!(1),
+            atr_period: 14,
+            bollinger_period: 20,
+            bollinger_sigma: dec!(2),
+            quote_size: dec!(0.01),
+            max_inventory: dec!(5),
+            min_spread_bps: dec!(5),
+            refresh_threshold_bps: dec!(2),
+            tick_size: dec!(0.01),
+            target_exchange: ExchangeId::BinanceUs,
+        }
+    }
+}
+
+impl MarketMakingConfig {
+    /// Avellaneda-Stoikov inputs (risk aversion, book liquidity, horizon), the
+    /// ATR and Bollinger volatility windows, quote size, inventory cap, spread
+    /// floor, requote threshold, tick size and venue.
+    pub fn schema() -> ParamSchema {
+        let defaults = Self::default();
+        ParamSchema::new()
+            .with(
+                ParamSpec::decimal(
+                    "risk_aversion",
+                    defaults.risk_aversion,
+                    Some(dec!(0.001)),
+                    Some(dec!(10)),
+                )
+                .describe("Inventory aversion; higher skews quotes harder against inventory"),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "order_book_liquidity",
+                    defaults.order_book_liquidity,
+                    Some(dec!(0.001)),
+                    None,
+                )
+                .describe("Order arrival decay; higher tightens the spread"),
+            )
+            .with(
+                ParamSpec::decimal("horizon", defaults.horizon, Some(dec!(0.01)), None)
+                    .describe("Remaining horizon in candles"),
+            )
+            .with(
+                ParamSpec::integer("atr_period", defaults.atr_period as i64, 2, 500)
+                    .describe("ATR period in candles"),
+            )
+            .with(
+                ParamSpec::integer("bollinger_period", defaults.bollinger_period as i64, 2, 500)
+                    .describe("Bollinger band period in candles"),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "bollinger_sigma",
+                    defaults.bollinger_sigma,
+                    Some(dec!(0.5)),
+                    Some(dec!(5)),
+                )
+                .describe("Bollinger band width in standard deviations"),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "quote_size",
+                    defaults.quote_size,
+                    Some(dec!(0.00000001)),
+                    None,
+                )
+                .describe("Quantity quoted on each side in base units"),
+            )
+            .with(
+                ParamSpec::decimal("max_inventory", defaults.max_inventory, Some(dec!(1)), None)
+                    .describe("Inventory in quote lots at which the adding side is pulled"),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "min_spread_bps",
+                    defaults.min_spread_bps,
+                    Some(dec!(0)),
+                    Some(dec!(1000)),
+                )
+                .describe("Minimum total spread in basis points"),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "refresh_threshold_bps",
+                    defaults.refresh_threshold_bps,
+                    Some(dec!(0)),
+                    Some(dec!(1000)),
+                )
+                .describe("Price move in basis points before a quote is replaced"),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "tick_size",
+                    defaults.tick_size,
+                    Some(dec!(0.00000001)),
+                    None,
+                )
+                .describe("Price increment quotes are rounded to"),
+            )
+            .with(
+                ParamSpec::choice(
+                    "target_exchange",
+                    exchange_name(defaults.target_exchange),
+                    EXCHANGES.iter().copied().map(exchange_name),
+                )
+                .describe("Venue signals are routed to"),
+            )
+    }
+
+    /// Every field is independent, so the schema's per-parameter bounds are
+    /// the only checks.
+    pub fn from_params(params: &ParamSet) -> Result<Self, ParamError> {
+        Ok(Self {
+            risk_aversion: params.decimal("risk_aversion")?,
+            order_book_liquidity: params.decimal("order_book_liquidity")?,
+            horizon: params.decimal("horizon")?,
+            atr_period: params.integer("atr_period")? as usize,
+            bollinger_period: params.integer("bollinger_period")? as usize,
+            bollinger_sigma: params.decimal("bollinger_sigma")?,
+            quote_size: params.decimal("quote_size")?,
+            max_inventory: params.decimal("max_inventory")?,
+            min_spread_bps: params.decimal("min_spread_bps")?,
+            refresh_threshold_bps: params.decimal("refresh_threshold_bps")?,
+            tick_size: params.decimal("tick_size")?,
+            target_exchange: target_exchange(params)?,
+        })
+    }
+
+    pub fn to_params(&self) -> ParamSet {
+        let mut params = ParamSet::default();
+        params.set("risk_aversion", ParamValue::Decimal(self.risk_aversion));
+        params.set(
+            "order_book_liquidity",
+            ParamValue::Decimal(self.order_book_liquidity),
+        );
+        params.set("horizon", ParamValue::Decimal(self.horizon));
+        params.set("atr_period", ParamValue::Integer(self.atr_period as i64));
+        params.set(
+            "bollinger_period",
+            ParamValue::Integer(self.bollinger_period as i64),
+        );
+        params.set("bollinger_sigma", ParamValue::Decimal(self.bollinger_sigma));
+        params.set("quote_size", ParamValue::Decimal(self.quote_size));
+        params.set("max_inventory", ParamValue::Decimal(self.max_inventory));
+        params.set("min_spread_bps", ParamValue::Decimal(self.min_spread_bps));
+        params.set(
+            "refresh_threshold_bps",
+            ParamValue::Decimal(self.refresh_threshold_bps),
+        );
+        params.set("tick_size", ParamValue::Decimal(self.tick_size));
+        params.set(
+            "target_exchange",
+            ParamValue::Choice(exchange_name(self.target_exchange)),
+        );
+        params
+    }
+}
+
+/// Local L2 book maintained from snapshots and deltas.
+#[derive(Debug, Clone, Default)]
+struct OrderBook {
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+}
+
+impl OrderBook {
+    fn apply(&mut self, payload: &MarketPayload) {
+        match payload {
+            MarketPayload::OrderBookSnapshot { bids, asks, .. } => {
+                self.bids.clear();
+                self.asks.clear();
+                Self::merge(&mut self.bids, bids.iter().map(|l| (l.price, l.size)));
+                Self::merge(&mut self.asks, asks.iter().map(|l| (l.price, l.size)));
+            }
+            MarketPayload::OrderBookDelta {
+                bid_updates,
+                ask_updates,
+                ..
+            } => {
+                Self::merge(
+                    &mut self.bids,
+                    bid_updates.iter().map(|l| (l.price, l.size)),
+                );
+                Self::merge(
+                    &mut self.asks,
+                    ask_updates.iter().map(|l| (l.price, l.size)),
+                );
+            }
+            MarketPayload::Tick { .. } => {}
+        }
+    }
+
+    /// Upserts levels; a zero size removes the level.
+    fn merge(
+        side: &mut BTreeMap<Decimal, Decimal>,
+        levels: impl Iterator<Item = (Decimal, Decimal)>,
+    ) {
+        for (price, size) in levels {
+            if size.is_zero() {
+                side.remove(&price);
+            } else {
+                side.insert(price, size);
+            }
+        }
+    }
+
+    fn best_bid(&self) -> Option<(Decimal, Decimal)> {
+        self.bids.iter().next_back().map(|(p, s)| (*p, *s))
+    }
+
+    fn best_ask(&self) -> Option<(Decimal, Decimal)> {
+        self.asks.iter().next().map(|(p, s)| (*p, *s))
+    }
+
+    /// Top-of-book price weighted towards the side with less size.
+    fn microprice(&self) -> Option<Decimal> {
+        let (bid, bid_size) = self.best_bid()?;
+        let (ask, ask_size) = self.best_ask()?;
+        if bid >= ask {
+            return None;
+        }
+        let depth = bid_size + ask_size;
+        if depth.is_zero() {
+            return Some((bid + ask) / dec!(2));
+        }
+        Some((bid * ask_size + ask * bid_size) / depth)
+    }
+}
+
+/// Bid and ask the strategy wants resting; a zero quantity means no order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Quote {
+    price: Decimal,
+    quantity: Decimal,
+}
+
+/// Inventory-aware two-sided quoting around the L2 microprice
+///
+/// The quoted symbol is the one given to [`MarketMakingStrategy::with_symbol`], or
+/// otherwise the first one observed. Inventory is read from the portfolio given to
+/// [`MarketMakingStrategy::with_portfolio`]; without one the strategy quotes flat.
+/// No quotes are posted until the volatility estimators have warmed up.
+pub struct MarketMakingStrategy {
+    name: String,
+    strategy_id: Uuid,
+    account_id: String,
+    config: MarketMakingConfig,
+    portfolio: Option<Arc<dyn PortfolioView>>,
+
+    symbol: Option<String>,
+    book: OrderBook,
+    mid: Option<Decimal>,
+    atr: Atr,
+    /// Latest ATR; the indicator does not expose its current value
+    atr_value: f64,
+    bollinger: BollingerBands,
+    /// Resting bid and ask, as last emitted
+    resting: [Option<Quote>; 2],
+    quoted_inventory: Decimal,
+    exposure: Decimal,
+    halted: bool,
+}
+
+impl MarketMakingStrategy {
+    /// Create a market maker that binds its symbol from the first event it sees
+    pub fn new(name: impl Into<String>, config: MarketMakingConfig) -> Self {
+        Self {
+            name: name.into(),
+            strategy_id: Uuid::new_v4(),
+            account_id: String::new(),
+            atr: Atr::new(config.atr_period),
+            atr_value: 0.0,
+            bollinger: BollingerBands::new(
+                config.bollinger_period,
+                dec_to_f64(config.bollinger_sigma),
+            ),
+            config,
+            portfolio: None,
+            symbol: None,
+            book: OrderBook::default(),
+            mid: None,
+            resting: [None, None],
+            quoted_inventory: Decimal::ZERO,
+            exposure: Decimal::ONE,
+            halted: false,
+        }
+    }
+
+    /// Fix the quoted symbol
+    pub fn with_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    /// Read inventory from `portfolio`
+    pub fn with_portfolio(mut self, portfolio: Arc<dyn PortfolioView>) -> Self {
+        self.portfolio = Some(portfolio);
+        self
+    }
+
+    /// Whether quoting is suspended by a risk halt
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    fn tracks(&mut self, symbol: &str) -> bool {
+        self.symbol.get_or_insert_with(|| symbol.to_string()) == symbol
+    }
+
+    fn fair_value(&self) -> Option<Decimal> {
+        self.book.microprice().or(self.mid)
+    }
+
+    fn on_candle(&mut self, candle: &Candle) {
+        self.atr_value = dec_to_f64(self.atr.update_ohlcv(candle).value);
+        self.bollinger.update(candle.close);
+    }
+
+    /// Price volatility per candle; `None` while the estimators warm up.
+    fn volatility(&self) -> Option<f64> {
+        if !self.atr.is_ready() || !self.bollinger.is_ready() {
+            return None;
+        }
+        let bands = self.bollinger.calculate_bands()?;
+        let std = (bands.upper - bands.lower) / (2.0 * dec_to_f64(self.config.bollinger_sigma));
+        Some(self.atr_value.max(std))
+    }
+
+    fn inventory(&self) -> Decimal {
+        match (&self.portfolio, &self.symbol) {
+            (Some(portfolio), Some(symbol)) => portfolio
+                .position(&self.account_id, symbol)
+                .map(|position| position.quantity)
+                .unwrap_or_default(),
+            _ => Decimal::ZERO,
+        }
+    }
+
+    /// Target bid and ask for the given fair value, volatility and inventory.
+    fn quotes(&self, fair: Decimal, sigma: f64, inventory: Decimal) -> Option<[Quote; 2]> {
+        let gamma = dec_to_f64(self.config.risk_aversion);
+        let kappa = dec_to_f64(self.config.order_book_liquidity);
+        let tau = dec_to_f64(self.config.horizon);
+        let lots = dec_to_f64(inventory / self.config.quote_size);
+
+        let reservation = dec_to_f64(fair) - lots * gamma * sigma * tau;
+        let floor = dec_to_f64(fair * self.config.min_spread_bps / BPS);
+        let spread =
+            (sigma * (gamma * tau + (2.0 / gamma) * (1.0 + gamma / kappa).ln())).max(floor);
+
+        let tick = self.config.tick_size;
+        let mut bid = (Decimal::from_f64(reservation - spread / 2.0)? / tick).floor() * tick;
+        let mut ask = (Decimal::from_f64(reservation + spread / 2.0)? / tick).ceil() * tick;
+        if let Some((best_ask, _)) = self.book.best_ask() {
+            bid = bid.min(best_ask - tick);
+        }
+        if let Some((best_bid, _)) = self.book.best_bid() {
+            ask = ask.max(best_bid + tick);
+        }
+        if bid <= Decimal::ZERO || bid >= ask {
+            return None;
+        }
+
+        let size = (self.config.quote_size * self.exposure).round_dp(8);
+        let limit = self.config.max_inventory * self.config.quote_size;
+        let sized = |blocked: bool| if blocked { Decimal::ZERO } else { size };
+        Some([
+            Quote {
+                price: bid,
+                quantity: sized(inventory >= limit),
+            },
+            Quote {
+                price: ask,
+                quantity: sized(inventory <= -limit),
+            },
+        ])
+    }
+
+    /// Whether a side's resting quote must be replaced by `target`.
+    fn stale(&self, resting: Option<Quote>, target: Quote, inventory_moved: bool) -> bool {
+        let Some(resting) = resting else {
+            return !target.quantity.is_zero();
+        };
+        if resting.quantity != target.quantity {
+            return true;
+        }
+        if resting.quantity.is_zero() {
+            return false;
+        }
+        let moved_bps = (target.price - resting.price).abs() / resting.price * BPS;
+        inventory_moved || moved_bps > self.config.refresh_threshold_bps
+    }
+
+    /// Updates the book and volatility from the context and returns quote changes.
+    fn requote(&mut self, ctx: &StrategyContext<'_, 8>) -> Vec<StrategySignal> {
+        for event in ctx.market_events().unwrap_or_default() {
+            let payload = event.payload();
+            let symbol = match payload {
+                MarketPayload::Tick { tick, .. } => &tick.symbol,
+                MarketPayload::OrderBookSnapshot { pair, .. }
+                | MarketPayload::OrderBookDelta { pair, .. } => &pair.symbol,
+            };
+            if self.tracks(symbol) {
+                self.book.apply(payload);
+            }
+        }
+        if let Some(latest) = ctx.snapshots().last() {
+            if self.tracks(&latest.symbol) && latest.bid > Decimal::ZERO && latest.ask > latest.bid
+            {
+                self.mid = Some((latest.bid + latest.ask) / dec!(2));
+            }
+        }
+        if let Some(candle) = ctx.closed_candle() {
+            self.on_candle(candle);
+        }
+        self.refresh()
+    }
+
+    fn refresh(&mut self) -> Vec<StrategySignal> {
+        if self.halted {
+            return Vec::new();
+        }
+        let (Some(fair), Some(sigma)) = (self.fair_value(), self.volatility()) else {
+            return Vec::new();
+        };
+        let inventory = self.inventory();
+        let Some(targets) = self.quotes(fair, sigma, inventory) else {
+            return Vec::new();
+        };
+        let inventory_moved = inventory != self.quoted_inventory;
+        self.quoted_inventory = inventory;
+
+        let mut signals = Vec::new();
+        for (index, side) in [OrderSide::Buy, OrderSide::Sell].into_iter().enumerate() {
+            let target = targets[index];
+            if !self.stale(self.resting[index], target, inventory_moved) {
+                continue;
+            }
+            self.resting[index] = Some(target);
+            signals.push(self.quote_signal(side, target, fair, inventory));
+        }
+        signals
+    }
+
+    fn quote_signal(
+        &self,
+        side: OrderSide,
+        quote: Quote,
+        fair: Decimal,
+        inventory: Decimal,
+    ) -> StrategySignal {
+        StrategySignal {
+            exchange: Some(self.config.target_exchange),
+            symbol: self.symbol.clone().unwrap_or_default(),
+            side,
+            order_type: OrderType::Limit,
+            quantity: quote.quantity,
+            limit_price: Some(quote.price),
+            confidence: 1.0,
+            metadata: HashMap::from([
+                (FAIR_VALUE_KEY.to_string(), fair.round_dp(8).to_string()),
+                (INVENTORY_KEY.to_string(), inventory.to_string()),
+            ]),
+            intent: SignalIntent::ReplaceQuote,
+        }
+    }
+
+    fn payload(&self, signal: StrategySignal) -> SignalEventPayload {
+        SignalEventPayload {
+            strategy_id: self.strategy_id,
+            account_id: self.account_id.clone(),
+            priority: Priority::High,
+            signal,
+        }
+    }
+}
+
+impl StrategyExecutor<8> for MarketMakingStrategy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn initialize(&mut self, ctx: StrategyInitContext<'_>) -> Result<(), StrategyError> {
+        self.strategy_id = ctx.strategy_id;
+        self.account_id = ctx.account_id.to_string();
+        info!(
+            strategy = %self.name,
+            id = %self.strategy_id,
+            "Market-making strategy initialized"
+        );
+        Ok(())
+    }
+
+    fn parameter_schema(&self) -> ParamSchema {
+        MarketMakingConfig::schema()
+    }
+
+    fn parameters(&self) -> ParamSet {
+        self.config.to_params()
+    }
+
+    fn update_parameters(&mut self, params: &ParamSet) -> Result<(), StrategyError> {
+        let next = MarketMakingConfig::from_params(params)?;
+        if next.atr_period != self.config.atr_period {
+            self.atr = Atr::new(next.atr_period);
+        }
+        if next.bollinger_period != self.config.bollinger_period
+            || next.bollinger_sigma != self.config.bollinger_sigma
+        {
+            self.bollinger =
+                BollingerBands::new(next.bollinger_period, dec_to_f64(next.bollinger_sigma));
+        }
+        self.config = next;
+
+        info!(strategy = %self.name, "Market-making strategy parameters updated");
+        Ok(())
+    }
+
+    fn evaluate(&mut self, ctx: StrategyContext<'_, 8>) -> Result<StrategyDecision, StrategyError> {
+        let start = Instant::now();
+        let mut decision = StrategyDecision::empty();
+
+        let signals = self.requote(&ctx);
+        if !signals.is_empty() {
+            decision.logs.push(format!(
+                "Requoted {} side(s) around {}",
+                signals.len(),
+                signals[0].metadata[FAIR_VALUE_KEY]
+            ));
+            decision.signals = signals
+                .into_iter()
+                .map(|signal| self.payload(signal))
+                .collect();
+        }
+
+        decision.metrics.evaluation_latency = start.elapsed();
+        Ok(decision)
+    }
+
+    fn on_risk(&mut self, action: &RiskAction) -> Result<StrategyDecision, StrategyError> {
+        let mut decision = StrategyDecision::empty();
+        match action {
+            RiskAction::HaltAll { reason } => {
+                self.halted = true;
+                self.resting = [None, None];
+                if let Some(symbol) = self.symbol.clone() {
+                    decision.signals.push(self.payload(StrategySignal {
+                        exchange: Some(self.config.target_exchange),
+                        symbol,
+                        side: OrderSide::Buy,
+                        order_type: OrderType::Limit,
+                        quantity: Decimal::ZERO,
+                        limit_price: None,
+                        confidence: 1.0,
+                        metadata: HashMap::new(),
+                        intent: SignalIntent::CancelQuotes,
+                    }));
+                }
+                decision.logs.push(format!("Quotes pulled: {}", reason));
+            }
+            RiskAction::Resume { reason } => {
+                self.halted = false;
+                decision.logs.push(format!("Quoting resumed: {}", reason));
+            }
+            RiskAction::AdjustExposure { factor, reason } => {
+                self.exposure = Decimal::from_f64(factor.clamp(0.0, 1.0)).unwrap_or(Decimal::ONE);
+                decision.logs.push(format!(
+                    "Quote size scaled to {}: {}",
+                    self.exposure, reason
+                ));
+            }
+            RiskAction::Advisory { .. } => {}
+        }
+        Ok(decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sandbox::{PortfolioSnapshot, PositionView};
+
+    fn warmed_up(config: MarketMakingConfig) -> MarketMakingStrategy {
+        let mut strategy = MarketMakingStrategy::new("mm", config).with_symbol("BTC-USD");
+        for i in 0..40 {
+            let close = if i % 2 == 0 { dec!(100.5) } else { dec!(99.5) };
+            strategy.on_candle(&Candle {
+                open: dec!(100),
+                high: dec!(101),
+                low: dec!(99),
+                close,
+                volume: dec!(1),
+                timestamp: i,
+            });
+        }
+        strategy.book.apply(&MarketPayload::OrderBookSnapshot {
+            pair: exchange_connectors::TradingPair {
+                base: "BTC".into(),
+                quote: "USD".into(),
+                symbol: "BTC-USD".into(),
+            },
+            bids: vec![event_bus::OrderBookLevel {
+                price: dec!(99.99),
+                size: dec!(3),
+            }],
+            asks: vec![event_bus::OrderBookLevel {
+                price: dec!(100.01),
+                size: dec!(1),
+            }],
+            depth: 1,
+        });
+        strategy
+    }
+
+    fn holding(quantity: Decimal) -> Arc<dyn PortfolioView> {
+        Arc::new(PortfolioSnapshot {
+            positions: HashMap::from([(
+                "BTC-USD".to_string(),
+                PositionView {
+                    quantity,
+                    average_price: dec!(100),
+                },
+            )]),
+            balances: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_quotes_straddle_microprice_and_skew_with_inventory() {
+        let mut flat = warmed_up(MarketMakingConfig::default());
+        // Larger bid size pulls the microprice towards the ask.
+        assert_eq!(flat.fair_value(), Some(dec!(100.005)));
+        let quotes = flat.refresh();
+        assert_eq!(quotes.len(), 2);
+        let (bid, ask) = (
+            quotes[0].limit_price.unwrap(),
+            quotes[1].limit_price.unwrap(),
+        );
+        assert!(bid < dec!(100.005) && ask > dec!(100.005));
+        assert!(quotes
+            .iter()
+            .all(|q| q.intent == SignalIntent::ReplaceQuote && q.quantity == dec!(0.01)));
+        assert!(flat.refresh().is_empty(), "unchanged quotes are not resent");
+
+        let mut long = warmed_up(MarketMakingConfig::default()).with_portfolio(holding(dec!(0.03)));
+        let skewed = long.refresh();
+        assert!(skewed[0].limit_price.unwrap() < bid);
+        assert!(skewed[1].limit_price.unwrap() < ask);
+
+        let mut capped =
+            warmed_up(MarketMakingConfig::default()).with_portfolio(holding(dec!(0.05)));
+        let capped_quotes = capped.refresh();
+        assert_eq!(
+            capped_quotes.len(),
+            1,
+            "bid at the inventory limit is not posted"
+        );
+        assert_eq!(capped_quotes[0].side, OrderSide::Sell);
+    }
+
+    #[test]
+    fn test_halt_pulls_quotes_until_resume() {
+        let mut strategy = warmed_up(MarketMakingConfig::default());
+        assert_eq!(strategy.refresh().len(), 2);
+
+        let halted = strategy
+            .on_risk(&RiskAction::HaltAll {
+                reason: "drawdown".into(),
+            })
+            .unwrap();
+        assert_eq!(halted.signals.len(), 1);
+        assert_eq!(halted.signals[0].signal.intent, SignalIntent::CancelQuotes);
+        assert!(strategy.is_halted());
+        assert!(strategy.refresh().is_empty());
+
+        strategy
+            .on_risk(&RiskAction::AdjustExposure {
+                factor: 0.5,
+                reason: "vol".into(),
+            })
+            .unwrap();
+        strategy
+            .on_risk(&RiskAction::Resume {
+                reason: "cleared".into(),
+            })
+            .unwrap();
+        let resumed = strategy.refresh();
+        assert_eq!(resumed.len(), 2);
+        assert!(resumed.iter().all(|q| q.quantity == dec!(0.005)));
+    }
+}
@@ -4,6 +4,98 @@
 //! `StrategyExecutor` trait. Strategies can be used directly or as templates
 //! for custom implementations.
 
+pub mod cointegration;
+pub mod dca_strategy;
+pub mod grid_strategy;
+pub mod ledger;
+pub mod market_making_strategy;
 pub mod momentum_strategy;
+pub mod pairs_strategy;
 
-pub use momentum_strategy::MomentumStrategy;
+use exchange_connectors::ExchangeId;
+
+use crate::params::{ParamError, ParamSchema, ParamSet};
+use crate::traits::{StrategyError, StrategyExecutor};
+
+pub use dca_strategy::{DcaConfig, DcaState, DcaStrategy};
+pub use grid_strategy::{GridConfig, GridSpacing, GridState, GridStrategy};
+pub use ledger::StrategyLedger;
+pub use market_making_strategy::{MarketMakingConfig, MarketMakingStrategy};
+pub use momentum_strategy::{MomentumConfig, MomentumStrategy};
+pub use pairs_strategy::{HedgeMethod, PairsConfig, PairsStrategy};
+
+/// Built-in strategy kinds that can be created by name.
+pub const STRATEGY_KINDS: &[&str] = &["momentum", "pairs", "market_making", "grid", "dca"];
+
+/// Parameter schema for a built-in strategy kind.
+///
+/// Every built-in config pairs a `schema()`, which the API serves and hot
+/// reconfiguration validates against, with a `from_params` that turns a
+/// validated set back into a config and rejects the cross-field combinations
+/// per-parameter bounds cannot express.
+pub fn parameter_schema(kind: &str) -> Option<ParamSchema> {
+    match kind {
+        "momentum" => Some(momentum_strategy::MomentumConfig::schema()),
+        "pairs" => Some(pairs_strategy::PairsConfig::schema()),
+        "market_making" => Some(market_making_strategy::MarketMakingConfig::schema()),
+        "grid" => Some(grid_strategy::GridConfig::schema()),
+        "dca" => Some(dca_strategy::DcaConfig::schema()),
+        _ => None,
+    }
+}
+
+/// Builds a built-in strategy of `kind` configured with `params`.
+pub fn create(
+    kind: &str,
+    name: impl Into<String>,
+    params: &ParamSet,
+) -> Result<Box<dyn StrategyExecutor<8>>, StrategyError> {
+    match kind {
+        "momentum" => Ok(Box::new(MomentumStrategy::new(
+            name,
+            MomentumConfig::from_params(params)?,
+        ))),
+        "pairs" => Ok(Box::new(PairsStrategy::new(
+            name,
+            PairsConfig::from_params(params)?,
+        ))),
+        "market_making" => Ok(Box::new(MarketMakingStrategy::new(
+            name,
+            MarketMakingConfig::from_params(params)?,
+        ))),
+        "grid" => Ok(Box::new(GridStrategy::new(
+            name,
+            GridConfig::from_params(params)?,
+        ))),
+        "dca" => Ok(Box::new(DcaStrategy::new(
+            name,
+            DcaConfig::from_params(params)?,
+        ))),
+        other => Err(ParamError::Invalid(format!("unknown strategy type `{}`", other)).into()),
+    }
+}
+
+/// Venues built-in strategies can route signals to.
+pub(crate) const EXCHANGES: [ExchangeId; 4] = [
+    ExchangeId::BinanceUs,
+    ExchangeId::Kraken,
+    ExchangeId::Oanda,
+    ExchangeId::Mock,
+];
+
+pub(crate) fn exchange_name(id: ExchangeId) -> String {
+    format!("{:?}", id)
+}
+
+/// Reads the `target_exchange` choice parameter.
+pub(crate) fn target_exchange(params: &ParamSet) -> Result<ExchangeId, ParamError> {
+    let name = params.choice("target_exchange")?;
+    EXCHANGES
+        .iter()
+        .copied()
+        .find(|id| exchange_name(*id) == name)
+        .ok_or_else(|| ParamError::InvalidChoice {
+            name: "target_exchange".into(),
+            choices: EXCHANGES.iter().copied().map(exchange_name).collect(),
+        })
+}
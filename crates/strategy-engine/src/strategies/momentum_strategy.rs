@@ -1,20 +1,26 @@
 //! Momentum-based trading strategy
 //!
 //! This strategy generates signals when price momentum exceeds configurable thresholds.
-//! Uses RSI and EMA crossover to identify trends.
+//! Uses RSI and EMA crossover to identify trends. Orders are `base_position_size` units
+//! unless a [`PositionSizer`] is attached and the host provides live account state.
 
 use std::time::Instant;
 
-use event_bus::{Priority, SignalEventPayload, StrategySignal};
+use event_bus::{Priority, SignalEventPayload, SignalIntent, StrategySignal};
 use exchange_connectors::ExchangeId;
+use ninja_gekko_core::position_sizing::{
+    realized_volatility, PositionSizer, SizingRequest, Volatility, VOLATILITY_KEY,
+};
 use ninja_gekko_core::types::{OrderSide, OrderType};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use tracing::{debug, info};
 use uuid::Uuid;
 
+use super::{exchange_name, target_exchange, EXCHANGES};
 use crate::indicators::prelude::*;
 use crate::indicators::state::IndicatorState;
+use crate::params::{ParamError, ParamSchema, ParamSet, ParamSpec, ParamValue};
 use crate::traits::{
     MarketSnapshot, StrategyContext, StrategyDecision, StrategyError, StrategyExecutor,
     StrategyInitContext, StrategyMetrics,
@@ -53,6 +59,111 @@ impl Default for MomentumConfig {
     }
 }
 
+impl MomentumConfig {
+    /// RSI and EMA lookbacks, RSI thresholds, order size and venue.
+    pub fn schema() -> ParamSchema {
+        let defaults = Self::default();
+        ParamSchema::new()
+            .with(
+                ParamSpec::integer("rsi_period", defaults.rsi_period as i64, 2, 100)
+                    .describe("RSI lookback in candles"),
+            )
+            .with(
+                ParamSpec::integer("ema_fast_period", defaults.ema_fast_period as i64, 2, 200)
+                    .describe("Fast EMA period; must be below the slow period"),
+            )
+            .with(
+                ParamSpec::integer("ema_slow_period", defaults.ema_slow_period as i64, 3, 400)
+                    .describe("Slow EMA period"),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "rsi_overbought",
+                    defaults.rsi_overbought,
+                    Some(dec!(50)),
+                    Some(dec!(100)),
+                )
+                .describe("RSI level above which sells are considered"),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "rsi_oversold",
+                    defaults.rsi_oversold,
+                    Some(dec!(0)),
+                    Some(dec!(50)),
+                )
+                .describe("RSI level below which buys are considered"),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "base_position_size",
+                    defaults.base_position_size,
+                    Some(dec!(0.00000001)),
+                    None,
+                )
+                .describe("Order quantity in base units"),
+            )
+            .with(
+                ParamSpec::choice(
+                    "target_exchange",
+                    exchange_name(defaults.target_exchange),
+                    EXCHANGES.iter().copied().map(exchange_name),
+                )
+                .describe("Venue signals are routed to"),
+            )
+    }
+
+    /// Requires `ema_fast_period < ema_slow_period` and
+    /// `rsi_oversold < rsi_overbought`.
+    pub fn from_params(params: &ParamSet) -> Result<Self, ParamError> {
+        let config = Self {
+            rsi_period: params.integer("rsi_period")? as usize,
+            ema_fast_period: params.integer("ema_fast_period")? as usize,
+            ema_slow_period: params.integer("ema_slow_period")? as usize,
+            rsi_overbought: params.decimal("rsi_overbought")?,
+            rsi_oversold: params.decimal("rsi_oversold")?,
+            base_position_size: params.decimal("base_position_size")?,
+            target_exchange: target_exchange(params)?,
+        };
+
+        if config.ema_fast_period >= config.ema_slow_period {
+            return Err(ParamError::Invalid(
+                "ema_fast_period must be below ema_slow_period".into(),
+            ));
+        }
+        if config.rsi_oversold >= config.rsi_overbought {
+            return Err(ParamError::Invalid(
+                "rsi_oversold must be below rsi_overbought".into(),
+            ));
+        }
+        Ok(config)
+    }
+
+    pub fn to_params(&self) -> ParamSet {
+        let mut params = ParamSet::default();
+        params.set("rsi_period", ParamValue::Integer(self.rsi_period as i64));
+        params.set(
+            "ema_fast_period",
+            ParamValue::Integer(self.ema_fast_period as i64),
+        );
+        params.set(
+            "ema_slow_period",
+            ParamValue::Integer(self.ema_slow_period as i64),
+        );
+        params.set("rsi_overbought", ParamValue::Decimal(self.rsi_overbought));
+        params.set("rsi_oversold", ParamValue::Decimal(self.rsi_oversold));
+        params.set(
+            "base_position_size",
+            ParamValue::Decimal(self.base_position_size),
+        );
+        params.set(
+            "target_exchange",
+            ParamValue::Choice(exchange_name(self.target_exchange)),
+        );
+        params
+    }
+}
+
 /// Momentum-based trading strategy
 ///
 /// Uses RSI and EMA crossover logic.
@@ -68,6 +179,8 @@ pub struct MomentumStrategy {
     rsi_idx: usize,
     ema_fast_idx: usize,
     ema_slow_idx: usize,
+
+    sizer: Option<PositionSizer>,
 }
 
 impl MomentumStrategy {
@@ -95,9 +208,29 @@ impl MomentumStrategy {
             rsi_idx,
             ema_fast_idx,
             ema_slow_idx,
+            sizer: None,
         }
     }
 
+    /// Size orders from the account's live equity instead of `base_position_size`,
+    /// using the realized volatility of the slow EMA window.
+    pub fn with_sizer(mut self, sizer: PositionSizer) -> Self {
+        self.sizer = Some(sizer);
+        self
+    }
+
+    /// Realized volatility of the closes in the slow EMA window.
+    fn volatility(&self) -> Option<Volatility> {
+        let depth = self.config.ema_slow_period + 1;
+        let closes: Vec<Decimal> = self
+            .state
+            .buffer
+            .last_n(depth)
+            .map(|candle| candle.close)
+            .collect();
+        realized_volatility(&closes).map(Volatility::Fraction)
+    }
+
     /// Create with default configuration
     pub fn with_defaults(name: impl Into<String>) -> Self {
         Self::new(name, MomentumConfig::default())
@@ -141,6 +274,7 @@ impl MomentumStrategy {
             limit_price: None,
             confidence,
             metadata: Default::default(),
+            intent: SignalIntent::Order,
         })
     }
 }
@@ -162,6 +296,35 @@ impl StrategyExecutor<8> for MomentumStrategy {
         Ok(())
     }
 
+    fn parameter_schema(&self) -> ParamSchema {
+        MomentumConfig::schema()
+    }
+
+    fn parameters(&self) -> ParamSet {
+        self.config.to_params()
+    }
+
+    fn update_parameters(&mut self, params: &ParamSet) -> Result<(), StrategyError> {
+        let next = MomentumConfig::from_params(params)?;
+
+        // Rebuilt indicators are replayed from the candle buffer so they stay warm.
+        if next.rsi_period != self.config.rsi_period {
+            self.state.replace(self.rsi_idx, Rsi::new(next.rsi_period));
+        }
+        if next.ema_fast_period != self.config.ema_fast_period {
+            self.state
+                .replace(self.ema_fast_idx, Ema::new(next.ema_fast_period));
+        }
+        if next.ema_slow_period != self.config.ema_slow_period {
+            self.state
+                .replace(self.ema_slow_idx, Ema::new(next.ema_slow_period));
+        }
+
+        info!(strategy = %self.name, "Momentum strategy parameters updated");
+        self.config = next;
+        Ok(())
+    }
+
     fn evaluate(&mut self, ctx: StrategyContext<'_, 8>) -> Result<StrategyDecision, StrategyError> {
         let start = Instant::now();
         let mut signals = Vec::new();
@@ -175,6 +338,7 @@ impl StrategyExecutor<8> for MomentumStrategy {
                 logs: vec!["No market snapshots available".to_string()],
                 metrics: StrategyMetrics {
                     evaluation_latency: start.elapsed(),
+                    ..Default::default()
                 },
             });
         }
@@ -199,27 +363,46 @@ impl StrategyExecutor<8> for MomentumStrategy {
         if let Some(mut signal) = self.on_candle(candle) {
             signal.symbol = latest.symbol.clone(); // Fix symbol
 
-            info!(
-                strategy = %self.name,
-                symbol = %signal.symbol,
-                side = ?signal.side,
-                quantity = %signal.quantity,
-                confidence = %signal.confidence,
-                "Generated momentum signal"
-            );
-
-            let payload = SignalEventPayload {
-                strategy_id: self.strategy_id,
-                account_id: self.account_id.clone(),
-                priority: if signal.confidence >= 0.8 {
-                    Priority::High
-                } else {
-                    Priority::Normal
-                },
-                signal,
-            };
-            signals.push(payload);
-            logs.push("Signal generated".to_string());
+            let volatility = self.volatility();
+            if let Some(Volatility::Fraction(fraction)) = volatility {
+                signal
+                    .metadata
+                    .insert(VOLATILITY_KEY.to_string(), fraction.to_string());
+            }
+            if let (Some(sizer), Some(account)) = (&self.sizer, ctx.account()) {
+                let mut request = SizingRequest::new(&signal.symbol, signal.side, latest.last)
+                    .with_confidence(signal.confidence);
+                request.volatility = volatility;
+                match sizer.size(account, &request) {
+                    Ok(sizing) => signal.quantity = sizing.quantity,
+                    Err(err) => logs.push(format!("Kept base position size: {}", err)),
+                }
+            }
+            if signal.quantity.is_zero() {
+                logs.push("Sized to zero; no room under account limits".to_string());
+            } else {
+                info!(
+                    strategy = %self.name,
+                    symbol = %signal.symbol,
+                    side = ?signal.side,
+                    quantity = %signal.quantity,
+                    confidence = %signal.confidence,
+                    "Generated momentum signal"
+                );
+
+                let payload = SignalEventPayload {
+                    strategy_id: self.strategy_id,
+                    account_id: self.account_id.clone(),
+                    priority: if signal.confidence >= 0.8 {
+                        Priority::High
+                    } else {
+                        Priority::Normal
+                    },
+                    signal,
+                };
+                signals.push(payload);
+                logs.push("Signal generated".to_string());
+            }
         }
 
         Ok(StrategyDecision {
@@ -227,6 +410,7 @@ impl StrategyExecutor<8> for MomentumStrategy {
             logs,
             metrics: StrategyMetrics {
                 evaluation_latency: start.elapsed(),
+                ..Default::default()
             },
         })
     }
@@ -270,4 +454,81 @@ mod tests {
     // The previous tests worked because lookback was 5.
     // Now lookback is tied to indicators (21+).
     // We would need a loop in the test to feed it data.
+
+    #[test]
+    fn test_parameter_update_keeps_indicators_warm() {
+        let mut strategy = MomentumStrategy::with_defaults("test-momentum");
+        for i in 0..40 {
+            let price = Decimal::from(100 + i);
+            strategy.on_candle(Candle {
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume: dec!(1),
+                timestamp: i,
+            });
+        }
+
+        let schema = strategy.parameter_schema();
+        let changes = [
+            ("rsi_period".to_string(), serde_json::json!(10)),
+            ("rsi_oversold".to_string(), serde_json::json!(25)),
+        ]
+        .into_iter()
+        .collect();
+        let params = schema.apply(&strategy.parameters(), &changes).unwrap();
+        strategy.update_parameters(&params).unwrap();
+
+        assert_eq!(strategy.config.rsi_period, 10);
+        assert_eq!(strategy.config.rsi_oversold, dec!(25));
+        assert!(strategy.state.indicators.iter().all(|i| i.is_ready()));
+
+        let inverted = [("ema_fast_period".to_string(), serde_json::json!(30))]
+            .into_iter()
+            .collect();
+        let params = schema.apply(&strategy.parameters(), &inverted).unwrap();
+        assert!(matches!(
+            strategy.update_parameters(&params),
+            Err(StrategyError::Parameters(ParamError::Invalid(_)))
+        ));
+        assert_eq!(strategy.config.ema_fast_period, 9);
+    }
+
+    #[test]
+    fn test_sizer_scales_signals_to_account_equity() {
+        use ninja_gekko_core::position_sizing::{AccountSnapshot, SizingMethod};
+
+        let mut strategy = MomentumStrategy::with_defaults("test-momentum").with_sizer(
+            PositionSizer::new(SizingMethod::Kelly {
+                fraction: dec!(0.5),
+                payoff_ratio: dec!(2),
+            }),
+        );
+        let account_id = "acct".to_string();
+        let account = AccountSnapshot::new(dec!(100000));
+
+        // A long climb followed by a sharp drop leaves RSI oversold while the fast
+        // EMA is still above the slow one.
+        let prices = (0..60)
+            .map(|i| Decimal::from(100 + i))
+            .chain(std::iter::once(dec!(124)));
+        let mut sized = None;
+        for price in prices {
+            let snapshots = create_snapshots(&[price; 8]);
+            let ctx = StrategyContext::new(&account_id, &snapshots, Uuid::new_v4(), Utc::now())
+                .with_account(&account);
+            let decision = strategy.evaluate(ctx).unwrap();
+            if let Some(payload) = decision.signals.into_iter().next() {
+                sized = Some((price, payload.signal));
+                break;
+            }
+        }
+
+        let (price, signal) = sized.expect("momentum signal");
+        assert_eq!(signal.side, OrderSide::Buy);
+        // Half Kelly at 0.8 confidence and 2:1 payoff stakes 35% of equity.
+        assert_eq!(signal.quantity, (dec!(35000) / price).normalize());
+        assert!(signal.metadata.contains_key(VOLATILITY_KEY));
+    }
 }
@@ -0,0 +1,697 @@
+//! Pairs trading strategy
+//!
+//! Trades the spread between two related instruments, such as BTC vs ETH or
+//! EUR_USD vs GBP_USD. The hedge ratio is estimated on log prices by rolling OLS or
+//! a Kalman filter, the pair is periodically re-tested for cointegration
+//! (Engle-Granger), and z-score bands on the spread drive entries and exits.
+//!
+//! Both legs of every trade are emitted in the same decision and tagged with a
+//! shared [`PAIR_GROUP_KEY`], so execution can treat them as one unit.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Instant;
+
+use event_bus::{Priority, SignalEventPayload, SignalIntent, StrategySignal};
+use exchange_connectors::ExchangeId;
+use ninja_gekko_core::types::{OrderSide, OrderType};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use tracing::info;
+use uuid::Uuid;
+
+use super::cointegration::{
+    engle_granger, CointegrationTest, HedgeRatio, KalmanHedge, Significance,
+};
+use super::{exchange_name, target_exchange, EXCHANGES};
+use crate::params::{ParamError, ParamSchema, ParamSet, ParamSpec, ParamValue};
+use crate::traits::{
+    StrategyContext, StrategyDecision, StrategyError, StrategyExecutor, StrategyInitContext,
+};
+
+/// Metadata key holding the id shared by signals that must be executed together.
+pub const PAIR_GROUP_KEY: &str = "pair_group";
+/// Metadata key holding the number of signals in a pair group.
+pub const PAIR_GROUP_SIZE_KEY: &str = "pair_group_size";
+/// Metadata key holding the leg index (`0` or `1`) of a paired signal.
+pub const PAIR_LEG_KEY: &str = "pair_leg";
+pub const HEDGE_RATIO_KEY: &str = "hedge_ratio";
+pub const ZSCORE_KEY: &str = "zscore";
+
+/// Observation noise of the Kalman hedge, in squared log-price units.
+const KALMAN_OBSERVATION_VARIANCE: f64 = 1e-3;
+
+/// Decimal places of the hedge leg quantity.
+const QUANTITY_SCALE: u32 = 8;
+
+/// Pair group of a signal that is one leg of a paired trade.
+pub fn pair_group(signal: &StrategySignal) -> Option<&str> {
+    signal.metadata.get(PAIR_GROUP_KEY).map(String::as_str)
+}
+
+/// How the hedge ratio between the two legs is estimated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HedgeMethod {
+    /// Ordinary least squares over the lookback window.
+    RollingOls,
+    /// Kalman filter updated with every sample.
+    Kalman,
+}
+
+impl HedgeMethod {
+    pub const ALL: [HedgeMethod; 2] = [HedgeMethod::RollingOls, HedgeMethod::Kalman];
+}
+
+impl fmt::Display for HedgeMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            HedgeMethod::RollingOls => "rolling_ols",
+            HedgeMethod::Kalman => "kalman",
+        })
+    }
+}
+
+impl FromStr for HedgeMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HedgeMethod::ALL
+            .into_iter()
+            .find(|method| method.to_string() == s)
+            .ok_or_else(|| format!("unknown hedge method `{}`", s))
+    }
+}
+
+/// Configuration for the pairs strategy
+#[derive(Debug, Clone)]
+pub struct PairsConfig {
+    /// Samples used for the hedge ratio, spread z-score and cointegration test
+    pub lookback: usize,
+    pub hedge_method: HedgeMethod,
+    /// Kalman state noise; higher values let the hedge ratio adapt faster
+    pub kalman_delta: Decimal,
+    /// Absolute spread z-score at which a position is opened
+    pub entry_z: Decimal,
+    /// Absolute spread z-score at which a position is closed
+    pub exit_z: Decimal,
+    /// Absolute spread z-score at which a losing position is abandoned
+    pub stop_z: Decimal,
+    /// Lagged differences in the ADF regression
+    pub adf_lags: usize,
+    pub significance: Significance,
+    /// Samples between cointegration re-tests
+    pub retest_interval: usize,
+    /// First-leg quantity; the second leg is sized by the hedge ratio
+    pub base_position_size: Decimal,
+    /// Target exchange for orders
+    pub target_exchange: ExchangeId,
+}
+
+impl Default for PairsConfig {
+    fn default() -> Self {
+        Self {
+            lookback: 100,
+            hedge_method: HedgeMethod::RollingOls,
+            kalman_delta: dec!(0.0001),
+            entry_z: dec!(2),
+            exit_z: dec!(0.5),
+            stop_z: dec!(4),
+            adf_lags: 1,
+            significance: Significance::FivePercent,
+            retest_interval: 20,
+            base_position_size: dec!(0.1),
+            target_exchange: ExchangeId::BinanceUs,
+        }
+    }
+}
+
+impl PairsConfig {
+    /// Spread lookback, hedge-ratio estimator, z-score bands, cointegration
+    /// test settings, order size and venue.
+    pub fn schema() -> ParamSchema {
+        let defaults = Self::default();
+        ParamSchema::new()
+            .with(
+                ParamSpec::integer("lookback", defaults.lookback as i64, 20, 5000)
+                    .describe("Paired samples used to fit and test the spread"),
+            )
+            .with(
+                ParamSpec::choice(
+                    "hedge_method",
+                    defaults.hedge_method.to_string(),
+                    HedgeMethod::ALL.iter().map(ToString::to_string),
+                )
+                .describe("Hedge ratio estimator"),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "kalman_delta",
+                    defaults.kalman_delta,
+                    Some(dec!(0.00000001)),
+                    Some(dec!(0.1)),
+                )
+                .describe("Kalman state noise; higher adapts the hedge ratio faster"),
+            )
+            .with(
+                ParamSpec::decimal("entry_z", defaults.entry_z, Some(dec!(0.1)), None)
+                    .describe("Spread z-score that opens a position"),
+            )
+            .with(
+                ParamSpec::decimal("exit_z", defaults.exit_z, Some(dec!(0)), None)
+                    .describe("Spread z-score that closes a position; must be below entry_z"),
+            )
+            .with(
+                ParamSpec::decimal("stop_z", defaults.stop_z, Some(dec!(0.2)), None)
+                    .describe("Spread z-score that stops out a position; must exceed entry_z"),
+            )
+            .with(
+                ParamSpec::integer("adf_lags", defaults.adf_lags as i64, 0, 12)
+                    .describe("Lagged differences in the cointegration test"),
+            )
+            .with(
+                ParamSpec::choice(
+                    "significance",
+                    defaults.significance.to_string(),
+                    Significance::ALL.iter().map(ToString::to_string),
+                )
+                .describe("Cointegration test level required to open positions"),
+            )
+            .with(
+                ParamSpec::integer(
+                    "retest_interval",
+                    defaults.retest_interval as i64,
+                    1,
+                    10_000,
+                )
+                .describe("Samples between cointegration re-tests"),
+            )
+            .with(
+                ParamSpec::decimal(
+                    "base_position_size",
+                    defaults.base_position_size,
+                    Some(dec!(0.00000001)),
+                    None,
+                )
+                .describe("First-leg order quantity in base units"),
+            )
+            .with(
+                ParamSpec::choice(
+                    "target_exchange",
+                    exchange_name(defaults.target_exchange),
+                    EXCHANGES.iter().copied().map(exchange_name),
+                )
+                .describe("Venue signals are routed to"),
+            )
+    }
+
+    /// Requires `exit_z < entry_z < stop_z`, and on top of the schema's 20..=5000
+    /// range a `lookback` at least 10 samples longer than `adf_lags`, so the ADF
+    /// regression keeps residual degrees of freedom.
+    pub fn from_params(params: &ParamSet) -> Result<Self, ParamError> {
+        let config = Self {
+            lookback: params.integer("lookback")? as usize,
+            hedge_method: params
+                .choice("hedge_method")?
+                .parse()
+                .map_err(ParamError::Invalid)?,
+            kalman_delta: params.decimal("kalman_delta")?,
+            entry_z: params.decimal("entry_z")?,
+            exit_z: params.decimal("exit_z")?,
+            stop_z: params.decimal("stop_z")?,
+            adf_lags: params.integer("adf_lags")? as usize,
+            significance: params
+                .choice("significance")?
+                .parse()
+                .map_err(ParamError::Invalid)?,
+            retest_interval: params.integer("retest_interval")? as usize,
+            base_position_size: params.decimal("base_position_size")?,
+            target_exchange: target_exchange(params)?,
+        };
+
+        if config.exit_z >= config.entry_z {
+            return Err(ParamError::Invalid("exit_z must be below entry_z".into()));
+        }
+        if config.entry_z >= config.stop_z {
+            return Err(ParamError::Invalid("entry_z must be below stop_z".into()));
+        }
+        if config.adf_lags + 10 > config.lookback {
+            return Err(ParamError::Invalid(
+                "lookback must exceed adf_lags by at least 10 samples".into(),
+            ));
+        }
+        Ok(config)
+    }
+
+    pub fn to_params(&self) -> ParamSet {
+        let mut params = ParamSet::default();
+        params.set("lookback", ParamValue::Integer(self.lookback as i64));
+        params.set(
+            "hedge_method",
+            ParamValue::Choice(self.hedge_method.to_string()),
+        );
+        params.set("kalman_delta", ParamValue::Decimal(self.kalman_delta));
+        params.set("entry_z", ParamValue::Decimal(self.entry_z));
+        params.set("exit_z", ParamValue::Decimal(self.exit_z));
+        params.set("stop_z", ParamValue::Decimal(self.stop_z));
+        params.set("adf_lags", ParamValue::Integer(self.adf_lags as i64));
+        params.set(
+            "significance",
+            ParamValue::Choice(self.significance.to_string()),
+        );
+        params.set(
+            "retest_interval",
+            ParamValue::Integer(self.retest_interval as i64),
+        );
+        params.set(
+            "base_position_size",
+            ParamValue::Decimal(self.base_position_size),
+        );
+        params.set(
+            "target_exchange",
+            ParamValue::Choice(exchange_name(self.target_exchange)),
+        );
+        params
+    }
+
+    fn kalman(&self) -> KalmanHedge {
+        KalmanHedge::new(
+            self.kalman_delta.to_f64().unwrap_or(0.0),
+            KALMAN_OBSERVATION_VARIANCE,
+        )
+    }
+}
+
+/// Open spread position, as the side and quantity of each leg.
+#[derive(Debug, Clone, PartialEq)]
+struct SpreadPosition {
+    legs: [(OrderSide, Decimal); 2],
+}
+
+impl SpreadPosition {
+    /// Long the spread means the first leg was bought.
+    fn is_long(&self) -> bool {
+        self.legs[0].0 == OrderSide::Buy
+    }
+}
+
+/// Statistical arbitrage between two cointegrated instruments
+///
+/// Legs are the two symbols given to [`PairsStrategy::with_legs`], or otherwise the
+/// first two distinct symbols observed; host the strategy with a symbol filter
+/// limited to the pair. A sample is taken each time both legs have a fresh price.
+pub struct PairsStrategy {
+    name: String,
+    strategy_id: Uuid,
+    account_id: String,
+    config: PairsConfig,
+
+    legs: [Option<String>; 2],
+    prices: [Option<Decimal>; 2],
+    fresh: [bool; 2],
+    /// Log prices of the first and second leg, oldest first
+    samples: VecDeque<(f64, f64)>,
+    kalman: KalmanHedge,
+    cointegration: Option<CointegrationTest>,
+    since_test: usize,
+    position: Option<SpreadPosition>,
+}
+
+impl PairsStrategy {
+    /// Create a pairs strategy that binds its legs from the first symbols it sees
+    pub fn new(name: impl Into<String>, config: PairsConfig) -> Self {
+        Self {
+            name: name.into(),
+            strategy_id: Uuid::new_v4(),
+            account_id: String::new(),
+            kalman: config.kalman(),
+            samples: VecDeque::with_capacity(config.lookback),
+            config,
+            legs: [None, None],
+            prices: [None, None],
+            fresh: [false; 2],
+            cointegration: None,
+            since_test: 0,
+            position: None,
+        }
+    }
+
+    /// Fix the traded pair; the first leg is the one regressed on the second
+    pub fn with_legs(mut self, first: impl Into<String>, second: impl Into<String>) -> Self {
+        self.legs = [Some(first.into()), Some(second.into())];
+        self
+    }
+
+    /// Latest cointegration test of the lookback window
+    pub fn cointegration(&self) -> Option<&CointegrationTest> {
+        self.cointegration.as_ref()
+    }
+
+    fn leg_index(&mut self, symbol: &str) -> Option<usize> {
+        if let Some(index) = self
+            .legs
+            .iter()
+            .position(|leg| leg.as_deref() == Some(symbol))
+        {
+            return Some(index);
+        }
+        let free = self.legs.iter().position(Option::is_none)?;
+        self.legs[free] = Some(symbol.to_string());
+        Some(free)
+    }
+
+    /// Records a price and returns the paired signals of any resulting trade.
+    fn observe(&mut self, symbol: &str, price: Decimal) -> Option<Vec<StrategySignal>> {
+        if price <= Decimal::ZERO {
+            return None;
+        }
+        let index = self.leg_index(symbol)?;
+        self.prices[index] = Some(price);
+        self.fresh[index] = true;
+        if !self.fresh.iter().all(|fresh| *fresh) {
+            return None;
+        }
+        self.fresh = [false; 2];
+
+        let first = self.prices[0]?.to_f64()?.ln();
+        let second = self.prices[1]?.to_f64()?.ln();
+        self.samples.push_back((first, second));
+        while self.samples.len() > self.config.lookback {
+            self.samples.pop_front();
+        }
+        let kalman = self.kalman.update(first, second);
+        self.since_test += 1;
+        if self.samples.len() < self.config.lookback {
+            return None;
+        }
+
+        let (ys, xs): (Vec<f64>, Vec<f64>) = self.samples.iter().copied().unzip();
+        if self.cointegration.is_none() || self.since_test >= self.config.retest_interval {
+            self.cointegration = engle_granger(&ys, &xs, self.config.adf_lags);
+            self.since_test = 0;
+        }
+        let hedge = match self.config.hedge_method {
+            HedgeMethod::RollingOls => HedgeRatio::ols(&ys, &xs)?,
+            HedgeMethod::Kalman => kalman,
+        };
+        let spreads: Vec<f64> = ys
+            .iter()
+            .zip(&xs)
+            .map(|(y, x)| hedge.spread(*y, *x))
+            .collect();
+        let z = zscore(&spreads)?;
+        self.decide(z, hedge)
+    }
+
+    fn decide(&mut self, z: f64, hedge: HedgeRatio) -> Option<Vec<StrategySignal>> {
+        let band = |value: Decimal| value.to_f64().unwrap_or(f64::INFINITY);
+        let (entry, exit, stop) = (
+            band(self.config.entry_z),
+            band(self.config.exit_z),
+            band(self.config.stop_z),
+        );
+
+        if let Some(position) = &self.position {
+            let (reverted, stopped) = if position.is_long() {
+                (z >= -exit, z <= -stop)
+            } else {
+                (z <= exit, z >= stop)
+            };
+            if !(reverted || stopped) {
+                return None;
+            }
+            let legs = position
+                .legs
+                .map(|(side, quantity)| (side.opposite(), quantity));
+            self.position = None;
+            return Some(self.paired_signals(legs, hedge, z, stop));
+        }
+
+        let cointegrated = self
+            .cointegration
+            .is_some_and(|test| test.is_cointegrated(self.config.significance));
+        if !cointegrated {
+            return None;
+        }
+        let long = if z <= -entry && z > -stop {
+            true
+        } else if z >= entry && z < stop {
+            false
+        } else {
+            return None;
+        };
+
+        // Log-price hedge ratio -> equal-value exposure scaled by the ratio.
+        let first_quantity = self.config.base_position_size;
+        let value_ratio = self.prices[0]? / self.prices[1]?;
+        let second_quantity =
+            (Decimal::from_f64(hedge.hedge_ratio.abs())? * first_quantity * value_ratio)
+                .round_dp(QUANTITY_SCALE);
+        if second_quantity.is_zero() {
+            return None;
+        }
+        let first_side = if long {
+            OrderSide::Buy
+        } else {
+            OrderSide::Sell
+        };
+        let second_side = if hedge.hedge_ratio >= 0.0 {
+            first_side.opposite()
+        } else {
+            first_side
+        };
+        let position = SpreadPosition {
+            legs: [(first_side, first_quantity), (second_side, second_quantity)],
+        };
+        let signals = self.paired_signals(position.legs, hedge, z, stop);
+        self.position = Some(position);
+        Some(signals)
+    }
+
+    fn paired_signals(
+        &self,
+        legs: [(OrderSide, Decimal); 2],
+        hedge: HedgeRatio,
+        z: f64,
+        stop: f64,
+    ) -> Vec<StrategySignal> {
+        let group = Uuid::new_v4().to_string();
+        legs.iter()
+            .zip(&self.legs)
+            .enumerate()
+            .map(|(index, ((side, quantity), symbol))| {
+                let metadata = HashMap::from([
+                    (PAIR_GROUP_KEY.to_string(), group.clone()),
+                    (PAIR_GROUP_SIZE_KEY.to_string(), legs.len().to_string()),
+                    (PAIR_LEG_KEY.to_string(), index.to_string()),
+                    (
+                        HEDGE_RATIO_KEY.to_string(),
+                        format!("{:.6}", hedge.hedge_ratio),
+                    ),
+                    (ZSCORE_KEY.to_string(), format!("{:.4}", z)),
+                ]);
+                StrategySignal {
+                    exchange: Some(self.config.target_exchange),
+                    symbol: symbol.clone().unwrap_or_default(),
+                    side: *side,
+                    order_type: OrderType::Market,
+                    quantity: *quantity,
+                    limit_price: None,
+                    confidence: (z.abs() / stop).clamp(0.5, 1.0),
+                    metadata,
+                    intent: SignalIntent::Order,
+                }
+            })
+            .collect()
+    }
+
+    fn restart_estimators(&mut self) {
+        self.kalman = self.config.kalman();
+        for (first, second) in &self.samples {
+            self.kalman.update(*first, *second);
+        }
+        self.cointegration = None;
+    }
+}
+
+/// Z-score of the last value against the whole series.
+fn zscore(series: &[f64]) -> Option<f64> {
+    let last = *series.last()?;
+    let n = series.len() as f64;
+    let mean = series.iter().sum::<f64>() / n;
+    let std = (series.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    (std > 0.0).then(|| (last - mean) / std)
+}
+
+impl StrategyExecutor<8> for PairsStrategy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn initialize(&mut self, ctx: StrategyInitContext<'_>) -> Result<(), StrategyError> {
+        self.strategy_id = ctx.strategy_id;
+        self.account_id = ctx.account_id.to_string();
+        info!(
+            strategy = %self.name,
+            id = %self.strategy_id,
+            "Pairs strategy initialized"
+        );
+        Ok(())
+    }
+
+    fn parameter_schema(&self) -> ParamSchema {
+        PairsConfig::schema()
+    }
+
+    fn parameters(&self) -> ParamSet {
+        self.config.to_params()
+    }
+
+    fn update_parameters(&mut self, params: &ParamSet) -> Result<(), StrategyError> {
+        let next = PairsConfig::from_params(params)?;
+        let rebuild = next.hedge_method != self.config.hedge_method
+            || next.kalman_delta != self.config.kalman_delta
+            || next.lookback != self.config.lookback
+            || next.adf_lags != self.config.adf_lags;
+        self.config = next;
+        while self.samples.len() > self.config.lookback {
+            self.samples.pop_front();
+        }
+        if rebuild {
+            self.restart_estimators();
+        }
+
+        info!(strategy = %self.name, "Pairs strategy parameters updated");
+        Ok(())
+    }
+
+    fn evaluate(&mut self, ctx: StrategyContext<'_, 8>) -> Result<StrategyDecision, StrategyError> {
+        let start = Instant::now();
+        let mut decision = StrategyDecision::empty();
+
+        if let Some(latest) = ctx.snapshots().last() {
+            if let Some(signals) = self.observe(&latest.symbol, latest.last) {
+                info!(
+                    strategy = %self.name,
+                    zscore = %signals[0].metadata[ZSCORE_KEY],
+                    first = ?signals[0].side,
+                    second = ?signals[1].side,
+                    "Generated paired signals"
+                );
+                decision.logs.push(format!(
+                    "Paired signal at z-score {}",
+                    signals[0].metadata[ZSCORE_KEY]
+                ));
+                decision.signals = signals
+                    .into_iter()
+                    .map(|signal| SignalEventPayload {
+                        strategy_id: self.strategy_id,
+                        account_id: self.account_id.clone(),
+                        priority: Priority::High,
+                        signal,
+                    })
+                    .collect();
+            }
+        }
+
+        decision.metrics.evaluation_latency = start.elapsed();
+        Ok(decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Feeds one paired sample; `shock` is added to the first leg's log price.
+    fn feed(
+        strategy: &mut PairsStrategy,
+        rng: &mut StdRng,
+        walk: &mut f64,
+        shock: f64,
+    ) -> Option<Vec<StrategySignal>> {
+        *walk += rng.gen_range(-0.01..0.01);
+        let second = 4.6 + *walk;
+        let first = 0.3 + second + rng.gen_range(-0.002..0.002) + shock;
+        let price = |log: f64| Decimal::from_f64(log.exp()).unwrap().round_dp(6);
+        assert!(strategy.observe("ETH-USD", price(first)).is_none());
+        strategy.observe("BTC-USD", price(second))
+    }
+
+    #[test]
+    fn test_pairs_strategy_trades_spread_with_paired_legs() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut walk = 0.0;
+        // Uniform noise keeps the spread within ~1.7 sigma, so only the shock crosses entry.
+        let config = PairsConfig {
+            lookback: 60,
+            entry_z: dec!(3),
+            stop_z: dec!(8),
+            ..PairsConfig::default()
+        };
+        let mut strategy = PairsStrategy::new("pairs", config);
+
+        for _ in 0..80 {
+            assert!(feed(&mut strategy, &mut rng, &mut walk, 0.0).is_none());
+        }
+        assert!(strategy
+            .cointegration()
+            .unwrap()
+            .is_cointegrated(Significance::FivePercent));
+
+        let entry = feed(&mut strategy, &mut rng, &mut walk, 0.005).expect("entry on wide spread");
+        assert_eq!(entry.len(), 2);
+        assert_eq!(entry[0].symbol, "ETH-USD");
+        assert_eq!(entry[0].side, OrderSide::Sell);
+        assert_eq!(entry[1].symbol, "BTC-USD");
+        assert_eq!(entry[1].side, OrderSide::Buy);
+        assert_eq!(pair_group(&entry[0]), pair_group(&entry[1]));
+        assert_eq!(entry[1].metadata[PAIR_LEG_KEY], "1");
+        let hedge_quantity = entry[1].quantity.to_f64().unwrap();
+        assert!((hedge_quantity - 0.1 * 0.3f64.exp()).abs() < 0.02);
+
+        let exit = (0..20)
+            .find_map(|_| feed(&mut strategy, &mut rng, &mut walk, 0.0))
+            .expect("exit once the spread reverts");
+        assert_eq!(exit[0].side, OrderSide::Buy);
+        assert_eq!(exit[0].quantity, entry[0].quantity);
+        assert_eq!(exit[1].side, OrderSide::Sell);
+        assert_eq!(exit[1].quantity, entry[1].quantity);
+        assert_ne!(pair_group(&exit[0]), pair_group(&entry[0]));
+    }
+
+    #[test]
+    fn test_parameter_update_validates_bands_and_swaps_estimator() {
+        let mut strategy = PairsStrategy::new("pairs", PairsConfig::default());
+        let schema = strategy.parameter_schema();
+
+        let inverted = [("exit_z".to_string(), serde_json::json!(3))]
+            .into_iter()
+            .collect();
+        let params = schema.apply(&strategy.parameters(), &inverted).unwrap();
+        assert!(matches!(
+            strategy.update_parameters(&params),
+            Err(StrategyError::Parameters(ParamError::Invalid(_)))
+        ));
+
+        let kalman = [
+            ("hedge_method".to_string(), serde_json::json!("kalman")),
+            ("significance".to_string(), serde_json::json!("1%")),
+        ]
+        .into_iter()
+        .collect();
+        let params = schema.apply(&strategy.parameters(), &kalman).unwrap();
+        strategy.update_parameters(&params).unwrap();
+        assert_eq!(strategy.config.hedge_method, HedgeMethod::Kalman);
+        assert_eq!(strategy.config.significance, Significance::OnePercent);
+        assert_eq!(
+            PairsConfig::from_params(&strategy.parameters())
+                .unwrap()
+                .to_params(),
+            strategy.parameters()
+        );
+    }
+}
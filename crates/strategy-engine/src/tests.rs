@@ -1,13 +1,39 @@
-use chrono::Utc;
-use event_bus::{EventBusBuilder, SignalEventPayload};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, TimeZone, Utc};
+use event_bus::{
+    EventBusBuilder, EventHandler, EventMetadata, EventSource, MarketEvent, MarketPayload,
+    Priority, RiskAction, RiskEvent, RiskEventPayload, SignalEventPayload, SignalIntent,
+    StrategySignal,
+};
+use exchange_connectors::{MarketTick, TradingPair};
+use ninja_gekko_core::types::{OrderSide, OrderType};
 use rust_decimal::Decimal;
 use uuid::Uuid;
 use wat::parse_str as parse_wat;
 
 use crate::{
-    sandbox::{WasmStrategyConfig, WasmStrategyModule},
-    traits::{MarketSnapshot, StrategyContext, StrategyMetrics},
-    StrategyEventBridge,
+    indicators::{
+        buffer::Candle,
+        prelude::{Atr, BollingerBands, Ema, Indicator, IndicatorInput, Macd, Obv, Rsi, Sma},
+        state::IndicatorState,
+    },
+    sandbox::{
+        InMemoryStateStore, PortfolioSnapshot, PositionView, StrategyStateStore, WasmStrategy,
+        WasmStrategyConfig, WasmStrategyModule, HOST_ABI_VERSION, STOP_LOSS_KEY,
+    },
+    strategies::MarketMakingConfig,
+    traits::{
+        MarketSnapshot, StrategyContext, StrategyDecision, StrategyError, StrategyExecutor,
+        StrategyInitContext, StrategyMetrics,
+    },
+    BacktestConfig, HostedStrategyConfig, MarketMakingStrategy, MomentumStrategy, Objective,
+    OptimizerConfig, OptimizerError, OverfitGuards, ParamError, ParamSchema, ParamSet, ParamSpec,
+    ParameterAuditSink, ParameterChange, SearchMethod, SearchSpace, StrategyActivity,
+    StrategyActivitySink, StrategyEventBridge, StrategyHost, StrategyHostError, StrategyOptimizer,
+    StrategyRunner, StrategyState, SymbolFilter,
 };
 
 const TEST_WASM: &str = r#"(module
@@ -59,6 +85,34 @@ fn wasm_strategy_emits_signal() {
     assert_eq!(signal_account, "sandbox-account");
 }
 
+#[test]
+fn wasm_signal_identity_cannot_be_spoofed() {
+    let wasm_bytes = parse_wat(TEST_WASM).expect("valid test wasm");
+    let module =
+        WasmStrategyModule::from_bytes(&wasm_bytes, &WasmStrategyConfig::default()).unwrap();
+    let mut instance = module.instantiate(WasmStrategyConfig::default()).unwrap();
+
+    // The module claims the nil strategy and `sandbox-account` whatever it runs as
+    let account_id = String::from("acct-1");
+    let strategy_id = Uuid::new_v4();
+    instance.set_identity(strategy_id, account_id.clone());
+    let snapshots = [MarketSnapshot {
+        symbol: "BTC-USD".into(),
+        bid: Decimal::from(30_000u32),
+        ask: Decimal::from(30_010u32),
+        last: Decimal::from(30_005u32),
+        timestamp: Utc::now(),
+    }];
+    let context = StrategyContext::new(&account_id, &snapshots, Uuid::new_v4(), Utc::now());
+    let decision = instance
+        .evaluate(&context)
+        .expect("strategy evaluation succeeds");
+
+    assert_eq!(decision.signals.len(), 1);
+    assert_eq!(decision.signals[0].strategy_id, strategy_id);
+    assert_eq!(decision.signals[0].account_id, "acct-1");
+}
+
 #[test]
 fn bridge_publishes_signals() {
     let wasm_bytes = parse_wat(TEST_WASM).expect("valid test wasm");
@@ -90,3 +144,966 @@ fn bridge_publishes_signals() {
     assert_eq!(event.payload().strategy_id, Uuid::nil());
     assert_eq!(event.payload().account_id, "sandbox-account");
 }
+
+/// Candle open time and buffered candle count seen by `EchoStrategy`.
+type ClosedCandles = Arc<Mutex<Vec<(i64, usize)>>>;
+
+/// Emits one market buy per evaluation and records the candles it was handed.
+struct EchoStrategy {
+    strategy_id: Uuid,
+    closed_candles: ClosedCandles,
+}
+
+impl EchoStrategy {
+    fn new() -> (Self, ClosedCandles) {
+        let closed_candles = Arc::new(Mutex::new(Vec::new()));
+        let strategy = Self {
+            strategy_id: Uuid::nil(),
+            closed_candles: Arc::clone(&closed_candles),
+        };
+        (strategy, closed_candles)
+    }
+}
+
+impl StrategyExecutor<4> for EchoStrategy {
+    fn name(&self) -> &str {
+        "echo"
+    }
+
+    fn initialize(&mut self, ctx: StrategyInitContext<'_>) -> Result<(), StrategyError> {
+        self.strategy_id = ctx.strategy_id;
+        Ok(())
+    }
+
+    fn evaluate(&mut self, ctx: StrategyContext<'_, 4>) -> Result<StrategyDecision, StrategyError> {
+        if let (Some(candle), Some(indicators)) = (ctx.closed_candle(), ctx.indicators()) {
+            self.closed_candles
+                .lock()
+                .unwrap()
+                .push((candle.timestamp, indicators.buffer.len()));
+        }
+        let latest = ctx.snapshots().last().expect("snapshot window");
+        let mut decision = StrategyDecision::empty();
+        decision.signals.push(SignalEventPayload {
+            strategy_id: self.strategy_id,
+            account_id: ctx.account_id().clone(),
+            priority: Priority::Normal,
+            signal: StrategySignal {
+                exchange: None,
+                symbol: latest.symbol.clone(),
+                side: OrderSide::Buy,
+                order_type: OrderType::Market,
+                quantity: Decimal::ONE,
+                limit_price: None,
+                confidence: 0.5,
+                metadata: HashMap::new(),
+                intent: SignalIntent::Order,
+            },
+        });
+        Ok(decision)
+    }
+}
+
+struct PanickingStrategy;
+
+impl StrategyExecutor<4> for PanickingStrategy {
+    fn name(&self) -> &str {
+        "panicking"
+    }
+
+    fn evaluate(
+        &mut self,
+        _ctx: StrategyContext<'_, 4>,
+    ) -> Result<StrategyDecision, StrategyError> {
+        panic!("strategy bug");
+    }
+}
+
+fn tick_at(symbol: &str, last: u32, timestamp: DateTime<Utc>) -> MarketEvent {
+    let (base, quote) = symbol.split_once('-').expect("dash separated symbol");
+    let last = Decimal::from(last);
+    MarketEvent::new(
+        EventMetadata::new(EventSource::new("test"), Priority::Normal),
+        MarketPayload::Tick {
+            tick: MarketTick {
+                symbol: symbol.to_string(),
+                bid: last - Decimal::ONE,
+                ask: last + Decimal::ONE,
+                last,
+                volume_24h: Decimal::ZERO,
+                timestamp,
+            },
+            pair: TradingPair {
+                base: base.to_string(),
+                quote: quote.to_string(),
+                symbol: symbol.to_string(),
+            },
+        },
+    )
+}
+
+fn tick(symbol: &str, last: u32) -> MarketEvent {
+    tick_at(symbol, last, Utc::now())
+}
+
+#[test]
+fn host_routes_by_symbol_and_isolates_panics() {
+    let bus = EventBusBuilder::default().build();
+    let receiver = bus.signal_receiver();
+    let host = StrategyHost::<4>::new(bus.signal_sender(), "host-account".into());
+
+    let (echo, _) = EchoStrategy::new();
+    let echo_id = host.register(
+        echo,
+        HostedStrategyConfig::default().with_symbols(SymbolFilter::only(["BTC-USD"])),
+    );
+    let panicking_id = host.register(PanickingStrategy, HostedStrategyConfig::default());
+    host.start(echo_id).unwrap();
+    host.start(panicking_id).unwrap();
+
+    let btc = tick("BTC-USD", 30_000);
+    host.dispatch(&btc);
+    host.dispatch(&tick("ETH-USD", 2_000));
+
+    let signal = receiver.try_recv().expect("echo signal published");
+    assert_eq!(signal.payload().strategy_id, echo_id);
+    assert_eq!(signal.payload().account_id, "host-account");
+    assert_eq!(signal.payload().signal.symbol, "BTC-USD");
+    assert_eq!(
+        signal.metadata().correlation_id,
+        btc.metadata().correlation_id
+    );
+    assert!(receiver.try_recv().is_err());
+
+    let echo_stats = host.stats(echo_id).unwrap();
+    assert_eq!(echo_stats.state, StrategyState::Running);
+    assert_eq!(echo_stats.events, 1);
+    assert_eq!(echo_stats.evaluations, 1);
+    assert_eq!(echo_stats.signals, 1);
+    assert_eq!(echo_stats.mean_latency(), echo_stats.total_latency);
+
+    let panicking_stats = host.stats(panicking_id).unwrap();
+    assert_eq!(panicking_stats.state, StrategyState::Failed);
+    assert_eq!(panicking_stats.panics, 1);
+    assert_eq!(
+        panicking_stats.events, 1,
+        "failed strategy receives no more events"
+    );
+    assert!(matches!(
+        host.start(panicking_id),
+        Err(StrategyHostError::InvalidTransition { .. })
+    ));
+    assert_eq!(host.report().len(), 2);
+}
+
+#[test]
+fn host_delivers_risk_actions_to_live_strategies() {
+    let bus = EventBusBuilder::default().build();
+    let receiver = bus.signal_receiver();
+    let host = StrategyHost::<8>::new(bus.signal_sender(), "host-account".into());
+
+    let quoting =
+        || MarketMakingStrategy::new("mm", MarketMakingConfig::default()).with_symbol("BTC-USD");
+    let running_id = host.register(quoting(), HostedStrategyConfig::default());
+    let registered_id = host.register(quoting(), HostedStrategyConfig::default());
+    host.start(running_id).unwrap();
+
+    let halt = RiskEvent::new(
+        EventMetadata::new(EventSource::new("risk"), Priority::Critical),
+        RiskEventPayload {
+            action: RiskAction::HaltAll {
+                reason: "drawdown".into(),
+            },
+            priority: Priority::Critical,
+            tags: HashMap::new(),
+        },
+    );
+    host.dispatch_risk(&halt);
+
+    let signal = receiver.try_recv().expect("quotes pulled");
+    assert_eq!(signal.payload().strategy_id, running_id);
+    assert_eq!(signal.payload().signal.intent, SignalIntent::CancelQuotes);
+    assert_eq!(
+        signal.metadata().correlation_id,
+        halt.metadata().correlation_id
+    );
+    assert!(
+        receiver.try_recv().is_err(),
+        "strategies that never started are not notified"
+    );
+    assert_eq!(host.stats(registered_id).unwrap().signals, 0);
+}
+
+fn sma_indicators() -> IndicatorState {
+    let mut indicators = IndicatorState::new(16);
+    indicators.add(Sma::new(2));
+    indicators
+}
+
+#[test]
+fn host_lifecycle_keeps_indicators_warm_while_paused() {
+    let bus = EventBusBuilder::default().build();
+    let receiver = bus.signal_receiver();
+    let host = StrategyHost::<4>::new(bus.signal_sender(), "host-account".into());
+
+    let (echo, closed_candles) = EchoStrategy::new();
+    let id = host.register(
+        echo,
+        HostedStrategyConfig::default()
+            .with_candle_interval(Duration::from_secs(60))
+            .with_indicators(sma_indicators),
+    );
+    assert_eq!(host.state(id), Some(StrategyState::Registered));
+    assert!(matches!(
+        host.pause(id),
+        Err(StrategyHostError::InvalidTransition { .. })
+    ));
+
+    host.start(id).unwrap();
+    host.pause(id).unwrap();
+    let at = |secs| Utc.timestamp_opt(secs, 0).unwrap();
+    host.dispatch(&tick_at("BTC-USD", 100, at(0)));
+    host.dispatch(&tick_at("BTC-USD", 110, at(30)));
+    host.dispatch(&tick_at("BTC-USD", 120, at(60)));
+    assert_eq!(host.stats(id).unwrap().evaluations, 0);
+    assert!(receiver.try_recv().is_err());
+
+    host.start(id).unwrap();
+    host.dispatch(&tick_at("BTC-USD", 130, at(120)));
+    assert_eq!(*closed_candles.lock().unwrap(), vec![(60, 2)]);
+    assert!(receiver.try_recv().is_ok());
+
+    host.stop(id).unwrap();
+    host.dispatch(&tick_at("BTC-USD", 140, at(180)));
+    let stats = host.remove(id).unwrap();
+    assert_eq!(stats.state, StrategyState::Stopped);
+    assert_eq!(stats.events, 4);
+    assert_eq!(stats.evaluations, 1);
+    assert!(host.is_empty());
+}
+
+/// Symbol, closed candle, buffered candle count and snapshot-window symbols per evaluation.
+type SymbolCandles = Arc<Mutex<Vec<(String, Candle, usize, Vec<String>)>>>;
+
+/// Records what each closed candle evaluation saw, without signalling.
+struct CandleRecorder {
+    seen: SymbolCandles,
+}
+
+impl StrategyExecutor<4> for CandleRecorder {
+    fn name(&self) -> &str {
+        "candle-recorder"
+    }
+
+    fn evaluate(&mut self, ctx: StrategyContext<'_, 4>) -> Result<StrategyDecision, StrategyError> {
+        if let (Some(candle), Some(indicators)) = (ctx.closed_candle(), ctx.indicators()) {
+            let window = ctx
+                .snapshots()
+                .iter()
+                .filter(|snapshot| !snapshot.symbol.is_empty())
+                .map(|snapshot| snapshot.symbol.clone())
+                .collect();
+            let latest = ctx.snapshots().last().expect("snapshot window");
+            self.seen.lock().unwrap().push((
+                latest.symbol.clone(),
+                candle.clone(),
+                indicators.buffer.len(),
+                window,
+            ));
+        }
+        Ok(StrategyDecision::empty())
+    }
+}
+
+#[test]
+fn host_keeps_candles_and_indicators_per_symbol() {
+    let bus = EventBusBuilder::default().build();
+    let host = StrategyHost::<4>::new(bus.signal_sender(), "host-account".into());
+    let seen = SymbolCandles::default();
+    let id = host.register(
+        CandleRecorder { seen: seen.clone() },
+        HostedStrategyConfig::default()
+            .with_candle_interval(Duration::from_secs(60))
+            .with_indicators(sma_indicators),
+    );
+    host.start(id).unwrap();
+
+    let at = |secs| Utc.timestamp_opt(secs, 0).unwrap();
+    host.dispatch(&tick_at("BTC-USD", 30_000, at(0)));
+    host.dispatch(&tick_at("ETH-USD", 2_000, at(10)));
+    host.dispatch(&tick_at("BTC-USD", 30_100, at(60)));
+    host.dispatch(&tick_at("ETH-USD", 2_100, at(70)));
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 2, "each symbol closes its own candle");
+    for ((symbol, candle, buffered, window), (expected, price)) in
+        seen.iter().zip([("BTC-USD", 30_000u32), ("ETH-USD", 2_000)])
+    {
+        assert_eq!(symbol, expected);
+        assert_eq!(candle.timestamp, 0);
+        assert_eq!(candle.low, Decimal::from(price));
+        assert_eq!(candle.high, Decimal::from(price));
+        assert_eq!(candle.volume, Decimal::ONE);
+        assert_eq!(*buffered, 1);
+        assert!(window.iter().all(|snapshot| snapshot == expected));
+    }
+}
+
+#[tokio::test]
+async fn runner_evaluates_market_events() {
+    let bus = EventBusBuilder::default().build();
+    let receiver = bus.signal_receiver();
+    let (echo, _) = EchoStrategy::new();
+    let runner = StrategyRunner::new(echo, bus.signal_sender(), "runner-account".into());
+
+    runner.handle(tick("BTC-USD", 30_000)).await.unwrap();
+
+    let signal = receiver.try_recv().expect("signal published");
+    assert_eq!(signal.payload().strategy_id, runner.strategy_id());
+    assert_eq!(runner.stats().unwrap().signals, 1);
+}
+
+#[derive(Default)]
+struct RecordingAuditSink {
+    changes: Mutex<Vec<ParameterChange>>,
+}
+
+impl ParameterAuditSink for RecordingAuditSink {
+    fn record(&self, change: &ParameterChange) {
+        self.changes.lock().unwrap().push(change.clone());
+    }
+}
+
+#[test]
+fn host_reconfigures_running_strategy_and_audits_changes() {
+    let bus = EventBusBuilder::default().build();
+    let sink = Arc::new(RecordingAuditSink::default());
+    let host = StrategyHost::<8>::new(bus.signal_sender(), "host-account".into())
+        .with_audit_sink(sink.clone());
+    let id = host.register(
+        MomentumStrategy::with_defaults("momentum"),
+        HostedStrategyConfig::default(),
+    );
+    host.start(id).unwrap();
+
+    let changes = serde_json::from_value(serde_json::json!({
+        "rsi_overbought": 75,
+        "target_exchange": "Kraken"
+    }))
+    .unwrap();
+    let change = host.update_parameters(id, &changes, "ops@desk").unwrap();
+    assert_eq!(change.changed, vec!["rsi_overbought", "target_exchange"]);
+    assert_eq!(host.state(id), Some(StrategyState::Running));
+    assert_eq!(
+        host.parameters(id)
+            .unwrap()
+            .choice("target_exchange")
+            .unwrap(),
+        "Kraken"
+    );
+
+    let out_of_range = serde_json::from_value(serde_json::json!({ "rsi_period": 1 })).unwrap();
+    assert!(matches!(
+        host.update_parameters(id, &out_of_range, "ops@desk"),
+        Err(StrategyHostError::Parameters(
+            ParamError::BelowMinimum { .. }
+        ))
+    ));
+    let crossed =
+        serde_json::from_value(serde_json::json!({ "rsi_oversold": 50, "rsi_overbought": 50 }))
+            .unwrap();
+    assert!(matches!(
+        host.update_parameters(id, &crossed, "ops@desk"),
+        Err(StrategyHostError::Reconfiguration(_))
+    ));
+
+    let history = host.parameter_history(id);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].changed_by, "ops@desk");
+    assert_eq!(sink.changes.lock().unwrap().len(), 1);
+}
+
+struct RejectingStrategy;
+
+impl StrategyExecutor<4> for RejectingStrategy {
+    fn name(&self) -> &str {
+        "rejecting"
+    }
+
+    fn initialize(&mut self, _ctx: StrategyInitContext<'_>) -> Result<(), StrategyError> {
+        Err(StrategyError::sandbox("missing exports"))
+    }
+
+    fn evaluate(
+        &mut self,
+        _ctx: StrategyContext<'_, 4>,
+    ) -> Result<StrategyDecision, StrategyError> {
+        Ok(StrategyDecision::empty())
+    }
+}
+
+#[derive(Default)]
+struct RecordingActivitySink {
+    activity: Mutex<Vec<StrategyActivity>>,
+}
+
+impl StrategyActivitySink for RecordingActivitySink {
+    fn record(&self, activity: &StrategyActivity) {
+        self.activity.lock().unwrap().push(activity.clone());
+    }
+}
+
+#[test]
+fn host_swaps_executor_in_place_and_reports_activity() {
+    let bus = EventBusBuilder::default().build();
+    let receiver = bus.signal_receiver();
+    let sink = Arc::new(RecordingActivitySink::default());
+    let host = StrategyHost::<4>::new(bus.signal_sender(), "host-account".into())
+        .with_activity_sink(sink.clone());
+
+    let id = Uuid::new_v4();
+    let (first, _) = EchoStrategy::new();
+    host.register_as(
+        id,
+        first,
+        HostedStrategyConfig::default()
+            .with_candle_interval(Duration::from_secs(60))
+            .with_indicators(sma_indicators),
+    )
+    .unwrap();
+    let (duplicate, _) = EchoStrategy::new();
+    assert!(matches!(
+        host.register_as(id, duplicate, HostedStrategyConfig::default()),
+        Err(StrategyHostError::DuplicateStrategy(_))
+    ));
+    host.start(id).unwrap();
+
+    let at = |secs| Utc.timestamp_opt(secs, 0).unwrap();
+    host.dispatch(&tick_at("BTC-USD", 100, at(0)));
+    host.dispatch(&tick_at("BTC-USD", 110, at(60)));
+    assert!(receiver.try_recv().is_ok() && receiver.try_recv().is_ok());
+
+    assert!(matches!(
+        host.swap_strategy(id, RejectingStrategy),
+        Err(StrategyHostError::Initialization(_))
+    ));
+    assert_eq!(host.state(id), Some(StrategyState::Running));
+
+    let (second, closed_candles) = EchoStrategy::new();
+    host.swap_strategy(id, second).unwrap();
+    host.dispatch(&tick_at("BTC-USD", 120, at(120)));
+    assert_eq!(
+        *closed_candles.lock().unwrap(),
+        vec![(60, 2)],
+        "replacement sees the candles buffered before the swap"
+    );
+    let signal = receiver.try_recv().expect("replacement publishes");
+    assert_eq!(signal.payload().strategy_id, id);
+
+    let stats = host.stats(id).unwrap();
+    assert_eq!(stats.evaluations, 3);
+    assert_eq!(stats.signals, 3);
+    let activity = sink.activity.lock().unwrap();
+    assert_eq!(activity.len(), 3);
+    assert!(activity
+        .iter()
+        .all(|entry| entry.strategy_id == id && entry.signals.len() == 1));
+}
+
+const BUY_INTENT: &str =
+    r#"{"symbol":"BTC-USD","side":"Buy","quantity":"1","stop_loss":"29000","take_profit":"32000"}"#;
+const SELL_INTENT: &str = r#"{"symbol":"BTC-USD","side":"Sell","quantity":"2","stop_loss":"32000","take_profit":"29000"}"#;
+
+/// ABI v2 module: logs "warm" once the SMA is ready, counts runs in persisted state and
+/// emits a sell intent when long, a buy intent otherwise.
+fn abi_v2_wat(abi_version: i32) -> String {
+    let escape = |json: &str| json.replace('"', "\\\"");
+    format!(
+        r#"(module
+  (import "host" "log" (func $log (param i32 i32)))
+  (import "host" "indicator_find" (func $indicator_find (param i32 i32) (result i32)))
+  (import "host" "indicator_value" (func $indicator_value (param i32) (result f64)))
+  (import "host" "state_get" (func $state_get (param i32 i32 i32 i32) (result i32)))
+  (import "host" "state_set" (func $state_set (param i32 i32 i32 i32) (result i32)))
+  (import "host" "position_quantity" (func $position_quantity (param i32 i32) (result f64)))
+  (import "host" "emit_order" (func $emit_order (param i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 4096))
+  (data (i32.const 0) "sma")
+  (data (i32.const 16) "warm")
+  (data (i32.const 32) "runs")
+  (data (i32.const 48) "BTC-USD")
+  (data (i32.const 64) "{buy}")
+  (data (i32.const 512) "{sell}")
+  (func (export "abi_version") (result i32) (i32.const {abi_version}))
+  (func (export "alloc") (param $size i32) (result i32)
+        (local $ptr i32)
+        (local.set $ptr (global.get $next))
+        (global.set $next (i32.add (local.get $ptr) (local.get $size)))
+        (local.get $ptr))
+  (func (export "evaluate") (param $ctx_ptr i32) (param $ctx_len i32) (result i32)
+        (local $sma f64)
+        (local.set $sma (call $indicator_value (call $indicator_find (i32.const 0) (i32.const 3))))
+        (if (f64.eq (local.get $sma) (local.get $sma))
+            (then (call $log (i32.const 16) (i32.const 4))))
+        (if (i32.eq (call $state_get (i32.const 32) (i32.const 4) (i32.const 1000) (i32.const 1)) (i32.const -1))
+            (then (i32.store8 (i32.const 1000) (i32.const 48))))
+        (i32.store8 (i32.const 1000) (i32.add (i32.load8_u (i32.const 1000)) (i32.const 1)))
+        (drop (call $state_set (i32.const 32) (i32.const 4) (i32.const 1000) (i32.const 1)))
+        (if (f64.gt (call $position_quantity (i32.const 48) (i32.const 7)) (f64.const 0))
+            (then (drop (call $emit_order (i32.const 512) (i32.const {sell_len}))))
+            (else (drop (call $emit_order (i32.const 64) (i32.const {buy_len})))))
+        (i32.const 0)))"#,
+        buy = escape(BUY_INTENT),
+        sell = escape(SELL_INTENT),
+        buy_len = BUY_INTENT.len(),
+        sell_len = SELL_INTENT.len(),
+    )
+}
+
+fn abi_v2_strategy(store: Arc<InMemoryStateStore>, long: bool) -> WasmStrategy {
+    let wasm_bytes = parse_wat(abi_v2_wat(2)).expect("valid abi v2 wasm");
+    let module =
+        WasmStrategyModule::from_bytes(&wasm_bytes, &WasmStrategyConfig::default()).unwrap();
+    let instance = module.instantiate(WasmStrategyConfig::default()).unwrap();
+    assert_eq!(instance.abi_version(), 2);
+
+    let mut portfolio = PortfolioSnapshot::default();
+    if long {
+        portfolio.positions.insert(
+            "BTC-USD".into(),
+            PositionView {
+                quantity: Decimal::from(2),
+                average_price: Decimal::from(30_000),
+            },
+        );
+    }
+    WasmStrategy::new("abi-v2", instance)
+        .with_state_store(store)
+        .with_portfolio(Arc::new(portfolio))
+}
+
+fn evaluate_wasm(
+    strategy: &mut WasmStrategy,
+    indicators: &IndicatorState,
+) -> Result<StrategyDecision, StrategyError> {
+    let account_id = String::from("sandbox-account");
+    let snapshots = [MarketSnapshot {
+        symbol: "BTC-USD".into(),
+        bid: Decimal::from(30_000u32),
+        ask: Decimal::from(30_010u32),
+        last: Decimal::from(30_005u32),
+        timestamp: Utc::now(),
+    }];
+    let context = StrategyContext::new(&account_id, &snapshots, Uuid::nil(), Utc::now())
+        .with_indicators(indicators);
+    StrategyExecutor::<1>::evaluate(strategy, context)
+}
+
+#[test]
+fn wasm_abi_v2_reads_host_data_and_persists_state() {
+    let store = Arc::new(InMemoryStateStore::new());
+    let strategy_id = Uuid::new_v4();
+    let account_id = String::from("sandbox-account");
+
+    let mut indicators = IndicatorState::new(8);
+    indicators.add(Sma::new(2));
+    let mut strategy = abi_v2_strategy(store.clone(), true);
+    StrategyExecutor::<1>::initialize(
+        &mut strategy,
+        StrategyInitContext {
+            strategy_id,
+            account_id: &account_id,
+        },
+    )
+    .unwrap();
+
+    let decision = evaluate_wasm(&mut strategy, &indicators).unwrap();
+    assert!(decision.logs.is_empty(), "SMA is not warm yet");
+    assert_eq!(decision.signals.len(), 1);
+    let signal = &decision.signals[0];
+    assert_eq!(signal.strategy_id, strategy_id);
+    assert_eq!(signal.account_id, "sandbox-account");
+    assert_eq!(signal.signal.side, OrderSide::Sell);
+    assert_eq!(signal.signal.metadata[STOP_LOSS_KEY], "32000");
+    assert!(decision.metrics.fuel_consumed > 0);
+    assert_eq!(store.load("abi-v2").unwrap()["runs"], "1");
+
+    for close in [100u32, 102] {
+        let price = Decimal::from(close);
+        indicators.update(crate::indicators::buffer::Candle {
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: Decimal::ONE,
+            timestamp: close as i64,
+        });
+    }
+
+    // A fresh instance (e.g. after a restart) picks the persisted state back up.
+    let mut restarted = abi_v2_strategy(store.clone(), false);
+    StrategyExecutor::<1>::initialize(
+        &mut restarted,
+        StrategyInitContext {
+            strategy_id,
+            account_id: &account_id,
+        },
+    )
+    .unwrap();
+    let decision = evaluate_wasm(&mut restarted, &indicators).unwrap();
+    assert_eq!(decision.logs, vec!["warm".to_string()]);
+    assert_eq!(decision.signals[0].signal.side, OrderSide::Buy);
+    assert_eq!(restarted.instance().state()["runs"], "2");
+    assert_eq!(store.load("abi-v2").unwrap()["runs"], "2");
+}
+
+#[test]
+fn wasm_runaway_loop_exhausts_fuel() {
+    let wasm = r#"(module
+  (memory (export "memory") 1)
+  (func (export "alloc") (param $size i32) (result i32) (i32.const 1024))
+  (func (export "evaluate") (param $ctx_ptr i32) (param $ctx_len i32) (result i32)
+        (loop $spin (br $spin))
+        (i32.const 0)))"#;
+    let wasm_bytes = parse_wat(wasm).expect("valid wasm");
+    let config = WasmStrategyConfig {
+        fuel_limit: 10_000,
+        ..WasmStrategyConfig::default()
+    };
+    let module = WasmStrategyModule::from_bytes(&wasm_bytes, &config).unwrap();
+    let instance = module.instantiate(config).unwrap();
+    let mut strategy = WasmStrategy::new("spinner", instance);
+
+    let result = evaluate_wasm(&mut strategy, &IndicatorState::new(1));
+    assert!(matches!(result, Err(StrategyError::FuelExhausted(10_000))));
+}
+
+/// ABI v3 module: logs "bands" when the Bollinger upper band sits above the lower one
+/// and "absent" when asked for a line the indicator does not have.
+const ABI_V3_WAT: &str = r#"(module
+  (import "host" "log" (func $log (param i32 i32)))
+  (import "host" "indicator_find" (func $indicator_find (param i32 i32) (result i32)))
+  (import "host" "indicator_component" (func $indicator_component (param i32 i32 i32) (result f64)))
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 4096))
+  (data (i32.const 0) "bollinger bands")
+  (data (i32.const 16) "upper")
+  (data (i32.const 24) "lower")
+  (data (i32.const 32) "nope")
+  (data (i32.const 48) "bands")
+  (data (i32.const 64) "absent")
+  (func (export "abi_version") (result i32) (i32.const 3))
+  (func (export "alloc") (param $size i32) (result i32)
+        (local $ptr i32)
+        (local.set $ptr (global.get $next))
+        (global.set $next (i32.add (local.get $ptr) (local.get $size)))
+        (local.get $ptr))
+  (func (export "evaluate") (param $ctx_ptr i32) (param $ctx_len i32) (result i32)
+        (local $index i32)
+        (local $missing f64)
+        (local.set $index (call $indicator_find (i32.const 0) (i32.const 15)))
+        (if (f64.gt (call $indicator_component (local.get $index) (i32.const 16) (i32.const 5))
+                    (call $indicator_component (local.get $index) (i32.const 24) (i32.const 5)))
+            (then (call $log (i32.const 48) (i32.const 5))))
+        (local.set $missing (call $indicator_component (local.get $index) (i32.const 32) (i32.const 4)))
+        (if (f64.ne (local.get $missing) (local.get $missing))
+            (then (call $log (i32.const 64) (i32.const 6))))
+        (i32.const 0)))"#;
+
+#[test]
+fn wasm_abi_v3_reads_named_indicator_components() {
+    let wasm_bytes = parse_wat(ABI_V3_WAT).expect("valid abi v3 wasm");
+    let module =
+        WasmStrategyModule::from_bytes(&wasm_bytes, &WasmStrategyConfig::default()).unwrap();
+    let instance = module.instantiate(WasmStrategyConfig::default()).unwrap();
+    assert_eq!(instance.abi_version(), 3);
+    let mut strategy = WasmStrategy::new("abi-v3", instance);
+
+    let mut indicators = IndicatorState::new(8);
+    indicators.add(BollingerBands::new(3, 2.0));
+    let decision = evaluate_wasm(&mut strategy, &indicators).unwrap();
+    assert_eq!(
+        decision.logs,
+        vec!["absent".to_string()],
+        "bands not warm yet"
+    );
+
+    for i in 0..4 {
+        indicators.update(swing_candle(i));
+    }
+    let decision = evaluate_wasm(&mut strategy, &indicators).unwrap();
+    assert_eq!(
+        decision.logs,
+        vec!["bands".to_string(), "absent".to_string()]
+    );
+}
+
+fn swing_candle(i: i64) -> Candle {
+    let close = Decimal::from(100 + (i * 7) % 11);
+    Candle {
+        open: close,
+        high: close + Decimal::ONE,
+        low: close - Decimal::TWO,
+        close,
+        volume: Decimal::from(10 + i % 4),
+        timestamp: i,
+    }
+}
+
+#[test]
+fn indicator_state_chains_named_components() {
+    let mut indicators = IndicatorState::new(64);
+    indicators
+        .add(Obv::new())
+        .add(Atr::new(5))
+        .add(Macd::new(3, 6, 3));
+    let rsi_of_obv = indicators.chain(0, "value", Rsi::new(5)).unwrap();
+    let ema_of_atr = indicators.chain(1, "value", Ema::new(4)).unwrap();
+    let ema_of_histogram = indicators.chain(2, "histogram", Ema::new(3)).unwrap();
+    assert!(indicators.chain(9, "value", Ema::new(3)).is_err());
+
+    assert_eq!(indicators.label(rsi_of_obv).as_deref(), Some("RSI of OBV"));
+    assert_eq!(indicators.label(ema_of_atr).as_deref(), Some("EMA of ATR"));
+    assert_eq!(
+        indicators.find("ema of macd.histogram"),
+        Some(ema_of_histogram)
+    );
+    assert_eq!(
+        indicators.input(ema_of_atr),
+        Some(&IndicatorInput::Component {
+            source: 1,
+            component: "value".into(),
+        })
+    );
+
+    // Standalone copies fed by hand from their sources' readings.
+    let (mut obv, mut atr, mut macd) = (Obv::new(), Atr::new(5), Macd::new(3, 6, 3));
+    let (mut rsi, mut ema, mut histogram_ema) = (Rsi::new(5), Ema::new(4), Ema::new(3));
+    for i in 0..40 {
+        let candle = swing_candle(i);
+        indicators.update(candle.clone());
+        obv.update_ohlcv(&candle);
+        if obv.is_ready() {
+            rsi.update(obv.current().unwrap().value);
+        }
+        atr.update_ohlcv(&candle);
+        if atr.is_ready() {
+            ema.update(atr.current().unwrap().value);
+        }
+        macd.update_ohlcv(&candle);
+        if macd.is_ready() {
+            histogram_ema.update(macd.output().unwrap().get("histogram").unwrap());
+        }
+    }
+
+    let macd_lines: Vec<_> = indicators
+        .output(2)
+        .unwrap()
+        .iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(macd_lines, ["macd", "signal", "histogram"]);
+    assert!(indicators.indicators[rsi_of_obv].is_ready());
+    assert_eq!(
+        indicators.component(rsi_of_obv, "value"),
+        rsi.current().map(|v| v.value)
+    );
+    assert_eq!(
+        indicators.component(ema_of_atr, "value"),
+        ema.current().map(|v| v.value)
+    );
+    assert_eq!(
+        indicators.component(ema_of_histogram, "value"),
+        histogram_ema.current().map(|v| v.value)
+    );
+
+    // A replacement for a chained indicator is warmed from the values it was fed.
+    let previous = indicators.replace(ema_of_atr, Ema::new(4)).unwrap();
+    assert_eq!(
+        indicators.indicators[ema_of_atr].current(),
+        previous.current()
+    );
+}
+
+#[test]
+fn wasm_rejects_newer_abi_version() {
+    let wasm_bytes = parse_wat(abi_v2_wat(HOST_ABI_VERSION as i32 + 1)).expect("valid wasm");
+    let module =
+        WasmStrategyModule::from_bytes(&wasm_bytes, &WasmStrategyConfig::default()).unwrap();
+    assert!(matches!(
+        module.instantiate(WasmStrategyConfig::default()),
+        Err(StrategyError::Sandbox(_))
+    ));
+}
+
+/// Buys below and sells above a rolling mean of closes.
+struct MeanReversion {
+    period: usize,
+    closes: std::collections::VecDeque<Decimal>,
+}
+
+impl StrategyExecutor<1> for MeanReversion {
+    fn name(&self) -> &str {
+        "mean-reversion"
+    }
+
+    fn evaluate(&mut self, ctx: StrategyContext<'_, 1>) -> Result<StrategyDecision, StrategyError> {
+        let mut decision = StrategyDecision::empty();
+        let Some(candle) = ctx.closed_candle() else {
+            return Ok(decision);
+        };
+        self.closes.push_back(candle.close);
+        if self.closes.len() > self.period {
+            self.closes.pop_front();
+        }
+        if self.closes.len() < self.period {
+            return Ok(decision);
+        }
+        let mean = self.closes.iter().sum::<Decimal>() / Decimal::from(self.period);
+        let side = if candle.close < mean {
+            OrderSide::Buy
+        } else {
+            OrderSide::Sell
+        };
+        decision.signals.push(SignalEventPayload {
+            strategy_id: Uuid::nil(),
+            account_id: ctx.account_id().clone(),
+            priority: Priority::Normal,
+            signal: StrategySignal {
+                exchange: None,
+                symbol: "BTC-USD".into(),
+                side,
+                order_type: OrderType::Market,
+                quantity: Decimal::ONE,
+                limit_price: None,
+                confidence: 1.0,
+                metadata: HashMap::new(),
+                intent: SignalIntent::Order,
+            },
+        });
+        Ok(decision)
+    }
+}
+
+fn mean_reversion(params: &ParamSet) -> Result<Box<dyn StrategyExecutor<1>>, StrategyError> {
+    let period = params.integer("period")? as usize;
+    if period % 7 == 0 {
+        return Err(ParamError::Invalid("period must not be a multiple of 7".into()).into());
+    }
+    Ok(Box::new(MeanReversion {
+        period,
+        closes: Default::default(),
+    }))
+}
+
+fn oscillating_candles(count: usize) -> Vec<Candle> {
+    (0..count)
+        .map(|i| {
+            let wave = (i as f64 * std::f64::consts::TAU / 40.0).sin() * 10.0;
+            let price = Decimal::from_f64_retain(100.0 + wave).unwrap().round_dp(4);
+            Candle {
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume: Decimal::ONE,
+                timestamp: i as i64 * 3600,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn optimizer_ranks_walk_forward_candidates_and_guards_overfitting() {
+    let schema = ParamSchema::new().with(ParamSpec::integer("period", 10, 2, 100));
+    let space = SearchSpace::from_json(
+        &schema,
+        &[(
+            "period".to_string(),
+            serde_json::json!({ "min": 2, "max": 60, "step": 1 }),
+        )]
+        .into_iter()
+        .collect(),
+    )
+    .unwrap();
+    let candles = oscillating_candles(400);
+
+    let config = OptimizerConfig {
+        method: SearchMethod::Grid,
+        objective: Objective::ProfitFactor,
+        max_evaluations: 100,
+        backtest: BacktestConfig {
+            fee_bps: 0.0,
+            slippage_bps: 0.0,
+            ..BacktestConfig::default()
+        },
+        threads: 4,
+        ..OptimizerConfig::default()
+    };
+    let optimizer = StrategyOptimizer::new(schema.defaults(), mean_reversion, config.clone());
+    let report = optimizer.optimize(&space, &candles).unwrap();
+
+    assert_eq!(report.evaluated, 59);
+    assert_eq!(report.invalid, 8);
+    let best = report.best().expect("an accepted candidate");
+    assert!(best.out_of_sample.is_some());
+    assert!(best.score > 1.0);
+    assert!(best.in_sample.trades >= config.guards.min_trades);
+    assert!(report
+        .candidates
+        .windows(2)
+        .all(|pair| pair[0].rejection.is_some() <= pair[1].rejection.is_some()));
+    let strict = StrategyOptimizer::new(
+        schema.defaults(),
+        mean_reversion,
+        OptimizerConfig {
+            guards: OverfitGuards {
+                min_trades: 10_000,
+                ..OverfitGuards::default()
+            },
+            ..config.clone()
+        },
+    );
+    let strict = strict.optimize(&space, &candles).unwrap();
+    assert!(strict.best().is_none());
+    assert_eq!(strict.rejected(), strict.candidates.len());
+
+    let adaptive = StrategyOptimizer::new(
+        schema.defaults(),
+        mean_reversion,
+        OptimizerConfig {
+            method: SearchMethod::Adaptive,
+            max_evaluations: 30,
+            seed: 11,
+            ..config.clone()
+        },
+    );
+    let first = adaptive.optimize(&space, &candles).unwrap();
+    let second = adaptive.optimize(&space, &candles).unwrap();
+    assert!(first.evaluated <= 30);
+    assert_eq!(
+        first.best().map(|c| c.parameters.clone()),
+        second.best().map(|c| c.parameters.clone())
+    );
+
+    let too_large = StrategyOptimizer::new(
+        schema.defaults(),
+        mean_reversion,
+        OptimizerConfig {
+            max_evaluations: 10,
+            ..config
+        },
+    );
+    assert!(matches!(
+        too_large.optimize(&space, &candles),
+        Err(OptimizerError::GridTooLarge {
+            size: 59,
+            limit: 10
+        })
+    ));
+    assert!(matches!(
+        optimizer.optimize(&space, &candles[..100]),
+        Err(OptimizerError::NotEnoughData { bars: 100, .. })
+    ));
+}
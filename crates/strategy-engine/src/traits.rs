@@ -2,13 +2,18 @@ use std::fmt;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
-use event_bus::{MarketEvent, Priority, SignalEventPayload, StrategySignal};
+use event_bus::{MarketEvent, Priority, RiskAction, SignalEventPayload, StrategySignal};
+use ninja_gekko_core::position_sizing::AccountSnapshot;
 use ninja_gekko_core::types::AccountId;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::indicators::buffer::Candle;
+use crate::indicators::state::IndicatorState;
+use crate::params::{ParamError, ParamSchema, ParamSet};
+
 /// Compile-time sized market snapshot buffer supplied to strategies.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketSnapshot {
@@ -43,6 +48,9 @@ pub struct StrategyContext<'a, const N: usize> {
     evaluation_id: Uuid,
     as_of: DateTime<Utc>,
     market_events: Option<&'a [MarketEvent]>,
+    indicators: Option<&'a IndicatorState>,
+    closed_candle: Option<&'a Candle>,
+    account: Option<&'a AccountSnapshot>,
 }
 
 impl<'a, const N: usize> StrategyContext<'a, N> {
@@ -58,6 +66,9 @@ impl<'a, const N: usize> StrategyContext<'a, N> {
             evaluation_id,
             as_of,
             market_events: None,
+            indicators: None,
+            closed_candle: None,
+            account: None,
         }
     }
 
@@ -66,6 +77,24 @@ impl<'a, const N: usize> StrategyContext<'a, N> {
         self
     }
 
+    /// Attaches the candle buffer and indicators maintained by the host.
+    pub fn with_indicators(mut self, indicators: &'a IndicatorState) -> Self {
+        self.indicators = Some(indicators);
+        self
+    }
+
+    /// Marks the evaluation as triggered by a freshly closed candle.
+    pub fn with_closed_candle(mut self, candle: &'a Candle) -> Self {
+        self.closed_candle = Some(candle);
+        self
+    }
+
+    /// Attaches the account's live equity and positions for position sizing.
+    pub fn with_account(mut self, account: &'a AccountSnapshot) -> Self {
+        self.account = Some(account);
+        self
+    }
+
     pub fn account_id(&self) -> &AccountId {
         self.account_id
     }
@@ -89,6 +118,20 @@ impl<'a, const N: usize> StrategyContext<'a, N> {
     pub fn market_events(&self) -> Option<&[MarketEvent]> {
         self.market_events
     }
+
+    pub fn indicators(&self) -> Option<&IndicatorState> {
+        self.indicators
+    }
+
+    /// Candle closed by the tick that triggered this evaluation, if any.
+    pub fn closed_candle(&self) -> Option<&Candle> {
+        self.closed_candle
+    }
+
+    /// Live account state, when the host tracks one.
+    pub fn account(&self) -> Option<&AccountSnapshot> {
+        self.account
+    }
 }
 
 /// Initialization context executed once prior to evaluation.
@@ -117,6 +160,8 @@ impl StrategyDecision {
 #[derive(Debug, Clone, Default)]
 pub struct StrategyMetrics {
     pub evaluation_latency: Duration,
+    /// Wasm fuel spent by sandboxed strategies; zero for native ones.
+    pub fuel_consumed: u64,
 }
 
 /// Contracts every strategy implementation must satisfy.
@@ -128,6 +173,26 @@ pub trait StrategyExecutor<const N: usize>: Send {
     }
 
     fn evaluate(&mut self, ctx: StrategyContext<'_, N>) -> Result<StrategyDecision, StrategyError>;
+
+    /// Typed parameters the strategy accepts; empty when it has nothing to tune.
+    fn parameter_schema(&self) -> ParamSchema {
+        ParamSchema::default()
+    }
+
+    /// Parameter values currently in effect.
+    fn parameters(&self) -> ParamSet {
+        self.parameter_schema().defaults()
+    }
+
+    /// Applies a schema-validated parameter set in place, keeping indicator state.
+    fn update_parameters(&mut self, _params: &ParamSet) -> Result<(), StrategyError> {
+        Ok(())
+    }
+
+    /// Reacts to a risk control action, e.g. pulling resting quotes on a halt.
+    fn on_risk(&mut self, _action: &RiskAction) -> Result<StrategyDecision, StrategyError> {
+        Ok(StrategyDecision::empty())
+    }
 }
 
 /// Errors surfaced during strategy execution or sandbox orchestration.
@@ -141,6 +206,10 @@ pub enum StrategyError {
     Wasm(#[from] anyhow::Error),
     #[error("strategy evaluation exceeded {0:?}")]
     Timeout(Duration),
+    #[error("strategy evaluation exhausted its fuel budget of {0}")]
+    FuelExhausted(u64),
+    #[error("invalid parameters: {0}")]
+    Parameters(#[from] ParamError),
 }
 
 impl StrategyError {
@@ -0,0 +1,54 @@
+-- V005: Versioned neural model registry
+-- Lets neural_models hold several versions per model name with their lifecycle
+-- status, feature schema hash and evaluation metrics, and records the outcome
+-- of shadow runs comparing a candidate version against the live one.
+
+-- Versions of a model share its name
+ALTER TABLE neural_models DROP CONSTRAINT IF EXISTS neural_models_name_key;
+ALTER TABLE neural_models ADD CONSTRAINT neural_models_name_version_key UNIQUE (name, version);
+
+-- Statistical models have no network architecture
+ALTER TABLE neural_models ALTER COLUMN model_type DROP NOT NULL;
+
+ALTER TABLE neural_models
+    ADD COLUMN IF NOT EXISTS task VARCHAR(20) CHECK (task IN ('volatility', 'arbitrage', 'risk')),
+    ADD COLUMN IF NOT EXISTS backend VARCHAR(20),
+    ADD COLUMN IF NOT EXISTS feature_schema_hash VARCHAR(64),
+    ADD COLUMN IF NOT EXISTS evaluation_metrics JSONB,
+    ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'candidate'
+        CHECK (status IN ('candidate', 'shadow', 'live', 'retired', 'rolled_back'));
+
+-- At most one live version per model
+CREATE UNIQUE INDEX IF NOT EXISTS idx_neural_models_one_live
+    ON neural_models(name) WHERE status = 'live';
+CREATE INDEX IF NOT EXISTS idx_neural_models_status ON neural_models(status);
+
+-- Shadow run comparisons
+CREATE TABLE IF NOT EXISTS model_shadow_comparisons (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    task VARCHAR(20) NOT NULL CHECK (task IN ('volatility', 'arbitrage', 'risk')),
+
+    -- Registry versions, when the compared predictors are versioned
+    live_model_id UUID REFERENCES neural_models(id),
+    shadow_model_id UUID REFERENCES neural_models(id),
+    live_predictor VARCHAR(150) NOT NULL,
+    shadow_predictor VARCHAR(150) NOT NULL,
+
+    -- Agreement
+    samples BIGINT NOT NULL DEFAULT 0,
+    live_only BIGINT NOT NULL DEFAULT 0,
+    shadow_only BIGINT NOT NULL DEFAULT 0,
+    shadow_errors BIGINT NOT NULL DEFAULT 0,
+    mean_abs_difference DOUBLE PRECISION[] NOT NULL, -- Per output, in task output order
+    max_abs_difference DOUBLE PRECISION NOT NULL DEFAULT 0,
+
+    -- Latency
+    avg_live_latency_ms DOUBLE PRECISION NOT NULL DEFAULT 0,
+    avg_shadow_latency_ms DOUBLE PRECISION NOT NULL DEFAULT 0,
+
+    started_at TIMESTAMPTZ NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_model_shadow_comparisons_shadow_model_id ON model_shadow_comparisons(shadow_model_id);
+CREATE INDEX IF NOT EXISTS idx_model_shadow_comparisons_recorded_at ON model_shadow_comparisons(recorded_at);
@@ -0,0 +1,10 @@
+-- V006: Online prediction monitoring
+-- Keeps every output of a logged prediction and of its realized targets, so
+-- calibration and per-output error can be recomputed from model_predictions.
+
+ALTER TABLE model_predictions
+    ADD COLUMN IF NOT EXISTS series VARCHAR(100),
+    ADD COLUMN IF NOT EXISTS output_values DOUBLE PRECISION[], -- In task output order
+    ADD COLUMN IF NOT EXISTS actual_values DOUBLE PRECISION[]; -- Realized targets, same order
+
+CREATE INDEX IF NOT EXISTS idx_model_predictions_validated_at ON model_predictions(validated_at);
@@ -0,0 +1,24 @@
+-- V007: Uploaded WASM strategy modules
+-- Keeps every uploaded version of a sandboxed strategy with the SHA-256 of its
+-- bytes, so deployments can name an exact version and re-uploads are detected.
+
+CREATE TABLE IF NOT EXISTS strategy_modules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    strategy_id UUID NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    version INTEGER NOT NULL CHECK (version > 0),
+
+    -- Module contents
+    content_hash VARCHAR(64) NOT NULL, -- Hex SHA-256 of wasm_bytes
+    abi_version INTEGER NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    wasm_bytes BYTEA NOT NULL,
+
+    uploaded_by VARCHAR(255) NOT NULL, -- User ID; the first version's uploader owns the strategy
+    uploaded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT strategy_modules_strategy_version_key UNIQUE (strategy_id, version),
+    CONSTRAINT strategy_modules_strategy_hash_key UNIQUE (strategy_id, content_hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_strategy_modules_strategy_id ON strategy_modules(strategy_id);
@@ -111,10 +111,43 @@ async fn main() -> Result<()> {
         event_bus::PublishMode::Try,
     ));
 
+    // Net signals from strategies sharing an account before they become orders
+    let signal_combiner =
+        std::sync::Arc::new(event_bus::core_bridges::SignalCombiner::new(signal_bridge));
+
+    // Initialize Main Trading API (8080) before the dispatcher, so strategies
+    // deployed through it share the market feed with the built-in runner.
+    // They publish their signals onto the same event bus.
+    info!("🚀 Initializing Main API Server...");
+    let main_api = match ninja_gekko_api::ApiServer::with_event_bus(event_bus.clone()).await {
+        Ok(server) => Some(server),
+        Err(e) => {
+            error!("💥 Failed to initialize Main API Server: {}", e);
+            None
+        }
+    };
+
+    // The bus hands each market event to a single handler, so fan it out here
+    let market_handler: std::sync::Arc<dyn event_bus::EventHandler<event_bus::MarketEvent>> =
+        match &main_api {
+            Some(server) => {
+                let deployments = server.state().strategy_deployments.clone();
+                let runner = strategy_runner.clone();
+                std::sync::Arc::new(event_bus::ClosureHandler::new(
+                    move |event: event_bus::MarketEvent| {
+                        let runner = runner.clone();
+                        deployments.dispatch(&event);
+                        async move { event_bus::EventHandler::handle(&*runner, event).await }
+                    },
+                ))
+            }
+            None => strategy_runner,
+        };
+
     // Initialize Event Dispatcher
     let dispatcher = event_bus::EventDispatcherBuilder::new(&event_bus)
-        .on_market(strategy_runner)
-        .on_signal(signal_bridge)
+        .on_market(market_handler)
+        .on_signal(signal_combiner)
         .build();
 
     let _dispatcher_controller = dispatcher.controller();
@@ -154,22 +187,16 @@ async fn main() -> Result<()> {
     info!("🌐 Chat orchestration API live at http://{chat_addr}");
 
     // --- Start Main Trading API (8080) ---
-    // This handles market data, trading endpoints, and WebSocket stream
+    // This handles market data, trading endpoints, and WebSocket stream.
     let main_api_handle = tokio::spawn(async move {
-        info!("🚀 Initializing Main API Server...");
-        match ninja_gekko_api::ApiServer::new().await {
-            Ok(server) => {
-                let config = server.config();
-                info!(
-                    "   Main API Config: Bind={} Env={}",
-                    config.bind_address, config.environment
-                );
-                if let Err(e) = server.serve().await {
-                    error!("💥 Main API Server crashed: {}", e);
-                }
-            }
-            Err(e) => {
-                error!("💥 Failed to initialize Main API Server: {}", e);
+        if let Some(server) = main_api {
+            let config = server.config();
+            info!(
+                "   Main API Config: Bind={} Env={}",
+                config.bind_address, config.environment
+            );
+            if let Err(e) = server.serve().await {
+                error!("💥 Main API Server crashed: {}", e);
             }
         }
     });